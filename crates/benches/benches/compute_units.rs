@@ -0,0 +1,319 @@
+//! 每个程序按真实使用顺序执行一遍全部指令，逐条统计 CU。
+//! 同类程序（Anchor / Pinocchio 金库、托管，原生 / Pinocchio AMM）使用相同的参数，表格可以直接对比。
+
+use benches::{programs, ClientPubkey as Pubkey, Ledger};
+use bootcamp_client::{
+    amm::Pool,
+    escrow,
+    token::{ata, create_ata_idempotent_ix, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    vault, AccountMeta, Instruction,
+};
+use solana_sdk_ids::system_program;
+
+/// 所有训练营程序都部署在同一个占位地址上，各自使用独立的 Mollusk 实例
+const PROGRAM_ID: Pubkey = vault::PROGRAM_ID;
+
+const DEPOSIT: u64 = 1_000_000_000;
+const ESCROW_AMOUNT: u64 = 1_000_000;
+const ESCROW_RECEIVE: u64 = 2_000_000;
+
+fn main() {
+    pxsol_ss();
+    anchor_vault();
+    pinocchio_vault();
+    anchor_escrow();
+    pinocchio_escrow();
+    native_amm();
+    pinocchio_amm();
+}
+
+fn pxsol_ss() {
+    let program_id = Pubkey::new_from_array([7; 32]);
+    let mut ledger = Ledger::new(&program_id, programs::PXSOL_SS);
+    let user = ledger.wallet();
+    let (greeting, _) = Pubkey::find_program_address(&[b"greeting", user.as_ref()], &program_id);
+
+    ledger.bench(
+        "hello",
+        &Instruction::new_with_bytes(program_id, &[], vec![]),
+    );
+
+    let name = b"bootcamp";
+    let mut data = vec![0u8];
+    data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    data.extend_from_slice(name);
+    ledger.bench(
+        "create_greeting",
+        &Instruction::new_with_bytes(
+            program_id,
+            &data,
+            vec![
+                AccountMeta::new(user, true),
+                AccountMeta::new(greeting, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+        ),
+    );
+    ledger.execute("pxsol_ss");
+}
+
+fn anchor_vault() {
+    let mut ledger = Ledger::new(&PROGRAM_ID, programs::ANCHOR_VAULT);
+    let signer = ledger.wallet();
+    ledger.bench("deposit", &vault::deposit_ix(&PROGRAM_ID, &signer, DEPOSIT));
+    ledger.bench("withdraw", &vault::withdraw_ix(&PROGRAM_ID, &signer));
+    ledger.execute("anchor_vault");
+}
+
+/// task4 与 Anchor 金库账户相同，判别器为单字节：Deposit = 0、Withdraw = 1
+fn pinocchio_vault() {
+    let mut ledger = Ledger::new(&PROGRAM_ID, programs::PINOCCHIO_VAULT);
+    let owner = ledger.wallet();
+    let (vault, _) = vault::vault_address(&PROGRAM_ID, &owner);
+    let accounts = vec![
+        AccountMeta::new(owner, true),
+        AccountMeta::new(vault, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    let mut deposit = vec![0u8];
+    deposit.extend_from_slice(&DEPOSIT.to_le_bytes());
+    ledger.bench(
+        "deposit",
+        &Instruction::new_with_bytes(PROGRAM_ID, &deposit, accounts.clone()),
+    );
+    ledger.bench(
+        "withdraw",
+        &Instruction::new_with_bytes(PROGRAM_ID, &[1], accounts),
+    );
+    ledger.execute("pinocchio_vault");
+}
+
+/// 托管的参与方：maker 持有代币 A，taker 持有代币 B
+struct Trade {
+    maker: Pubkey,
+    taker: Pubkey,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+}
+
+impl Trade {
+    fn new(ledger: &mut Ledger) -> Self {
+        let maker = ledger.wallet();
+        let taker = ledger.wallet();
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        ledger.mint(&mint_a, &maker);
+        ledger.mint(&mint_b, &taker);
+        ledger.token_account(&ata(&maker, &mint_a), &mint_a, &maker, 10 * ESCROW_AMOUNT);
+        ledger.token_account(&ata(&taker, &mint_b), &mint_b, &taker, 10 * ESCROW_RECEIVE);
+        Self {
+            maker,
+            taker,
+            mint_a,
+            mint_b,
+        }
+    }
+}
+
+/// task3：判别器为单字节，Make 数据为 seed + receive + amount，
+/// 账户列表末尾依次是 ATA 程序、Token 程序、System 程序
+fn anchor_escrow() {
+    let mut ledger = Ledger::new(&PROGRAM_ID, programs::ANCHOR_ESCROW);
+    let trade = Trade::new(&mut ledger);
+    let tail = [
+        AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    let make = |seed: u64| {
+        let (escrow, _) = escrow::escrow_address(&PROGRAM_ID, &trade.maker, seed);
+        let mut data = vec![0u8];
+        data.extend_from_slice(&seed.to_le_bytes());
+        data.extend_from_slice(&ESCROW_RECEIVE.to_le_bytes());
+        data.extend_from_slice(&ESCROW_AMOUNT.to_le_bytes());
+        data.extend_from_slice(&0i64.to_le_bytes());
+        data.extend_from_slice(&[0; 32]);
+        let mut accounts = vec![
+            AccountMeta::new(trade.maker, true),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new_readonly(trade.mint_a, false),
+            AccountMeta::new_readonly(trade.mint_b, false),
+            AccountMeta::new(ata(&trade.maker, &trade.mint_a), false),
+            AccountMeta::new(ata(&escrow, &trade.mint_a), false),
+        ];
+        accounts.extend_from_slice(&tail);
+        (
+            escrow,
+            Instruction::new_with_bytes(PROGRAM_ID, &data, accounts),
+        )
+    };
+
+    let (escrow, ix) = make(1);
+    ledger.bench("make", &ix);
+    let mut accounts = vec![
+        AccountMeta::new(trade.taker, true),
+        AccountMeta::new(trade.maker, false),
+        AccountMeta::new(escrow, false),
+        AccountMeta::new_readonly(trade.mint_a, false),
+        AccountMeta::new_readonly(trade.mint_b, false),
+        AccountMeta::new(ata(&escrow, &trade.mint_a), false),
+        AccountMeta::new(ata(&trade.taker, &trade.mint_a), false),
+        AccountMeta::new(ata(&trade.taker, &trade.mint_b), false),
+        AccountMeta::new(ata(&trade.maker, &trade.mint_b), false),
+    ];
+    accounts.extend_from_slice(&tail);
+    ledger.bench(
+        "take",
+        &Instruction::new_with_bytes(PROGRAM_ID, &[1], accounts),
+    );
+
+    let (escrow, ix) = make(2);
+    ledger.run(&ix);
+    // maker 自己退款：caller 与 maker 是同一个账户
+    let mut accounts = vec![
+        AccountMeta::new(trade.maker, true),
+        AccountMeta::new(trade.maker, false),
+        AccountMeta::new(escrow, false),
+        AccountMeta::new_readonly(trade.mint_a, false),
+        AccountMeta::new(ata(&escrow, &trade.mint_a), false),
+        AccountMeta::new(ata(&trade.maker, &trade.mint_a), false),
+    ];
+    accounts.extend_from_slice(&tail);
+    ledger.bench(
+        "refund",
+        &Instruction::new_with_bytes(PROGRAM_ID, &[2], accounts),
+    );
+    ledger.execute("anchor_escrow");
+}
+
+fn pinocchio_escrow() {
+    let mut ledger = Ledger::new(&PROGRAM_ID, programs::PINOCCHIO_ESCROW);
+    let trade = Trade::new(&mut ledger);
+    let make = |seed: u64| {
+        escrow::make_ix(
+            &PROGRAM_ID,
+            &trade.maker,
+            &trade.mint_a,
+            &trade.mint_b,
+            seed,
+            ESCROW_RECEIVE,
+            ESCROW_AMOUNT,
+            0,
+            None,
+        )
+    };
+    // take / refund 只用到 Escrow 中的 maker 与 mint，直接按 make 的参数构造
+    let state = |seed: u64| escrow::Escrow {
+        seed,
+        maker: trade.maker,
+        mint_a: trade.mint_a,
+        mint_b: trade.mint_b,
+        receive: ESCROW_RECEIVE,
+        bump: escrow::escrow_address(&PROGRAM_ID, &trade.maker, seed).1,
+        expiry: 0,
+        taker: Pubkey::default(),
+    };
+
+    ledger.bench("make", &make(1));
+    let (address, _) = escrow::escrow_address(&PROGRAM_ID, &trade.maker, 1);
+    ledger.bench(
+        "take",
+        &escrow::take_ix(
+            &PROGRAM_ID,
+            &trade.taker,
+            &address,
+            &state(1),
+            &Pubkey::default(),
+        ),
+    );
+
+    ledger.run(&make(2));
+    let (address, _) = escrow::escrow_address(&PROGRAM_ID, &trade.maker, 2);
+    ledger.bench(
+        "refund",
+        &escrow::refund_ix(&PROGRAM_ID, &trade.maker, &address, &state(2)),
+    );
+    ledger.execute("pinocchio_escrow");
+}
+
+/// AMM 的流动性提供者：持有按字节序排列的 mint_x / mint_y
+struct Provider {
+    user: Pubkey,
+    mint_x: Pubkey,
+    mint_y: Pubkey,
+}
+
+impl Provider {
+    fn new(ledger: &mut Ledger) -> Self {
+        let user = ledger.wallet();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mint_x, mint_y) = if a < b { (a, b) } else { (b, a) };
+        for mint in [mint_x, mint_y] {
+            ledger.mint(&mint, &user);
+            ledger.token_account(&ata(&user, &mint), &mint, &user, 1_000 * DEPOSIT);
+        }
+        Self {
+            user,
+            mint_x,
+            mint_y,
+        }
+    }
+
+    /// Initialize 之后的共同流程：存入、双向兑换、取回一半
+    fn trade(&self, ledger: &mut Ledger, pool: &Pool) {
+        let user = &self.user;
+        ledger.run(&create_ata_idempotent_ix(user, user, &pool.mint_lp));
+        ledger.bench("deposit", &pool.deposit_ix(user, DEPOSIT, DEPOSIT, DEPOSIT));
+        ledger.bench("swap_x_to_y", &pool.swap_ix(user, true, DEPOSIT / 100, 1));
+        ledger.bench("swap_y_to_x", &pool.swap_ix(user, false, DEPOSIT / 100, 1));
+        ledger.bench("withdraw", &pool.withdraw_ix(user, DEPOSIT / 2, 1, 1));
+    }
+}
+
+fn native_amm() {
+    let mut ledger = Ledger::new(&PROGRAM_ID, programs::NATIVE_AMM);
+    let provider = Provider::new(&mut ledger);
+    let pool = Pool::canonical(&PROGRAM_ID, &provider.mint_x, &provider.mint_y);
+
+    ledger.bench(
+        "initialize",
+        &pool.initialize_ix(&provider.user, 30, &provider.user),
+    );
+    provider.trade(&mut ledger, &pool);
+    ledger.execute("native_amm");
+}
+
+/// pinocchio_amm 的 Initialize 只接收 [initializer, mint_lp, config] 与 CPI 用到的程序，
+/// 数据为 seed + fee + mint_x + mint_y + config_bump + lp_bump + authority；金库需事先创建
+fn pinocchio_amm() {
+    let mut ledger = Ledger::new(&PROGRAM_ID, programs::PINOCCHIO_AMM);
+    let provider = Provider::new(&mut ledger);
+    let pool = Pool::seeded(&PROGRAM_ID, 1, &provider.mint_x, &provider.mint_y);
+    ledger.token_account(&pool.vault_x, &pool.mint_x, &pool.config, 0);
+    ledger.token_account(&pool.vault_y, &pool.mint_y, &pool.config, 0);
+
+    let mut data = vec![0u8];
+    data.extend_from_slice(&pool.seed.to_le_bytes());
+    data.extend_from_slice(&30u16.to_le_bytes());
+    data.extend_from_slice(pool.mint_x.as_ref());
+    data.extend_from_slice(pool.mint_y.as_ref());
+    data.push(pool.config_bump);
+    data.push(pool.lp_bump);
+    data.extend_from_slice(provider.user.as_ref());
+    ledger.bench(
+        "initialize",
+        &Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &data,
+            vec![
+                AccountMeta::new(provider.user, true),
+                AccountMeta::new(pool.mint_lp, false),
+                AccountMeta::new(pool.config, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ],
+        ),
+    );
+    provider.trade(&mut ledger, &pool);
+    ledger.execute("pinocchio_amm");
+}
@@ -0,0 +1,209 @@
+//! CU 基准的公共部分：加载程序、准备代币账户，以及按顺序执行指令并记录每一步的输入。
+//!
+//! 指令统一用 bootcamp_client 构造（solana-sdk 2.x 类型），Mollusk 使用 3.x 类型，
+//! 两者只在 [`Ledger`] 内部转换。
+
+use bootcamp_client::Instruction as ClientInstruction;
+use mollusk_svm::{program::keyed_account_for_system_program, Mollusk};
+use mollusk_svm_bencher::MolluskComputeUnitBencher;
+use mollusk_svm_programs_token::{associated_token, token};
+use solana_account::Account;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use token_fixtures::{mollusk as fixtures, TokenProgram};
+
+pub use bootcamp_client::Pubkey as ClientPubkey;
+
+pub const SOL: u64 = 1_000_000_000;
+
+/// 各程序 `cargo build-sbf` 产物的路径（不含 .so 扩展名，Mollusk 会自动添加）
+pub mod programs {
+    macro_rules! deploy {
+        ($dir:literal, $name:literal) => {
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../../",
+                $dir,
+                "/target/deploy/",
+                $name
+            )
+        };
+    }
+
+    pub const PXSOL_SS: &str = deploy!("pxsol-ss", "hell_sol");
+    pub const ANCHOR_VAULT: &str =
+        deploy!("task2/blueshift_anchor_vault", "blueshift_anchor_vault");
+    pub const ANCHOR_ESCROW: &str =
+        deploy!("task3/blueshift_anchor_escrow", "blueshift_anchor_escrow");
+    pub const PINOCCHIO_VAULT: &str = deploy!("task4/blueshift_vault", "blueshift_vault");
+    pub const PINOCCHIO_ESCROW: &str = deploy!("task5/blueshift_escrow", "blueshift_escrow");
+    pub const NATIVE_AMM: &str = deploy!(
+        "task6/solana-pinocchio-amm-workshop-main/blueshift_native_amm",
+        "blueshift_native_amm"
+    );
+    pub const PINOCCHIO_AMM: &str = deploy!(
+        "task6/solana-pinocchio-amm-workshop-main/pinocchio_amm",
+        "pinocchio_amm"
+    );
+}
+
+fn key(pubkey: &ClientPubkey) -> Pubkey {
+    Pubkey::new_from_array(pubkey.to_bytes())
+}
+
+fn instruction(ix: &ClientInstruction) -> Instruction {
+    Instruction {
+        program_id: key(&ix.program_id),
+        accounts: ix
+            .accounts
+            .iter()
+            .map(|meta| AccountMeta {
+                pubkey: key(&meta.pubkey),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: ix.data.clone(),
+    }
+}
+
+/// 一条被测指令：名称 + 执行前的账户状态
+struct Case {
+    name: &'static str,
+    ix: Instruction,
+    accounts: Vec<(Pubkey, Account)>,
+}
+
+/// 单个程序的执行环境：成功执行后的账户状态写回 Ledger，供下一条指令使用
+pub struct Ledger {
+    mollusk: Mollusk,
+    accounts: Vec<(Pubkey, Account)>,
+    system_program: Pubkey,
+    cases: Vec<Case>,
+}
+
+impl Ledger {
+    /// 加载程序，并内置 SPL Token 与 ATA 程序
+    pub fn new(program_id: &ClientPubkey, program: &str) -> Self {
+        let mut mollusk = Mollusk::new(&key(program_id), program);
+        token::add_program(&mut mollusk);
+        associated_token::add_program(&mut mollusk);
+        let system = keyed_account_for_system_program();
+        let system_program = system.0;
+        Self {
+            mollusk,
+            accounts: vec![
+                system,
+                token::keyed_account(),
+                associated_token::keyed_account(),
+            ],
+            system_program,
+            cases: Vec::new(),
+        }
+    }
+
+    pub fn rent(&self, len: usize) -> u64 {
+        self.mollusk.sysvars.rent.minimum_balance(len)
+    }
+
+    fn set(&mut self, key: Pubkey, account: Account) {
+        match self.accounts.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => *existing = account,
+            None => self.accounts.push((key, account)),
+        }
+    }
+
+    /// 不存在的地址视为空的系统账户
+    fn get(&self, key: &Pubkey) -> Account {
+        self.accounts
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, account)| account.clone())
+            .unwrap_or_default()
+    }
+
+    /// 新建一个持有 100 SOL 的钱包
+    pub fn wallet(&mut self) -> ClientPubkey {
+        let wallet = ClientPubkey::new_unique();
+        let account = Account::new(100 * SOL, 0, &self.system_program);
+        self.set(key(&wallet), account);
+        wallet
+    }
+
+    /// 直接写入一个已初始化的 mint（6 位小数）
+    pub fn mint(&mut self, mint: &ClientPubkey, authority: &ClientPubkey) {
+        let (address, account) =
+            fixtures::mint(&key(mint), &key(authority), 0, 6, TokenProgram::Token);
+        self.set(address, account);
+    }
+
+    /// 直接写入一个已初始化、余额为 amount 的代币账户
+    pub fn token_account(
+        &mut self,
+        address: &ClientPubkey,
+        mint: &ClientPubkey,
+        owner: &ClientPubkey,
+        amount: u64,
+    ) {
+        let (address, account) = fixtures::token_account(
+            &key(address),
+            &key(mint),
+            &key(owner),
+            amount,
+            TokenProgram::Token,
+        );
+        self.set(address, account);
+    }
+
+    /// 执行并要求成功，结果写回 Ledger
+    pub fn run(&mut self, ix: &ClientInstruction) {
+        let ix = instruction(ix);
+        let accounts = self.inputs(&ix);
+        self.apply(&ix, &accounts);
+    }
+
+    /// 同 [`Ledger::run`]，同时把这一步记为名为 `name` 的基准
+    pub fn bench(&mut self, name: &'static str, ix: &ClientInstruction) {
+        let ix = instruction(ix);
+        let accounts = self.inputs(&ix);
+        self.apply(&ix, &accounts);
+        self.cases.push(Case { name, ix, accounts });
+    }
+
+    fn inputs(&self, ix: &Instruction) -> Vec<(Pubkey, Account)> {
+        let mut accounts: Vec<(Pubkey, Account)> = Vec::new();
+        for meta in &ix.accounts {
+            if !accounts.iter().any(|(k, _)| *k == meta.pubkey) {
+                accounts.push((meta.pubkey, self.get(&meta.pubkey)));
+            }
+        }
+        accounts
+    }
+
+    fn apply(&mut self, ix: &Instruction, accounts: &[(Pubkey, Account)]) {
+        let result = self.mollusk.process_instruction(ix, accounts);
+        assert!(
+            !result.program_result.is_err(),
+            "{} 执行失败: {:?}",
+            ix.program_id,
+            result.program_result
+        );
+        for (key, account) in result.resulting_accounts {
+            self.set(key, account);
+        }
+    }
+
+    /// 重新执行记录下的每一步并统计 CU，结果写入 baseline/<program>/compute_units.md
+    pub fn execute(self, program: &str) {
+        let out_dir = format!("{}/baseline/{program}", env!("CARGO_MANIFEST_DIR"));
+        let mut bencher = MolluskComputeUnitBencher::new(self.mollusk)
+            .must_pass(true)
+            .out_dir(&out_dir);
+        for case in &self.cases {
+            bencher = bencher.bench((case.name, &case.ix, case.accounts.as_slice()));
+        }
+        bencher.execute();
+    }
+}
@@ -0,0 +1,106 @@
+//! pinocchio 0.9 与 0.10 的账户接口差异。校验代码只通过这里的函数访问账户。
+
+#[cfg(feature = "pinocchio-0_10")]
+mod imp {
+    pub use pinocchio::{error::ProgramError, AccountView, Address};
+
+    #[inline(always)]
+    pub const fn address_from_array(bytes: [u8; 32]) -> Address {
+        Address::new_from_array(bytes)
+    }
+
+    #[inline(always)]
+    pub fn address(account: &AccountView) -> &Address {
+        account.address()
+    }
+
+    #[inline(always)]
+    pub fn owned_by(account: &AccountView, program: &Address) -> bool {
+        account.owned_by(program)
+    }
+
+    /// 借用账户数据并交给 `f` 读取
+    #[inline(always)]
+    pub fn read<R>(account: &AccountView, f: impl FnOnce(&[u8]) -> R) -> Result<R, ProgramError> {
+        Ok(f(&account.try_borrow()?))
+    }
+
+    #[inline(always)]
+    pub fn find_program_address(seeds: &[&[u8]], program: &Address) -> Address {
+        Address::find_program_address(seeds, program).0
+    }
+
+    #[inline(always)]
+    pub fn to_bytes(address: &Address) -> [u8; 32] {
+        address.to_bytes()
+    }
+
+    /// 可变借用账户数据并交给 `f` 修改
+    #[inline(always)]
+    pub fn write<R>(
+        account: &AccountView,
+        f: impl FnOnce(&mut [u8]) -> R,
+    ) -> Result<R, ProgramError> {
+        Ok(f(&mut account.try_borrow_mut()?))
+    }
+
+    #[inline(always)]
+    pub fn set_return_data(data: &[u8]) {
+        pinocchio::cpi::set_return_data(data)
+    }
+}
+
+#[cfg(all(feature = "pinocchio-0_9", not(feature = "pinocchio-0_10")))]
+mod imp {
+    pub use pinocchio_0_9::{
+        account_info::AccountInfo as AccountView, program_error::ProgramError,
+        pubkey::Pubkey as Address,
+    };
+
+    #[inline(always)]
+    pub const fn address_from_array(bytes: [u8; 32]) -> Address {
+        bytes
+    }
+
+    #[inline(always)]
+    pub fn address(account: &AccountView) -> &Address {
+        account.key()
+    }
+
+    #[inline(always)]
+    pub fn owned_by(account: &AccountView, program: &Address) -> bool {
+        account.is_owned_by(program)
+    }
+
+    /// 借用账户数据并交给 `f` 读取
+    #[inline(always)]
+    pub fn read<R>(account: &AccountView, f: impl FnOnce(&[u8]) -> R) -> Result<R, ProgramError> {
+        Ok(f(&account.try_borrow_data()?))
+    }
+
+    #[inline(always)]
+    pub fn find_program_address(seeds: &[&[u8]], program: &Address) -> Address {
+        pinocchio_0_9::pubkey::find_program_address(seeds, program).0
+    }
+
+    #[inline(always)]
+    pub fn to_bytes(address: &Address) -> [u8; 32] {
+        *address
+    }
+
+    /// 可变借用账户数据并交给 `f` 修改
+    #[inline(always)]
+    pub fn write<R>(
+        account: &AccountView,
+        f: impl FnOnce(&mut [u8]) -> R,
+    ) -> Result<R, ProgramError> {
+        Ok(f(&mut account.try_borrow_mut_data()?))
+    }
+
+    #[inline(always)]
+    pub fn set_return_data(data: &[u8]) {
+        pinocchio_0_9::cpi::set_return_data(data)
+    }
+}
+
+pub use imp::*;
@@ -0,0 +1,20 @@
+//! 不会 panic 的小端整数读取。
+//!
+//! `u64::from_le_bytes(data[a..b].try_into().unwrap())` 在越界时 panic，链上只留下一句
+//! `** PANICKED **`；这里越界返回 `None`，由调用方换成具体的 `ProgramError`。
+
+/// 从 `offset` 起读取 8 字节小端 u64
+#[inline(always)]
+pub fn read_u64_le(data: &[u8], offset: usize) -> Option<u64> {
+    data.get(offset..)?
+        .first_chunk::<8>()
+        .map(|bytes| u64::from_le_bytes(*bytes))
+}
+
+/// 从 `offset` 起读取 8 字节小端 i64
+#[inline(always)]
+pub fn read_i64_le(data: &[u8], offset: usize) -> Option<i64> {
+    data.get(offset..)?
+        .first_chunk::<8>()
+        .map(|bytes| i64::from_le_bytes(*bytes))
+}
@@ -0,0 +1,170 @@
+use core::marker::PhantomData;
+
+use crate::{
+    account, AccountError, AccountView, Address, ProgramError, ASSOCIATED_TOKEN_PROGRAM_ID,
+    MINT_LEN, SYSTEM_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_ACCOUNT_LEN, TOKEN_PROGRAM_ID,
+};
+
+/// Token-2022 账户在旧版布局之后的第 165 字节存放账户类型
+const TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET: usize = 165;
+
+/// Token-2022 Mint 账户的类型值
+pub const TOKEN_2022_MINT_DISCRIMINATOR: u8 = 0x01;
+
+/// Token-2022 Token Account 的类型值
+pub const TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR: u8 = 0x02;
+
+/// 单个账户的校验，对应 Anchor 中一个账户类型自带的约束
+pub trait AccountCheck {
+    fn check(account: &AccountView) -> Result<(), ProgramError>;
+}
+
+/// ATA 校验：`associated_token::authority / mint / token_program`
+pub trait AssociatedTokenAccountCheck {
+    fn check(
+        account: &AccountView,
+        authority: &AccountView,
+        mint: &AccountView,
+        token_program: &AccountView,
+    ) -> Result<(), ProgramError>;
+}
+
+/// `Signer<'info>`：账户必须签名
+pub struct SignerAccount;
+
+impl AccountCheck for SignerAccount {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        if !account.is_signer() {
+            return Err(AccountError::NotSigner.into());
+        }
+        Ok(())
+    }
+}
+
+/// `SystemAccount<'info>`：账户由 System Program 持有，不要求签名
+pub struct SystemAccount;
+
+impl AccountCheck for SystemAccount {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        if !account::owned_by(account, &SYSTEM_PROGRAM_ID) {
+            return Err(AccountError::InvalidOwner.into());
+        }
+        Ok(())
+    }
+}
+
+/// 旧版 Token 账户必须恰好是 `len` 字节；Token-2022 账户可以带扩展，
+/// 此时必须在第 165 字节标明账户类型
+fn check_token_layout(
+    account: &AccountView,
+    len: usize,
+    discriminator: u8,
+) -> Result<(), ProgramError> {
+    if account::owned_by(account, &TOKEN_PROGRAM_ID) {
+        if account.data_len() != len {
+            return Err(AccountError::InvalidAccountData.into());
+        }
+        return Ok(());
+    }
+    if !account::owned_by(account, &TOKEN_2022_PROGRAM_ID) {
+        return Err(AccountError::InvalidOwner.into());
+    }
+    let valid = account::read(account, |data| {
+        data.len() == len
+            || data.get(TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET) == Some(&discriminator)
+    })?;
+    if !valid {
+        return Err(AccountError::InvalidAccountData.into());
+    }
+    Ok(())
+}
+
+/// `InterfaceAccount<'info, Mint>`：旧版 Token Program 或 Token-2022 的 Mint
+pub struct MintInterface;
+
+impl AccountCheck for MintInterface {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        check_token_layout(account, MINT_LEN, TOKEN_2022_MINT_DISCRIMINATOR)
+    }
+}
+
+/// `InterfaceAccount<'info, TokenAccount>`：旧版 Token Program 或 Token-2022 的代币账户
+pub struct TokenAccountInterface;
+
+impl AccountCheck for TokenAccountInterface {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        check_token_layout(
+            account,
+            TOKEN_ACCOUNT_LEN,
+            TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR,
+        )
+    }
+}
+
+/// 关联代币账户：地址由 `[authority, token_program, mint]` 在 ATA Program 下派生
+pub struct AssociatedTokenAccount;
+
+impl AssociatedTokenAccount {
+    /// 推导 `authority` 持有的 `mint` ATA 地址
+    pub fn find_address(authority: &Address, mint: &Address, token_program: &Address) -> Address {
+        account::find_program_address(
+            &[authority.as_ref(), token_program.as_ref(), mint.as_ref()],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+    }
+
+    /// 按地址校验：调用方手里只有 authority / mint 的地址时使用
+    pub fn check_address(
+        account: &AccountView,
+        authority: &Address,
+        mint: &Address,
+        token_program: &Address,
+    ) -> Result<(), ProgramError> {
+        TokenAccountInterface::check(account)?;
+        if Self::find_address(authority, mint, token_program).ne(account::address(account)) {
+            return Err(AccountError::InvalidAddress.into());
+        }
+        Ok(())
+    }
+}
+
+impl AssociatedTokenAccountCheck for AssociatedTokenAccount {
+    fn check(
+        account: &AccountView,
+        authority: &AccountView,
+        mint: &AccountView,
+        token_program: &AccountView,
+    ) -> Result<(), ProgramError> {
+        Self::check_address(
+            account,
+            account::address(authority),
+            account::address(mint),
+            account::address(token_program),
+        )
+    }
+}
+
+/// 由某个程序持有、长度固定的账户数据类型
+pub trait ProgramOwned {
+    /// 持有该账户的程序
+    const PROGRAM_ID: Address;
+    /// 账户数据长度
+    const LEN: usize;
+}
+
+/// `Account<'info, T>`：账户由 `T::PROGRAM_ID` 持有且长度为 `T::LEN`。
+///
+/// PDA 的 seeds / bump 校验仍由各指令自己完成。
+pub struct ProgramAccount<T>(PhantomData<T>);
+
+impl<T: ProgramOwned> AccountCheck for ProgramAccount<T> {
+    fn check(account: &AccountView) -> Result<(), ProgramError> {
+        if !account::owned_by(account, &T::PROGRAM_ID) {
+            return Err(AccountError::InvalidOwner.into());
+        }
+        if account.data_len() != T::LEN {
+            return Err(AccountError::InvalidAccountData.into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+//! `get_version` / `set_features` 的通用部分与功能开关账户的校验，格式定义在 `blueshift_version`。
+//!
+//! 各程序只需要提供自己的版本号和创建开关账户的 CPI（依赖各自的 pinocchio-system 版本）：
+//!
+//! ```ignore
+//! pub fn set_features(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+//!     feature_gate::set_features(&crate::ID, accounts, data, |payer, gate, bump| {
+//!         // CreateAccount { from: payer, to: gate, space: FeatureGate::LEN, owner: &crate::ID, .. }
+//!         // .invoke_signed(&[["feature_gate", bump]])
+//!     })
+//! }
+//! ```
+//!
+//! 新代码路径用 [`require_feature`] 检查开关；开关账户由调用方以可选账户的形式传入，
+//! 没有传入或账户尚未创建时功能视为关闭。
+
+use blueshift_version::{FeatureGate, Version};
+
+use crate::{
+    account, read_u64_le, AccountCheck, AccountError, AccountView, Address, ProgramError,
+    SignerAccount,
+};
+
+/// 开关账户中打开的功能位。账户尚未创建（没有数据）时返回 0；
+/// 只有地址、所有者、长度与首字节都正确的开关账户才能打开功能
+pub fn enabled_features(gate: &AccountView, program_id: &Address) -> Result<u64, ProgramError> {
+    if gate.data_len() == 0 {
+        return Ok(0);
+    }
+    if !account::owned_by(gate, program_id) {
+        return Err(AccountError::InvalidOwner.into());
+    }
+    let (flags, bump) = account::read(gate, |data| {
+        FeatureGate::load(data).map(|gate| (gate.flags(), gate.bump()))
+    })?
+    .ok_or(AccountError::InvalidAccountData)?;
+    let expected = pdas::feature_gate_pda_with_bump(&account::to_bytes(program_id), bump);
+    if expected != Some(account::to_bytes(account::address(gate))) {
+        return Err(AccountError::InvalidAddress.into());
+    }
+    Ok(flags)
+}
+
+/// `feature` 的每一位都已打开时返回 `Ok(())`，否则返回 [`AccountError::FeatureDisabled`]
+pub fn require_feature(
+    gate: Option<&AccountView>,
+    program_id: &Address,
+    feature: u64,
+) -> Result<(), ProgramError> {
+    let enabled = match gate {
+        Some(gate) => enabled_features(gate, program_id)?,
+        None => 0,
+    };
+    if enabled & feature != feature {
+        return Err(AccountError::FeatureDisabled.into());
+    }
+    Ok(())
+}
+
+/// `get_version`：账户为可选的开关账户，结果写入 return data
+pub fn get_version(
+    version: Version,
+    program_id: &Address,
+    accounts: &[AccountView],
+) -> Result<(), ProgramError> {
+    let enabled = match accounts.first() {
+        Some(gate) => enabled_features(gate, program_id)?,
+        None => 0,
+    };
+    account::set_return_data(&version.with_enabled(enabled).encode());
+    Ok(())
+}
+
+/// 校验 `authority` 是 `program_id` 当前的升级权限。
+/// `program_data` 必须是该程序在 Upgradeable Loader 下的 ProgramData 账户
+pub fn check_upgrade_authority(
+    program_id: &Address,
+    program_data: &AccountView,
+    authority: &AccountView,
+) -> Result<(), ProgramError> {
+    SignerAccount::check(authority)?;
+    let loader = account::address_from_array(pdas::BPF_LOADER_UPGRADEABLE_ID);
+    if !account::owned_by(program_data, &loader) {
+        return Err(AccountError::InvalidOwner.into());
+    }
+    let (expected, _) = pdas::program_data_pda(&account::to_bytes(program_id));
+    if account::to_bytes(account::address(program_data)) != expected {
+        return Err(AccountError::InvalidAddress.into());
+    }
+    let upgrade_authority = account::read(program_data, blueshift_version::upgrade_authority)?;
+    if upgrade_authority != Some(account::to_bytes(account::address(authority))) {
+        return Err(AccountError::NotUpgradeAuthority.into());
+    }
+    Ok(())
+}
+
+/// `set_features`：由升级权限覆盖写入开关账户中的功能位，账户不存在时先调用 `create` 创建。
+///
+/// 账户：`[authority (签名，付租金), program_data, feature_gate (可写), system_program]`；
+/// 数据：u64 (LE) 功能位。`create(payer, gate, bump)` 需要以 `["feature_gate", bump]`
+/// 为种子签名，创建 [`FeatureGate::LEN`] 字节、由本程序持有的账户。
+pub fn set_features(
+    program_id: &Address,
+    accounts: &[AccountView],
+    data: &[u8],
+    create: impl FnOnce(&AccountView, &AccountView, u8) -> Result<(), ProgramError>,
+) -> Result<(), ProgramError> {
+    let [authority, program_data, gate, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let flags = read_u64_le(data, 0).ok_or(ProgramError::InvalidInstructionData)?;
+    check_upgrade_authority(program_id, program_data, authority)?;
+
+    let (expected, bump) = pdas::feature_gate_pda(&account::to_bytes(program_id));
+    if account::to_bytes(account::address(gate)) != expected {
+        return Err(AccountError::InvalidAddress.into());
+    }
+    if gate.data_len() == 0 {
+        create(authority, gate, bump)?;
+    } else if !account::owned_by(gate, program_id) {
+        return Err(AccountError::InvalidOwner.into());
+    }
+    account::write(gate, |data| {
+        FeatureGate::init_or_load_mut(data, bump).map(|gate| gate.set_flags(flags))
+    })?
+    .ok_or(AccountError::InvalidAccountData.into())
+}
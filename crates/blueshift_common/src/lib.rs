@@ -0,0 +1,76 @@
+//! 各原生（Pinocchio）程序共用的账户校验。
+//!
+//! 每个校验对应一条 Anchor 账户约束：
+//!
+//! | 本 crate                              | Anchor                                   |
+//! |---------------------------------------|------------------------------------------|
+//! | [`SignerAccount`]                     | `Signer<'info>`                          |
+//! | [`SystemAccount`]                     | `SystemAccount<'info>`                   |
+//! | [`MintInterface`]                     | `InterfaceAccount<'info, Mint>`          |
+//! | [`TokenAccountInterface`]             | `InterfaceAccount<'info, TokenAccount>`  |
+//! | [`AssociatedTokenAccount`]            | `associated_token::{authority, mint, token_program}` |
+//! | [`ProgramAccount<T>`]                 | `Account<'info, T>`                      |
+//!
+//! 托管程序使用 pinocchio 0.10（`AccountView` / `Address`），原生 AMM 仍在 0.9
+//! （`AccountInfo` / `Pubkey`），通过 feature 二选一：`pinocchio-0_10`（默认）或 `pinocchio-0_9`。
+//! 两个版本的差异集中在 `account` 模块，校验逻辑只写一份。
+//!
+//! 创建 / 关闭账户需要 CPI，依赖各程序自己的 pinocchio-system / pinocchio-token 版本，
+//! 因此不在这里，而是由各程序为这里的类型实现自己的 init / close trait。
+//!
+//! [`feature_gate`] 是各程序 `get_version` / `set_features` 指令的公共实现与功能开关的检查。
+
+#![no_std]
+
+#[cfg(all(feature = "pinocchio-0_10", feature = "pinocchio-0_9"))]
+compile_error!("feature `pinocchio-0_10` 与 `pinocchio-0_9` 只能启用一个");
+
+#[cfg(not(any(feature = "pinocchio-0_10", feature = "pinocchio-0_9")))]
+compile_error!("需要启用 feature `pinocchio-0_10` 或 `pinocchio-0_9`");
+
+mod account;
+pub use account::{AccountView, Address, ProgramError};
+
+pub mod checks;
+pub use checks::*;
+
+pub mod bytes;
+pub use bytes::{read_i64_le, read_u64_le};
+
+pub mod feature_gate;
+
+/// 账户校验错误，编号在 `blueshift_errors` 统一分配（6300 起）
+pub use blueshift_errors::AccountError;
+
+/// SPL Token Program：TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA
+pub const TOKEN_PROGRAM_ID: Address = account::address_from_array([
+    0x06, 0xdd, 0xf6, 0xe1, 0xd7, 0x65, 0xa1, 0x93, 0xd9, 0xcb, 0xe1, 0x46, 0xce, 0xeb, 0x79, 0xac,
+    0x1c, 0xb4, 0x85, 0xed, 0x5f, 0x5b, 0x37, 0x91, 0x3a, 0x8c, 0xf5, 0x85, 0x7e, 0xff, 0x00, 0xa9,
+]);
+
+/// Token-2022 Program：TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb
+pub const TOKEN_2022_PROGRAM_ID: Address = account::address_from_array([
+    0x06, 0xdd, 0xf6, 0xe1, 0xee, 0x75, 0x8f, 0xde, 0x18, 0x42, 0x5d, 0xbc, 0xe4, 0x6c, 0xcd, 0xda,
+    0xb6, 0x1a, 0xfc, 0x4d, 0x83, 0xb9, 0x0d, 0x27, 0xfe, 0xbd, 0xf9, 0x28, 0xd8, 0xa1, 0x8b, 0xfc,
+]);
+
+/// Associated Token Account Program：ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Address = account::address_from_array([
+    0x8c, 0x97, 0x25, 0x8f, 0x4e, 0x24, 0x89, 0xf1, 0xbb, 0x3d, 0x10, 0x29, 0x14, 0x8e, 0x0d, 0x83,
+    0x0b, 0x5a, 0x13, 0x99, 0xda, 0xff, 0x10, 0x84, 0x04, 0x8e, 0x7b, 0xd8, 0xdb, 0xe9, 0xf8, 0x59,
+]);
+
+/// wSOL mint：So11111111111111111111111111111111111111112（旧版 Token Program 的原生 SOL 代币）
+pub const NATIVE_MINT: Address = account::address_from_array([
+    0x06, 0x9b, 0x88, 0x57, 0xfe, 0xab, 0x81, 0x84, 0xfb, 0x68, 0x7f, 0x63, 0x46, 0x18, 0xc0, 0x35,
+    0xda, 0xc4, 0x39, 0xdc, 0x1a, 0xeb, 0x3b, 0x55, 0x98, 0xa0, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x01,
+]);
+
+/// System Program：11111111111111111111111111111111
+pub const SYSTEM_PROGRAM_ID: Address = account::address_from_array([0; 32]);
+
+/// 旧版 Token Program 的 Mint 账户长度
+pub const MINT_LEN: usize = 82;
+
+/// 旧版 Token Program 的 Token Account 长度
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
@@ -0,0 +1,19 @@
+use crate::{program_errors, ACCOUNT_ERROR_OFFSET};
+
+program_errors! {
+    /// `blueshift_common` 账户校验失败时返回的错误
+    pub enum AccountError: ACCOUNT_ERROR_OFFSET {
+        /// 账户未签名
+        NotSigner = "没有签名",
+        /// 账户的 owner 不是预期的程序
+        InvalidOwner = "非法的所有者",
+        /// 账户数据长度或判别器不符合预期
+        InvalidAccountData = "非法的账户数据",
+        /// 账户地址与推导出的地址不一致
+        InvalidAddress = "非法的地址",
+        /// 签名者不是程序的升级权限，或程序已不可升级
+        NotUpgradeAuthority = "不是程序的升级权限",
+        /// 功能开关账户中没有打开该功能
+        FeatureDisabled = "功能未启用",
+    }
+}
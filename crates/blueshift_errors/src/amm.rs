@@ -0,0 +1,9 @@
+use crate::{program_errors, AMM_ERROR_OFFSET};
+
+program_errors! {
+    /// 原生 AMM 的错误
+    pub enum AmmError: AMM_ERROR_OFFSET {
+        /// 计算出的数量超出用户设定的滑点边界（min / max）
+        SlippageExceeded = "超出滑点限制",
+    }
+}
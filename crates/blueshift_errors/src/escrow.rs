@@ -0,0 +1,32 @@
+use crate::{program_errors, ESCROW_ERROR_OFFSET};
+
+program_errors! {
+    /// 托管程序的错误，顺序与 Anchor 托管 `#[error_code(offset = 6100)] EscrowError` 一致。
+    /// NotRentExempt 只有 Pinocchio 版会返回（Anchor 版对应的是框架的 ConstraintRentExempt），
+    /// Anchor 版保留同名占位，之后的编号两边相同
+    pub enum EscrowError: ESCROW_ERROR_OFFSET {
+        /// 数量必须大于 0
+        InvalidAmount = "Invalid amount",
+        /// maker 账户与 escrow 数据不一致
+        InvalidMaker = "Invalid maker",
+        /// mint A 与 escrow 数据不一致
+        InvalidMintA = "Invalid mint a",
+        /// mint B 与 escrow 数据不一致
+        InvalidMintB = "Invalid mint b",
+        /// 账户余额低于租金豁免阈值
+        NotRentExempt = "Lamport balance below rent-exempt threshold",
+        /// 托管单已过期：Make 的过期时间不在未来，或 Take 时已到过期时间
+        EscrowExpired = "Escrow has expired",
+        /// 托管单未过期，只有 maker 可以 Refund
+        EscrowNotExpired = "Escrow has not expired",
+        /// 托管单指定了接受者，签名的 taker 不是它
+        InvalidTaker = "Invalid taker",
+        /// InitConfig 的协议手续费率超过 Config::MAX_FEE_BPS（只有 Pinocchio 版会返回）
+        FeeTooHigh = "Fee too high",
+        /// Take / TakePartial 应付的代币 B 超过接受者给出的 max_receive，
+        /// 通常是创建者在吃单之前改了价（只有 Pinocchio 版会返回）
+        ReceiveAboveMax = "Receive exceeds the taker's maximum",
+        /// 创建者未成交的托管单已占满 OfferIndex 的容量，需要先成交或退款（只有 Pinocchio 版会返回）
+        TooManyOffers = "Too many open offers",
+    }
+}
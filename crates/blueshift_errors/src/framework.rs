@@ -0,0 +1,132 @@
+//! Anchor 框架错误码，以及把 Pinocchio 程序的错误换算成 Anchor 会返回的编号。
+//!
+//! Anchor 托管的账户校验写在 `#[derive(Accounts)]` 约束里，失败时返回的是框架错误
+//! （缺签名 3010、owner 不对 3007、seeds 不对 2006……），而不是 `EscrowError`。
+//! Pinocchio 版做的是同样的检查，返回的却是 `ProgramError` 内置错误或 [`AccountError`]。
+//! 入口处调用 [`AnchorCompat::anchor_compat`] 换算之后，两个实现返回的错误码完全一致，
+//! 客户端只需要一张 [`BootcampError`](crate::BootcampError) 表。
+//!
+//! 编号与 anchor-lang 0.32 的 `anchor_lang::error::ErrorCode` 相同。
+
+use crate::{AccountError, EscrowError};
+
+/// Pinocchio 程序会用到的 Anchor 框架错误
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum FrameworkError {
+    /// 指令数据解析失败（Anchor 的判别器匹配上但参数反序列化失败）
+    InstructionDidNotDeserialize = 102,
+    /// `rent_exempt` / `init` 约束：余额不足以免租
+    ConstraintRentExempt = 2005,
+    /// `seeds` 约束：PDA 地址与推导结果不一致
+    ConstraintSeeds = 2006,
+    /// `address` / `associated_token` 约束：地址与预期不一致
+    ConstraintAddress = 2012,
+    /// 账户数据无法按声明的类型反序列化
+    AccountDidNotDeserialize = 3003,
+    /// 账户数量少于指令声明的数量
+    AccountNotEnoughKeys = 3005,
+    /// 账户的 owner 不是预期的程序
+    AccountOwnedByWrongProgram = 3007,
+    /// `Signer<'info>` 账户没有签名
+    AccountNotSigner = 3010,
+}
+
+impl FrameworkError {
+    /// 全部错误，按编号递增
+    pub const ALL: &'static [Self] = &[
+        Self::InstructionDidNotDeserialize,
+        Self::ConstraintRentExempt,
+        Self::ConstraintSeeds,
+        Self::ConstraintAddress,
+        Self::AccountDidNotDeserialize,
+        Self::AccountNotEnoughKeys,
+        Self::AccountOwnedByWrongProgram,
+        Self::AccountNotSigner,
+    ];
+
+    /// 按编号还原错误
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::ALL.iter().copied().find(|error| error.code() == code)
+    }
+
+    /// `ProgramError::Custom` 中的编号
+    pub const fn code(self) -> u32 {
+        self as u32
+    }
+
+    /// 训练营自定义错误中，在 Anchor 版里由框架约束报出的那部分
+    ///
+    /// 功能开关相关的 `NotUpgradeAuthority` / `FeatureDisabled` 两个实现都直接返回，不换算。
+    pub fn from_custom(code: u32) -> Option<Self> {
+        if let Some(error) = AccountError::from_code(code) {
+            return match error {
+                AccountError::NotSigner => Some(Self::AccountNotSigner),
+                AccountError::InvalidOwner => Some(Self::AccountOwnedByWrongProgram),
+                AccountError::InvalidAccountData => Some(Self::AccountDidNotDeserialize),
+                AccountError::InvalidAddress => Some(Self::ConstraintAddress),
+                AccountError::NotUpgradeAuthority | AccountError::FeatureDisabled => None,
+            };
+        }
+        (code == EscrowError::NotRentExempt.code()).then_some(Self::ConstraintRentExempt)
+    }
+}
+
+impl core::fmt::Display for FrameworkError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // 与 Anchor 的 #[msg] 文本一致，日志里两个实现打印的说明也相同
+        f.write_str(match self {
+            Self::InstructionDidNotDeserialize => {
+                "The program could not deserialize the given instruction"
+            }
+            Self::ConstraintRentExempt => "A rent exemption constraint was violated",
+            Self::ConstraintSeeds => "A seeds constraint was violated",
+            Self::ConstraintAddress => "An address constraint was violated",
+            Self::AccountDidNotDeserialize => "Failed to deserialize the account",
+            Self::AccountNotEnoughKeys => "Not enough account keys given to the instruction",
+            Self::AccountOwnedByWrongProgram => {
+                "The given account is owned by a different program than expected"
+            }
+            Self::AccountNotSigner => "The given account did not sign",
+        })
+    }
+}
+
+/// 把错误换算成 Anchor 版同一检查失败时的错误码；没有对应关系的错误原样返回
+///
+/// 用在 Pinocchio 程序的入口：`dispatch(...).map_err(AnchorCompat::anchor_compat)`。
+pub trait AnchorCompat {
+    fn anchor_compat(self) -> Self;
+}
+
+#[cfg(any(feature = "pinocchio-0_10", feature = "pinocchio-0_9"))]
+macro_rules! anchor_compat {
+    ($program_error:ty) => {
+        impl AnchorCompat for $program_error {
+            fn anchor_compat(self) -> Self {
+                let error = match &self {
+                    Self::InvalidInstructionData => {
+                        Some(FrameworkError::InstructionDidNotDeserialize)
+                    }
+                    Self::NotEnoughAccountKeys => Some(FrameworkError::AccountNotEnoughKeys),
+                    Self::MissingRequiredSignature => Some(FrameworkError::AccountNotSigner),
+                    Self::InvalidAccountOwner | Self::IllegalOwner => {
+                        Some(FrameworkError::AccountOwnedByWrongProgram)
+                    }
+                    Self::InvalidAccountData => Some(FrameworkError::AccountDidNotDeserialize),
+                    Self::InvalidSeeds => Some(FrameworkError::ConstraintSeeds),
+                    Self::AccountNotRentExempt => Some(FrameworkError::ConstraintRentExempt),
+                    Self::Custom(code) => FrameworkError::from_custom(*code),
+                    _ => None,
+                };
+                error.map_or(self, |error| Self::Custom(error.code()))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "pinocchio-0_10")]
+anchor_compat!(pinocchio::error::ProgramError);
+
+#[cfg(feature = "pinocchio-0_9")]
+anchor_compat!(pinocchio_0_9::program_error::ProgramError);
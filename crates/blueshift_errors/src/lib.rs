@@ -0,0 +1,264 @@
+//! 训练营各程序的自定义错误，统一编号。
+//!
+//! 每个程序占用一段互不重叠的 `ProgramError::Custom` 编号，客户端拿到任意一个错误码都可以用
+//! [`BootcampError::from_code`] 还原成具体的错误，无需先判断是哪个程序返回的：
+//!
+//! | 范围        | 程序                                       |
+//! |-------------|--------------------------------------------|
+//! | 100..6000   | Anchor 框架约束（Pinocchio 版经 [`AnchorCompat`] 换算后也返回这些编号） |
+//! | 6000..6100  | 金库（Anchor 默认 offset 6000）            |
+//! | 6100..6200  | 托管（Anchor 版 `#[error_code(offset = 6100)]` 与 Pinocchio 版） |
+//! | 6200..6300  | 原生 AMM                                   |
+//! | 6300..6400  | `blueshift_common` 的账户校验              |
+//!
+//! 到 `ProgramError` 的转换按 feature 提供：`pinocchio-0_10`、`pinocchio-0_9`、`anchor`。
+//!
+//! 原生 AMM 没有 Anchor 版，`AmmError` 的编号就是唯一的定义；它与 Pinocchio 托管一样在入口做
+//! [`AnchorCompat`] 换算，账户校验失败时返回的编号与 Anchor 程序一致。
+
+#![no_std]
+
+use core::fmt;
+
+mod account;
+mod amm;
+mod escrow;
+mod framework;
+mod vault;
+
+pub use account::AccountError;
+pub use amm::AmmError;
+pub use escrow::EscrowError;
+pub use framework::{AnchorCompat, FrameworkError};
+pub use vault::VaultError;
+
+/// 金库错误的起始编号
+pub const VAULT_ERROR_OFFSET: u32 = 6000;
+/// 托管错误的起始编号
+pub const ESCROW_ERROR_OFFSET: u32 = 6100;
+/// AMM 错误的起始编号
+pub const AMM_ERROR_OFFSET: u32 = 6200;
+/// 账户校验错误的起始编号
+pub const ACCOUNT_ERROR_OFFSET: u32 = 6300;
+/// 每个程序可用的编号数量
+pub const ERROR_RANGE_LEN: u32 = 100;
+
+/// 任意一个训练营程序返回的自定义错误
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BootcampError {
+    Anchor(FrameworkError),
+    Vault(VaultError),
+    Escrow(EscrowError),
+    Amm(AmmError),
+    Account(AccountError),
+}
+
+impl BootcampError {
+    /// 按 `ProgramError::Custom` 的编号还原错误；不属于任何程序的编号返回 `None`
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            ..VAULT_ERROR_OFFSET => FrameworkError::from_code(code).map(Self::Anchor),
+            VAULT_ERROR_OFFSET..ESCROW_ERROR_OFFSET => VaultError::from_code(code).map(Self::Vault),
+            ESCROW_ERROR_OFFSET..AMM_ERROR_OFFSET => EscrowError::from_code(code).map(Self::Escrow),
+            AMM_ERROR_OFFSET..ACCOUNT_ERROR_OFFSET => AmmError::from_code(code).map(Self::Amm),
+            ACCOUNT_ERROR_OFFSET.. => AccountError::from_code(code).map(Self::Account),
+        }
+    }
+
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::Anchor(error) => error.code(),
+            Self::Vault(error) => error.code(),
+            Self::Escrow(error) => error.code(),
+            Self::Amm(error) => error.code(),
+            Self::Account(error) => error.code(),
+        }
+    }
+
+    /// 返回该错误的程序名，方便日志按程序归类
+    pub fn program(&self) -> &'static str {
+        match self {
+            Self::Anchor(_) => "anchor",
+            Self::Vault(_) => "vault",
+            Self::Escrow(_) => "escrow",
+            Self::Amm(_) => "amm",
+            Self::Account(_) => "account",
+        }
+    }
+}
+
+impl fmt::Display for BootcampError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Anchor(error) => write!(f, "[anchor {}] {error}", self.code()),
+            Self::Vault(error) => write!(f, "[vault {}] {error}", self.code()),
+            Self::Escrow(error) => write!(f, "[escrow {}] {error}", self.code()),
+            Self::Amm(error) => write!(f, "[amm {}] {error}", self.code()),
+            Self::Account(error) => write!(f, "[account {}] {error}", self.code()),
+        }
+    }
+}
+
+/// 为错误枚举实现 `from_code`、`Display` 以及各 feature 下到 `ProgramError` 的转换
+macro_rules! program_errors {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident : $offset:ident {
+            $($(#[doc = $doc:literal])* $variant:ident = $msg:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        #[repr(u32)]
+        pub enum $name {
+            $($(#[doc = $doc])* $variant,)+
+        }
+
+        impl $name {
+            /// 全部错误，按编号递增
+            pub const ALL: &'static [Self] = &[$(Self::$variant,)+];
+
+            /// 按编号还原错误
+            pub fn from_code(code: u32) -> Option<Self> {
+                Self::ALL.iter().copied().find(|error| error.code() == code)
+            }
+
+            /// `ProgramError::Custom` 中的编号
+            pub const fn code(self) -> u32 {
+                $offset + self as u32
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match self {
+                    $(Self::$variant => f.write_str($msg),)+
+                }
+            }
+        }
+
+        #[cfg(feature = "pinocchio-0_10")]
+        impl From<$name> for pinocchio::error::ProgramError {
+            fn from(error: $name) -> Self {
+                Self::Custom(error.code())
+            }
+        }
+
+        #[cfg(feature = "pinocchio-0_9")]
+        impl From<$name> for pinocchio_0_9::program_error::ProgramError {
+            fn from(error: $name) -> Self {
+                Self::Custom(error.code())
+            }
+        }
+
+        #[cfg(feature = "anchor")]
+        impl From<$name> for anchor_lang::error::Error {
+            fn from(error: $name) -> Self {
+                anchor_lang::prelude::ProgramError::Custom(error.code()).into()
+            }
+        }
+    };
+}
+pub(crate) use program_errors;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip_within_ranges() {
+        let all = VaultError::ALL
+            .iter()
+            .map(|e| BootcampError::Vault(*e))
+            .chain(EscrowError::ALL.iter().map(|e| BootcampError::Escrow(*e)))
+            .chain(AmmError::ALL.iter().map(|e| BootcampError::Amm(*e)))
+            .chain(AccountError::ALL.iter().map(|e| BootcampError::Account(*e)));
+        for error in all {
+            let offset = match error {
+                BootcampError::Vault(_) => VAULT_ERROR_OFFSET,
+                BootcampError::Escrow(_) => ESCROW_ERROR_OFFSET,
+                BootcampError::Amm(_) => AMM_ERROR_OFFSET,
+                BootcampError::Account(_) => ACCOUNT_ERROR_OFFSET,
+                BootcampError::Anchor(_) => unreachable!("上面只串联了训练营自己的错误"),
+            };
+            assert!((offset..offset + ERROR_RANGE_LEN).contains(&error.code()));
+            assert_eq!(BootcampError::from_code(error.code()), Some(error));
+        }
+    }
+
+    #[test]
+    fn matches_anchor_error_codes() {
+        // Anchor 金库使用默认 offset，Anchor 托管使用 offset = 6100
+        assert_eq!(VaultError::VaultAlreadyExists.code(), 6000);
+        assert_eq!(VaultError::InvalidAmount.code(), 6001);
+        assert_eq!(VaultError::BelowRentMinimum.code(), 6002);
+        assert_eq!(VaultError::LockShortened.code(), 6004);
+        assert_eq!(VaultError::NotDelegate.code(), 6005);
+        assert_eq!(VaultError::WrongRecipient.code(), 6006);
+        assert_eq!(VaultError::AboveMaxBalance.code(), 6010);
+        assert_eq!(VaultError::InvalidCoSigner.code(), 6012);
+        assert_eq!(VaultError::MetadataTooLong.code(), 6014);
+        assert_eq!(VaultError::WithdrawTooSoon.code(), 6015);
+        assert_eq!(VaultError::FeeTooHigh.code(), 6016);
+        assert_eq!(VaultError::InvalidSubVault.code(), 6017);
+        assert_eq!(EscrowError::InvalidAmount.code(), 6100);
+        assert_eq!(EscrowError::InvalidMintB.code(), 6103);
+        assert_eq!(EscrowError::EscrowNotExpired.code(), 6106);
+        assert_eq!(EscrowError::InvalidTaker.code(), 6107);
+        assert_eq!(EscrowError::FeeTooHigh.code(), 6108);
+        assert_eq!(EscrowError::ReceiveAboveMax.code(), 6109);
+        assert_eq!(EscrowError::TooManyOffers.code(), 6110);
+    }
+
+    #[test]
+    fn framework_codes_match_anchor() {
+        // anchor_lang::error::ErrorCode 中的编号
+        assert_eq!(FrameworkError::InstructionDidNotDeserialize.code(), 102);
+        assert_eq!(FrameworkError::ConstraintSeeds.code(), 2006);
+        assert_eq!(FrameworkError::AccountNotEnoughKeys.code(), 3005);
+        assert_eq!(FrameworkError::AccountNotSigner.code(), 3010);
+        for error in FrameworkError::ALL {
+            assert!(error.code() < VAULT_ERROR_OFFSET);
+            assert_eq!(
+                BootcampError::from_code(error.code()),
+                Some(BootcampError::Anchor(*error))
+            );
+        }
+    }
+
+    #[test]
+    fn account_errors_translate_to_anchor_constraints() {
+        assert_eq!(
+            FrameworkError::from_custom(AccountError::NotSigner.code()),
+            Some(FrameworkError::AccountNotSigner)
+        );
+        assert_eq!(
+            FrameworkError::from_custom(AccountError::InvalidOwner.code()),
+            Some(FrameworkError::AccountOwnedByWrongProgram)
+        );
+        assert_eq!(
+            FrameworkError::from_custom(EscrowError::NotRentExempt.code()),
+            Some(FrameworkError::ConstraintRentExempt)
+        );
+        // Anchor 托管自己的 #[error_code] 与功能开关错误保持原样
+        assert_eq!(
+            FrameworkError::from_custom(EscrowError::InvalidMaker.code()),
+            None
+        );
+        assert_eq!(
+            FrameworkError::from_custom(AccountError::FeatureDisabled.code()),
+            None
+        );
+        assert_eq!(
+            FrameworkError::from_custom(AmmError::SlippageExceeded.code()),
+            None
+        );
+    }
+
+    #[test]
+    fn unknown_codes() {
+        assert_eq!(BootcampError::from_code(0), None);
+        assert_eq!(BootcampError::from_code(6099), None);
+        assert_eq!(BootcampError::from_code(6400), None);
+    }
+}
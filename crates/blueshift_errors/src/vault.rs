@@ -0,0 +1,43 @@
+use crate::{program_errors, VAULT_ERROR_OFFSET};
+
+program_errors! {
+    /// 金库程序的错误，与 Anchor 金库 `#[error_code] VaultError` 的顺序一致
+    pub enum VaultError: VAULT_ERROR_OFFSET {
+        /// 向已有余额的金库重复存款（追加存款走 top_up）
+        VaultAlreadyExists = "金库已存在，不能重复存款",
+        /// 存款金额不足租金豁免，从空金库取款、向空金库追加，或金额为 0
+        InvalidAmount = "无效的金额",
+        /// 部分取款后金库余额会低于免租金限额
+        BelowRentMinimum = "取款后金库余额低于免租金限额，全部取出请使用 withdraw",
+        /// 解锁时间未到
+        VaultLocked = "金库仍在锁定期内",
+        /// extend_lock 试图把解锁时间提前
+        LockShortened = "解锁时间只能延后，不能提前",
+        /// withdraw_as_delegate 的签名者不是记录的代理人
+        NotDelegate = "签名者不是该金库的代理人",
+        /// claim_stream 的 recipient 与流中记录的不同
+        WrongRecipient = "收款人与流式支付记录的不一致",
+        /// initialize_config 不是升级权限签名，或 update_config 不是管理员签名
+        Unauthorized = "签名者没有修改全局配置的权限",
+        /// min_deposit 高于 max_vault_balance
+        InvalidConfig = "最小存款不能高于金库余额上限",
+        /// 首笔存款低于 min_deposit
+        BelowMinDeposit = "存款金额低于全局配置的最小存款",
+        /// 存款或追加后余额超过 max_vault_balance
+        AboveMaxBalance = "存款后金库余额超过全局配置的上限",
+        /// 设置了共同签名策略，但共同签名者没有签名
+        CoSignerRequired = "该金库需要共同签名者一同签名",
+        /// 共同签名者与所有者相同
+        InvalidCoSigner = "共同签名者不能是金库所有者",
+        /// 管理员暂停了存款
+        VaultPaused = "存款已被管理员暂停",
+        /// 元数据的标签超过 32 字节或备注超过 256 字节
+        MetadataTooLong = "金库标签或备注超出长度上限",
+        /// 距上次取款不足冷却间隔（还需等待的秒数见程序日志），或在冷却期内缩短冷却
+        WithdrawTooSoon = "取款过于频繁，冷却时间未到",
+        /// set_fee 的费率超过 1000 bps
+        FeeTooHigh = "存款手续费率超出上限",
+        /// sweep 的某个账户不是签名者对应序号的子金库
+        InvalidSubVault = "账户不是签名者对应序号的子金库",
+    }
+}
@@ -0,0 +1,51 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{impl_event, Address};
+
+/// 创建池子；规范池的 `seed` 为 0
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct PoolInitialized {
+    pub config: Address,
+    pub authority: Address,
+    pub mint_x: Address,
+    pub mint_y: Address,
+    pub seed: u64,
+    /// 基点
+    pub fee: u16,
+}
+impl_event!(PoolInitialized, [100, 118, 173, 87, 12, 198, 254, 229]);
+
+/// 添加流动性：存入 `amount_x` / `amount_y`，铸造 `lp_amount` 个 LP（锁仓存款同样记录）
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct LiquidityDeposited {
+    pub config: Address,
+    pub user: Address,
+    pub lp_amount: u64,
+    pub amount_x: u64,
+    pub amount_y: u64,
+}
+impl_event!(LiquidityDeposited, [218, 155, 74, 193, 59, 66, 94, 122]);
+
+/// 移除流动性：销毁 `lp_amount` 个 LP，取回 `amount_x` / `amount_y`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct LiquidityWithdrawn {
+    pub config: Address,
+    pub user: Address,
+    pub lp_amount: u64,
+    pub amount_x: u64,
+    pub amount_y: u64,
+}
+impl_event!(LiquidityWithdrawn, [240, 120, 73, 139, 154, 31, 218, 68]);
+
+/// 兑换：`is_x` 为 true 时用 X 换 Y；`fee` 为本次实际适用的费率（含 LP 返还）
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct Swapped {
+    pub config: Address,
+    pub user: Address,
+    pub is_x: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// 基点
+    pub fee: u16,
+}
+impl_event!(Swapped, [217, 52, 52, 83, 147, 135, 96, 109]);
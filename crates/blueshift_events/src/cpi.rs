@@ -0,0 +1,46 @@
+//! Anchor 程序通过自调用（事件 CPI）发出事件（`cpi` feature）。
+//!
+//! 与 Anchor `emit_cpi!` 的约定相同：账户结构体加上 `#[event_cpi]`（追加 `event_authority`
+//! 与 `program` 两个账户），`anchor-lang` 打开 `event-cpi` feature 由 `#[program]` 接收自调用。
+//! 区别只在事件本身：这里发出的是本 crate 的事件，与 Pinocchio 程序写进日志的字节完全相同，
+//! 解析方用 [`ProgramEvent::decode_cpi`](crate::ProgramEvent::decode_cpi) 处理内部指令即可。
+//!
+//! ```ignore
+//! blueshift_events::cpi::emit_event(
+//!     &crate::ID,
+//!     &ctx.accounts.event_authority,
+//!     ctx.bumps.event_authority,
+//!     &VaultDepositedV2 { owner, vault, amount, timestamp },
+//! )?;
+//! ```
+//!
+//! 事件写在内部指令的数据里，不受日志长度上限影响，RPC 截断日志时也不会丢失。
+
+use solana_account_info::AccountInfo;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_program_error::ProgramResult;
+use solana_pubkey::Pubkey;
+
+use crate::Event;
+
+pub use pdas::EVENT_AUTHORITY_SEED;
+
+/// 以 `event_authority`（`["__event_authority", bump]`）签名自调用 `program_id`，
+/// 指令数据为 [`Event::cpi_data`]
+pub fn emit_event<E: Event>(
+    program_id: &Pubkey,
+    event_authority: &AccountInfo,
+    bump: u8,
+    event: &E,
+) -> ProgramResult {
+    let ix = Instruction::new_with_bytes(
+        *program_id,
+        &event.cpi_data(),
+        alloc::vec![AccountMeta::new_readonly(*event_authority.key, true)],
+    );
+    solana_cpi::invoke_signed(
+        &ix,
+        core::slice::from_ref(event_authority),
+        &[&[EVENT_AUTHORITY_SEED, &[bump]]],
+    )
+}
@@ -0,0 +1,56 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{impl_event, Address};
+
+/// 挂单：maker 存入 `amount` 个 mint_a，期望换回 `receive` 个 mint_b
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct EscrowMade {
+    pub escrow: Address,
+    pub maker: Address,
+    pub mint_a: Address,
+    pub mint_b: Address,
+    pub seed: u64,
+    pub amount: u64,
+    pub receive: u64,
+}
+impl_event!(EscrowMade, [45, 225, 74, 129, 146, 57, 61, 98]);
+
+/// 吃单：taker 取走金库中的 `amount` 个 mint_a，向 maker 支付 `receive` 个 mint_b
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct EscrowTaken {
+    pub escrow: Address,
+    pub maker: Address,
+    pub taker: Address,
+    pub amount: u64,
+    pub receive: u64,
+}
+impl_event!(EscrowTaken, [164, 79, 50, 26, 174, 149, 92, 158]);
+
+/// 撤单：金库中的 `amount` 个 mint_a 退回 maker
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct EscrowRefunded {
+    pub escrow: Address,
+    pub maker: Address,
+    pub amount: u64,
+}
+impl_event!(EscrowRefunded, [132, 209, 49, 109, 135, 138, 28, 81]);
+
+/// 改单：maker 把挂单的报价改为 `receive` 个 `mint_b`（只有 Pinocchio 托管会发出）
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct EscrowUpdated {
+    pub escrow: Address,
+    pub maker: Address,
+    pub mint_b: Address,
+    pub receive: u64,
+}
+impl_event!(EscrowUpdated, [206, 217, 203, 80, 31, 243, 215, 165]);
+
+/// 加仓：maker 向金库追加 `amount` 个 mint_a，整单报价改为 `receive`（只有 Pinocchio 托管会发出）
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct EscrowToppedUp {
+    pub escrow: Address,
+    pub maker: Address,
+    pub amount: u64,
+    pub receive: u64,
+}
+impl_event!(EscrowToppedUp, [55, 95, 238, 56, 146, 173, 210, 70]);
@@ -0,0 +1,449 @@
+//! 训练营各程序的事件定义，链上发出与链下解析共用一份。
+//!
+//! 编码约定与 Anchor `emit!` 一致：一次 `sol_log_data` 只写一个字段，内容为
+//! `discriminator (8 字节) + borsh(事件)`，日志中显示为 `Program data: <base64>`。
+//! discriminator 取 `sha256("event:<事件名>")` 的前 8 字节，只由事件名决定，
+//! 因此事件改名等于换成新事件。已发布事件的布局不再改动：需要新字段时定义带版本后缀的
+//! 新事件（例如 [`VaultDepositedV2`]），旧事件保留在 [`ProgramEvent`] 中解析历史日志。
+//!
+//! | 事件                                           | 程序                     |
+//! |------------------------------------------------|--------------------------|
+//! | [`VaultDepositedV2`] / [`VaultWithdrawnV2`] / [`VaultLiquidityProvided`] / [`VaultLiquidityRemoved`]（以及历史的 [`VaultDeposited`] / [`VaultWithdrawn`]） | 金库（Pinocchio / Anchor） |
+//! | [`EscrowMade`] / [`EscrowTaken`] / [`EscrowRefunded`] / [`EscrowUpdated`] / [`EscrowToppedUp`] | 托管（Pinocchio / Anchor；改单与加仓只有 Pinocchio） |
+//! | [`PoolInitialized`] / [`LiquidityDeposited`] / [`LiquidityWithdrawn`] / [`Swapped`] | 原生 AMM |
+//! | [`TreasuryDeposited`] / [`TreasuryWithdrawn`] / [`TreasuryDistributed`] | 国库 |
+//!
+//! Pinocchio 程序用 [`Event::emit`] 写日志；Anchor 程序统一走事件 CPI（`cpi` feature 的
+//! [`cpi::emit_event`]）：以 `["__event_authority"]` PDA 签名自调用，指令数据为
+//! [`EVENT_IX_TAG`] + 上面的编码。两种方式承载的字节相同，以后新增的 Anchor 程序也按此发出事件。
+//!
+//! 地址统一用 `[u8; 32]`，不绑定 pinocchio 0.9 / 0.10 或 solana-sdk 的类型。
+//! 链下解析：[`ProgramEvent::decode`] 解一条 `Program data`，打开 `logs` feature 后
+//! [`events_from_logs`] 直接处理整笔交易的日志；事件 CPI 的内部指令用 [`ProgramEvent::decode_cpi`]。
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt;
+
+pub use borsh;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+mod amm;
+mod escrow;
+mod treasury;
+mod vault;
+
+#[cfg(feature = "cpi")]
+pub mod cpi;
+
+#[cfg(feature = "logs")]
+mod logs;
+
+pub use amm::{LiquidityDeposited, LiquidityWithdrawn, PoolInitialized, Swapped};
+pub use escrow::{EscrowMade, EscrowRefunded, EscrowTaken, EscrowToppedUp, EscrowUpdated};
+#[cfg(feature = "logs")]
+pub use logs::events_from_logs;
+pub use treasury::{TreasuryDeposited, TreasuryDistributed, TreasuryWithdrawn};
+pub use vault::{
+    VaultDeposited, VaultDepositedV2, VaultLiquidityProvided, VaultLiquidityRemoved,
+    VaultWithdrawn, VaultWithdrawnV2,
+};
+
+/// 事件中的地址：32 字节公钥
+pub type Address = [u8; 32];
+
+/// 事件 CPI 指令数据的前缀，即 Anchor 的 `EVENT_IX_TAG_LE`
+/// （`sha256("anchor:event")[..8]` 按大端读成 u64 后的小端字节）
+pub const EVENT_IX_TAG: [u8; 8] = 0x1d9a_cb51_2ea5_45e4u64.to_le_bytes();
+
+/// 一个可以发出的事件
+pub trait Event: BorshSerialize + BorshDeserialize {
+    /// 事件名，即 discriminator 的哈希输入
+    const NAME: &'static str;
+    /// `sha256("event:<NAME>")[..8]`
+    const DISCRIMINATOR: [u8; 8];
+
+    /// `discriminator + borsh(self)`
+    fn encode(&self) -> Vec<u8> {
+        let mut data = Self::DISCRIMINATOR.to_vec();
+        // 写入 Vec 不会失败
+        let _ = self.serialize(&mut data);
+        data
+    }
+
+    /// 事件 CPI 的指令数据：`EVENT_IX_TAG + discriminator + borsh(self)`
+    fn cpi_data(&self) -> Vec<u8> {
+        let mut data = EVENT_IX_TAG.to_vec();
+        data.extend_from_slice(&self.encode());
+        data
+    }
+
+    /// 通过 `sol_log_data` 写入程序日志；链下构建没有该系统调用，什么也不做
+    fn emit(&self) {
+        log_data(&self.encode());
+    }
+}
+
+#[cfg(target_os = "solana")]
+fn log_data(data: &[u8]) {
+    let fields: [&[u8]; 1] = [data];
+    unsafe {
+        solana_define_syscall::definitions::sol_log_data(
+            fields.as_ptr() as *const u8,
+            fields.len() as u64,
+        )
+    };
+}
+
+#[cfg(not(target_os = "solana"))]
+fn log_data(_data: &[u8]) {}
+
+/// 为事件结构体实现 [`Event`]
+macro_rules! impl_event {
+    ($event:ident, $discriminator:expr) => {
+        impl $crate::Event for $event {
+            const NAME: &'static str = stringify!($event);
+            const DISCRIMINATOR: [u8; 8] = $discriminator;
+        }
+    };
+}
+pub(crate) use impl_event;
+
+/// 无法解析的 `Program data`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// 不足 8 字节，没有 discriminator
+    TooShort,
+    /// discriminator 不属于任何训练营事件
+    UnknownDiscriminator([u8; 8]),
+    /// `Program data:` 后的字段不是合法的 base64
+    Base64,
+    /// discriminator 已知，但 borsh 解码失败
+    Borsh(&'static str),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort => write!(f, "事件数据不足 8 字节"),
+            Self::UnknownDiscriminator(discriminator) => {
+                write!(f, "未知的事件 discriminator {discriminator:?}")
+            }
+            Self::Base64 => write!(f, "事件数据不是合法的 base64"),
+            Self::Borsh(name) => write!(f, "{name} 事件解码失败"),
+        }
+    }
+}
+
+/// 列出全部事件，生成 [`ProgramEvent`] 及其按 discriminator 分发的解码
+macro_rules! program_events {
+    ($($program:literal => [$($event:ident),* $(,)?]),* $(,)?) => {
+        /// 任意一个训练营程序发出的事件
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        pub enum ProgramEvent {
+            $($($event($event),)*)*
+        }
+
+        impl ProgramEvent {
+            /// 解码一条 `Program data`（已完成 base64 解码）
+            pub fn decode(data: &[u8]) -> Result<Self, DecodeError> {
+                let (discriminator, payload) =
+                    data.split_first_chunk::<8>().ok_or(DecodeError::TooShort)?;
+                match *discriminator {
+                    // 不要求读完 payload：旧版解码器可以忽略新版在末尾追加的字段
+                    $($(<$event as Event>::DISCRIMINATOR => $event::deserialize(&mut &payload[..])
+                        .map(Self::$event)
+                        .map_err(|_| DecodeError::Borsh(<$event as Event>::NAME)),)*)*
+                    _ => Err(DecodeError::UnknownDiscriminator(*discriminator)),
+                }
+            }
+
+            /// 按该事件自身的 discriminator 重新编码
+            pub fn encode(&self) -> Vec<u8> {
+                match self {
+                    $($(Self::$event(event) => event.encode(),)*)*
+                }
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $($(Self::$event(_) => <$event as Event>::NAME,)*)*
+                }
+            }
+
+            /// 返回发出该事件的程序名，与 `blueshift_errors::BootcampError::program` 一致
+            pub fn program(&self) -> &'static str {
+                match self {
+                    $($(Self::$event(_) => $program,)*)*
+                }
+            }
+        }
+    };
+}
+
+program_events! {
+    "vault" => [
+        VaultDeposited,
+        VaultWithdrawn,
+        VaultLiquidityProvided,
+        VaultDepositedV2,
+        VaultWithdrawnV2,
+        VaultLiquidityRemoved,
+    ],
+    "escrow" => [EscrowMade, EscrowTaken, EscrowRefunded, EscrowUpdated, EscrowToppedUp],
+    "amm" => [PoolInitialized, LiquidityDeposited, LiquidityWithdrawn, Swapped],
+    "treasury" => [TreasuryDeposited, TreasuryWithdrawn, TreasuryDistributed],
+}
+
+impl ProgramEvent {
+    /// 解码一条事件 CPI 的指令数据；不以 [`EVENT_IX_TAG`] 开头（不是事件 CPI）时返回 `None`。
+    ///
+    /// 调用方还应确认该内部指令调用的是发出事件的程序本身，且唯一的账户是它的
+    /// `event_authority`（`pdas::event_authority_pda`），否则任何人都能伪造一条同样的指令
+    pub fn decode_cpi(data: &[u8]) -> Option<Result<Self, DecodeError>> {
+        data.strip_prefix(&EVENT_IX_TAG).map(Self::decode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    fn all() -> Vec<ProgramEvent> {
+        let address = |byte: u8| [byte; 32];
+        alloc::vec![
+            ProgramEvent::VaultDeposited(VaultDeposited {
+                owner: address(1),
+                vault: address(2),
+                amount: 1_000_000_000,
+            }),
+            ProgramEvent::VaultWithdrawn(VaultWithdrawn {
+                owner: address(1),
+                vault: address(2),
+                amount: 1_000_000_000,
+            }),
+            ProgramEvent::VaultLiquidityProvided(VaultLiquidityProvided {
+                owner: address(1),
+                vault: address(2),
+                pool: address(7),
+                lamports: 500_000_000,
+                lp: 1_000,
+            }),
+            ProgramEvent::VaultDepositedV2(VaultDepositedV2 {
+                owner: address(1),
+                vault: address(2),
+                amount: 1_000_000_000,
+                timestamp: 1_700_000_000,
+            }),
+            ProgramEvent::VaultWithdrawnV2(VaultWithdrawnV2 {
+                owner: address(1),
+                vault: address(2),
+                amount: 1_000_000_000,
+                timestamp: 1_700_000_060,
+            }),
+            ProgramEvent::VaultLiquidityRemoved(VaultLiquidityRemoved {
+                owner: address(1),
+                vault: address(2),
+                pool: address(7),
+                lp: 1_000,
+                lamports: 250_000_000,
+                amount: 300,
+            }),
+            ProgramEvent::EscrowMade(EscrowMade {
+                escrow: address(3),
+                maker: address(1),
+                mint_a: address(4),
+                mint_b: address(5),
+                seed: 42,
+                amount: 50,
+                receive: 80,
+            }),
+            ProgramEvent::EscrowTaken(EscrowTaken {
+                escrow: address(3),
+                maker: address(1),
+                taker: address(6),
+                amount: 50,
+                receive: 80,
+            }),
+            ProgramEvent::EscrowRefunded(EscrowRefunded {
+                escrow: address(3),
+                maker: address(1),
+                amount: 50,
+            }),
+            ProgramEvent::EscrowUpdated(EscrowUpdated {
+                escrow: address(3),
+                maker: address(1),
+                mint_b: address(5),
+                receive: 120,
+            }),
+            ProgramEvent::EscrowToppedUp(EscrowToppedUp {
+                escrow: address(3),
+                maker: address(1),
+                amount: 25,
+                receive: 160,
+            }),
+            ProgramEvent::PoolInitialized(PoolInitialized {
+                config: address(7),
+                authority: address(1),
+                mint_x: address(4),
+                mint_y: address(5),
+                seed: 0,
+                fee: 30,
+            }),
+            ProgramEvent::LiquidityDeposited(LiquidityDeposited {
+                config: address(7),
+                user: address(1),
+                lp_amount: 100,
+                amount_x: 10,
+                amount_y: 20,
+            }),
+            ProgramEvent::LiquidityWithdrawn(LiquidityWithdrawn {
+                config: address(7),
+                user: address(1),
+                lp_amount: 100,
+                amount_x: 10,
+                amount_y: 20,
+            }),
+            ProgramEvent::Swapped(Swapped {
+                config: address(7),
+                user: address(6),
+                is_x: true,
+                amount_in: 1_000,
+                amount_out: 996,
+                fee: 30,
+            }),
+            ProgramEvent::TreasuryDeposited(TreasuryDeposited {
+                treasury: address(8),
+                depositor: address(7),
+                mint: [0; 32],
+                source: 2,
+                amount: 3_000,
+            }),
+            ProgramEvent::TreasuryWithdrawn(TreasuryWithdrawn {
+                treasury: address(8),
+                authority: address(1),
+                recipient: address(6),
+                mint: address(4),
+                amount: 1_500,
+            }),
+            ProgramEvent::TreasuryDistributed(TreasuryDistributed {
+                treasury: address(8),
+                authority: address(1),
+                recipients: 3,
+                amount: 9_000,
+            }),
+        ]
+    }
+
+    #[test]
+    fn discriminators_follow_anchor_convention() {
+        for event in all() {
+            let hash = Sha256::digest(alloc::format!("event:{}", event.name()));
+            assert_eq!(event.encode()[..8], hash[..8], "{}", event.name());
+        }
+    }
+
+    #[test]
+    fn encode_decode_round_trip() {
+        for event in all() {
+            assert_eq!(ProgramEvent::decode(&event.encode()), Ok(event));
+        }
+    }
+
+    #[test]
+    fn layout_is_discriminator_then_fields() {
+        let event = VaultDeposited {
+            owner: [1; 32],
+            vault: [2; 32],
+            amount: 7,
+        };
+        let data = event.encode();
+        assert_eq!(data.len(), 8 + 32 + 32 + 8);
+        assert_eq!(data[..8], VaultDeposited::DISCRIMINATOR);
+        assert_eq!(data[8..40], [1; 32]);
+        assert_eq!(data[72..], 7u64.to_le_bytes());
+    }
+
+    #[test]
+    fn versioned_event_appends_fields_under_new_discriminator() {
+        let event = VaultDepositedV2 {
+            owner: [1; 32],
+            vault: [2; 32],
+            amount: 7,
+            timestamp: -1,
+        };
+        let data = event.encode();
+        assert_eq!(data.len(), 8 + 32 + 32 + 8 + 8);
+        assert_ne!(
+            VaultDepositedV2::DISCRIMINATOR,
+            VaultDeposited::DISCRIMINATOR
+        );
+        assert_eq!(data[..8], VaultDepositedV2::DISCRIMINATOR);
+        assert_eq!(data[72..80], 7u64.to_le_bytes());
+        assert_eq!(data[80..], (-1i64).to_le_bytes());
+        // 只认识旧事件的解码器会报未知 discriminator，而不是把 timestamp 当作多余字节忽略掉
+        assert_eq!(
+            ProgramEvent::decode(&data),
+            Ok(ProgramEvent::VaultDepositedV2(event))
+        );
+    }
+
+    #[test]
+    fn cpi_data_is_anchor_event_ix() {
+        let hash = Sha256::digest("anchor:event");
+        let mut tag = [0u8; 8];
+        tag.copy_from_slice(&hash[..8]);
+        tag.reverse();
+        assert_eq!(EVENT_IX_TAG, tag);
+
+        let deposited = VaultDepositedV2 {
+            owner: [1; 32],
+            vault: [2; 32],
+            amount: 7,
+            timestamp: 0,
+        };
+        let data = deposited.cpi_data();
+        assert_eq!(data[..8], EVENT_IX_TAG);
+        assert_eq!(data[8..], deposited.encode());
+        assert_eq!(
+            ProgramEvent::decode_cpi(&data),
+            Some(Ok(ProgramEvent::VaultDepositedV2(deposited)))
+        );
+        // 普通指令（例如 Anchor 的 deposit）不是事件 CPI
+        assert_eq!(
+            ProgramEvent::decode_cpi(&[242, 35, 198, 137, 82, 225, 242, 182]),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_data() {
+        assert_eq!(ProgramEvent::decode(&[1, 2, 3]), Err(DecodeError::TooShort));
+        assert_eq!(
+            ProgramEvent::decode(&[0; 8]),
+            Err(DecodeError::UnknownDiscriminator([0; 8]))
+        );
+        let mut truncated = all()[0].encode();
+        truncated.pop();
+        assert_eq!(
+            ProgramEvent::decode(&truncated),
+            Err(DecodeError::Borsh("VaultDeposited"))
+        );
+    }
+
+    #[test]
+    fn events_are_grouped_by_program() {
+        let programs: Vec<_> = all().iter().map(ProgramEvent::program).collect();
+        assert_eq!(
+            programs,
+            [
+                "vault", "vault", "vault", "vault", "vault", "vault", "escrow", "escrow", "escrow",
+                "escrow", "escrow", "amm", "amm", "amm", "amm", "treasury", "treasury", "treasury"
+            ]
+        );
+    }
+}
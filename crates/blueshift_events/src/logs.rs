@@ -0,0 +1,83 @@
+//! 从交易日志中取出训练营程序发出的事件。
+
+use alloc::vec::Vec;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::{DecodeError, ProgramEvent};
+
+/// 取出 `program_id`（base58）发出的全部事件（按出现顺序）。
+///
+/// 通过 `Program <id> invoke [n]` / `Program <id> success|failed` 跟踪调用栈，
+/// 其它程序（包括被 CPI 调用的程序）打印的 `Program data:` 会被忽略。
+pub fn events_from_logs<S: AsRef<str>>(
+    program_id: &str,
+    logs: &[S],
+) -> Vec<Result<ProgramEvent, DecodeError>> {
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(data) = log.strip_prefix("Program data: ") {
+            if stack.last() == Some(&program_id) {
+                // 每个事件只写一个字段
+                let field = data.split(' ').next().unwrap_or_default();
+                events.push(
+                    STANDARD
+                        .decode(field)
+                        .map_err(|_| DecodeError::Base64)
+                        .and_then(|bytes| ProgramEvent::decode(&bytes)),
+                );
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            match (words.next(), words.next()) {
+                (Some(id), Some("invoke")) => stack.push(id),
+                (Some(_), Some("success" | "failed:")) => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, Swapped};
+    use alloc::{format, string::String, vec};
+
+    const AMM: &str = "22222222222222222222222222222222222222222222";
+    const TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    #[test]
+    fn only_events_of_the_program_are_decoded() {
+        let swapped = Swapped {
+            config: [7; 32],
+            user: [6; 32],
+            is_x: false,
+            amount_in: 1_000,
+            amount_out: 996,
+            fee: 25,
+        };
+        let data = STANDARD.encode(swapped.encode());
+        let logs: Vec<String> = vec![
+            format!("Program {AMM} invoke [1]"),
+            format!("Program {TOKEN} invoke [2]"),
+            // CPI 中的同名前缀属于 Token 程序，不解码
+            format!("Program data: {data}"),
+            format!("Program {TOKEN} success"),
+            format!("Program data: {data}"),
+            "Program data: !!!".into(),
+            format!("Program {AMM} success"),
+        ];
+        let events = events_from_logs(AMM, &logs);
+        assert_eq!(
+            events,
+            [Ok(ProgramEvent::Swapped(swapped)), Err(DecodeError::Base64)]
+        );
+    }
+}
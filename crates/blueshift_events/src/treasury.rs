@@ -0,0 +1,36 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{impl_event, Address};
+
+/// 存入手续费：`depositor` 向国库转入 `amount`。`mint` 全零表示 SOL（lamports），
+/// `source` 是来源程序编号（金库 0、托管 1、AMM 2、其他 255）
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryDeposited {
+    pub treasury: Address,
+    pub depositor: Address,
+    pub mint: Address,
+    pub source: u8,
+    pub amount: u64,
+}
+impl_event!(TreasuryDeposited, [1, 193, 184, 0, 137, 134, 85, 50]);
+
+/// 提取：持有提取角色的 `authority` 把 `amount` 转给 `recipient`，`mint` 含义同上
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryWithdrawn {
+    pub treasury: Address,
+    pub authority: Address,
+    pub recipient: Address,
+    pub mint: Address,
+    pub amount: u64,
+}
+impl_event!(TreasuryWithdrawn, [143, 181, 157, 169, 87, 155, 170, 46]);
+
+/// 分配：持有分配角色的 `authority` 把 `amount` lamports 按份额分给 `recipients` 个地址
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryDistributed {
+    pub treasury: Address,
+    pub authority: Address,
+    pub recipients: u8,
+    pub amount: u64,
+}
+impl_event!(TreasuryDistributed, [202, 215, 152, 240, 128, 243, 153, 77]);
@@ -0,0 +1,74 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{impl_event, Address};
+
+/// 存款成功：`amount` lamports 从 owner 转入其金库 PDA。
+/// 金库程序现在发出 [`VaultDepositedV2`]，这里保留原来的布局用于解析历史日志
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct VaultDeposited {
+    pub owner: Address,
+    pub vault: Address,
+    pub amount: u64,
+}
+impl_event!(VaultDeposited, [59, 62, 43, 200, 220, 104, 100, 67]);
+
+/// 取款成功：金库的全部 `amount` lamports 退回 owner。
+/// 与 [`VaultDeposited`] 一样只用于解析历史日志，现在发出的是 [`VaultWithdrawnV2`]
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct VaultWithdrawn {
+    pub owner: Address,
+    pub vault: Address,
+    pub amount: u64,
+}
+impl_event!(VaultWithdrawn, [238, 9, 219, 172, 188, 77, 72, 104]);
+
+/// 存款成功，带时间戳：在 [`VaultDeposited`] 的字段之后追加 `timestamp`。
+/// 布局变了，所以换成新的事件名（discriminator 随之改变），旧解码器不会把它误读成旧事件
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct VaultDepositedV2 {
+    pub owner: Address,
+    pub vault: Address,
+    pub amount: u64,
+    /// 发出事件时 Clock 的 unix_timestamp，索引器不必再按 slot 查询区块时间
+    pub timestamp: i64,
+}
+impl_event!(VaultDepositedV2, [214, 196, 150, 151, 233, 182, 39, 100]);
+
+/// 取款成功，带时间戳：`amount` lamports 从金库转出（全部取出时即金库原有余额）
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct VaultWithdrawnV2 {
+    pub owner: Address,
+    pub vault: Address,
+    pub amount: u64,
+    /// 发出事件时 Clock 的 unix_timestamp
+    pub timestamp: i64,
+}
+impl_event!(VaultWithdrawnV2, [86, 135, 35, 225, 87, 148, 121, 236]);
+
+/// 金库资金做市：`lamports` 包装为 wSOL，一半换成池子另一侧代币后存入 `pool`，
+/// 为金库 PDA 铸造 `lp` 个 LP 代币（task4）
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct VaultLiquidityProvided {
+    pub owner: Address,
+    pub vault: Address,
+    pub pool: Address,
+    pub lamports: u64,
+    pub lp: u64,
+}
+impl_event!(VaultLiquidityProvided, [167, 48, 16, 63, 254, 78, 18, 31]);
+
+/// 金库撤出流动性：从 `pool` 赎回 `lp` 个 LP，wSOL 一侧解包后 `lamports` 回到金库的 SOL 余额，
+/// 另一侧的 `amount` 个代币留在金库 PDA 的 ATA 中，由 SweepTokens 转给 owner（task4）
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct VaultLiquidityRemoved {
+    pub owner: Address,
+    pub vault: Address,
+    pub pool: Address,
+    pub lp: u64,
+    pub lamports: u64,
+    pub amount: u64,
+}
+impl_event!(
+    VaultLiquidityRemoved,
+    [191, 105, 119, 147, 219, 186, 182, 179]
+);
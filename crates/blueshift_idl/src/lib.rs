@@ -0,0 +1,531 @@
+//! 为 Pinocchio / 原生程序生成 JSON IDL，供各程序的 build.rs 使用。
+//!
+//! 输出格式与 shank 相同（即 Anchor 0.30 之前的 IDL 格式，`metadata.origin = "shank"`），
+//! Codama / Solita 等工具可以直接据此生成客户端。程序内的名字按 Rust 的 snake_case 书写，
+//! 写出时转成 IDL 约定的 camelCase。
+//!
+//! 这些程序的指令数据是定长的 `#[repr(C, packed)]` 结构体，字段依次排列、没有对齐填充，
+//! 与 borsh 对定长字段的编码一致，所以可以直接用 IDL 的基本类型描述。
+//! 按某个字段决定长度的尾部数组（如 `SwapBatch` 的 legs）没有对应的 IDL 类型，
+//! 只写在指令的 docs 里，由客户端自行追加在参数之后；剩余账户同理。
+//!
+//! ```no_run
+//! use blueshift_idl::{Account, Idl, Instruction, Type};
+//!
+//! Idl::new("my_program", "22222222222222222222222222222222222222222222")
+//!     .instruction(
+//!         Instruction::new("deposit", 0)
+//!             .account(Account::new("owner").writable().signer())
+//!             .arg("amount", Type::U64),
+//!     )
+//!     .write()
+//!     .unwrap();
+//! ```
+
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Map, Value};
+
+/// 字段类型
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Type {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I16,
+    I32,
+    I64,
+    I128,
+    PublicKey,
+    /// 定长数组
+    Array(Box<Type>, usize),
+    /// 引用 `types` / `accounts` 中定义的结构体
+    Defined(&'static str),
+}
+
+impl Type {
+    pub fn array(ty: Type, len: usize) -> Self {
+        Self::Array(Box::new(ty), len)
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            Self::Bool => json!("bool"),
+            Self::U8 => json!("u8"),
+            Self::U16 => json!("u16"),
+            Self::U32 => json!("u32"),
+            Self::U64 => json!("u64"),
+            Self::U128 => json!("u128"),
+            Self::I16 => json!("i16"),
+            Self::I32 => json!("i32"),
+            Self::I64 => json!("i64"),
+            Self::I128 => json!("i128"),
+            Self::PublicKey => json!("publicKey"),
+            Self::Array(ty, len) => json!({ "array": [ty.to_json(), len] }),
+            Self::Defined(name) => json!({ "defined": name }),
+        }
+    }
+}
+
+/// 结构体字段或指令参数
+#[derive(Clone, Debug)]
+pub struct Field {
+    name: &'static str,
+    ty: Type,
+    docs: Vec<&'static str>,
+}
+
+impl Field {
+    pub fn new(name: &'static str, ty: Type) -> Self {
+        Self {
+            name,
+            ty,
+            docs: Vec::new(),
+        }
+    }
+
+    pub fn doc(mut self, doc: &'static str) -> Self {
+        self.docs.push(doc);
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        let mut field = Map::new();
+        field.insert("name".into(), json!(camel_case(self.name)));
+        if !self.docs.is_empty() {
+            field.insert("docs".into(), json!(self.docs));
+        }
+        field.insert("type".into(), self.ty.to_json());
+        Value::Object(field)
+    }
+}
+
+/// 指令的一个账户
+#[derive(Clone, Debug)]
+pub struct Account {
+    name: &'static str,
+    is_mut: bool,
+    is_signer: bool,
+    is_optional: bool,
+    docs: Vec<&'static str>,
+}
+
+impl Account {
+    /// 只读、非签名的账户
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            is_mut: false,
+            is_signer: false,
+            is_optional: false,
+            docs: Vec::new(),
+        }
+    }
+
+    pub fn writable(mut self) -> Self {
+        self.is_mut = true;
+        self
+    }
+
+    pub fn signer(mut self) -> Self {
+        self.is_signer = true;
+        self
+    }
+
+    /// 可以省略的尾部账户；省略时后面的账户依次前移
+    pub fn optional(mut self) -> Self {
+        self.is_optional = true;
+        self
+    }
+
+    pub fn doc(mut self, doc: &'static str) -> Self {
+        self.docs.push(doc);
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        let mut account = Map::new();
+        account.insert("name".into(), json!(camel_case(self.name)));
+        account.insert("isMut".into(), json!(self.is_mut));
+        account.insert("isSigner".into(), json!(self.is_signer));
+        if self.is_optional {
+            account.insert("isOptional".into(), json!(true));
+        }
+        if !self.docs.is_empty() {
+            account.insert("docs".into(), json!(self.docs));
+        }
+        Value::Object(account)
+    }
+}
+
+/// 一条指令：1 字节 discriminator + 按顺序排列的参数
+#[derive(Clone, Debug)]
+pub struct Instruction {
+    name: &'static str,
+    discriminant: u8,
+    docs: Vec<&'static str>,
+    accounts: Vec<Account>,
+    args: Vec<Field>,
+}
+
+impl Instruction {
+    pub fn new(name: &'static str, discriminant: u8) -> Self {
+        Self {
+            name,
+            discriminant,
+            docs: Vec::new(),
+            accounts: Vec::new(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn doc(mut self, doc: &'static str) -> Self {
+        self.docs.push(doc);
+        self
+    }
+
+    pub fn account(mut self, account: Account) -> Self {
+        self.accounts.push(account);
+        self
+    }
+
+    pub fn arg(self, name: &'static str, ty: Type) -> Self {
+        self.arg_with(Field::new(name, ty))
+    }
+
+    /// 带 docs 的参数
+    pub fn arg_with(mut self, field: Field) -> Self {
+        self.args.push(field);
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        let mut instruction = Map::new();
+        instruction.insert("name".into(), json!(camel_case(self.name)));
+        if !self.docs.is_empty() {
+            instruction.insert("docs".into(), json!(self.docs));
+        }
+        instruction.insert(
+            "accounts".into(),
+            self.accounts.iter().map(Account::to_json).collect(),
+        );
+        instruction.insert(
+            "args".into(),
+            self.args.iter().map(Field::to_json).collect(),
+        );
+        instruction.insert(
+            "discriminant".into(),
+            json!({ "type": "u8", "value": self.discriminant }),
+        );
+        Value::Object(instruction)
+    }
+}
+
+/// 账户数据或参数中使用的结构体
+#[derive(Clone, Debug)]
+pub struct TypeDef {
+    name: &'static str,
+    docs: Vec<&'static str>,
+    fields: Vec<Field>,
+}
+
+impl TypeDef {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            docs: Vec::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn doc(mut self, doc: &'static str) -> Self {
+        self.docs.push(doc);
+        self
+    }
+
+    pub fn field(self, name: &'static str, ty: Type) -> Self {
+        self.field_with(Field::new(name, ty))
+    }
+
+    /// 带 docs 的字段
+    pub fn field_with(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        let mut def = Map::new();
+        def.insert("name".into(), json!(self.name));
+        if !self.docs.is_empty() {
+            def.insert("docs".into(), json!(self.docs));
+        }
+        def.insert(
+            "type".into(),
+            json!({
+                "kind": "struct",
+                "fields": self.fields.iter().map(Field::to_json).collect::<Vec<_>>(),
+            }),
+        );
+        Value::Object(def)
+    }
+}
+
+/// 自定义错误，编号即 `ProgramError::Custom` 中的值
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Error {
+    pub code: u32,
+    pub name: String,
+    pub msg: String,
+}
+
+/// 一个程序的 IDL
+#[derive(Clone, Debug)]
+pub struct Idl {
+    name: &'static str,
+    address: &'static str,
+    version: String,
+    docs: Vec<&'static str>,
+    instructions: Vec<Instruction>,
+    accounts: Vec<TypeDef>,
+    types: Vec<TypeDef>,
+    errors: Vec<Error>,
+}
+
+impl Idl {
+    /// `name` 同时决定输出文件名 `target/idl/<name>.json`；版本取自 `CARGO_PKG_VERSION`
+    pub fn new(name: &'static str, address: &'static str) -> Self {
+        Self {
+            name,
+            address,
+            version: env::var("CARGO_PKG_VERSION").unwrap_or_else(|_| "0.1.0".into()),
+            docs: Vec::new(),
+            instructions: Vec::new(),
+            accounts: Vec::new(),
+            types: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn doc(mut self, doc: &'static str) -> Self {
+        self.docs.push(doc);
+        self
+    }
+
+    pub fn instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// 程序拥有的账户的数据布局
+    pub fn account(mut self, account: TypeDef) -> Self {
+        self.accounts.push(account);
+        self
+    }
+
+    /// 其它结构体，通过 [`Type::Defined`] 引用
+    pub fn ty(mut self, ty: TypeDef) -> Self {
+        self.types.push(ty);
+        self
+    }
+
+    /// 追加一组错误，通常是 `blueshift_errors` 中某个枚举的 `ALL` 与 `code`
+    pub fn errors<E>(mut self, all: &[E], code: fn(E) -> u32) -> Self
+    where
+        E: Copy + fmt::Debug + fmt::Display,
+    {
+        self.errors.extend(all.iter().map(|&error| Error {
+            code: code(error),
+            name: format!("{error:?}"),
+            msg: error.to_string(),
+        }));
+        self
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut idl = Map::new();
+        idl.insert("version".into(), json!(self.version));
+        idl.insert("name".into(), json!(self.name));
+        if !self.docs.is_empty() {
+            idl.insert("docs".into(), json!(self.docs));
+        }
+        idl.insert(
+            "instructions".into(),
+            self.instructions.iter().map(Instruction::to_json).collect(),
+        );
+        idl.insert(
+            "accounts".into(),
+            self.accounts.iter().map(TypeDef::to_json).collect(),
+        );
+        idl.insert(
+            "types".into(),
+            self.types.iter().map(TypeDef::to_json).collect(),
+        );
+        idl.insert(
+            "errors".into(),
+            self.errors
+                .iter()
+                .map(|error| json!({ "code": error.code, "name": error.name, "msg": error.msg }))
+                .collect(),
+        );
+        idl.insert(
+            "metadata".into(),
+            json!({ "origin": "shank", "address": self.address }),
+        );
+        Value::Object(idl)
+    }
+
+    /// 在 build.rs 中调用：写入 `<target-dir>/idl/<name>.json` 并返回路径
+    pub fn write(&self) -> io::Result<PathBuf> {
+        let out_dir = env::var_os("OUT_DIR")
+            .map(PathBuf::from)
+            .ok_or_else(|| io::Error::other("OUT_DIR 未设置，Idl::write 只能在 build.rs 中调用"))?;
+        let target = env::var("TARGET").unwrap_or_default();
+        let dir = target_dir(&out_dir, &target)
+            .ok_or_else(|| {
+                io::Error::other(format!("无法从 {} 推出 target 目录", out_dir.display()))
+            })?
+            .join("idl");
+        self.write_to(&dir)
+    }
+
+    /// 写入 `<dir>/<name>.json`，内容不变时不改写文件
+    pub fn write_to(&self, dir: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}.json", self.name));
+        let mut json = serde_json::to_string_pretty(&self.to_json())?;
+        json.push('\n');
+        if fs::read_to_string(&path).ok().as_deref() != Some(json.as_str()) {
+            fs::write(&path, json)?;
+        }
+        Ok(path)
+    }
+}
+
+/// `OUT_DIR` 形如 `<target-dir>[/<triple>]/<profile>/build/<crate>-<hash>/out`，
+/// 交叉编译（如 `cargo build-sbf`）时多一层 triple 目录
+fn target_dir(out_dir: &Path, target: &str) -> Option<PathBuf> {
+    let profile_dir = out_dir.ancestors().nth(3)?;
+    let mut dir = profile_dir.parent()?;
+    if !target.is_empty() && dir.file_name().is_some_and(|name| name == target) {
+        dir = dir.parent()?;
+    }
+    Some(dir.to_path_buf())
+}
+
+fn camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug)]
+    enum TestError {
+        Zero,
+        Overflow,
+    }
+
+    impl fmt::Display for TestError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(match self {
+                Self::Zero => "Amount is zero",
+                Self::Overflow => "Overflow",
+            })
+        }
+    }
+
+    fn sample() -> Idl {
+        Idl::new("sample", "22222222222222222222222222222222222222222222")
+            .instruction(
+                Instruction::new("make_offer", 3)
+                    .account(Account::new("maker").writable().signer())
+                    .account(Account::new("token_program"))
+                    .account(Account::new("lp_lock").writable().optional())
+                    .arg("seed", Type::U64)
+                    .arg("legs", Type::array(Type::Defined("Leg"), 2)),
+            )
+            .account(TypeDef::new("Offer").field("maker", Type::PublicKey))
+            .errors(&[TestError::Zero, TestError::Overflow], |error| {
+                6000 + error as u32
+            })
+    }
+
+    #[test]
+    fn shank_layout() {
+        let idl = sample().to_json();
+        assert_eq!(idl["metadata"]["origin"], "shank");
+        assert_eq!(idl["name"], "sample");
+
+        let instruction = &idl["instructions"][0];
+        assert_eq!(instruction["name"], "makeOffer");
+        assert_eq!(
+            instruction["discriminant"],
+            json!({ "type": "u8", "value": 3 })
+        );
+        assert_eq!(
+            instruction["accounts"][0],
+            json!({ "name": "maker", "isMut": true, "isSigner": true })
+        );
+        assert_eq!(instruction["accounts"][2]["name"], "lpLock");
+        assert_eq!(instruction["accounts"][2]["isOptional"], true);
+        assert_eq!(
+            instruction["args"][1]["type"],
+            json!({ "array": [{ "defined": "Leg" }, 2] })
+        );
+
+        assert_eq!(
+            idl["accounts"][0]["type"],
+            json!({ "kind": "struct", "fields": [{ "name": "maker", "type": "publicKey" }] })
+        );
+        assert_eq!(
+            idl["errors"][1],
+            json!({ "code": 6001, "name": "Overflow", "msg": "Overflow" })
+        );
+    }
+
+    #[test]
+    fn target_dir_from_out_dir() {
+        let host = Path::new("/repo/target/debug/build/prog-0123/out");
+        assert_eq!(
+            target_dir(host, "x86_64-unknown-linux-gnu"),
+            Some(PathBuf::from("/repo/target"))
+        );
+        let sbf = Path::new("/repo/target/sbpf-solana-solana/release/build/prog-0123/out");
+        assert_eq!(
+            target_dir(sbf, "sbpf-solana-solana"),
+            Some(PathBuf::from("/repo/target"))
+        );
+    }
+
+    #[test]
+    fn write_skips_unchanged_file() {
+        let dir = env::temp_dir().join(format!("blueshift_idl_{}", std::process::id()));
+        let path = sample().write_to(&dir).unwrap();
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+        sample().write_to(&dir).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), modified);
+
+        let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, sample().to_json());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
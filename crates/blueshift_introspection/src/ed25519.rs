@@ -0,0 +1,197 @@
+//! Ed25519 预编译指令的数据格式。
+//!
+//! 指令数据：u8 签名数 n、u8 填充，然后 n 组 [`Ed25519SignatureOffsets`]，
+//! 签名、公钥与消息放在其后，由各组偏移指向。运行时在交易执行前验证全部签名，
+//! 程序只需要确认被验证的正是自己期望的公钥与消息。
+
+use crate::{read_address, read_u16, Instruction};
+
+/// Ed25519 预编译程序：Ed25519SigVerify111111111111111111111111111
+pub const ED25519_PROGRAM_ID: [u8; 32] = [
+    0x03, 0x7d, 0x46, 0xd6, 0x7c, 0x93, 0xfb, 0xbe, 0x12, 0xf9, 0x42, 0x8f, 0x83, 0x8d, 0x40, 0xff,
+    0x05, 0x70, 0x74, 0x49, 0x27, 0xf4, 0x8a, 0x64, 0xfc, 0xca, 0x70, 0x44, 0x80, 0x00, 0x00, 0x00,
+];
+
+/// 签名组之前的字节：签名数 + 填充
+const HEADER_LEN: usize = 2;
+const SIGNATURE_LEN: usize = 64;
+
+/// 指令序号取该值时表示数据就在这条预编译指令中
+const THIS_INSTRUCTION: u16 = u16::MAX;
+
+/// 一组签名的位置，七个 u16（小端）
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Ed25519SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u16,
+    pub public_key_offset: u16,
+    pub public_key_instruction_index: u16,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u16,
+}
+
+impl Ed25519SignatureOffsets {
+    pub const LEN: usize = 14;
+
+    fn read(data: &[u8], offset: usize) -> Option<Self> {
+        let field = |index: usize| read_u16(data, offset + index * 2);
+        Some(Self {
+            signature_offset: field(0)?,
+            signature_instruction_index: field(1)?,
+            public_key_offset: field(2)?,
+            public_key_instruction_index: field(3)?,
+            message_data_offset: field(4)?,
+            message_data_size: field(5)?,
+            message_instruction_index: field(6)?,
+        })
+    }
+
+    /// 三处数据都在第 `index` 条指令（即这条预编译指令）自身中。
+    /// 指向其他指令的签名可以拼接别人的数据，不予承认
+    fn self_contained(&self, index: usize) -> bool {
+        [
+            self.signature_instruction_index,
+            self.public_key_instruction_index,
+            self.message_instruction_index,
+        ]
+        .iter()
+        .all(|ix| *ix == THIS_INSTRUCTION || usize::from(*ix) == index)
+    }
+}
+
+/// 第 `index` 条指令 `instruction` 是 Ed25519 预编译指令，且其中有一组签名由 `signer` 对 `message` 签出
+pub(crate) fn verifies(
+    instruction: &Instruction<'_>,
+    index: usize,
+    signer: &[u8; 32],
+    message: &[u8],
+) -> bool {
+    if instruction.program_id() != &ED25519_PROGRAM_ID {
+        return false;
+    }
+    let data = instruction.data();
+    let Some(&count) = data.first() else {
+        return false;
+    };
+    (0..usize::from(count)).any(|i| {
+        let Some(offsets) =
+            Ed25519SignatureOffsets::read(data, HEADER_LEN + i * Ed25519SignatureOffsets::LEN)
+        else {
+            return false;
+        };
+        let signature_start = usize::from(offsets.signature_offset);
+        let message_start = usize::from(offsets.message_data_offset);
+        let message_end = message_start + usize::from(offsets.message_data_size);
+        offsets.self_contained(index)
+            && data
+                .get(signature_start..signature_start + SIGNATURE_LEN)
+                .is_some()
+            && read_address(data, usize::from(offsets.public_key_offset)) == Some(signer)
+            && data.get(message_start..message_end) == Some(message)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::{
+        tests::{sysvar, Ix},
+        Instructions, IntrospectionError,
+    };
+
+    const VAULT: [u8; 32] = [0x44; 32];
+    const SIGNER: [u8; 32] = [0x55; 32];
+    const MESSAGE: &[u8] = b"permit:withdraw:1000";
+
+    /// 与 solana-sdk `new_ed25519_instruction` 相同的布局：偏移之后依次是公钥、签名、消息
+    fn ed25519_data(signer: &[u8; 32], message: &[u8], instruction_index: u16) -> Vec<u8> {
+        let public_key_offset = HEADER_LEN + Ed25519SignatureOffsets::LEN;
+        let signature_offset = public_key_offset + 32;
+        let message_offset = signature_offset + SIGNATURE_LEN;
+        let mut data = std::vec![1, 0];
+        for field in [
+            signature_offset as u16,
+            instruction_index,
+            public_key_offset as u16,
+            instruction_index,
+            message_offset as u16,
+            message.len() as u16,
+            instruction_index,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        data.extend_from_slice(signer);
+        data.extend_from_slice(&[0x66; SIGNATURE_LEN]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    fn find(instructions: &[Ix<'_>], current: u16) -> Result<usize, IntrospectionError> {
+        let data = sysvar(instructions, current);
+        Instructions::new(&data)?.find_ed25519(&SIGNER, MESSAGE)
+    }
+
+    #[test]
+    fn program_id_matches_base58() {
+        assert_eq!(
+            solana_pubkey::Pubkey::from_str_const("Ed25519SigVerify111111111111111111111111111")
+                .to_bytes(),
+            ED25519_PROGRAM_ID
+        );
+    }
+
+    #[test]
+    fn finds_verify_before_or_after_current_instruction() {
+        let verify = ed25519_data(&SIGNER, MESSAGE, THIS_INSTRUCTION);
+        assert_eq!(
+            find(&[(VAULT, &[], &[1]), (ED25519_PROGRAM_ID, &[], &verify)], 0),
+            Ok(1)
+        );
+        assert_eq!(
+            find(&[(ED25519_PROGRAM_ID, &[], &verify), (VAULT, &[], &[1])], 1),
+            Ok(0)
+        );
+        // 用自身序号代替 u16::MAX 同样认可
+        let indexed = ed25519_data(&SIGNER, MESSAGE, 1);
+        assert_eq!(
+            find(
+                &[(VAULT, &[], &[1]), (ED25519_PROGRAM_ID, &[], &indexed)],
+                0
+            ),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn rejects_other_signers_messages_and_borrowed_data() {
+        let other_signer = ed25519_data(&[0x77; 32], MESSAGE, THIS_INSTRUCTION);
+        let other_message = ed25519_data(&SIGNER, b"permit:withdraw:9999", THIS_INSTRUCTION);
+        // 数据指向第 0 条指令：可能拼接了别人的公钥或消息
+        let borrowed = ed25519_data(&SIGNER, MESSAGE, 0);
+        // 同样的数据但不是预编译程序
+        let fake = ed25519_data(&SIGNER, MESSAGE, THIS_INSTRUCTION);
+        for (program_id, data) in [
+            (ED25519_PROGRAM_ID, &other_signer),
+            (ED25519_PROGRAM_ID, &other_message),
+            (ED25519_PROGRAM_ID, &borrowed),
+            (VAULT, &fake),
+        ] {
+            assert_eq!(
+                find(&[(VAULT, &[], &[1]), (program_id, &[], data)], 0),
+                Err(IntrospectionError::NotFound)
+            );
+        }
+        // 签名数为 0 时后面的数据不算数
+        let mut empty = ed25519_data(&SIGNER, MESSAGE, THIS_INSTRUCTION);
+        empty[0] = 0;
+        assert_eq!(
+            find(&[(VAULT, &[], &[1]), (ED25519_PROGRAM_ID, &[], &empty)], 0),
+            Err(IntrospectionError::NotFound)
+        );
+    }
+}
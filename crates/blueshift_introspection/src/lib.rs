@@ -0,0 +1,401 @@
+//! Instructions sysvar 的只读视图与基于它的检查。
+//!
+//! 程序把 `Sysvar1nstructions1111111111111111111111111` 作为账户传入，借用其数据后交给
+//! [`Instructions::new`]，就能看到同一笔交易中的全部顶层指令：
+//!
+//! - [`Instructions::find_ed25519`]：交易中有一条 Ed25519 预编译指令，验证了某个公钥对某段消息的签名
+//!   （链下签名的授权 / permit）；
+//! - [`Instructions::find_following_call`]：当前指令之后还有一条指定程序、指定判别器的指令
+//!   （闪电贷要求同一笔交易稍后归还）；
+//! - [`Instructions::is_top_level`] / [`is_cpi`]：当前指令是否由其他程序 CPI 调用。
+//!
+//! 账户数据布局（小端）：
+//!
+//! ```text
+//! u16 指令数 n
+//! u16 × n 各指令的起始偏移
+//! 每条指令：u16 账户数 m，m × (u8 标志位 + 32 字节地址)，32 字节程序 ID，u16 数据长度，数据
+//! u16 当前执行的顶层指令序号（位于账户数据最后两个字节）
+//! ```
+//!
+//! 与 `pdas` 一样地址统一用 `[u8; 32]`，错误由各程序换成自己的 `ProgramError`。
+
+#![no_std]
+
+mod ed25519;
+
+use core::fmt;
+
+pub use ed25519::{Ed25519SignatureOffsets, ED25519_PROGRAM_ID};
+
+/// Instructions sysvar：Sysvar1nstructions1111111111111111111111111
+pub const INSTRUCTIONS_SYSVAR_ID: [u8; 32] = [
+    0x06, 0xa7, 0xd5, 0x17, 0x18, 0x7b, 0xd1, 0x66, 0x35, 0xda, 0xd4, 0x04, 0x55, 0xfd, 0xc2, 0xc0,
+    0xc1, 0x24, 0xc6, 0x8f, 0x21, 0x56, 0x75, 0xa5, 0xdb, 0xba, 0xcb, 0x5f, 0x08, 0x00, 0x00, 0x00,
+];
+
+/// 顶层指令的调用栈高度；每经过一层 CPI 加一
+pub const TRANSACTION_LEVEL_STACK_HEIGHT: u64 = 1;
+
+/// 账户标志位：签名者
+const IS_SIGNER: u8 = 1 << 0;
+/// 账户标志位：可写
+const IS_WRITABLE: u8 = 1 << 1;
+
+/// 每个账户占用的字节：标志位 + 地址
+const ACCOUNT_META_LEN: usize = 1 + 32;
+
+/// 读取 Instructions sysvar 失败
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IntrospectionError {
+    /// 账户不是 Instructions sysvar，或数据不完整
+    InvalidSysvar,
+    /// 指令序号超出交易中的指令数
+    IndexOutOfRange,
+    /// 交易中没有要求的指令
+    NotFound,
+}
+
+impl fmt::Display for IntrospectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InvalidSysvar => "不是有效的 Instructions sysvar",
+            Self::IndexOutOfRange => "指令序号超出范围",
+            Self::NotFound => "交易中没有要求的指令",
+        })
+    }
+}
+
+/// 读取 `data[offset..offset + 2]` 的 u16
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+/// 读取 `data[offset..offset + 32]` 的地址
+fn read_address(data: &[u8], offset: usize) -> Option<&[u8; 32]> {
+    data.get(offset..offset.checked_add(32)?)?.try_into().ok()
+}
+
+/// Instructions sysvar 的账户数据
+#[derive(Clone, Copy, Debug)]
+pub struct Instructions<'a> {
+    data: &'a [u8],
+    len: usize,
+    current: usize,
+}
+
+impl<'a> Instructions<'a> {
+    /// 校验指令数、偏移表与当前序号都在数据范围内；各条指令的内容在 [`Instructions::get`] 时再校验。
+    /// 调用方负责确认账户地址是 [`INSTRUCTIONS_SYSVAR_ID`]，否则任何人都能传入伪造的数据
+    pub fn new(data: &'a [u8]) -> Result<Self, IntrospectionError> {
+        let len = read_u16(data, 0).ok_or(IntrospectionError::InvalidSysvar)? as usize;
+        let current = data
+            .len()
+            .checked_sub(2)
+            .and_then(|offset| read_u16(data, offset))
+            .ok_or(IntrospectionError::InvalidSysvar)? as usize;
+        // 偏移表之后至少还有当前序号
+        if data.len() < 2 + len * 2 + 2 || current >= len {
+            return Err(IntrospectionError::InvalidSysvar);
+        }
+        Ok(Self { data, len, current })
+    }
+
+    /// 交易中的顶层指令数
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 正在执行的顶层指令序号；CPI 中读取时仍是发起 CPI 的那条顶层指令
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    /// 第 `index` 条顶层指令
+    pub fn get(&self, index: usize) -> Result<Instruction<'a>, IntrospectionError> {
+        if index >= self.len {
+            return Err(IntrospectionError::IndexOutOfRange);
+        }
+        let offset = read_u16(self.data, 2 + index * 2).ok_or(IntrospectionError::InvalidSysvar)?;
+        Instruction::parse(self.data, offset as usize).ok_or(IntrospectionError::InvalidSysvar)
+    }
+
+    /// 正在执行的顶层指令
+    pub fn current(&self) -> Result<Instruction<'a>, IntrospectionError> {
+        self.get(self.current)
+    }
+
+    /// 当前指令是否由 `program_id` 直接作为顶层指令执行。
+    /// 返回 `false` 说明本程序是被顶层指令的程序 CPI 调用的
+    pub fn is_top_level(&self, program_id: &[u8; 32]) -> Result<bool, IntrospectionError> {
+        Ok(self.current()?.program_id() == program_id)
+    }
+
+    /// 当前指令之后第一条满足 `predicate` 的指令及其序号
+    pub fn find_following(
+        &self,
+        predicate: impl Fn(&Instruction<'a>) -> bool,
+    ) -> Result<(usize, Instruction<'a>), IntrospectionError> {
+        for index in self.current + 1..self.len {
+            let instruction = self.get(index)?;
+            if predicate(&instruction) {
+                return Ok((index, instruction));
+            }
+        }
+        Err(IntrospectionError::NotFound)
+    }
+
+    /// 当前指令之后第一条调用 `program_id`、数据以 `discriminator` 开头的指令，
+    /// 例如闪电贷借出时要求同一笔交易稍后归还。归还的账户与数量由调用方继续检查
+    pub fn find_following_call(
+        &self,
+        program_id: &[u8; 32],
+        discriminator: &[u8],
+    ) -> Result<(usize, Instruction<'a>), IntrospectionError> {
+        self.find_following(|ix| {
+            ix.program_id() == program_id && ix.data().starts_with(discriminator)
+        })
+    }
+
+    /// 交易中验证了 `signer` 对 `message` 签名的 Ed25519 预编译指令的序号。
+    ///
+    /// 预编译指令在交易开始执行前就已全部验证，所以它排在当前指令之前或之后都可以；
+    /// 只认签名、公钥与消息都写在这条预编译指令自身数据中的签名
+    pub fn find_ed25519(
+        &self,
+        signer: &[u8; 32],
+        message: &[u8],
+    ) -> Result<usize, IntrospectionError> {
+        for index in 0..self.len {
+            if index == self.current {
+                continue;
+            }
+            if ed25519::verifies(&self.get(index)?, index, signer, message) {
+                return Ok(index);
+            }
+        }
+        Err(IntrospectionError::NotFound)
+    }
+}
+
+/// 交易中的一条顶层指令
+#[derive(Clone, Copy, Debug)]
+pub struct Instruction<'a> {
+    accounts: &'a [u8],
+    program_id: &'a [u8; 32],
+    data: &'a [u8],
+}
+
+impl<'a> Instruction<'a> {
+    fn parse(sysvar: &'a [u8], offset: usize) -> Option<Self> {
+        let num_accounts = read_u16(sysvar, offset)? as usize;
+        let accounts_start = offset + 2;
+        let accounts_end = accounts_start.checked_add(num_accounts * ACCOUNT_META_LEN)?;
+        let accounts = sysvar.get(accounts_start..accounts_end)?;
+        let program_id = read_address(sysvar, accounts_end)?;
+        let data_len = read_u16(sysvar, accounts_end + 32)? as usize;
+        let data_start = accounts_end + 32 + 2;
+        let data = sysvar.get(data_start..data_start.checked_add(data_len)?)?;
+        Some(Self {
+            accounts,
+            program_id,
+            data,
+        })
+    }
+
+    pub fn program_id(&self) -> &'a [u8; 32] {
+        self.program_id
+    }
+
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn num_accounts(&self) -> usize {
+        self.accounts.len() / ACCOUNT_META_LEN
+    }
+
+    /// 第 `index` 个账户
+    pub fn account(&self, index: usize) -> Option<AccountMeta<'a>> {
+        let start = index.checked_mul(ACCOUNT_META_LEN)?;
+        let meta = self.accounts.get(start..start + ACCOUNT_META_LEN)?;
+        Some(AccountMeta {
+            key: read_address(meta, 1)?,
+            is_signer: meta[0] & IS_SIGNER != 0,
+            is_writable: meta[0] & IS_WRITABLE != 0,
+        })
+    }
+}
+
+/// 指令中的一个账户
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AccountMeta<'a> {
+    pub key: &'a [u8; 32],
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// 当前的调用栈高度：顶层指令为 [`TRANSACTION_LEVEL_STACK_HEIGHT`]，每层 CPI 加一。
+/// 链下构建没有该系统调用，恒为顶层
+#[cfg(target_os = "solana")]
+pub fn stack_height() -> u64 {
+    unsafe { solana_define_syscall::definitions::sol_get_stack_height() }
+}
+
+#[cfg(not(target_os = "solana"))]
+pub fn stack_height() -> u64 {
+    TRANSACTION_LEVEL_STACK_HEIGHT
+}
+
+/// 当前指令是否经由 CPI 调用。与 [`Instructions::is_top_level`] 不同，
+/// 本程序自己 CPI 调用自己时同样返回 `true`
+pub fn is_cpi() -> bool {
+    stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::*;
+
+    /// 测试用的指令：(程序 ID, [(地址, 签名, 可写)], 数据)
+    pub(crate) type Ix<'a> = ([u8; 32], &'a [([u8; 32], bool, bool)], &'a [u8]);
+
+    /// 按运行时的格式写出 Instructions sysvar 的数据
+    pub(crate) fn sysvar(instructions: &[Ix<'_>], current: u16) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(instructions.len() as u16).to_le_bytes());
+        let table = data.len();
+        data.resize(table + instructions.len() * 2, 0);
+        for (index, (program_id, accounts, ix_data)) in instructions.iter().enumerate() {
+            let offset = data.len() as u16;
+            data[table + index * 2..table + index * 2 + 2].copy_from_slice(&offset.to_le_bytes());
+            data.extend_from_slice(&(accounts.len() as u16).to_le_bytes());
+            for (key, is_signer, is_writable) in accounts.iter() {
+                let mut flags = 0;
+                if *is_signer {
+                    flags |= IS_SIGNER;
+                }
+                if *is_writable {
+                    flags |= IS_WRITABLE;
+                }
+                data.push(flags);
+                data.extend_from_slice(key);
+            }
+            data.extend_from_slice(program_id);
+            data.extend_from_slice(&(ix_data.len() as u16).to_le_bytes());
+            data.extend_from_slice(ix_data);
+        }
+        data.extend_from_slice(&current.to_le_bytes());
+        data
+    }
+
+    const AMM: [u8; 32] = [0xaa; 32];
+    const ROUTER: [u8; 32] = [0xbb; 32];
+    const BORROW: u8 = 20;
+    const REPAY: u8 = 21;
+
+    #[test]
+    fn sysvar_id_matches_base58() {
+        assert_eq!(
+            solana_pubkey::Pubkey::from_str_const("Sysvar1nstructions1111111111111111111111111")
+                .to_bytes(),
+            INSTRUCTIONS_SYSVAR_ID
+        );
+    }
+
+    #[test]
+    fn reads_accounts_and_data_of_each_instruction() {
+        let user = [1; 32];
+        let pool = [2; 32];
+        let accounts = [(user, true, true), (pool, false, true)];
+        let data = sysvar(&[(AMM, &accounts, &[BORROW, 7]), (ROUTER, &[], &[])], 0);
+        let instructions = Instructions::new(&data).unwrap();
+
+        assert_eq!((instructions.len(), instructions.current_index()), (2, 0));
+        let borrow = instructions.current().unwrap();
+        assert_eq!(borrow.program_id(), &AMM);
+        assert_eq!(borrow.data(), &[BORROW, 7]);
+        assert_eq!(borrow.num_accounts(), 2);
+        assert_eq!(
+            borrow.account(0),
+            Some(AccountMeta {
+                key: &user,
+                is_signer: true,
+                is_writable: true,
+            })
+        );
+        assert!(!borrow.account(1).unwrap().is_signer);
+        assert_eq!(borrow.account(2), None);
+        assert_eq!(
+            instructions.get(2).err(),
+            Some(IntrospectionError::IndexOutOfRange)
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let data = sysvar(&[(AMM, &[([1; 32], true, false)], &[BORROW])], 0);
+        assert_eq!(
+            Instructions::new(&data[..3]).err(),
+            Some(IntrospectionError::InvalidSysvar)
+        );
+        // 当前序号超出指令数
+        let mut bad_current = data.clone();
+        let last = bad_current.len() - 2;
+        bad_current[last] = 1;
+        assert!(Instructions::new(&bad_current).is_err());
+        // 偏移表完好但指令声明的账户数超出数据：构造成功，读取该指令时报错
+        let mut truncated = data.clone();
+        truncated[4] = 9;
+        let instructions = Instructions::new(&truncated).unwrap();
+        assert_eq!(
+            instructions.get(0).err(),
+            Some(IntrospectionError::InvalidSysvar)
+        );
+    }
+
+    #[test]
+    fn flash_loan_requires_a_later_repay() {
+        let pool = [(AMM, false, true)];
+        // 借出 -> 路由 -> 归还
+        let data = sysvar(
+            &[
+                (AMM, &pool, &[BORROW]),
+                (ROUTER, &[], &[REPAY]),
+                (AMM, &pool, &[REPAY, 1]),
+            ],
+            0,
+        );
+        let instructions = Instructions::new(&data).unwrap();
+        let (index, repay) = instructions.find_following_call(&AMM, &[REPAY]).unwrap();
+        assert_eq!(index, 2);
+        assert_eq!(repay.account(0).unwrap().key, &AMM);
+
+        // 归还排在借出之前不算
+        let data = sysvar(&[(AMM, &pool, &[REPAY]), (AMM, &pool, &[BORROW])], 1);
+        let instructions = Instructions::new(&data).unwrap();
+        assert_eq!(
+            instructions.find_following_call(&AMM, &[REPAY]).err(),
+            Some(IntrospectionError::NotFound)
+        );
+    }
+
+    #[test]
+    fn top_level_program_reveals_cpi() {
+        let data = sysvar(&[(ROUTER, &[], &[1]), (AMM, &[], &[2])], 0);
+        let instructions = Instructions::new(&data).unwrap();
+        // 执行顶层的路由指令时，AMM 只能是被 CPI 调用的
+        assert!(!instructions.is_top_level(&AMM).unwrap());
+        assert!(instructions.is_top_level(&ROUTER).unwrap());
+        assert!(!is_cpi());
+    }
+}
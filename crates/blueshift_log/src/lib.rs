@@ -0,0 +1,169 @@
+//! 训练营程序统一的文本日志。
+//!
+//! 每行日志都带程序标识、版本与指令名，格式固定为
+//! `[escrow v0.1.0] make: amount=10 receive=20`，在浏览器或 `solana logs` 里一眼就能看出
+//! 是哪个程序的哪个版本、哪条指令打印的。
+//!
+//! 程序在 crate 根调用一次 [`program!`] 声明标识，版本取自自己的 Cargo.toml（与 `get_version`
+//! 返回的一致），之后在任意位置用 [`bslog!`] 打印。格式化写入栈上的 [`Line`]，不需要堆分配，
+//! no_std 的 Pinocchio 程序同样可用；超过 [`MAX_LEN`] 的部分截断，并以 `…` 结尾。
+//!
+//! 程序打开 `quiet` feature（转发到 `blueshift_log/quiet`）后，`bslog!` 展开为不会执行的代码：
+//! 参数仍做类型检查，但不会求值，格式化代码也不会编进 .so。
+//!
+//! ```
+//! blueshift_log::program!("escrow");
+//!
+//! fn main() {
+//!     let amount = 10u64;
+//!     blueshift_log::bslog!("make", "amount={amount}");
+//!     blueshift_log::bslog!("refund");
+//! }
+//! ```
+
+#![no_std]
+
+use core::fmt::{self, Write};
+
+/// 单行日志的最大字节数（含截断标记）
+pub const MAX_LEN: usize = 256;
+
+/// 截断标记
+const ELLIPSIS: &str = "…";
+
+/// 声明本程序的日志标识，在 crate 根调用一次，生成 `bslog!` 使用的 `BSLOG_PREFIX`
+#[macro_export]
+macro_rules! program {
+    ($tag:literal) => {
+        #[doc(hidden)]
+        pub(crate) const BSLOG_PREFIX: &str = concat!($tag, " v", env!("CARGO_PKG_VERSION"));
+    };
+}
+
+/// 打印一行 `[程序 v版本] 指令: 内容`；只给指令名时省略冒号与内容
+///
+/// `crate::BSLOG_PREFIX` 有意指向调用方 crate 根由 [`program!`] 生成的常量。
+#[cfg(not(feature = "quiet"))]
+#[allow(clippy::crate_in_macro_def)]
+#[macro_export]
+macro_rules! bslog {
+    ($instruction:expr) => {
+        $crate::log(crate::BSLOG_PREFIX, $instruction, format_args!(""))
+    };
+    ($instruction:expr, $($arg:tt)+) => {
+        $crate::log(crate::BSLOG_PREFIX, $instruction, format_args!($($arg)+))
+    };
+}
+
+/// `quiet`：不打印，也不求值参数
+#[cfg(feature = "quiet")]
+#[allow(clippy::crate_in_macro_def)]
+#[macro_export]
+macro_rules! bslog {
+    ($instruction:expr $(, $($arg:tt)+)?) => {
+        if false {
+            let _ = (crate::BSLOG_PREFIX, $instruction $(, format_args!($($arg)+))?);
+        }
+    };
+}
+
+/// 格式化并写入程序日志；链下构建没有该系统调用，什么也不做
+#[inline(never)]
+pub fn log(prefix: &str, instruction: &str, args: fmt::Arguments) {
+    sol_log(Line::new(prefix, instruction, args).as_str());
+}
+
+#[cfg(target_os = "solana")]
+fn sol_log(message: &str) {
+    unsafe { solana_define_syscall::definitions::sol_log_(message.as_ptr(), message.len() as u64) };
+}
+
+#[cfg(not(target_os = "solana"))]
+fn sol_log(_message: &str) {}
+
+/// 栈上格式化好的一行日志
+pub struct Line {
+    buf: [u8; MAX_LEN],
+    len: usize,
+    truncated: bool,
+}
+
+impl Line {
+    pub fn new(prefix: &str, instruction: &str, args: fmt::Arguments) -> Self {
+        let mut line = Self {
+            buf: [0; MAX_LEN],
+            len: 0,
+            truncated: false,
+        };
+        // 截断不算错误，write_str 总是返回 Ok
+        let _ = write!(line, "[{prefix}] {instruction}");
+        if args.as_str() != Some("") {
+            let _ = write!(line, ": {args}");
+        }
+        if line.truncated {
+            line.push(ELLIPSIS);
+        }
+        line
+    }
+
+    pub fn as_str(&self) -> &str {
+        // 只按完整字符写入，内容始终是合法的 UTF-8
+        let bytes = self.buf.get(..self.len).unwrap_or_default();
+        core::str::from_utf8(bytes).unwrap_or_default()
+    }
+
+    /// 是否因为超长被截断
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    fn push(&mut self, s: &str) {
+        for (dst, src) in self.buf.iter_mut().skip(self.len).zip(s.as_bytes()) {
+            *dst = *src;
+        }
+        self.len = (self.len + s.len()).min(MAX_LEN);
+    }
+}
+
+impl Write for Line {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.truncated {
+            return Ok(());
+        }
+        // 给截断标记预留位置，超出时退到字符边界
+        let room = MAX_LEN - ELLIPSIS.len() - self.len;
+        let mut take = s.len().min(room);
+        if take < s.len() {
+            while !s.is_char_boundary(take) {
+                take -= 1;
+            }
+            self.truncated = true;
+        }
+        self.push(s.get(..take).unwrap_or_default());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_prefix_instruction_and_message() {
+        let amount = 10;
+        let line = Line::new("escrow v0.1.0", "make", format_args!("amount={amount}"));
+        assert_eq!(line.as_str(), "[escrow v0.1.0] make: amount=10");
+
+        let line = Line::new("escrow v0.1.0", "refund", format_args!(""));
+        assert_eq!(line.as_str(), "[escrow v0.1.0] refund");
+    }
+
+    #[test]
+    fn truncates_on_char_boundary() {
+        let long = "金库".repeat(100);
+        let line = Line::new("vault v0.1.0", "deposit", format_args!("{long}"));
+        assert!(line.is_truncated());
+        assert!(line.as_str().len() <= MAX_LEN);
+        assert!(line.as_str().ends_with("库…") || line.as_str().ends_with("金…"));
+    }
+}
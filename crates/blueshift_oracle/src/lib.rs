@@ -0,0 +1,301 @@
+//! 价格账户的布局与读取价格时的校验。
+//!
+//! 价格按 Pyth 的方式表示：`price × 10^expo`，`conf` 是同一精度下的置信区间半宽，
+//! `publish_time` 是发布时的 Unix 时间戳（秒）。需要价格的程序只读 [`PriceFeed`]，
+//! 读出 [`Price`] 后按自己的参数检查：
+//!
+//! - [`Price::no_older_than`]：距当前时间超过 `max_age` 秒的价格视为过期；
+//! - [`Price::check_confidence`]：置信区间相对价格过宽时拒绝；
+//! - [`Price::check_deviation`]：池子 / 报价与预言机价格偏离过大时拒绝。
+//!
+//! 链上的 [`PriceFeed`] 账户由模拟预言机（`programs/blueshift_mock_oracle`）创建，任何时候都可以
+//! 由其 authority 改写价格，测试里不需要 mainnet 的 Pyth 账户就能构造过期或偏离的价格。
+//! 托管与 AMM 今后接入价格时读取同样的布局；错误由各程序换成自己的 `ProgramError`。
+//!
+//! ```
+//! use blueshift_oracle::{Price, PriceFeed};
+//!
+//! let mut data = [0u8; PriceFeed::LEN];
+//! let feed = PriceFeed::init(&mut data, [1; 32], [2; 32], 255).unwrap();
+//! feed.set(&Price { price: 150_000_000, conf: 50_000, expo: -6, publish_time: 1_000 });
+//!
+//! let price = PriceFeed::load(&data).unwrap().price();
+//! assert!(price.no_older_than(1_030, 60).is_ok());
+//! assert!(price.no_older_than(1_061, 60).is_err());
+//! assert!(price.check_deviation(151_000_000, 100).is_ok());
+//! ```
+
+#![no_std]
+
+use core::fmt;
+
+use blueshift_zerocopy::{layout, Pod, Zeroable};
+
+/// 基点的分母
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+/// 读取或校验价格失败
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OracleError {
+    /// 账户长度不符或首字节不是 [`PriceFeed::TAG`]
+    InvalidAccount,
+    /// 价格不是正数
+    NotPositive,
+    /// 价格发布时间早于允许的最大时长
+    Stale,
+    /// 置信区间相对价格过宽
+    ConfidenceTooWide,
+    /// 观测价格与预言机价格偏离过大
+    Deviation,
+}
+
+impl fmt::Display for OracleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InvalidAccount => "不是价格账户",
+            Self::NotPositive => "价格不是正数",
+            Self::Stale => "价格已过期",
+            Self::ConfidenceTooWide => "价格置信区间过宽",
+            Self::Deviation => "价格偏离预言机过大",
+        })
+    }
+}
+
+/// 一次报价：`price × 10^expo`，置信区间 `± conf × 10^expo`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Price {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    /// Unix 时间戳（秒）
+    pub publish_time: i64,
+}
+
+impl Price {
+    /// 指令数据中的长度：price (i64) + conf (u64) + expo (i32) + publish_time (i64)，均为小端
+    pub const LEN: usize = 28;
+
+    pub fn encode(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[..8].copy_from_slice(&self.price.to_le_bytes());
+        data[8..16].copy_from_slice(&self.conf.to_le_bytes());
+        data[16..20].copy_from_slice(&self.expo.to_le_bytes());
+        data[20..].copy_from_slice(&self.publish_time.to_le_bytes());
+        data
+    }
+
+    /// 数据不足 [`Price::LEN`] 字节时返回 `None`，多出的部分忽略
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        Some(Self {
+            price: i64::from_le_bytes(data.get(..8)?.try_into().ok()?),
+            conf: u64::from_le_bytes(data.get(8..16)?.try_into().ok()?),
+            expo: i32::from_le_bytes(data.get(16..20)?.try_into().ok()?),
+            publish_time: i64::from_le_bytes(data.get(20..28)?.try_into().ok()?),
+        })
+    }
+
+    /// 价格为正且 `now - publish_time <= max_age` 时返回自身。
+    /// 发布时间晚于 `now`（时钟偏差）不算过期
+    pub fn no_older_than(&self, now: i64, max_age: u64) -> Result<&Self, OracleError> {
+        if self.price <= 0 {
+            return Err(OracleError::NotPositive);
+        }
+        let age = now.saturating_sub(self.publish_time);
+        if age > 0 && age as u64 > max_age {
+            return Err(OracleError::Stale);
+        }
+        Ok(self)
+    }
+
+    /// `conf / price <= max_bps / 10000`
+    pub fn check_confidence(&self, max_bps: u16) -> Result<&Self, OracleError> {
+        let price = self.positive()?;
+        if u128::from(self.conf) * BPS_DENOMINATOR > price * u128::from(max_bps) {
+            return Err(OracleError::ConfidenceTooWide);
+        }
+        Ok(self)
+    }
+
+    /// `|observed - price| / price <= max_bps / 10000`；`observed` 与本价格使用同一 `expo`
+    pub fn check_deviation(&self, observed: u64, max_bps: u16) -> Result<&Self, OracleError> {
+        let price = self.positive()?;
+        let diff = price.abs_diff(u128::from(observed));
+        if diff * BPS_DENOMINATOR > price * u128::from(max_bps) {
+            return Err(OracleError::Deviation);
+        }
+        Ok(self)
+    }
+
+    fn positive(&self) -> Result<u128, OracleError> {
+        u64::try_from(self.price)
+            .ok()
+            .filter(|price| *price > 0)
+            .map(u128::from)
+            .ok_or(OracleError::NotPositive)
+    }
+}
+
+/// 价格账户：PDA `["price_feed", feed_id]`（见 `pdas::price_feed_pda`），由 `authority` 改写
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[bytemuck(crate = "blueshift_zerocopy::bytemuck")]
+pub struct PriceFeed {
+    tag: u8,
+    bump: u8,
+    authority: [u8; 32],
+    /// 与 Pyth 相同的 32 字节 feed ID
+    feed_id: [u8; 32],
+    price: [u8; 8],
+    conf: [u8; 8],
+    expo: [u8; 4],
+    publish_time: [u8; 8],
+}
+
+layout!(PriceFeed = 94 {
+    TAG_OFFSET: tag = 0,
+    BUMP_OFFSET: bump = 1,
+    AUTHORITY_OFFSET: authority = 2,
+    FEED_ID_OFFSET: feed_id = 34,
+    PRICE_OFFSET: price = 66,
+    CONF_OFFSET: conf = 74,
+    EXPO_OFFSET: expo = 82,
+    PUBLISH_TIME_OFFSET: publish_time = 86,
+});
+
+impl PriceFeed {
+    pub const LEN: usize = core::mem::size_of::<Self>();
+    pub const TAG: u8 = 0x0f;
+
+    pub fn load(data: &[u8]) -> Result<&Self, OracleError> {
+        blueshift_zerocopy::load::<Self>(data)
+            .ok()
+            .filter(|feed| feed.tag == Self::TAG)
+            .ok_or(OracleError::InvalidAccount)
+    }
+
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, OracleError> {
+        blueshift_zerocopy::load_mut::<Self>(data)
+            .ok()
+            .filter(|feed| feed.tag == Self::TAG)
+            .ok_or(OracleError::InvalidAccount)
+    }
+
+    /// 写入新建（全零）的账户；已经初始化过的账户返回错误
+    pub fn init(
+        data: &mut [u8],
+        authority: [u8; 32],
+        feed_id: [u8; 32],
+        bump: u8,
+    ) -> Result<&mut Self, OracleError> {
+        let feed = blueshift_zerocopy::load_mut::<Self>(data)
+            .ok()
+            .filter(|feed| feed.tag == 0)
+            .ok_or(OracleError::InvalidAccount)?;
+        feed.tag = Self::TAG;
+        feed.bump = bump;
+        feed.authority = authority;
+        feed.feed_id = feed_id;
+        Ok(feed)
+    }
+
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn authority(&self) -> &[u8; 32] {
+        &self.authority
+    }
+
+    pub fn feed_id(&self) -> &[u8; 32] {
+        &self.feed_id
+    }
+
+    /// 账户中的最新报价，未经任何校验
+    pub fn price(&self) -> Price {
+        Price {
+            price: i64::from_le_bytes(self.price),
+            conf: u64::from_le_bytes(self.conf),
+            expo: i32::from_le_bytes(self.expo),
+            publish_time: i64::from_le_bytes(self.publish_time),
+        }
+    }
+
+    pub fn set(&mut self, price: &Price) {
+        self.price = price.price.to_le_bytes();
+        self.conf = price.conf.to_le_bytes();
+        self.expo = price.expo.to_le_bytes();
+        self.publish_time = price.publish_time.to_le_bytes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRICE: Price = Price {
+        price: 2_000_000,
+        conf: 1_000,
+        expo: -4,
+        publish_time: 500,
+    };
+
+    #[test]
+    fn feed_round_trips_and_rejects_foreign_data() {
+        let mut data = [0u8; PriceFeed::LEN];
+        assert_eq!(
+            PriceFeed::load(&data).err(),
+            Some(OracleError::InvalidAccount)
+        );
+        PriceFeed::init(&mut data, [3; 32], [4; 32], 250)
+            .unwrap()
+            .set(&PRICE);
+        let feed = PriceFeed::load(&data).unwrap();
+        assert_eq!(feed.price(), PRICE);
+        assert_eq!(feed.authority(), &[3; 32]);
+        assert_eq!((feed.feed_id(), feed.bump()), (&[4; 32], 250));
+        assert_eq!(
+            data[PriceFeed::PRICE_OFFSET..PriceFeed::PRICE_OFFSET + 8],
+            PRICE.price.to_le_bytes()
+        );
+
+        // 不能重复初始化，长度不符的数据不是价格账户
+        assert!(PriceFeed::init(&mut data, [5; 32], [4; 32], 250).is_err());
+        assert!(PriceFeed::load(&data[..PriceFeed::LEN - 1]).is_err());
+        assert_eq!(Price::decode(&PRICE.encode()), Some(PRICE));
+        assert_eq!(Price::decode(&PRICE.encode()[..Price::LEN - 1]), None);
+    }
+
+    #[test]
+    fn staleness_uses_publish_time() {
+        assert!(PRICE.no_older_than(560, 60).is_ok());
+        assert_eq!(PRICE.no_older_than(561, 60), Err(OracleError::Stale));
+        // 发布时间在未来（验证者时钟偏差）不算过期
+        assert!(PRICE.no_older_than(400, 0).is_ok());
+
+        let negative = Price { price: -1, ..PRICE };
+        assert_eq!(
+            negative.no_older_than(500, 60),
+            Err(OracleError::NotPositive)
+        );
+    }
+
+    #[test]
+    fn confidence_and_deviation_are_in_bps() {
+        // conf / price = 5 bps
+        assert!(PRICE.check_confidence(5).is_ok());
+        assert_eq!(
+            PRICE.check_confidence(4),
+            Err(OracleError::ConfidenceTooWide)
+        );
+
+        // 偏离 1% = 100 bps，两个方向对称
+        assert!(PRICE.check_deviation(2_020_000, 100).is_ok());
+        assert!(PRICE.check_deviation(1_980_000, 100).is_ok());
+        assert_eq!(
+            PRICE.check_deviation(2_020_001, 100),
+            Err(OracleError::Deviation)
+        );
+        let zero = Price { price: 0, ..PRICE };
+        assert_eq!(zero.check_deviation(0, 100), Err(OracleError::NotPositive));
+    }
+}
@@ -0,0 +1,319 @@
+//! 各程序统一的 `get_version` 指令与功能开关账户（feature gate）。
+//!
+//! **get_version**：指令数据为 [`GET_VERSION_DISCRIMINATOR`]，即 Anchor 指令 `get_version`
+//! 的判别器 `sha256("global:get_version")[..8]`，Anchor 程序与原生程序用同一段数据调用。
+//! 账户可选：传入本程序的功能开关账户时一并返回其中已启用的功能。结果写入 return data，
+//! 客户端模拟交易即可读取，格式见 [`Version`]。
+//!
+//! **功能开关账户**：原生程序的 PDA `["feature_gate"]`（`pdas::feature_gate_pda`），布局见
+//! [`FeatureGate`]，由程序的升级权限通过 `set_features`（[`SET_FEATURES_DISCRIMINATOR`]）
+//! 创建与修改。需要分阶段上线的新代码路径先检查对应的功能位，
+//! 程序升级后可以先部署、再按集群逐步打开。账户不存在时所有功能视为关闭。
+//!
+//! 功能位按程序划分在 [`features`] 中。`supported` 是当前构建实现了哪些功能，
+//! `enabled` 是开关账户中打开了哪些，二者都置位才算可用（[`Version::is_enabled`]）。
+//!
+//! ```
+//! use blueshift_version::{features, Version};
+//!
+//! let version = Version::new(0, 1, 0, features::escrow::TOKEN_2022);
+//! let returned = version.with_enabled(features::escrow::TOKEN_2022).encode();
+//! let decoded = Version::decode(&returned).unwrap();
+//! assert!(decoded.is_enabled(features::escrow::TOKEN_2022));
+//! assert!(!decoded.is_enabled(features::escrow::PARTIAL_FILL));
+//! ```
+
+#![no_std]
+
+use core::fmt;
+
+use blueshift_zerocopy::{layout, Pod, Zeroable};
+
+/// `sha256("global:get_version")[..8]`
+pub const GET_VERSION_DISCRIMINATOR: [u8; 8] = [0xa8, 0x55, 0xf4, 0x2d, 0x51, 0x38, 0x82, 0x32];
+
+/// `sha256("global:set_features")[..8]`，后接 8 字节小端的功能位
+pub const SET_FEATURES_DISCRIMINATOR: [u8; 8] = [0x9c, 0xef, 0x5a, 0xec, 0x02, 0x2d, 0x29, 0xcb];
+
+/// 各程序的功能位
+pub mod features {
+    /// 托管（Pinocchio）
+    pub mod escrow {
+        /// 代币可以是 Token-2022 的 Mint
+        pub const TOKEN_2022: u64 = 1 << 0;
+        /// 部分成交（TakePartial）；指令本身不检查开关，这一位只用于在 `supported` 中声明支持
+        pub const PARTIAL_FILL: u64 = 1 << 1;
+        /// 当前构建实现的功能
+        pub const SUPPORTED: u64 = TOKEN_2022 | PARTIAL_FILL;
+    }
+
+    /// 原生 AMM
+    pub mod amm {
+        /// 多资产池（InitializeMulti / DepositMulti / WithdrawMulti / SwapMulti）
+        pub const MULTI_ASSET: u64 = 1 << 0;
+        /// 集中流动性池（Clmm*）
+        pub const CLMM: u64 = 1 << 1;
+        /// 一笔指令内多跳兑换（SwapBatch）
+        pub const SWAP_BATCH: u64 = 1 << 2;
+        /// 参数变更经过 timelock（ProposeUpdate / ExecuteUpdate）
+        pub const TIMELOCK: u64 = 1 << 3;
+        /// 当前构建实现的功能
+        pub const SUPPORTED: u64 = MULTI_ASSET | CLMM | SWAP_BATCH | TIMELOCK;
+    }
+}
+
+/// `get_version` 的返回值。return data 为小端编码：
+///
+/// | 偏移 | 长度 | 字段        |
+/// |------|------|-------------|
+/// | 0    | 2    | `major`     |
+/// | 2    | 2    | `minor`     |
+/// | 4    | 2    | `patch`     |
+/// | 6    | 8    | `supported` |
+/// | 14   | 8    | `enabled`   |
+///
+/// 以后只在末尾追加字段，[`Version::decode`] 忽略多出的字节。
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Version {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+    /// 当前构建实现的功能
+    pub supported: u64,
+    /// 功能开关账户中打开的功能；调用时没有传入开关账户或账户不存在时为 0
+    pub enabled: u64,
+}
+
+impl Version {
+    pub const LEN: usize = 22;
+
+    pub const fn new(major: u16, minor: u16, patch: u16, supported: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            supported,
+            enabled: 0,
+        }
+    }
+
+    pub const fn with_enabled(self, enabled: u64) -> Self {
+        Self { enabled, ..self }
+    }
+
+    pub fn encode(&self) -> [u8; Self::LEN] {
+        let mut out = [0u8; Self::LEN];
+        out[0..2].copy_from_slice(&self.major.to_le_bytes());
+        out[2..4].copy_from_slice(&self.minor.to_le_bytes());
+        out[4..6].copy_from_slice(&self.patch.to_le_bytes());
+        out[6..14].copy_from_slice(&self.supported.to_le_bytes());
+        out[14..22].copy_from_slice(&self.enabled.to_le_bytes());
+        out
+    }
+
+    /// 不足 [`Version::LEN`] 字节时返回 `None`
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let data: &[u8; Self::LEN] = data.get(..Self::LEN)?.try_into().ok()?;
+        let u16_at = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+        let u64_at = |at: usize| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&data[at..at + 8]);
+            u64::from_le_bytes(bytes)
+        };
+        Some(Self {
+            major: u16_at(0),
+            minor: u16_at(2),
+            patch: u16_at(4),
+            supported: u64_at(6),
+            enabled: u64_at(14),
+        })
+    }
+
+    /// `feature` 中的每一位都已实现且已打开
+    pub const fn is_enabled(&self, feature: u64) -> bool {
+        self.supported & self.enabled & feature == feature
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}（支持 {:#x}，已启用 {:#x}）",
+            self.major, self.minor, self.patch, self.supported, self.enabled
+        )
+    }
+}
+
+/// 编译期解析 `CARGO_PKG_VERSION_*`，供 [`version!`] 使用
+#[doc(hidden)]
+pub const fn parse_u16(digits: &str) -> u16 {
+    let bytes = digits.as_bytes();
+    let mut value: u16 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(bytes[i].is_ascii_digit(), "版本号只能包含数字");
+        value = value * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+    value
+}
+
+/// 调用方 crate 的版本号（`Cargo.toml` 中的 `version`）加上 `supported` 功能位：
+///
+/// ```
+/// const VERSION: blueshift_version::Version = blueshift_version::version!(0);
+/// assert_eq!(VERSION.major, 0);
+/// ```
+#[macro_export]
+macro_rules! version {
+    ($supported:expr) => {
+        $crate::Version::new(
+            $crate::parse_u16(env!("CARGO_PKG_VERSION_MAJOR")),
+            $crate::parse_u16(env!("CARGO_PKG_VERSION_MINOR")),
+            $crate::parse_u16(env!("CARGO_PKG_VERSION_PATCH")),
+            $supported,
+        )
+    };
+}
+
+/// 功能开关账户。与程序的其他账户靠长度和首字节 [`FeatureGate::TAG`] 区分
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[bytemuck(crate = "blueshift_zerocopy::bytemuck")]
+pub struct FeatureGate {
+    tag: u8,
+    flags: [u8; 8],
+    bump: u8,
+}
+
+layout!(FeatureGate = 10 {
+    TAG_OFFSET: tag = 0,
+    FLAGS_OFFSET: flags = 1,
+    BUMP_OFFSET: bump = 9,
+});
+
+impl FeatureGate {
+    pub const LEN: usize = core::mem::size_of::<Self>();
+    pub const TAG: u8 = 0xfe;
+
+    /// 长度不符或首字节不是 [`FeatureGate::TAG`] 时返回 `None`
+    pub fn load(data: &[u8]) -> Option<&Self> {
+        blueshift_zerocopy::load::<Self>(data)
+            .ok()
+            .filter(|gate| gate.tag == Self::TAG)
+    }
+
+    /// 写入新账户（全零）或已有的开关账户；其他数据返回 `None`
+    pub fn init_or_load_mut(data: &mut [u8], bump: u8) -> Option<&mut Self> {
+        let gate = blueshift_zerocopy::load_mut::<Self>(data).ok()?;
+        match gate.tag {
+            0 => {
+                gate.tag = Self::TAG;
+                gate.bump = bump;
+            }
+            Self::TAG if gate.bump == bump => {}
+            _ => return None,
+        }
+        Some(gate)
+    }
+
+    pub fn flags(&self) -> u64 {
+        u64::from_le_bytes(self.flags)
+    }
+
+    pub fn set_flags(&mut self, flags: u64) {
+        self.flags = flags.to_le_bytes();
+    }
+
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+/// ProgramData 账户中记录的升级权限；程序已不可升级（权限为空）或数据不是 ProgramData 时返回 `None`。
+///
+/// 布局：`u32` 枚举序号（3 = ProgramData）+ `u64` 部署 slot + `Option<Pubkey>`（1 字节标志 + 32 字节）
+pub fn upgrade_authority(program_data: &[u8]) -> Option<[u8; 32]> {
+    const PROGRAM_DATA: [u8; 4] = 3u32.to_le_bytes();
+    if program_data.get(..4)? != PROGRAM_DATA || *program_data.get(12)? != 1 {
+        return None;
+    }
+    program_data.get(13..45)?.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use std::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn version_round_trips_and_tolerates_appended_fields() {
+        let version =
+            Version::new(1, 2, 3, features::amm::SUPPORTED).with_enabled(features::amm::CLMM);
+        let mut data = [0xffu8; Version::LEN + 4];
+        data[..Version::LEN].copy_from_slice(&version.encode());
+        assert_eq!(Version::decode(&data), Some(version));
+        assert_eq!(Version::decode(&data[..Version::LEN - 1]), None);
+
+        assert!(version.is_enabled(features::amm::CLMM));
+        assert!(!version.is_enabled(features::amm::CLMM | features::amm::SWAP_BATCH));
+        // 开关打开但当前构建没有实现，仍不可用
+        let staged = Version::new(1, 2, 3, 0).with_enabled(features::escrow::PARTIAL_FILL);
+        assert!(!staged.is_enabled(features::escrow::PARTIAL_FILL));
+        assert_eq!(version.to_string(), "1.2.3（支持 0xf，已启用 0x2）");
+    }
+
+    #[test]
+    fn version_macro_reads_package_version() {
+        const VERSION: Version = version!(features::escrow::SUPPORTED);
+        assert_eq!(
+            (VERSION.major, VERSION.minor, VERSION.patch),
+            (
+                env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap(),
+                env!("CARGO_PKG_VERSION_MINOR").parse().unwrap(),
+                env!("CARGO_PKG_VERSION_PATCH").parse().unwrap(),
+            )
+        );
+        assert_eq!(parse_u16("105"), 105);
+    }
+
+    #[test]
+    fn feature_gate_only_accepts_its_own_data() {
+        let mut data = [0u8; FeatureGate::LEN];
+        assert!(FeatureGate::load(&data).is_none());
+
+        FeatureGate::init_or_load_mut(&mut data, 254)
+            .unwrap()
+            .set_flags(features::escrow::PARTIAL_FILL);
+        let gate = FeatureGate::load(&data).unwrap();
+        assert_eq!(gate.flags(), features::escrow::PARTIAL_FILL);
+        assert_eq!(gate.bump(), 254);
+        assert_eq!(data[FeatureGate::TAG_OFFSET], FeatureGate::TAG);
+
+        // bump 不符、首字节是别的数据、长度不符
+        assert!(FeatureGate::init_or_load_mut(&mut data, 253).is_none());
+        data[FeatureGate::TAG_OFFSET] = 1;
+        assert!(FeatureGate::init_or_load_mut(&mut data, 254).is_none());
+        assert!(FeatureGate::load(&[0u8; FeatureGate::LEN + 1]).is_none());
+    }
+
+    #[test]
+    fn reads_upgrade_authority_from_program_data() {
+        let mut data = [0u8; 45 + 16];
+        data[..4].copy_from_slice(&3u32.to_le_bytes());
+        data[4..12].copy_from_slice(&77u64.to_le_bytes());
+        data[12] = 1;
+        data[13..45].copy_from_slice(&[9; 32]);
+        assert_eq!(upgrade_authority(&data), Some([9; 32]));
+
+        data[12] = 0;
+        assert_eq!(upgrade_authority(&data), None);
+        data[12] = 1;
+        data[0] = 2;
+        assert_eq!(upgrade_authority(&data), None);
+        assert_eq!(upgrade_authority(&data[..20]), None);
+    }
+}
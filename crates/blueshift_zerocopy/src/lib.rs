@@ -0,0 +1,216 @@
+//! 状态账户的零拷贝读写。
+//!
+//! 账户数据直接按 `#[repr(C)]` 结构体解释，不做反序列化。为了让任意一段账户数据都能安全地
+//! 当作结构体引用，这里要求：
+//!
+//! - 结构体实现 [`Pod`]（`#[derive(Pod, Zeroable)]`）：字段都是纯数据、没有填充字节；
+//! - 对齐为 1：多字节整数存成 `[u8; N]`，通过 getter 按小端读写，
+//!   这样账户数据从任何偏移开始都能直接借用，不依赖运行时的 8 字节对齐；
+//! - 长度与各字段偏移用 [`layout!`] 在编译期核对，并生成 `*_OFFSET` 常量，
+//!   客户端与其他程序按偏移读取字段时用同一组数字。
+//!
+//! 加载函数只在长度不符时失败，不会 panic；错误由各程序换成自己的 `ProgramError`。
+//!
+//! ```
+//! use blueshift_zerocopy::{layout, Pod, Zeroable};
+//!
+//! #[repr(C)]
+//! #[derive(Clone, Copy, Pod, Zeroable)]
+//! #[bytemuck(crate = "blueshift_zerocopy::bytemuck")]
+//! struct Counter {
+//!     owner: [u8; 32],
+//!     count: [u8; 8],
+//! }
+//!
+//! layout!(Counter = 40 {
+//!     OWNER_OFFSET: owner = 0,
+//!     COUNT_OFFSET: count = 32,
+//! });
+//!
+//! let mut data = [0u8; 40];
+//! let counter = blueshift_zerocopy::load_mut::<Counter>(&mut data).unwrap();
+//! counter.count = 7u64.to_le_bytes();
+//! assert_eq!(blueshift_zerocopy::read::<[u8; 8]>(&data, Counter::COUNT_OFFSET), Ok(7u64.to_le_bytes()));
+//! ```
+
+#![no_std]
+
+use core::{fmt, mem::size_of};
+
+pub use bytemuck::{self, Pod, Zeroable};
+
+/// 账户数据无法按结构体解释
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LayoutError {
+    /// 长度不足或不等于结构体大小
+    Size { expected: usize, actual: usize },
+    /// 结构体对齐大于 1 且数据地址没有对齐（[`layout!`] 检查过的类型不会出现）
+    Misaligned,
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Size { expected, actual } => {
+                write!(f, "账户数据应为 {expected} 字节，实际 {actual}")
+            }
+            Self::Misaligned => f.write_str("账户数据未对齐"),
+        }
+    }
+}
+
+fn cast_error<T>(err: bytemuck::PodCastError, actual: usize) -> LayoutError {
+    match err {
+        bytemuck::PodCastError::SizeMismatch => LayoutError::Size {
+            expected: size_of::<T>(),
+            actual,
+        },
+        _ => LayoutError::Misaligned,
+    }
+}
+
+/// 把整段数据当作 `T` 借用，长度必须恰好是 `size_of::<T>()`
+#[inline(always)]
+pub fn load<T: Pod>(bytes: &[u8]) -> Result<&T, LayoutError> {
+    bytemuck::try_from_bytes(bytes).map_err(|err| cast_error::<T>(err, bytes.len()))
+}
+
+/// [`load`] 的可变版本
+#[inline(always)]
+pub fn load_mut<T: Pod>(bytes: &mut [u8]) -> Result<&mut T, LayoutError> {
+    let len = bytes.len();
+    bytemuck::try_from_bytes_mut(bytes).map_err(|err| cast_error::<T>(err, len))
+}
+
+/// 从 `offset` 起复制出一个 `T`，不要求对齐；越界时返回错误
+#[inline(always)]
+pub fn read<T: Pod>(bytes: &[u8], offset: usize) -> Result<T, LayoutError> {
+    let field = field(bytes, offset, size_of::<T>())?;
+    Ok(bytemuck::pod_read_unaligned(field))
+}
+
+/// 把 `value` 写到 `offset` 起的位置；越界时返回错误，数据不变
+#[inline(always)]
+pub fn write<T: Pod>(bytes: &mut [u8], offset: usize, value: &T) -> Result<(), LayoutError> {
+    let actual = bytes.len();
+    offset
+        .checked_add(size_of::<T>())
+        .and_then(|end| bytes.get_mut(offset..end))
+        .ok_or(LayoutError::Size {
+            expected: offset.saturating_add(size_of::<T>()),
+            actual,
+        })?
+        .copy_from_slice(bytemuck::bytes_of(value));
+    Ok(())
+}
+
+fn field(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8], LayoutError> {
+    offset
+        .checked_add(len)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or(LayoutError::Size {
+            expected: offset.saturating_add(len),
+            actual: bytes.len(),
+        })
+}
+
+/// 编译期核对结构体的大小、对齐（必须为 1）与各字段偏移，并生成偏移常量：
+///
+/// ```ignore
+/// layout!(Escrow = 113 {
+///     SEED_OFFSET: seed = 0,
+///     MAKER_OFFSET: maker = 8,
+///     ...
+/// });
+/// ```
+///
+/// 字段改了宽度或顺序而没有同步这里的数字时编译失败。
+#[macro_export]
+macro_rules! layout {
+    ($ty:ident = $size:literal { $($offset_name:ident : $field:ident = $offset:literal),* $(,)? }) => {
+        const _: () = {
+            assert!(
+                ::core::mem::size_of::<$ty>() == $size,
+                concat!(stringify!($ty), " 的大小不是 ", stringify!($size), " 字节")
+            );
+            assert!(
+                ::core::mem::align_of::<$ty>() == 1,
+                concat!(stringify!($ty), " 的对齐必须为 1，多字节整数请用 [u8; N] 存储")
+            );
+            $(
+                assert!(
+                    ::core::mem::offset_of!($ty, $field) == $offset,
+                    concat!(stringify!($ty), "::", stringify!($field), " 的偏移不是 ", stringify!($offset))
+                );
+            )*
+        };
+
+        impl $ty {
+            $(
+                #[doc = concat!("`", stringify!($field), "` 字段在账户数据中的偏移")]
+                pub const $offset_name: usize = $offset;
+            )*
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+    struct Sample {
+        tag: u8,
+        amount: [u8; 8],
+        owner: [u8; 32],
+    }
+
+    layout!(Sample = 41 {
+        TAG_OFFSET: tag = 0,
+        AMOUNT_OFFSET: amount = 1,
+        OWNER_OFFSET: owner = 9,
+    });
+
+    #[test]
+    fn load_requires_exact_length_but_not_alignment() {
+        let mut buffer = [0u8; 43];
+        // 从奇数偏移开始借用，对齐为 1 的结构体照样可以加载
+        let data = &mut buffer[1..42];
+        load_mut::<Sample>(data).unwrap().amount = 5u64.to_le_bytes();
+        assert_eq!(data[Sample::AMOUNT_OFFSET], 5);
+        assert_eq!(load::<Sample>(data).unwrap().tag, data[Sample::TAG_OFFSET]);
+
+        assert_eq!(
+            load::<Sample>(&buffer[..40]),
+            Err(LayoutError::Size {
+                expected: 41,
+                actual: 40
+            })
+        );
+        assert!(load_mut::<Sample>(&mut buffer).is_err());
+        // 对齐大于 1 的类型在未对齐地址上报告 Misaligned 而不是 panic
+        let words = [0u64; 2];
+        let bytes: &[u8] = bytemuck::bytes_of(&words);
+        assert_eq!(load::<u64>(&bytes[1..9]), Err(LayoutError::Misaligned));
+    }
+
+    #[test]
+    fn read_and_write_are_bounds_checked() {
+        let mut data = [0u8; 41];
+        write(&mut data, Sample::OWNER_OFFSET, &[7u8; 32]).unwrap();
+        assert_eq!(read::<[u8; 32]>(&data, Sample::OWNER_OFFSET), Ok([7; 32]));
+        assert_eq!(load::<Sample>(&data).unwrap().owner, [7; 32]);
+
+        assert_eq!(
+            read::<u64>(&data, 40),
+            Err(LayoutError::Size {
+                expected: 48,
+                actual: 41
+            })
+        );
+        assert!(read::<u64>(&data, usize::MAX).is_err());
+        assert!(write(&mut data, 38, &[1u8; 4]).is_err());
+        assert_eq!(data[38..], [7, 7, 7]);
+    }
+}
@@ -0,0 +1,335 @@
+//! 地址查找表（Address Lookup Table）：创建、扩展、停用与关闭指令，以及表账户的解析。
+//!
+//! escrow 的 Take、router 的复合指令各带 12 个以上账户，再加上计算预算指令与多个程序，
+//! legacy 交易很容易超过 1232 字节。把常用地址放进查找表后，v0 交易里每个地址只占 1 字节索引。
+//! 典型流程：
+//!
+//! 1. [`create_ix`] 以最近的 slot 创建表，地址由 authority 与 slot 派生；
+//! 2. [`extend_ixs`] 分批写入 [`common_addresses`] 以及池子、托管等自己常用的账户；
+//! 3. 等表被扩展的 slot 过去（新写入的地址下一个 slot 才能使用），
+//!    之后用 `tx::TxBuilder::lookup_tables` 发送 v0 交易。
+//!
+//! 交易顶层调用的程序必须出现在静态账户里，查找表只能替换指令的账户参数（包括作为参数传入、
+//! 供 CPI 调用的程序）；计算预算程序之类只在顶层出现的地址放进表里也用不上。
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+use solana_sdk_ids::{system_program, sysvar};
+
+use crate::{
+    token::{ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, TOKEN_PROGRAM_ID},
+    AccountDataError,
+};
+
+pub use solana_sdk_ids::address_lookup_table::ID as PROGRAM_ID;
+
+/// 一张表最多容纳的地址数
+pub const MAX_ADDRESSES: usize = 256;
+
+/// 一条 Extend 指令写入的地址数上限。一笔 1232 字节的交易在签名、账户与计算预算指令之后
+/// 大约还能放 20 多个地址，这里留出余量
+pub const MAX_ADDRESSES_PER_EXTEND: usize = 20;
+
+/// 表账户数据中地址之前的元数据长度：discriminator、停用 slot、最后扩展 slot 及其起始序号、
+/// Option<authority>，补齐到 56 字节
+pub const META_LEN: usize = 56;
+
+/// 表未停用时 `deactivation_slot` 的取值
+const ACTIVE: u64 = u64::MAX;
+
+/// 指令编号，bincode 枚举以 u32 小端编码
+const CREATE: u32 = 0;
+const FREEZE: u32 = 1;
+const EXTEND: u32 = 2;
+const DEACTIVATE: u32 = 3;
+const CLOSE: u32 = 4;
+
+/// 链上的查找表账户
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LookupTable {
+    /// 开始停用的 slot，未停用时为 `u64::MAX`
+    pub deactivation_slot: u64,
+    pub last_extended_slot: u64,
+    /// `last_extended_slot` 那次扩展写入的第一个地址的序号，这之后的地址要等下一个 slot 才能使用
+    pub last_extended_slot_start_index: u8,
+    /// 冻结后为 `None`，表不能再修改
+    pub authority: Option<Pubkey>,
+    pub addresses: Vec<Pubkey>,
+}
+
+impl LookupTable {
+    /// 解析表账户。元数据之后的长度必须是 32 的整数倍
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let error = |expected| AccountDataError {
+            account: "lookup_table",
+            expected,
+            actual: data.len(),
+        };
+        if data.len() < META_LEN || data[..4] != 1u32.to_le_bytes() {
+            return Err(error(META_LEN));
+        }
+        let (meta, addresses) = data.split_at(META_LEN);
+        if addresses.len() % 32 != 0 {
+            return Err(error(META_LEN + addresses.len() / 32 * 32));
+        }
+        let u64_at =
+            |offset: usize| u64::from_le_bytes(meta[offset..offset + 8].try_into().unwrap());
+        Ok(Self {
+            deactivation_slot: u64_at(4),
+            last_extended_slot: u64_at(12),
+            last_extended_slot_start_index: meta[20],
+            authority: (meta[21] == 1)
+                .then(|| Pubkey::new_from_array(meta[22..54].try_into().unwrap())),
+            addresses: addresses
+                .chunks_exact(32)
+                .map(|chunk| Pubkey::new_from_array(chunk.try_into().unwrap()))
+                .collect(),
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.deactivation_slot == ACTIVE
+    }
+
+    /// 表中缺少的 `addresses`（去重、保持顺序），用于只扩展还没有写入的部分
+    pub fn missing(&self, addresses: &[Pubkey]) -> Vec<Pubkey> {
+        let mut missing: Vec<Pubkey> = Vec::new();
+        for address in addresses {
+            if !self.addresses.contains(address) && !missing.contains(address) {
+                missing.push(*address);
+            }
+        }
+        missing
+    }
+}
+
+/// 查找表地址：[authority, recent_slot (u64 小端)]，`recent_slot` 必须仍在 SlotHashes 中（约 150 个 slot 内）
+pub fn lookup_table_address(authority: &Pubkey, recent_slot: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[authority.as_ref(), &recent_slot.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn data(tag: u32, body: &[u8]) -> Vec<u8> {
+    let mut data = tag.to_le_bytes().to_vec();
+    data.extend_from_slice(body);
+    data
+}
+
+/// CreateLookupTable：由 `payer` 支付租金创建，返回指令与表地址
+pub fn create_ix(authority: &Pubkey, payer: &Pubkey, recent_slot: u64) -> (Instruction, Pubkey) {
+    let (table, bump) = lookup_table_address(authority, recent_slot);
+    let mut body = recent_slot.to_le_bytes().to_vec();
+    body.push(bump);
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(table, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: data(CREATE, &body),
+    };
+    (ix, table)
+}
+
+/// ExtendLookupTable：追加 `addresses`，`payer` 补足增加的租金。
+/// 单条指令的地址数应不超过 [`MAX_ADDRESSES_PER_EXTEND`]，更多时用 [`extend_ixs`]
+pub fn extend_ix(
+    table: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    addresses: &[Pubkey],
+) -> Instruction {
+    let mut body = (addresses.len() as u64).to_le_bytes().to_vec();
+    for address in addresses {
+        body.extend_from_slice(address.as_ref());
+    }
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*table, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: data(EXTEND, &body),
+    }
+}
+
+/// 按 [`MAX_ADDRESSES_PER_EXTEND`] 分批的 Extend 指令，每条单独成一笔交易发送
+pub fn extend_ixs(
+    table: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    addresses: &[Pubkey],
+) -> Vec<Instruction> {
+    addresses
+        .chunks(MAX_ADDRESSES_PER_EXTEND)
+        .map(|chunk| extend_ix(table, authority, payer, chunk))
+        .collect()
+}
+
+/// FreezeLookupTable：清空 authority，表从此不可修改也不能关闭
+pub fn freeze_ix(table: &Pubkey, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*table, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: data(FREEZE, &[]),
+    }
+}
+
+/// DeactivateLookupTable：停用后约 512 个 slot（SlotHashes 不再包含停用的 slot）才能关闭
+pub fn deactivate_ix(table: &Pubkey, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*table, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: data(DEACTIVATE, &[]),
+    }
+}
+
+/// CloseLookupTable：租金退给 `recipient`
+pub fn close_ix(table: &Pubkey, authority: &Pubkey, recipient: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*table, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*recipient, false),
+        ],
+        data: data(CLOSE, &[]),
+    }
+}
+
+/// 训练营交易中反复出现的地址：系统 / 代币 / ATA 程序、wSOL、常用 sysvar，
+/// 以及已开启 feature 的程序 ID 与固定 PDA（默认部署地址，去重）。
+/// 程序部署到其他地址时，在结果后面追加实际地址
+pub fn common_addresses() -> Vec<Pubkey> {
+    #[allow(unused_mut)]
+    let mut addresses = vec![
+        system_program::ID,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        NATIVE_MINT,
+        sysvar::rent::ID,
+        sysvar::clock::ID,
+        sysvar::instructions::ID,
+    ];
+    #[cfg(feature = "vault")]
+    addresses.extend([
+        crate::vault::PROGRAM_ID,
+        crate::vault::event_authority_address(&crate::vault::PROGRAM_ID).0,
+    ]);
+    #[cfg(feature = "escrow")]
+    addresses.push(crate::escrow::PROGRAM_ID);
+    #[cfg(feature = "amm")]
+    addresses.push(crate::amm::PROGRAM_ID);
+    #[cfg(feature = "router")]
+    addresses.push(crate::router::PROGRAM_ID);
+    #[cfg(feature = "treasury")]
+    addresses.extend([
+        crate::treasury::PROGRAM_ID,
+        crate::treasury::treasury_address(&crate::treasury::PROGRAM_ID).0,
+    ]);
+
+    let mut unique = Vec::with_capacity(addresses.len());
+    for address in addresses {
+        if !unique.contains(&address) {
+            unique.push(address);
+        }
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按链上 LookupTableMeta 的 bincode 布局写出表账户
+    fn table_data(authority: Option<Pubkey>, addresses: &[Pubkey]) -> Vec<u8> {
+        let mut data = vec![0u8; META_LEN];
+        data[..4].copy_from_slice(&1u32.to_le_bytes());
+        data[4..12].copy_from_slice(&ACTIVE.to_le_bytes());
+        data[12..20].copy_from_slice(&77u64.to_le_bytes());
+        data[20] = 3;
+        if let Some(authority) = authority {
+            data[21] = 1;
+            data[22..54].copy_from_slice(authority.as_ref());
+        }
+        for address in addresses {
+            data.extend_from_slice(address.as_ref());
+        }
+        data
+    }
+
+    #[test]
+    fn instructions_match_program_encoding() {
+        let (authority, payer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (create, table) = create_ix(&authority, &payer, 123);
+        let (expected, bump) = lookup_table_address(&authority, 123);
+        assert_eq!(table, expected);
+        assert_eq!(create.data[..4], [0, 0, 0, 0]);
+        assert_eq!(create.data[4..12], 123u64.to_le_bytes());
+        assert_eq!(create.data[12..], [bump]);
+        assert_eq!(create.accounts[0].pubkey, table);
+
+        let addresses: Vec<Pubkey> = (0..45).map(|_| Pubkey::new_unique()).collect();
+        let extends = extend_ixs(&table, &authority, &payer, &addresses);
+        assert_eq!(extends.len(), 3);
+        assert_eq!(extends[0].data[..4], EXTEND.to_le_bytes());
+        assert_eq!(extends[0].data[4..12], 20u64.to_le_bytes());
+        assert_eq!(extends[0].data.len(), 12 + 20 * 32);
+        assert_eq!(extends[2].data[4..12], 5u64.to_le_bytes());
+        assert_eq!(&extends[2].data[12..44], addresses[40].as_ref());
+
+        assert_eq!(deactivate_ix(&table, &authority).data, [3, 0, 0, 0]);
+        assert_eq!(close_ix(&table, &authority, &payer).accounts.len(), 3);
+    }
+
+    #[test]
+    fn parses_table_and_reports_missing_addresses() {
+        let authority = Pubkey::new_unique();
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let table = LookupTable::try_from_bytes(&table_data(Some(authority), &[a, b])).unwrap();
+        assert_eq!(table.authority, Some(authority));
+        assert_eq!(table.last_extended_slot, 77);
+        assert_eq!(table.last_extended_slot_start_index, 3);
+        assert!(table.is_active());
+        assert_eq!(table.addresses, [a, b]);
+        assert_eq!(table.missing(&[b, c, c, a]), [c]);
+
+        let frozen = LookupTable::try_from_bytes(&table_data(None, &[])).unwrap();
+        assert_eq!(frozen.authority, None);
+
+        let mut torn = table_data(None, &[a]);
+        torn.pop();
+        assert_eq!(
+            LookupTable::try_from_bytes(&torn).unwrap_err().expected,
+            META_LEN
+        );
+        // 未初始化的账户
+        assert!(LookupTable::try_from_bytes(&[0; META_LEN]).is_err());
+    }
+
+    #[test]
+    fn common_addresses_are_unique() {
+        let addresses = common_addresses();
+        assert!(addresses.contains(&TOKEN_PROGRAM_ID));
+        assert!(addresses.len() <= MAX_ADDRESSES);
+        for (i, address) in addresses.iter().enumerate() {
+            assert!(!addresses[i + 1..].contains(address));
+        }
+    }
+}
@@ -0,0 +1,433 @@
+//! task6 原生 AMM 的指令构造与 Config 解析，账户顺序与数据布局同 amm_e2e 中的指令构造函数。
+//! pinocchio_amm 的 Deposit / Withdraw / Swap 布局与原生 AMM 相同，可以复用 `Pool`。
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sdk_ids::system_program;
+
+use crate::{
+    fixed, pda, read,
+    token::{ata, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    AccountDataError,
+};
+
+pub const PROGRAM_ID: Pubkey = pubkey!("22222222222222222222222222222222222222222222");
+
+pub use pdas::{CONFIG_SEED, MINT_LP_SEED};
+
+/// 池子的全部派生地址
+#[derive(Clone, Debug)]
+pub struct Pool {
+    pub program_id: Pubkey,
+    /// 规范池为 0（不参与派生）
+    pub seed: u64,
+    pub canonical: bool,
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+    pub config: Pubkey,
+    pub config_bump: u8,
+    pub mint_lp: Pubkey,
+    pub lp_bump: u8,
+    /// 池子金库：config 在 mint_x / mint_y 上的 ATA
+    pub vault_x: Pubkey,
+    pub vault_y: Pubkey,
+}
+
+impl Pool {
+    /// 规范池按字节序排列 mint，调用方需保证 `mint_x < mint_y`，
+    /// 否则派生出的 config 与链上不一致
+    pub fn canonical(program_id: &Pubkey, mint_x: &Pubkey, mint_y: &Pubkey) -> Self {
+        let (config, config_bump) = pda(pdas::amm_canonical_config_pda(
+            &program_id.to_bytes(),
+            &mint_x.to_bytes(),
+            &mint_y.to_bytes(),
+        ));
+        Self::with_config(program_id, 0, true, mint_x, mint_y, config, config_bump)
+    }
+
+    /// 非规范池：config PDA = [b"config", seed (LE), mint_x, mint_y]，
+    /// 同一对 mint 可以用不同 seed 建多个池（pinocchio_amm 只支持这种池）
+    pub fn seeded(program_id: &Pubkey, seed: u64, mint_x: &Pubkey, mint_y: &Pubkey) -> Self {
+        let (config, config_bump) = pda(pdas::amm_config_pda(
+            &program_id.to_bytes(),
+            seed,
+            &mint_x.to_bytes(),
+            &mint_y.to_bytes(),
+        ));
+        Self::with_config(program_id, seed, false, mint_x, mint_y, config, config_bump)
+    }
+
+    fn with_config(
+        program_id: &Pubkey,
+        seed: u64,
+        canonical: bool,
+        mint_x: &Pubkey,
+        mint_y: &Pubkey,
+        config: Pubkey,
+        config_bump: u8,
+    ) -> Self {
+        let (mint_lp, lp_bump) = pda(pdas::amm_mint_lp_pda(
+            &program_id.to_bytes(),
+            &config.to_bytes(),
+        ));
+        Self {
+            program_id: *program_id,
+            seed,
+            canonical,
+            mint_x: *mint_x,
+            mint_y: *mint_y,
+            config,
+            config_bump,
+            mint_lp,
+            lp_bump,
+            vault_x: ata(&config, mint_x),
+            vault_y: ata(&config, mint_y),
+        }
+    }
+
+    /// 用户在本池三种代币上的 ATA：(x, y, lp)
+    pub fn user_atas(&self, user: &Pubkey) -> (Pubkey, Pubkey, Pubkey) {
+        (
+            ata(user, &self.mint_x),
+            ata(user, &self.mint_y),
+            ata(user, &self.mint_lp),
+        )
+    }
+
+    /// 本池每笔兑换 / 存取都会用到、与用户无关的账户，供写入地址查找表（见 `alt`）
+    pub fn lookup_addresses(&self) -> [Pubkey; 6] {
+        [
+            self.config,
+            self.mint_x,
+            self.mint_y,
+            self.mint_lp,
+            self.vault_x,
+            self.vault_y,
+        ]
+    }
+
+    /// Initialize：50/50 池，由 payer 出资同时创建两个金库 ATA
+    pub fn initialize_ix(&self, payer: &Pubkey, fee: u16, authority: &Pubkey) -> Instruction {
+        let mut data = vec![0u8];
+        data.extend_from_slice(&self.seed.to_le_bytes()); // 规范池忽略
+        data.extend_from_slice(&fee.to_le_bytes());
+        data.extend_from_slice(self.mint_x.as_ref());
+        data.extend_from_slice(self.mint_y.as_ref());
+        data.push(self.config_bump);
+        data.push(self.lp_bump);
+        data.extend_from_slice(&0u16.to_le_bytes()); // weight_x = 0 → 50/50
+        data.push(self.canonical as u8);
+        data.push(0); // lp_decimals = 0 → 默认 6 位
+        data.extend_from_slice(authority.as_ref());
+
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(*payer, true),
+                AccountMeta::new(self.mint_lp, false),
+                AccountMeta::new(self.config, false),
+                AccountMeta::new_readonly(self.mint_x, false),
+                AccountMeta::new_readonly(self.mint_y, false),
+                AccountMeta::new(self.vault_x, false),
+                AccountMeta::new(self.vault_y, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            ],
+            data,
+        }
+    }
+
+    /// Deposit 与 Withdraw 的账户列表相同，只有判别器和参数含义不同
+    fn liquidity_ix(&self, user: &Pubkey, discriminator: u8, args: [u64; 3]) -> Instruction {
+        let (user_x, user_y, user_lp) = self.user_atas(user);
+        let mut data = vec![discriminator];
+        for arg in args {
+            data.extend_from_slice(&arg.to_le_bytes());
+        }
+        data.extend_from_slice(&i64::MAX.to_le_bytes()); // expiration：不过期
+
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(*user, true),
+                AccountMeta::new(self.mint_lp, false),
+                AccountMeta::new(self.vault_x, false),
+                AccountMeta::new(self.vault_y, false),
+                AccountMeta::new(user_x, false),
+                AccountMeta::new(user_y, false),
+                AccountMeta::new(user_lp, false),
+                AccountMeta::new(self.config, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ],
+            data,
+        }
+    }
+
+    /// Deposit：铸造 amount 个 LP，最多存入 max_x / max_y
+    pub fn deposit_ix(&self, user: &Pubkey, amount: u64, max_x: u64, max_y: u64) -> Instruction {
+        self.liquidity_ix(user, 1, [amount, max_x, max_y])
+    }
+
+    /// Withdraw：销毁 amount 个 LP，至少取回 min_x / min_y
+    pub fn withdraw_ix(&self, user: &Pubkey, amount: u64, min_x: u64, min_y: u64) -> Instruction {
+        self.liquidity_ix(user, 2, [amount, min_x, min_y])
+    }
+
+    /// Swap：`is_x` 为 true 时输入 X 换出 Y，至少得到 min 个输出代币
+    pub fn swap_ix(&self, user: &Pubkey, is_x: bool, amount: u64, min: u64) -> Instruction {
+        let (user_x, user_y, _) = self.user_atas(user);
+        let mut data = vec![3u8, is_x as u8];
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&min.to_le_bytes());
+        data.extend_from_slice(&i64::MAX.to_le_bytes());
+
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(*user, true),
+                AccountMeta::new(user_x, false),
+                AccountMeta::new(user_y, false),
+                AccountMeta::new(self.vault_x, false),
+                AccountMeta::new(self.vault_y, false),
+                AccountMeta::new(self.config, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ],
+            data,
+        }
+    }
+
+    /// ProposeUpdate：authority 提出参数变更，`kind` 见程序的 `ConfigUpdate`（1 手续费、2 状态、
+    /// 3 authority、4 timelock、5 返还档位、6 赎回宽限），`value` 按该项的小端布局写在开头
+    pub fn propose_update_ix(&self, authority: &Pubkey, kind: u8, value: [u8; 32]) -> Instruction {
+        let mut data = vec![15u8, kind];
+        data.extend_from_slice(&value);
+        self.authority_ix(authority, data)
+    }
+
+    /// ExecuteUpdate：timelock 到期后由 authority 执行待生效的变更
+    pub fn execute_update_ix(&self, authority: &Pubkey) -> Instruction {
+        self.authority_ix(authority, vec![16])
+    }
+
+    fn authority_ix(&self, authority: &Pubkey, data: Vec<u8>) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new(self.config, false),
+            ],
+            data,
+        }
+    }
+}
+
+/// 链上 Config 账户中客户端常用的字段，偏移与程序 `state::Config`（全部为字节数组，无填充）一致
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AmmConfig {
+    /// 0 未初始化、1 正常、2 停用、3 只允许赎回
+    pub state: u8,
+    pub seed: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub authority: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub mint_x: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub mint_y: Pubkey,
+    /// 手续费（基点）
+    pub fee: u16,
+    /// mint_x 的权重（基点），0 表示 50/50
+    pub weight_x: u16,
+    pub config_bump: u8,
+    pub canonical: bool,
+    /// 待执行提案的 `ConfigUpdate` 类型，0 表示没有提案
+    pub pending_kind: u8,
+    /// 提案可以执行的最早 Unix 时间戳
+    pub pending_eta: i64,
+    pub reserve_x: u64,
+    pub reserve_y: u64,
+    pub lp_decimals: u8,
+}
+
+impl AmmConfig {
+    pub const LEN: usize = 223;
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let data = fixed::<{ Self::LEN }>("amm config", data)?;
+        Ok(Self {
+            state: data[0],
+            seed: u64::from_le_bytes(read(data, 1)),
+            authority: Pubkey::new_from_array(read(data, 9)),
+            mint_x: Pubkey::new_from_array(read(data, 41)),
+            mint_y: Pubkey::new_from_array(read(data, 73)),
+            fee: u16::from_le_bytes(read(data, 105)),
+            weight_x: u16::from_le_bytes(read(data, 107)),
+            config_bump: data[109],
+            canonical: data[110] == 1,
+            pending_kind: data[119],
+            pending_eta: i64::from_le_bytes(read(data, 120)),
+            reserve_x: u64::from_le_bytes(read(data, 190)),
+            reserve_y: u64::from_le_bytes(read(data, 198)),
+            lp_decimals: data[206],
+        })
+    }
+
+    /// 有提案且 timelock 已在 `now` 之前到期，可以发送 ExecuteUpdate
+    pub fn update_ready(&self, now: i64) -> bool {
+        self.pending_kind != 0 && now >= self.pending_eta
+    }
+
+    /// 按当前储备报价：输入 `amount_in` 个 X（`is_x`）或 Y 可换出的数量。
+    ///
+    /// 与程序 `curve::constant_product_out` 逐位相同：手续费从输入中扣除，输出向下取整。
+    /// 持有 LP 的用户可能享受更低的返还费率，实际输出不少于报价。
+    /// 加权池的曲线依赖程序内的定点幂运算，这里不报价，返回 `None`；
+    /// 程序会拒绝的输入（数量为 0、换出 0）同样返回 `None`
+    pub fn quote_swap(&self, is_x: bool, amount_in: u64) -> Option<u64> {
+        if self.weight_x != 0 || amount_in == 0 {
+            return None;
+        }
+        let (balance_in, balance_out) = if is_x {
+            (self.reserve_x, self.reserve_y)
+        } else {
+            (self.reserve_y, self.reserve_x)
+        };
+        let amount_in = u128::from(amount_in).checked_mul(10_000 - u128::from(self.fee))? / 10_000;
+        let denominator = u128::from(balance_in).checked_add(amount_in)?;
+        let out = u128::from(balance_out)
+            .checked_mul(amount_in)?
+            .checked_div(denominator)?;
+        u64::try_from(out).ok().filter(|out| *out > 0)
+    }
+
+    /// 铸造 `lp` 个 LP（当前供应量 `supply`）需要存入的 (x, y)，向上取整；
+    /// 首次存入（`supply` 为 0）由用户自定比例，返回 `None`
+    pub fn quote_deposit(&self, lp: u64, supply: u64) -> Option<(u64, u64)> {
+        Some((
+            proportional(self.reserve_x, lp, supply, true)?,
+            proportional(self.reserve_y, lp, supply, true)?,
+        ))
+    }
+
+    /// 销毁 `lp` 个未锁定的 LP 可取回的 (x, y)，向下取整；全额销毁时取走全部储备
+    pub fn quote_withdraw(&self, lp: u64, supply: u64) -> Option<(u64, u64)> {
+        if lp == 0 || lp > supply {
+            return None;
+        }
+        if lp == supply {
+            return Some((self.reserve_x, self.reserve_y));
+        }
+        Some((
+            proportional(self.reserve_x, lp, supply, false)?,
+            proportional(self.reserve_y, lp, supply, false)?,
+        ))
+    }
+}
+
+/// `balance * lp / supply`，与程序 `curve::proportional_amount` 相同
+fn proportional(balance: u64, lp: u64, supply: u64, round_up: bool) -> Option<u64> {
+    if supply == 0 {
+        return None;
+    }
+    let numerator = u128::from(balance).checked_mul(u128::from(lp))?;
+    let supply = u128::from(supply);
+    let amount = if round_up {
+        numerator.div_ceil(supply)
+    } else {
+        numerator / supply
+    };
+    u64::try_from(amount).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_fields_at_program_offsets() {
+        let mint_x = Pubkey::new_unique();
+        let mut data = [0u8; AmmConfig::LEN];
+        data[0] = 1;
+        data[41..73].copy_from_slice(mint_x.as_ref());
+        data[105..107].copy_from_slice(&30u16.to_le_bytes());
+        data[110] = 1;
+        data[190..198].copy_from_slice(&500u64.to_le_bytes());
+        data[198..206].copy_from_slice(&700u64.to_le_bytes());
+
+        let config = AmmConfig::try_from_bytes(&data).unwrap();
+        assert_eq!(config.mint_x, mint_x);
+        assert_eq!(config.fee, 30);
+        assert!(config.canonical);
+        assert_eq!((config.reserve_x, config.reserve_y), (500, 700));
+        assert!(AmmConfig::try_from_bytes(&data[..200]).is_err());
+    }
+
+    /// 程序提交的黄金快照，取值见快照文件的注释
+    #[test]
+    fn parses_program_layout_snapshot() {
+        let data = layout_snapshot::snapshot_bytes(include_str!(
+            "../../../task6/solana-pinocchio-amm-workshop-main/blueshift_native_amm/snapshots/config.layout"
+        ));
+        let config = AmmConfig::try_from_bytes(&data).unwrap();
+        assert_eq!(
+            config,
+            AmmConfig {
+                state: 1,
+                seed: 0x0807_0605_0403_0201,
+                authority: Pubkey::new_from_array([0xaa; 32]),
+                mint_x: Pubkey::new_from_array([0x11; 32]),
+                mint_y: Pubkey::new_from_array([0x22; 32]),
+                fee: 30,
+                weight_x: 6_000,
+                config_bump: 0xfd,
+                canonical: true,
+                pending_kind: 1,
+                pending_eta: 1_000 + 172_800,
+                reserve_x: 1_000_000_000,
+                reserve_y: 4_000_000_000,
+                lp_decimals: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn quotes_follow_program_rounding() {
+        let mut data = [0u8; AmmConfig::LEN];
+        data[105..107].copy_from_slice(&30u16.to_le_bytes());
+        data[190..198].copy_from_slice(&1_000_000u64.to_le_bytes());
+        data[198..206].copy_from_slice(&2_000_000u64.to_le_bytes());
+        let config = AmmConfig::try_from_bytes(&data).unwrap();
+
+        // 10_000 扣除 0.3% 后为 9_970：2e6 * 9_970 / 1_009_970 = 19_743.15...
+        assert_eq!(config.quote_swap(true, 10_000), Some(19_743));
+        assert_eq!(config.quote_swap(false, 10_000), Some(4_960));
+        assert_eq!(config.quote_swap(true, 0), None);
+
+        // 存入向上取整、取出向下取整，全额取出拿走全部储备
+        assert_eq!(config.quote_deposit(1, 3), Some((333_334, 666_667)));
+        assert_eq!(config.quote_withdraw(1, 3), Some((333_333, 666_666)));
+        assert_eq!(config.quote_withdraw(3, 3), Some((1_000_000, 2_000_000)));
+        assert_eq!(config.quote_deposit(1, 0), None);
+
+        let weighted = AmmConfig {
+            weight_x: 6_000,
+            ..config
+        };
+        assert_eq!(weighted.quote_swap(true, 10_000), None);
+    }
+
+    #[test]
+    fn deposit_and_withdraw_share_accounts() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mint_x, mint_y) = if a < b { (a, b) } else { (b, a) };
+        let pool = Pool::canonical(&PROGRAM_ID, &mint_x, &mint_y);
+        let user = Pubkey::new_unique();
+
+        let deposit = pool.deposit_ix(&user, 10, 20, 30);
+        let withdraw = pool.withdraw_ix(&user, 10, 20, 30);
+        assert_eq!(deposit.accounts, withdraw.accounts);
+        assert_eq!((deposit.data[0], withdraw.data[0]), (1, 2));
+        assert_eq!(deposit.data.len(), 1 + 8 * 4);
+    }
+}
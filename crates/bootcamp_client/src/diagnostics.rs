@@ -0,0 +1,152 @@
+//! 交易失败的可读说明：把 `InstructionError::Custom(0x17d5)` 这样的错误码还原成
+//! blueshift_errors 中的具体错误，并从程序日志里找出是哪个程序失败。
+//!
+//! 不依赖 RPC：LiteSVM / Mollusk 测试拿到的 `TransactionError` 与日志同样可以用。
+//!
+//! ```
+//! # use bootcamp_client::diagnostics::Failure;
+//! # use solana_instruction::error::InstructionError;
+//! # use solana_transaction_error::TransactionError;
+//! let err = TransactionError::InstructionError(1, InstructionError::Custom(6100));
+//! assert!(Failure::from(&err).to_string().starts_with("指令 #1 失败：[escrow 6100]"));
+//! ```
+
+use core::fmt;
+
+use blueshift_errors::BootcampError;
+use solana_instruction::error::InstructionError;
+use solana_transaction_error::TransactionError;
+
+/// 交易失败的原因
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Failure {
+    /// 第 `index` 条指令返回了训练营程序的自定义错误
+    Program { index: u8, error: BootcampError },
+    /// 第 `index` 条指令的其他错误（未知的错误码、账户校验失败等）
+    Instruction { index: u8, error: InstructionError },
+    /// 交易层面的错误（签名、余额、blockhash 等），没有具体的指令
+    Transaction(TransactionError),
+}
+
+impl From<&TransactionError> for Failure {
+    fn from(err: &TransactionError) -> Self {
+        match err {
+            TransactionError::InstructionError(index, InstructionError::Custom(code)) => {
+                match BootcampError::from_code(*code) {
+                    Some(error) => Self::Program {
+                        index: *index,
+                        error,
+                    },
+                    None => Self::Instruction {
+                        index: *index,
+                        error: InstructionError::Custom(*code),
+                    },
+                }
+            }
+            TransactionError::InstructionError(index, error) => Self::Instruction {
+                index: *index,
+                error: error.clone(),
+            },
+            err => Self::Transaction(err.clone()),
+        }
+    }
+}
+
+impl Failure {
+    /// 失败的指令序号（计入计算预算指令）
+    pub fn instruction_index(&self) -> Option<u8> {
+        match self {
+            Self::Program { index, .. } | Self::Instruction { index, .. } => Some(*index),
+            Self::Transaction(_) => None,
+        }
+    }
+
+    /// 训练营程序的错误
+    pub fn program_error(&self) -> Option<BootcampError> {
+        match self {
+            Self::Program { error, .. } => Some(*error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Program { index, error } => write!(f, "指令 #{index} 失败：{error}"),
+            Self::Instruction {
+                index,
+                error: InstructionError::Custom(code),
+            } => write!(
+                f,
+                "指令 #{index} 失败：未知的自定义错误 {code}（{code:#x}）"
+            ),
+            Self::Instruction { index, error } => write!(f, "指令 #{index} 失败：{error}"),
+            Self::Transaction(err) => write!(f, "交易失败：{err}"),
+        }
+    }
+}
+
+impl std::error::Error for Failure {}
+
+/// 日志中最内层失败的程序与原因：`Program <id> failed: <reason>`
+pub fn failed_program<S: AsRef<str>>(logs: &[S]) -> Option<(&str, &str)> {
+    logs.iter().find_map(|line| {
+        line.as_ref()
+            .strip_prefix("Program ")?
+            .split_once(" failed: ")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_codes_map_to_program_errors() {
+        let err = TransactionError::InstructionError(2, InstructionError::Custom(6200));
+        let failure = Failure::from(&err);
+        assert_eq!(failure.instruction_index(), Some(2));
+        assert_eq!(failure.program_error().unwrap().program(), "amm");
+
+        let unknown = Failure::from(&TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(1),
+        ));
+        assert_eq!(
+            unknown.to_string(),
+            "指令 #0 失败：未知的自定义错误 1（0x1）"
+        );
+
+        let tx = Failure::from(&TransactionError::BlockhashNotFound);
+        assert_eq!(tx.instruction_index(), None);
+    }
+
+    #[test]
+    fn anchor_framework_codes_are_program_errors() {
+        // 缺签名：Anchor 程序与换算后的 Pinocchio 程序都返回 3010
+        let err = TransactionError::InstructionError(0, InstructionError::Custom(3010));
+        assert_eq!(
+            Failure::from(&err).to_string(),
+            "指令 #0 失败：[anchor 3010] The given account did not sign"
+        );
+    }
+
+    #[test]
+    fn innermost_failure_comes_first_in_logs() {
+        let logs = [
+            "Program Router1111111111111111111111111111111111111 invoke [1]",
+            "Program 22222222222222222222222222222222222222222222 invoke [2]",
+            "Program 22222222222222222222222222222222222222222222 failed: custom program error: 0x17d4",
+            "Program Router1111111111111111111111111111111111111 failed: custom program error: 0x17d4",
+        ];
+        assert_eq!(
+            failed_program(&logs),
+            Some((
+                "22222222222222222222222222222222222222222222",
+                "custom program error: 0x17d4"
+            ))
+        );
+        assert_eq!(failed_program::<&str>(&[]), None);
+    }
+}
@@ -0,0 +1,673 @@
+//! task5 Pinocchio 托管：判别器 Make = 0、Take = 1、Refund = 2、TakePartial = 3，
+//! 每条指令的账户列表末尾都带上 ATA 程序（程序内部用它 CPI 创建代币账户）。
+//! 托管过期（expiry 非 0 且已到期）后 Take 被拒绝，任何人都可以代 maker 调用 Refund。
+//! 指定了 taker 的托管只接受该地址的 Take / TakePartial。
+//! InitConfig = 4 由升级权限创建 Config PDA，之后 Take / TakePartial 按其中的费率从 maker
+//! 收到的代币 B 里扣下协议手续费转给 treasury，所以这两条指令还要带上 config 与 treasury 的 ATA。
+//! UpdateOffer = 5 让 maker 直接修改未过期托管的 mint_b 与 receive，不用退款重挂，
+//! taker 用 [`with_max_receive`] 给 Take / TakePartial 加上价格上限，避免被抢先改价；
+//! TopUp = 6 向金库追加代币 A 并同时改写整单的 receive。
+//! Make / Take / Refund（以及 TakePartial）末尾还要带上 maker 的挂单索引 [`OfferIndex`]：
+//! Make 登记新托管单的 seed，关闭托管时注销，读这一个账户就能列出 maker 的全部挂单。
+//! mint_a / mint_b 传 [`NATIVE_MINT`](crate::token::NATIVE_MINT) 即为原生 SOL：账户列表不变，
+//! 程序直接收付 lamports，对应一侧的 ATA 不会被创建，调用方也不用自己包装 wSOL。
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sdk_ids::system_program;
+
+use crate::{
+    fixed, pda, read,
+    token::{ata, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    version::program_data_address,
+    AccountDataError,
+};
+
+pub const PROGRAM_ID: Pubkey = pubkey!("22222222222222222222222222222222222222222222");
+
+pub use pdas::ESCROW_SEED;
+
+/// 链上 Escrow 账户：判别器 1 + seed u64 + maker + mint_a + mint_b + receive u64 + bump + expiry i64 + taker。
+/// Pinocchio 与 Anchor 两个实现的账户数据逐字节相同
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Escrow {
+    pub seed: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub maker: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub mint_a: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub mint_b: Pubkey,
+    pub receive: u64,
+    pub bump: u8,
+    /// 过期的 Unix 时间戳，0 表示永不过期
+    pub expiry: i64,
+    /// 指定的接受者，全零（`Pubkey::default()`）表示任何人都可以接受
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub taker: Pubkey,
+}
+
+impl Escrow {
+    pub const LEN: usize = 1 + 8 + 32 * 3 + 8 + 1 + 8 + 32;
+    /// 账户判别器，即 Anchor 版的 `#[account(discriminator = 1)]`
+    pub const DISCRIMINATOR: u8 = 1;
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let data = fixed::<{ Self::LEN }>("escrow", data)?;
+        if data[0] != Self::DISCRIMINATOR {
+            return Err(AccountDataError::discriminator("escrow", data));
+        }
+        Ok(Self {
+            seed: u64::from_le_bytes(read(data, 1)),
+            maker: Pubkey::new_from_array(read(data, 9)),
+            mint_a: Pubkey::new_from_array(read(data, 41)),
+            mint_b: Pubkey::new_from_array(read(data, 73)),
+            receive: u64::from_le_bytes(read(data, 105)),
+            bump: data[113],
+            expiry: i64::from_le_bytes(read(data, 114)),
+            taker: Pubkey::new_from_array(read(data, 122)),
+        })
+    }
+
+    /// 与程序相同：expiry 非 0 且 `now` 已到达时视为过期
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expiry != 0 && now >= self.expiry
+    }
+
+    /// 与程序相同：没有指定接受者，或 `taker` 正是指定的接受者
+    pub fn allows_taker(&self, taker: &Pubkey) -> bool {
+        self.taker == Pubkey::default() || self.taker == *taker
+    }
+
+    /// 托管金库：escrow 在 mint_a 上的 ATA
+    pub fn vault(&self, escrow: &Pubkey) -> Pubkey {
+        ata(escrow, &self.mint_a)
+    }
+
+    /// 金库余额为 `vault_amount` 时买走 `amount` 个代币 A 需要支付的代币 B，
+    /// 与程序相同按比例向上取整；数量为 0、超过金库余额，或者不是最后一笔却会付清
+    /// 全部 receive 时返回 None（程序拒绝这样的成交）
+    pub fn partial_receive(&self, amount: u64, vault_amount: u64) -> Option<u64> {
+        if amount == 0 || amount > vault_amount {
+            return None;
+        }
+        let owed =
+            (u128::from(self.receive) * u128::from(amount)).div_ceil(u128::from(vault_amount));
+        let owed = u64::try_from(owed).ok()?;
+        (amount == vault_amount || owed < self.receive).then_some(owed)
+    }
+}
+
+/// 链上 Config 账户：treasury + fee_bps u16 + bump
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Config {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub bump: u8,
+}
+
+impl Config {
+    pub const LEN: usize = 32 + 2 + 1;
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let data = fixed::<{ Self::LEN }>("escrow config", data)?;
+        Ok(Self {
+            treasury: Pubkey::new_from_array(read(data, 0)),
+            fee_bps: u16::from_le_bytes(read(data, 32)),
+            bump: data[34],
+        })
+    }
+
+    /// 与程序相同：成交 `amount` 个代币 B 时的协议手续费，向下取整
+    pub fn fee(&self, amount: u64) -> u64 {
+        (u128::from(amount) * u128::from(self.fee_bps) / 10_000) as u64
+    }
+}
+
+/// 链上 OfferIndex 账户：判别器 2 + bump + count + 16 个 seed（u64），只有前 count 个有效
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OfferIndex {
+    pub bump: u8,
+    /// 未成交托管单的 seed，顺序不固定
+    pub seeds: Vec<u64>,
+}
+
+impl OfferIndex {
+    /// 每个 maker 同时挂单的上限，再 Make 返回 TooManyOffers
+    pub const MAX_OPEN_OFFERS: usize = 16;
+    pub const LEN: usize = 1 + 1 + 1 + 8 * Self::MAX_OPEN_OFFERS;
+    pub const DISCRIMINATOR: u8 = 2;
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let data = fixed::<{ Self::LEN }>("offer index", data)?;
+        let count = usize::from(data[2]);
+        // 与程序相同：count 超过容量的数据也不是索引
+        if data[0] != Self::DISCRIMINATOR || count > Self::MAX_OPEN_OFFERS {
+            return Err(AccountDataError::discriminator("offer index", data));
+        }
+        Ok(Self {
+            bump: data[1],
+            seeds: (0..count)
+                .map(|i| u64::from_le_bytes(read(data, 3 + 8 * i)))
+                .collect(),
+        })
+    }
+
+    /// 索引中各托管单的地址，与 `seeds` 一一对应
+    pub fn escrows(&self, program_id: &Pubkey, maker: &Pubkey) -> Vec<Pubkey> {
+        self.seeds
+            .iter()
+            .map(|seed| escrow_address(program_id, maker, *seed).0)
+            .collect()
+    }
+}
+
+/// 挂单索引 PDA：[b"offers", maker]，maker 第一次 Make 时创建
+pub fn offer_index_address(program_id: &Pubkey, maker: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::offer_index_pda(
+        &program_id.to_bytes(),
+        &maker.to_bytes(),
+    ))
+}
+
+/// 配置 PDA：[b"config"]，每个部署只有一个
+pub fn config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::escrow_config_pda(&program_id.to_bytes()))
+}
+
+/// 托管 PDA：[b"escrow", maker, seed (LE)]
+pub fn escrow_address(program_id: &Pubkey, maker: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    pda(pdas::escrow_pda(
+        &program_id.to_bytes(),
+        &maker.to_bytes(),
+        seed,
+    ))
+}
+
+/// Make：存入 amount 个代币 A，要求对方支付 receive 个代币 B；`expiry` 为 0 时永不过期，
+/// `taker` 为 None 时任何人都可以接受
+#[allow(clippy::too_many_arguments)]
+pub fn make_ix(
+    program_id: &Pubkey,
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    seed: u64,
+    receive: u64,
+    amount: u64,
+    expiry: i64,
+    taker: Option<Pubkey>,
+) -> Instruction {
+    let (escrow, _) = escrow_address(program_id, maker, seed);
+    let mut data = vec![0u8];
+    data.extend_from_slice(&seed.to_le_bytes());
+    data.extend_from_slice(&receive.to_le_bytes());
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&expiry.to_le_bytes());
+    data.extend_from_slice(taker.unwrap_or_default().as_ref());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new_readonly(*mint_a, false),
+            AccountMeta::new_readonly(*mint_b, false),
+            AccountMeta::new(ata(maker, mint_a), false),
+            AccountMeta::new(ata(&escrow, mint_a), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new(offer_index_address(program_id, maker).0, false),
+        ],
+        data,
+    }
+}
+
+/// InitConfig：升级权限 `authority` 设置协议手续费率与接收方，只能调用一次
+pub fn init_config_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    fee_bps: u16,
+    treasury: &Pubkey,
+) -> Instruction {
+    let mut data = vec![4u8];
+    data.extend_from_slice(&fee_bps.to_le_bytes());
+    data.extend_from_slice(treasury.as_ref());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(program_data_address(program_id), false),
+            AccountMeta::new(config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// Take：taker 支付代币 B，取走金库中的代币 A，托管账户关闭给 maker。
+/// 协议手续费转入 `treasury` 在 mint_b 上的 ATA（需已存在）；还没有 Config 时传 `Pubkey::default()`
+pub fn take_ix(
+    program_id: &Pubkey,
+    taker: &Pubkey,
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    treasury: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new(escrow.maker, false),
+            AccountMeta::new(*escrow_address, false),
+            AccountMeta::new_readonly(escrow.mint_a, false),
+            AccountMeta::new_readonly(escrow.mint_b, false),
+            AccountMeta::new(escrow.vault(escrow_address), false),
+            AccountMeta::new(ata(taker, &escrow.mint_a), false),
+            AccountMeta::new(ata(taker, &escrow.mint_b), false),
+            AccountMeta::new(ata(&escrow.maker, &escrow.mint_b), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(config_address(program_id).0, false),
+            AccountMeta::new(ata(treasury, &escrow.mint_b), false),
+            AccountMeta::new(offer_index_address(program_id, &escrow.maker).0, false),
+        ],
+        data: vec![1],
+    }
+}
+
+/// TakePartial：taker 只买走 `amount` 个代币 A，按比例支付代币 B（见 [`Escrow::partial_receive`]）；
+/// 账户与 [`take_ix`] 相同，买完时托管账户同样关闭
+pub fn take_partial_ix(
+    program_id: &Pubkey,
+    taker: &Pubkey,
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    treasury: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut ix = take_ix(program_id, taker, escrow_address, escrow, treasury);
+    ix.data = vec![3];
+    ix.data.extend_from_slice(&amount.to_le_bytes());
+    ix
+}
+
+/// 给 [`take_ix`] / [`take_partial_ix`] 加上接受者的价格上限：成交时应付的代币 B（不含转账手续费）
+/// 超过 `max_receive` 则返回 ReceiveAboveMax，maker 抢先 UpdateOffer / TopUp 提价时不会按新价格成交
+pub fn with_max_receive(mut ix: Instruction, max_receive: u64) -> Instruction {
+    ix.data.extend_from_slice(&max_receive.to_le_bytes());
+    ix
+}
+
+/// UpdateOffer：maker 把报价改为 `receive` 个 `mint_b`（可以就是原来的 mint_b），金库保持不变
+pub fn update_offer_ix(
+    program_id: &Pubkey,
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    mint_b: &Pubkey,
+    receive: u64,
+) -> Instruction {
+    let mut data = vec![5u8];
+    data.extend_from_slice(&receive.to_le_bytes());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(escrow.maker, true),
+            AccountMeta::new(*escrow_address, false),
+            AccountMeta::new_readonly(escrow.mint_a, false),
+            AccountMeta::new_readonly(*mint_b, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// TopUp：maker 向金库追加 `amount` 个代币 A，整单报价改为 `receive`（追加后的总价，不是增量）；
+/// 代币 A 是原生 SOL 时程序直接从 maker 扣 lamports，末尾的 system_program 总是带上
+pub fn top_up_ix(
+    program_id: &Pubkey,
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    amount: u64,
+    receive: u64,
+) -> Instruction {
+    let mut data = vec![6u8];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&receive.to_le_bytes());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(escrow.maker, true),
+            AccountMeta::new(*escrow_address, false),
+            AccountMeta::new_readonly(escrow.mint_a, false),
+            AccountMeta::new(ata(&escrow.maker, &escrow.mint_a), false),
+            AccountMeta::new(escrow.vault(escrow_address), false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// Refund：代币 A 退回 maker，托管账户关闭给 maker；`caller` 签名并在需要时支付 maker ATA 的租金。
+/// 未过期时 `caller` 必须是 maker 本人，过期后任何人都可以调用
+pub fn refund_ix(
+    program_id: &Pubkey,
+    caller: &Pubkey,
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*caller, true),
+            AccountMeta::new(escrow.maker, false),
+            AccountMeta::new(*escrow_address, false),
+            AccountMeta::new_readonly(escrow.mint_a, false),
+            AccountMeta::new(escrow.vault(escrow_address), false),
+            AccountMeta::new(ata(&escrow.maker, &escrow.mint_a), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new(offer_index_address(program_id, &escrow.maker).0, false),
+        ],
+        data: vec![2],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escrow_round_trip() {
+        let escrow = Escrow {
+            seed: 42,
+            maker: Pubkey::new_unique(),
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            receive: 1_000,
+            bump: 254,
+            expiry: 1_700_000_000,
+            taker: Pubkey::new_unique(),
+        };
+        let mut data = Vec::with_capacity(Escrow::LEN);
+        data.push(Escrow::DISCRIMINATOR);
+        data.extend_from_slice(&escrow.seed.to_le_bytes());
+        data.extend_from_slice(escrow.maker.as_ref());
+        data.extend_from_slice(escrow.mint_a.as_ref());
+        data.extend_from_slice(escrow.mint_b.as_ref());
+        data.extend_from_slice(&escrow.receive.to_le_bytes());
+        data.push(escrow.bump);
+        data.extend_from_slice(&escrow.expiry.to_le_bytes());
+        data.extend_from_slice(escrow.taker.as_ref());
+
+        assert_eq!(Escrow::try_from_bytes(&data), Ok(escrow.clone()));
+        assert!(Escrow::try_from_bytes(&data[1..]).is_err());
+        let mut closed = data.clone();
+        closed[0] = 0xff;
+        assert!(Escrow::try_from_bytes(&closed).is_err());
+
+        assert!(!escrow.is_expired(escrow.expiry - 1));
+        assert!(escrow.is_expired(escrow.expiry));
+        assert!(escrow.allows_taker(&escrow.taker));
+        assert!(!escrow.allows_taker(&escrow.maker));
+        let open = Escrow {
+            expiry: 0,
+            taker: Pubkey::default(),
+            ..escrow
+        };
+        assert!(!open.is_expired(i64::MAX));
+        assert!(open.allows_taker(&open.maker));
+    }
+
+    #[test]
+    fn make_appends_expiry_and_designated_taker() {
+        let (maker, taker) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let private = make_ix(
+            &PROGRAM_ID,
+            &maker,
+            &mint_a,
+            &mint_b,
+            1,
+            2,
+            3,
+            4,
+            Some(taker),
+        );
+        assert_eq!(private.data.len(), 1 + 32 + 32);
+        assert_eq!(private.data[25..33], 4i64.to_le_bytes());
+        assert_eq!(private.data[33..], taker.to_bytes());
+
+        let open = make_ix(&PROGRAM_ID, &maker, &mint_a, &mint_b, 1, 2, 3, 4, None);
+        assert_eq!(open.data[..33], private.data[..33]);
+        assert_eq!(open.data[33..], [0; 32]);
+        assert_eq!(
+            open.accounts.last(),
+            Some(&AccountMeta::new(
+                offer_index_address(&PROGRAM_ID, &maker).0,
+                false
+            ))
+        );
+    }
+
+    #[test]
+    fn refund_caller_signs_and_maker_receives() {
+        let escrow = Escrow {
+            seed: 3,
+            maker: Pubkey::new_unique(),
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            receive: 10,
+            bump: 255,
+            expiry: 1,
+            taker: Pubkey::default(),
+        };
+        let (caller, address) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let refund = refund_ix(&PROGRAM_ID, &caller, &address, &escrow);
+        assert_eq!(refund.accounts[0], AccountMeta::new(caller, true));
+        assert_eq!(refund.accounts[1], AccountMeta::new(escrow.maker, false));
+        assert_eq!(
+            refund.accounts[5].pubkey,
+            ata(&escrow.maker, &escrow.mint_a)
+        );
+        assert_eq!(
+            refund.accounts[9].pubkey,
+            offer_index_address(&PROGRAM_ID, &escrow.maker).0
+        );
+        assert_eq!(refund.data, [2]);
+    }
+
+    #[test]
+    fn partial_take_pays_proportionally_and_rounds_up() {
+        let escrow = Escrow {
+            seed: 1,
+            maker: Pubkey::new_unique(),
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            receive: 1_000,
+            bump: 255,
+            expiry: 0,
+            taker: Pubkey::default(),
+        };
+        // 金库 3 个代币 A 换 1000 个 B：买 1 个付 334，零头归 maker
+        assert_eq!(escrow.partial_receive(1, 3), Some(334));
+        assert_eq!(escrow.partial_receive(3, 3), Some(1_000));
+        assert_eq!(escrow.partial_receive(0, 3), None);
+        assert_eq!(escrow.partial_receive(4, 3), None);
+        // receive 比金库余额小时每笔至少付 1，但不能在还剩代币 A 时把 receive 付清
+        let cheap = Escrow {
+            receive: 2,
+            ..escrow.clone()
+        };
+        assert_eq!(cheap.partial_receive(1, 1_000), Some(1));
+        assert_eq!(cheap.partial_receive(999, 1_000), None);
+        assert_eq!(cheap.partial_receive(1_000, 1_000), Some(2));
+
+        let address = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let take = take_ix(&PROGRAM_ID, &escrow.maker, &address, &escrow, &treasury);
+        let partial = take_partial_ix(&PROGRAM_ID, &escrow.maker, &address, &escrow, &treasury, 7);
+        assert_eq!(partial.accounts, take.accounts);
+        assert_eq!(partial.data[0], 3);
+        assert_eq!(partial.data[1..], 7u64.to_le_bytes());
+
+        // 价格上限追加在原有数据之后
+        assert_eq!(with_max_receive(take, 80).data[1..], 80u64.to_le_bytes());
+        assert_eq!(with_max_receive(partial, 12).data[9..], 12u64.to_le_bytes());
+    }
+
+    #[test]
+    fn update_and_top_up_are_signed_by_maker() {
+        let escrow = Escrow {
+            seed: 9,
+            maker: Pubkey::new_unique(),
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            receive: 10,
+            bump: 255,
+            expiry: 0,
+            taker: Pubkey::default(),
+        };
+        let (address, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let update = update_offer_ix(&PROGRAM_ID, &address, &escrow, &mint_b, 25);
+        assert_eq!(
+            update.accounts[0],
+            AccountMeta::new_readonly(escrow.maker, true)
+        );
+        assert_eq!(update.accounts[1], AccountMeta::new(address, false));
+        assert_eq!(update.accounts[3].pubkey, mint_b);
+        assert_eq!(update.data[0], 5);
+        assert_eq!(update.data[1..], 25u64.to_le_bytes());
+
+        let top_up = top_up_ix(&PROGRAM_ID, &address, &escrow, 7, 30);
+        // 原生 SOL 的 TopUp 从 maker 扣 lamports，所以 maker 可写
+        assert_eq!(top_up.accounts[0], AccountMeta::new(escrow.maker, true));
+        assert_eq!(top_up.accounts[4].pubkey, escrow.vault(&address));
+        assert_eq!(top_up.accounts[6].pubkey, system_program::ID);
+        assert_eq!(top_up.data[0], 6);
+        assert_eq!(top_up.data[1..9], 7u64.to_le_bytes());
+        assert_eq!(top_up.data[9..], 30u64.to_le_bytes());
+    }
+
+    #[test]
+    fn take_routes_protocol_fee_to_treasury() {
+        let escrow = Escrow {
+            seed: 1,
+            maker: Pubkey::new_unique(),
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            receive: 1_000,
+            bump: 255,
+            expiry: 0,
+            taker: Pubkey::default(),
+        };
+        let (taker, treasury) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let take = take_ix(
+            &PROGRAM_ID,
+            &taker,
+            &Pubkey::new_unique(),
+            &escrow,
+            &treasury,
+        );
+        assert_eq!(take.accounts.len(), 15);
+        assert_eq!(
+            take.accounts[12],
+            AccountMeta::new_readonly(config_address(&PROGRAM_ID).0, false)
+        );
+        assert_eq!(
+            take.accounts[13],
+            AccountMeta::new(ata(&treasury, &escrow.mint_b), false)
+        );
+        // 挂单索引按 maker 派生，与 taker 无关
+        assert_eq!(
+            take.accounts[14],
+            AccountMeta::new(offer_index_address(&PROGRAM_ID, &escrow.maker).0, false)
+        );
+
+        let init = init_config_ix(&PROGRAM_ID, &taker, 250, &treasury);
+        assert_eq!(init.data[..3], [4, 250, 0]);
+        assert_eq!(init.data[3..], treasury.to_bytes());
+        assert_eq!(init.accounts[2].pubkey, config_address(&PROGRAM_ID).0);
+
+        // 2.5%：1000 个代币 B 扣 25 个，零头不收
+        let config = Config {
+            treasury,
+            fee_bps: 250,
+            bump: 255,
+        };
+        assert_eq!(config.fee(1_000), 25);
+        assert_eq!(config.fee(39), 0);
+    }
+
+    /// 与 task5 程序的布局测试共用同一份快照
+    #[test]
+    fn parses_config_layout_snapshot() {
+        let data = layout_snapshot::snapshot_bytes(include_str!(
+            "../../../task5/blueshift_escrow/snapshots/config.layout"
+        ));
+        assert_eq!(
+            Config::try_from_bytes(&data),
+            Ok(Config {
+                treasury: Pubkey::new_from_array([0x55; 32]),
+                fee_bps: 50,
+                bump: 0xfd,
+            })
+        );
+    }
+
+    /// 与 task5 程序的布局测试共用同一份快照
+    #[test]
+    fn parses_offer_index_layout_snapshot() {
+        let mut data = layout_snapshot::snapshot_bytes(include_str!(
+            "../../../task5/blueshift_escrow/snapshots/offer_index.layout"
+        ));
+        let index = OfferIndex::try_from_bytes(&data).unwrap();
+        assert_eq!(
+            index,
+            OfferIndex {
+                bump: 0xfc,
+                seeds: vec![9, 0x0807_0605_0403_0201],
+            }
+        );
+        let maker = Pubkey::new_unique();
+        assert_eq!(
+            index.escrows(&PROGRAM_ID, &maker)[0],
+            escrow_address(&PROGRAM_ID, &maker, 9).0
+        );
+
+        data[2] = OfferIndex::MAX_OPEN_OFFERS as u8 + 1;
+        assert!(OfferIndex::try_from_bytes(&data).is_err());
+    }
+
+    /// 与 task5 程序的布局测试共用同一份快照
+    #[test]
+    fn parses_program_layout_snapshot() {
+        let data = layout_snapshot::snapshot_bytes(include_str!(
+            "../../../task5/blueshift_escrow/snapshots/escrow.layout"
+        ));
+        assert_eq!(
+            Escrow::try_from_bytes(&data),
+            Ok(Escrow {
+                seed: 0x0807_0605_0403_0201,
+                maker: Pubkey::new_from_array([0x11; 32]),
+                mint_a: Pubkey::new_from_array([0x22; 32]),
+                mint_b: Pubkey::new_from_array([0x33; 32]),
+                receive: 1_000_000,
+                bump: 0xfe,
+                expiry: 1_767_225_600,
+                taker: Pubkey::new_from_array([0x44; 32]),
+            })
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! programs/blueshift_governance：CreateCouncil = 0、Propose = 1、Approve = 2、Execute = 3、SetMembers = 4。
+//!
+//! 提案保存一条完整的指令，通常是以理事会签名地址（[`authority_address`]）为 authority
+//! 的其他程序指令，例如 `amm::Pool::propose_update_ix`。[`propose_ix`] 与 [`execute_ix`]
+//! 接收同一条 `Instruction`，前者把它编码进提案，后者按相同顺序附上它引用的账户。
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sdk_ids::system_program;
+
+use crate::pda;
+
+pub const PROGRAM_ID: Pubkey = pubkey!("Gov1111111111111111111111111111111111111111");
+
+pub use pdas::{COUNCIL_SEED, GOVERNANCE_AUTHORITY_SEED, PROPOSAL_SEED};
+
+/// 账户元信息标志，与程序 `state::META_SIGNER` / `META_WRITABLE` 一致
+const META_SIGNER: u8 = 1 << 0;
+const META_WRITABLE: u8 = 1 << 1;
+
+/// 理事会 PDA：[b"council", seed (LE)]
+pub fn council_address(program_id: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    pda(pdas::council_pda(&program_id.to_bytes(), seed))
+}
+
+/// 提案 PDA：[b"proposal", council, index (LE)]
+pub fn proposal_address(program_id: &Pubkey, council: &Pubkey, index: u64) -> (Pubkey, u8) {
+    pda(pdas::proposal_pda(
+        &program_id.to_bytes(),
+        &council.to_bytes(),
+        index,
+    ))
+}
+
+/// 理事会签名地址：[b"authority", council]，设为 AMM 池子等程序的 authority
+pub fn authority_address(program_id: &Pubkey, council: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::governance_authority_pda(
+        &program_id.to_bytes(),
+        &council.to_bytes(),
+    ))
+}
+
+fn members_data(threshold: u8, members: &[Pubkey]) -> Vec<u8> {
+    let mut data = vec![threshold];
+    for member in members {
+        data.extend_from_slice(member.as_ref());
+    }
+    data
+}
+
+/// CreateCouncil：最多 8 名成员，`threshold` 票通过
+pub fn create_council_ix(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    seed: u64,
+    threshold: u8,
+    members: &[Pubkey],
+) -> Instruction {
+    let mut data = vec![0u8];
+    data.extend_from_slice(&seed.to_le_bytes());
+    data.extend(members_data(threshold, members));
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(council_address(program_id, seed).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// Propose：`index` 为理事会当前的提案计数（链上 Council 的 proposal_count）
+pub fn propose_ix(
+    program_id: &Pubkey,
+    proposer: &Pubkey,
+    council: &Pubkey,
+    index: u64,
+    instruction: &Instruction,
+) -> Instruction {
+    let mut data = vec![1u8];
+    data.extend_from_slice(instruction.program_id.as_ref());
+    data.push(instruction.accounts.len() as u8);
+    for meta in &instruction.accounts {
+        data.extend_from_slice(meta.pubkey.as_ref());
+        let mut flags = 0;
+        if meta.is_signer {
+            flags |= META_SIGNER;
+        }
+        if meta.is_writable {
+            flags |= META_WRITABLE;
+        }
+        data.push(flags);
+    }
+    data.extend_from_slice(&instruction.data);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*proposer, true),
+            AccountMeta::new(*council, false),
+            AccountMeta::new(proposal_address(program_id, council, index).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// Approve：成员投赞成票
+pub fn approve_ix(
+    program_id: &Pubkey,
+    member: &Pubkey,
+    council: &Pubkey,
+    index: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*member, true),
+            AccountMeta::new_readonly(*council, false),
+            AccountMeta::new(proposal_address(program_id, council, index).0, false),
+        ],
+        data: vec![2],
+    }
+}
+
+/// Execute：`instruction` 必须与提案时传入的相同；任何人都可以发送，不需要签名
+pub fn execute_ix(
+    program_id: &Pubkey,
+    council: &Pubkey,
+    index: u64,
+    instruction: &Instruction,
+) -> Instruction {
+    let authority = authority_address(program_id, council).0;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*council, false),
+        AccountMeta::new(proposal_address(program_id, council, index).0, false),
+        AccountMeta::new_readonly(authority, false),
+        AccountMeta::new_readonly(instruction.program_id, false),
+    ];
+    // 签名地址由程序以 PDA 签名，外层交易中不是签名者
+    accounts.extend(instruction.accounts.iter().map(|meta| AccountMeta {
+        is_signer: meta.is_signer && meta.pubkey != authority,
+        ..meta.clone()
+    }));
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: vec![3],
+    }
+}
+
+/// SetMembers：只能作为提案内容，由理事会签名地址签名
+pub fn set_members_ix(
+    program_id: &Pubkey,
+    council: &Pubkey,
+    threshold: u8,
+    members: &[Pubkey],
+) -> Instruction {
+    let mut data = vec![4u8];
+    data.extend(members_data(threshold, members));
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority_address(program_id, council).0, true),
+            AccountMeta::new(*council, false),
+        ],
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "amm")]
+    #[test]
+    fn proposal_encodes_amm_fee_update() {
+        let (council, _) = council_address(&PROGRAM_ID, 1);
+        let (authority, _) = authority_address(&PROGRAM_ID, &council);
+        let pool = crate::amm::Pool::seeded(
+            &crate::amm::PROGRAM_ID,
+            0,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        );
+        let mut value = [0u8; 32];
+        value[..2].copy_from_slice(&25u16.to_le_bytes());
+        let update = pool.propose_update_ix(&authority, 1, value);
+
+        let proposer = Pubkey::new_unique();
+        let ix = propose_ix(&PROGRAM_ID, &proposer, &council, 0, &update);
+        assert_eq!(ix.data[0], 1);
+        assert_eq!(ix.data[1..33], pool.program_id.to_bytes());
+        assert_eq!(ix.data[33], 2);
+        assert_eq!(ix.data[34..66], authority.to_bytes());
+        assert_eq!(ix.data[66], META_SIGNER);
+        assert_eq!(ix.data[67..99], pool.config.to_bytes());
+        assert_eq!(ix.data[99], META_WRITABLE);
+        assert_eq!(ix.data[100..], update.data);
+    }
+
+    #[test]
+    fn execute_appends_instruction_accounts_without_signature() {
+        let (council, _) = council_address(&PROGRAM_ID, 1);
+        let (authority, _) = authority_address(&PROGRAM_ID, &council);
+        let rotate = set_members_ix(&PROGRAM_ID, &council, 1, &[Pubkey::new_unique()]);
+        let ix = execute_ix(&PROGRAM_ID, &council, 3, &rotate);
+
+        assert_eq!(ix.accounts[2].pubkey, authority);
+        assert_eq!(ix.accounts[3].pubkey, PROGRAM_ID);
+        assert_eq!(ix.accounts[4].pubkey, authority);
+        assert!(ix.accounts.iter().all(|meta| !meta.is_signer));
+        assert_eq!(
+            ix.accounts[1].pubkey,
+            proposal_address(&PROGRAM_ID, &council, 3).0
+        );
+    }
+}
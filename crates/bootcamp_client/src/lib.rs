@@ -0,0 +1,143 @@
+//! 训练营各程序的 Rust 客户端：指令构造、PDA 派生与账户解析。
+//!
+//! 账户顺序与数据布局与各程序 `TryFrom` / `#[derive(Accounts)]` 中的定义一一对应，
+//! 程序改动布局时这里要同步修改。所有构造函数都显式接收 `program_id`，
+//! 部署到非默认地址时传入实际地址即可。
+//!
+//! 指令构造、PDA 派生、账户解析与报价只依赖 solana-pubkey / solana-instruction，
+//! 关闭 `rpc`（默认关闭）时可以编译到 `wasm32-unknown-unknown`，浏览器前端直接复用同一套计算：
+//!
+//! ```text
+//! cargo build -p bootcamp_client --target wasm32-unknown-unknown
+//! ```
+
+use core::fmt;
+
+pub mod alt;
+pub mod diagnostics;
+pub mod token;
+pub mod version;
+
+#[cfg(feature = "vault")]
+pub mod vault;
+
+#[cfg(feature = "escrow")]
+pub mod escrow;
+
+#[cfg(feature = "amm")]
+pub mod amm;
+
+#[cfg(feature = "router")]
+pub mod router;
+
+#[cfg(feature = "governance")]
+pub mod governance;
+
+#[cfg(feature = "oracle")]
+pub mod oracle;
+
+#[cfg(feature = "treasury")]
+pub mod treasury;
+
+#[cfg(feature = "upgrade_guard")]
+pub mod upgrade_guard;
+
+#[cfg(feature = "rpc")]
+pub mod tx;
+
+/// 构造函数使用的地址与指令类型，与 solana-sdk 2.x 中的同名类型相同
+pub use solana_instruction::{AccountMeta, Instruction};
+pub use solana_pubkey::Pubkey;
+
+/// `rpc` 使用的 solana-sdk 版本，调用方可以直接复用，避免版本不一致
+#[cfg(feature = "rpc")]
+pub use solana_sdk;
+
+/// 程序事件的定义与解析，交易日志用 `events::events_from_logs` 处理
+pub use blueshift_events as events;
+
+/// 各程序的种子布局，与链上程序共用；下面各模块的 `*_address` 是它的 `Pubkey` 版本
+pub use pdas;
+
+#[cfg(feature = "serde")]
+pub(crate) mod serde_pubkey;
+
+/// 账户数据无法按预期布局解析。`expected == actual` 表示长度正确、但开头的判别器不符
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AccountDataError {
+    pub account: &'static str,
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl AccountDataError {
+    /// 长度正确但判别器不符（其它类型的账户，或已关闭的账户）
+    #[cfg(feature = "escrow")]
+    pub(crate) fn discriminator(account: &'static str, data: &[u8]) -> Self {
+        Self {
+            account,
+            expected: data.len(),
+            actual: data.len(),
+        }
+    }
+}
+
+impl fmt::Display for AccountDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.expected == self.actual {
+            return write!(f, "{} 账户的判别器不符", self.account);
+        }
+        write!(
+            f,
+            "{} 账户长度应为 {} 字节，实际为 {}",
+            self.account, self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for AccountDataError {}
+
+/// 校验账户长度，返回定长数组引用，供各程序的解析函数按偏移读取字段
+#[cfg(any(
+    feature = "vault",
+    feature = "escrow",
+    feature = "amm",
+    feature = "treasury",
+    feature = "upgrade_guard"
+))]
+pub(crate) fn fixed<'a, const N: usize>(
+    account: &'static str,
+    data: &'a [u8],
+) -> Result<&'a [u8; N], AccountDataError> {
+    data.try_into().map_err(|_| AccountDataError {
+        account,
+        expected: N,
+        actual: data.len(),
+    })
+}
+
+/// `pdas` 返回的 `([u8; 32], bump)` 转为 solana-sdk 的地址
+#[cfg(any(
+    feature = "vault",
+    feature = "escrow",
+    feature = "amm",
+    feature = "governance",
+    feature = "oracle",
+    feature = "treasury",
+    feature = "upgrade_guard"
+))]
+pub(crate) fn pda((address, bump): (pdas::Address, u8)) -> (Pubkey, u8) {
+    (Pubkey::new_from_array(address), bump)
+}
+
+/// 从 `data[offset..]` 读取定长字段
+#[cfg(any(
+    feature = "vault",
+    feature = "escrow",
+    feature = "amm",
+    feature = "treasury",
+    feature = "upgrade_guard"
+))]
+pub(crate) fn read<const N: usize>(data: &[u8], offset: usize) -> [u8; N] {
+    data[offset..offset + N].try_into().unwrap()
+}
@@ -0,0 +1,84 @@
+//! programs/blueshift_mock_oracle：InitFeed = 0、SetPrice = 1。
+//!
+//! 只用于测试与 localnet：价格账户的布局和校验在 `blueshift_oracle` 中，依赖价格的程序
+//! 读取同样的布局，因此在测试里用 [`set_price_ix`] 写入过期或偏离的报价即可覆盖对应分支。
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sdk_ids::system_program;
+
+use crate::pda;
+
+pub use blueshift_oracle::{OracleError, Price, PriceFeed};
+pub use pdas::PRICE_FEED_SEED;
+
+pub const PROGRAM_ID: Pubkey = pubkey!("Mock111111111111111111111111111111111111111");
+
+/// 价格账户 PDA：[b"price_feed", feed_id]
+pub fn price_feed_address(program_id: &Pubkey, feed_id: &[u8; 32]) -> (Pubkey, u8) {
+    pda(pdas::price_feed_pda(&program_id.to_bytes(), feed_id))
+}
+
+/// InitFeed：`authority` 付租金并成为唯一可以改写价格的账户
+pub fn init_feed_ix(program_id: &Pubkey, authority: &Pubkey, feed_id: &[u8; 32]) -> Instruction {
+    let mut data = vec![0u8];
+    data.extend_from_slice(feed_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(price_feed_address(program_id, feed_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// SetPrice：程序不检查报价是否合理，负价格或未来的发布时间都会原样写入
+pub fn set_price_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    feed_id: &[u8; 32],
+    price: &Price,
+) -> Instruction {
+    let mut data = vec![1u8];
+    data.extend_from_slice(&price.encode());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(price_feed_address(program_id, feed_id).0, false),
+        ],
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_price_targets_feed_pda() {
+        let feed_id = [9; 32];
+        let authority = Pubkey::new_unique();
+        let price = Price {
+            price: -5,
+            conf: 1,
+            expo: -8,
+            publish_time: 1_700_000_000,
+        };
+        let ix = set_price_ix(&PROGRAM_ID, &authority, &feed_id, &price);
+        assert_eq!(ix.data[0], 1);
+        assert_eq!(Price::decode(&ix.data[1..]), Some(price));
+        assert_eq!(
+            ix.accounts[1].pubkey,
+            init_feed_ix(&PROGRAM_ID, &authority, &feed_id).accounts[1].pubkey
+        );
+        assert_eq!(
+            ix.accounts[1].pubkey,
+            Pubkey::find_program_address(&[PRICE_FEED_SEED, &feed_id], &PROGRAM_ID).0
+        );
+    }
+}
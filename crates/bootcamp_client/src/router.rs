@@ -0,0 +1,303 @@
+//! programs/blueshift_router：VaultToEscrow = 0、TakeAndDeposit = 1、TakeAndSwap = 2。
+//!
+//! 路由通过 CPI 调用 task4 金库、task5 托管与 task6 原生 AMM，这三个程序的实际部署地址
+//! 作为参数传入（它们的默认地址相同，不能同时部署）。前两条指令的数据分别与托管 Make、AMM Deposit 相同。
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sdk_ids::system_program;
+
+use crate::{
+    amm::Pool,
+    escrow::{config_address, escrow_address, offer_index_address, Escrow},
+    token::{ata, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, TOKEN_PROGRAM_ID},
+    vault::vault_address,
+};
+
+pub const PROGRAM_ID: Pubkey = pubkey!("Router1111111111111111111111111111111111111");
+
+/// VaultToEscrow：取出金库，把其中 amount lamports 包装成 wSOL 挂托管单换 receive 个 mint_b，
+/// 剩余部分存回金库
+#[allow(clippy::too_many_arguments)]
+pub fn vault_to_escrow_ix(
+    program_id: &Pubkey,
+    vault_program: &Pubkey,
+    escrow_program: &Pubkey,
+    owner: &Pubkey,
+    mint_b: &Pubkey,
+    seed: u64,
+    receive: u64,
+    amount: u64,
+    expiry: i64,
+    taker: Option<Pubkey>,
+) -> Instruction {
+    let (escrow, _) = escrow_address(escrow_program, owner, seed);
+    let mut data = vec![0u8];
+    data.extend_from_slice(&seed.to_le_bytes());
+    data.extend_from_slice(&receive.to_le_bytes());
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&expiry.to_le_bytes());
+    data.extend_from_slice(taker.unwrap_or_default().as_ref());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(vault_address(vault_program, owner).0, false),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new_readonly(NATIVE_MINT, false),
+            AccountMeta::new_readonly(*mint_b, false),
+            AccountMeta::new(ata(owner, &NATIVE_MINT), false),
+            AccountMeta::new(ata(&escrow, &NATIVE_MINT), false),
+            AccountMeta::new(offer_index_address(escrow_program, owner).0, false),
+            AccountMeta::new_readonly(*vault_program, false),
+            AccountMeta::new_readonly(*escrow_program, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// TakeAndDeposit：吃掉托管单，再向包含 mint_a 的 `pool` 存入流动性，铸造 lp_amount 个 LP。
+/// `treasury` 与 [`crate::escrow::take_ix`] 相同，接收托管的协议手续费
+#[allow(clippy::too_many_arguments)]
+pub fn take_and_deposit_ix(
+    program_id: &Pubkey,
+    escrow_program: &Pubkey,
+    taker: &Pubkey,
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    treasury: &Pubkey,
+    pool: &Pool,
+    lp_amount: u64,
+    max_x: u64,
+    max_y: u64,
+) -> Instruction {
+    let (taker_x, taker_y, taker_lp) = pool.user_atas(taker);
+    let mut data = vec![1u8];
+    for arg in [lp_amount, max_x, max_y] {
+        data.extend_from_slice(&arg.to_le_bytes());
+    }
+    data.extend_from_slice(&i64::MAX.to_le_bytes()); // expiration：不过期
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new(escrow.maker, false),
+            AccountMeta::new(*escrow_address, false),
+            AccountMeta::new_readonly(escrow.mint_a, false),
+            AccountMeta::new_readonly(escrow.mint_b, false),
+            AccountMeta::new(escrow.vault(escrow_address), false),
+            AccountMeta::new(ata(taker, &escrow.mint_a), false),
+            AccountMeta::new(ata(taker, &escrow.mint_b), false),
+            AccountMeta::new(ata(&escrow.maker, &escrow.mint_b), false),
+            AccountMeta::new_readonly(config_address(escrow_program).0, false),
+            AccountMeta::new(ata(treasury, &escrow.mint_b), false),
+            AccountMeta::new(offer_index_address(escrow_program, &escrow.maker).0, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new(pool.mint_lp, false),
+            AccountMeta::new(pool.vault_x, false),
+            AccountMeta::new(pool.vault_y, false),
+            AccountMeta::new(taker_x, false),
+            AccountMeta::new(taker_y, false),
+            AccountMeta::new(taker_lp, false),
+            AccountMeta::new_readonly(*escrow_program, false),
+            AccountMeta::new_readonly(pool.program_id, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// TakeAndSwap：吃掉托管单，把换到的 mint_a 全部卖回由 mint_a / mint_b 组成的 `pool`。
+/// AMM 的最低输出取 `min_out` 与托管单 receive 中较大的一个
+#[allow(clippy::too_many_arguments)]
+pub fn take_and_swap_ix(
+    program_id: &Pubkey,
+    escrow_program: &Pubkey,
+    taker: &Pubkey,
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    treasury: &Pubkey,
+    pool: &Pool,
+    min_out: u64,
+) -> Instruction {
+    let mut data = vec![2u8];
+    data.extend_from_slice(&min_out.to_le_bytes());
+    data.extend_from_slice(&i64::MAX.to_le_bytes()); // expiration：不过期
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new(escrow.maker, false),
+            AccountMeta::new(*escrow_address, false),
+            AccountMeta::new_readonly(escrow.mint_a, false),
+            AccountMeta::new_readonly(escrow.mint_b, false),
+            AccountMeta::new(escrow.vault(escrow_address), false),
+            AccountMeta::new(ata(taker, &escrow.mint_a), false),
+            AccountMeta::new(ata(taker, &escrow.mint_b), false),
+            AccountMeta::new(ata(&escrow.maker, &escrow.mint_b), false),
+            AccountMeta::new_readonly(config_address(escrow_program).0, false),
+            AccountMeta::new(ata(treasury, &escrow.mint_b), false),
+            AccountMeta::new(offer_index_address(escrow_program, &escrow.maker).0, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new(pool.vault_x, false),
+            AccountMeta::new(pool.vault_y, false),
+            AccountMeta::new_readonly(*escrow_program, false),
+            AccountMeta::new_readonly(pool.program_id, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::escrow;
+
+    /// 内层指令用到的每个账户都要出现在路由指令中，且可写性不能更弱
+    fn assert_covers(outer: &Instruction, inner: &Instruction) {
+        for meta in &inner.accounts {
+            let found = outer
+                .accounts
+                .iter()
+                .find(|outer_meta| outer_meta.pubkey == meta.pubkey)
+                .unwrap_or_else(|| panic!("路由指令缺少账户 {}", meta.pubkey));
+            assert!(
+                found.is_writable || !meta.is_writable,
+                "{} 应可写",
+                meta.pubkey
+            );
+            assert!(found.is_signer || !meta.is_signer, "{} 应签名", meta.pubkey);
+        }
+        assert!(outer
+            .accounts
+            .iter()
+            .any(|meta| meta.pubkey == inner.program_id));
+    }
+
+    #[test]
+    fn vault_to_escrow_forwards_make() {
+        let (vault_program, escrow_program) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (owner, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let taker = Pubkey::new_unique();
+        let ix = vault_to_escrow_ix(
+            &PROGRAM_ID,
+            &vault_program,
+            &escrow_program,
+            &owner,
+            &mint_b,
+            7,
+            80,
+            50,
+            1_767_225_600,
+            Some(taker),
+        );
+        let make = escrow::make_ix(
+            &escrow_program,
+            &owner,
+            &NATIVE_MINT,
+            &mint_b,
+            7,
+            80,
+            50,
+            1_767_225_600,
+            Some(taker),
+        );
+
+        assert_eq!(ix.data[0], 0);
+        assert_eq!(ix.data[1..], make.data[1..]);
+        assert_covers(&ix, &make);
+        // task4 金库的 Withdraw 只有 Anchor 版的前三个账户，没有事件 CPI 账户
+        let mut withdraw = crate::vault::withdraw_ix(&vault_program, &owner);
+        withdraw.accounts.truncate(3);
+        assert_covers(&ix, &withdraw);
+    }
+
+    #[test]
+    fn take_and_deposit_forwards_take_and_deposit() {
+        let (escrow_program, amm_program) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let taker = Pubkey::new_unique();
+        let (mint_a, mint_y) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (escrow_key, treasury) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let escrow = Escrow {
+            seed: 1,
+            maker: Pubkey::new_unique(),
+            mint_a,
+            mint_b: Pubkey::new_unique(),
+            receive: 80,
+            bump: 255,
+            expiry: 0,
+            taker: Pubkey::default(),
+        };
+        let pool = Pool::seeded(&amm_program, 0, &mint_a, &mint_y);
+        let ix = take_and_deposit_ix(
+            &PROGRAM_ID,
+            &escrow_program,
+            &taker,
+            &escrow_key,
+            &escrow,
+            &treasury,
+            &pool,
+            100,
+            10,
+            20,
+        );
+        let deposit = pool.deposit_ix(&taker, 100, 10, 20);
+
+        assert_eq!(ix.data[0], 1);
+        assert_eq!(ix.data[1..], deposit.data[1..]);
+        assert_covers(
+            &ix,
+            &escrow::take_ix(&escrow_program, &taker, &escrow_key, &escrow, &treasury),
+        );
+        assert_covers(&ix, &deposit);
+    }
+
+    #[test]
+    fn take_and_swap_forwards_take_and_swap() {
+        let (escrow_program, amm_program) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let taker = Pubkey::new_unique();
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (escrow_key, treasury) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let escrow = Escrow {
+            seed: 1,
+            maker: Pubkey::new_unique(),
+            mint_a,
+            mint_b,
+            receive: 80,
+            bump: 255,
+            expiry: 0,
+            taker: Pubkey::default(),
+        };
+        // mint_a 在池子的 Y 侧，卖出 A 对应 is_x = false
+        let pool = Pool::seeded(&amm_program, 0, &mint_b, &mint_a);
+        let ix = take_and_swap_ix(
+            &PROGRAM_ID,
+            &escrow_program,
+            &taker,
+            &escrow_key,
+            &escrow,
+            &treasury,
+            &pool,
+            90,
+        );
+
+        assert_eq!(ix.data[0], 2);
+        assert_eq!(ix.data[1..9], 90u64.to_le_bytes());
+        assert_covers(
+            &ix,
+            &escrow::take_ix(&escrow_program, &taker, &escrow_key, &escrow, &treasury),
+        );
+        assert_covers(&ix, &pool.swap_ix(&taker, false, 50, 90));
+    }
+}
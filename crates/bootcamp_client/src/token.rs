@@ -0,0 +1,81 @@
+//! SPL Token 与 ATA 程序的常用地址和指令；Token-2022 只提供程序地址与 ATA 推导
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sdk_ids::system_program;
+
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+/// wSOL：SPL Token 上与 SOL 1:1 包装的 mint
+pub const NATIVE_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+/// `owner` 在 `mint` 上的 ATA（SPL Token）
+pub fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    ata_with_program(owner, mint, &TOKEN_PROGRAM_ID)
+}
+
+/// `owner` 在 `mint` 上的 ATA；`token_program` 是 mint 所属的代币程序（SPL Token 或 Token-2022）
+pub fn ata_with_program(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// ATA 程序的 CreateIdempotent（判别器 1）：账户已存在时不报错，
+/// 用于在存入 / 兑换前确保收款的代币账户存在。
+pub fn create_ata_idempotent_ix(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(ata(owner, mint), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data: vec![1],
+    }
+}
+
+/// Mint 账户长度（SPL Token）
+pub const MINT_LEN: usize = 82;
+
+/// SPL Token 的 InitializeMint2（判别器 20）：不需要 Rent sysvar，没有冻结权限。
+/// `mint` 需要先由 System Program 以 [`MINT_LEN`] 字节、owner 为代币程序创建
+pub fn initialize_mint2_ix(mint: &Pubkey, decimals: u8, authority: &Pubkey) -> Instruction {
+    let mut data = vec![20, decimals];
+    data.extend_from_slice(authority.as_ref());
+    data.push(0); // freeze_authority: None
+
+    Instruction {
+        program_id: TOKEN_PROGRAM_ID,
+        accounts: vec![AccountMeta::new(*mint, false)],
+        data,
+    }
+}
+
+/// SPL Token 的 MintTo（判别器 7）：向 `destination` 代币账户铸造 `amount`
+pub fn mint_to_ix(
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut data = vec![7u8];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data,
+    }
+}
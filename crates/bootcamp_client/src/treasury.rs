@@ -0,0 +1,265 @@
+//! programs/blueshift_treasury：Initialize = 0、Deposit = 1、DepositToken = 2、Withdraw = 3、
+//! WithdrawToken = 4、Distribute = 5、SetRole = 6。
+//!
+//! 国库 PDA 每个部署只有一个（[`treasury_address`]），代币存在它的 ATA 中，
+//! DepositToken 之前需要先用 `token::create_ata_idempotent_ix` 创建。
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sdk_ids::system_program;
+
+use crate::{
+    fixed, pda, read,
+    token::{ata, TOKEN_PROGRAM_ID},
+    version::program_data_address,
+    AccountDataError,
+};
+
+pub const PROGRAM_ID: Pubkey = pubkey!("Treasury11111111111111111111111111111111111");
+
+pub use pdas::TREASURY_SEED;
+
+/// 存款来源，与程序 `state::source` 一致
+pub mod source {
+    pub const VAULT: u8 = 0;
+    pub const ESCROW: u8 = 1;
+    pub const AMM: u8 = 2;
+    pub const OTHER: u8 = 255;
+}
+
+/// 角色编号，与程序 `state::Role` 一致
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    Admin = 0,
+    Withdrawer = 1,
+    Distributor = 2,
+}
+
+/// 链上 Treasury 账户：bump + admin + withdrawer + distributor + 按来源累计的 SOL 存款
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Treasury {
+    pub bump: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub admin: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub withdrawer: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub distributor: Pubkey,
+    /// 金库、托管、AMM、其他来源各自累计存入的 lamports
+    pub deposited: [u64; 4],
+}
+
+impl Treasury {
+    pub const LEN: usize = 1 + 32 * 3 + 8 * 4;
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let data = fixed::<{ Self::LEN }>("treasury", data)?;
+        Ok(Self {
+            bump: data[0],
+            admin: Pubkey::new_from_array(read(data, 1)),
+            withdrawer: Pubkey::new_from_array(read(data, 33)),
+            distributor: Pubkey::new_from_array(read(data, 65)),
+            deposited: core::array::from_fn(|i| u64::from_le_bytes(read(data, 97 + i * 8))),
+        })
+    }
+}
+
+/// 国库 PDA：[b"treasury"]
+pub fn treasury_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::treasury_pda(&program_id.to_bytes()))
+}
+
+fn deposit_data(discriminator: u8, amount: u64, source: u8) -> Vec<u8> {
+    let mut data = vec![discriminator];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(source);
+    data
+}
+
+/// Initialize：`authority` 必须是程序的升级权限
+pub fn initialize_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    admin: &Pubkey,
+    withdrawer: &Pubkey,
+    distributor: &Pubkey,
+) -> Instruction {
+    let mut data = vec![0u8];
+    data.extend_from_slice(admin.as_ref());
+    data.extend_from_slice(withdrawer.as_ref());
+    data.extend_from_slice(distributor.as_ref());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(program_data_address(program_id), false),
+            AccountMeta::new(treasury_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// Deposit：存入 `amount` lamports
+pub fn deposit_ix(program_id: &Pubkey, depositor: &Pubkey, amount: u64, source: u8) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*depositor, true),
+            AccountMeta::new(treasury_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: deposit_data(1, amount, source),
+    }
+}
+
+/// DepositToken：从 depositor 的 ATA 存入 `amount` 个 `mint`
+pub fn deposit_token_ix(
+    program_id: &Pubkey,
+    depositor: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    source: u8,
+) -> Instruction {
+    let treasury = treasury_address(program_id).0;
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*depositor, true),
+            AccountMeta::new(ata(depositor, mint), false),
+            AccountMeta::new_readonly(treasury, false),
+            AccountMeta::new(ata(&treasury, mint), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data: deposit_data(2, amount, source),
+    }
+}
+
+/// Withdraw：提取角色取出 `amount` lamports 给 `recipient`
+pub fn withdraw_ix(
+    program_id: &Pubkey,
+    withdrawer: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut data = vec![3u8];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*withdrawer, true),
+            AccountMeta::new(treasury_address(program_id).0, false),
+            AccountMeta::new(*recipient, false),
+        ],
+        data,
+    }
+}
+
+/// WithdrawToken：转到 `recipient` 的 ATA（需已创建）
+pub fn withdraw_token_ix(
+    program_id: &Pubkey,
+    withdrawer: &Pubkey,
+    recipient: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let treasury = treasury_address(program_id).0;
+    let mut data = vec![4u8];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*withdrawer, true),
+            AccountMeta::new_readonly(treasury, false),
+            AccountMeta::new(ata(&treasury, mint), false),
+            AccountMeta::new(ata(recipient, mint), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// Distribute：`shares` 为 (收款地址, 基点)，基点合计必须是 10000，最多 8 个
+pub fn distribute_ix(
+    program_id: &Pubkey,
+    distributor: &Pubkey,
+    shares: &[(Pubkey, u16)],
+) -> Instruction {
+    let mut data = vec![5u8];
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*distributor, true),
+        AccountMeta::new(treasury_address(program_id).0, false),
+    ];
+    for (recipient, bps) in shares {
+        data.extend_from_slice(&bps.to_le_bytes());
+        accounts.push(AccountMeta::new(*recipient, false));
+    }
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// SetRole：管理员把 `role` 交给 `holder`
+pub fn set_role_ix(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    role: Role,
+    holder: &Pubkey,
+) -> Instruction {
+    let mut data = vec![6u8, role as u8];
+    data.extend_from_slice(holder.as_ref());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(treasury_address(program_id).0, false),
+        ],
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_treasury_account() {
+        let mut data = vec![254];
+        for byte in [1u8, 2, 3] {
+            data.extend_from_slice(&[byte; 32]);
+        }
+        for amount in [10u64, 20, 30, 40] {
+            data.extend_from_slice(&amount.to_le_bytes());
+        }
+        let treasury = Treasury::try_from_bytes(&data).unwrap();
+        assert_eq!(treasury.bump, 254);
+        assert_eq!(treasury.withdrawer, Pubkey::new_from_array([2; 32]));
+        assert_eq!(treasury.deposited, [10, 20, 30, 40]);
+        assert!(Treasury::try_from_bytes(&data[1..]).is_err());
+    }
+
+    #[test]
+    fn distribute_pairs_shares_with_recipients() {
+        let distributor = Pubkey::new_unique();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = distribute_ix(&PROGRAM_ID, &distributor, &[(a, 7_000), (b, 3_000)]);
+        assert_eq!(ix.data, [5, 0x58, 0x1b, 0xb8, 0x0b]);
+        assert_eq!(ix.accounts[1].pubkey, treasury_address(&PROGRAM_ID).0);
+        assert_eq!(ix.accounts[2].pubkey, a);
+        assert_eq!(ix.accounts[3].pubkey, b);
+        assert!(ix.accounts[2..].iter().all(|meta| meta.is_writable));
+
+        let deposit = deposit_ix(&PROGRAM_ID, &distributor, 5, source::AMM);
+        assert_eq!(deposit.data, [1, 5, 0, 0, 0, 0, 0, 0, 0, source::AMM]);
+    }
+}
@@ -0,0 +1,618 @@
+//! 发送交易（`rpc` feature）：自动加上计算预算指令，blockhash 过期时重新签名重试。
+//!
+//! devnet 拥堵时只靠默认的 200k CU / 零优先费，交易常常排不上块，
+//! 等到 blockhash 过期才以 "unable to confirm transaction" 失败。[`TxBuilder`] 在发送前：
+//!
+//! 1. 模拟一次交易，按实际消耗的 CU 加余量设置 `SetComputeUnitLimit`；
+//!    模拟失败（程序报错）直接返回，不会把注定失败的交易发上链，错误里保留模拟日志；
+//! 2. 按写入账户最近的优先费取分位数，设置 `SetComputeUnitPrice`；
+//! 3. 发送并等待确认；blockhash 过期或网络错误时先查上一笔签名是否已经落地，
+//!    没有落地才换新的 blockhash 重新签名，不会重复执行。
+//!
+//! ```no_run
+//! # use bootcamp_client::{tx::{PriorityFee, TxBuilder}, vault};
+//! # use solana_client::rpc_client::RpcClient;
+//! # use solana_sdk::signature::{Keypair, Signer};
+//! # let (client, payer) = (RpcClient::new("https://api.devnet.solana.com"), Keypair::new());
+//! let ix = vault::deposit_ix(&vault::PROGRAM_ID, &payer.pubkey(), 1_000_000_000);
+//! let signature = TxBuilder::new(&client, &payer)
+//!     .instruction(ix)
+//!     .priority_fee(PriorityFee::Recent { percentile: 75, max: 100_000 })
+//!     .send()?;
+//! # Ok::<(), solana_client::client_error::ClientError>(())
+//! ```
+//!
+//! 只想看看交易会怎样执行时用 [`simulate`] / [`TxBuilder::simulate`]：返回消耗的 CU、日志，
+//! 失败时把自定义错误码还原成 [`BootcampError`](blueshift_errors::BootcampError)。
+//! `send` 失败后用 [`failure`] 与 [`simulation_logs`] 取同样的信息。
+//!
+//! 账户较多的交易（router 复合指令、多程序组合）用 [`TxBuilder::lookup_tables`] 传入地址查找表
+//! （由 [`crate::alt`] 创建、[`fetch_lookup_table`] 读取），改为发送 v0 交易；
+//! 编译出的交易超过 1232 字节时在模拟之前直接报错，而不是等节点拒绝。
+
+// 直接返回 RpcClient 的 ClientError，调用方可以照常用 get_transaction_error 取程序错误码
+#![allow(clippy::result_large_err)]
+
+use core::fmt;
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_client::RpcClient,
+    rpc_config::RpcSimulateTransactionConfig,
+    rpc_custom_error::JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE,
+    rpc_request::{RpcError, RpcResponseErrorData},
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, AddressLookupTableAccount, Message, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::{Signature, Signer},
+    transaction::{TransactionError, VersionedTransaction},
+};
+
+use crate::{
+    alt::LookupTable,
+    diagnostics::{self, Failure},
+    version::{self, Version},
+};
+
+/// 单笔交易的计算单元上限
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// `getRecentPrioritizationFees` 最多接受的账户数
+const MAX_FEE_ACCOUNTS: usize = 128;
+
+/// `SetComputeUnitPrice` 的取值方式，单位 micro-lamports / CU
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PriorityFee {
+    /// 不加 `SetComputeUnitPrice`
+    None,
+    /// 固定单价
+    Fixed(u64),
+    /// 写入账户最近约 150 个 slot 优先费的 `percentile` 分位数（0–100），不超过 `max`
+    Recent { percentile: u8, max: u64 },
+}
+
+impl Default for PriorityFee {
+    fn default() -> Self {
+        Self::Recent {
+            percentile: 75,
+            max: 1_000_000,
+        }
+    }
+}
+
+/// 一次模拟的结果
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Simulation {
+    /// 节点报告的计算单元消耗（旧节点可能不返回）
+    pub units_consumed: Option<u64>,
+    pub logs: Vec<String>,
+    pub err: Option<TransactionError>,
+    /// 最后一次 `set_return_data` 写入的数据（例如 `get_version` 的结果），没有时为空
+    pub return_data: Vec<u8>,
+}
+
+impl Simulation {
+    /// 失败原因，自定义错误码已还原成训练营程序的错误
+    pub fn failure(&self) -> Option<Failure> {
+        self.err.as_ref().map(Failure::from)
+    }
+
+    /// 实际消耗加 `margin`% 余量后的计算单元上限；节点没有报告时取单笔交易上限
+    pub fn compute_unit_limit(&self, margin: u32) -> u32 {
+        match self.units_consumed {
+            Some(consumed) => with_margin(consumed, margin),
+            None => MAX_COMPUTE_UNIT_LIMIT,
+        }
+    }
+
+    /// 解析日志中 `program_id` 发出的事件
+    pub fn events(
+        &self,
+        program_id: &Pubkey,
+    ) -> Vec<Result<crate::events::ProgramEvent, crate::events::DecodeError>> {
+        crate::events::events_from_logs(&program_id.to_string(), &self.logs)
+    }
+}
+
+impl From<RpcSimulateTransactionResult> for Simulation {
+    fn from(result: RpcSimulateTransactionResult) -> Self {
+        Self {
+            units_consumed: result.units_consumed,
+            logs: result.logs.unwrap_or_default(),
+            err: result.err,
+            // return data 固定以 base64 返回，解码失败只可能是节点出错，按没有数据处理
+            return_data: result
+                .return_data
+                .and_then(|data| BASE64_STANDARD.decode(data.data.0).ok())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// 首行是结果与 CU 消耗；失败时再列出失败的程序与完整日志
+impl fmt::Display for Simulation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.failure() {
+            None => write!(f, "模拟成功")?,
+            Some(failure) => write!(f, "{failure}")?,
+        }
+        if let Some(units) = self.units_consumed {
+            write!(f, "，消耗 {units} CU")?;
+        }
+        if self.err.is_some() {
+            if let Some((program, reason)) = diagnostics::failed_program(&self.logs) {
+                write!(f, "\n  程序 {program}：{reason}")?;
+            }
+            for line in &self.logs {
+                write!(f, "\n    {line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 不签名、替换 blockhash 模拟 `instructions`，原样执行（不加计算预算指令）。
+/// 程序报错不算 `Err`，在返回的 [`Simulation::err`] 中
+pub fn simulate(
+    client: &RpcClient,
+    payer: &Pubkey,
+    instructions: &[Instruction],
+) -> Result<Simulation, ClientError> {
+    simulate_raw(client, payer, instructions, &[]).map(Simulation::from)
+}
+
+fn simulate_raw(
+    client: &RpcClient,
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<RpcSimulateTransactionResult, ClientError> {
+    let message = compile(payer, instructions, lookup_tables, Hash::default())?;
+    let tx = VersionedTransaction {
+        signatures: vec![Signature::default(); message.header().num_required_signatures as usize],
+        message,
+    };
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(client.commitment()),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    Ok(client.simulate_transaction_with_config(&tx, config)?.value)
+}
+
+/// 模拟 `get_version` 读取程序的版本号与功能位，不需要签名也不花费 lamports。
+/// `with_gate` 见 [`version::get_version_ix`]；模拟失败（旧版本程序不认识该指令，或开关账户不合法）时返回 `Ok(None)`
+pub fn fetch_version(
+    client: &RpcClient,
+    payer: &Pubkey,
+    program_id: &Pubkey,
+    with_gate: bool,
+) -> Result<Option<Version>, ClientError> {
+    let ix = version::get_version_ix(program_id, with_gate);
+    let simulation = simulate(client, payer, &[ix])?;
+    if simulation.err.is_some() {
+        return Ok(None);
+    }
+    Ok(Version::decode(&simulation.return_data))
+}
+
+/// 读取地址查找表，转为编译 v0 交易用的形式
+pub fn fetch_lookup_table(
+    client: &RpcClient,
+    address: &Pubkey,
+) -> Result<AddressLookupTableAccount, ClientError> {
+    let data = client.get_account_data(address)?;
+    let table = LookupTable::try_from_bytes(&data)
+        .map_err(|err| ClientErrorKind::Custom(format!("{address}：{err}")))?;
+    Ok(AddressLookupTableAccount {
+        key: *address,
+        addresses: table.addresses,
+    })
+}
+
+/// 编译交易消息：没有查找表时是 legacy 消息，否则是 v0 消息。
+/// 序列化后超过单笔交易上限（1232 字节）时报错
+pub fn compile(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<VersionedMessage, ClientError> {
+    let message = if lookup_tables.is_empty() {
+        VersionedMessage::Legacy(Message::new_with_blockhash(
+            instructions,
+            Some(payer),
+            &blockhash,
+        ))
+    } else {
+        let message = v0::Message::try_compile(payer, instructions, lookup_tables, blockhash)
+            .map_err(|err| ClientErrorKind::Custom(format!("编译 v0 交易失败：{err}")))?;
+        VersionedMessage::V0(message)
+    };
+    let size = transaction_size(&message);
+    if size > PACKET_DATA_SIZE {
+        return Err(ClientErrorKind::Custom(format!(
+            "交易 {size} 字节，超过 {PACKET_DATA_SIZE} 字节上限；把常用账户写入地址查找表后用 lookup_tables 发送"
+        ))
+        .into());
+    }
+    Ok(message)
+}
+
+/// 签名后的交易长度：签名数（compact-u16，签名者不超过 127 个时占 1 字节）+ 签名 + 消息
+fn transaction_size(message: &VersionedMessage) -> usize {
+    1 + 64 * message.header().num_required_signatures as usize + message.serialize().len()
+}
+
+/// 交易失败的原因（模拟或预检失败、上链后执行失败）；网络错误等返回 `None`
+pub fn failure(err: &ClientError) -> Option<Failure> {
+    err.get_transaction_error().as_ref().map(Failure::from)
+}
+
+/// 模拟或预检失败时节点返回的程序日志
+pub fn simulation_logs(err: &ClientError) -> Option<&[String]> {
+    match err.kind() {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data:
+                RpcResponseErrorData::SendTransactionPreflightFailure(RpcSimulateTransactionResult {
+                    logs: Some(logs),
+                    ..
+                }),
+            ..
+        }) => Some(logs),
+        _ => None,
+    }
+}
+
+/// 带计算预算与重试的交易构造器，payer 同时是第一个签名者
+pub struct TxBuilder<'a> {
+    client: &'a RpcClient,
+    payer: &'a dyn Signer,
+    signers: Vec<&'a dyn Signer>,
+    instructions: Vec<Instruction>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    compute_unit_limit: Option<u32>,
+    compute_unit_margin: u32,
+    priority_fee: PriorityFee,
+    max_attempts: usize,
+}
+
+impl<'a> TxBuilder<'a> {
+    pub fn new(client: &'a RpcClient, payer: &'a dyn Signer) -> Self {
+        Self {
+            client,
+            payer,
+            signers: Vec::new(),
+            instructions: Vec::new(),
+            lookup_tables: Vec::new(),
+            compute_unit_limit: None,
+            compute_unit_margin: 10,
+            priority_fee: PriorityFee::default(),
+            max_attempts: 3,
+        }
+    }
+
+    pub fn instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    pub fn instructions(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+
+    /// payer 以外的签名者（例如新建的 mint）
+    pub fn signer(mut self, signer: &'a dyn Signer) -> Self {
+        self.signers.push(signer);
+        self
+    }
+
+    /// 用地址查找表压缩账户，发送 v0 交易。表中新写入的地址要等下一个 slot 才能使用
+    pub fn lookup_tables(
+        mut self,
+        tables: impl IntoIterator<Item = AddressLookupTableAccount>,
+    ) -> Self {
+        self.lookup_tables.extend(tables);
+        self
+    }
+
+    /// 固定计算单元上限，不再模拟估算
+    pub fn compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(units);
+        self
+    }
+
+    /// 模拟估算时在实际消耗上增加的百分比（默认 10）
+    pub fn compute_unit_margin(mut self, percent: u32) -> Self {
+        self.compute_unit_margin = percent;
+        self
+    }
+
+    pub fn priority_fee(mut self, priority_fee: PriorityFee) -> Self {
+        self.priority_fee = priority_fee;
+        self
+    }
+
+    /// 最多发送几次（默认 3），每次都换新的 blockhash
+    pub fn max_attempts(mut self, attempts: usize) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// 估算计算预算后发送，返回已确认交易的签名
+    pub fn send(self) -> Result<Signature, ClientError> {
+        let price = self.compute_unit_price()?;
+        let limit = match self.compute_unit_limit {
+            Some(units) => units,
+            None => self.estimate(price)?,
+        };
+        let instructions = self.with_budget(limit, price);
+
+        let mut signers = vec![self.payer];
+        signers.extend_from_slice(&self.signers);
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let blockhash = self.client.get_latest_blockhash()?;
+            let message = compile(
+                &self.payer.pubkey(),
+                &instructions,
+                &self.lookup_tables,
+                blockhash,
+            )?;
+            let tx = VersionedTransaction::try_new(message, &signers)?;
+            let signature = tx.signatures[0];
+
+            match self.client.send_and_confirm_transaction(&tx) {
+                Ok(signature) => return Ok(signature),
+                Err(err) if attempt < self.max_attempts && is_retryable(&err) => {
+                    // 确认超时不代表没有落地：先查一次状态，确实没有才换 blockhash 重发
+                    match self.client.get_signature_status(&signature)? {
+                        Some(Ok(())) => return Ok(signature),
+                        Some(Err(err)) => return Err(err.into()),
+                        None => {}
+                    }
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// 按 `priority_fee` 计算单价
+    fn compute_unit_price(&self) -> Result<Option<u64>, ClientError> {
+        match self.priority_fee {
+            PriorityFee::None => Ok(None),
+            PriorityFee::Fixed(price) => Ok(Some(price)),
+            PriorityFee::Recent { percentile, max } => {
+                let accounts = writable_accounts(&self.instructions);
+                let mut fees: Vec<u64> = self
+                    .client
+                    .get_recent_prioritization_fees(&accounts)?
+                    .into_iter()
+                    .map(|fee| fee.prioritization_fee)
+                    .collect();
+                Ok(Some(fee_percentile(&mut fees, percentile).min(max)))
+            }
+        }
+    }
+
+    /// 按 `priority_fee` 定价、以最大计算单元上限模拟，不发送
+    pub fn simulate(&self) -> Result<Simulation, ClientError> {
+        let price = self.compute_unit_price()?;
+        let instructions = self.with_budget(MAX_COMPUTE_UNIT_LIMIT, price);
+        simulate_raw(
+            self.client,
+            &self.payer.pubkey(),
+            &instructions,
+            &self.lookup_tables,
+        )
+        .map(Simulation::from)
+    }
+
+    /// 以最大上限模拟，返回加上余量后的计算单元数；程序报错时返回带日志的预检失败错误
+    fn estimate(&self, price: Option<u64>) -> Result<u32, ClientError> {
+        let instructions = self.with_budget(MAX_COMPUTE_UNIT_LIMIT, price);
+        let result = simulate_raw(
+            self.client,
+            &self.payer.pubkey(),
+            &instructions,
+            &self.lookup_tables,
+        )?;
+        if let Some(err) = &result.err {
+            return Err(RpcError::RpcResponseError {
+                code: JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE,
+                message: format!("模拟失败：{}", Failure::from(err)),
+                data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+            }
+            .into());
+        }
+        Ok(Simulation::from(result).compute_unit_limit(self.compute_unit_margin))
+    }
+
+    fn with_budget(&self, limit: u32, price: Option<u64>) -> Vec<Instruction> {
+        budget_instructions(limit, price)
+            .into_iter()
+            .chain(self.instructions.iter().cloned())
+            .collect()
+    }
+}
+
+/// 计算预算指令，放在交易最前面
+pub fn budget_instructions(limit: u32, price: Option<u64>) -> Vec<Instruction> {
+    let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(limit)];
+    if let Some(price) = price {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+    }
+    instructions
+}
+
+/// 实际消耗加 `percent`% 余量，向上取整并限制在单笔交易上限内
+fn with_margin(consumed: u64, percent: u32) -> u32 {
+    let units = consumed.saturating_mul(100 + percent as u64).div_ceil(100);
+    units.min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32
+}
+
+/// `fees` 的 `percentile` 分位数（最近邻），为空时返回 0
+fn fee_percentile(fees: &mut [u64], percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let rank = (fees.len() - 1) * percentile.min(100) as usize / 100;
+    fees[rank]
+}
+
+/// 指令中的可写账户（去重，最多 128 个），优先费只与写锁竞争有关
+fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = Vec::new();
+    for meta in instructions.iter().flat_map(|ix| &ix.accounts) {
+        if meta.is_writable && !accounts.contains(&meta.pubkey) {
+            accounts.push(meta.pubkey);
+        }
+    }
+    accounts.truncate(MAX_FEE_ACCOUNTS);
+    accounts
+}
+
+/// blockhash 过期、确认超时或网络错误：换 blockhash 重发可能成功
+fn is_retryable(err: &ClientError) -> bool {
+    if let Some(tx_err) = err.get_transaction_error() {
+        return tx_err == TransactionError::BlockhashNotFound;
+    }
+    matches!(
+        err.kind(),
+        ClientErrorKind::Io(_)
+            | ClientErrorKind::Reqwest(_)
+            | ClientErrorKind::RpcError(RpcError::ForUser(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{compute_budget, instruction::AccountMeta};
+
+    #[test]
+    fn margin_rounds_up_and_caps() {
+        assert_eq!(with_margin(10_001, 10), 11_002);
+        assert_eq!(with_margin(0, 10), 0);
+        assert_eq!(with_margin(1_300_000, 10), MAX_COMPUTE_UNIT_LIMIT);
+    }
+
+    #[test]
+    fn percentile_of_recent_fees() {
+        assert_eq!(fee_percentile(&mut [], 75), 0);
+        let mut fees = [500, 0, 100, 0, 10_000];
+        assert_eq!(fee_percentile(&mut fees, 0), 0);
+        assert_eq!(fee_percentile(&mut fees, 50), 100);
+        assert_eq!(fee_percentile(&mut fees, 75), 500);
+        assert_eq!(fee_percentile(&mut fees, 100), 10_000);
+    }
+
+    #[test]
+    fn budget_comes_first_and_price_is_optional() {
+        let instructions = budget_instructions(50_000, Some(7));
+        assert_eq!(instructions.len(), 2);
+        assert!(instructions
+            .iter()
+            .all(|ix| ix.program_id == compute_budget::id()));
+        assert_eq!(budget_instructions(50_000, None).len(), 1);
+    }
+
+    #[test]
+    fn simulation_failure_keeps_logs_and_program_error() {
+        use solana_sdk::instruction::InstructionError;
+
+        let err = TransactionError::InstructionError(1, InstructionError::Custom(6101));
+        let result = RpcSimulateTransactionResult {
+            err: Some(err.clone()),
+            logs: Some(vec![
+                "Program 22222222222222222222222222222222222222222222 failed: custom program error: 0x17d5".into(),
+            ]),
+            accounts: None,
+            units_consumed: Some(4_321),
+            return_data: None,
+            inner_instructions: None,
+        };
+        let simulation = Simulation::from(result.clone());
+        assert_eq!(
+            simulation
+                .failure()
+                .unwrap()
+                .program_error()
+                .unwrap()
+                .code(),
+            6101
+        );
+        assert!(simulation.to_string().contains("消耗 4321 CU"));
+        assert_eq!(simulation.compute_unit_limit(10), 4_754);
+
+        let client_err: ClientError = RpcError::RpcResponseError {
+            code: JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE,
+            message: String::new(),
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+        }
+        .into();
+        assert_eq!(failure(&client_err), simulation.failure());
+        assert_eq!(simulation_logs(&client_err).unwrap(), simulation.logs);
+        assert!(!is_retryable(&client_err));
+        assert_eq!(
+            Simulation::default().compute_unit_limit(10),
+            MAX_COMPUTE_UNIT_LIMIT
+        );
+    }
+
+    #[test]
+    fn lookup_tables_keep_wide_transactions_under_limit() {
+        let payer = Pubkey::new_unique();
+        let accounts: Vec<Pubkey> = (0..40).map(|_| Pubkey::new_unique()).collect();
+        let metas = |keys: &[Pubkey]| {
+            keys.iter()
+                .map(|key| AccountMeta::new(*key, false))
+                .collect()
+        };
+        let program = Pubkey::new_unique();
+        let instructions = [
+            Instruction::new_with_bytes(program, &[1], metas(&accounts[..20])),
+            Instruction::new_with_bytes(program, &[2], metas(&accounts[20..])),
+        ];
+
+        // 42 个地址的 legacy 交易超过 1232 字节
+        let err = compile(&payer, &instructions, &[], Hash::default()).unwrap_err();
+        assert!(matches!(err.kind(), ClientErrorKind::Custom(_)));
+
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: accounts,
+        };
+        let message = compile(&payer, &instructions, &[table], Hash::default()).unwrap();
+        assert!(matches!(message, VersionedMessage::V0(_)));
+        // 程序与 payer 仍是静态账户
+        assert_eq!(message.static_account_keys(), [payer, program]);
+        assert!(transaction_size(&message) <= PACKET_DATA_SIZE);
+    }
+
+    #[test]
+    fn fee_accounts_are_writable_and_unique() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = |accounts| Instruction::new_with_bytes(Pubkey::new_unique(), &[], accounts);
+        let instructions = [
+            ix(vec![
+                AccountMeta::new(a, true),
+                AccountMeta::new_readonly(b, false),
+            ]),
+            ix(vec![AccountMeta::new(a, false)]),
+        ];
+        assert_eq!(writable_accounts(&instructions), [a]);
+    }
+}
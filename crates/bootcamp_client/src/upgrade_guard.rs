@@ -0,0 +1,247 @@
+//! programs/blueshift_upgrade_guard：InitGuard = 0、ProposeUpgrade = 1、CancelUpgrade = 2、
+//! ExecuteUpgrade = 3、UpdateConfig = 4。
+//!
+//! 升级流程：`solana program write-buffer` 写入新版本，`solana program set-buffer-authority`
+//! 把缓冲区权限设为 [`guard_address`]，再由管理员发送 [`propose_upgrade_ix`]；
+//! 等待期满后任何人都可以发送 [`execute_upgrade_ix`]。
+//! 提案前可以用 [`buffer_hash`] 核对缓冲区内容，与链上 Guard 记录的哈希相同。
+
+use sha2::{Digest, Sha256};
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sdk_ids::{bpf_loader_upgradeable, system_program, sysvar};
+
+use crate::{fixed, pda, read, version::program_data_address, AccountDataError};
+
+pub const PROGRAM_ID: Pubkey = pubkey!("UpgradeGuard1111111111111111111111111111111");
+
+pub use pdas::UPGRADE_GUARD_SEED;
+
+/// Buffer 账户头部长度：u32 枚举序号 + Option<Pubkey> 权限
+pub const BUFFER_METADATA_LEN: usize = 37;
+
+/// 链上 Guard 账户
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Guard {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub program: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub admin: Pubkey,
+    /// 等待期（秒）
+    pub delay: i64,
+    pub bump: u8,
+    /// 待执行的升级：缓冲区、程序字节哈希、最早执行时间（unix 秒）
+    pub pending: Option<PendingUpgrade>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingUpgrade {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub buffer: Pubkey,
+    pub buffer_hash: [u8; 32],
+    pub eta: i64,
+}
+
+impl Guard {
+    pub const LEN: usize = 32 + 32 + 8 + 1 + 1 + 32 + 32 + 8;
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let data = fixed::<{ Self::LEN }>("upgrade_guard", data)?;
+        Ok(Self {
+            program: Pubkey::new_from_array(read(data, 0)),
+            admin: Pubkey::new_from_array(read(data, 32)),
+            delay: i64::from_le_bytes(read(data, 64)),
+            bump: data[72],
+            pending: (data[73] != 0).then(|| PendingUpgrade {
+                buffer: Pubkey::new_from_array(read(data, 74)),
+                buffer_hash: read(data, 106),
+                eta: i64::from_le_bytes(read(data, 138)),
+            }),
+        })
+    }
+}
+
+/// 守卫 PDA：[b"upgrade_guard", program]，也是 `program` 转交后的升级权限
+pub fn guard_address(program_id: &Pubkey, program: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::upgrade_guard_pda(
+        &program_id.to_bytes(),
+        &program.to_bytes(),
+    ))
+}
+
+/// Buffer 账户数据中程序字节的 sha256；数据短于头部时返回 `None`
+pub fn buffer_hash(buffer_data: &[u8]) -> Option<[u8; 32]> {
+    let program = buffer_data.get(BUFFER_METADATA_LEN..)?;
+    Some(Sha256::digest(program).into())
+}
+
+/// InitGuard：`authority` 必须是 `program` 当前的升级权限，执行后权限归守卫所有
+pub fn init_guard_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    program: &Pubkey,
+    admin: &Pubkey,
+    delay: i64,
+) -> Instruction {
+    let mut data = vec![0u8];
+    data.extend_from_slice(&delay.to_le_bytes());
+    data.extend_from_slice(admin.as_ref());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(guard_address(program_id, program).0, false),
+            AccountMeta::new_readonly(*program, false),
+            AccountMeta::new(program_data_address(program), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(bpf_loader_upgradeable::ID, false),
+        ],
+        data,
+    }
+}
+
+/// ProposeUpgrade：`buffer` 的权限需要先设为守卫地址
+pub fn propose_upgrade_ix(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    program: &Pubkey,
+    buffer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(guard_address(program_id, program).0, false),
+            AccountMeta::new_readonly(*buffer, false),
+        ],
+        data: vec![1],
+    }
+}
+
+/// CancelUpgrade：撤回待执行的升级
+pub fn cancel_upgrade_ix(program_id: &Pubkey, admin: &Pubkey, program: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(guard_address(program_id, program).0, false),
+        ],
+        data: vec![2],
+    }
+}
+
+/// ExecuteUpgrade：任何人都可以发送；缓冲区的 lamports 退到 `spill`
+pub fn execute_upgrade_ix(
+    program_id: &Pubkey,
+    program: &Pubkey,
+    buffer: &Pubkey,
+    spill: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(guard_address(program_id, program).0, false),
+            AccountMeta::new(program_data_address(program), false),
+            AccountMeta::new(*program, false),
+            AccountMeta::new(*buffer, false),
+            AccountMeta::new(*spill, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new_readonly(bpf_loader_upgradeable::ID, false),
+        ],
+        data: vec![3],
+    }
+}
+
+/// UpdateConfig：更换管理员；`delay` 不能小于当前等待期
+pub fn update_config_ix(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    program: &Pubkey,
+    new_admin: &Pubkey,
+    delay: i64,
+) -> Instruction {
+    let mut data = vec![4u8];
+    data.extend_from_slice(new_admin.as_ref());
+    data.extend_from_slice(&delay.to_le_bytes());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(guard_address(program_id, program).0, false),
+        ],
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guard_account_round_trips_pending_upgrade() {
+        let (program, admin, buffer) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut data = vec![0u8; Guard::LEN];
+        data[..32].copy_from_slice(program.as_ref());
+        data[32..64].copy_from_slice(admin.as_ref());
+        data[64..72].copy_from_slice(&86_400i64.to_le_bytes());
+        data[72] = 254;
+        assert_eq!(Guard::try_from_bytes(&data).unwrap().pending, None);
+
+        data[73] = 1;
+        data[74..106].copy_from_slice(buffer.as_ref());
+        data[106..138].copy_from_slice(&[9; 32]);
+        data[138..].copy_from_slice(&1_700_000_000i64.to_le_bytes());
+        let guard = Guard::try_from_bytes(&data).unwrap();
+        assert_eq!(guard.program, program);
+        assert_eq!(guard.admin, admin);
+        assert_eq!(guard.delay, 86_400);
+        assert_eq!(
+            guard.pending,
+            Some(PendingUpgrade {
+                buffer,
+                buffer_hash: [9; 32],
+                eta: 1_700_000_000,
+            })
+        );
+        assert!(Guard::try_from_bytes(&data[1..]).is_err());
+    }
+
+    #[test]
+    fn execute_signs_loader_upgrade_with_guard() {
+        let (program, buffer, spill) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = execute_upgrade_ix(&PROGRAM_ID, &program, &buffer, &spill);
+        assert_eq!(
+            ix.accounts[0].pubkey,
+            guard_address(&PROGRAM_ID, &program).0
+        );
+        assert_eq!(ix.accounts[1].pubkey, program_data_address(&program));
+        assert_eq!(ix.accounts[7].pubkey, bpf_loader_upgradeable::ID);
+        // 守卫由程序以 PDA 签名，外层交易不需要任何签名者
+        assert!(ix.accounts.iter().all(|meta| !meta.is_signer));
+
+        let ix = init_guard_ix(&PROGRAM_ID, &spill, &program, &buffer, 3_600);
+        assert_eq!(ix.data[1..9], 3_600i64.to_le_bytes());
+        assert_eq!(ix.data[9..], buffer.to_bytes());
+    }
+
+    #[test]
+    fn buffer_hash_skips_metadata() {
+        let mut data = vec![0u8; BUFFER_METADATA_LEN];
+        data.extend_from_slice(b"\x7fELF");
+        assert_eq!(buffer_hash(&data), Some(Sha256::digest(b"\x7fELF").into()));
+        assert_eq!(buffer_hash(&data[..10]), None);
+    }
+}
@@ -0,0 +1,1439 @@
+//! task2 Anchor SOL 金库：账户 [signer (w, s), vault PDA (w), system_program,
+//! vault_state PDA (w), config PDA, policy PDA, cooldown PDA (w), treasury PDA (w), event_authority, program]，
+//! 最后两个是事件 CPI
+//! （`#[event_cpi]`）需要的账户。
+//!
+//! 金库是不带数据的系统账户，余额即存款，读取 lamports 即可；解锁时间与代理人记录在单独的
+//! [`VaultState`] 账户中，首笔存款时创建，withdraw 全部取出或 [`close_vault_ix`] 时关闭。流式支付的速率与收款人
+//! 记录在 [`Stream`] 账户中，金库耗尽后由 claim_stream 关闭。全局存款限制在 [`GlobalConfig`] 中，
+//! 未初始化时不限制；其中的 fee_bps 不为 0 时 deposit / top_up 按比例扣下手续费转入 [`treasury_address`]。
+//! 所有者创建 [`VaultPolicy`] 后，取款类指令还需要共同签名者，
+//! 用 [`with_co_signer`] 把它追加到指令末尾。代币存放在金库 PDA 的 ATA 中，
+//! 见 [`deposit_token_ix`]，SPL Token 与 Token-2022 的 mint 都可以；[`wrap_deposit_ix`] 把金库里的
+//! SOL 包装成同一位置的 wSOL。可选的 [`VaultMetadata`] 记录钱包显示用的标签与备注，
+//! 可选的 [`WithdrawCooldown`] 限制两次取款的最短间隔。子金库 [`indexed_vault_address`] 直接接收
+//! 系统转账，[`sweep_ix`] 在一笔指令中取回多个子金库。
+//!
+//! task4 Pinocchio 金库的 Deposit / Withdraw 只有前三个账户、判别器只有 1 字节，事件写在日志里；
+//! 它额外的 ProvideLiquidity / RemoveLiquidity 见 [`provide_liquidity_ix`] / [`remove_liquidity_ix`]
+//! （需要 `amm` feature），留在金库 PDA 的 ATA 中的代币用 [`sweep_tokens_ix`] 取回。
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sdk_ids::system_program;
+
+use crate::{
+    fixed, pda, read,
+    token::{ata_with_program, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, TOKEN_PROGRAM_ID},
+    version::program_data_address,
+    AccountDataError,
+};
+
+pub const PROGRAM_ID: Pubkey = pubkey!("22222222222222222222222222222222222222222222");
+
+pub use pdas::{POLICY_SEED, STREAM_SEED, VAULT_SEED, VAULT_STATE_SEED};
+
+/// Anchor 判别器：sha256("global:<指令名>") 的前 8 字节
+pub const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const TOP_UP_DISCRIMINATOR: [u8; 8] = [236, 225, 96, 9, 60, 106, 77, 208];
+pub const WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+pub const WITHDRAW_PARTIAL_DISCRIMINATOR: [u8; 8] = [142, 181, 230, 69, 132, 105, 19, 229];
+pub const WITHDRAW_TO_DISCRIMINATOR: [u8; 8] = [2, 147, 75, 241, 190, 56, 152, 93];
+pub const CLOSE_VAULT_DISCRIMINATOR: [u8; 8] = [141, 103, 17, 126, 72, 75, 29, 29];
+pub const TRANSFER_VAULT_OWNERSHIP_DISCRIMINATOR: [u8; 8] = [168, 55, 1, 116, 3, 197, 112, 26];
+pub const DEPOSIT_TOKEN_DISCRIMINATOR: [u8; 8] = [11, 156, 96, 218, 39, 163, 180, 19];
+pub const WITHDRAW_TOKEN_DISCRIMINATOR: [u8; 8] = [136, 235, 181, 5, 101, 109, 57, 81];
+pub const WRAP_DEPOSIT_DISCRIMINATOR: [u8; 8] = [121, 151, 152, 207, 69, 12, 190, 153];
+pub const UNWRAP_WITHDRAW_DISCRIMINATOR: [u8; 8] = [130, 52, 213, 80, 250, 182, 129, 238];
+pub const EXTEND_LOCK_DISCRIMINATOR: [u8; 8] = [68, 151, 140, 144, 139, 122, 118, 170];
+pub const SET_DELEGATE_DISCRIMINATOR: [u8; 8] = [242, 30, 46, 76, 108, 235, 128, 181];
+pub const WITHDRAW_AS_DELEGATE_DISCRIMINATOR: [u8; 8] = [64, 213, 196, 16, 20, 18, 48, 18];
+pub const OPEN_STREAM_DISCRIMINATOR: [u8; 8] = [205, 39, 151, 131, 10, 188, 219, 17];
+pub const CLAIM_STREAM_DISCRIMINATOR: [u8; 8] = [157, 247, 164, 226, 240, 158, 183, 36];
+pub const INITIALIZE_CONFIG_DISCRIMINATOR: [u8; 8] = [208, 127, 21, 1, 194, 190, 196, 70];
+pub const UPDATE_CONFIG_DISCRIMINATOR: [u8; 8] = [29, 158, 252, 191, 10, 83, 219, 99];
+pub const EMERGENCY_PAUSE_DISCRIMINATOR: [u8; 8] = [21, 143, 27, 142, 200, 181, 210, 255];
+pub const UNPAUSE_DISCRIMINATOR: [u8; 8] = [169, 144, 4, 38, 10, 141, 188, 255];
+pub const SET_FEE_DISCRIMINATOR: [u8; 8] = [18, 154, 24, 18, 237, 214, 19, 80];
+pub const COLLECT_FEES_DISCRIMINATOR: [u8; 8] = [164, 152, 207, 99, 30, 186, 19, 182];
+pub const CREATE_POLICY_DISCRIMINATOR: [u8; 8] = [27, 81, 33, 27, 196, 103, 246, 53];
+pub const UPDATE_POLICY_DISCRIMINATOR: [u8; 8] = [212, 245, 246, 7, 163, 151, 18, 57];
+pub const SET_COOLDOWN_DISCRIMINATOR: [u8; 8] = [57, 78, 91, 178, 112, 152, 211, 87];
+pub const INIT_METADATA_DISCRIMINATOR: [u8; 8] = [226, 15, 9, 225, 77, 52, 247, 27];
+pub const UPDATE_METADATA_DISCRIMINATOR: [u8; 8] = [170, 182, 43, 239, 97, 78, 225, 186];
+pub const CLOSE_METADATA_DISCRIMINATOR: [u8; 8] = [10, 220, 196, 138, 19, 60, 204, 130];
+pub const SWEEP_DISCRIMINATOR: [u8; 8] = [40, 23, 234, 175, 14, 61, 154, 177];
+
+/// 账户判别器：sha256("account:<账户名>") 的前 8 字节
+pub const VAULT_STATE_DISCRIMINATOR: [u8; 8] = [228, 196, 82, 165, 98, 210, 235, 152];
+pub const STREAM_DISCRIMINATOR: [u8; 8] = [166, 224, 59, 4, 202, 10, 186, 83];
+pub const GLOBAL_CONFIG_DISCRIMINATOR: [u8; 8] = [149, 8, 156, 202, 160, 252, 176, 217];
+pub const VAULT_POLICY_DISCRIMINATOR: [u8; 8] = [114, 95, 33, 99, 228, 82, 116, 98];
+pub const WITHDRAW_COOLDOWN_DISCRIMINATOR: [u8; 8] = [172, 167, 151, 140, 26, 158, 94, 119];
+pub const VAULT_METADATA_DISCRIMINATOR: [u8; 8] = [248, 177, 244, 93, 67, 19, 117, 57];
+
+/// 链上 VaultState 账户（去掉判别器后的字段）
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VaultState {
+    /// 解锁时间（unix 秒），之前 withdraw / withdraw_partial 都会失败
+    pub unlock_timestamp: i64,
+    /// 可以代为取出全部余额的公钥
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey::option"))]
+    pub delegate: Option<Pubkey>,
+}
+
+impl VaultState {
+    /// 判别器 + i64 + Option<Pubkey>（按最大长度分配，None 时后 32 字节为 0）
+    pub const LEN: usize = 8 + 8 + 1 + 32;
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let data = fixed::<{ Self::LEN }>("vault_state", data)?;
+        Ok(Self {
+            unlock_timestamp: i64::from_le_bytes(read(data, 8)),
+            delegate: (data[16] != 0).then(|| Pubkey::new_from_array(read(data, 17))),
+        })
+    }
+}
+
+/// 链上 Stream 账户（去掉判别器后的字段）
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stream {
+    /// 金库所有者
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub recipient: Pubkey,
+    /// 每秒释放的 lamports
+    pub rate_per_second: u64,
+    /// 上次结算的时间（unix 秒）
+    pub last_claimed: i64,
+}
+
+impl Stream {
+    /// 判别器 + 2 × Pubkey + u64 + i64
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8;
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let data = fixed::<{ Self::LEN }>("stream", data)?;
+        Ok(Self {
+            owner: Pubkey::new_from_array(read(data, 8)),
+            recipient: Pubkey::new_from_array(read(data, 40)),
+            rate_per_second: u64::from_le_bytes(read(data, 72)),
+            last_claimed: i64::from_le_bytes(read(data, 80)),
+        })
+    }
+
+    /// 按 `owner` 构造的 claim_stream 指令
+    pub fn claim_ix(&self, program_id: &Pubkey) -> Instruction {
+        claim_stream_ix(program_id, &self.owner, &self.recipient)
+    }
+
+    /// 截至 `now` 累计的 lamports，与链上的计算相同（不考虑金库余额）
+    pub fn accrued(&self, now: i64) -> u64 {
+        let elapsed = u64::try_from(now.saturating_sub(self.last_claimed)).unwrap_or(0);
+        self.rate_per_second.saturating_mul(elapsed)
+    }
+}
+
+/// 链上 GlobalConfig 账户（去掉判别器后的字段）
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlobalConfig {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub admin: Pubkey,
+    /// 单个金库余额上限，`u64::MAX` 表示不限制
+    pub max_vault_balance: u64,
+    /// 首笔存款的最小金额
+    pub min_deposit: u64,
+    /// 管理员暂停了 deposit / top_up
+    pub paused: bool,
+    /// deposit / top_up 的手续费率（基点）
+    pub fee_bps: u16,
+}
+
+impl GlobalConfig {
+    /// 判别器 + Pubkey + 2 × u64 + bool + u16
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1 + 2;
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let data = fixed::<{ Self::LEN }>("vault_config", data)?;
+        Ok(Self {
+            admin: Pubkey::new_from_array(read(data, 8)),
+            max_vault_balance: u64::from_le_bytes(read(data, 40)),
+            min_deposit: u64::from_le_bytes(read(data, 48)),
+            paused: data[56] != 0,
+            fee_bps: u16::from_le_bytes(read(data, 57)),
+        })
+    }
+
+    /// 存入 `amount` 时被扣下的手续费，与程序一样向下取整；金库实际收到 `amount - fee`
+    pub fn fee(&self, amount: u64) -> u64 {
+        (u128::from(amount) * u128::from(self.fee_bps) / 10_000) as u64
+    }
+}
+
+/// 链上 VaultPolicy 账户（去掉判别器后的字段）
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VaultPolicy {
+    /// 取款时必须一同签名的公钥
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub co_signer: Pubkey,
+}
+
+impl VaultPolicy {
+    /// 判别器 + Pubkey
+    pub const LEN: usize = 8 + 32;
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let data = fixed::<{ Self::LEN }>("vault_policy", data)?;
+        Ok(Self {
+            co_signer: Pubkey::new_from_array(read(data, 8)),
+        })
+    }
+}
+
+/// 链上 WithdrawCooldown 账户（去掉判别器后的字段）
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WithdrawCooldown {
+    /// 两次取款之间的最短间隔（秒）
+    pub interval: i64,
+    /// 上一次取款的时间（unix 秒），从未取款时为 0
+    pub last_withdrawal: i64,
+}
+
+impl WithdrawCooldown {
+    /// 判别器 + 2 × i64
+    pub const LEN: usize = 8 + 8 + 8;
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let data = fixed::<{ Self::LEN }>("withdraw_cooldown", data)?;
+        Ok(Self {
+            interval: i64::from_le_bytes(read(data, 8)),
+            last_withdrawal: i64::from_le_bytes(read(data, 16)),
+        })
+    }
+
+    /// `now` 时还需等待的秒数，0 表示可以取款
+    pub fn remaining(&self, now: i64) -> i64 {
+        self.last_withdrawal
+            .saturating_add(self.interval)
+            .saturating_sub(now)
+            .max(0)
+    }
+}
+
+/// 链上 VaultMetadata 账户（去掉判别器后的字段），长度随标签与备注变化
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VaultMetadata {
+    /// 创建时间（unix 秒）
+    pub created_at: i64,
+    pub label: String,
+    pub notes: String,
+}
+
+impl VaultMetadata {
+    /// 标签与备注的字节数上限，与程序中的 MAX_LABEL_LEN / MAX_NOTES_LEN 相同
+    pub const MAX_LABEL_LEN: usize = 32;
+    pub const MAX_NOTES_LEN: usize = 256;
+
+    /// 判别器 + i64 + 两个 Borsh 字符串（u32 长度前缀 + UTF-8 字节），不能有多余的字节
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, AccountDataError> {
+        let error = |expected| AccountDataError {
+            account: "vault_metadata",
+            expected,
+            actual: data.len(),
+        };
+        let mut offset = 16;
+        let mut string = || {
+            let start = offset + 4;
+            let prefix = data.get(offset..start).ok_or(error(start))?;
+            let end = start + u32::from_le_bytes(prefix.try_into().unwrap()) as usize;
+            let bytes = data.get(start..end).ok_or(error(end))?;
+            offset = end;
+            Ok(String::from_utf8_lossy(bytes).into_owned())
+        };
+        let created_at = i64::from_le_bytes(data.get(8..16).ok_or(error(24))?.try_into().unwrap());
+        let label = string()?;
+        let notes = string()?;
+        if offset != data.len() {
+            return Err(error(offset));
+        }
+        Ok(Self {
+            created_at,
+            label,
+            notes,
+        })
+    }
+}
+
+/// 金库 PDA：[b"vault", signer]
+pub fn vault_address(program_id: &Pubkey, signer: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::vault_pda(&program_id.to_bytes(), &signer.to_bytes()))
+}
+
+/// 子金库 PDA：[b"vault", signer, index]
+pub fn indexed_vault_address(program_id: &Pubkey, signer: &Pubkey, index: u8) -> (Pubkey, u8) {
+    pda(pdas::indexed_vault_pda(
+        &program_id.to_bytes(),
+        &signer.to_bytes(),
+        index,
+    ))
+}
+
+/// 金库状态 PDA：[b"vault_state", signer]
+pub fn vault_state_address(program_id: &Pubkey, signer: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::vault_state_pda(
+        &program_id.to_bytes(),
+        &signer.to_bytes(),
+    ))
+}
+
+/// 全局配置 PDA：[b"config"]
+pub fn config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::vault_config_pda(&program_id.to_bytes()))
+}
+
+/// 存款手续费国库 PDA：[b"treasury"]，与国库程序的种子相同，但由金库程序派生
+pub fn treasury_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::treasury_pda(&program_id.to_bytes()))
+}
+
+/// 流式支付 PDA：[b"stream", owner]
+pub fn stream_address(program_id: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::stream_pda(&program_id.to_bytes(), &owner.to_bytes()))
+}
+
+/// 共同签名策略 PDA：[b"policy", owner]
+pub fn policy_address(program_id: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::policy_pda(&program_id.to_bytes(), &owner.to_bytes()))
+}
+
+/// 取款冷却 PDA：[b"cooldown", owner]
+pub fn cooldown_address(program_id: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::cooldown_pda(
+        &program_id.to_bytes(),
+        &owner.to_bytes(),
+    ))
+}
+
+/// 元数据 PDA：[b"metadata", owner]
+pub fn metadata_address(program_id: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::metadata_pda(
+        &program_id.to_bytes(),
+        &owner.to_bytes(),
+    ))
+}
+
+/// Anchor 程序事件 CPI 的签名者：[b"__event_authority"]
+pub fn event_authority_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    pda(pdas::event_authority_pda(&program_id.to_bytes()))
+}
+
+fn accounts(program_id: &Pubkey, signer: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*signer, true),
+        AccountMeta::new(vault_address(program_id, signer).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(vault_state_address(program_id, signer).0, false),
+        AccountMeta::new_readonly(config_address(program_id).0, false),
+        AccountMeta::new_readonly(policy_address(program_id, signer).0, false),
+        AccountMeta::new(cooldown_address(program_id, signer).0, false),
+        AccountMeta::new(treasury_address(program_id).0, false),
+        AccountMeta::new_readonly(event_authority_address(program_id).0, false),
+        AccountMeta::new_readonly(*program_id, false),
+    ]
+}
+
+/// 金库设置了共同签名策略时，把 `co_signer` 作为签名者追加到取款类指令末尾（remaining_accounts）
+pub fn with_co_signer(mut ix: Instruction, co_signer: &Pubkey) -> Instruction {
+    ix.accounts
+        .push(AccountMeta::new_readonly(*co_signer, true));
+    ix
+}
+
+/// 存入 lamports：金库必须为空，且金额超过免租金最低限额；不锁定
+pub fn deposit_ix(program_id: &Pubkey, signer: &Pubkey, amount: u64) -> Instruction {
+    deposit_locked_ix(program_id, signer, amount, 0)
+}
+
+/// 存入 lamports，`lock_seconds` 秒内不能取款
+pub fn deposit_locked_ix(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    amount: u64,
+    lock_seconds: u32,
+) -> Instruction {
+    let mut data = DEPOSIT_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&lock_seconds.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: accounts(program_id, signer),
+        data,
+    }
+}
+
+/// 向已有余额的金库追加 lamports；金库为空时用 [`deposit_ix`]
+pub fn top_up_ix(program_id: &Pubkey, signer: &Pubkey, amount: u64) -> Instruction {
+    let mut data = TOP_UP_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: accounts(program_id, signer),
+        data,
+    }
+}
+
+/// 取回金库中的全部 lamports
+pub fn withdraw_ix(program_id: &Pubkey, signer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: accounts(program_id, signer),
+        data: WITHDRAW_DISCRIMINATOR.to_vec(),
+    }
+}
+
+/// 只取出 `amount` lamports；取款后金库余额不能低于免租金限额，全部取出用 [`withdraw_ix`]
+pub fn withdraw_partial_ix(program_id: &Pubkey, signer: &Pubkey, amount: u64) -> Instruction {
+    let mut data = WITHDRAW_PARTIAL_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: accounts(program_id, signer),
+        data,
+    }
+}
+
+/// WithdrawTo：取回全部 lamports 但直接转给 `recipient`，仍需 `signer` 签名；没有状态账户的金库也可以。
+/// 账户 [signer (w, s), recipient (w), vault, system_program, vault_state (w), policy, cooldown (w), event_authority, program]
+pub fn withdraw_to_ix(program_id: &Pubkey, signer: &Pubkey, recipient: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(*recipient, false),
+            AccountMeta::new(vault_address(program_id, signer).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(vault_state_address(program_id, signer).0, false),
+            AccountMeta::new_readonly(policy_address(program_id, signer).0, false),
+            AccountMeta::new(cooldown_address(program_id, signer).0, false),
+            AccountMeta::new_readonly(event_authority_address(program_id).0, false),
+            AccountMeta::new_readonly(*program_id, false),
+        ],
+        data: WITHDRAW_TO_DISCRIMINATOR.to_vec(),
+    }
+}
+
+/// CloseVault：取回全部 lamports，并关闭状态账户与流，租金都退还 `signer`；金库已空时也可调用。
+/// 账户 [signer (w, s), vault (w), system_program, vault_state (w), stream (w), policy, cooldown (w), event_authority, program]
+pub fn close_vault_ix(program_id: &Pubkey, signer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(vault_address(program_id, signer).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(vault_state_address(program_id, signer).0, false),
+            AccountMeta::new(stream_address(program_id, signer).0, false),
+            AccountMeta::new_readonly(policy_address(program_id, signer).0, false),
+            AccountMeta::new(cooldown_address(program_id, signer).0, false),
+            AccountMeta::new_readonly(event_authority_address(program_id).0, false),
+            AccountMeta::new_readonly(*program_id, false),
+        ],
+        data: CLOSE_VAULT_DISCRIMINATOR.to_vec(),
+    }
+}
+
+/// TransferVaultOwnership：把 `signer` 金库的全部 lamports 转入 `new_owner` 的金库（必须为空），
+/// 并关闭原状态账户与流。账户与 [`close_vault_ix`] 相同，只是在 vault 之后插入 new_vault (w)
+pub fn transfer_vault_ownership_ix(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    new_owner: &Pubkey,
+) -> Instruction {
+    let mut ix = close_vault_ix(program_id, signer);
+    ix.accounts.insert(
+        2,
+        AccountMeta::new(vault_address(program_id, new_owner).0, false),
+    );
+    ix.data = TRANSFER_VAULT_OWNERSHIP_DISCRIMINATOR.to_vec();
+    ix.data.extend_from_slice(new_owner.as_ref());
+    ix
+}
+
+/// Sweep：取回 `indices` 中各子金库的全部 lamports，空的子金库被跳过。
+/// 账户 [signer (w, s), system_program, policy, cooldown (w), event_authority, program]，
+/// 之后按 `indices` 的顺序追加子金库 (w)；共同签名者用 [`with_co_signer`] 追加在最后
+pub fn sweep_ix(program_id: &Pubkey, signer: &Pubkey, indices: &[u8]) -> Instruction {
+    let mut data = SWEEP_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&(indices.len() as u32).to_le_bytes());
+    data.extend_from_slice(indices);
+    let mut accounts = vec![
+        AccountMeta::new(*signer, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(policy_address(program_id, signer).0, false),
+        AccountMeta::new(cooldown_address(program_id, signer).0, false),
+        AccountMeta::new_readonly(event_authority_address(program_id).0, false),
+        AccountMeta::new_readonly(*program_id, false),
+    ];
+    accounts.extend(
+        indices.iter().map(|index| {
+            AccountMeta::new(indexed_vault_address(program_id, signer, *index).0, false)
+        }),
+    );
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// DepositToken：把 `signer` ATA 中的 `amount` 个 `mint` 代币存入金库 PDA 的 ATA（不存在时创建）。
+/// `token_program` 是 mint 所属的代币程序；带转账手续费的 mint 实际到账更少。
+/// 账户 [signer (w, s), vault, mint, user_token (w), token_vault (w), config, token_program,
+/// associated_token_program, system_program]
+pub fn deposit_token_ix(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let vault = vault_address(program_id, signer).0;
+    let mut data = DEPOSIT_TOKEN_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(ata_with_program(signer, mint, token_program), false),
+            AccountMeta::new(ata_with_program(&vault, mint, token_program), false),
+            AccountMeta::new_readonly(config_address(program_id).0, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// WithdrawToken：从金库 PDA 的 ATA 取出 `amount` 个代币到 `signer` 的 ATA（不存在时创建）。
+/// 账户 [signer (w, s), vault, mint, user_token (w), token_vault (w), vault_state, policy,
+/// cooldown (w), token_program, associated_token_program, system_program]
+pub fn withdraw_token_ix(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let vault = vault_address(program_id, signer).0;
+    let mut data = WITHDRAW_TOKEN_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(ata_with_program(signer, mint, token_program), false),
+            AccountMeta::new(ata_with_program(&vault, mint, token_program), false),
+            AccountMeta::new_readonly(vault_state_address(program_id, signer).0, false),
+            AccountMeta::new_readonly(policy_address(program_id, signer).0, false),
+            AccountMeta::new(cooldown_address(program_id, signer).0, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// WrapDeposit：把金库中的 `amount` lamports 转入金库 PDA 的 wSOL ATA（不存在时由 signer 付租金创建）。
+/// 剩余余额必须仍然免租金，不能把金库包装到 0。
+/// 受取款冷却约束。
+/// 账户 [signer (w, s), vault (w), native_mint, wsol_vault (w), token_program,
+/// associated_token_program, system_program, cooldown (w)]
+pub fn wrap_deposit_ix(program_id: &Pubkey, signer: &Pubkey, amount: u64) -> Instruction {
+    let vault = vault_address(program_id, signer).0;
+    let mut data = WRAP_DEPOSIT_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(NATIVE_MINT, false),
+            AccountMeta::new(
+                ata_with_program(&vault, &NATIVE_MINT, &TOKEN_PROGRAM_ID),
+                false,
+            ),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(cooldown_address(program_id, signer).0, false),
+        ],
+        data,
+    }
+}
+
+/// UnwrapWithdraw：关闭金库 PDA 的 wSOL ATA，全部余额连同租金回到金库，同样受取款冷却约束。
+/// 账户 [signer (s), vault (w), native_mint, wsol_vault (w), token_program, cooldown (w)]
+pub fn unwrap_withdraw_ix(program_id: &Pubkey, signer: &Pubkey) -> Instruction {
+    let vault = vault_address(program_id, signer).0;
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(NATIVE_MINT, false),
+            AccountMeta::new(
+                ata_with_program(&vault, &NATIVE_MINT, &TOKEN_PROGRAM_ID),
+                false,
+            ),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new(cooldown_address(program_id, signer).0, false),
+        ],
+        data: UNWRAP_WITHDRAW_DISCRIMINATOR.to_vec(),
+    }
+}
+
+/// ExtendLock：把解锁时间改为 `unlock_timestamp`，只能延后。账户只有 signer 与 vault_state
+pub fn extend_lock_ix(program_id: &Pubkey, signer: &Pubkey, unlock_timestamp: i64) -> Instruction {
+    let mut data = EXTEND_LOCK_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&unlock_timestamp.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(vault_state_address(program_id, signer).0, false),
+        ],
+        data,
+    }
+}
+
+/// SetDelegate：指定（`None` 时取消）可以代为取款的公钥。账户与 [`extend_lock_ix`] 相同
+pub fn set_delegate_ix(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    delegate: Option<&Pubkey>,
+) -> Instruction {
+    let mut data = SET_DELEGATE_DISCRIMINATOR.to_vec();
+    match delegate {
+        Some(delegate) => {
+            data.push(1);
+            data.extend_from_slice(delegate.as_ref());
+        }
+        None => data.push(0),
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(vault_state_address(program_id, signer).0, false),
+        ],
+        data,
+    }
+}
+
+/// WithdrawAsDelegate：代理人取出 `owner` 金库的全部 lamports，状态账户的租金退还 `owner`。
+/// 账户 [delegate (w, s), owner (w), vault, system_program, vault_state, policy, cooldown (w), event_authority, program]
+pub fn withdraw_as_delegate_ix(
+    program_id: &Pubkey,
+    delegate: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*delegate, true),
+            AccountMeta::new(*owner, false),
+            AccountMeta::new(vault_address(program_id, owner).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(vault_state_address(program_id, owner).0, false),
+            AccountMeta::new_readonly(policy_address(program_id, owner).0, false),
+            AccountMeta::new(cooldown_address(program_id, owner).0, false),
+            AccountMeta::new_readonly(event_authority_address(program_id).0, false),
+            AccountMeta::new_readonly(*program_id, false),
+        ],
+        data: WITHDRAW_AS_DELEGATE_DISCRIMINATOR.to_vec(),
+    }
+}
+
+/// OpenStream：从现在起每秒向 `recipient` 释放 `rate_per_second` lamports。
+/// 账户 [signer (w, s), stream (w), system_program, policy, cooldown (w)]
+pub fn open_stream_ix(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    recipient: &Pubkey,
+    rate_per_second: u64,
+) -> Instruction {
+    let mut data = OPEN_STREAM_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(recipient.as_ref());
+    data.extend_from_slice(&rate_per_second.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(stream_address(program_id, signer).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(policy_address(program_id, signer).0, false),
+            AccountMeta::new(cooldown_address(program_id, signer).0, false),
+        ],
+        data,
+    }
+}
+
+/// ClaimStream：把 `owner` 金库的流累计的 lamports 转给 `recipient`，任何人都可以发送；
+/// 每次结算都受 `owner` 的取款冷却约束。
+/// 账户 [owner (w), recipient (w), vault, system_program, vault_state, stream (w), cooldown (w),
+/// event_authority, program]
+pub fn claim_stream_ix(program_id: &Pubkey, owner: &Pubkey, recipient: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*owner, false),
+            AccountMeta::new(*recipient, false),
+            AccountMeta::new(vault_address(program_id, owner).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(vault_state_address(program_id, owner).0, false),
+            AccountMeta::new(stream_address(program_id, owner).0, false),
+            AccountMeta::new(cooldown_address(program_id, owner).0, false),
+            AccountMeta::new_readonly(event_authority_address(program_id).0, false),
+            AccountMeta::new_readonly(*program_id, false),
+        ],
+        data: CLAIM_STREAM_DISCRIMINATOR.to_vec(),
+    }
+}
+
+/// InitializeConfig：由升级权限 `authority` 创建全局配置并指定 `admin`，每个部署一次。
+/// 账户 [authority (w, s), program_data, config (w), system_program]
+pub fn initialize_config_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    admin: &Pubkey,
+    max_vault_balance: u64,
+    min_deposit: u64,
+) -> Instruction {
+    let mut data = INITIALIZE_CONFIG_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(admin.as_ref());
+    data.extend_from_slice(&max_vault_balance.to_le_bytes());
+    data.extend_from_slice(&min_deposit.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(program_data_address(program_id), false),
+            AccountMeta::new(config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// UpdateConfig：管理员修改存款限制。账户 [admin (s), config (w)]
+pub fn update_config_ix(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    max_vault_balance: u64,
+    min_deposit: u64,
+) -> Instruction {
+    let mut data = UPDATE_CONFIG_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&max_vault_balance.to_le_bytes());
+    data.extend_from_slice(&min_deposit.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(config_address(program_id).0, false),
+        ],
+        data,
+    }
+}
+
+/// EmergencyPause：管理员暂停 deposit / top_up，取款不受影响。账户与 [`update_config_ix`] 相同
+pub fn emergency_pause_ix(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(config_address(program_id).0, false),
+        ],
+        data: EMERGENCY_PAUSE_DISCRIMINATOR.to_vec(),
+    }
+}
+
+/// Unpause：管理员恢复存款
+pub fn unpause_ix(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+    Instruction {
+        data: UNPAUSE_DISCRIMINATOR.to_vec(),
+        ..emergency_pause_ix(program_id, admin)
+    }
+}
+
+/// SetFee：管理员设置存款手续费率（基点，上限 1000）；treasury 不足免租金时由管理员补足。
+/// 账户 [admin (w, s), config (w), treasury (w), system_program]
+pub fn set_fee_ix(program_id: &Pubkey, admin: &Pubkey, fee_bps: u16) -> Instruction {
+    let mut data = SET_FEE_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&fee_bps.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(config_address(program_id).0, false),
+            AccountMeta::new(treasury_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// CollectFees：把 treasury 中超出免租金限额的手续费转给管理员。账户与 [`set_fee_ix`] 相同
+pub fn collect_fees_ix(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+    Instruction {
+        data: COLLECT_FEES_DISCRIMINATOR.to_vec(),
+        ..set_fee_ix(program_id, admin, 0)
+    }
+}
+
+/// CreatePolicy：之后取款需要 `co_signer` 一同签名。账户 [signer (w, s), policy (w), system_program]
+pub fn create_policy_ix(program_id: &Pubkey, signer: &Pubkey, co_signer: &Pubkey) -> Instruction {
+    let mut data = CREATE_POLICY_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(co_signer.as_ref());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(policy_address(program_id, signer).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// UpdatePolicy：所有者与当前共同签名者一起更换共同签名者，`new_co_signer` 为 `None` 时删除策略。
+/// 账户 [signer (w, s), co_signer (s), policy (w)]
+pub fn update_policy_ix(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    co_signer: &Pubkey,
+    new_co_signer: Option<&Pubkey>,
+) -> Instruction {
+    let mut data = UPDATE_POLICY_DISCRIMINATOR.to_vec();
+    match new_co_signer {
+        Some(co_signer) => {
+            data.push(1);
+            data.extend_from_slice(co_signer.as_ref());
+        }
+        None => data.push(0),
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new_readonly(*co_signer, true),
+            AccountMeta::new(policy_address(program_id, signer).0, false),
+        ],
+        data,
+    }
+}
+
+/// SetCooldown：两次取款之间至少间隔 `interval_seconds` 秒，0 删除冷却；冷却期内只能延长。
+/// 账户 [signer (w, s), cooldown (w), system_program]
+pub fn set_cooldown_ix(program_id: &Pubkey, signer: &Pubkey, interval_seconds: u32) -> Instruction {
+    let mut data = SET_COOLDOWN_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&interval_seconds.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(cooldown_address(program_id, signer).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+fn metadata_data(discriminator: [u8; 8], label: &str, notes: &str) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    for text in [label, notes] {
+        data.extend_from_slice(&(text.len() as u32).to_le_bytes());
+        data.extend_from_slice(text.as_bytes());
+    }
+    data
+}
+
+/// InitMetadata：为 `signer` 的金库创建元数据账户，标签不超过 32 字节、备注不超过 256 字节。
+/// 账户 [signer (w, s), metadata (w), system_program]
+pub fn init_metadata_ix(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    label: &str,
+    notes: &str,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(metadata_address(program_id, signer).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: metadata_data(INIT_METADATA_DISCRIMINATOR, label, notes),
+    }
+}
+
+/// UpdateMetadata：替换标签与备注，账户按新长度 realloc。账户与 [`init_metadata_ix`] 相同
+pub fn update_metadata_ix(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    label: &str,
+    notes: &str,
+) -> Instruction {
+    let mut ix = init_metadata_ix(program_id, signer, label, notes);
+    ix.data = metadata_data(UPDATE_METADATA_DISCRIMINATOR, label, notes);
+    ix
+}
+
+/// CloseMetadata：删除元数据账户，租金退还 `signer`。账户 [signer (w, s), metadata (w)]
+pub fn close_metadata_ix(program_id: &Pubkey, signer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(metadata_address(program_id, signer).0, false),
+        ],
+        data: CLOSE_METADATA_DISCRIMINATOR.to_vec(),
+    }
+}
+
+/// task4 Pinocchio 金库 ProvideLiquidity（判别器 2）：把金库中的 `lamports` 一半换成池子另一侧
+/// 的代币，再按池子比例存入，LP 留在金库 PDA 的 ATA 上。`pool` 必须有一侧是 wSOL 且已有流动性
+#[cfg(feature = "amm")]
+pub fn provide_liquidity_ix(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &crate::amm::Pool,
+    lamports: u64,
+    min_swap_out: u64,
+    min_lp: u64,
+) -> Instruction {
+    let vault = vault_address(program_id, owner).0;
+    let (vault_x, vault_y, vault_lp) = pool.user_atas(&vault);
+    let mut data = vec![2u8];
+    data.extend_from_slice(&lamports.to_le_bytes());
+    data.extend_from_slice(&min_swap_out.to_le_bytes());
+    data.extend_from_slice(&min_lp.to_le_bytes());
+    data.extend_from_slice(&i64::MAX.to_le_bytes()); // expiration：不过期
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new(pool.mint_lp, false),
+            AccountMeta::new_readonly(pool.mint_x, false),
+            AccountMeta::new_readonly(pool.mint_y, false),
+            AccountMeta::new(pool.vault_x, false),
+            AccountMeta::new(pool.vault_y, false),
+            AccountMeta::new(vault_x, false),
+            AccountMeta::new(vault_y, false),
+            AccountMeta::new(vault_lp, false),
+            AccountMeta::new_readonly(pool.program_id, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// task4 Pinocchio 金库 RemoveLiquidity（判别器 3）：金库 PDA 赎回 `lp` 个 LP，wSOL 一侧解包回金库，
+/// 另一侧代币留在金库 PDA 的 ATA 上（再用 [`sweep_tokens_ix`] 取回）。账户与 [`provide_liquidity_ix`] 相同
+#[cfg(feature = "amm")]
+pub fn remove_liquidity_ix(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &crate::amm::Pool,
+    lp: u64,
+    min_x: u64,
+    min_y: u64,
+) -> Instruction {
+    let mut ix = provide_liquidity_ix(program_id, owner, pool, 0, 0, 0);
+    let mut data = vec![3u8];
+    data.extend_from_slice(&lp.to_le_bytes());
+    data.extend_from_slice(&min_x.to_le_bytes());
+    data.extend_from_slice(&min_y.to_le_bytes());
+    data.extend_from_slice(&i64::MAX.to_le_bytes()); // expiration：不过期
+    ix.data = data;
+    ix
+}
+
+/// task4 Pinocchio 金库 SweepTokens（判别器 4）：把金库 PDA 在 `mint` 下 ATA 的全部余额转到 owner 的 ATA
+/// 并关闭前者。账户 [owner (w, s), vault PDA, vault ATA (w), owner ATA (w), mint, token_program,
+/// associated_token_program, system_program]
+pub fn sweep_tokens_ix(program_id: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    let vault = vault_address(program_id, owner).0;
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(ata_with_program(&vault, mint, &TOKEN_PROGRAM_ID), false),
+            AccountMeta::new(ata_with_program(owner, mint, &TOKEN_PROGRAM_ID), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: vec![4],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::hash;
+
+    #[test]
+    fn discriminators_match_anchor() {
+        assert_eq!(
+            hash(b"global:deposit").to_bytes()[..8],
+            DEPOSIT_DISCRIMINATOR
+        );
+        assert_eq!(hash(b"global:top_up").to_bytes()[..8], TOP_UP_DISCRIMINATOR);
+        assert_eq!(
+            hash(b"global:withdraw").to_bytes()[..8],
+            WITHDRAW_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:withdraw_partial").to_bytes()[..8],
+            WITHDRAW_PARTIAL_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:withdraw_to").to_bytes()[..8],
+            WITHDRAW_TO_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:close_vault").to_bytes()[..8],
+            CLOSE_VAULT_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:transfer_vault_ownership").to_bytes()[..8],
+            TRANSFER_VAULT_OWNERSHIP_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:deposit_token").to_bytes()[..8],
+            DEPOSIT_TOKEN_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:withdraw_token").to_bytes()[..8],
+            WITHDRAW_TOKEN_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:wrap_deposit").to_bytes()[..8],
+            WRAP_DEPOSIT_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:unwrap_withdraw").to_bytes()[..8],
+            UNWRAP_WITHDRAW_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:extend_lock").to_bytes()[..8],
+            EXTEND_LOCK_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:set_delegate").to_bytes()[..8],
+            SET_DELEGATE_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:withdraw_as_delegate").to_bytes()[..8],
+            WITHDRAW_AS_DELEGATE_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:open_stream").to_bytes()[..8],
+            OPEN_STREAM_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:claim_stream").to_bytes()[..8],
+            CLAIM_STREAM_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"account:VaultState").to_bytes()[..8],
+            VAULT_STATE_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"account:Stream").to_bytes()[..8],
+            STREAM_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:initialize_config").to_bytes()[..8],
+            INITIALIZE_CONFIG_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:update_config").to_bytes()[..8],
+            UPDATE_CONFIG_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"account:GlobalConfig").to_bytes()[..8],
+            GLOBAL_CONFIG_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:emergency_pause").to_bytes()[..8],
+            EMERGENCY_PAUSE_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:unpause").to_bytes()[..8],
+            UNPAUSE_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:set_fee").to_bytes()[..8],
+            SET_FEE_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:collect_fees").to_bytes()[..8],
+            COLLECT_FEES_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:create_policy").to_bytes()[..8],
+            CREATE_POLICY_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:update_policy").to_bytes()[..8],
+            UPDATE_POLICY_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:set_cooldown").to_bytes()[..8],
+            SET_COOLDOWN_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:init_metadata").to_bytes()[..8],
+            INIT_METADATA_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:update_metadata").to_bytes()[..8],
+            UPDATE_METADATA_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:close_metadata").to_bytes()[..8],
+            CLOSE_METADATA_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"account:VaultPolicy").to_bytes()[..8],
+            VAULT_POLICY_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"account:WithdrawCooldown").to_bytes()[..8],
+            WITHDRAW_COOLDOWN_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"account:VaultMetadata").to_bytes()[..8],
+            VAULT_METADATA_DISCRIMINATOR
+        );
+        assert_eq!(hash(b"global:sweep").to_bytes()[..8], SWEEP_DISCRIMINATOR);
+    }
+
+    #[test]
+    fn sweep_lists_sub_vaults_after_fixed_accounts() {
+        let (owner, co_signer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        // Vec<u8> 按 Borsh 编码：u32 长度后紧跟各序号
+        let ix = with_co_signer(sweep_ix(&PROGRAM_ID, &owner, &[0, 3]), &co_signer);
+        assert_eq!(ix.data[8..], [2, 0, 0, 0, 0, 3]);
+        assert_eq!(ix.accounts.len(), 9);
+        assert_eq!(
+            ix.accounts[6],
+            AccountMeta::new(indexed_vault_address(&PROGRAM_ID, &owner, 0).0, false)
+        );
+        assert_eq!(
+            ix.accounts[7].pubkey,
+            indexed_vault_address(&PROGRAM_ID, &owner, 3).0
+        );
+        assert_eq!(ix.accounts[8], AccountMeta::new_readonly(co_signer, true));
+        assert_ne!(ix.accounts[6].pubkey, vault_address(&PROGRAM_ID, &owner).0);
+    }
+
+    #[test]
+    fn ownership_transfer_moves_into_the_new_owners_vault() {
+        let (owner, new_owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = transfer_vault_ownership_ix(&PROGRAM_ID, &owner, &new_owner);
+        assert_eq!(ix.data[8..], new_owner.to_bytes());
+        assert_eq!(ix.accounts.len(), 10);
+        assert_eq!(
+            ix.accounts[2].pubkey,
+            vault_address(&PROGRAM_ID, &new_owner).0
+        );
+        assert!(ix.accounts[2].is_writable && !ix.accounts[2].is_signer);
+        // 其余账户仍然属于原所有者
+        assert_eq!(
+            ix.accounts[4].pubkey,
+            vault_state_address(&PROGRAM_ID, &owner).0
+        );
+    }
+
+    #[test]
+    fn token_vault_uses_the_mints_token_program() {
+        use crate::token::TOKEN_2022_PROGRAM_ID;
+
+        let (owner, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let vault = vault_address(&PROGRAM_ID, &owner).0;
+        let deposit = deposit_token_ix(&PROGRAM_ID, &owner, &mint, &TOKEN_2022_PROGRAM_ID, 7);
+        assert_eq!(deposit.data[8..], 7u64.to_le_bytes());
+        assert_eq!(
+            deposit.accounts[4].pubkey,
+            ata_with_program(&vault, &mint, &TOKEN_2022_PROGRAM_ID)
+        );
+        // 同一个 mint 在两个代币程序下的 ATA 不同
+        assert_ne!(deposit.accounts[3].pubkey, crate::token::ata(&owner, &mint));
+
+        let withdraw = withdraw_token_ix(&PROGRAM_ID, &owner, &mint, &TOKEN_2022_PROGRAM_ID, 7);
+        assert_eq!(withdraw.accounts[3].pubkey, deposit.accounts[3].pubkey);
+        assert_eq!(withdraw.accounts[4].pubkey, deposit.accounts[4].pubkey);
+        assert_eq!(withdraw.accounts[8].pubkey, TOKEN_2022_PROGRAM_ID);
+
+        // 包装出的 wSOL 与 deposit_token 存入的 wSOL 落在同一个 ATA
+        let wrap = wrap_deposit_ix(&PROGRAM_ID, &owner, 7);
+        let wsol = deposit_token_ix(&PROGRAM_ID, &owner, &NATIVE_MINT, &TOKEN_PROGRAM_ID, 7);
+        assert_eq!(wrap.accounts[3].pubkey, wsol.accounts[4].pubkey);
+        assert_eq!(
+            unwrap_withdraw_ix(&PROGRAM_ID, &owner).accounts[3].pubkey,
+            wrap.accounts[3].pubkey
+        );
+    }
+
+    #[test]
+    fn cooldown_follows_every_withdrawal_path() {
+        let (owner, delegate) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let cooldown = cooldown_address(&PROGRAM_ID, &owner).0;
+        for ix in [
+            withdraw_ix(&PROGRAM_ID, &owner),
+            withdraw_to_ix(&PROGRAM_ID, &owner, &delegate),
+            close_vault_ix(&PROGRAM_ID, &owner),
+            withdraw_as_delegate_ix(&PROGRAM_ID, &delegate, &owner),
+            open_stream_ix(&PROGRAM_ID, &owner, &delegate, 1),
+            claim_stream_ix(&PROGRAM_ID, &owner, &delegate),
+            wrap_deposit_ix(&PROGRAM_ID, &owner, 1),
+            unwrap_withdraw_ix(&PROGRAM_ID, &owner),
+            sweep_ix(&PROGRAM_ID, &owner, &[0]),
+        ] {
+            assert!(ix
+                .accounts
+                .iter()
+                .any(|meta| meta.pubkey == cooldown && meta.is_writable));
+        }
+        assert_eq!(
+            set_cooldown_ix(&PROGRAM_ID, &owner, 60).accounts[1].pubkey,
+            cooldown
+        );
+
+        let mut data = WITHDRAW_COOLDOWN_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&60i64.to_le_bytes());
+        data.extend_from_slice(&1_000i64.to_le_bytes());
+        let parsed = WithdrawCooldown::try_from_bytes(&data).unwrap();
+        assert_eq!(parsed.remaining(1_010), 50);
+        assert_eq!(parsed.remaining(1_060), 0);
+        assert_eq!(parsed.remaining(2_000), 0);
+    }
+
+    #[test]
+    fn metadata_round_trips_through_instruction_data() {
+        let owner = Pubkey::new_unique();
+        let init = init_metadata_ix(&PROGRAM_ID, &owner, "储蓄", "");
+        let update = update_metadata_ix(&PROGRAM_ID, &owner, "储蓄", "");
+        assert_eq!(init.accounts, update.accounts);
+        assert_eq!(init.data[8..], update.data[8..]);
+
+        // 账户数据 = 判别器 + created_at + 指令参数的 Borsh 编码
+        let mut data = VAULT_METADATA_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&42i64.to_le_bytes());
+        data.extend_from_slice(&init.data[8..]);
+        let metadata = VaultMetadata::try_from_bytes(&data).unwrap();
+        assert_eq!(metadata.created_at, 42);
+        assert_eq!(metadata.label, "储蓄");
+        assert!(metadata.notes.is_empty());
+
+        // 截断或多出字节都不能解析
+        assert!(VaultMetadata::try_from_bytes(&data[..data.len() - 1]).is_err());
+        data.push(0);
+        assert_eq!(
+            VaultMetadata::try_from_bytes(&data).unwrap_err().expected,
+            data.len() - 1
+        );
+        assert_eq!(
+            close_metadata_ix(&PROGRAM_ID, &owner).accounts[1].pubkey,
+            metadata_address(&PROGRAM_ID, &owner).0
+        );
+    }
+
+    #[test]
+    fn co_signer_follows_the_program_accounts() {
+        let (owner, co_signer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let policy = policy_address(&PROGRAM_ID, &owner).0;
+        let withdraw = with_co_signer(withdraw_ix(&PROGRAM_ID, &owner), &co_signer);
+        assert_eq!(withdraw.accounts[5].pubkey, policy);
+        let last = withdraw.accounts.last().unwrap();
+        assert!(last.pubkey == co_signer && last.is_signer && !last.is_writable);
+        assert_eq!(
+            withdraw_to_ix(&PROGRAM_ID, &owner, &co_signer).accounts[5].pubkey,
+            policy
+        );
+
+        let create = create_policy_ix(&PROGRAM_ID, &owner, &co_signer);
+        let mut data = VAULT_POLICY_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&create.data[8..]);
+        assert_eq!(
+            VaultPolicy::try_from_bytes(&data).unwrap().co_signer,
+            co_signer
+        );
+
+        let remove = update_policy_ix(&PROGRAM_ID, &owner, &co_signer, None);
+        assert_eq!(remove.data[8..], [0]);
+        assert!(remove.accounts[1].is_signer);
+        assert_eq!(remove.accounts[2].pubkey, policy);
+    }
+
+    #[test]
+    fn config_instructions_share_limit_encoding() {
+        let (authority, admin) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let init = initialize_config_ix(&PROGRAM_ID, &authority, &admin, 5, 1);
+        let update = update_config_ix(&PROGRAM_ID, &admin, 5, 1);
+        assert_eq!(init.data[8..40], admin.to_bytes());
+        assert_eq!(init.data[40..], update.data[8..]);
+        assert_eq!(init.accounts[2].pubkey, update.accounts[1].pubkey);
+
+        assert_eq!(
+            emergency_pause_ix(&PROGRAM_ID, &admin).accounts,
+            update.accounts
+        );
+
+        let set_fee = set_fee_ix(&PROGRAM_ID, &admin, 250);
+        assert_eq!(set_fee.accounts[1].pubkey, update.accounts[1].pubkey);
+        assert_eq!(set_fee.accounts[2].pubkey, treasury_address(&PROGRAM_ID).0);
+
+        let mut data = GLOBAL_CONFIG_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&init.data[8..]);
+        data.push(1);
+        data.extend_from_slice(&set_fee.data[8..]);
+        let config = GlobalConfig::try_from_bytes(&data).unwrap();
+        assert_eq!(
+            config,
+            GlobalConfig {
+                admin,
+                max_vault_balance: 5,
+                min_deposit: 1,
+                paused: true,
+                fee_bps: 250,
+            }
+        );
+        // 2.5%，向下取整
+        assert_eq!(config.fee(1_000_000), 25_000);
+        assert_eq!(config.fee(39), 0);
+        assert_eq!(config.fee(u64::MAX), u64::MAX / 40);
+    }
+
+    #[test]
+    fn stream_parses_and_accrues_like_the_program() {
+        let (owner, recipient) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = STREAM_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(recipient.as_ref());
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        let stream = Stream::try_from_bytes(&data).unwrap();
+        assert_eq!(stream.recipient, recipient);
+        assert_eq!(
+            stream.claim_ix(&PROGRAM_ID).accounts[5].pubkey,
+            stream_address(&PROGRAM_ID, &owner).0
+        );
+        assert_eq!(stream.accrued(1_700_000_060), 60_000);
+        // 时钟回拨不会产生负数，极大速率饱和而不是溢出
+        assert_eq!(stream.accrued(1_699_999_999), 0);
+        let fast = Stream {
+            rate_per_second: u64::MAX,
+            ..stream
+        };
+        assert_eq!(fast.accrued(1_700_000_002), u64::MAX);
+
+        let open = open_stream_ix(&PROGRAM_ID, &Pubkey::new_unique(), &recipient, 1_000);
+        assert_eq!(open.data[8..40], recipient.to_bytes());
+        assert_eq!(open.data[40..], 1_000u64.to_le_bytes());
+    }
+
+    #[test]
+    fn deposit_carries_lock_and_state_account() {
+        let signer = Pubkey::new_unique();
+        let ix = deposit_locked_ix(&PROGRAM_ID, &signer, 5, 3_600);
+        assert_eq!(ix.data[8..16], 5u64.to_le_bytes());
+        assert_eq!(ix.data[16..], 3_600u32.to_le_bytes());
+        assert_eq!(deposit_ix(&PROGRAM_ID, &signer, 5).data[16..], [0; 4]);
+        assert_eq!(
+            ix.accounts[3].pubkey,
+            vault_state_address(&PROGRAM_ID, &signer).0
+        );
+
+        let mut data = VAULT_STATE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        data.resize(VaultState::LEN, 0);
+        assert_eq!(
+            VaultState::try_from_bytes(&data).unwrap(),
+            VaultState {
+                unlock_timestamp: 1_700_000_000,
+                delegate: None,
+            }
+        );
+        assert!(VaultState::try_from_bytes(&data[1..]).is_err());
+    }
+
+    #[test]
+    fn delegate_is_encoded_as_borsh_option() {
+        let (owner, delegate) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let set = set_delegate_ix(&PROGRAM_ID, &owner, Some(&delegate));
+        assert_eq!(set.data[8], 1);
+        assert_eq!(set.data[9..], delegate.to_bytes());
+        assert_eq!(set_delegate_ix(&PROGRAM_ID, &owner, None).data[8..], [0]);
+
+        // 代理人签名，金库与状态账户仍由 owner 派生
+        let withdraw = withdraw_as_delegate_ix(&PROGRAM_ID, &delegate, &owner);
+        assert!(withdraw.accounts[0].is_signer && !withdraw.accounts[1].is_signer);
+        assert_eq!(
+            withdraw.accounts[2].pubkey,
+            vault_address(&PROGRAM_ID, &owner).0
+        );
+        assert_eq!(
+            withdraw.accounts[4].pubkey,
+            vault_state_address(&PROGRAM_ID, &owner).0
+        );
+
+        let mut data = VAULT_STATE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&0i64.to_le_bytes());
+        data.extend_from_slice(&set.data[8..]);
+        assert_eq!(
+            VaultState::try_from_bytes(&data).unwrap().delegate,
+            Some(delegate)
+        );
+    }
+
+    #[test]
+    fn sweep_tokens_moves_vault_ata_to_owner_ata() {
+        let (owner, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let vault = vault_address(&PROGRAM_ID, &owner).0;
+        let ix = sweep_tokens_ix(&PROGRAM_ID, &owner, &mint);
+        assert_eq!(ix.data, [4]);
+        assert!(ix.accounts[0].is_signer && !ix.accounts[1].is_writable);
+        assert_eq!(ix.accounts[1].pubkey, vault);
+        assert_eq!(
+            ix.accounts[2].pubkey,
+            ata_with_program(&vault, &mint, &TOKEN_PROGRAM_ID)
+        );
+        assert_eq!(
+            ix.accounts[3].pubkey,
+            ata_with_program(&owner, &mint, &TOKEN_PROGRAM_ID)
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! 所有程序共用的 `get_version` / `set_features`（格式见 `blueshift_version`）。
+//!
+//! 判别器是 8 字节的 Anchor 风格判别器，Anchor 程序与 Pinocchio 程序都能识别。
+//! 只有 Pinocchio 程序有功能开关账户；Anchor 程序与 pxsol-ss 的 `get_version` 不读取账户，
+//! 返回的 `enabled` 恒为 0。结果在交易的 return data 中，用 [`Version::decode`] 解析，
+//! 需要 RPC 时直接用 `tx::fetch_version`。
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+use solana_sdk_ids::system_program;
+
+pub use blueshift_version::{
+    features, FeatureGate, Version, GET_VERSION_DISCRIMINATOR, SET_FEATURES_DISCRIMINATOR,
+};
+
+/// 功能开关账户 PDA：[b"feature_gate"]，由程序自己持有
+pub fn feature_gate_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    let (address, bump) = pdas::feature_gate_pda(&program_id.to_bytes());
+    (Pubkey::new_from_array(address), bump)
+}
+
+/// 程序在 Upgradeable Loader 下的 ProgramData 账户，`set_features` 从中读取升级权限
+pub fn program_data_address(program_id: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(pdas::program_data_pda(&program_id.to_bytes()).0)
+}
+
+/// 查询版本号与功能位；`with_gate` 为真时带上功能开关账户，结果中才有已启用的功能
+pub fn get_version_ix(program_id: &Pubkey, with_gate: bool) -> Instruction {
+    let accounts = if with_gate {
+        vec![AccountMeta::new_readonly(
+            feature_gate_address(program_id).0,
+            false,
+        )]
+    } else {
+        Vec::new()
+    };
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: GET_VERSION_DISCRIMINATOR.to_vec(),
+    }
+}
+
+/// 覆盖写入功能开关账户中的功能位（只有 Pinocchio 程序支持）。
+/// `authority` 必须是程序当前的升级权限，首次调用时由它支付开关账户的租金
+pub fn set_features_ix(program_id: &Pubkey, authority: &Pubkey, flags: u64) -> Instruction {
+    let mut data = SET_FEATURES_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&flags.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(program_data_address(program_id), false),
+            AccountMeta::new(feature_gate_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::hash;
+    use solana_sdk_ids::bpf_loader_upgradeable;
+
+    #[test]
+    fn discriminators_and_accounts_match_programs() {
+        assert_eq!(
+            hash(b"global:get_version").to_bytes()[..8],
+            GET_VERSION_DISCRIMINATOR
+        );
+        assert_eq!(
+            hash(b"global:set_features").to_bytes()[..8],
+            SET_FEATURES_DISCRIMINATOR
+        );
+
+        let program_id = Pubkey::new_unique();
+        assert_eq!(
+            program_data_address(&program_id),
+            Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID).0
+        );
+        let gate = Pubkey::find_program_address(&[b"feature_gate"], &program_id);
+        assert_eq!(feature_gate_address(&program_id), gate);
+
+        assert!(get_version_ix(&program_id, false).accounts.is_empty());
+        assert_eq!(get_version_ix(&program_id, true).accounts[0].pubkey, gate.0);
+        let ix = set_features_ix(&program_id, &Pubkey::new_unique(), 0b101);
+        assert_eq!(ix.data[8..], 5u64.to_le_bytes());
+        assert_eq!(ix.accounts[2].pubkey, gate.0);
+    }
+}
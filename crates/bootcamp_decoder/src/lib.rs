@@ -0,0 +1,324 @@
+//! 训练营各程序账户的解析：输入账户所属程序与账户数据，输出带类型的 [`BootcampAccount`]。
+//!
+//! | 账户          | 程序                        | 识别方式                                   |
+//! |---------------|-----------------------------|--------------------------------------------|
+//! | [`VaultState`]| 金库（task2 Anchor / task4）| 没有数据，余额即存款                       |
+//! | [`Escrow`]    | Pinocchio 托管（task5）     | `Escrow::LEN` 字节，首字节判别器 `1`       |
+//! | [`Escrow`]    | Anchor 托管（task3）        | 与 task5 逐字节相同                        |
+//! | [`AmmConfig`] | 原生 AMM（task6）           | `AmmConfig::LEN` 字节                      |
+//! | [`Treasury`]  | 国库                        | `Treasury::LEN` 字节                       |
+//!
+//! 各程序默认都部署在 2222…2222，只看 program_id 分不出是哪个程序，所以 [`Decoder`]
+//! 先找出注册在该地址上的候选程序，再按数据长度和判别器确定账户类型。
+//!
+//! 金库 PDA 归 System Program 所有，Geyser 推送时 owner 是 System Program；
+//! 调用方需要先按地址（`pdas::vault_pda`）确认它是金库，再以金库程序 ID 调用 [`Decoder::decode`]。
+//!
+//! 事件与账户使用同一个 [`Decoder`]：Pinocchio 程序的事件在日志里（`events::events_from_logs`），
+//! Anchor 程序的事件在自调用的内部指令里，用 [`Decoder::decode_event_cpi`] 解析，
+//! 两种来源解出的都是同一个 [`ProgramEvent`]。
+//!
+//! 所有账户类型都实现了 serde，地址序列化为 base58 字符串：
+//!
+//! ```json
+//! {"type":"amm_config","state":1,"seed":0,"authority":"1111…","mint_x":"So11…", …}
+//! ```
+
+use core::fmt;
+
+use bootcamp_client::{amm, escrow, pdas, treasury, vault};
+pub use bootcamp_client::{
+    amm::AmmConfig,
+    escrow::Escrow,
+    events::{self, ProgramEvent},
+    treasury::Treasury,
+    Pubkey,
+};
+use serde::{Deserialize, Serialize};
+
+/// Anchor 托管（task3）`#[account(discriminator = 1)]` 的判别器，与 Pinocchio 托管的
+/// [`Escrow::DISCRIMINATOR`] 相同
+pub const ANCHOR_ESCROW_DISCRIMINATOR: u8 = Escrow::DISCRIMINATOR;
+
+/// 训练营程序
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Program {
+    /// SOL 金库，Anchor（task2）与 Pinocchio（task4）账户相同
+    Vault,
+    /// Pinocchio 托管（task5）
+    Escrow,
+    /// Anchor 托管（task3）
+    AnchorEscrow,
+    /// 原生 AMM（task6）
+    Amm,
+    /// 国库，各程序的协议手续费汇集于此
+    Treasury,
+}
+
+impl Program {
+    /// 数据形状符合本程序的某种账户时返回解析结果
+    fn decode(self, lamports: u64, data: &[u8]) -> Option<BootcampAccount> {
+        match self {
+            Self::Vault if data.is_empty() => Some(BootcampAccount::Vault(VaultState { lamports })),
+            // 两个托管实现的账户布局相同，判别器由 try_from_bytes 检查
+            Self::Escrow | Self::AnchorEscrow if data.len() == Escrow::LEN => {
+                Escrow::try_from_bytes(data)
+                    .ok()
+                    .map(BootcampAccount::Escrow)
+            }
+            Self::Amm if data.len() == AmmConfig::LEN => AmmConfig::try_from_bytes(data)
+                .ok()
+                .map(BootcampAccount::AmmConfig),
+            Self::Treasury if data.len() == Treasury::LEN => Treasury::try_from_bytes(data)
+                .ok()
+                .map(BootcampAccount::Treasury),
+            _ => None,
+        }
+    }
+}
+
+/// 金库状态：金库是不带数据的系统账户，存款就是账户余额
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct VaultState {
+    pub lamports: u64,
+}
+
+/// 解析出的账户，序列化时以 `type` 字段区分
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BootcampAccount {
+    Vault(VaultState),
+    Escrow(Escrow),
+    AmmConfig(AmmConfig),
+    Treasury(Treasury),
+}
+
+/// 无法识别的账户
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// 该地址上没有注册训练营程序
+    UnknownProgram(Pubkey),
+    /// 程序已注册，但数据不符合其任何账户的布局
+    UnknownAccount { program_id: Pubkey, len: usize },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownProgram(program_id) => write!(f, "{program_id} 不是已注册的训练营程序"),
+            Self::UnknownAccount { program_id, len } => {
+                write!(f, "{program_id} 下没有长度为 {len} 字节的已知账户")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// 程序地址到程序的映射，同一地址可以注册多个程序
+#[derive(Clone, Debug)]
+pub struct Decoder {
+    programs: Vec<(Pubkey, Program)>,
+}
+
+impl Default for Decoder {
+    /// 全部程序，使用 bootcamp_client 中的默认部署地址
+    fn default() -> Self {
+        Self::empty()
+            .with_program(Program::Vault, vault::PROGRAM_ID)
+            .with_program(Program::Escrow, escrow::PROGRAM_ID)
+            .with_program(Program::AnchorEscrow, escrow::PROGRAM_ID)
+            .with_program(Program::Amm, amm::PROGRAM_ID)
+            .with_program(Program::Treasury, treasury::PROGRAM_ID)
+    }
+}
+
+impl Decoder {
+    /// 不含任何程序，用 [`Decoder::with_program`] 按实际部署地址逐个注册
+    pub fn empty() -> Self {
+        Self {
+            programs: Vec::new(),
+        }
+    }
+
+    pub fn with_program(mut self, program: Program, program_id: Pubkey) -> Self {
+        self.programs.push((program_id, program));
+        self
+    }
+
+    /// 解析 `program_id` 下的一个账户；`lamports` 只有金库会用到
+    pub fn decode(
+        &self,
+        program_id: &Pubkey,
+        lamports: u64,
+        data: &[u8],
+    ) -> Result<BootcampAccount, DecodeError> {
+        let mut candidates = self
+            .programs
+            .iter()
+            .filter(|(id, _)| id == program_id)
+            .peekable();
+        if candidates.peek().is_none() {
+            return Err(DecodeError::UnknownProgram(*program_id));
+        }
+        candidates
+            .find_map(|(_, program)| program.decode(lamports, data))
+            .ok_or(DecodeError::UnknownAccount {
+                program_id: *program_id,
+                len: data.len(),
+            })
+    }
+
+    /// 解析一条内部指令中的事件 CPI。只有调用已注册的程序、且第一个账户是该程序
+    /// event_authority PDA 的指令才算事件，其余指令（包括别人伪造的同样数据）返回 `None`
+    pub fn decode_event_cpi(
+        &self,
+        program_id: &Pubkey,
+        accounts: &[Pubkey],
+        data: &[u8],
+    ) -> Option<Result<ProgramEvent, events::DecodeError>> {
+        if !self.programs.iter().any(|(id, _)| id == program_id) {
+            return None;
+        }
+        let (authority, _) = pdas::event_authority_pda(&program_id.to_bytes());
+        if accounts.first().map(|key| key.to_bytes()) != Some(authority) {
+            return None;
+        }
+        ProgramEvent::decode_cpi(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escrow_bytes(maker: &Pubkey) -> Vec<u8> {
+        let mut data = vec![0u8; Escrow::LEN];
+        data[0] = Escrow::DISCRIMINATOR;
+        data[1..9].copy_from_slice(&7u64.to_le_bytes());
+        data[9..41].copy_from_slice(maker.as_ref());
+        data[105..113].copy_from_slice(&500u64.to_le_bytes());
+        data[113] = 254;
+        data
+    }
+
+    #[test]
+    fn shared_program_id_is_resolved_by_layout() {
+        let decoder = Decoder::default();
+        let id = escrow::PROGRAM_ID;
+        let maker = Pubkey::new_unique();
+
+        // 两个托管实现写出的字节相同，解析结果也相同
+        let data = escrow_bytes(&maker);
+        let BootcampAccount::Escrow(escrow) = decoder.decode(&id, 0, &data).unwrap() else {
+            panic!("应解析为托管");
+        };
+        assert_eq!((escrow.seed, escrow.maker, escrow.receive), (7, maker, 500));
+        // 判别器不符（例如已关闭的账户）不算托管
+        let mut closed = data.clone();
+        closed[0] = 0xff;
+        assert_eq!(
+            decoder.decode(&id, 0, &closed),
+            Err(DecodeError::UnknownAccount {
+                program_id: id,
+                len: Escrow::LEN
+            })
+        );
+
+        assert_eq!(
+            decoder.decode(&id, 2_000_000, &[]),
+            Ok(BootcampAccount::Vault(VaultState {
+                lamports: 2_000_000
+            }))
+        );
+        assert!(matches!(
+            decoder.decode(&id, 0, &[0; AmmConfig::LEN]),
+            Ok(BootcampAccount::AmmConfig(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_program_and_layout() {
+        let decoder = Decoder::empty().with_program(Program::Amm, amm::PROGRAM_ID);
+        let other = Pubkey::new_unique();
+        assert_eq!(
+            decoder.decode(&other, 0, &[]),
+            Err(DecodeError::UnknownProgram(other))
+        );
+        // 只注册了 AMM 时，托管数据不会被误认
+        assert_eq!(
+            decoder.decode(&amm::PROGRAM_ID, 0, &escrow_bytes(&other)),
+            Err(DecodeError::UnknownAccount {
+                program_id: amm::PROGRAM_ID,
+                len: Escrow::LEN
+            })
+        );
+    }
+
+    #[test]
+    fn event_cpi_requires_event_authority() {
+        use events::{Event, VaultDepositedV2};
+
+        let decoder = Decoder::default();
+        let deposited = VaultDepositedV2 {
+            owner: [1; 32],
+            vault: [2; 32],
+            amount: 5,
+            timestamp: 0,
+        };
+        let data = deposited.cpi_data();
+        let authority = vault::event_authority_address(&vault::PROGRAM_ID).0;
+        assert_eq!(
+            decoder.decode_event_cpi(&vault::PROGRAM_ID, &[authority], &data),
+            Some(Ok(ProgramEvent::VaultDepositedV2(deposited)))
+        );
+        // 签名者不是 event_authority，或者程序没有注册：都不是训练营程序发出的事件
+        let stranger = Pubkey::new_unique();
+        assert_eq!(
+            decoder.decode_event_cpi(&vault::PROGRAM_ID, &[stranger], &data),
+            None
+        );
+        assert_eq!(
+            decoder.decode_event_cpi(&stranger, &[authority], &data),
+            None
+        );
+        // 普通指令不是事件 CPI
+        assert_eq!(
+            decoder.decode_event_cpi(&vault::PROGRAM_ID, &[authority], &data[8..]),
+            None
+        );
+    }
+
+    #[test]
+    fn treasury_has_its_own_program_id() {
+        let decoder = Decoder::default();
+        let mut data = vec![0u8; Treasury::LEN];
+        data[33..65].copy_from_slice(&[9; 32]);
+        data[97 + 16..97 + 24].copy_from_slice(&42u64.to_le_bytes());
+        let BootcampAccount::Treasury(state) =
+            decoder.decode(&treasury::PROGRAM_ID, 0, &data).unwrap()
+        else {
+            panic!("应解析为国库");
+        };
+        assert_eq!(state.withdrawer, Pubkey::new_from_array([9; 32]));
+        assert_eq!(state.deposited[2], 42);
+        // 同样长度的数据放在 2222…2222 下不会被当成国库
+        assert!(decoder.decode(&escrow::PROGRAM_ID, 0, &data).is_err());
+    }
+
+    #[test]
+    fn json_uses_type_tag_and_base58() {
+        let maker = Pubkey::new_unique();
+        let account = Decoder::default()
+            .decode(&escrow::PROGRAM_ID, 0, &escrow_bytes(&maker))
+            .unwrap();
+        let json = serde_json::to_value(&account).unwrap();
+        assert_eq!(json["type"], "escrow");
+        assert_eq!(json["maker"], maker.to_string());
+        assert_eq!(
+            serde_json::from_value::<BootcampAccount>(json).unwrap(),
+            account
+        );
+    }
+}
@@ -0,0 +1,464 @@
+//! bootstrap：在 devnet 上准备一套可以直接上手的训练营环境。
+//!
+//! ```text
+//! cargo xtask deploy --cluster devnet -p escrow -p amm -p treasury -p mock-oracle
+//! cargo run -p bootstrap -- --keypair ~/.config/solana/id.json
+//! xcli --url https://api.devnet.solana.com --keypair target/devnet/wallets/1.json \
+//!     --escrow-program <escrow.program> escrow take --maker <escrow.maker> --seed 1
+//! ```
+//!
+//! 付费钱包依次完成：
+//! 1. 测试钱包写在 `<dir>/wallets/<i>.json`，先向水龙头申请空投，被限流时改由付费钱包转账；
+//! 2. 两个演示 mint（按地址排序，可以直接组成规范池），铸币权限是付费钱包，
+//!    付费钱包与每个测试钱包各得到一份代币；
+//! 3. 初始化规范 AMM 池，并由付费钱包注入初始流动性；
+//! 4. 国库 Initialize（付费钱包必须是国库程序的升级权限）与模拟预言机的示例价格源；
+//! 5. 0 号测试钱包挂出一笔托管：用 mint 0 换 mint 1。
+//!
+//! 钱包与 mint 的密钥保存在 `<dir>` 下，已经存在的账户不会重复创建，中途失败后可以直接重跑。
+//! 集群上没有部署的程序跳过对应步骤。全部地址以 JSON 打印到标准输出，同时写入
+//! `<dir>/bootstrap.json`；进度与交易签名打印到标准错误。
+
+mod programs;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, bail, ensure, Context as _, Result};
+use bootcamp_client::{
+    amm::{self, Pool},
+    escrow,
+    oracle::{self, Price},
+    token::{
+        ata, create_ata_idempotent_ix, initialize_mint2_ix, mint_to_ix, MINT_LEN, TOKEN_PROGRAM_ID,
+    },
+    treasury,
+    tx::{self, PriorityFee, TxBuilder},
+};
+use clap::Parser;
+use serde_json::{json, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, write_keypair_file, Keypair, Signer},
+    system_instruction,
+};
+
+use crate::programs::{cluster_name, Deployed, DEPLOYMENTS};
+
+const SOL: u64 = 1_000_000_000;
+
+/// 示例托管固定使用的 seed，重跑时能找到上一次挂出的托管
+const ESCROW_SEED: u64 = 1;
+
+/// 示例价格源：feed ID 为 ASCII "SOL/USD" 右侧补零，报价 150 USD（expo -8）
+const FEED_NAME: &[u8] = b"SOL/USD";
+const FEED_PRICE: i64 = 150 * 100_000_000;
+
+#[derive(Parser)]
+#[command(
+    name = "bootstrap",
+    version,
+    about = "在 devnet 上创建训练营的测试钱包、mint、AMM 池、配置 PDA 与示例托管"
+)]
+struct Cli {
+    /// RPC 地址
+    #[arg(
+        long,
+        env = "BOOTSTRAP_URL",
+        default_value = "https://api.devnet.solana.com"
+    )]
+    url: String,
+
+    /// 有余额的付费钱包（默认 ~/.config/solana/id.json），同时是 mint 的铸币权限与池子管理员
+    #[arg(long, env = "BOOTSTRAP_KEYPAIR")]
+    keypair: Option<String>,
+
+    /// 输出目录：钱包与 mint 的密钥、bootstrap.json
+    #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/devnet"))]
+    dir: PathBuf,
+
+    /// 测试钱包个数（至少 1 个，0 号钱包是示例托管的 maker）
+    #[arg(long, default_value_t = 2)]
+    wallets: usize,
+
+    /// 每个测试钱包至少持有的 lamports
+    #[arg(long, default_value_t = SOL)]
+    lamports: u64,
+
+    /// 演示 mint 的小数位
+    #[arg(long, default_value_t = 6)]
+    decimals: u8,
+
+    /// 付费钱包与每个测试钱包在每个 mint 上得到的代币数量（最小单位）
+    #[arg(long, default_value_t = 1_000_000_000)]
+    tokens: u64,
+
+    /// AMM 池手续费（基点）
+    #[arg(long, default_value_t = 30)]
+    fee: u16,
+
+    /// 初始流动性：X / Y 各存入的数量，同时是铸造的 LP 数量
+    #[arg(long, default_value_t = 100_000_000)]
+    liquidity: u64,
+
+    /// 示例托管存入的 mint 0 数量
+    #[arg(long, default_value_t = 10_000_000)]
+    escrow_amount: u64,
+
+    /// 示例托管要求支付的 mint 1 数量
+    #[arg(long, default_value_t = 20_000_000)]
+    escrow_receive: u64,
+
+    /// 优先费（micro-lamports / CU）；不指定时取写入账户最近优先费的 75 分位
+    #[arg(long, env = "BOOTSTRAP_PRIORITY_FEE")]
+    priority_fee: Option<u64>,
+
+    /// escrow 程序 ID；默认取 deployments.json 中该集群的记录，没有记录时取源码中的声明
+    #[arg(long)]
+    escrow_program: Option<Pubkey>,
+
+    /// amm 程序 ID
+    #[arg(long)]
+    amm_program: Option<Pubkey>,
+
+    /// treasury 程序 ID
+    #[arg(long)]
+    treasury_program: Option<Pubkey>,
+
+    /// mock-oracle 程序 ID
+    #[arg(long)]
+    oracle_program: Option<Pubkey>,
+}
+
+fn keypair(path: Option<&str>) -> Result<Keypair> {
+    let path = match path {
+        Some(path) => path.to_string(),
+        None => {
+            let home = std::env::var("HOME").context("未设置 HOME，请用 --keypair 指定钱包")?;
+            format!("{home}/.config/solana/id.json")
+        }
+    };
+    read_keypair_file(&path).map_err(|e| anyhow!("读取钱包 {path} 失败: {e}"))
+}
+
+/// 读取 `path` 中的密钥，不存在时生成并写入，重跑时沿用同一批钱包与 mint
+fn load_or_create(path: &Path) -> Result<Keypair> {
+    if path.exists() {
+        return read_keypair_file(path).map_err(|e| anyhow!("读取 {} 失败: {e}", path.display()));
+    }
+    let keypair = Keypair::new();
+    write_keypair_file(&keypair, path).map_err(|e| anyhow!("写入 {} 失败: {e}", path.display()))?;
+    Ok(keypair)
+}
+
+fn unix_time() -> Result<i64> {
+    Ok(SystemTime::now()
+        .duration_since(UNIX_EPOCH)?
+        .as_secs()
+        .try_into()?)
+}
+
+struct Bootstrap {
+    client: RpcClient,
+    payer: Keypair,
+    priority_fee: PriorityFee,
+}
+
+impl Bootstrap {
+    fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// 由付费钱包付费，`signers` 是除它之外还需要签名的账户
+    fn send(&self, label: &str, ixs: Vec<Instruction>, signers: &[&Keypair]) -> Result<()> {
+        let mut builder = TxBuilder::new(&self.client, &self.payer)
+            .instructions(ixs)
+            .priority_fee(self.priority_fee);
+        for signer in signers {
+            builder = builder.signer(*signer);
+        }
+        match builder.send() {
+            Ok(signature) => {
+                eprintln!("{label}: {signature}");
+                Ok(())
+            }
+            Err(err) => {
+                let reason = tx::failure(&err)
+                    .map_or_else(|| err.to_string(), |failure| failure.to_string());
+                bail!("{label}失败: {reason}")
+            }
+        }
+    }
+
+    fn exists(&self, address: &Pubkey) -> Result<bool> {
+        let account = self
+            .client
+            .get_account_with_commitment(address, self.client.commitment())?
+            .value;
+        Ok(account.is_some())
+    }
+
+    /// `program_id` 上有可执行程序；没有时打印提示，调用方跳过对应步骤
+    fn deployed(&self, name: &str, program_id: &Pubkey) -> Result<bool> {
+        let account = self
+            .client
+            .get_account_with_commitment(program_id, self.client.commitment())?
+            .value;
+        let deployed = account.is_some_and(|account| account.executable);
+        if !deployed {
+            eprintln!("跳过 {name}：{program_id} 上没有部署程序");
+        }
+        Ok(deployed)
+    }
+
+    /// 补足到 `lamports`：先申请空投，失败或 30 秒内未确认时改由付费钱包转账
+    fn fund(&self, wallet: &Pubkey, lamports: u64) -> Result<()> {
+        let balance = self.client.get_balance(wallet)?;
+        let Some(missing) = lamports.checked_sub(balance).filter(|&missing| missing > 0) else {
+            return Ok(());
+        };
+        if let Ok(signature) = self.client.request_airdrop(wallet, missing) {
+            for _ in 0..30 {
+                if self.client.confirm_transaction(&signature).unwrap_or(false) {
+                    eprintln!("空投 {wallet}: {signature}");
+                    return Ok(());
+                }
+                thread::sleep(Duration::from_secs(1));
+            }
+        }
+        let transfer = system_instruction::transfer(&self.payer(), wallet, missing);
+        self.send(&format!("转账 {wallet}"), vec![transfer], &[])
+    }
+
+    fn create_mint(&self, mint: &Keypair, decimals: u8) -> Result<()> {
+        if self.exists(&mint.pubkey())? {
+            return Ok(());
+        }
+        let rent = self
+            .client
+            .get_minimum_balance_for_rent_exemption(MINT_LEN)?;
+        let ixs = vec![
+            system_instruction::create_account(
+                &self.payer(),
+                &mint.pubkey(),
+                rent,
+                MINT_LEN as u64,
+                &TOKEN_PROGRAM_ID,
+            ),
+            initialize_mint2_ix(&mint.pubkey(), decimals, &self.payer()),
+        ];
+        self.send(&format!("创建 mint {}", mint.pubkey()), ixs, &[mint])
+    }
+
+    /// 为 `holder` 创建各 mint 上的 ATA 并铸币；ATA 已存在的 mint 不再增发
+    fn mint_to_holder(&self, holder: &Pubkey, mints: &[Pubkey], amount: u64) -> Result<()> {
+        let mut ixs = Vec::new();
+        for mint in mints {
+            let account = ata(holder, mint);
+            if self.exists(&account)? {
+                continue;
+            }
+            ixs.push(create_ata_idempotent_ix(&self.payer(), holder, mint));
+            ixs.push(mint_to_ix(mint, &account, &self.payer(), amount));
+        }
+        if ixs.is_empty() {
+            return Ok(());
+        }
+        self.send(&format!("向 {holder} 铸币"), ixs, &[])
+    }
+
+    /// 规范池第一次存入时按 max_x / max_y 全额注入，铸造 `liquidity` 个 LP
+    fn init_pool(&self, pool: &Pool, fee: u16, liquidity: u64) -> Result<()> {
+        if self.exists(&pool.config)? {
+            return Ok(());
+        }
+        let payer = self.payer();
+        self.send(
+            "初始化 AMM 池",
+            vec![pool.initialize_ix(&payer, fee, &payer)],
+            &[],
+        )?;
+        self.send(
+            "注入流动性",
+            vec![
+                create_ata_idempotent_ix(&payer, &payer, &pool.mint_lp),
+                pool.deposit_ix(&payer, liquidity, liquidity, liquidity),
+            ],
+            &[],
+        )
+    }
+
+    /// 付费钱包同时担任 admin / withdrawer / distributor，之后可以用 set_role 移交
+    fn init_treasury(&self, program_id: &Pubkey) -> Result<Pubkey> {
+        let (address, _) = treasury::treasury_address(program_id);
+        if !self.exists(&address)? {
+            let payer = self.payer();
+            let ix = treasury::initialize_ix(program_id, &payer, &payer, &payer, &payer);
+            self.send("初始化国库", vec![ix], &[])?;
+        }
+        Ok(address)
+    }
+
+    /// 创建示例价格源（已存在时跳过），每次都刷新报价与发布时间，避免读取方判定过期
+    fn init_feed(&self, program_id: &Pubkey, feed_id: &[u8; 32]) -> Result<Pubkey> {
+        let (address, _) = oracle::price_feed_address(program_id, feed_id);
+        let payer = self.payer();
+        let mut ixs = Vec::new();
+        if !self.exists(&address)? {
+            ixs.push(oracle::init_feed_ix(program_id, &payer, feed_id));
+        }
+        let price = Price {
+            price: FEED_PRICE,
+            conf: FEED_PRICE / 1_000,
+            expo: -8,
+            publish_time: unix_time()?,
+        };
+        ixs.push(oracle::set_price_ix(program_id, &payer, feed_id, &price));
+        self.send("写入示例报价", ixs, &[])?;
+        Ok(address)
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    ensure!(cli.wallets >= 1, "--wallets 至少为 1");
+
+    let deployed = Deployed::load(Path::new(DEPLOYMENTS), cluster_name(&cli.url))?;
+    let escrow_program = deployed.resolve("escrow", cli.escrow_program, escrow::PROGRAM_ID);
+    let amm_program = deployed.resolve("amm", cli.amm_program, amm::PROGRAM_ID);
+    let treasury_program = deployed.resolve("treasury", cli.treasury_program, treasury::PROGRAM_ID);
+    let oracle_program = deployed.resolve("mock-oracle", cli.oracle_program, oracle::PROGRAM_ID);
+
+    let bootstrap = Bootstrap {
+        client: RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed()),
+        payer: keypair(cli.keypair.as_deref())?,
+        priority_fee: cli
+            .priority_fee
+            .map_or(PriorityFee::default(), PriorityFee::Fixed),
+    };
+    let payer = bootstrap.payer();
+    fs::create_dir_all(cli.dir.join("wallets"))?;
+    fs::create_dir_all(cli.dir.join("mints"))?;
+
+    // 1. 测试钱包
+    let mut wallets = Vec::new();
+    let mut wallet_json = Vec::new();
+    for index in 0..cli.wallets {
+        let path = cli.dir.join("wallets").join(format!("{index}.json"));
+        let wallet = load_or_create(&path)?;
+        bootstrap.fund(&wallet.pubkey(), cli.lamports)?;
+        wallet_json.push(json!({
+            "address": wallet.pubkey().to_string(),
+            "keypair": path.display().to_string(),
+        }));
+        wallets.push(wallet);
+    }
+
+    // 2. mint 与 ATA
+    let mut mint_keys = (0..2)
+        .map(|index| load_or_create(&cli.dir.join("mints").join(format!("{index}.json"))))
+        .collect::<Result<Vec<_>>>()?;
+    mint_keys.sort_by_key(Signer::pubkey);
+    for mint in &mint_keys {
+        bootstrap.create_mint(mint, cli.decimals)?;
+    }
+    let mints: Vec<Pubkey> = mint_keys.iter().map(Signer::pubkey).collect();
+    let holders = std::iter::once(payer).chain(wallets.iter().map(Signer::pubkey));
+    for holder in holders {
+        bootstrap.mint_to_holder(&holder, &mints, cli.tokens)?;
+    }
+
+    // 3. AMM 规范池
+    let amm = if bootstrap.deployed("amm", &amm_program)? {
+        let pool = Pool::canonical(&amm_program, &mints[0], &mints[1]);
+        bootstrap.init_pool(&pool, cli.fee, cli.liquidity)?;
+        json!({
+            "program": amm_program.to_string(),
+            "config": pool.config.to_string(),
+            "mint_lp": pool.mint_lp.to_string(),
+            "vault_x": pool.vault_x.to_string(),
+            "vault_y": pool.vault_y.to_string(),
+            "fee": cli.fee,
+        })
+    } else {
+        Value::Null
+    };
+
+    // 4. 国库与预言机
+    let treasury = if bootstrap.deployed("treasury", &treasury_program)? {
+        let address = bootstrap.init_treasury(&treasury_program)?;
+        json!({
+            "program": treasury_program.to_string(),
+            "treasury": address.to_string(),
+        })
+    } else {
+        Value::Null
+    };
+    let oracle = if bootstrap.deployed("mock-oracle", &oracle_program)? {
+        let mut feed_id = [0u8; 32];
+        feed_id[..FEED_NAME.len()].copy_from_slice(FEED_NAME);
+        let address = bootstrap.init_feed(&oracle_program, &feed_id)?;
+        json!({
+            "program": oracle_program.to_string(),
+            "feed": String::from_utf8_lossy(FEED_NAME),
+            "price_feed": address.to_string(),
+        })
+    } else {
+        Value::Null
+    };
+
+    // 5. 示例托管：maker 自己付租金，手续费仍由付费钱包支付
+    let escrow = if bootstrap.deployed("escrow", &escrow_program)? {
+        let maker = &wallets[0];
+        let (address, _) = escrow::escrow_address(&escrow_program, &maker.pubkey(), ESCROW_SEED);
+        if !bootstrap.exists(&address)? {
+            let ix = escrow::make_ix(
+                &escrow_program,
+                &maker.pubkey(),
+                &mints[0],
+                &mints[1],
+                ESCROW_SEED,
+                cli.escrow_receive,
+                cli.escrow_amount,
+                0,
+                None,
+            );
+            bootstrap.send("挂出示例托管", vec![ix], &[maker])?;
+        }
+        json!({
+            "program": escrow_program.to_string(),
+            "escrow": address.to_string(),
+            "vault": ata(&address, &mints[0]).to_string(),
+            "maker": maker.pubkey().to_string(),
+            "seed": ESCROW_SEED,
+            "amount": cli.escrow_amount,
+            "receive": cli.escrow_receive,
+        })
+    } else {
+        Value::Null
+    };
+
+    let output = json!({
+        "url": cli.url,
+        "payer": payer.to_string(),
+        "wallets": wallet_json,
+        "mints": mints.iter().map(Pubkey::to_string).collect::<Vec<_>>(),
+        "decimals": cli.decimals,
+        "amm": amm,
+        "treasury": treasury,
+        "oracle": oracle,
+        "escrow": escrow,
+    });
+    let mut text = serde_json::to_string_pretty(&output)?;
+    text.push('\n');
+    fs::write(cli.dir.join("bootstrap.json"), &text)?;
+    print!("{text}");
+    Ok(())
+}
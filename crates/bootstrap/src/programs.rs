@@ -0,0 +1,82 @@
+//! 目标集群上各程序的地址：命令行参数优先，其次是 `cargo xtask deploy` 写入的
+//! deployments.json，最后是源码中声明的默认地址。
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{Context, Result};
+use solana_sdk::pubkey::Pubkey;
+
+/// 仓库根目录的 deployments.json
+pub const DEPLOYMENTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../deployments.json");
+
+/// 把 RPC 地址换成 deployments.json 中的集群名，写法与 xtask 记录时相同；自定义地址原样返回
+pub fn cluster_name(url: &str) -> &str {
+    match url.trim_end_matches('/') {
+        "l" | "localhost" | "localnet" | "http://localhost:8899" | "http://127.0.0.1:8899" => {
+            "localnet"
+        }
+        "d" | "devnet" | "https://api.devnet.solana.com" => "devnet",
+        "t" | "testnet" | "https://api.testnet.solana.com" => "testnet",
+        "m" | "mainnet-beta" | "https://api.mainnet-beta.solana.com" => "mainnet-beta",
+        other => other,
+    }
+}
+
+/// `cluster` 上已记录的程序名 → 地址；文件或集群不存在时为空
+#[derive(Debug, Default)]
+pub struct Deployed(BTreeMap<String, Pubkey>);
+
+impl Deployed {
+    pub fn load(path: &Path, cluster: &str) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json = fs::read_to_string(path)?;
+        Self::parse(&json, cluster).with_context(|| format!("{} 格式不正确", path.display()))
+    }
+
+    fn parse(json: &str, cluster: &str) -> Result<Self> {
+        let mut table: BTreeMap<String, BTreeMap<String, String>> = serde_json::from_str(json)?;
+        let programs = table.remove(cluster).unwrap_or_default();
+        programs
+            .into_iter()
+            .map(|(name, address)| {
+                let address = address
+                    .parse()
+                    .with_context(|| format!("{name} 的地址 {address} 无法解析"))?;
+                Ok((name, address))
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    /// 参数指定的地址 > 已部署的地址 > `default`
+    pub fn resolve(&self, name: &str, arg: Option<Pubkey>, default: Pubkey) -> Pubkey {
+        arg.or_else(|| self.0.get(name).copied()).unwrap_or(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_address_beats_deployments_and_default() {
+        let escrow = Pubkey::new_unique();
+        let json = format!(r#"{{"devnet": {{"escrow": "{escrow}"}}, "localnet": {{}}}}"#);
+        let deployed =
+            Deployed::parse(&json, cluster_name("https://api.devnet.solana.com/")).unwrap();
+        let (arg, default) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert_eq!(deployed.resolve("escrow", None, default), escrow);
+        assert_eq!(deployed.resolve("escrow", Some(arg), default), arg);
+        assert_eq!(deployed.resolve("amm", None, default), default);
+        assert!(Deployed::parse(&json, "testnet").unwrap().0.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_addresses() {
+        let json = r#"{"devnet": {"amm": "not-a-key"}}"#;
+        assert!(Deployed::parse(json, "devnet").is_err());
+    }
+}
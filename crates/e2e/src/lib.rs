@@ -0,0 +1,153 @@
+//! 跨程序端到端测试的 LiteSVM 环境。
+//!
+//! 训练营的程序都声明同一个占位地址 2222…2222（Blueshift 挑战要求），而一个地址上同时只能有一个程序，
+//! 所以 [`World::send`] 在每笔交易前把该地址上的程序换成本次要调用的那个。账户状态全程保留：
+//! 金库、托管单和池子的账户同属这个地址，正好检验各程序不会把彼此的账户当成自己的。
+
+use std::collections::HashMap;
+
+use bootcamp_client::{amm, diagnostics::Failure, escrow, token::ata, vault};
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+use token_fixtures::{litesvm as fixtures, TokenProgram};
+
+pub const SOL: u64 = 1_000_000_000;
+
+/// 一个已构建的程序：部署地址 + `target/deploy` 下的 .so
+#[derive(Clone, Copy, Debug)]
+pub struct Program {
+    pub id: Pubkey,
+    pub so: &'static str,
+}
+
+macro_rules! deploy {
+    ($dir:literal, $name:literal) => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../",
+            $dir,
+            "/target/deploy/",
+            $name,
+            ".so"
+        )
+    };
+}
+
+pub const ANCHOR_VAULT: Program = Program {
+    id: vault::PROGRAM_ID,
+    so: deploy!("task2/blueshift_anchor_vault", "blueshift_anchor_vault"),
+};
+pub const PINOCCHIO_ESCROW: Program = Program {
+    id: escrow::PROGRAM_ID,
+    so: deploy!("task5/blueshift_escrow", "blueshift_escrow"),
+};
+pub const NATIVE_AMM: Program = Program {
+    id: amm::PROGRAM_ID,
+    so: deploy!(
+        "task6/solana-pinocchio-amm-workshop-main/blueshift_native_amm",
+        "blueshift_native_amm"
+    ),
+};
+
+/// 共享的 LiteSVM（SPL Token 与 ATA 程序由 LiteSVM 内置）及各地址当前加载的程序
+pub struct World {
+    pub svm: LiteSVM,
+    loaded: HashMap<Pubkey, &'static str>,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self {
+            svm: LiteSVM::new(),
+            loaded: HashMap::new(),
+        }
+    }
+
+    /// 让 `program` 占据它的地址；已经是它时不重复加载
+    pub fn switch(&mut self, program: &Program) {
+        if self.loaded.get(&program.id) == Some(&program.so) {
+            return;
+        }
+        self.svm
+            .add_program_from_file(program.id, program.so)
+            .unwrap_or_else(|err| panic!("无法加载 {}（请先构建对应程序）: {err}", program.so));
+        self.loaded.insert(program.id, program.so);
+    }
+
+    /// 切换到 `program` 后，以 `payer` 付费并签名发送一笔交易。
+    /// 失败时返回可读的错误说明（自定义错误码还原成 blueshift_errors）与程序日志
+    pub fn send(
+        &mut self,
+        program: &Program,
+        ixs: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> Result<(), String> {
+        self.switch(program);
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&payer.pubkey()),
+            &all_signers,
+            self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(tx).map(|_| ()).map_err(|failed| {
+            format!(
+                "{}\n{}",
+                Failure::from(&failed.err),
+                failed.meta.logs.join("\n")
+            )
+        })
+    }
+
+    /// 新建一个持有 10 SOL 的钱包
+    pub fn wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        self.svm.airdrop(&wallet.pubkey(), 10 * SOL).unwrap();
+        wallet
+    }
+
+    /// 新建一个 6 位小数的 mint，`authority` 为铸币权限
+    pub fn mint(&mut self, authority: &Keypair) -> Pubkey {
+        fixtures::create_mint(&mut self.svm, &authority.pubkey(), 6, TokenProgram::Token)
+    }
+
+    /// 给 `owner` 在 `mint` 上的 ATA 铸造 `amount` 个代币，ATA 不存在时先创建
+    pub fn mint_to(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) {
+        fixtures::mint_to(&mut self.svm, mint, owner, amount, TokenProgram::Token);
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        self.svm.get_balance(address).unwrap_or(0)
+    }
+
+    pub fn exists(&self, address: &Pubkey) -> bool {
+        self.svm
+            .get_account(address)
+            .is_some_and(|account| account.lamports > 0)
+    }
+
+    pub fn data(&self, address: &Pubkey) -> Vec<u8> {
+        self.svm.get_account(address).expect("account").data
+    }
+
+    /// `owner` 在 `mint` 上的 ATA 余额，ATA 不存在时为 0
+    pub fn token_balance(&self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        let account = ata(owner, mint);
+        if self.exists(&account) {
+            fixtures::token_balance(&self.svm, &account)
+        } else {
+            0
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! 托管单与 AMM 池子对同一交易对报价不同时的套利路径（路由 TakeAndSwap）：
+//! taker 用 B 吃下托管单拿到 A，再把 A 全部卖回池子换 B。
+//!
+//! 路由要在一笔交易里 CPI 托管和 AMM，但两者都声明 2222…2222，LiteSVM 里同一时刻只能有一个，
+//! 所以这里按路由的顺序和参数分两笔交易执行这两步（`router::take_and_swap_ix` 的账户覆盖由
+//! bootcamp_client 的单元测试检查）。每个场景都断言：两种代币的总量在所有持有者之间守恒，
+//! taker 的收益恰好来自池子和 maker，不会凭空多出代币。
+
+use bootcamp_client::{
+    amm::{AmmConfig, Pool},
+    escrow::{self, Escrow},
+    token::create_ata_idempotent_ix,
+};
+use e2e::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use token_fixtures::litesvm::mint_supply;
+
+const ESCROW_SEED: u64 = 7;
+/// 池子手续费（基点）
+const FEE: u16 = 30;
+
+/// 一个价格场景：池子里 A / B 的储备，托管单卖出 offer 个 A、要求 receive 个 B
+struct Case {
+    pool_a: u64,
+    pool_b: u64,
+    offer: u64,
+    receive: u64,
+    /// 池子买下 offer 个 A 付出的 B 是否不少于 receive
+    profitable: bool,
+}
+
+const CASES: &[Case] = &[
+    // 同价池子，托管单打折卖 A
+    Case {
+        pool_a: 1_000_000_000,
+        pool_b: 1_000_000_000,
+        offer: 50_000_000,
+        receive: 40_000_000,
+        profitable: true,
+    },
+    // 池子里 A 更贵（1 A ≈ 2 B），托管单按 1:1.6 卖
+    Case {
+        pool_a: 1_000_000_000,
+        pool_b: 2_000_000_000,
+        offer: 50_000_000,
+        receive: 80_000_000,
+        profitable: true,
+    },
+    // 托管单报价与池子相同，手续费让套利无利可图
+    Case {
+        pool_a: 1_000_000_000,
+        pool_b: 1_000_000_000,
+        offer: 50_000_000,
+        receive: 50_000_000,
+        profitable: false,
+    },
+    // 池子里 A 更便宜，卖回池子一定亏
+    Case {
+        pool_a: 2_000_000_000,
+        pool_b: 1_000_000_000,
+        offer: 50_000_000,
+        receive: 40_000_000,
+        profitable: false,
+    },
+    // 托管单远大于池子深度：滑点极大，但托管单要价更低，仍有利可图
+    Case {
+        pool_a: 1_000_000,
+        pool_b: 1_000_000,
+        offer: 500_000_000,
+        receive: 500_000,
+        profitable: true,
+    },
+];
+
+/// 一个场景中持有 A / B 的全部账户
+struct Holders {
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    maker: Keypair,
+    taker: Keypair,
+    /// 托管账户地址（托管金库是它的 ATA）
+    escrow: Pubkey,
+    state: Escrow,
+    pool: Pool,
+}
+
+impl Holders {
+    /// `mint` 在 maker、taker、托管金库与池子金库中的余额之和
+    fn total(&self, world: &World, mint: &Pubkey) -> u64 {
+        [
+            self.maker.pubkey(),
+            self.taker.pubkey(),
+            self.escrow,
+            self.pool.config,
+        ]
+        .iter()
+        .map(|owner| world.token_balance(owner, mint))
+        .sum()
+    }
+
+    /// 两种代币的总量都等于各自 mint 的供应量
+    fn assert_conserved(&self, world: &World) {
+        for mint in [self.mint_a, self.mint_b] {
+            assert_eq!(self.total(world, &mint), mint_supply(&world.svm, &mint));
+        }
+    }
+}
+
+/// 建池、挂单并给 taker 准备足够支付 receive 的 B
+fn setup(world: &mut World, case: &Case) -> Holders {
+    let admin = world.wallet();
+    let maker = world.wallet();
+    let taker = world.wallet();
+    let mint_a = world.mint(&admin);
+    let mint_b = world.mint(&admin);
+    let (mint_x, mint_y) = if mint_a < mint_b {
+        (mint_a, mint_b)
+    } else {
+        (mint_b, mint_a)
+    };
+    let pool = Pool::canonical(&NATIVE_AMM.id, &mint_x, &mint_y);
+
+    // admin 提供初始流动性后把 LP 留在自己手里，不参与代币计数
+    let owner = admin.pubkey();
+    world
+        .send(
+            &NATIVE_AMM,
+            &[pool.initialize_ix(&owner, FEE, &owner)],
+            &admin,
+            &[],
+        )
+        .unwrap();
+    world.mint_to(&mint_a, &owner, case.pool_a);
+    world.mint_to(&mint_b, &owner, case.pool_b);
+    let (max_x, max_y) = if mint_a == mint_x {
+        (case.pool_a, case.pool_b)
+    } else {
+        (case.pool_b, case.pool_a)
+    };
+    world
+        .send(
+            &NATIVE_AMM,
+            &[
+                create_ata_idempotent_ix(&owner, &owner, &pool.mint_lp),
+                pool.deposit_ix(&owner, case.pool_a.min(case.pool_b), max_x, max_y),
+            ],
+            &admin,
+            &[],
+        )
+        .unwrap();
+    assert_eq!(world.token_balance(&owner, &mint_a), 0);
+    assert_eq!(world.token_balance(&owner, &mint_b), 0);
+
+    world.mint_to(&mint_a, &maker.pubkey(), case.offer);
+    let make = escrow::make_ix(
+        &PINOCCHIO_ESCROW.id,
+        &maker.pubkey(),
+        &mint_a,
+        &mint_b,
+        ESCROW_SEED,
+        case.receive,
+        case.offer,
+        0,
+        None,
+    );
+    world.send(&PINOCCHIO_ESCROW, &[make], &maker, &[]).unwrap();
+    let (escrow, _) = escrow::escrow_address(&PINOCCHIO_ESCROW.id, &maker.pubkey(), ESCROW_SEED);
+    let state = Escrow::try_from_bytes(&world.data(&escrow)).unwrap();
+    world.mint_to(&mint_b, &taker.pubkey(), case.receive);
+
+    Holders {
+        mint_a,
+        mint_b,
+        maker,
+        taker,
+        escrow,
+        state,
+        pool,
+    }
+}
+
+#[test]
+fn arbitrage_never_creates_tokens() {
+    for (i, case) in CASES.iter().enumerate() {
+        let mut world = World::new();
+        let holders = setup(&mut world, case);
+        holders.assert_conserved(&world);
+        let supply = (
+            mint_supply(&world.svm, &holders.mint_a),
+            mint_supply(&world.svm, &holders.mint_b),
+        );
+        let taker = holders.taker.pubkey();
+
+        // 1. Take：taker 支付 receive 个 B，拿到 offer 个 A
+        let take = escrow::take_ix(
+            &PINOCCHIO_ESCROW.id,
+            &taker,
+            &holders.escrow,
+            &holders.state,
+            &Pubkey::default(),
+        );
+        world
+            .send(&PINOCCHIO_ESCROW, &[take], &holders.taker, &[])
+            .unwrap();
+        holders.assert_conserved(&world);
+        assert_eq!(world.token_balance(&taker, &holders.mint_a), case.offer);
+        assert_eq!(world.token_balance(&taker, &holders.mint_b), 0);
+
+        // 2. Swap：按路由的参数把 A 全部卖回池子，最低输出为 receive
+        let is_x = holders.mint_a == holders.pool.mint_x;
+        let swap = holders.pool.swap_ix(&taker, is_x, case.offer, case.receive);
+        let swapped = world.send(&NATIVE_AMM, &[swap], &holders.taker, &[]);
+        holders.assert_conserved(&world);
+        assert_eq!(
+            (
+                mint_supply(&world.svm, &holders.mint_a),
+                mint_supply(&world.svm, &holders.mint_b),
+            ),
+            supply,
+            "场景 {i}：swap 不应铸造或销毁代币"
+        );
+
+        let config = AmmConfig::try_from_bytes(&world.data(&holders.pool.config)).unwrap();
+        let (reserve_a, reserve_b) = if is_x {
+            (config.reserve_x, config.reserve_y)
+        } else {
+            (config.reserve_y, config.reserve_x)
+        };
+        let bought = world.token_balance(&taker, &holders.mint_b);
+        assert_eq!(swapped.is_ok(), case.profitable, "场景 {i}: {swapped:?}");
+        if swapped.is_ok() {
+            // taker 手里的 B 全部来自池子；扣掉先前付给 maker 的 receive 就是套利收益
+            assert_eq!(world.token_balance(&taker, &holders.mint_a), 0);
+            assert_eq!(reserve_a, case.pool_a + case.offer, "场景 {i}");
+            assert_eq!(case.pool_b - reserve_b, bought, "场景 {i}");
+            assert!(bought >= case.receive, "场景 {i}");
+        } else {
+            // 池子的报价不够支付 receive：路由会整体回滚，这里 taker 仍持有 A，池子不变
+            assert_eq!(world.token_balance(&taker, &holders.mint_a), case.offer);
+            assert_eq!(
+                (reserve_a, reserve_b),
+                (case.pool_a, case.pool_b),
+                "场景 {i}"
+            );
+            assert_eq!(bought, 0);
+        }
+        assert_eq!(
+            world.token_balance(&holders.maker.pubkey(), &holders.mint_b),
+            case.receive
+        );
+    }
+}
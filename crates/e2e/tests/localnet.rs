@@ -0,0 +1,75 @@
+//! 同样的托管流程在真实的 solana-test-validator 上跑一遍：走 RPC、真实的 blockhash 与确认，
+//! 交易由 `tx::TxBuilder` 模拟估算后发送。
+//!
+//! 需要 PATH 中的 solana-test-validator 与已构建的 task5 托管，默认不运行：
+//! `cargo test -p e2e --test localnet -- --ignored`
+
+use bootcamp_client::escrow::{self, Escrow};
+use localnet::{Localnet, PINOCCHIO_ESCROW};
+use solana_sdk::signer::Signer;
+
+const SEED: u64 = 7;
+const OFFER: u64 = 50_000_000;
+const RECEIVE: u64 = 80_000_000;
+const BALANCE: u64 = 1_000_000_000;
+
+fn token_balance(
+    localnet: &Localnet,
+    owner: &solana_sdk::pubkey::Pubkey,
+    mint: &solana_sdk::pubkey::Pubkey,
+) -> u64 {
+    let ata = bootcamp_client::token::ata(owner, mint);
+    let balance = localnet.client.get_token_account_balance(&ata).unwrap();
+    balance.amount.parse().unwrap()
+}
+
+#[test]
+#[ignore = "需要 solana-test-validator 与已构建的 task5 托管"]
+fn escrow_make_and_take_on_validator() {
+    let localnet = Localnet::builder()
+        .program(PINOCCHIO_ESCROW)
+        .token_balance(BALANCE)
+        .start()
+        .unwrap_or_else(|err| panic!("{err}"));
+    let (maker, taker) = (&localnet.wallets[1], &localnet.wallets[2]);
+    let (mint_a, mint_b) = (localnet.mints[0], localnet.mints[1]);
+
+    let make = escrow::make_ix(
+        &PINOCCHIO_ESCROW.id,
+        &maker.pubkey(),
+        &mint_a,
+        &mint_b,
+        SEED,
+        RECEIVE,
+        OFFER,
+        0,
+        None,
+    );
+    localnet.tx(maker).instruction(make).send().unwrap();
+    let (escrow_address, _) = escrow::escrow_address(&PINOCCHIO_ESCROW.id, &maker.pubkey(), SEED);
+    let state = Escrow::try_from_bytes(&localnet.client.get_account_data(&escrow_address).unwrap())
+        .unwrap();
+    assert_eq!((state.maker, state.receive), (maker.pubkey(), RECEIVE));
+
+    let take = escrow::take_ix(
+        &PINOCCHIO_ESCROW.id,
+        &taker.pubkey(),
+        &escrow_address,
+        &state,
+        &solana_sdk::pubkey::Pubkey::default(),
+    );
+    localnet.tx(taker).instruction(take).send().unwrap();
+    assert_eq!(
+        token_balance(&localnet, &taker.pubkey(), &mint_a),
+        BALANCE + OFFER
+    );
+    assert_eq!(
+        token_balance(&localnet, &taker.pubkey(), &mint_b),
+        BALANCE - RECEIVE
+    );
+    assert_eq!(
+        token_balance(&localnet, &maker.pubkey(), &mint_b),
+        BALANCE + RECEIVE
+    );
+    assert!(localnet.client.get_account(&escrow_address).is_err());
+}
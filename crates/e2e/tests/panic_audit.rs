@@ -0,0 +1,64 @@
+//! 扫描 Pinocchio 程序的 .so，确认其中没有 core 的 panic 信息。
+//!
+//! 程序代码由 `clippy::unwrap_used / expect_used / panic` 拦住，这里再对构建产物兜底：
+//! 依赖里的 unwrap、宏展开出的 panic 都会把下面这些字符串留在 .rodata 里。
+
+use std::path::PathBuf;
+
+/// (程序目录, .so 名)，先在各目录执行 `cargo build-sbf`
+const PROGRAMS: &[(&str, &str)] = &[
+    ("task4/blueshift_vault", "blueshift_vault"),
+    ("task5/blueshift_escrow", "blueshift_escrow"),
+    (
+        "task6/solana-pinocchio-amm-workshop-main/blueshift_native_amm",
+        "blueshift_native_amm",
+    ),
+    (
+        "task6/solana-pinocchio-amm-workshop-main/pinocchio_amm",
+        "pinocchio_amm",
+    ),
+];
+
+/// `Option::unwrap` / `Result::unwrap` / `panic!()` / `unreachable!()` / `todo!()` 的固定信息
+const PANIC_MESSAGES: &[&str] = &[
+    "called `Option::unwrap()` on a `None` value",
+    "called `Result::unwrap()` on an `Err` value",
+    "explicit panic",
+    "internal error: entered unreachable code",
+    "not yet implemented",
+];
+
+fn so_path(dir: &str, name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .join(dir)
+        .join("target/deploy")
+        .join(format!("{name}.so"))
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle.as_bytes())
+}
+
+#[test]
+fn pinocchio_programs_contain_no_panic_messages() {
+    let mut found = Vec::new();
+    for (dir, name) in PROGRAMS {
+        let path = so_path(dir, name);
+        let so = std::fs::read(&path)
+            .unwrap_or_else(|err| panic!("无法读取 {}（请先构建对应程序）: {err}", path.display()));
+        found.extend(
+            PANIC_MESSAGES
+                .iter()
+                .filter(|message| contains(&so, message))
+                .map(|message| format!("{name}.so: {message}")),
+        );
+    }
+    assert!(
+        found.is_empty(),
+        "构建产物中仍有 panic 路径:\n{}",
+        found.join("\n")
+    );
+}
@@ -0,0 +1,913 @@
+//! 金库 → 托管 → AMM 的跨程序场景：maker 把 SOL 存进 Anchor 金库并挂出托管单，
+//! taker 手里只有代币 A，先在原生 AMM 把 A 换成 B，再用换来的 B 吃单。
+
+use bootcamp_client::{
+    amm::{AmmConfig, Pool},
+    escrow::{self, Escrow, OfferIndex},
+    token::{ata, create_ata_idempotent_ix, NATIVE_MINT},
+    vault,
+};
+use e2e::*;
+use solana_sdk::{
+    account::Account, clock::Clock, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+
+const VAULT_DEPOSIT: u64 = SOL;
+const LIQUIDITY: u64 = 1_000_000_000;
+const ESCROW_SEED: u64 = 42;
+const OFFER: u64 = 50_000_000;
+const RECEIVE: u64 = 80_000_000;
+/// 1:1 的池子扣除 0.3% 手续费和滑点后约换出 90.6M，足够支付 RECEIVE
+const SWAP_IN: u64 = 100_000_000;
+
+/// 代币 A / B 以及由 admin 提供初始流动性的 A/B 规范池
+struct Market {
+    admin: Keypair,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    pool: Pool,
+}
+
+impl Market {
+    fn new(world: &mut World) -> Self {
+        let admin = world.wallet();
+        let mint_a = world.mint(&admin);
+        let mint_b = world.mint(&admin);
+        let (mint_x, mint_y) = if mint_a < mint_b {
+            (mint_a, mint_b)
+        } else {
+            (mint_b, mint_a)
+        };
+        let pool = Pool::canonical(&NATIVE_AMM.id, &mint_x, &mint_y);
+
+        let owner = admin.pubkey();
+        world
+            .send(
+                &NATIVE_AMM,
+                &[pool.initialize_ix(&owner, 30, &owner)],
+                &admin,
+                &[],
+            )
+            .unwrap();
+        world.mint_to(&mint_x, &owner, LIQUIDITY);
+        world.mint_to(&mint_y, &owner, LIQUIDITY);
+        world
+            .send(
+                &NATIVE_AMM,
+                &[
+                    create_ata_idempotent_ix(&owner, &owner, &pool.mint_lp),
+                    pool.deposit_ix(&owner, LIQUIDITY, LIQUIDITY, LIQUIDITY),
+                ],
+                &admin,
+                &[],
+            )
+            .unwrap();
+
+        Self {
+            admin,
+            mint_a,
+            mint_b,
+            pool,
+        }
+    }
+
+    /// maker 持有 OFFER 个 A 并挂单换 RECEIVE 个 B，`expiry` 为 0 时永不过期，
+    /// `taker` 为 None 时任何人都可以接受；返回托管地址
+    fn make(
+        &self,
+        world: &mut World,
+        maker: &Keypair,
+        expiry: i64,
+        taker: Option<Pubkey>,
+    ) -> Pubkey {
+        world.mint_to(&self.mint_a, &maker.pubkey(), OFFER);
+        let ix = escrow::make_ix(
+            &PINOCCHIO_ESCROW.id,
+            &maker.pubkey(),
+            &self.mint_a,
+            &self.mint_b,
+            ESCROW_SEED,
+            RECEIVE,
+            OFFER,
+            expiry,
+            taker,
+        );
+        world.send(&PINOCCHIO_ESCROW, &[ix], maker, &[]).unwrap();
+        escrow::escrow_address(&PINOCCHIO_ESCROW.id, &maker.pubkey(), ESCROW_SEED).0
+    }
+}
+
+#[test]
+fn vault_escrow_amm_round_trip() {
+    let mut world = World::new();
+    let market = Market::new(&mut world);
+    let maker = world.wallet();
+    let taker = world.wallet();
+
+    // 1. maker 把 SOL 存进金库
+    let (vault_address, _) = vault::vault_address(&ANCHOR_VAULT.id, &maker.pubkey());
+    let deposit = vault::deposit_ix(&ANCHOR_VAULT.id, &maker.pubkey(), VAULT_DEPOSIT);
+    world.send(&ANCHOR_VAULT, &[deposit], &maker, &[]).unwrap();
+    assert_eq!(world.lamports(&vault_address), VAULT_DEPOSIT);
+
+    // 2. maker 挂出托管单：OFFER 个 A 换 RECEIVE 个 B
+    let escrow_address = market.make(&mut world, &maker, 0, None);
+    let state = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    assert_eq!((state.maker, state.receive), (maker.pubkey(), RECEIVE));
+    assert_eq!(world.token_balance(&escrow_address, &market.mint_a), OFFER);
+
+    // 3. taker 只有 A，先在 AMM 换出至少 RECEIVE 个 B
+    world.mint_to(&market.mint_a, &taker.pubkey(), SWAP_IN);
+    let is_x = market.mint_a == market.pool.mint_x;
+    let swap = [
+        create_ata_idempotent_ix(&taker.pubkey(), &taker.pubkey(), &market.mint_b),
+        market.pool.swap_ix(&taker.pubkey(), is_x, SWAP_IN, RECEIVE),
+    ];
+    world.send(&NATIVE_AMM, &swap, &taker, &[]).unwrap();
+    let swapped = world.token_balance(&taker.pubkey(), &market.mint_b);
+    assert!(swapped >= RECEIVE);
+    assert_eq!(world.token_balance(&taker.pubkey(), &market.mint_a), 0);
+
+    // 4. taker 用换来的 B 吃单，托管账户关闭
+    let take = escrow::take_ix(
+        &PINOCCHIO_ESCROW.id,
+        &taker.pubkey(),
+        &escrow_address,
+        &state,
+        &Pubkey::default(),
+    );
+    world.send(&PINOCCHIO_ESCROW, &[take], &taker, &[]).unwrap();
+    assert_eq!(world.token_balance(&taker.pubkey(), &market.mint_a), OFFER);
+    assert_eq!(
+        world.token_balance(&taker.pubkey(), &market.mint_b),
+        swapped - RECEIVE
+    );
+    assert_eq!(
+        world.token_balance(&maker.pubkey(), &market.mint_b),
+        RECEIVE
+    );
+    assert!(!world.exists(&escrow_address));
+    assert!(!world.exists(&state.vault(&escrow_address)));
+
+    // 5. 池子的记账储备与金库余额保持一致
+    let config = AmmConfig::try_from_bytes(&world.data(&market.pool.config)).unwrap();
+    let (reserve_a, reserve_b) = if is_x {
+        (config.reserve_x, config.reserve_y)
+    } else {
+        (config.reserve_y, config.reserve_x)
+    };
+    assert_eq!(reserve_a, LIQUIDITY + SWAP_IN);
+    assert_eq!(reserve_b, LIQUIDITY - swapped);
+    assert_eq!(
+        world.token_balance(&market.pool.config, &market.mint_a),
+        reserve_a
+    );
+    assert_eq!(
+        world.token_balance(&market.pool.config, &market.mint_b),
+        reserve_b
+    );
+
+    // 6. maker 取回金库中的 SOL
+    let withdraw = vault::withdraw_ix(&ANCHOR_VAULT.id, &maker.pubkey());
+    world.send(&ANCHOR_VAULT, &[withdraw], &maker, &[]).unwrap();
+    assert_eq!(world.lamports(&vault_address), 0);
+}
+
+/// 部分成交：分两笔买完，每笔按比例付款，最后一笔关闭托管账户
+#[test]
+fn partial_fills_until_escrow_closes() {
+    let mut world = World::new();
+    let market = Market::new(&mut world);
+    let maker = world.wallet();
+    let taker = world.wallet();
+    let escrow_address = market.make(&mut world, &maker, 0, None);
+    let state = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    world.mint_to(&market.mint_b, &taker.pubkey(), RECEIVE);
+    let partial = |amount| {
+        escrow::take_partial_ix(
+            &PINOCCHIO_ESCROW.id,
+            &taker.pubkey(),
+            &escrow_address,
+            &state,
+            &Pubkey::default(),
+            amount,
+        )
+    };
+
+    // 1. 买走 2/5：支付 receive 的 2/5，剩余部分按原价格继续挂单
+    let first = OFFER * 2 / 5;
+    let owed = state.partial_receive(first, OFFER).unwrap();
+    assert_eq!(owed, RECEIVE * 2 / 5);
+    world
+        .send(&PINOCCHIO_ESCROW, &[partial(first)], &taker, &[])
+        .unwrap();
+    assert_eq!(world.token_balance(&taker.pubkey(), &market.mint_a), first);
+    assert_eq!(world.token_balance(&maker.pubkey(), &market.mint_b), owed);
+    assert_eq!(
+        world.token_balance(&escrow_address, &market.mint_a),
+        OFFER - first
+    );
+    let remaining = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    assert_eq!(remaining.receive, RECEIVE - owed);
+
+    // 2. 买走剩下的全部：与 Take 相同，金库和托管账户关闭
+    world
+        .send(&PINOCCHIO_ESCROW, &[partial(OFFER - first)], &taker, &[])
+        .unwrap();
+    assert_eq!(world.token_balance(&taker.pubkey(), &market.mint_a), OFFER);
+    assert_eq!(world.token_balance(&taker.pubkey(), &market.mint_b), 0);
+    assert_eq!(
+        world.token_balance(&maker.pubkey(), &market.mint_b),
+        RECEIVE
+    );
+    assert!(!world.exists(&escrow_address));
+    assert!(!world.exists(&state.vault(&escrow_address)));
+}
+
+/// 零碎成交：receive 远小于金库余额时每笔至少付 1 个代币 B，
+/// 但不能在还有代币 A 时把 receive 付清，maker 最终收到的不少于原来的 receive
+#[test]
+fn small_partial_fills_cannot_drain_receive() {
+    const CHEAP: u64 = 3;
+    let mut world = World::new();
+    let market = Market::new(&mut world);
+    let maker = world.wallet();
+    let taker = world.wallet();
+    world.mint_to(&market.mint_a, &maker.pubkey(), OFFER);
+    let make = escrow::make_ix(
+        &PINOCCHIO_ESCROW.id,
+        &maker.pubkey(),
+        &market.mint_a,
+        &market.mint_b,
+        ESCROW_SEED,
+        CHEAP,
+        OFFER,
+        0,
+        None,
+    );
+    world.send(&PINOCCHIO_ESCROW, &[make], &maker, &[]).unwrap();
+    let escrow_address =
+        escrow::escrow_address(&PINOCCHIO_ESCROW.id, &maker.pubkey(), ESCROW_SEED).0;
+    let state = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    world.mint_to(&market.mint_b, &taker.pubkey(), CHEAP);
+    let partial = escrow::take_partial_ix(
+        &PINOCCHIO_ESCROW.id,
+        &taker.pubkey(),
+        &escrow_address,
+        &state,
+        &Pubkey::default(),
+        1,
+    );
+
+    // 1. 每次只买 1 个代币 A，直到程序拒绝
+    let mut fills = 0;
+    loop {
+        let current = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+        let vault = world.token_balance(&escrow_address, &market.mint_a);
+        world.svm.expire_blockhash();
+        let sent = world.send(&PINOCCHIO_ESCROW, &[partial.clone()], &taker, &[]);
+        match current.partial_receive(1, vault) {
+            Some(owed) => {
+                assert_eq!(owed, 1);
+                sent.unwrap();
+                fills += 1;
+            }
+            None => {
+                assert!(sent.is_err());
+                assert_eq!(current.receive, 1);
+                break;
+            }
+        }
+    }
+    assert_eq!(fills, CHEAP - 1);
+    assert_eq!(
+        world.token_balance(&escrow_address, &market.mint_a),
+        OFFER - fills
+    );
+
+    // 2. 剩下的代币 A 只能按剩余的 receive 整单买走
+    let remaining = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    let take = escrow::take_ix(
+        &PINOCCHIO_ESCROW.id,
+        &taker.pubkey(),
+        &escrow_address,
+        &remaining,
+        &Pubkey::default(),
+    );
+    world.send(&PINOCCHIO_ESCROW, &[take], &taker, &[]).unwrap();
+    assert_eq!(world.token_balance(&taker.pubkey(), &market.mint_a), OFFER);
+    assert!(world.token_balance(&maker.pubkey(), &market.mint_b) >= CHEAP);
+    assert!(!world.exists(&escrow_address));
+}
+
+/// 过期的托管单：到期前只有 maker 能退款；到期后 Take 被拒绝，任何人都可以替 maker 退款
+#[test]
+fn expired_escrow_is_refunded_by_anyone() {
+    let mut world = World::new();
+    let market = Market::new(&mut world);
+    let maker = world.wallet();
+    let (taker, cranker) = (world.wallet(), world.wallet());
+    let now = world.svm.get_sysvar::<Clock>().unix_timestamp;
+    let escrow_address = market.make(&mut world, &maker, now + 60, None);
+    let state = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    assert_eq!(state.expiry, now + 60);
+    world.mint_to(&market.mint_b, &taker.pubkey(), RECEIVE);
+    let refund = escrow::refund_ix(
+        &PINOCCHIO_ESCROW.id,
+        &cranker.pubkey(),
+        &escrow_address,
+        &state,
+    );
+
+    // 1. 尚未到期：第三方不能退款
+    let err = world
+        .send(&PINOCCHIO_ESCROW, &[refund.clone()], &cranker, &[])
+        .unwrap_err();
+    assert!(err.contains("Escrow has not expired"), "{err}");
+
+    // 2. 时钟走到 expiry：Take 被拒绝
+    let mut clock = world.svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = state.expiry;
+    world.svm.set_sysvar(&clock);
+    let take = escrow::take_ix(
+        &PINOCCHIO_ESCROW.id,
+        &taker.pubkey(),
+        &escrow_address,
+        &state,
+        &Pubkey::default(),
+    );
+    let err = world
+        .send(&PINOCCHIO_ESCROW, &[take], &taker, &[])
+        .unwrap_err();
+    assert!(err.contains("Escrow has expired"), "{err}");
+
+    // 3. 第三方退款：代币 A 与托管账户的租金都回到 maker
+    world
+        .send(&PINOCCHIO_ESCROW, &[refund], &cranker, &[])
+        .unwrap();
+    assert_eq!(world.token_balance(&maker.pubkey(), &market.mint_a), OFFER);
+    assert_eq!(
+        world.token_balance(&taker.pubkey(), &market.mint_b),
+        RECEIVE
+    );
+    assert!(!world.exists(&escrow_address));
+    assert!(!world.exists(&state.vault(&escrow_address)));
+}
+
+/// 指定了接受者的托管单：其他人不能吃单，指定的 taker 照常成交
+#[test]
+fn private_escrow_only_accepts_designated_taker() {
+    let mut world = World::new();
+    let market = Market::new(&mut world);
+    let maker = world.wallet();
+    let (taker, sniper) = (world.wallet(), world.wallet());
+    let escrow_address = market.make(&mut world, &maker, 0, Some(taker.pubkey()));
+    let state = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    assert_eq!(state.taker, taker.pubkey());
+    let take = |taker: &Keypair| {
+        escrow::take_ix(
+            &PINOCCHIO_ESCROW.id,
+            &taker.pubkey(),
+            &escrow_address,
+            &state,
+            &Pubkey::default(),
+        )
+    };
+
+    // 1. 第三方抢先吃单被拒绝，托管单保持原样
+    world.mint_to(&market.mint_b, &sniper.pubkey(), RECEIVE);
+    let err = world
+        .send(&PINOCCHIO_ESCROW, &[take(&sniper)], &sniper, &[])
+        .unwrap_err();
+    assert!(err.contains("Invalid taker"), "{err}");
+    assert_eq!(world.token_balance(&escrow_address, &market.mint_a), OFFER);
+
+    // 2. 指定的 taker 成交
+    world.mint_to(&market.mint_b, &taker.pubkey(), RECEIVE);
+    world
+        .send(&PINOCCHIO_ESCROW, &[take(&taker)], &taker, &[])
+        .unwrap();
+    assert_eq!(world.token_balance(&taker.pubkey(), &market.mint_a), OFFER);
+    assert_eq!(
+        world.token_balance(&maker.pubkey(), &market.mint_b),
+        RECEIVE
+    );
+    assert!(!world.exists(&escrow_address));
+}
+
+/// 改单：maker 换成另一种代币 B 并提高报价，金库不变；taker 按新报价成交
+#[test]
+fn maker_updates_offer_before_take() {
+    let mut world = World::new();
+    let market = Market::new(&mut world);
+    let maker = world.wallet();
+    let taker = world.wallet();
+    let escrow_address = market.make(&mut world, &maker, 0, None);
+    let state = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    let mint_c = world.mint(&market.admin);
+    let update = |signer: &Keypair| {
+        let mut ix = escrow::update_offer_ix(
+            &PINOCCHIO_ESCROW.id,
+            &escrow_address,
+            &state,
+            &mint_c,
+            RECEIVE * 2,
+        );
+        ix.accounts[0].pubkey = signer.pubkey();
+        ix
+    };
+
+    // 1. 只有 maker 能改单
+    assert!(world
+        .send(&PINOCCHIO_ESCROW, &[update(&taker)], &taker, &[])
+        .is_err());
+
+    // 2. maker 改单：托管账户与金库原地保留
+    world
+        .send(&PINOCCHIO_ESCROW, &[update(&maker)], &maker, &[])
+        .unwrap();
+    let updated = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    assert_eq!((updated.mint_b, updated.receive), (mint_c, RECEIVE * 2));
+    assert_eq!((updated.seed, updated.mint_a), (state.seed, state.mint_a));
+    assert_eq!(world.token_balance(&escrow_address, &market.mint_a), OFFER);
+
+    // 3. 按新报价成交
+    world.mint_to(&mint_c, &taker.pubkey(), RECEIVE * 2);
+    let take = escrow::take_ix(
+        &PINOCCHIO_ESCROW.id,
+        &taker.pubkey(),
+        &escrow_address,
+        &updated,
+        &Pubkey::default(),
+    );
+    world.send(&PINOCCHIO_ESCROW, &[take], &taker, &[]).unwrap();
+    assert_eq!(world.token_balance(&taker.pubkey(), &market.mint_a), OFFER);
+    assert_eq!(world.token_balance(&maker.pubkey(), &mint_c), RECEIVE * 2);
+    assert!(!world.exists(&escrow_address));
+}
+
+/// 抢先改价：taker 按看到的 receive 设置价格上限，maker 在吃单之前提价时吃单失败，
+/// 托管单原样保留；taker 接受新价格后重新吃单
+#[test]
+fn take_rejects_offer_repriced_after_quote() {
+    let mut world = World::new();
+    let market = Market::new(&mut world);
+    let maker = world.wallet();
+    let taker = world.wallet();
+    let escrow_address = market.make(&mut world, &maker, 0, None);
+    let quoted = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    world.mint_to(&market.mint_b, &taker.pubkey(), RECEIVE * 2);
+    let take = |max_receive| {
+        escrow::with_max_receive(
+            escrow::take_ix(
+                &PINOCCHIO_ESCROW.id,
+                &taker.pubkey(),
+                &escrow_address,
+                &quoted,
+                &Pubkey::default(),
+            ),
+            max_receive,
+        )
+    };
+
+    // 1. maker 抢在吃单之前把报价翻倍，mint_b 不变
+    let reprice = escrow::update_offer_ix(
+        &PINOCCHIO_ESCROW.id,
+        &escrow_address,
+        &quoted,
+        &market.mint_b,
+        RECEIVE * 2,
+    );
+    world
+        .send(&PINOCCHIO_ESCROW, &[reprice], &maker, &[])
+        .unwrap();
+
+    // 2. 按原报价设置上限的吃单被拒绝，taker 的代币 B 一个没少
+    let err = world
+        .send(&PINOCCHIO_ESCROW, &[take(RECEIVE)], &taker, &[])
+        .unwrap_err();
+    assert!(err.contains("Receive exceeds the taker's maximum"), "{err}");
+    assert_eq!(
+        world.token_balance(&taker.pubkey(), &market.mint_b),
+        RECEIVE * 2
+    );
+    assert_eq!(world.token_balance(&escrow_address, &market.mint_a), OFFER);
+
+    // 3. 接受新价格
+    world
+        .send(&PINOCCHIO_ESCROW, &[take(RECEIVE * 2)], &taker, &[])
+        .unwrap();
+    assert_eq!(world.token_balance(&taker.pubkey(), &market.mint_a), OFFER);
+    assert_eq!(
+        world.token_balance(&maker.pubkey(), &market.mint_b),
+        RECEIVE * 2
+    );
+    assert!(!world.exists(&escrow_address));
+}
+
+/// 加仓：maker 追加代币 A 并提高整单报价，部分成交按新的金库余额与报价结算
+#[test]
+fn maker_tops_up_escrow() {
+    let mut world = World::new();
+    let market = Market::new(&mut world);
+    let maker = world.wallet();
+    let taker = world.wallet();
+    let escrow_address = market.make(&mut world, &maker, 0, None);
+    let state = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+
+    world.mint_to(&market.mint_a, &maker.pubkey(), OFFER);
+    let top_up = escrow::top_up_ix(
+        &PINOCCHIO_ESCROW.id,
+        &escrow_address,
+        &state,
+        OFFER,
+        RECEIVE * 2,
+    );
+    world
+        .send(&PINOCCHIO_ESCROW, &[top_up], &maker, &[])
+        .unwrap();
+    assert_eq!(world.token_balance(&maker.pubkey(), &market.mint_a), 0);
+    assert_eq!(
+        world.token_balance(&escrow_address, &market.mint_a),
+        OFFER * 2
+    );
+    let topped = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    assert_eq!(topped.receive, RECEIVE * 2);
+
+    world.mint_to(&market.mint_b, &taker.pubkey(), RECEIVE * 2);
+    let take = escrow::take_ix(
+        &PINOCCHIO_ESCROW.id,
+        &taker.pubkey(),
+        &escrow_address,
+        &topped,
+        &Pubkey::default(),
+    );
+    world.send(&PINOCCHIO_ESCROW, &[take], &taker, &[]).unwrap();
+    assert_eq!(
+        world.token_balance(&taker.pubkey(), &market.mint_a),
+        OFFER * 2
+    );
+    assert_eq!(
+        world.token_balance(&maker.pubkey(), &market.mint_b),
+        RECEIVE * 2
+    );
+}
+
+/// 抢先加仓：TopUp 在部分成交之前执行并抬高单价，按旧价格设置上限的 TakePartial 被拒绝
+#[test]
+fn partial_fill_rejects_top_up_that_raises_unit_price() {
+    let mut world = World::new();
+    let market = Market::new(&mut world);
+    let maker = world.wallet();
+    let taker = world.wallet();
+    let escrow_address = market.make(&mut world, &maker, 0, None);
+    let quoted = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    let owed = quoted.partial_receive(OFFER / 2, OFFER).unwrap();
+    world.mint_to(&market.mint_b, &taker.pubkey(), RECEIVE);
+    let partial = |max_receive| {
+        escrow::with_max_receive(
+            escrow::take_partial_ix(
+                &PINOCCHIO_ESCROW.id,
+                &taker.pubkey(),
+                &escrow_address,
+                &quoted,
+                &Pubkey::default(),
+                OFFER / 2,
+            ),
+            max_receive,
+        )
+    };
+
+    // 1. 金库翻倍，整单报价变成三倍：单价是原来的 1.5 倍
+    world.mint_to(&market.mint_a, &maker.pubkey(), OFFER);
+    let top_up = escrow::top_up_ix(
+        &PINOCCHIO_ESCROW.id,
+        &escrow_address,
+        &quoted,
+        OFFER,
+        RECEIVE * 3,
+    );
+    world
+        .send(&PINOCCHIO_ESCROW, &[top_up], &maker, &[])
+        .unwrap();
+
+    // 2. 按旧单价计算的上限不够支付，成交被拒绝
+    let err = world
+        .send(&PINOCCHIO_ESCROW, &[partial(owed)], &taker, &[])
+        .unwrap_err();
+    assert!(err.contains("Receive exceeds the taker's maximum"), "{err}");
+    assert_eq!(
+        world.token_balance(&taker.pubkey(), &market.mint_b),
+        RECEIVE
+    );
+
+    // 3. 按新单价放宽上限后成交
+    let topped = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    let repriced = topped.partial_receive(OFFER / 2, OFFER * 2).unwrap();
+    assert!(repriced > owed);
+    world
+        .send(&PINOCCHIO_ESCROW, &[partial(repriced)], &taker, &[])
+        .unwrap();
+    assert_eq!(
+        world.token_balance(&taker.pubkey(), &market.mint_a),
+        OFFER / 2
+    );
+    assert_eq!(
+        world.token_balance(&maker.pubkey(), &market.mint_b),
+        repriced
+    );
+}
+
+/// 挂单索引：Make 登记 seed，Refund / Take 关闭托管时注销，索引账户本身保留
+#[test]
+fn offer_index_tracks_open_escrows() {
+    let mut world = World::new();
+    let market = Market::new(&mut world);
+    let maker = world.wallet();
+    let taker = world.wallet();
+    let (index_address, _) = escrow::offer_index_address(&PINOCCHIO_ESCROW.id, &maker.pubkey());
+    let offers = |world: &World| OfferIndex::try_from_bytes(&world.data(&index_address)).unwrap();
+
+    // 1. 两笔挂单：第一笔创建索引，第二笔追加
+    let first = market.make(&mut world, &maker, 0, None);
+    world.mint_to(&market.mint_a, &maker.pubkey(), OFFER);
+    let ix = escrow::make_ix(
+        &PINOCCHIO_ESCROW.id,
+        &maker.pubkey(),
+        &market.mint_a,
+        &market.mint_b,
+        ESCROW_SEED + 1,
+        RECEIVE,
+        OFFER,
+        0,
+        None,
+    );
+    world.send(&PINOCCHIO_ESCROW, &[ix], &maker, &[]).unwrap();
+    let second = escrow::escrow_address(&PINOCCHIO_ESCROW.id, &maker.pubkey(), ESCROW_SEED + 1).0;
+    let index = offers(&world);
+    assert_eq!(index.seeds, [ESCROW_SEED, ESCROW_SEED + 1]);
+    assert_eq!(
+        index.escrows(&PINOCCHIO_ESCROW.id, &maker.pubkey()),
+        [first, second]
+    );
+
+    // 2. 退掉第一笔：只剩第二笔
+    let state = Escrow::try_from_bytes(&world.data(&first)).unwrap();
+    let refund = escrow::refund_ix(&PINOCCHIO_ESCROW.id, &maker.pubkey(), &first, &state);
+    world
+        .send(&PINOCCHIO_ESCROW, &[refund], &maker, &[])
+        .unwrap();
+    assert_eq!(offers(&world).seeds, [ESCROW_SEED + 1]);
+
+    // 3. 第二笔成交：索引清空但账户还在，下次挂单不用再付租金
+    let state = Escrow::try_from_bytes(&world.data(&second)).unwrap();
+    world.mint_to(&market.mint_b, &taker.pubkey(), RECEIVE);
+    let take = escrow::take_ix(
+        &PINOCCHIO_ESCROW.id,
+        &taker.pubkey(),
+        &second,
+        &state,
+        &Pubkey::default(),
+    );
+    world.send(&PINOCCHIO_ESCROW, &[take], &taker, &[]).unwrap();
+    assert!(offers(&world).seeds.is_empty());
+}
+
+/// 原生 SOL：卖 SOL 的一方直接从钱包出 lamports，买到 SOL 的一方直接收 lamports，
+/// 双方都不需要自己包装 / 解包 wSOL
+#[test]
+fn native_sol_legs_settle_in_lamports() {
+    let mut world = World::new();
+    let admin = world.wallet();
+    let token = world.mint(&admin);
+    let (maker, taker) = (world.wallet(), world.wallet());
+    let make = |world: &mut World, maker: &Keypair, mint_a: &Pubkey, mint_b: &Pubkey, amount| {
+        let ix = escrow::make_ix(
+            &PINOCCHIO_ESCROW.id,
+            &maker.pubkey(),
+            mint_a,
+            mint_b,
+            ESCROW_SEED,
+            RECEIVE,
+            amount,
+            0,
+            None,
+        );
+        world.send(&PINOCCHIO_ESCROW, &[ix], maker, &[]).unwrap();
+        let address = escrow::escrow_address(&PINOCCHIO_ESCROW.id, &maker.pubkey(), ESCROW_SEED).0;
+        let state = Escrow::try_from_bytes(&world.data(&address)).unwrap();
+        (address, state)
+    };
+    let take = |world: &mut World, address: &Pubkey, state: &Escrow| {
+        let ix = escrow::take_ix(
+            &PINOCCHIO_ESCROW.id,
+            &taker.pubkey(),
+            address,
+            state,
+            &Pubkey::default(),
+        );
+        world.send(&PINOCCHIO_ESCROW, &[ix], &taker, &[]).unwrap();
+    };
+
+    // 1. maker 卖出 SOL：金库是 wSOL，maker 没有 wSOL ATA
+    let before = world.lamports(&maker.pubkey());
+    let (address, state) = make(&mut world, &maker, &NATIVE_MINT, &token, SOL);
+    assert!(before - world.lamports(&maker.pubkey()) > SOL);
+    assert_eq!(world.token_balance(&address, &NATIVE_MINT), SOL);
+    assert!(!world.exists(&ata(&maker.pubkey(), &NATIVE_MINT)));
+
+    // taker 付代币 B，拿到的是 SOL 而不是 wSOL；金库与托管账户的租金都回到 maker
+    world.mint_to(&token, &taker.pubkey(), RECEIVE);
+    let before = world.lamports(&taker.pubkey());
+    take(&mut world, &address, &state);
+    // taker 只额外付了交易费与 maker_ata_b 的租金
+    assert!(world.lamports(&taker.pubkey()) > before + SOL - SOL / 100);
+    assert!(!world.exists(&ata(&taker.pubkey(), &NATIVE_MINT)));
+    assert_eq!(world.token_balance(&maker.pubkey(), &token), RECEIVE);
+    assert!(!world.exists(&address));
+    assert!(!world.exists(&state.vault(&address)));
+
+    // 2. 另一位 maker 用代币换 SOL：taker 直接付 lamports，maker 不需要 wSOL ATA
+    let seller = world.wallet();
+    world.mint_to(&token, &seller.pubkey(), OFFER);
+    let seller_before = world.lamports(&seller.pubkey());
+    let (address, state) = make(&mut world, &seller, &token, &NATIVE_MINT, OFFER);
+    let before = world.lamports(&taker.pubkey());
+    take(&mut world, &address, &state);
+    assert!(before - world.lamports(&taker.pubkey()) >= RECEIVE);
+    // seller 收回托管与金库的租金，净得 RECEIVE 减去交易费与挂单索引的租金
+    assert!(world.lamports(&seller.pubkey()) > seller_before + RECEIVE - SOL / 100);
+    assert!(!world.exists(&ata(&seller.pubkey(), &NATIVE_MINT)));
+    assert_eq!(world.token_balance(&taker.pubkey(), &token), OFFER);
+}
+
+/// 原生 SOL 的代币 A：TopUp 直接从 maker 扣 lamports，部分成交时 taker 收到的也是 lamports
+#[test]
+fn native_sol_escrow_tops_up_and_fills_partially() {
+    let mut world = World::new();
+    let admin = world.wallet();
+    let token = world.mint(&admin);
+    let (maker, taker) = (world.wallet(), world.wallet());
+    let make = escrow::make_ix(
+        &PINOCCHIO_ESCROW.id,
+        &maker.pubkey(),
+        &NATIVE_MINT,
+        &token,
+        ESCROW_SEED,
+        RECEIVE,
+        SOL,
+        0,
+        None,
+    );
+    world.send(&PINOCCHIO_ESCROW, &[make], &maker, &[]).unwrap();
+    let address = escrow::escrow_address(&PINOCCHIO_ESCROW.id, &maker.pubkey(), ESCROW_SEED).0;
+    let state = Escrow::try_from_bytes(&world.data(&address)).unwrap();
+
+    // 1. 追加 1 SOL：maker 没有 wSOL ATA，lamports 直接包装进金库
+    let before = world.lamports(&maker.pubkey());
+    let top_up = escrow::top_up_ix(&PINOCCHIO_ESCROW.id, &address, &state, SOL, RECEIVE * 2);
+    world
+        .send(&PINOCCHIO_ESCROW, &[top_up], &maker, &[])
+        .unwrap();
+    assert!(before - world.lamports(&maker.pubkey()) >= SOL);
+    assert_eq!(world.token_balance(&address, &NATIVE_MINT), SOL * 2);
+    assert!(!world.exists(&ata(&maker.pubkey(), &NATIVE_MINT)));
+
+    // 2. 买走一半：taker 拿到 lamports，临时的 wSOL ATA 关闭、租金退回
+    world.mint_to(&token, &taker.pubkey(), RECEIVE);
+    let topped = Escrow::try_from_bytes(&world.data(&address)).unwrap();
+    let partial = escrow::take_partial_ix(
+        &PINOCCHIO_ESCROW.id,
+        &taker.pubkey(),
+        &address,
+        &topped,
+        &Pubkey::default(),
+        SOL,
+    );
+    let before = world.lamports(&taker.pubkey());
+    world
+        .send(&PINOCCHIO_ESCROW, &[partial], &taker, &[])
+        .unwrap();
+    // taker 只额外付了交易费与 maker_ata_b 的租金
+    assert!(world.lamports(&taker.pubkey()) > before + SOL - SOL / 100);
+    assert!(!world.exists(&ata(&taker.pubkey(), &NATIVE_MINT)));
+    assert_eq!(world.token_balance(&taker.pubkey(), &token), 0);
+    assert_eq!(world.token_balance(&maker.pubkey(), &token), RECEIVE);
+    assert_eq!(world.token_balance(&address, &NATIVE_MINT), SOL);
+    let remaining = Escrow::try_from_bytes(&world.data(&address)).unwrap();
+    assert_eq!(remaining.receive, RECEIVE);
+
+    // 3. taker 已有的 wSOL ATA 不会被关闭：买走的部分以 wSOL 留在其中
+    world.mint_to(&token, &taker.pubkey(), RECEIVE / 2);
+    let create = create_ata_idempotent_ix(&taker.pubkey(), &taker.pubkey(), &NATIVE_MINT);
+    let partial = escrow::take_partial_ix(
+        &PINOCCHIO_ESCROW.id,
+        &taker.pubkey(),
+        &address,
+        &remaining,
+        &Pubkey::default(),
+        SOL / 2,
+    );
+    world
+        .send(&PINOCCHIO_ESCROW, &[create, partial], &taker, &[])
+        .unwrap();
+    assert_eq!(world.token_balance(&taker.pubkey(), &NATIVE_MINT), SOL / 2);
+    assert_eq!(world.token_balance(&address, &NATIVE_MINT), SOL / 2);
+}
+
+/// 协议手续费：Config 存在时 taker 仍支付 receive，其中按费率扣下的部分转入 treasury
+#[test]
+fn take_routes_protocol_fee_to_treasury() {
+    let mut world = World::new();
+    let market = Market::new(&mut world);
+    let maker = world.wallet();
+    let (taker, treasury) = (world.wallet(), world.wallet());
+    let escrow_address = market.make(&mut world, &maker, 0, None);
+    let state = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+    world.mint_to(&market.mint_b, &taker.pubkey(), RECEIVE);
+
+    // LiteSVM 加载的程序没有升级权限，不能调用 InitConfig，直接写入 Config
+    let (config_address, bump) = escrow::config_address(&PINOCCHIO_ESCROW.id);
+    let config = escrow::Config {
+        treasury: treasury.pubkey(),
+        fee_bps: 250,
+        bump,
+    };
+    let mut data = treasury.pubkey().to_bytes().to_vec();
+    data.extend_from_slice(&config.fee_bps.to_le_bytes());
+    data.push(bump);
+    world
+        .svm
+        .set_account(
+            config_address,
+            Account {
+                lamports: SOL,
+                data,
+                owner: PINOCCHIO_ESCROW.id,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    let take = [
+        create_ata_idempotent_ix(&taker.pubkey(), &treasury.pubkey(), &market.mint_b),
+        escrow::take_ix(
+            &PINOCCHIO_ESCROW.id,
+            &taker.pubkey(),
+            &escrow_address,
+            &state,
+            &treasury.pubkey(),
+        ),
+    ];
+    world.send(&PINOCCHIO_ESCROW, &take, &taker, &[]).unwrap();
+    let fee = config.fee(RECEIVE);
+    assert_eq!(fee, RECEIVE / 40);
+    assert_eq!(world.token_balance(&taker.pubkey(), &market.mint_a), OFFER);
+    assert_eq!(world.token_balance(&taker.pubkey(), &market.mint_b), 0);
+    assert_eq!(world.token_balance(&treasury.pubkey(), &market.mint_b), fee);
+    assert_eq!(
+        world.token_balance(&maker.pubkey(), &market.mint_b),
+        RECEIVE - fee
+    );
+    assert!(!world.exists(&escrow_address));
+}
+
+/// 托管单和池子 config 属于同一个程序地址，两个程序都必须拒绝对方的账户
+#[test]
+fn programs_reject_each_others_accounts() {
+    let mut world = World::new();
+    let market = Market::new(&mut world);
+    let maker = world.wallet();
+    let escrow_address = market.make(&mut world, &maker, 0, None);
+    let state = Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap();
+
+    // 把池子 config 当作托管单吃单
+    let take = escrow::take_ix(
+        &PINOCCHIO_ESCROW.id,
+        &market.admin.pubkey(),
+        &market.pool.config,
+        &state,
+        &Pubkey::default(),
+    );
+    assert!(world
+        .send(&PINOCCHIO_ESCROW, &[take], &market.admin, &[])
+        .is_err());
+
+    // 把托管单当作池子 config 兑换
+    let mut pool = market.pool.clone();
+    pool.config = escrow_address;
+    let is_x = market.mint_a == pool.mint_x;
+    let swap = pool.swap_ix(&market.admin.pubkey(), is_x, 1_000, 1);
+    assert!(world
+        .send(&NATIVE_AMM, &[swap], &market.admin, &[])
+        .is_err());
+
+    // 两边的账户都没有被改动
+    assert_eq!(
+        Escrow::try_from_bytes(&world.data(&escrow_address)).unwrap(),
+        state
+    );
+    let config = AmmConfig::try_from_bytes(&world.data(&market.pool.config)).unwrap();
+    assert_eq!((config.reserve_x, config.reserve_y), (LIQUIDITY, LIQUIDITY));
+}
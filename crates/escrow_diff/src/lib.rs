@@ -0,0 +1,553 @@
+//! Anchor 托管（task3）与 Pinocchio 托管（task5）的差分执行。
+//!
+//! 两个实现部署在同一个占位地址上，PDA、金库 ATA 与指令数据完全相同，账户顺序也只差末尾三个
+//! 程序账户（Pinocchio 的 Take 另外多出协议手续费的两个账户，场景中不创建 Config，不收手续费；
+//! 三条指令末尾还都有 maker 的挂单索引，Anchor 版没有）。[`run`] 用同一个 [`Scenario`] 分别驱动两个 Mollusk 实例，每一步之后比较：
+//!
+//! - 执行结果：两边必须同时成功或同时失败；
+//! - 执行后的状态：各代币账户的原始数据、每个 seed 上托管单的字段（两边的账户布局逐字节相同）、
+//!   taker 的 lamports，以及 maker 与其托管单 lamports 的合计（Pinocchio 版还要算上挂单索引的租金，
+//!   它由 maker 支付，合计与 Anchor 版仍然必须一致）。
+//!
+//! 单元测试逐条检查已知用例，这里检查的是组合：先 make 再用同一 seed make、余额恰好不够、
+//! take 时 ATA 不存在……任何一处语义不一致都会以 [`Divergence`] 报告，附带能复现的场景。
+//!
+//! 两边都失败但错误码不同时记入 [`Report::code_mismatches`]，不算分歧：Anchor 按
+//! `#[derive(Accounts)]` 的字段顺序检查约束、先于账户校验解析参数，Pinocchio 按手写顺序，
+//! 同一笔错误交易最先触发的检查可以不同。测试会打印这些差异，供对齐错误码时参考。
+//!
+//! 已知分歧见 [`KnownDrift`]：遇到时记录并结束该场景，之后的状态不再可比。
+
+use std::{collections::HashMap, fmt};
+
+use blueshift_errors::BootcampError;
+use bootcamp_client::{
+    escrow::{self, Escrow},
+    token::ata,
+    Instruction as ClientInstruction, Pubkey as ClientPubkey,
+};
+use mollusk_svm::{program::keyed_account_for_system_program, result::ProgramResult, Mollusk};
+use solana_account::Account;
+use solana_program_error::ProgramError;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use token_fixtures::{mollusk as fixtures, TokenProgram};
+
+mod scenario;
+
+pub use scenario::{seeded_bytes, Action, Fault, Input, Scenario, Step, MAX_STEPS, SEEDS};
+
+/// 两个托管的部署地址相同
+pub const PROGRAM_ID: ClientPubkey = escrow::PROGRAM_ID;
+
+/// 各钱包初始持有的 lamports
+pub const WALLET_LAMPORTS: u64 = 10_000_000_000;
+
+/// 两个实现 `cargo build-sbf` / `anchor build` 产物的路径（不含 .so 扩展名）
+pub mod programs {
+    pub const ANCHOR_ESCROW: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../task3/blueshift_anchor_escrow/target/deploy/blueshift_anchor_escrow"
+    );
+    pub const PINOCCHIO_ESCROW: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../task5/blueshift_escrow/target/deploy/blueshift_escrow"
+    );
+}
+
+/// 参与比较的实现
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Build {
+    Anchor,
+    Pinocchio,
+}
+
+impl Build {
+    fn program(self) -> &'static str {
+        match self {
+            Self::Anchor => programs::ANCHOR_ESCROW,
+            Self::Pinocchio => programs::PINOCCHIO_ESCROW,
+        }
+    }
+
+    /// bootcamp_client 按 Pinocchio 的顺序构造指令，末尾是 System、Token、ATA 三个程序；
+    /// Anchor 版的顺序正好相反，也没有末尾的挂单索引，Take 还没有之前的 config 与 treasury ATA
+    fn instruction(self, ix: &ClientInstruction) -> Instruction {
+        let mut accounts: Vec<AccountMeta> = ix
+            .accounts
+            .iter()
+            .map(|meta| AccountMeta {
+                pubkey: key(&meta.pubkey),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect();
+        if self == Self::Anchor {
+            accounts.pop();
+            if ix.data.first() == Some(&1) {
+                accounts.truncate(accounts.len().saturating_sub(2));
+            }
+            let tail = accounts.len().saturating_sub(3);
+            accounts[tail..].reverse();
+        }
+        Instruction {
+            program_id: key(&ix.program_id),
+            accounts,
+            data: ix.data.clone(),
+        }
+    }
+}
+
+fn key(pubkey: &ClientPubkey) -> Pubkey {
+    Pubkey::new_from_array(pubkey.to_bytes())
+}
+
+/// 场景中的固定地址，两个实现使用同一组，状态可以逐个地址比较
+#[derive(Clone, Copy, Debug)]
+struct Parties {
+    maker: ClientPubkey,
+    taker: ClientPubkey,
+    outsider: ClientPubkey,
+    mint_a: ClientPubkey,
+    mint_b: ClientPubkey,
+}
+
+const PARTIES: Parties = Parties {
+    maker: ClientPubkey::new_from_array([0x11; 32]),
+    taker: ClientPubkey::new_from_array([0x22; 32]),
+    outsider: ClientPubkey::new_from_array([0x33; 32]),
+    mint_a: ClientPubkey::new_from_array([0xaa; 32]),
+    mint_b: ClientPubkey::new_from_array([0xbb; 32]),
+};
+
+impl Parties {
+    fn escrow(&self, seed: u64) -> ClientPubkey {
+        escrow::escrow_address(&PROGRAM_ID, &self.maker, seed).0
+    }
+
+    /// maker 的挂单索引，只有 Pinocchio 版会创建
+    fn offer_index(&self) -> ClientPubkey {
+        escrow::offer_index_address(&PROGRAM_ID, &self.maker).0
+    }
+
+    /// take / refund 只用到 maker 与两个 mint，其余字段不影响账户列表
+    fn escrow_state(&self, seed: u64) -> Escrow {
+        Escrow {
+            seed,
+            maker: self.maker,
+            mint_a: self.mint_a,
+            mint_b: self.mint_b,
+            receive: 0,
+            bump: 0,
+            expiry: 0,
+            taker: ClientPubkey::default(),
+        }
+    }
+
+    /// 按步骤构造指令并施加故障
+    fn instruction(&self, step: &Step) -> ClientInstruction {
+        let mut ix = match step.action {
+            Action::Make {
+                seed,
+                receive,
+                amount,
+            } => escrow::make_ix(
+                &PROGRAM_ID,
+                &self.maker,
+                &self.mint_a,
+                &self.mint_b,
+                seed,
+                receive,
+                amount,
+                0,
+                None,
+            ),
+            Action::Take { seed } => escrow::take_ix(
+                &PROGRAM_ID,
+                &self.taker,
+                &self.escrow(seed),
+                &self.escrow_state(seed),
+                &ClientPubkey::default(),
+            ),
+            // 场景中的托管单都不过期，由 maker 本人退款
+            Action::Refund { seed } => escrow::refund_ix(
+                &PROGRAM_ID,
+                &self.maker,
+                &self.escrow(seed),
+                &self.escrow_state(seed),
+            ),
+        };
+        let replace = |ix: &mut ClientInstruction, from: ClientPubkey, to: ClientPubkey| {
+            for meta in ix.accounts.iter_mut().filter(|meta| meta.pubkey == from) {
+                meta.pubkey = to;
+            }
+        };
+        match step.fault {
+            None => {}
+            Some(Fault::MissingSignature) => ix.accounts[0].is_signer = false,
+            Some(Fault::WrongMint) => replace(&mut ix, self.mint_a, self.mint_b),
+            Some(Fault::WrongMaker) => replace(&mut ix, self.maker, self.outsider),
+            Some(Fault::Truncated(len)) => ix.data.truncate(len.max(1)),
+            Some(Fault::TrailingByte(byte)) => ix.data.push(byte),
+        }
+        ix
+    }
+
+    /// 参与比较的代币账户：双方在两个 mint 上的 ATA，以及每个 seed 的金库
+    fn token_accounts(&self) -> Vec<ClientPubkey> {
+        let mut accounts = vec![
+            ata(&self.maker, &self.mint_a),
+            ata(&self.maker, &self.mint_b),
+            ata(&self.taker, &self.mint_a),
+            ata(&self.taker, &self.mint_b),
+        ];
+        accounts.extend((0..SEEDS).map(|seed| ata(&self.escrow(seed), &self.mint_a)));
+        accounts
+    }
+}
+
+/// 一条指令的执行结果
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Success,
+    Failure(ProgramError),
+    /// 无法换算成 ProgramError 的运行时错误（例如 CU 耗尽），保留原始描述
+    Unknown(String),
+}
+
+impl Outcome {
+    fn is_success(&self) -> bool {
+        *self == Self::Success
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Success => f.write_str("成功"),
+            Self::Failure(ProgramError::Custom(code)) => match BootcampError::from_code(*code) {
+                Some(error) => write!(f, "{error}"),
+                None => write!(f, "Custom({code})"),
+            },
+            Self::Failure(error) => write!(f, "{error:?}"),
+            Self::Unknown(error) => f.write_str(error),
+        }
+    }
+}
+
+/// 某个 seed 上的托管单
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EscrowSlot {
+    Closed,
+    Open(Escrow),
+    /// 账户存在，但数据无法按托管布局解析（例如判别器不符）
+    Unreadable(Vec<u8>),
+}
+
+/// 一次执行后可以在两个实现之间比较的状态
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct State {
+    /// maker 钱包、挂单索引与其全部托管单的 lamports 合计
+    pub maker_lamports: u64,
+    pub taker_lamports: u64,
+    /// 代币账户的原始数据，账户不存在时为 `None`
+    pub tokens: Vec<(ClientPubkey, Option<Vec<u8>>)>,
+    pub escrows: Vec<EscrowSlot>,
+}
+
+/// 单个实现的执行环境：成功执行后的账户写回，供下一步使用
+pub struct Runner {
+    build: Build,
+    mollusk: Mollusk,
+    accounts: HashMap<Pubkey, Account>,
+}
+
+impl Runner {
+    /// 加载程序与代币程序，写入场景的初始状态
+    pub fn new(build: Build, scenario: &Scenario) -> Self {
+        let mut mollusk = Mollusk::new(&key(&PROGRAM_ID), build.program());
+        fixtures::add_token_programs(&mut mollusk);
+
+        let p = PARTIES;
+        let program = TokenProgram::Token;
+        let mut accounts: HashMap<Pubkey, Account> = fixtures::token_program_accounts()
+            .into_iter()
+            .chain([
+                keyed_account_for_system_program(),
+                fixtures::wallet(&key(&p.maker), WALLET_LAMPORTS),
+                fixtures::wallet(&key(&p.taker), WALLET_LAMPORTS),
+                fixtures::wallet(&key(&p.outsider), WALLET_LAMPORTS),
+                fixtures::mint(
+                    &key(&p.mint_a),
+                    &key(&p.outsider),
+                    scenario.maker_a,
+                    scenario.decimals,
+                    program,
+                ),
+                fixtures::mint(
+                    &key(&p.mint_b),
+                    &key(&p.outsider),
+                    scenario.taker_b,
+                    scenario.decimals,
+                    program,
+                ),
+                fixtures::ata(&key(&p.maker), &key(&p.mint_a), scenario.maker_a, program),
+                fixtures::ata(&key(&p.taker), &key(&p.mint_b), scenario.taker_b, program),
+            ])
+            .collect();
+        if scenario.taker_has_ata_a {
+            let (address, account) = fixtures::ata(&key(&p.taker), &key(&p.mint_a), 0, program);
+            accounts.insert(address, account);
+        }
+        if scenario.maker_has_ata_b {
+            let (address, account) = fixtures::ata(&key(&p.maker), &key(&p.mint_b), 0, program);
+            accounts.insert(address, account);
+        }
+
+        Self {
+            build,
+            mollusk,
+            accounts,
+        }
+    }
+
+    /// 执行一条指令；成功时把结果写回，失败时状态保持不变
+    pub fn execute(&mut self, ix: &ClientInstruction) -> Outcome {
+        let ix = self.build.instruction(ix);
+        let mut inputs: Vec<(Pubkey, Account)> = Vec::new();
+        for meta in &ix.accounts {
+            if !inputs.iter().any(|(address, _)| *address == meta.pubkey) {
+                // 不存在的地址视为空的系统账户
+                let account = self.accounts.get(&meta.pubkey).cloned().unwrap_or_default();
+                inputs.push((meta.pubkey, account));
+            }
+        }
+
+        let result = self.mollusk.process_instruction(&ix, &inputs);
+        match result.program_result {
+            ProgramResult::Success => {
+                self.accounts.extend(result.resulting_accounts);
+                Outcome::Success
+            }
+            ProgramResult::Failure(error) => Outcome::Failure(error),
+            ProgramResult::UnknownError(error) => Outcome::Unknown(format!("{error:?}")),
+        }
+    }
+
+    fn live(&self, address: &ClientPubkey) -> Option<&Account> {
+        self.accounts
+            .get(&key(address))
+            .filter(|account| account.lamports > 0)
+    }
+
+    pub fn state(&self) -> State {
+        let p = PARTIES;
+        let lamports = |address: &ClientPubkey| self.live(address).map_or(0, |a| a.lamports);
+        let escrows: Vec<ClientPubkey> = (0..SEEDS).map(|seed| p.escrow(seed)).collect();
+        State {
+            maker_lamports: lamports(&p.maker)
+                + lamports(&p.offer_index())
+                + escrows.iter().map(lamports).sum::<u64>(),
+            taker_lamports: lamports(&p.taker),
+            tokens: p
+                .token_accounts()
+                .into_iter()
+                .map(|address| {
+                    let data = self.live(&address).map(|account| account.data.clone());
+                    (address, data)
+                })
+                .collect(),
+            escrows: escrows
+                .iter()
+                .map(|address| match self.live(address) {
+                    None => EscrowSlot::Closed,
+                    Some(account) => match Escrow::try_from_bytes(&account.data) {
+                        Ok(escrow) => EscrowSlot::Open(escrow),
+                        Err(_) => EscrowSlot::Unreadable(account.data.clone()),
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 两个实现在某一步上的分歧：一边成功一边失败，或执行后的状态不同
+#[derive(Clone, Debug)]
+pub struct Divergence {
+    pub step: usize,
+    pub scenario: Scenario,
+    pub anchor: (Outcome, State),
+    pub pinocchio: (Outcome, State),
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let step = self.scenario.steps[self.step];
+        writeln!(f, "第 {} 步 {:?} 两个实现不一致", self.step, step)?;
+        writeln!(f, "  Anchor:    {}", self.anchor.0)?;
+        writeln!(f, "  Pinocchio: {}", self.pinocchio.0)?;
+        if self.anchor.1 != self.pinocchio.1 {
+            writeln!(f, "  Anchor 状态:    {:?}", self.anchor.1)?;
+            writeln!(f, "  Pinocchio 状态: {:?}", self.pinocchio.1)?;
+        }
+        write!(f, "  场景: {:?}", self.scenario)
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// 已经确认、暂不修复的行为差异
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KnownDrift {
+    /// Make 的指令数据末尾有多余字节：Anchor 只反序列化需要的部分并照常执行，
+    /// Pinocchio 要求恰好 64 字节
+    TrailingMakeData,
+    /// Make 的 receive 为 0：Anchor 的 `require_gt!` 拒绝，Pinocchio 只检查 amount
+    ZeroReceive,
+}
+
+impl KnownDrift {
+    fn classify(step: &Step, anchor: &Outcome, pinocchio: &Outcome) -> Option<Self> {
+        let Action::Make { receive, .. } = step.action else {
+            return None;
+        };
+        if anchor.is_success() == pinocchio.is_success() {
+            return None;
+        }
+        if matches!(step.fault, Some(Fault::TrailingByte(_))) && !pinocchio.is_success() {
+            return Some(Self::TrailingMakeData);
+        }
+        (receive == 0 && !anchor.is_success()).then_some(Self::ZeroReceive)
+    }
+}
+
+/// 一个场景的运行结果
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    /// 两边都成功的步数
+    pub succeeded: usize,
+    /// 两边都失败、错误码不同的步骤：(步骤, Anchor 结果, Pinocchio 结果)
+    pub code_mismatches: Vec<(Step, Outcome, Outcome)>,
+    /// 遇到已知分歧的步骤，之后的步骤没有执行
+    pub known_drift: Option<(usize, KnownDrift)>,
+}
+
+/// 同一场景在两个实现上逐步执行并比较
+pub fn run(scenario: &Scenario) -> Result<Report, Divergence> {
+    let mut anchor = Runner::new(Build::Anchor, scenario);
+    let mut pinocchio = Runner::new(Build::Pinocchio, scenario);
+    let mut report = Report::default();
+
+    for (index, step) in scenario.steps.iter().enumerate() {
+        let ix = PARTIES.instruction(step);
+        let anchor_outcome = anchor.execute(&ix);
+        let pinocchio_outcome = pinocchio.execute(&ix);
+        if let Some(drift) = KnownDrift::classify(step, &anchor_outcome, &pinocchio_outcome) {
+            report.known_drift = Some((index, drift));
+            return Ok(report);
+        }
+
+        let (anchor_state, pinocchio_state) = (anchor.state(), pinocchio.state());
+        if anchor_outcome.is_success() != pinocchio_outcome.is_success()
+            || anchor_state != pinocchio_state
+        {
+            return Err(Divergence {
+                step: index,
+                scenario: scenario.clone(),
+                anchor: (anchor_outcome, anchor_state),
+                pinocchio: (pinocchio_outcome, pinocchio_state),
+            });
+        }
+        if anchor_outcome.is_success() {
+            report.succeeded += 1;
+        } else if anchor_outcome != pinocchio_outcome {
+            report
+                .code_mismatches
+                .push((*step, anchor_outcome, pinocchio_outcome));
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchor_accounts_reverse_the_program_tail() {
+        let step = Step {
+            action: Action::Refund { seed: 1 },
+            fault: None,
+        };
+        let ix = PARTIES.instruction(&step);
+        let anchor = Build::Anchor.instruction(&ix);
+        let pinocchio = Build::Pinocchio.instruction(&ix);
+        // Pinocchio 版末尾多出挂单索引，Anchor 版去掉它之后再倒转三个程序账户
+        let n = anchor.accounts.len();
+        assert_eq!(pinocchio.accounts.len(), n + 1);
+        assert_eq!(pinocchio.accounts[n].pubkey, key(&PARTIES.offer_index()));
+        assert_eq!(anchor.accounts[..n - 3], pinocchio.accounts[..n - 3]);
+        assert_eq!(anchor.accounts[n - 1], pinocchio.accounts[n - 3]);
+        assert_eq!(anchor.accounts[n - 3], pinocchio.accounts[n - 1]);
+        assert_eq!(anchor.data, pinocchio.data);
+    }
+
+    #[test]
+    fn faults_rewrite_one_aspect_of_the_instruction() {
+        let make = Action::Make {
+            seed: 0,
+            receive: 5,
+            amount: 7,
+        };
+        let with = |fault| {
+            PARTIES.instruction(&Step {
+                action: make,
+                fault: Some(fault),
+            })
+        };
+        let clean = PARTIES.instruction(&Step {
+            action: make,
+            fault: None,
+        });
+
+        assert!(!with(Fault::MissingSignature).accounts[0].is_signer);
+        assert_eq!(with(Fault::Truncated(0)).data, clean.data[..1]);
+        assert_eq!(
+            with(Fault::TrailingByte(9)).data.len(),
+            clean.data.len() + 1
+        );
+        assert!(with(Fault::WrongMint)
+            .accounts
+            .iter()
+            .all(|meta| meta.pubkey != PARTIES.mint_a));
+        let wrong_maker = with(Fault::WrongMaker);
+        assert_eq!(wrong_maker.accounts[0].pubkey, PARTIES.outsider);
+        // PDA 与 ATA 仍按真正的 maker 派生，程序必须发现两者对不上
+        assert_eq!(wrong_maker.accounts[1].pubkey, clean.accounts[1].pubkey);
+    }
+
+    #[test]
+    fn escrow_layouts_are_identical() {
+        let escrow = PARTIES.escrow_state(2);
+        let mut data = Vec::with_capacity(Escrow::LEN);
+        data.push(Escrow::DISCRIMINATOR);
+        data.extend_from_slice(&escrow.seed.to_le_bytes());
+        data.extend_from_slice(escrow.maker.as_ref());
+        data.extend_from_slice(escrow.mint_a.as_ref());
+        data.extend_from_slice(escrow.mint_b.as_ref());
+        data.extend_from_slice(&escrow.receive.to_le_bytes());
+        data.push(escrow.bump);
+        data.extend_from_slice(&escrow.expiry.to_le_bytes());
+        data.extend_from_slice(escrow.taker.as_ref());
+
+        // 判别器与 Anchor 的 `#[account(discriminator = 1)]` 相同，两边写出的就是这些字节
+        assert_eq!(data.len(), Escrow::LEN);
+        assert_eq!(Escrow::try_from_bytes(&data).ok(), Some(escrow));
+        // 去掉判别器的旧 Pinocchio 布局不再被接受
+        let mut legacy = data[1..].to_vec();
+        legacy.push(0);
+        assert!(Escrow::try_from_bytes(&legacy).is_err());
+    }
+}
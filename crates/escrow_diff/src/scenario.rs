@@ -0,0 +1,195 @@
+//! 由字节流生成场景：初始余额、每一步的指令与参数，以及可选的一处故障。
+//!
+//! 字节不够时一律按 0 处理，任何输入都能得到一个合法的场景，fuzz 与种子测试共用同一套生成规则。
+//! 参数故意集中在边界附近（0、1、恰好等于余额、多 1、u64::MAX），而不是均匀随机。
+
+/// 同一个 maker 可以使用的 seed 数量；取值范围小，make / take / refund 才会落到同一个托管单上
+pub const SEEDS: u64 = 3;
+
+/// 一个场景最多的步数，避免长输入生成过长的序列
+pub const MAX_STEPS: usize = 8;
+
+/// 初始状态与指令序列
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Scenario {
+    /// 两个 mint 的小数位，0..=9
+    pub decimals: u8,
+    /// maker 在 mint_a 上的余额
+    pub maker_a: u64,
+    /// taker 在 mint_b 上的余额
+    pub taker_b: u64,
+    /// taker 的 mint_a ATA、maker 的 mint_b ATA 是否预先存在（否则由 Take 创建）
+    pub taker_has_ata_a: bool,
+    pub maker_has_ata_b: bool,
+    pub steps: Vec<Step>,
+}
+
+/// 一条指令及施加在它上面的故障
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Step {
+    pub action: Action,
+    pub fault: Option<Fault>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Action {
+    Make {
+        seed: u64,
+        receive: u64,
+        amount: u64,
+    },
+    Take {
+        seed: u64,
+    },
+    Refund {
+        seed: u64,
+    },
+}
+
+/// 对指令的单处改动，两个实现都应当拒绝（或同样地接受）
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fault {
+    /// 第一个账户（maker / taker）不签名
+    MissingSignature,
+    /// 用 mint_b 顶替 mint_a
+    WrongMint,
+    /// 用第三方钱包顶替 maker 出现的每个位置
+    WrongMaker,
+    /// 指令数据截断到前 n 字节（至少保留判别器）
+    Truncated(usize),
+    /// 指令数据末尾多出一个字节
+    TrailingByte(u8),
+}
+
+/// 按字节顺序读取，读完后返回 0
+pub struct Input<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Input<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub fn byte(&mut self) -> u8 {
+        match self.data.split_first() {
+            Some((byte, rest)) => {
+                self.data = rest;
+                *byte
+            }
+            None => 0,
+        }
+    }
+
+    pub fn u64(&mut self) -> u64 {
+        u64::from_le_bytes(core::array::from_fn(|_| self.byte()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// 相对 `balance` 的边界值，或 1..=balance 之间的随机值
+    fn amount(&mut self, balance: u64) -> u64 {
+        match self.byte() % 8 {
+            0 => 0,
+            1 => 1,
+            2 => balance,
+            3 => balance.saturating_add(1),
+            4 => u64::MAX,
+            _ => 1 + self.u64() % balance.max(1),
+        }
+    }
+}
+
+impl Scenario {
+    /// 由字节生成场景；`faults` 为假时不施加任何故障，只变化参数与状态
+    pub fn generate(data: &[u8], faults: bool) -> Self {
+        let mut input = Input::new(data);
+        let decimals = input.byte() % 10;
+        // 余额同样取边界附近的值，0 余额能覆盖代币程序返回的错误
+        let maker_a = input.amount(1_000_000);
+        let taker_b = input.amount(1_000_000);
+        let flags = input.byte();
+
+        let mut steps = Vec::new();
+        while steps.len() < MAX_STEPS && !input.is_empty() {
+            let seed = u64::from(input.byte()) % SEEDS;
+            let action = match input.byte() % 3 {
+                0 => Action::Make {
+                    seed,
+                    receive: input.amount(taker_b),
+                    amount: input.amount(maker_a),
+                },
+                1 => Action::Take { seed },
+                _ => Action::Refund { seed },
+            };
+            let fault = if faults {
+                match input.byte() % 10 {
+                    0 => Some(Fault::MissingSignature),
+                    1 => Some(Fault::WrongMint),
+                    2 => Some(Fault::WrongMaker),
+                    3 => Some(Fault::Truncated(usize::from(input.byte()) % 25)),
+                    4 => Some(Fault::TrailingByte(input.byte())),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+            steps.push(Step { action, fault });
+        }
+
+        Self {
+            decimals,
+            maker_a,
+            taker_b,
+            taker_has_ata_a: flags & 1 != 0,
+            maker_has_ata_b: flags & 2 != 0,
+            steps,
+        }
+    }
+}
+
+/// 种子测试用的字节流（splitmix64），同一个种子总是得到同一个场景
+pub fn seeded_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    let mut next = move || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+    (0..len.div_ceil(8))
+        .flat_map(|_| next().to_le_bytes())
+        .take(len)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_input_is_a_valid_scenario() {
+        let scenario = Scenario::generate(&[], true);
+        assert!(scenario.steps.is_empty());
+        assert_eq!(scenario.maker_a, 0);
+    }
+
+    #[test]
+    fn generation_is_deterministic_and_respects_fault_switch() {
+        let bytes = seeded_bytes(7, 128);
+        assert_eq!(
+            Scenario::generate(&bytes, true),
+            Scenario::generate(&bytes, true)
+        );
+        assert!(!Scenario::generate(&bytes, true).steps.is_empty());
+
+        for seed in 0..64 {
+            let scenario = Scenario::generate(&seeded_bytes(seed, 128), false);
+            assert!(scenario.steps.len() <= MAX_STEPS);
+            assert!(scenario.steps.iter().all(|step| step.fault.is_none()));
+        }
+    }
+}
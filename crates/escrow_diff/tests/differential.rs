@@ -0,0 +1,50 @@
+//! 固定种子的差分测试：无故障与带故障各跑一批场景，任何分歧都直接失败并打印可复现的场景。
+//! 两边都失败但错误码不同的步骤按 (指令, Anchor, Pinocchio) 汇总后打印，不影响结果。
+//! 需要先构建两个托管程序，见 Cargo.toml 开头的说明。
+
+use std::collections::BTreeMap;
+
+use escrow_diff::{run, seeded_bytes, Action, Scenario};
+
+const CASES: u64 = 256;
+const INPUT_LEN: usize = 96;
+
+fn action_name(action: &Action) -> &'static str {
+    match action {
+        Action::Make { .. } => "make",
+        Action::Take { .. } => "take",
+        Action::Refund { .. } => "refund",
+    }
+}
+
+/// 运行 `CASES` 个场景，返回两边都成功的总步数
+fn run_batch(offset: u64, faults: bool) -> usize {
+    let mut succeeded = 0;
+    let mut mismatches: BTreeMap<String, usize> = BTreeMap::new();
+    for seed in offset..offset + CASES {
+        let scenario = Scenario::generate(&seeded_bytes(seed, INPUT_LEN), faults);
+        let report =
+            run(&scenario).unwrap_or_else(|divergence| panic!("种子 {seed}: {divergence}"));
+        succeeded += report.succeeded;
+        for (step, anchor, pinocchio) in &report.code_mismatches {
+            let key = format!("{}: {anchor} / {pinocchio}", action_name(&step.action));
+            *mismatches.entry(key).or_default() += 1;
+        }
+    }
+    for (key, count) in &mismatches {
+        eprintln!("错误码不同 ×{count} {key}");
+    }
+    succeeded
+}
+
+#[test]
+fn implementations_agree_without_faults() {
+    // 生成规则偏向边界值，但仍应有相当一部分 make / take / refund 成功执行，
+    // 否则说明场景构造有误，比较的只是两边同样的失败
+    assert!(run_batch(0, false) > CASES as usize / 4);
+}
+
+#[test]
+fn implementations_agree_under_faults() {
+    run_batch(1 << 32, true);
+}
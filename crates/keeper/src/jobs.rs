@@ -0,0 +1,233 @@
+//! keeper 执行的任务。
+//!
+//! 每个任务扫描一个程序的账户，找出此刻已经可以执行的指令（[`Crank`]）。
+//! 新的按时间推进的功能（例如托管过期退款）上线后，在这里加一个 [`Job`] 实现，
+//! 再在 `main.rs` 的 `--jobs` 中注册即可。
+
+use anyhow::{Context as _, Result};
+use bootcamp_client::{
+    amm::{AmmConfig, Pool},
+    vault::{stream_address, Stream, STREAM_DISCRIMINATOR},
+};
+use solana_client::{
+    rpc_client::RpcClient, rpc_config::RpcProgramAccountsConfig, rpc_filter::RpcFilterType,
+};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+/// 一条待发送的指令
+pub struct Crank {
+    /// 日志中显示的对象，通常是账户地址
+    pub target: Pubkey,
+    pub instruction: Instruction,
+}
+
+/// 一类按时间推进的指令
+pub trait Job {
+    /// `--jobs` 中使用的名字，也是日志与统计的分组
+    fn name(&self) -> &'static str;
+
+    /// 需要监听的程序：websocket 收到这些程序的账户变化时提前开始下一轮扫描
+    fn program_id(&self) -> Pubkey;
+
+    /// `now`（链上 Clock 的 Unix 时间戳）时可以执行的指令
+    fn scan(&self, client: &RpcClient, now: i64) -> Result<Vec<Crank>>;
+
+    /// keeper 付费钱包之外还需要的签名者（例如池子 authority）
+    fn signer(&self) -> Option<&dyn Signer> {
+        None
+    }
+}
+
+/// 取出 `program_id` 下长度为 `len` 的全部账户
+fn accounts_of_len(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    len: usize,
+) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::DataSize(len as u64)]),
+        ..Default::default()
+    };
+    let accounts = client
+        .get_program_accounts_with_config(program_id, config)
+        .with_context(|| format!("读取 {program_id} 的账户失败"))?;
+    Ok(accounts
+        .into_iter()
+        .map(|(address, account)| (address, account.data))
+        .collect())
+}
+
+/// 原生 AMM（task6）：timelock 到期后执行池子的参数变更提案。
+///
+/// ExecuteUpdate 要求池子 authority 签名，只处理 authority 是 `authority` 的池子；
+/// 其余池子的提案由各自的管理员执行
+pub struct AmmTimelock {
+    pub program_id: Pubkey,
+    pub authority: Keypair,
+}
+
+impl AmmTimelock {
+    /// 从 Config 账户得到可以执行的提案
+    fn crank(&self, address: Pubkey, data: &[u8], now: i64) -> Option<Crank> {
+        let config = AmmConfig::try_from_bytes(data).ok()?;
+        if config.authority != self.authority.pubkey() || !config.update_ready(now) {
+            return None;
+        }
+        let pool = if config.canonical {
+            Pool::canonical(&self.program_id, &config.mint_x, &config.mint_y)
+        } else {
+            Pool::seeded(
+                &self.program_id,
+                config.seed,
+                &config.mint_x,
+                &config.mint_y,
+            )
+        };
+        // 派生不出同一个地址说明不是本程序的双币池（或 mint 顺序被篡改），不去碰它
+        (pool.config == address).then(|| Crank {
+            target: address,
+            instruction: pool.execute_update_ix(&self.authority.pubkey()),
+        })
+    }
+}
+
+impl Job for AmmTimelock {
+    fn name(&self) -> &'static str {
+        "amm-timelock"
+    }
+
+    fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    fn scan(&self, client: &RpcClient, now: i64) -> Result<Vec<Crank>> {
+        let configs = accounts_of_len(client, &self.program_id, AmmConfig::LEN)?;
+        Ok(configs
+            .into_iter()
+            .filter_map(|(address, data)| self.crank(address, &data, now))
+            .collect())
+    }
+
+    fn signer(&self) -> Option<&dyn Signer> {
+        Some(&self.authority)
+    }
+}
+
+/// Anchor 金库（task2）：结算累计金额达到 `min_claim` 的流式支付。
+///
+/// claim_stream 不要求任何特定账户签名，keeper 只支付手续费；金库仍在锁定期内时
+/// 模拟即失败，这笔交易不会发出
+pub struct VaultStream {
+    pub program_id: Pubkey,
+    /// 累计不足这么多 lamports 的流留到以后的轮次，免得手续费比支付的金额还高
+    pub min_claim: u64,
+}
+
+impl VaultStream {
+    fn crank(&self, address: Pubkey, data: &[u8], now: i64) -> Option<Crank> {
+        if !data.starts_with(&STREAM_DISCRIMINATOR) {
+            return None;
+        }
+        let stream = Stream::try_from_bytes(data).ok()?;
+        if stream.accrued(now) < self.min_claim.max(1) {
+            return None;
+        }
+        // owner 派生不出这个地址就不是本程序开启的流
+        (stream_address(&self.program_id, &stream.owner).0 == address).then(|| Crank {
+            target: address,
+            instruction: stream.claim_ix(&self.program_id),
+        })
+    }
+}
+
+impl Job for VaultStream {
+    fn name(&self) -> &'static str {
+        "vault-stream"
+    }
+
+    fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    fn scan(&self, client: &RpcClient, now: i64) -> Result<Vec<Crank>> {
+        let streams = accounts_of_len(client, &self.program_id, Stream::LEN)?;
+        Ok(streams
+            .into_iter()
+            .filter_map(|(address, data)| self.crank(address, &data, now))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bootcamp_client::amm::PROGRAM_ID;
+
+    use super::*;
+
+    /// 按客户端解析的偏移写出一个带提案的规范池 Config
+    fn config(pool: &Pool, authority: &Pubkey, eta: i64) -> Vec<u8> {
+        let mut data = vec![0u8; AmmConfig::LEN];
+        data[0] = 1;
+        data[9..41].copy_from_slice(authority.as_ref());
+        data[41..73].copy_from_slice(pool.mint_x.as_ref());
+        data[73..105].copy_from_slice(pool.mint_y.as_ref());
+        data[110] = 1;
+        data[119] = 1;
+        data[120..128].copy_from_slice(&eta.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn amm_timelock_waits_for_eta_and_own_pools() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mint_x, mint_y) = if a < b { (a, b) } else { (b, a) };
+        let pool = Pool::canonical(&PROGRAM_ID, &mint_x, &mint_y);
+        let job = AmmTimelock {
+            program_id: PROGRAM_ID,
+            authority: Keypair::new(),
+        };
+        let authority = job.authority.pubkey();
+        let data = config(&pool, &authority, 1_000);
+
+        assert!(job.crank(pool.config, &data, 999).is_none());
+        let crank = job.crank(pool.config, &data, 1_000).unwrap();
+        assert_eq!(crank.instruction, pool.execute_update_ix(&authority));
+
+        // 别人的池子、地址对不上的账户都跳过
+        let other = config(&pool, &Pubkey::new_unique(), 1_000);
+        assert!(job.crank(pool.config, &other, 1_000).is_none());
+        assert!(job.crank(Pubkey::new_unique(), &data, 1_000).is_none());
+    }
+
+    #[test]
+    fn vault_stream_waits_for_min_claim() {
+        let program_id = bootcamp_client::vault::PROGRAM_ID;
+        let (owner, recipient) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let address = stream_address(&program_id, &owner).0;
+        let mut data = STREAM_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(recipient.as_ref());
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&100i64.to_le_bytes());
+        let job = VaultStream {
+            program_id,
+            min_claim: 10_000,
+        };
+
+        assert!(job.crank(address, &data, 109).is_none());
+        let crank = job.crank(address, &data, 110).unwrap();
+        assert_eq!(
+            crank.instruction,
+            bootcamp_client::vault::claim_stream_ix(&program_id, &owner, &recipient)
+        );
+
+        // 地址与 owner 对不上、判别器不对的账户都跳过
+        assert!(job.crank(Pubkey::new_unique(), &data, 110).is_none());
+        data[0] ^= 1;
+        assert!(job.crank(address, &data, 110).is_none());
+    }
+}
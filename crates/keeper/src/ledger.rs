@@ -0,0 +1,111 @@
+//! 按任务统计 keeper 的开销：发送 / 失败次数、交易费与小费。
+//!
+//! 交易费取每次发送前后付费钱包余额之差再减去小费，已经包含优先费与签名费；
+//! 失败的交易如果上了链同样扣费，也计入。
+
+use std::{collections::BTreeMap, fmt};
+
+/// 单个任务的累计
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Tally {
+    pub sent: u64,
+    pub failed: u64,
+    pub fees: u64,
+    pub tips: u64,
+}
+
+impl Tally {
+    /// 平均每笔成功交易的开销（lamports），没有成功交易时为 0
+    pub fn cost_per_crank(&self) -> u64 {
+        (self.fees + self.tips).checked_div(self.sent).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Ledger {
+    jobs: BTreeMap<&'static str, Tally>,
+}
+
+impl Ledger {
+    /// 记录一次发送。`balance_before` / `balance_after` 是付费钱包在发送前后的余额，
+    /// `tip` 是这笔交易附带转给小费账户的 lamports
+    pub fn record(
+        &mut self,
+        job: &'static str,
+        ok: bool,
+        balance_before: u64,
+        balance_after: u64,
+        tip: u64,
+    ) {
+        let tally = self.jobs.entry(job).or_default();
+        let spent = balance_before.saturating_sub(balance_after);
+        // 失败的交易没有执行小费转账
+        let tip = if ok { tip.min(spent) } else { 0 };
+        if ok {
+            tally.sent += 1;
+        } else {
+            tally.failed += 1;
+        }
+        tally.fees += spent - tip;
+        tally.tips += tip;
+    }
+
+    pub fn total(&self) -> Tally {
+        self.jobs
+            .values()
+            .fold(Tally::default(), |total, tally| Tally {
+                sent: total.sent + tally.sent,
+                failed: total.failed + tally.failed,
+                fees: total.fees + tally.fees,
+                tips: total.tips + tally.tips,
+            })
+    }
+}
+
+/// 每个任务一行，最后一行是合计
+impl fmt::Display for Ledger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.jobs.iter().map(|(job, tally)| (*job, *tally));
+        for (job, tally) in rows.chain([("合计", self.total())]) {
+            writeln!(
+                f,
+                "{job:<16} 成功 {:>4}  失败 {:>4}  交易费 {:>10}  小费 {:>10}  每笔 {:>8}",
+                tally.sent,
+                tally.failed,
+                tally.fees,
+                tally.tips,
+                tally.cost_per_crank()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_balance_change_into_fees_and_tips() {
+        let mut ledger = Ledger::default();
+        ledger.record("amm-timelock", true, 1_000_000, 989_000, 10_000);
+        ledger.record("amm-timelock", true, 989_000, 978_000, 10_000);
+        // 失败但上了链：只扣交易费，没有小费
+        ledger.record("amm-timelock", false, 978_000, 973_000, 10_000);
+        // 余额中途被充值时不会算出负数
+        ledger.record("other", true, 100, 5_000, 0);
+
+        assert_eq!(
+            ledger.jobs["amm-timelock"],
+            Tally {
+                sent: 2,
+                failed: 1,
+                fees: 7_000,
+                tips: 20_000,
+            }
+        );
+        assert_eq!(ledger.jobs["amm-timelock"].cost_per_crank(), 13_500);
+        assert_eq!(ledger.total().sent, 3);
+        assert_eq!(ledger.to_string().lines().count(), 3);
+    }
+}
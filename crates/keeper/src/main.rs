@@ -0,0 +1,236 @@
+//! keeper：定期扫描训练营程序的账户，把已经到期的指令发上链。
+//!
+//! ```text
+//! keeper --jobs amm-timelock --amm-authority ~/.config/solana/amm-admin.json
+//! keeper --jobs amm-timelock,vault-stream --stream-min-claim 5000000
+//! keeper --ws-url ws://127.0.0.1:8900 --tip 10000 --tip-account <ADDRESS>
+//! ```
+//!
+//! 每隔 `--interval` 秒扫描一轮；指定 `--ws-url` 时还会订阅各任务程序的账户变化，
+//! 收到通知后立刻开始下一轮。是否到期以链上 Clock 为准，不看本机时间。
+//! 发送过交易的一轮结束后打印各任务的交易费与小费累计（见 `ledger.rs`）。
+
+mod jobs;
+mod ledger;
+mod watch;
+
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use bootcamp_client::tx::{self, PriorityFee, TxBuilder};
+use clap::Parser;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    system_instruction, sysvar,
+};
+
+use crate::{
+    jobs::{AmmTimelock, Job, VaultStream},
+    ledger::Ledger,
+};
+
+/// Clock sysvar 中 unix_timestamp 的偏移：slot、epoch_start_timestamp、epoch、leader_schedule_epoch 之后
+const CLOCK_UNIX_TIMESTAMP_OFFSET: usize = 32;
+
+#[derive(Parser)]
+#[command(name = "keeper", version, about = "执行训练营程序中到期的指令")]
+struct Cli {
+    /// RPC 地址
+    #[arg(long, env = "KEEPER_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// websocket 地址；不指定时只按 --interval 轮询
+    #[arg(long, env = "KEEPER_WS_URL")]
+    ws_url: Option<String>,
+
+    /// 付费钱包（默认 ~/.config/solana/id.json）
+    #[arg(long, env = "KEEPER_KEYPAIR")]
+    keypair: Option<String>,
+
+    /// 要执行的任务，逗号分隔
+    #[arg(long, value_delimiter = ',', default_value = "amm-timelock")]
+    jobs: Vec<String>,
+
+    /// 两轮扫描之间的最长间隔（秒）
+    #[arg(long, default_value_t = 30)]
+    interval: u64,
+
+    /// 只扫描一轮就退出
+    #[arg(long)]
+    once: bool,
+
+    /// 只打印到期的指令，不发送
+    #[arg(long)]
+    dry_run: bool,
+
+    /// 优先费（micro-lamports / CU）；不指定时取写入账户最近优先费的 75 分位
+    #[arg(long, env = "KEEPER_PRIORITY_FEE")]
+    priority_fee: Option<u64>,
+
+    /// 每笔交易附带转给 --tip-account 的 lamports
+    #[arg(long, default_value_t = 0, requires = "tip_account")]
+    tip: u64,
+
+    /// 小费收款地址（例如区块引擎公布的小费账户）
+    #[arg(long)]
+    tip_account: Option<Pubkey>,
+
+    /// amm 程序 ID
+    #[arg(long, default_value_t = bootcamp_client::amm::PROGRAM_ID)]
+    amm_program: Pubkey,
+
+    /// amm-timelock 使用的池子 authority 钱包，默认与付费钱包相同
+    #[arg(long, env = "KEEPER_AMM_AUTHORITY")]
+    amm_authority: Option<String>,
+
+    /// Anchor 金库程序 ID
+    #[arg(long, default_value_t = bootcamp_client::vault::PROGRAM_ID)]
+    vault_program: Pubkey,
+
+    /// vault-stream 只结算累计达到这么多 lamports 的流
+    #[arg(long, default_value_t = 1_000_000)]
+    stream_min_claim: u64,
+}
+
+fn keypair(path: Option<&str>) -> Result<Keypair> {
+    let path = match path {
+        Some(path) => path.to_string(),
+        None => {
+            let home = std::env::var("HOME").context("未设置 HOME，请用 --keypair 指定钱包")?;
+            format!("{home}/.config/solana/id.json")
+        }
+    };
+    read_keypair_file(&path).map_err(|e| anyhow!("读取钱包 {path} 失败: {e}"))
+}
+
+fn build_jobs(cli: &Cli) -> Result<Vec<Box<dyn Job>>> {
+    cli.jobs
+        .iter()
+        .map(|name| -> Result<Box<dyn Job>> {
+            match name.as_str() {
+                "amm-timelock" => Ok(Box::new(AmmTimelock {
+                    program_id: cli.amm_program,
+                    authority: keypair(cli.amm_authority.as_deref().or(cli.keypair.as_deref()))?,
+                })),
+                "vault-stream" => Ok(Box::new(VaultStream {
+                    program_id: cli.vault_program,
+                    min_claim: cli.stream_min_claim,
+                })),
+                other => bail!("未知任务 {other}，可选：amm-timelock、vault-stream"),
+            }
+        })
+        .collect()
+}
+
+/// 链上 Clock 的 Unix 时间戳
+fn chain_time(client: &RpcClient) -> Result<i64> {
+    let data = client
+        .get_account_data(&sysvar::clock::id())
+        .context("读取 Clock 失败")?;
+    let bytes = data
+        .get(CLOCK_UNIX_TIMESTAMP_OFFSET..CLOCK_UNIX_TIMESTAMP_OFFSET + 8)
+        .ok_or_else(|| anyhow!("Clock 数据长度不足"))?;
+    Ok(i64::from_le_bytes(bytes.try_into()?))
+}
+
+struct Keeper {
+    client: RpcClient,
+    payer: Keypair,
+    priority_fee: PriorityFee,
+    tip: Option<(Pubkey, u64)>,
+    dry_run: bool,
+    ledger: Ledger,
+}
+
+impl Keeper {
+    /// 扫描并发送一轮，返回发送的交易数；单个任务或单笔交易失败只打印，不影响其余任务
+    fn round(&mut self, jobs: &[Box<dyn Job>]) -> Result<usize> {
+        let now = chain_time(&self.client)?;
+        let mut sent = 0;
+        for job in jobs {
+            let cranks = match job.scan(&self.client, now) {
+                Ok(cranks) => cranks,
+                Err(err) => {
+                    eprintln!("[{}] 扫描失败: {err:#}", job.name());
+                    continue;
+                }
+            };
+            for crank in cranks {
+                if self.dry_run {
+                    println!("[{}] {} 已到期", job.name(), crank.target);
+                    continue;
+                }
+                let mut ixs = vec![crank.instruction];
+                if let Some((account, lamports)) = self.tip {
+                    ixs.push(system_instruction::transfer(
+                        &self.payer.pubkey(),
+                        &account,
+                        lamports,
+                    ));
+                }
+                let before = self.client.get_balance(&self.payer.pubkey())?;
+                let mut builder = TxBuilder::new(&self.client, &self.payer)
+                    .instructions(ixs)
+                    .priority_fee(self.priority_fee);
+                if let Some(signer) = job.signer() {
+                    builder = builder.signer(signer);
+                }
+                let result = builder.send();
+                sent += 1;
+                let after = self.client.get_balance(&self.payer.pubkey())?;
+                let tip = self.tip.map_or(0, |(_, lamports)| lamports);
+                self.ledger
+                    .record(job.name(), result.is_ok(), before, after, tip);
+                match result {
+                    Ok(signature) => println!("[{}] {} {signature}", job.name(), crank.target),
+                    Err(err) => {
+                        let reason = tx::failure(&err)
+                            .map_or_else(|| err.to_string(), |failure| failure.to_string());
+                        eprintln!("[{}] {} 失败: {reason}", job.name(), crank.target);
+                    }
+                }
+            }
+        }
+        Ok(sent)
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let jobs = build_jobs(&cli)?;
+    let mut keeper = Keeper {
+        client: RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed()),
+        payer: keypair(cli.keypair.as_deref())?,
+        priority_fee: cli
+            .priority_fee
+            .map_or(PriorityFee::default(), PriorityFee::Fixed),
+        tip: cli.tip_account.map(|account| (account, cli.tip)),
+        dry_run: cli.dry_run,
+        ledger: Ledger::default(),
+    };
+
+    let programs: Vec<Pubkey> = jobs.iter().map(|job| job.program_id()).collect();
+    let wake = match &cli.ws_url {
+        Some(url) => Some(watch::subscribe(url, &programs)?),
+        None => None,
+    };
+    let interval = Duration::from_secs(cli.interval);
+    loop {
+        match keeper.round(&jobs) {
+            // 有新交易时打印一次累计
+            Ok(sent) if sent > 0 => print!("{}", keeper.ledger),
+            Ok(_) => {}
+            Err(err) => eprintln!("本轮扫描失败: {err:#}"),
+        }
+        if cli.once {
+            return Ok(());
+        }
+        match &wake {
+            Some(wake) => wake.wait(interval),
+            None => std::thread::sleep(interval),
+        }
+    }
+}
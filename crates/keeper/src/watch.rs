@@ -0,0 +1,54 @@
+//! websocket 订阅：任务程序的账户发生变化时唤醒主循环，提前开始下一轮扫描。
+//!
+//! 只用通知作为触发，不解析推送的账户内容；每轮仍然通过 RPC 完整扫描，
+//! 漏掉通知或连接断开时退化为按间隔轮询。
+
+use std::{
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{Context as _, Result};
+use solana_client::pubsub_client::PubsubClient;
+use solana_sdk::pubkey::Pubkey;
+
+pub struct Wake {
+    receiver: Receiver<()>,
+}
+
+impl Wake {
+    /// 等到下一次通知或超时；期间积压的多条通知合并成一次唤醒
+    pub fn wait(&self, timeout: Duration) {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(()) => while self.receiver.try_recv().is_ok() {},
+            // 订阅线程都已退出：继续按间隔轮询
+            Err(RecvTimeoutError::Disconnected) => thread::sleep(timeout),
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+    }
+}
+
+/// 为每个程序开一个 `programSubscribe` 订阅
+pub fn subscribe(url: &str, programs: &[Pubkey]) -> Result<Wake> {
+    let (sender, receiver) = mpsc::channel();
+    let mut programs = programs.to_vec();
+    programs.sort();
+    programs.dedup();
+    for program in programs {
+        let (subscription, notifications) = PubsubClient::program_subscribe(url, &program, None)
+            .with_context(|| format!("订阅 {program} 失败"))?;
+        let sender = sender.clone();
+        thread::spawn(move || {
+            // 订阅对象离开作用域会取消订阅，随线程一直保留
+            let _subscription = subscription;
+            while notifications.recv().is_ok() {
+                if sender.send(()).is_err() {
+                    break;
+                }
+            }
+            eprintln!("{program} 的订阅已断开，改为按间隔轮询");
+        });
+    }
+    Ok(Wake { receiver })
+}
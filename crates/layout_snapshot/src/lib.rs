@@ -0,0 +1,149 @@
+//! 链上账户布局的黄金快照比对。
+//!
+//! 已部署的账户不会随程序升级迁移：字段换了顺序、改了宽度，旧账户照样按新布局读取，
+//! 数据就悄悄坏了。每个状态账户提交一份快照，测试用已知取值写出账户数据后与之逐字段比对，
+//! 布局的任何变动都会让 `cargo test` 失败，必须同时改快照（以及迁移方案）。
+//!
+//! 快照是纯文本，每行一个字段，偏移量写明，diff 时一眼能看出哪个字段动了：
+//!
+//! ```text
+//! # 注释与空行忽略
+//! # offset  field    little-endian hex
+//! 0         seed     0102030405060708
+//! 8         maker    1111111111111111111111111111111111111111111111111111111111111111
+//! ```
+//!
+//! 字段必须从 0 开始首尾相接，最后一个字段的结尾就是账户长度。
+//!
+//! 金库（task2 / task4）目前没有状态账户，PDA 只持有 lamports；
+//! 以后加入 `VaultState` 时照同样方式提交快照即可。
+
+use std::fmt::Write;
+
+/// 快照中的一个字段
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Field {
+    pub offset: usize,
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+/// 解析快照；格式错误或字段不连续时 panic，指出行号
+pub fn parse(snapshot: &str) -> Vec<Field> {
+    let mut fields: Vec<Field> = Vec::new();
+    let mut end = 0;
+    for (line_no, line) in snapshot.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line_no = line_no + 1;
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let [offset, name, hex] = parts[..] else {
+            panic!("快照第 {line_no} 行应为 `<offset> <field> <hex>`: {line}");
+        };
+        let offset: usize = offset
+            .parse()
+            .unwrap_or_else(|_| panic!("快照第 {line_no} 行偏移量不是十进制整数: {offset}"));
+        assert_eq!(
+            offset, end,
+            "快照第 {line_no} 行 `{name}` 的偏移量应为 {end}（紧接上一个字段）"
+        );
+        let bytes = decode_hex(hex).unwrap_or_else(|| {
+            panic!("快照第 {line_no} 行 `{name}` 不是偶数长度的十六进制: {hex}")
+        });
+        end = offset + bytes.len();
+        fields.push(Field {
+            offset,
+            name: name.to_string(),
+            bytes,
+        });
+    }
+    fields
+}
+
+/// 快照描述的完整账户数据
+pub fn snapshot_bytes(snapshot: &str) -> Vec<u8> {
+    parse(snapshot)
+        .into_iter()
+        .flat_map(|field| field.bytes)
+        .collect()
+}
+
+/// `actual` 与快照逐字段比对，列出所有不一致的字段后 panic
+pub fn assert_layout(actual: &[u8], snapshot: &str) {
+    let fields = parse(snapshot);
+    let expected_len = fields
+        .last()
+        .map_or(0, |field| field.offset + field.bytes.len());
+
+    let mut report = String::new();
+    if actual.len() != expected_len {
+        writeln!(report, "长度 {}，快照为 {expected_len}", actual.len()).unwrap();
+    }
+    for field in &fields {
+        let range = field.offset..field.offset + field.bytes.len();
+        let got = actual.get(range.clone()).unwrap_or(&[]);
+        if got != field.bytes {
+            writeln!(
+                report,
+                "{:?} {}: 期望 {}，实际 {}",
+                range,
+                field.name,
+                encode_hex(&field.bytes),
+                encode_hex(got)
+            )
+            .unwrap();
+        }
+    }
+    assert!(report.is_empty(), "账户布局与快照不一致:\n{report}");
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNAPSHOT: &str = "
+        # 示例
+        0 seed 0102
+        2 bump fe
+    ";
+
+    #[test]
+    fn matching_layout_passes() {
+        assert_eq!(snapshot_bytes(SNAPSHOT), [1, 2, 0xfe]);
+        assert_layout(&[1, 2, 0xfe], SNAPSHOT);
+    }
+
+    #[test]
+    #[should_panic(expected = "0..2 seed: 期望 0102，实际 0201")]
+    fn swapped_bytes_name_the_field() {
+        assert_layout(&[2, 1, 0xfe], SNAPSHOT);
+    }
+
+    #[test]
+    #[should_panic(expected = "长度 4，快照为 3")]
+    fn size_change_fails() {
+        assert_layout(&[1, 2, 0xfe, 0], SNAPSHOT);
+    }
+
+    #[test]
+    #[should_panic(expected = "偏移量应为 2")]
+    fn gaps_are_rejected() {
+        parse("0 seed 0102\n3 bump fe");
+    }
+}
@@ -0,0 +1,120 @@
+//! 创世账户：按 `solana account --output json` 的格式写出，由 `--account <地址> <文件>` 加载。
+//!
+//! 钱包与代币账户直接写进创世状态，节点启动后立即可用，不需要空投，也不需要为建 mint、
+//! 建 ATA、铸币逐笔发交易。代币账户布局来自 token_fixtures，与 LiteSVM / Mollusk 夹具一致。
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk_ids::system_program;
+use token_fixtures::{
+    ata_address, mint_data, rent_exempt, token_account_data, TokenProgram, TOKEN_PROGRAM_ID,
+};
+
+/// 一个预装账户
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GenesisAccount {
+    pub address: Pubkey,
+    pub lamports: u64,
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+}
+
+impl GenesisAccount {
+    /// 只有 lamports 的系统账户
+    pub fn wallet(address: Pubkey, lamports: u64) -> Self {
+        Self {
+            address,
+            lamports,
+            owner: system_program::ID,
+            data: Vec::new(),
+        }
+    }
+
+    /// 归 SPL Token 所有、刚好免租的账户
+    fn token_owned(address: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            address,
+            lamports: rent_exempt(data.len()),
+            owner: Pubkey::new_from_array(TOKEN_PROGRAM_ID),
+            data,
+        }
+    }
+
+    /// `solana-test-validator --account` 接受的 JSON
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"pubkey":"{}","account":{{"lamports":{},"data":["{}","base64"],"owner":"{}","executable":false,"rentEpoch":0,"space":{}}}}}"#,
+            self.address,
+            self.lamports,
+            BASE64_STANDARD.encode(&self.data),
+            self.owner,
+            self.data.len()
+        )
+    }
+}
+
+/// `mint`（铸币权限 `authority`）以及每个 `holders` 持有 `balance` 的 ATA，mint 供应量等于余额之和
+pub fn token_accounts(
+    mint: &Pubkey,
+    authority: &Pubkey,
+    decimals: u8,
+    holders: &[Pubkey],
+    balance: u64,
+) -> Vec<GenesisAccount> {
+    let supply = balance * holders.len() as u64;
+    let mint_bytes = mint.to_bytes();
+    let mut accounts = vec![GenesisAccount::token_owned(
+        *mint,
+        mint_data(&authority.to_bytes(), supply, decimals),
+    )];
+    for holder in holders {
+        let holder = holder.to_bytes();
+        let ata = ata_address(&holder, &mint_bytes, TokenProgram::Token);
+        accounts.push(GenesisAccount::token_owned(
+            Pubkey::new_from_array(ata),
+            token_account_data(&mint_bytes, &holder, balance, TokenProgram::Token),
+        ));
+    }
+    accounts
+}
+
+#[cfg(test)]
+mod tests {
+    use bootcamp_client::token::{ata, TOKEN_PROGRAM_ID};
+    use token_fixtures::{mint_supply, token_amount};
+
+    use super::*;
+
+    #[test]
+    fn token_accounts_match_client_addresses_and_supply() {
+        let (mint, authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let holders = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let accounts = token_accounts(&mint, &authority, 6, &holders, 500);
+
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(accounts[0].address, mint);
+        assert_eq!(mint_supply(&accounts[0].data), 1_000);
+        for (holder, account) in holders.iter().zip(&accounts[1..]) {
+            assert_eq!(account.address, ata(holder, &mint));
+            assert_eq!(account.owner, TOKEN_PROGRAM_ID);
+            assert_eq!(token_amount(&account.data), 500);
+        }
+    }
+
+    #[test]
+    fn json_carries_base64_data() {
+        let account = GenesisAccount {
+            address: Pubkey::new_unique(),
+            lamports: 42,
+            owner: Pubkey::new_unique(),
+            data: vec![1, 2, 3],
+        };
+        let json = account.to_json();
+        assert!(json.contains(r#""data":["AQID","base64"]"#));
+        assert!(json.contains(r#""lamports":42"#));
+        assert!(json.contains(&format!(r#""owner":"{}""#, account.owner)));
+        assert!(GenesisAccount::wallet(account.address, 1)
+            .to_json()
+            .contains(r#""data":["","base64"],"owner":"11111111111111111111111111111111""#));
+    }
+}
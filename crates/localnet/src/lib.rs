@@ -0,0 +1,443 @@
+//! 本地验证节点夹具：启动 `solana-test-validator`，预装训练营程序、测试钱包与代币。
+//!
+//! ```no_run
+//! # use localnet::Localnet;
+//! let localnet = Localnet::builder().wallets(2).mints(2).start()?;
+//! let (alice, mint) = (&localnet.wallets[1], localnet.mints[0]);
+//! // 各钱包已有 100 SOL，并在每个 mint 上持有 1_000_000_000 个代币
+//! # Ok::<(), localnet::Error>(())
+//! ```
+//!
+//! 程序以 `--upgradeable-program` 加载到声明的地址，升级权限是第一个钱包（[`Localnet::payer`]），
+//! 国库 Initialize 等要求升级权限签名的指令可以直接用它。金库、托管与原生 AMM 都声明占位地址
+//! 2222…2222，同一个节点上只能装其中一个：默认是 Anchor 金库，用 [`Builder::program`]
+//! 传入 [`PINOCCHIO_ESCROW`] 或 [`NATIVE_AMM`] 替换。
+//!
+//! 节点在 [`Localnet`] 离开作用域时结束。默认每个实例用独立的临时目录与空闲端口，
+//! 多个测试可以并行；日志写在 `<dir>/validator.log`。
+
+mod genesis;
+
+use std::{
+    fmt, fs,
+    net::TcpListener,
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use bootcamp_client::{
+    amm, escrow, governance, oracle, treasury,
+    tx::{PriorityFee, TxBuilder},
+    vault,
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{write_keypair_file, Keypair, Signer},
+};
+
+pub use crate::genesis::{token_accounts, GenesisAccount};
+
+pub const SOL: u64 = 1_000_000_000;
+
+/// `cargo run -p localnet` 与 `xcli --localnet` 使用的 RPC 端口（websocket 为下一个端口）
+pub const DEFAULT_RPC_PORT: u16 = 8899;
+
+/// `cargo run -p localnet` 与 `xcli --localnet` 使用的工作目录
+pub const DEFAULT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/localnet");
+
+/// 一个已构建的程序：名字与 `cargo xtask` 一致
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Program {
+    pub name: &'static str,
+    pub id: Pubkey,
+    pub so: &'static str,
+}
+
+macro_rules! deploy {
+    ($dir:literal, $name:literal) => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../",
+            $dir,
+            "/target/deploy/",
+            $name,
+            ".so"
+        )
+    };
+}
+
+pub const ANCHOR_VAULT: Program = Program {
+    name: "anchor-vault",
+    id: vault::PROGRAM_ID,
+    so: deploy!("task2/blueshift_anchor_vault", "blueshift_anchor_vault"),
+};
+pub const PINOCCHIO_ESCROW: Program = Program {
+    name: "escrow",
+    id: escrow::PROGRAM_ID,
+    so: deploy!("task5/blueshift_escrow", "blueshift_escrow"),
+};
+pub const NATIVE_AMM: Program = Program {
+    name: "amm",
+    id: amm::PROGRAM_ID,
+    so: deploy!(
+        "task6/solana-pinocchio-amm-workshop-main/blueshift_native_amm",
+        "blueshift_native_amm"
+    ),
+};
+pub const ROUTER: Program = Program {
+    name: "router",
+    id: bootcamp_client::router::PROGRAM_ID,
+    so: deploy!("programs/blueshift_router", "blueshift_router"),
+};
+pub const GOVERNANCE: Program = Program {
+    name: "governance",
+    id: governance::PROGRAM_ID,
+    so: deploy!("programs/blueshift_governance", "blueshift_governance"),
+};
+pub const TREASURY: Program = Program {
+    name: "treasury",
+    id: treasury::PROGRAM_ID,
+    so: deploy!("programs/blueshift_treasury", "blueshift_treasury"),
+};
+pub const MOCK_ORACLE: Program = Program {
+    name: "mock-oracle",
+    id: oracle::PROGRAM_ID,
+    so: deploy!("programs/blueshift_mock_oracle", "blueshift_mock_oracle"),
+};
+
+/// 可以加载的全部程序；前三个共用占位地址
+pub const PROGRAMS: &[Program] = &[
+    ANCHOR_VAULT,
+    PINOCCHIO_ESCROW,
+    NATIVE_AMM,
+    ROUTER,
+    GOVERNANCE,
+    TREASURY,
+    MOCK_ORACLE,
+];
+
+/// 第 `index` 个测试钱包的密钥文件，0 号是付费钱包与程序升级权限
+pub fn wallet_path(dir: &Path, index: usize) -> PathBuf {
+    dir.join("wallets").join(format!("{index}.json"))
+}
+
+/// 启动失败的原因
+#[derive(Debug)]
+pub enum Error {
+    /// 程序还没有构建
+    MissingProgram {
+        name: &'static str,
+        so: &'static str,
+    },
+    /// 写入工作目录失败，或找不到 `solana-test-validator`
+    Io(std::io::Error),
+    /// 节点启动后退出
+    Exited { status: ExitStatus, log: PathBuf },
+    /// 超时仍未能通过 RPC 取到 blockhash
+    Timeout { log: PathBuf },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingProgram { name, so } => {
+                write!(f, "找不到 {so}，请先执行 cargo xtask build -p {name}")
+            }
+            Self::Io(err) => write!(f, "启动 solana-test-validator 失败: {err}"),
+            Self::Exited { status, log } => {
+                write!(
+                    f,
+                    "solana-test-validator 已退出（{status}），见 {}",
+                    log.display()
+                )
+            }
+            Self::Timeout { log } => {
+                write!(f, "solana-test-validator 未能就绪，见 {}", log.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// 启动参数，由 [`Localnet::builder`] 创建
+pub struct Builder {
+    dir: Option<PathBuf>,
+    rpc_port: Option<u16>,
+    programs: Vec<Program>,
+    wallets: usize,
+    lamports: u64,
+    mints: usize,
+    decimals: u8,
+    token_balance: u64,
+    accounts: Vec<GenesisAccount>,
+    timeout: Duration,
+}
+
+impl Builder {
+    /// 工作目录：账本、钱包密钥、创世账户与日志。默认在系统临时目录下新建
+    pub fn dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// RPC 端口，websocket 与 faucet 依次使用后两个端口。默认取空闲端口
+    pub fn rpc_port(mut self, port: u16) -> Self {
+        self.rpc_port = Some(port);
+        self
+    }
+
+    /// 加载 `program`，替换同一地址上已有的程序
+    pub fn program(mut self, program: Program) -> Self {
+        self.programs.retain(|loaded| loaded.id != program.id);
+        self.programs.push(program);
+        self
+    }
+
+    /// 只加载 `programs`
+    pub fn programs(mut self, programs: impl IntoIterator<Item = Program>) -> Self {
+        self.programs.clear();
+        programs
+            .into_iter()
+            .fold(self, |builder, program| builder.program(program))
+    }
+
+    /// 测试钱包个数（默认 3，至少 1 个）
+    pub fn wallets(mut self, count: usize) -> Self {
+        self.wallets = count.max(1);
+        self
+    }
+
+    /// 每个钱包的 lamports（默认 100 SOL）
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.lamports = lamports;
+        self
+    }
+
+    /// 测试 mint 个数（默认 2），铸币权限是付费钱包
+    pub fn mints(mut self, count: usize) -> Self {
+        self.mints = count;
+        self
+    }
+
+    /// 测试 mint 的小数位（默认 6）
+    pub fn decimals(mut self, decimals: u8) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// 每个钱包在每个 mint 上的 ATA 余额（默认 1_000_000_000）
+    pub fn token_balance(mut self, amount: u64) -> Self {
+        self.token_balance = amount;
+        self
+    }
+
+    /// 额外的创世账户（例如预先写好的价格账户）
+    pub fn account(mut self, account: GenesisAccount) -> Self {
+        self.accounts.push(account);
+        self
+    }
+
+    /// 等待节点就绪的最长时间（默认 60 秒）
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn start(self) -> Result<Localnet, Error> {
+        for program in &self.programs {
+            if !Path::new(program.so).exists() {
+                return Err(Error::MissingProgram {
+                    name: program.name,
+                    so: program.so,
+                });
+            }
+        }
+        let dir = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => temp_dir(),
+        };
+        let rpc_port = match self.rpc_port {
+            Some(port) => port,
+            None => free_port()?,
+        };
+
+        // 账本目录每次 --reset，钱包与创世账户放在它旁边
+        let accounts_dir = dir.join("accounts");
+        fs::create_dir_all(&accounts_dir)?;
+        fs::create_dir_all(dir.join("wallets"))?;
+
+        let wallets: Vec<Keypair> = (0..self.wallets).map(|_| Keypair::new()).collect();
+        for (index, wallet) in wallets.iter().enumerate() {
+            write_keypair_file(wallet, wallet_path(&dir, index))
+                .map_err(|err| std::io::Error::other(err.to_string()))?;
+        }
+        let holders: Vec<Pubkey> = wallets.iter().map(Signer::pubkey).collect();
+        // 按地址排序，前两个可以直接作为规范池的 mint_x / mint_y
+        let mut mints: Vec<Pubkey> = (0..self.mints).map(|_| Keypair::new().pubkey()).collect();
+        mints.sort();
+
+        let mut accounts: Vec<GenesisAccount> = holders
+            .iter()
+            .map(|holder| GenesisAccount::wallet(*holder, self.lamports))
+            .collect();
+        for mint in &mints {
+            accounts.extend(token_accounts(
+                mint,
+                &holders[0],
+                self.decimals,
+                &holders,
+                self.token_balance,
+            ));
+        }
+        accounts.extend(self.accounts);
+
+        let mut command = Command::new("solana-test-validator");
+        command
+            .arg("--reset")
+            .arg("--quiet")
+            .arg("--ledger")
+            .arg(dir.join("ledger"))
+            .args(["--bind-address", "127.0.0.1"])
+            .args(["--rpc-port", &rpc_port.to_string()])
+            .args(["--faucet-port", &(rpc_port + 2).to_string()]);
+        for program in &self.programs {
+            command
+                .arg("--upgradeable-program")
+                .arg(program.id.to_string())
+                .arg(program.so)
+                .arg(holders[0].to_string());
+        }
+        for account in &accounts {
+            let path = accounts_dir.join(format!("{}.json", account.address));
+            fs::write(&path, account.to_json())?;
+            command
+                .arg("--account")
+                .arg(account.address.to_string())
+                .arg(path);
+        }
+
+        let log_path = dir.join("validator.log");
+        let log = fs::File::create(&log_path)?;
+        let child = command
+            .stdout(log.try_clone()?)
+            .stderr(log)
+            .stdin(Stdio::null())
+            .spawn()?;
+
+        let url = format!("http://127.0.0.1:{rpc_port}");
+        let mut localnet = Localnet {
+            client: RpcClient::new_with_commitment(url.clone(), CommitmentConfig::confirmed()),
+            ws_url: format!("ws://127.0.0.1:{}", rpc_port + 1),
+            url,
+            dir,
+            wallets,
+            mints,
+            programs: self.programs,
+            child,
+        };
+        localnet.wait_ready(self.timeout, log_path)?;
+        Ok(localnet)
+    }
+}
+
+/// 运行中的本地节点，离开作用域时结束
+pub struct Localnet {
+    pub client: RpcClient,
+    pub url: String,
+    pub ws_url: String,
+    pub dir: PathBuf,
+    /// 测试钱包，0 号是付费钱包、mint 的铸币权限与各程序的升级权限
+    pub wallets: Vec<Keypair>,
+    /// 测试 mint（SPL Token），按地址升序
+    pub mints: Vec<Pubkey>,
+    pub programs: Vec<Program>,
+    child: Child,
+}
+
+impl Localnet {
+    /// 默认：Anchor 金库、路由、治理、国库与模拟预言机，3 个钱包、2 个 mint
+    pub fn builder() -> Builder {
+        Builder {
+            dir: None,
+            rpc_port: None,
+            programs: vec![ANCHOR_VAULT, ROUTER, GOVERNANCE, TREASURY, MOCK_ORACLE],
+            wallets: 3,
+            lamports: 100 * SOL,
+            mints: 2,
+            decimals: 6,
+            token_balance: 1_000_000_000,
+            accounts: Vec::new(),
+            timeout: Duration::from_secs(60),
+        }
+    }
+
+    pub fn payer(&self) -> &Keypair {
+        &self.wallets[0]
+    }
+
+    /// 以 `payer` 付费的交易构造器；本地节点没有优先费竞争，不查询也不设置单价
+    pub fn tx<'a>(&'a self, payer: &'a Keypair) -> TxBuilder<'a> {
+        TxBuilder::new(&self.client, payer).priority_fee(PriorityFee::None)
+    }
+
+    /// 阻塞到节点退出（`cargo run -p localnet` 前台运行时使用）
+    pub fn wait(&mut self) -> std::io::Result<ExitStatus> {
+        self.child.wait()
+    }
+
+    fn wait_ready(&mut self, timeout: Duration, log: PathBuf) -> Result<(), Error> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                return Err(Error::Exited { status, log });
+            }
+            if self.client.get_latest_blockhash().is_ok() {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::Timeout { log });
+            }
+            thread::sleep(Duration::from_millis(250));
+        }
+    }
+}
+
+impl Drop for Localnet {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// 系统临时目录下按进程号与序号区分的新目录
+fn temp_dir() -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let index = NEXT.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!("localnet-{}-{index}", std::process::id()))
+}
+
+/// 连续三个空闲端口中的第一个（RPC、websocket、faucet）。
+/// 检查后即释放，与其他进程之间仍可能抢占，但足以让并行的测试互不冲突
+fn free_port() -> std::io::Result<u16> {
+    loop {
+        let port = TcpListener::bind("127.0.0.1:0")?.local_addr()?.port();
+        let Some(last) = port.checked_add(2) else {
+            continue;
+        };
+        if (port + 1..=last).all(|next| TcpListener::bind(("127.0.0.1", next)).is_ok()) {
+            return Ok(port);
+        }
+    }
+}
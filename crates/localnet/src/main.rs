@@ -0,0 +1,89 @@
+//! 在前台启动一个本地节点，直到 Ctrl-C。
+//!
+//! ```text
+//! cargo run -p localnet
+//! cargo run -p localnet -- --programs escrow,router --wallets 5 --mints 3
+//! xcli --localnet escrow make --mint-a <A> --mint-b <B> --receive 10 --amount 20
+//! ```
+//!
+//! 默认工作目录是 `target/localnet`、RPC 端口 8899，与 `xcli --localnet` 的约定一致；
+//! 0 号钱包写在 `target/localnet/wallets/0.json`。
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use localnet::{Localnet, Program, DEFAULT_DIR, DEFAULT_RPC_PORT, PROGRAMS};
+use solana_sdk::signer::Signer;
+
+#[derive(Parser)]
+#[command(
+    name = "localnet",
+    version,
+    about = "启动预装训练营程序与测试账户的 solana-test-validator"
+)]
+struct Cli {
+    /// 工作目录
+    #[arg(long, default_value = DEFAULT_DIR)]
+    dir: PathBuf,
+
+    #[arg(long, default_value_t = DEFAULT_RPC_PORT)]
+    rpc_port: u16,
+
+    /// 要加载的程序，逗号分隔；anchor-vault / escrow / amm 共用一个地址，只能选一个
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "anchor-vault,router,governance,treasury,mock-oracle"
+    )]
+    programs: Vec<String>,
+
+    #[arg(long, default_value_t = 3)]
+    wallets: usize,
+
+    #[arg(long, default_value_t = 2)]
+    mints: usize,
+}
+
+fn program(name: &str) -> Result<Program, String> {
+    PROGRAMS
+        .iter()
+        .find(|program| program.name == name)
+        .copied()
+        .ok_or_else(|| {
+            let names: Vec<&str> = PROGRAMS.iter().map(|program| program.name).collect();
+            format!("未知程序 {name}，可选：{}", names.join(" / "))
+        })
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let programs = cli
+        .programs
+        .iter()
+        .map(|name| program(name))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut localnet = Localnet::builder()
+        .dir(&cli.dir)
+        .rpc_port(cli.rpc_port)
+        .programs(programs)
+        .wallets(cli.wallets)
+        .mints(cli.mints)
+        .start()?;
+
+    println!("RPC        {}", localnet.url);
+    println!("websocket  {}", localnet.ws_url);
+    for program in &localnet.programs {
+        println!("程序       {:<13} {}", program.name, program.id);
+    }
+    for (index, wallet) in localnet.wallets.iter().enumerate() {
+        let path = localnet::wallet_path(&localnet.dir, index);
+        println!("钱包 {index}     {}  {}", wallet.pubkey(), path.display());
+    }
+    for mint in &localnet.mints {
+        println!("mint       {mint}");
+    }
+    println!("Ctrl-C 结束");
+
+    let status = localnet.wait()?;
+    Err(format!("solana-test-validator 已退出（{status}）").into())
+}
@@ -0,0 +1,127 @@
+use crate::{find_program_address, Address};
+
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const MINT_LP_SEED: &[u8] = b"mint_lp";
+pub const MULTI_CONFIG_SEED: &[u8] = b"multi_config";
+pub const LP_LOCK_SEED: &[u8] = b"lp_lock";
+pub const LP_REWARDS_SEED: &[u8] = b"lp_rewards";
+pub const CLMM_POOL_SEED: &[u8] = b"clmm_pool";
+pub const TICK_SEED: &[u8] = b"tick";
+pub const TICK_BITMAP_SEED: &[u8] = b"tick_bitmap";
+pub const POSITION_SEED: &[u8] = b"position";
+
+/// 非规范池的 config：`["config", seed (u64 LE), mint_x, mint_y]`，
+/// 同一对 mint 可以用不同 seed 建多个池（pinocchio_amm 只有这种池）
+#[inline]
+pub fn amm_config_pda(
+    program_id: &Address,
+    seed: u64,
+    mint_x: &Address,
+    mint_y: &Address,
+) -> (Address, u8) {
+    find_program_address(
+        &[CONFIG_SEED, &seed.to_le_bytes(), mint_x, mint_y],
+        program_id,
+    )
+}
+
+/// 规范池的 config：`["config", mint_x, mint_y]`，每对 mint 只有一个。
+/// 要求 `mint_x < mint_y`（按字节序），顺序反了得到的地址与链上不一致
+#[inline]
+pub fn amm_canonical_config_pda(
+    program_id: &Address,
+    mint_x: &Address,
+    mint_y: &Address,
+) -> (Address, u8) {
+    find_program_address(&[CONFIG_SEED, mint_x, mint_y], program_id)
+}
+
+/// LP mint：`["mint_lp", config]`，两币池与多币池相同
+#[inline]
+pub fn amm_mint_lp_pda(program_id: &Address, config: &Address) -> (Address, u8) {
+    find_program_address(&[MINT_LP_SEED, config], program_id)
+}
+
+/// 多币池的 MultiConfig：`["multi_config", seed (u64 LE)]`
+#[inline]
+pub fn amm_multi_config_pda(program_id: &Address, seed: u64) -> (Address, u8) {
+    find_program_address(&[MULTI_CONFIG_SEED, &seed.to_le_bytes()], program_id)
+}
+
+/// 用户在某个池子的 LP 锁仓记录：`["lp_lock", config, user]`
+#[inline]
+pub fn amm_lp_lock_pda(program_id: &Address, config: &Address, user: &Address) -> (Address, u8) {
+    find_program_address(&[LP_LOCK_SEED, config, user], program_id)
+}
+
+/// 锁仓奖励池的所有者：`["lp_rewards", config]`，它持有的 LP 代币账户就是奖励池
+#[inline]
+pub fn amm_lp_rewards_pda(program_id: &Address, config: &Address) -> (Address, u8) {
+    find_program_address(&[LP_REWARDS_SEED, config], program_id)
+}
+
+/// CLMM 池：`["clmm_pool", mint_x, mint_y, tick_spacing (u16 LE)]`
+#[inline]
+pub fn clmm_pool_pda(
+    program_id: &Address,
+    mint_x: &Address,
+    mint_y: &Address,
+    tick_spacing: u16,
+) -> (Address, u8) {
+    find_program_address(
+        &[CLMM_POOL_SEED, mint_x, mint_y, &tick_spacing.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// 单个 tick 的状态：`["tick", pool, tick (i32 LE)]`
+#[inline]
+pub fn clmm_tick_pda(program_id: &Address, pool: &Address, tick: i32) -> (Address, u8) {
+    find_program_address(&[TICK_SEED, pool, &tick.to_le_bytes()], program_id)
+}
+
+/// tick 位图的一个字：`["tick_bitmap", pool, word_pos (i16 LE)]`
+#[inline]
+pub fn clmm_tick_bitmap_pda(program_id: &Address, pool: &Address, word_pos: i16) -> (Address, u8) {
+    find_program_address(
+        &[TICK_BITMAP_SEED, pool, &word_pos.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// 仓位：`["position", pool, owner, tick_lower (i32 LE), tick_upper (i32 LE)]`
+#[inline]
+pub fn clmm_position_pda(
+    program_id: &Address,
+    pool: &Address,
+    owner: &Address,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> (Address, u8) {
+    find_program_address(
+        &[
+            POSITION_SEED,
+            pool,
+            owner,
+            &tick_lower.to_le_bytes(),
+            &tick_upper.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM_ID: Address = [0x22; 32];
+
+    #[test]
+    fn canonical_and_seeded_pools_do_not_collide() {
+        let (x, y) = ([1; 32], [2; 32]);
+        let canonical = amm_canonical_config_pda(&PROGRAM_ID, &x, &y).0;
+        assert_ne!(canonical, amm_config_pda(&PROGRAM_ID, 0, &x, &y).0);
+        // 规范池对 mint 顺序敏感，排序由调用方负责
+        assert_ne!(canonical, amm_canonical_config_pda(&PROGRAM_ID, &y, &x).0);
+    }
+}
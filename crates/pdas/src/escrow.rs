@@ -0,0 +1,74 @@
+use crate::{create_program_address, find_program_address, Address, CONFIG_SEED};
+
+pub const ESCROW_SEED: &[u8] = b"escrow";
+pub const OFFER_INDEX_SEED: &[u8] = b"offers";
+
+/// 托管 PDA：`["escrow", maker, seed (u64 LE)]`，同一 maker 用不同 seed 开多个托管
+#[inline]
+pub fn escrow_pda(program_id: &Address, maker: &Address, seed: u64) -> (Address, u8) {
+    find_program_address(&[ESCROW_SEED, maker, &seed.to_le_bytes()], program_id)
+}
+
+/// 用 Escrow 账户中保存的 bump 重新计算托管地址（Take / Refund 校验用）
+#[inline]
+pub fn escrow_pda_with_bump(
+    program_id: &Address,
+    maker: &Address,
+    seed: u64,
+    bump: u8,
+) -> Option<Address> {
+    create_program_address(
+        &[ESCROW_SEED, maker, &seed.to_le_bytes(), &[bump]],
+        program_id,
+    )
+}
+
+/// Pinocchio 托管的全局配置（协议手续费）：`["config"]`，整个程序只有一个，与 AMM 共用种子常量
+#[inline]
+pub fn escrow_config_pda(program_id: &Address) -> (Address, u8) {
+    find_program_address(&[CONFIG_SEED], program_id)
+}
+
+/// 创建者的挂单索引：`["offers", maker]`，记录该 maker 所有未成交托管单的 seed
+#[inline]
+pub fn offer_index_pda(program_id: &Address, maker: &Address) -> (Address, u8) {
+    find_program_address(&[OFFER_INDEX_SEED, maker], program_id)
+}
+
+/// 用 OfferIndex 账户中保存的 bump 重新计算索引地址
+#[inline]
+pub fn offer_index_pda_with_bump(
+    program_id: &Address,
+    maker: &Address,
+    bump: u8,
+) -> Option<Address> {
+    create_program_address(&[OFFER_INDEX_SEED, maker, &[bump]], program_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_selects_escrow() {
+        let program_id = [0x22; 32];
+        let maker = [1; 32];
+        let (first, bump) = escrow_pda(&program_id, &maker, 1);
+        assert_ne!(first, escrow_pda(&program_id, &maker, 2).0);
+        assert_eq!(
+            escrow_pda_with_bump(&program_id, &maker, 1, bump),
+            Some(first)
+        );
+    }
+
+    #[test]
+    fn offer_index_is_per_maker() {
+        let program_id = [0x22; 32];
+        let (index, bump) = offer_index_pda(&program_id, &[1; 32]);
+        assert_ne!(index, offer_index_pda(&program_id, &[2; 32]).0);
+        assert_eq!(
+            offer_index_pda_with_bump(&program_id, &[1; 32], bump),
+            Some(index)
+        );
+    }
+}
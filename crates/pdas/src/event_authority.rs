@@ -0,0 +1,11 @@
+use crate::{find_program_address, Address};
+
+/// 与 Anchor `#[event_cpi]` 相同的种子
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// 事件 CPI 的签名者：`["__event_authority"]`。程序以它为签名者自调用来发出事件，
+/// 解析方据此确认内部指令确实由程序自己发出
+#[inline]
+pub fn event_authority_pda(program_id: &Address) -> (Address, u8) {
+    find_program_address(&[EVENT_AUTHORITY_SEED], program_id)
+}
@@ -0,0 +1,45 @@
+use crate::{create_program_address, find_program_address, Address};
+
+pub const FEATURE_GATE_SEED: &[u8] = b"feature_gate";
+
+/// BPF Upgradeable Loader：BPFLoaderUpgradeab1e11111111111111111111111
+pub const BPF_LOADER_UPGRADEABLE_ID: Address = [
+    0x02, 0xa8, 0xf6, 0x91, 0x4e, 0x88, 0xa1, 0xb0, 0xe2, 0x10, 0x15, 0x3e, 0xf7, 0x63, 0xae, 0x2b,
+    0x00, 0xc2, 0xb9, 0x3d, 0x16, 0xc1, 0x24, 0xd2, 0xc0, 0x53, 0x7a, 0x10, 0x04, 0x80, 0x00, 0x00,
+];
+
+/// 程序的功能开关账户：`["feature_gate"]`，每个程序一个，由程序自己持有
+#[inline]
+pub fn feature_gate_pda(program_id: &Address) -> (Address, u8) {
+    find_program_address(&[FEATURE_GATE_SEED], program_id)
+}
+
+/// 用开关账户中保存的 bump 重新计算地址
+#[inline]
+pub fn feature_gate_pda_with_bump(program_id: &Address, bump: u8) -> Option<Address> {
+    create_program_address(&[FEATURE_GATE_SEED, &[bump]], program_id)
+}
+
+/// 可升级程序的 ProgramData 账户：Upgradeable Loader 下的 `[program_id]`，
+/// 其中记录着升级权限（upgrade authority）
+#[inline]
+pub fn program_data_pda(program_id: &Address) -> (Address, u8) {
+    find_program_address(&[program_id], &BPF_LOADER_UPGRADEABLE_ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feature_gate_is_per_program() {
+        let (gate, bump) = feature_gate_pda(&[0x22; 32]);
+        assert_ne!(gate, feature_gate_pda(&[0x33; 32]).0);
+        assert_eq!(feature_gate_pda_with_bump(&[0x22; 32], bump), Some(gate));
+        assert_eq!(
+            solana_pubkey::Pubkey::from_str_const("BPFLoaderUpgradeab1e11111111111111111111111")
+                .to_bytes(),
+            BPF_LOADER_UPGRADEABLE_ID
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{create_program_address, find_program_address, Address};
+
+pub const COUNCIL_SEED: &[u8] = b"council";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const GOVERNANCE_AUTHORITY_SEED: &[u8] = b"authority";
+
+/// 理事会 PDA：`["council", seed (u64 LE)]`
+#[inline]
+pub fn council_pda(program_id: &Address, seed: u64) -> (Address, u8) {
+    find_program_address(&[COUNCIL_SEED, &seed.to_le_bytes()], program_id)
+}
+
+/// 提案 PDA：`["proposal", council, index (u64 LE)]`，index 为理事会的提案序号
+#[inline]
+pub fn proposal_pda(program_id: &Address, council: &Address, index: u64) -> (Address, u8) {
+    find_program_address(&[PROPOSAL_SEED, council, &index.to_le_bytes()], program_id)
+}
+
+/// 理事会的签名地址：`["authority", council]`。不带数据的系统账户，
+/// 设为 AMM 池子等程序的 authority 后，只有通过的提案能以它的名义签名
+#[inline]
+pub fn governance_authority_pda(program_id: &Address, council: &Address) -> (Address, u8) {
+    find_program_address(&[GOVERNANCE_AUTHORITY_SEED, council], program_id)
+}
+
+/// 用理事会中保存的 bump 重新计算签名地址
+#[inline]
+pub fn governance_authority_pda_with_bump(
+    program_id: &Address,
+    council: &Address,
+    bump: u8,
+) -> Option<Address> {
+    create_program_address(&[GOVERNANCE_AUTHORITY_SEED, council, &[bump]], program_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_council_has_its_own_authority() {
+        let program_id = [0x22; 32];
+        let (first, _) = council_pda(&program_id, 1);
+        let (second, _) = council_pda(&program_id, 2);
+        let (authority, bump) = governance_authority_pda(&program_id, &first);
+        assert_ne!(authority, governance_authority_pda(&program_id, &second).0);
+        assert_eq!(
+            governance_authority_pda_with_bump(&program_id, &first, bump),
+            Some(authority)
+        );
+        assert_ne!(
+            proposal_pda(&program_id, &first, 0).0,
+            proposal_pda(&program_id, &first, 1).0
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! 训练营各程序 PDA 的种子布局与地址派生。
+//!
+//! 链上程序校验 / 签名、集成测试和客户端 SDK 都从这里取种子，种子布局只写一份：
+//!
+//! | PDA                         | 种子                                              | 程序        |
+//! |-----------------------------|---------------------------------------------------|-------------|
+//! | [`vault_pda`]               | `["vault", owner]`                                | 金库        |
+//! | [`indexed_vault_pda`]       | `["vault", owner, index (u8)]`                    | Anchor 金库 |
+//! | [`vault_state_pda`]         | `["vault_state", owner]`                          | Anchor 金库 |
+//! | [`stream_pda`]              | `["stream", owner]`                               | Anchor 金库 |
+//! | [`policy_pda`]              | `["policy", owner]`                               | Anchor 金库 |
+//! | [`metadata_pda`]            | `["metadata", owner]`                             | Anchor 金库 |
+//! | [`cooldown_pda`]            | `["cooldown", owner]`                             | Anchor 金库 |
+//! | [`vault_config_pda`]        | `["config"]`                                      | Anchor 金库 |
+//! | [`escrow_pda`]              | `["escrow", maker, seed (u64 LE)]`                | 托管        |
+//! | [`escrow_config_pda`]       | `["config"]`                                      | 托管        |
+//! | [`offer_index_pda`]         | `["offers", maker]`                               | 托管        |
+//! | [`amm_config_pda`]          | `["config", seed (u64 LE), mint_x, mint_y]`       | AMM         |
+//! | [`amm_canonical_config_pda`]| `["config", mint_x, mint_y]`                      | AMM         |
+//! | [`amm_mint_lp_pda`]         | `["mint_lp", config]`                             | AMM         |
+//! | [`amm_multi_config_pda`]    | `["multi_config", seed (u64 LE)]`                 | AMM         |
+//! | [`amm_lp_lock_pda`]         | `["lp_lock", config, user]`                       | AMM         |
+//! | [`amm_lp_rewards_pda`]      | `["lp_rewards", config]`                          | AMM         |
+//! | [`clmm_pool_pda`]           | `["clmm_pool", mint_x, mint_y, tick_spacing (u16 LE)]` | AMM CLMM |
+//! | [`clmm_tick_pda`]           | `["tick", pool, tick (i32 LE)]`                   | AMM CLMM    |
+//! | [`clmm_tick_bitmap_pda`]    | `["tick_bitmap", pool, word_pos (i16 LE)]`        | AMM CLMM    |
+//! | [`clmm_position_pda`]       | `["position", pool, owner, lower (i32 LE), upper (i32 LE)]` | AMM CLMM |
+//! | [`council_pda`]             | `["council", seed (u64 LE)]`                      | 治理        |
+//! | [`proposal_pda`]            | `["proposal", council, index (u64 LE)]`           | 治理        |
+//! | [`governance_authority_pda`]| `["authority", council]`                          | 治理        |
+//! | [`feature_gate_pda`]        | `["feature_gate"]`                                | 各原生程序  |
+//! | [`program_data_pda`]        | `[program_id]`（Upgradeable Loader 下）           | 各原生程序  |
+//! | [`event_authority_pda`]     | `["__event_authority"]`                           | 各 Anchor 程序 |
+//! | [`price_feed_pda`]          | `["price_feed", feed_id]`                         | 模拟预言机  |
+//! | [`treasury_pda`]            | `["treasury"]`                                    | 国库、Anchor 金库 |
+//! | [`upgrade_guard_pda`]       | `["upgrade_guard", program]`                      | 升级守卫    |
+//!
+//! 程序 ID 由调用方传入：链上用 `crate::ID`，测试可以把程序部署在任意地址。
+//! 与 `blueshift_events` 一样，地址统一用 `[u8; 32]`，pinocchio 0.9 可直接传 `Pubkey`，
+//! pinocchio 0.10 / solana-sdk 通过 `to_bytes()` / `new_from_array` 转换。
+//!
+//! 各函数返回 `(地址, bump)`；`*_with_bump` 系列用已存储的 bump 重新计算地址，
+//! 比重新搜索 bump 便宜得多，种子不合法（地址落在曲线上）时返回 `None`。
+
+#![no_std]
+
+mod amm;
+mod escrow;
+mod event_authority;
+mod feature_gate;
+mod governance;
+mod oracle;
+mod treasury;
+mod upgrade_guard;
+mod vault;
+
+pub use amm::*;
+pub use escrow::*;
+pub use event_authority::*;
+pub use feature_gate::*;
+pub use governance::*;
+pub use oracle::*;
+pub use treasury::*;
+pub use upgrade_guard::*;
+pub use vault::*;
+
+use solana_pubkey::Pubkey;
+
+/// 32 字节公钥
+pub type Address = [u8; 32];
+
+/// 搜索 `seeds` 在 `program_id` 下的 PDA 与 bump（从 255 往下第一个不在曲线上的地址）
+#[inline]
+pub fn find_program_address(seeds: &[&[u8]], program_id: &Address) -> (Address, u8) {
+    let (address, bump) = Pubkey::find_program_address(seeds, &Pubkey::new_from_array(*program_id));
+    (address.to_bytes(), bump)
+}
+
+/// 用完整种子（最后一个通常是 bump）计算 PDA；地址落在曲线上或种子超限时返回 `None`
+#[inline]
+pub fn create_program_address(seeds: &[&[u8]], program_id: &Address) -> Option<Address> {
+    Pubkey::create_program_address(seeds, &Pubkey::new_from_array(*program_id))
+        .ok()
+        .map(|address| address.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM_ID: Address = [0x22; 32];
+
+    #[test]
+    fn create_matches_find() {
+        let owner = [7; 32];
+        let (address, bump) = find_program_address(&[VAULT_SEED, &owner], &PROGRAM_ID);
+        assert_eq!(
+            create_program_address(&[VAULT_SEED, &owner, &[bump]], &PROGRAM_ID),
+            Some(address)
+        );
+        // 只有 bump 不同的种子得到不同地址（或不合法）
+        assert_ne!(
+            create_program_address(&[VAULT_SEED, &owner, &[bump.wrapping_sub(1)]], &PROGRAM_ID),
+            Some(address)
+        );
+    }
+
+    #[test]
+    fn program_id_is_part_of_the_address() {
+        let owner = [7; 32];
+        assert_ne!(
+            vault_pda(&PROGRAM_ID, &owner).0,
+            vault_pda(&[0x33; 32], &owner).0
+        );
+    }
+
+    #[test]
+    fn indexed_vaults_never_alias_the_main_vault() {
+        let owner = [7; 32];
+        let (main, _) = vault_pda(&PROGRAM_ID, &owner);
+        let (first, _) = indexed_vault_pda(&PROGRAM_ID, &owner, 0);
+        let (second, _) = indexed_vault_pda(&PROGRAM_ID, &owner, 1);
+        assert_ne!(first, main);
+        assert_ne!(second, main);
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,10 @@
+use crate::{find_program_address, Address};
+
+pub const PRICE_FEED_SEED: &[u8] = b"price_feed";
+
+/// 模拟预言机的价格账户：`["price_feed", feed_id]`。`feed_id` 沿用 Pyth 的 32 字节 feed ID，
+/// 测试里同一个 ID 可以对应真实网络上的价格账户
+#[inline]
+pub fn price_feed_pda(program_id: &Address, feed_id: &[u8; 32]) -> (Address, u8) {
+    find_program_address(&[PRICE_FEED_SEED, feed_id], program_id)
+}
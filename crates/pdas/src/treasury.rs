@@ -0,0 +1,10 @@
+use crate::{find_program_address, Address};
+
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+/// 手续费国库：`["treasury"]`，每个部署只有一个。SOL 存在它自己的 lamports 中，
+/// 代币存在它持有的 ATA 中。Anchor 金库用同样的种子派生收取存款手续费的系统账户
+#[inline]
+pub fn treasury_pda(program_id: &Address) -> (Address, u8) {
+    find_program_address(&[TREASURY_SEED], program_id)
+}
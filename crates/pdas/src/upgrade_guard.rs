@@ -0,0 +1,36 @@
+use crate::{create_program_address, find_program_address, Address};
+
+pub const UPGRADE_GUARD_SEED: &[u8] = b"upgrade_guard";
+
+/// 升级守卫 PDA：`["upgrade_guard", program]`，每个受保护的程序一个。
+/// 它同时是该程序的升级权限，只有等待期满的升级提案能以它的名义调用 Upgradeable Loader
+#[inline]
+pub fn upgrade_guard_pda(program_id: &Address, program: &Address) -> (Address, u8) {
+    find_program_address(&[UPGRADE_GUARD_SEED, program], program_id)
+}
+
+/// 用守卫账户中保存的 bump 重新计算地址
+#[inline]
+pub fn upgrade_guard_pda_with_bump(
+    program_id: &Address,
+    program: &Address,
+    bump: u8,
+) -> Option<Address> {
+    create_program_address(&[UPGRADE_GUARD_SEED, program, &[bump]], program_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_program_has_its_own_guard() {
+        let program_id = [0x44; 32];
+        let (guard, bump) = upgrade_guard_pda(&program_id, &[0x22; 32]);
+        assert_ne!(guard, upgrade_guard_pda(&program_id, &[0x33; 32]).0);
+        assert_eq!(
+            upgrade_guard_pda_with_bump(&program_id, &[0x22; 32], bump),
+            Some(guard)
+        );
+    }
+}
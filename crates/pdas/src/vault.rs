@@ -0,0 +1,56 @@
+use crate::{find_program_address, Address, CONFIG_SEED};
+
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const VAULT_STATE_SEED: &[u8] = b"vault_state";
+pub const STREAM_SEED: &[u8] = b"stream";
+pub const POLICY_SEED: &[u8] = b"policy";
+pub const METADATA_SEED: &[u8] = b"metadata";
+pub const COOLDOWN_SEED: &[u8] = b"cooldown";
+
+/// 金库 PDA：`["vault", owner]`。Anchor 版（task2）与 Pinocchio 版（task4）布局相同
+#[inline]
+pub fn vault_pda(program_id: &Address, owner: &Address) -> (Address, u8) {
+    find_program_address(&[VAULT_SEED, owner], program_id)
+}
+
+/// Anchor 金库的子金库：`["vault", owner, index]`，由 sweep 一次归集
+#[inline]
+pub fn indexed_vault_pda(program_id: &Address, owner: &Address, index: u8) -> (Address, u8) {
+    find_program_address(&[VAULT_SEED, owner, &[index]], program_id)
+}
+
+/// Anchor 金库（task2）记录解锁时间与代理人的状态账户：`["vault_state", owner]`
+#[inline]
+pub fn vault_state_pda(program_id: &Address, owner: &Address) -> (Address, u8) {
+    find_program_address(&[VAULT_STATE_SEED, owner], program_id)
+}
+
+/// Anchor 金库的流式支付，每个金库至多一个：`["stream", owner]`
+#[inline]
+pub fn stream_pda(program_id: &Address, owner: &Address) -> (Address, u8) {
+    find_program_address(&[STREAM_SEED, owner], program_id)
+}
+
+/// Anchor 金库的共同签名策略，存在时取款需要第二个签名者：`["policy", owner]`
+#[inline]
+pub fn policy_pda(program_id: &Address, owner: &Address) -> (Address, u8) {
+    find_program_address(&[POLICY_SEED, owner], program_id)
+}
+
+/// Anchor 金库的元数据（标签与备注），供钱包显示：`["metadata", owner]`
+#[inline]
+pub fn metadata_pda(program_id: &Address, owner: &Address) -> (Address, u8) {
+    find_program_address(&[METADATA_SEED, owner], program_id)
+}
+
+/// Anchor 金库的取款冷却，存在时两次取款之间至少间隔固定秒数：`["cooldown", owner]`
+#[inline]
+pub fn cooldown_pda(program_id: &Address, owner: &Address) -> (Address, u8) {
+    find_program_address(&[COOLDOWN_SEED, owner], program_id)
+}
+
+/// Anchor 金库的全局配置（存款限制），整个程序只有一个：`["config"]`，与 AMM 共用种子常量
+#[inline]
+pub fn vault_config_pda(program_id: &Address) -> (Address, u8) {
+    find_program_address(&[CONFIG_SEED], program_id)
+}
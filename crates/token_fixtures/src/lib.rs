@@ -0,0 +1,170 @@
+//! 测试用的代币账户夹具：直接写入已初始化的 mint、ATA 与带余额的代币账户。
+//!
+//! 托管与 AMM 的测试每个用例都要先准备 mint 和代币账户。走真实指令（CreateMint /
+//! CreateAssociatedTokenAccount / MintTo）要为每一步签名发交易，Mollusk 里更是只能手写字节；
+//! 这里按 SPL Token 的 `Pack` 布局直接生成账户数据，由各虚拟机的模块写入：
+//!
+//! - `litesvm` feature：[`litesvm`] 模块，`set_account` 写入 LiteSVM，余额 / 供应量可增量铸造；
+//! - `mollusk` feature：[`mollusk`] 模块，返回 Mollusk 需要的 `(Pubkey, Account)`。
+//!
+//! [`TokenProgram::Token2022`] 的 mint 与代币账户使用不带扩展的基础布局；代币账户额外写入
+//! `AccountType` 与 `ImmutableOwner` 扩展头，与 ATA 程序为 Token-2022 mint 创建的账户一致。
+//!
+//! 与 `pdas` 一样，这一层的地址都是 `[u8; 32]`，不绑定任何 solana-sdk 版本。
+
+#[cfg(feature = "litesvm")]
+pub mod litesvm;
+#[cfg(feature = "mollusk")]
+pub mod mollusk;
+
+pub use pdas::Address;
+use solana_pubkey::pubkey;
+
+pub const TOKEN_PROGRAM_ID: Address =
+    pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").to_bytes();
+pub const TOKEN_2022_PROGRAM_ID: Address =
+    pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb").to_bytes();
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Address =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL").to_bytes();
+
+/// `spl_token::state::Mint::LEN`
+pub const MINT_LEN: usize = 82;
+/// `spl_token::state::Account::LEN`
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+/// Token-2022 代币账户：基础布局 + `AccountType` 1 字节 + `ImmutableOwner` 的 TLV 头 4 字节
+pub const TOKEN_2022_ACCOUNT_LEN: usize = TOKEN_ACCOUNT_LEN + 1 + 4;
+
+/// Token-2022 `AccountType::Account`
+const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+/// Token-2022 `ExtensionType::ImmutableOwner`
+const EXTENSION_IMMUTABLE_OWNER: u16 = 7;
+
+/// 代币账户所属的代币程序
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum TokenProgram {
+    #[default]
+    Token,
+    Token2022,
+}
+
+impl TokenProgram {
+    pub const fn id(self) -> Address {
+        match self {
+            Self::Token => TOKEN_PROGRAM_ID,
+            Self::Token2022 => TOKEN_2022_PROGRAM_ID,
+        }
+    }
+
+    /// 本夹具生成的代币账户长度
+    pub const fn token_account_len(self) -> usize {
+        match self {
+            Self::Token => TOKEN_ACCOUNT_LEN,
+            Self::Token2022 => TOKEN_2022_ACCOUNT_LEN,
+        }
+    }
+}
+
+/// `owner` 在 `mint` 上的 ATA；代币程序 ID 是种子的一部分，同一 mint 在两个程序下地址不同
+pub fn ata_address(owner: &Address, mint: &Address, program: TokenProgram) -> Address {
+    pdas::find_program_address(&[owner, &program.id(), mint], &ASSOCIATED_TOKEN_PROGRAM_ID).0
+}
+
+/// 默认 `Rent`（3480 lamports/字节·年，两年免租）下 `len` 字节账户的最低余额，
+/// LiteSVM 与 Mollusk 默认都使用这组参数
+pub const fn rent_exempt(len: usize) -> u64 {
+    (128 + len as u64) * 3480 * 2
+}
+
+/// 已初始化的 mint：有铸币权限、没有冻结权限
+pub fn mint_data(authority: &Address, supply: u64, decimals: u8) -> Vec<u8> {
+    let mut data = vec![0u8; MINT_LEN];
+    data[0..4].copy_from_slice(&1u32.to_le_bytes()); // mint_authority: Some
+    data[4..36].copy_from_slice(authority);
+    data[36..44].copy_from_slice(&supply.to_le_bytes());
+    data[44] = decimals;
+    data[45] = 1; // is_initialized
+    data
+}
+
+/// 已初始化、余额为 `amount` 的代币账户，没有 delegate / close_authority
+pub fn token_account_data(
+    mint: &Address,
+    owner: &Address,
+    amount: u64,
+    program: TokenProgram,
+) -> Vec<u8> {
+    let mut data = vec![0u8; program.token_account_len()];
+    data[0..32].copy_from_slice(mint);
+    data[32..64].copy_from_slice(owner);
+    data[64..72].copy_from_slice(&amount.to_le_bytes());
+    data[108] = 1; // AccountState::Initialized
+    if program == TokenProgram::Token2022 {
+        data[TOKEN_ACCOUNT_LEN] = ACCOUNT_TYPE_ACCOUNT;
+        // ImmutableOwner 没有数据，TLV 只有 type + length(0)
+        data[166..168].copy_from_slice(&EXTENSION_IMMUTABLE_OWNER.to_le_bytes());
+    }
+    data
+}
+
+/// mint 数据中的供应量
+pub fn mint_supply(data: &[u8]) -> u64 {
+    u64::from_le_bytes(data[36..44].try_into().unwrap())
+}
+
+pub fn set_mint_supply(data: &mut [u8], supply: u64) {
+    data[36..44].copy_from_slice(&supply.to_le_bytes());
+}
+
+/// 代币账户数据中的余额
+pub fn token_amount(data: &[u8]) -> u64 {
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+pub fn set_token_amount(data: &mut [u8], amount: u64) {
+    data[64..72].copy_from_slice(&amount.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rent_matches_cluster_defaults() {
+        assert_eq!(rent_exempt(MINT_LEN), 1_461_600);
+        assert_eq!(rent_exempt(TOKEN_ACCOUNT_LEN), 2_039_280);
+    }
+
+    #[test]
+    fn mint_and_account_fields_round_trip() {
+        let (authority, mint, owner) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+
+        let mut data = mint_data(&authority, 10, 6);
+        assert_eq!((data.len(), data[44], data[45]), (MINT_LEN, 6, 1));
+        set_mint_supply(&mut data, 25);
+        assert_eq!(mint_supply(&data), 25);
+
+        let mut data = token_account_data(&mint, &owner, 500, TokenProgram::Token);
+        assert_eq!(data.len(), TOKEN_ACCOUNT_LEN);
+        assert_eq!((&data[0..32], &data[32..64]), (&mint[..], &owner[..]));
+        set_token_amount(&mut data, 700);
+        assert_eq!(token_amount(&data), 700);
+    }
+
+    #[test]
+    fn token_2022_account_carries_immutable_owner() {
+        let base = token_account_data(&[2; 32], &[3; 32], 1, TokenProgram::Token);
+        let data = token_account_data(&[2; 32], &[3; 32], 1, TokenProgram::Token2022);
+        assert_eq!(data.len(), 170);
+        assert_eq!(data[..TOKEN_ACCOUNT_LEN], base[..]);
+        assert_eq!(&data[TOKEN_ACCOUNT_LEN..], &[2, 7, 0, 0, 0]);
+    }
+
+    #[test]
+    fn ata_depends_on_token_program() {
+        let (owner, mint) = ([3u8; 32], [2u8; 32]);
+        assert_ne!(
+            ata_address(&owner, &mint, TokenProgram::Token),
+            ata_address(&owner, &mint, TokenProgram::Token2022)
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! LiteSVM 版本：账户通过 `set_account` 直接写入，不需要 mint 权限签名。
+//!
+//! [`mint_to`] 同时增加代币账户余额与 mint 供应量，两者始终一致，
+//! 之后程序内的 Transfer / Burn 看到的是正常的链上状态。
+
+use litesvm::LiteSVM;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+use crate::{
+    ata_address, mint_data, rent_exempt, set_mint_supply, set_token_amount, token_account_data,
+    TokenProgram,
+};
+
+fn set_token_owned(svm: &mut LiteSVM, address: &Pubkey, data: Vec<u8>, program: TokenProgram) {
+    let account = Account {
+        lamports: rent_exempt(data.len()),
+        data,
+        owner: Pubkey::new_from_array(program.id()),
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(*address, account).unwrap();
+}
+
+fn data(svm: &LiteSVM, address: &Pubkey) -> Vec<u8> {
+    svm.get_account(address)
+        .unwrap_or_else(|| panic!("{address} 不存在"))
+        .data
+}
+
+/// 在新地址上创建供应量为 0 的 mint
+pub fn create_mint(
+    svm: &mut LiteSVM,
+    authority: &Pubkey,
+    decimals: u8,
+    program: TokenProgram,
+) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    create_mint_at(svm, &mint, authority, decimals, program);
+    mint
+}
+
+/// 在指定地址上创建 mint，用于需要固定排序或地址的场景
+pub fn create_mint_at(
+    svm: &mut LiteSVM,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    decimals: u8,
+    program: TokenProgram,
+) {
+    let data = mint_data(&authority.to_bytes(), 0, decimals);
+    set_token_owned(svm, mint, data, program);
+}
+
+/// 创建余额为 0 的 ATA；`owner` 可以是 PDA。已存在时保持原样
+pub fn create_ata(
+    svm: &mut LiteSVM,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    program: TokenProgram,
+) -> Pubkey {
+    let ata = Pubkey::new_from_array(ata_address(&owner.to_bytes(), &mint.to_bytes(), program));
+    if svm
+        .get_account(&ata)
+        .is_none_or(|account| account.data.is_empty())
+    {
+        let data = token_account_data(&mint.to_bytes(), &owner.to_bytes(), 0, program);
+        set_token_owned(svm, &ata, data, program);
+    }
+    ata
+}
+
+/// 给 `owner` 的 ATA 铸造 `amount`，ATA 不存在时先创建；返回 ATA 地址
+pub fn mint_to(
+    svm: &mut LiteSVM,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    program: TokenProgram,
+) -> Pubkey {
+    let ata = create_ata(svm, owner, mint, program);
+
+    let mut account = data(svm, &ata);
+    set_token_amount(&mut account, token_balance(svm, &ata) + amount);
+    set_token_owned(svm, &ata, account, program);
+
+    let mut mint_account = data(svm, mint);
+    set_mint_supply(&mut mint_account, mint_supply(svm, mint) + amount);
+    set_token_owned(svm, mint, mint_account, program);
+    ata
+}
+
+/// 代币账户余额，账户不存在时 panic
+pub fn token_balance(svm: &LiteSVM, account: &Pubkey) -> u64 {
+    crate::token_amount(&data(svm, account))
+}
+
+/// mint 供应量，账户不存在时 panic
+pub fn mint_supply(svm: &LiteSVM, mint: &Pubkey) -> u64 {
+    crate::mint_supply(&data(svm, mint))
+}
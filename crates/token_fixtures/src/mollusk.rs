@@ -0,0 +1,86 @@
+//! Mollusk 版本：Mollusk 不保存账户状态，这里只构造 `(Pubkey, Account)`，
+//! 由调用方放进 `process_instruction` 的账户列表。
+
+use mollusk_svm::Mollusk;
+use mollusk_svm_programs_token::{associated_token, token, token2022};
+use solana_account::Account;
+use solana_sdk_3::pubkey::Pubkey;
+
+use crate::{ata_address, mint_data, rent_exempt, token_account_data, TokenProgram};
+
+fn token_owned(address: &Pubkey, data: Vec<u8>, program: TokenProgram) -> (Pubkey, Account) {
+    let account = Account {
+        lamports: rent_exempt(data.len()),
+        data,
+        owner: Pubkey::new_from_array(program.id()),
+        executable: false,
+        rent_epoch: 0,
+    };
+    (*address, account)
+}
+
+/// 加载 SPL Token、Token-2022 与 ATA 程序
+pub fn add_token_programs(mollusk: &mut Mollusk) {
+    token::add_program(mollusk);
+    token2022::add_program(mollusk);
+    associated_token::add_program(mollusk);
+}
+
+/// 与 [`add_token_programs`] 对应的程序账户，CPI 到代币程序的指令需要一并传入
+pub fn token_program_accounts() -> Vec<(Pubkey, Account)> {
+    vec![
+        token::keyed_account(),
+        token2022::keyed_account(),
+        associated_token::keyed_account(),
+    ]
+}
+
+/// 持有 `lamports` 的系统账户
+pub fn wallet(address: &Pubkey, lamports: u64) -> (Pubkey, Account) {
+    (
+        *address,
+        Account::new(
+            lamports,
+            0,
+            &mollusk_svm::program::keyed_account_for_system_program().0,
+        ),
+    )
+}
+
+/// 已初始化的 mint
+pub fn mint(
+    address: &Pubkey,
+    authority: &Pubkey,
+    supply: u64,
+    decimals: u8,
+    program: TokenProgram,
+) -> (Pubkey, Account) {
+    token_owned(
+        address,
+        mint_data(&authority.to_bytes(), supply, decimals),
+        program,
+    )
+}
+
+/// 任意地址上的代币账户，例如程序自己创建的金库
+pub fn token_account(
+    address: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    program: TokenProgram,
+) -> (Pubkey, Account) {
+    let data = token_account_data(&mint.to_bytes(), &owner.to_bytes(), amount, program);
+    token_owned(address, data, program)
+}
+
+/// `owner` 在 `mint` 上余额为 `amount` 的 ATA
+pub fn ata(owner: &Pubkey, mint: &Pubkey, amount: u64, program: TokenProgram) -> (Pubkey, Account) {
+    let address = Pubkey::new_from_array(ata_address(&owner.to_bytes(), &mint.to_bytes(), program));
+    token_account(&address, mint, owner, amount, program)
+}
+
+/// 执行结果中代币账户的余额
+pub fn token_balance(account: &Account) -> u64 {
+    crate::token_amount(&account.data)
+}
@@ -0,0 +1,130 @@
+//! 原生 AMM（task6），只操作规范池
+
+use anyhow::{ensure, Result};
+use bootcamp_client::{amm::Pool, token::create_ata_idempotent_ix};
+use clap::{Args, Subcommand};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::Context;
+
+/// 各指令共用的池子参数
+#[derive(Args)]
+pub struct PoolArgs {
+    #[arg(long)]
+    mint_x: Pubkey,
+    #[arg(long)]
+    mint_y: Pubkey,
+}
+
+#[derive(Subcommand)]
+pub enum AmmCommand {
+    /// 创建 50/50 规范池及其两个金库
+    Init {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// 手续费（基点）
+        #[arg(long)]
+        fee: u16,
+        /// 池子管理员，默认为当前钱包
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// 铸造 amount 个 LP，最多存入 max_x / max_y
+    Deposit {
+        #[command(flatten)]
+        pool: PoolArgs,
+        #[arg(long)]
+        amount: u64,
+        #[arg(long)]
+        max_x: u64,
+        #[arg(long)]
+        max_y: u64,
+    },
+    /// 用 amount 个输入代币兑换，至少得到 min 个输出代币
+    Swap {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// 输入 X 换出 Y；不加则输入 Y 换出 X
+        #[arg(long)]
+        x_to_y: bool,
+        #[arg(long)]
+        amount: u64,
+        #[arg(long)]
+        min: u64,
+    },
+    /// 销毁 amount 个 LP，至少取回 min_x / min_y
+    Withdraw {
+        #[command(flatten)]
+        pool: PoolArgs,
+        #[arg(long)]
+        amount: u64,
+        #[arg(long)]
+        min_x: u64,
+        #[arg(long)]
+        min_y: u64,
+    },
+}
+
+/// 规范池要求 mint_x < mint_y，否则 `--x-to-y` 与链上的 X / Y 对不上
+fn canonical_pool(program_id: &Pubkey, args: &PoolArgs) -> Result<Pool> {
+    ensure!(
+        args.mint_x < args.mint_y,
+        "规范池要求 mint_x < mint_y，请交换 --mint-x 与 --mint-y"
+    );
+    Ok(Pool::canonical(program_id, &args.mint_x, &args.mint_y))
+}
+
+pub fn run(ctx: &Context, program_id: &Pubkey, cmd: AmmCommand) -> Result<()> {
+    let me = ctx.pubkey();
+    // 收款的代币账户可能还不存在，先幂等创建
+    let ixs = match cmd {
+        AmmCommand::Init {
+            pool,
+            fee,
+            authority,
+        } => {
+            let pool = canonical_pool(program_id, &pool)?;
+            println!("config: {}\nmint_lp: {}", pool.config, pool.mint_lp);
+            vec![pool.initialize_ix(&me, fee, &authority.unwrap_or(me))]
+        }
+        AmmCommand::Deposit {
+            pool,
+            amount,
+            max_x,
+            max_y,
+        } => {
+            let pool = canonical_pool(program_id, &pool)?;
+            vec![
+                create_ata_idempotent_ix(&me, &me, &pool.mint_lp),
+                pool.deposit_ix(&me, amount, max_x, max_y),
+            ]
+        }
+        AmmCommand::Swap {
+            pool,
+            x_to_y,
+            amount,
+            min,
+        } => {
+            let pool = canonical_pool(program_id, &pool)?;
+            let out_mint = if x_to_y { pool.mint_y } else { pool.mint_x };
+            vec![
+                create_ata_idempotent_ix(&me, &me, &out_mint),
+                pool.swap_ix(&me, x_to_y, amount, min),
+            ]
+        }
+        AmmCommand::Withdraw {
+            pool,
+            amount,
+            min_x,
+            min_y,
+        } => {
+            let pool = canonical_pool(program_id, &pool)?;
+            vec![
+                create_ata_idempotent_ix(&me, &me, &pool.mint_x),
+                create_ata_idempotent_ix(&me, &me, &pool.mint_y),
+                pool.withdraw_ix(&me, amount, min_x, min_y),
+            ]
+        }
+    };
+    ctx.send(&ixs)
+}
@@ -0,0 +1,110 @@
+use anyhow::{anyhow, Context as _, Result};
+use bootcamp_client::tx::{self, PriorityFee, TxBuilder};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+};
+
+/// 一次命令执行所需的 RPC 连接与付费钱包
+pub struct Context {
+    pub client: RpcClient,
+    pub payer: Keypair,
+    pub priority_fee: PriorityFee,
+}
+
+impl Context {
+    pub fn new(url: &str, keypair: Option<&str>, priority_fee: Option<u64>) -> Result<Self> {
+        let path = match keypair {
+            Some(path) => path.to_string(),
+            None => {
+                let home = std::env::var("HOME").context("未设置 HOME，请用 --keypair 指定钱包")?;
+                format!("{home}/.config/solana/id.json")
+            }
+        };
+        let payer = read_keypair(&path)?;
+        Ok(Self {
+            client: RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()),
+            payer,
+            priority_fee: priority_fee.map_or(PriorityFee::default(), PriorityFee::Fixed),
+        })
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// 由 payer 付费签名并发送，等待确认后打印签名。
+    /// 计算预算按模拟结果设置，blockhash 过期时自动重发；
+    /// 交易失败时说明是哪条指令、哪个 blueshift_errors 错误，并附上模拟日志。
+    pub fn send(&self, ixs: &[Instruction]) -> Result<()> {
+        self.send_with(ixs, &[])
+    }
+
+    /// 与 [`Context::send`] 相同，`signers` 是 payer 之外还需要签名的钱包（例如金库的共同签名者）
+    pub fn send_with(&self, ixs: &[Instruction], signers: &[&dyn Signer]) -> Result<()> {
+        let mut builder = TxBuilder::new(&self.client, &self.payer)
+            .instructions(ixs.iter().cloned())
+            .priority_fee(self.priority_fee);
+        for signer in signers {
+            builder = builder.signer(*signer);
+        }
+        let result = builder.send();
+        match result {
+            Ok(signature) => {
+                println!("{signature}");
+                Ok(())
+            }
+            Err(err) => match tx::failure(&err) {
+                Some(failure) => {
+                    let mut message = failure.to_string();
+                    for line in tx::simulation_logs(&err).unwrap_or_default() {
+                        message.push_str("\n    ");
+                        message.push_str(line);
+                    }
+                    Err(anyhow!(message))
+                }
+                None => Err(err.into()),
+            },
+        }
+    }
+
+    /// 账户所属的程序（例如 mint 属于 SPL Token 还是 Token-2022），账户不存在时报错
+    pub fn account_owner(&self, address: &Pubkey) -> Result<Pubkey> {
+        self.client
+            .get_account(address)
+            .map(|account| account.owner)
+            .with_context(|| format!("读取账户 {address} 失败"))
+    }
+
+    /// 读取账户数据，账户不存在时报错
+    pub fn account_data(&self, address: &Pubkey) -> Result<Vec<u8>> {
+        self.client
+            .get_account_data(address)
+            .with_context(|| format!("读取账户 {address} 失败"))
+    }
+
+    /// 读取账户数据，账户不存在时返回 None
+    pub fn try_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let response = self
+            .client
+            .get_account_with_commitment(address, self.client.commitment())
+            .with_context(|| format!("读取账户 {address} 失败"))?;
+        Ok(response.value.map(|account| account.data))
+    }
+
+    /// 代币账户的余额（SPL Token 账户偏移 64..72）
+    pub fn token_amount(&self, address: &Pubkey) -> Result<u64> {
+        let data = self.account_data(address)?;
+        data.get(64..72)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or_else(|| anyhow!("{address} 不是代币账户"))
+    }
+}
+
+/// 读取 solana-keygen 格式的钱包文件
+pub fn read_keypair(path: &str) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|e| anyhow!("读取钱包 {path} 失败: {e}"))
+}
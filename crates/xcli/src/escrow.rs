@@ -0,0 +1,256 @@
+//! Pinocchio 托管（task5）
+
+use anyhow::Result;
+use bootcamp_client::{
+    escrow::{
+        config_address, escrow_address, init_config_ix, make_ix, offer_index_address, refund_ix,
+        take_ix, take_partial_ix, top_up_ix, update_offer_ix, with_max_receive, Config, Escrow,
+        OfferIndex,
+    },
+    token::create_ata_idempotent_ix,
+};
+use clap::Subcommand;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::common::Context;
+
+#[derive(Subcommand)]
+pub enum EscrowCommand {
+    /// 存入 amount 个代币 A，要求对方支付 receive 个代币 B
+    Make {
+        #[arg(long)]
+        mint_a: Pubkey,
+        #[arg(long)]
+        mint_b: Pubkey,
+        #[arg(long)]
+        receive: u64,
+        #[arg(long)]
+        amount: u64,
+        /// 区分同一 maker 的多笔托管，默认取当前时间戳
+        #[arg(long)]
+        seed: Option<u64>,
+        /// 过期的 Unix 时间戳，过期后不能再被接受、任何人都可以退款；0 表示永不过期
+        #[arg(long, default_value_t = 0)]
+        expiry: i64,
+        /// 只允许该地址接受（场外约定成交），默认任何人都可以接受
+        #[arg(long)]
+        taker: Option<Pubkey>,
+    },
+    /// 接受 maker 的第 seed 笔托管：支付代币 B，取走金库中的代币 A
+    Take {
+        #[arg(long)]
+        maker: Pubkey,
+        #[arg(long)]
+        seed: u64,
+    },
+    /// 只买走金库中的 amount 个代币 A，按比例支付代币 B
+    TakePartial {
+        #[arg(long)]
+        maker: Pubkey,
+        #[arg(long)]
+        seed: u64,
+        #[arg(long)]
+        amount: u64,
+    },
+    /// 取消 maker 的第 seed 笔托管，代币 A 退回 maker
+    Refund {
+        #[arg(long)]
+        seed: u64,
+        /// 替别人退款（托管必须已经过期），默认是自己的托管
+        #[arg(long)]
+        maker: Option<Pubkey>,
+    },
+    /// 修改自己第 seed 笔托管的报价，金库中的代币 A 不动
+    UpdateOffer {
+        #[arg(long)]
+        seed: u64,
+        #[arg(long)]
+        receive: u64,
+        /// 换成另一种代币 B，默认保持原来的 mint_b
+        #[arg(long)]
+        mint_b: Option<Pubkey>,
+    },
+    /// 向自己第 seed 笔托管追加 amount 个代币 A，整单报价改为 receive
+    TopUp {
+        #[arg(long)]
+        seed: u64,
+        #[arg(long)]
+        amount: u64,
+        /// 追加之后整个金库的报价（不是增量）
+        #[arg(long)]
+        receive: u64,
+    },
+    /// 创建全局配置：Take 时从 maker 收到的代币 B 中按 fee_bps 抽取协议手续费给 treasury
+    /// （需要程序的升级权限，只能执行一次）
+    InitConfig {
+        /// 费率（基点），最高 1000
+        #[arg(long)]
+        fee_bps: u16,
+        #[arg(long)]
+        treasury: Pubkey,
+    },
+    /// 列出 maker 未成交的托管单（读取链上挂单索引，不发送交易）
+    Offers {
+        /// 默认列出自己的挂单
+        #[arg(long)]
+        maker: Option<Pubkey>,
+    },
+}
+
+/// 读取链上 Config：存在且费率非 0 时返回 treasury，并先创建它在 mint_b 上的 ATA；
+/// 否则不收手续费，treasury 传默认地址
+fn protocol_fee(
+    ctx: &Context,
+    program_id: &Pubkey,
+    escrow: &Escrow,
+    owed: u64,
+) -> Result<(Pubkey, Vec<Instruction>)> {
+    let Some(data) = ctx.try_account_data(&config_address(program_id).0)? else {
+        return Ok((Pubkey::default(), Vec::new()));
+    };
+    let config = Config::try_from_bytes(&data)?;
+    let fee = config.fee(owed);
+    if fee == 0 {
+        return Ok((config.treasury, Vec::new()));
+    }
+    println!("其中 {fee} 个作为协议手续费转给 {}", config.treasury);
+    let me = ctx.pubkey();
+    Ok((
+        config.treasury,
+        vec![create_ata_idempotent_ix(
+            &me,
+            &config.treasury,
+            &escrow.mint_b,
+        )],
+    ))
+}
+
+pub fn run(ctx: &Context, program_id: &Pubkey, cmd: EscrowCommand) -> Result<()> {
+    let me = ctx.pubkey();
+    let ixs = match cmd {
+        EscrowCommand::Make {
+            mint_a,
+            mint_b,
+            receive,
+            amount,
+            seed,
+            expiry,
+            taker,
+        } => {
+            let seed = match seed {
+                Some(seed) => seed,
+                None => std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)?
+                    .as_secs(),
+            };
+            let (escrow, _) = escrow_address(program_id, &me, seed);
+            println!("escrow: {escrow} (seed {seed})");
+            vec![make_ix(
+                program_id, &me, &mint_a, &mint_b, seed, receive, amount, expiry, taker,
+            )]
+        }
+        // take / refund 需要的 mint 从链上 escrow 账户读取
+        EscrowCommand::Take { maker, seed } => {
+            let (address, _) = escrow_address(program_id, &maker, seed);
+            let escrow = Escrow::try_from_bytes(&ctx.account_data(&address)?)?;
+            if !escrow.allows_taker(&me) {
+                anyhow::bail!("该托管只允许 {} 接受", escrow.taker);
+            }
+            println!(
+                "支付 {} 个 {}，换取金库中的 {}",
+                escrow.receive, escrow.mint_b, escrow.mint_a
+            );
+            let (treasury, mut ixs) = protocol_fee(ctx, program_id, &escrow, escrow.receive)?;
+            // 按这里显示的价格成交：maker 在此之前改价时交易失败
+            ixs.push(with_max_receive(
+                take_ix(program_id, &me, &address, &escrow, &treasury),
+                escrow.receive,
+            ));
+            ixs
+        }
+        EscrowCommand::TakePartial {
+            maker,
+            seed,
+            amount,
+        } => {
+            let (address, _) = escrow_address(program_id, &maker, seed);
+            let escrow = Escrow::try_from_bytes(&ctx.account_data(&address)?)?;
+            if !escrow.allows_taker(&me) {
+                anyhow::bail!("该托管只允许 {} 接受", escrow.taker);
+            }
+            let vault = ctx.token_amount(&escrow.vault(&address))?;
+            let owed = escrow.partial_receive(amount, vault).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "amount 必须在 1..={vault} 之间，且不是最后一笔时不能付清剩余的 {} 个代币 B",
+                    escrow.receive
+                )
+            })?;
+            println!(
+                "支付 {owed} 个 {}，换取金库中 {vault} 个 {} 里的 {amount} 个",
+                escrow.mint_b, escrow.mint_a
+            );
+            let (treasury, mut ixs) = protocol_fee(ctx, program_id, &escrow, owed)?;
+            ixs.push(with_max_receive(
+                take_partial_ix(program_id, &me, &address, &escrow, &treasury, amount),
+                owed,
+            ));
+            ixs
+        }
+        EscrowCommand::Refund { seed, maker } => {
+            let (address, _) = escrow_address(program_id, &maker.unwrap_or(me), seed);
+            let escrow = Escrow::try_from_bytes(&ctx.account_data(&address)?)?;
+            vec![refund_ix(program_id, &me, &address, &escrow)]
+        }
+        EscrowCommand::UpdateOffer {
+            seed,
+            receive,
+            mint_b,
+        } => {
+            let (address, _) = escrow_address(program_id, &me, seed);
+            let escrow = Escrow::try_from_bytes(&ctx.account_data(&address)?)?;
+            let mint_b = mint_b.unwrap_or(escrow.mint_b);
+            println!(
+                "报价 {} 个 {} → {receive} 个 {mint_b}",
+                escrow.receive, escrow.mint_b
+            );
+            vec![update_offer_ix(
+                program_id, &address, &escrow, &mint_b, receive,
+            )]
+        }
+        EscrowCommand::TopUp {
+            seed,
+            amount,
+            receive,
+        } => {
+            let (address, _) = escrow_address(program_id, &me, seed);
+            let escrow = Escrow::try_from_bytes(&ctx.account_data(&address)?)?;
+            let vault = ctx.token_amount(&escrow.vault(&address))?;
+            println!(
+                "金库 {vault} → {} 个 {}，报价 {} → {receive}",
+                vault + amount,
+                escrow.mint_a,
+                escrow.receive
+            );
+            vec![top_up_ix(program_id, &address, &escrow, amount, receive)]
+        }
+        EscrowCommand::InitConfig { fee_bps, treasury } => {
+            println!("config: {}", config_address(program_id).0);
+            vec![init_config_ix(program_id, &me, fee_bps, &treasury)]
+        }
+        EscrowCommand::Offers { maker } => {
+            let maker = maker.unwrap_or(me);
+            let (index, _) = offer_index_address(program_id, &maker);
+            // 从未挂过单的 maker 没有索引账户
+            let Some(data) = ctx.try_account_data(&index)? else {
+                println!("{maker} 没有挂单");
+                return Ok(());
+            };
+            let offers = OfferIndex::try_from_bytes(&data)?;
+            for (seed, escrow) in offers.seeds.iter().zip(offers.escrows(program_id, &maker)) {
+                println!("{escrow} (seed {seed})");
+            }
+            return Ok(());
+        }
+    };
+    ctx.send(&ixs)
+}
@@ -0,0 +1,102 @@
+//! xcli：在命令行里直接调用训练营的三个程序，不用再写 TypeScript 脚本。
+//!
+//! ```text
+//! xcli vault deposit 1000000000
+//! xcli escrow make --mint-a <A> --mint-b <B> --receive 10 --amount 20
+//! xcli amm swap --mint-x <X> --mint-y <Y> --x-to-y --amount 100 --min 95
+//! ```
+//!
+//! 程序 ID 默认取各程序 `declare_id!` / `ID` 中的值，部署到其它地址时用
+//! `--vault-program` 等参数覆盖。
+//!
+//! `--localnet` 连接 `cargo run -p localnet` 启动的本地节点，并默认用它的 0 号测试钱包付费。
+
+mod amm;
+mod common;
+mod escrow;
+mod vault;
+
+use std::path::Path;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::Context;
+
+#[derive(Parser)]
+#[command(
+    name = "xcli",
+    version,
+    about = "与 vault / escrow / amm 程序交互的命令行工具"
+)]
+struct Cli {
+    /// RPC 地址
+    #[arg(
+        long,
+        global = true,
+        env = "XCLI_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+
+    /// 付费并签名的钱包文件（默认 ~/.config/solana/id.json）
+    #[arg(long, global = true, env = "XCLI_KEYPAIR")]
+    keypair: Option<String>,
+
+    /// 连接 `cargo run -p localnet` 启动的节点（127.0.0.1:8899），钱包默认取其 0 号测试钱包
+    #[arg(long, global = true, conflicts_with = "url")]
+    localnet: bool,
+
+    /// 优先费（micro-lamports / CU）；不指定时取写入账户最近优先费的 75 分位
+    #[arg(long, global = true, env = "XCLI_PRIORITY_FEE")]
+    priority_fee: Option<u64>,
+
+    /// vault 程序 ID
+    #[arg(long, global = true, default_value_t = bootcamp_client::vault::PROGRAM_ID)]
+    vault_program: Pubkey,
+
+    /// escrow 程序 ID
+    #[arg(long, global = true, default_value_t = bootcamp_client::escrow::PROGRAM_ID)]
+    escrow_program: Pubkey,
+
+    /// amm 程序 ID
+    #[arg(long, global = true, default_value_t = bootcamp_client::amm::PROGRAM_ID)]
+    amm_program: Pubkey,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Anchor SOL 金库（task2）
+    #[command(subcommand)]
+    Vault(vault::VaultCommand),
+    /// Pinocchio 托管（task5）
+    #[command(subcommand)]
+    Escrow(escrow::EscrowCommand),
+    /// 原生 AMM（task6）
+    #[command(subcommand)]
+    Amm(amm::AmmCommand),
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let ctx = if cli.localnet {
+        let url = format!("http://127.0.0.1:{}", localnet::DEFAULT_RPC_PORT);
+        let wallet = localnet::wallet_path(Path::new(localnet::DEFAULT_DIR), 0);
+        let keypair = cli
+            .keypair
+            .unwrap_or_else(|| wallet.to_string_lossy().into_owned());
+        Context::new(&url, Some(&keypair), cli.priority_fee)?
+    } else {
+        Context::new(&cli.url, cli.keypair.as_deref(), cli.priority_fee)?
+    };
+
+    match cli.command {
+        Command::Vault(cmd) => vault::run(&ctx, &cli.vault_program, cmd),
+        Command::Escrow(cmd) => escrow::run(&ctx, &cli.escrow_program, cmd),
+        Command::Amm(cmd) => amm::run(&ctx, &cli.amm_program, cmd),
+    }
+}
@@ -0,0 +1,254 @@
+//! Anchor SOL 金库（task2）
+
+use anyhow::Result;
+use bootcamp_client::vault::{
+    close_metadata_ix, close_vault_ix, collect_fees_ix, create_policy_ix, deposit_locked_ix,
+    deposit_token_ix, emergency_pause_ix, extend_lock_ix, init_metadata_ix, initialize_config_ix,
+    open_stream_ix, set_cooldown_ix, set_delegate_ix, set_fee_ix, stream_address, sweep_ix,
+    top_up_ix, transfer_vault_ownership_ix, unpause_ix, unwrap_withdraw_ix, update_config_ix,
+    update_metadata_ix, update_policy_ix, with_co_signer, withdraw_as_delegate_ix, withdraw_ix,
+    withdraw_partial_ix, withdraw_to_ix, withdraw_token_ix, wrap_deposit_ix, Stream,
+};
+use clap::Subcommand;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+use crate::common::{read_keypair, Context};
+
+#[derive(Subcommand)]
+pub enum VaultCommand {
+    /// 存入 lamports（金库必须为空，且金额超过免租金最低限额）；--lock 秒内不能取款
+    Deposit {
+        amount: u64,
+        #[arg(long, default_value_t = 0)]
+        lock: u32,
+    },
+    /// 向已有余额的金库追加 lamports
+    TopUp { amount: u64 },
+    /// 取回金库中的全部 lamports；指定 --amount 时只取出这么多，金库保持免租金。
+    /// 金库设置了共同签名策略时用 --co-signer 指定共同签名者的钱包文件（下同）
+    Withdraw {
+        #[arg(long)]
+        amount: Option<u64>,
+        #[arg(long)]
+        co_signer: Option<String>,
+    },
+    /// 取回金库中的全部 lamports，直接转给 RECIPIENT
+    WithdrawTo {
+        recipient: Pubkey,
+        #[arg(long)]
+        co_signer: Option<String>,
+    },
+    /// 关闭金库：取回全部余额，并回收状态账户与流式支付账户的租金
+    Close {
+        #[arg(long)]
+        co_signer: Option<String>,
+    },
+    /// 把金库的全部 lamports 转入 NEW_OWNER 的金库（对方金库必须为空），并关闭原状态账户与流
+    TransferOwnership {
+        new_owner: Pubkey,
+        #[arg(long)]
+        co_signer: Option<String>,
+    },
+    /// 把 AMOUNT 个 MINT 代币存入金库（SPL Token 与 Token-2022 均可，代币程序按 mint 的所有者选择）
+    DepositToken { mint: Pubkey, amount: u64 },
+    /// 从金库取出 AMOUNT 个 MINT 代币到当前钱包的 ATA
+    WithdrawToken {
+        mint: Pubkey,
+        amount: u64,
+        #[arg(long)]
+        co_signer: Option<String>,
+    },
+    /// 把金库中的 AMOUNT lamports 包装成 wSOL，仍由金库持有
+    Wrap { amount: u64 },
+    /// 把金库持有的 wSOL 全部解包回金库的 SOL 余额
+    Unwrap,
+    /// 把解锁时间推迟到 unix 时间戳 UNLOCK_TIMESTAMP，不能提前
+    ExtendLock { unlock_timestamp: i64 },
+    /// 指定可以代为取出全部余额的公钥；省略 DELEGATE 时取消代理
+    SetDelegate { delegate: Option<Pubkey> },
+    /// 以代理人身份取出 OWNER 金库的全部 lamports（当前钱包必须是 OWNER 指定的代理人）
+    WithdrawAsDelegate {
+        owner: Pubkey,
+        #[arg(long)]
+        co_signer: Option<String>,
+    },
+    /// 开启流式支付：每秒向 RECIPIENT 释放 RATE_PER_SECOND lamports
+    OpenStream {
+        recipient: Pubkey,
+        rate_per_second: u64,
+        #[arg(long)]
+        co_signer: Option<String>,
+    },
+    /// 结算 OWNER 金库的流式支付（任何人都可以发送），收款人从链上的 Stream 账户读取
+    ClaimStream { owner: Pubkey },
+    /// 创建全局存款限制（当前钱包必须是程序的升级权限），ADMIN 之后可以修改
+    InitConfig {
+        admin: Pubkey,
+        #[arg(long, default_value_t = u64::MAX)]
+        max_vault_balance: u64,
+        #[arg(long, default_value_t = 0)]
+        min_deposit: u64,
+    },
+    /// 修改全局存款限制（当前钱包必须是配置中的管理员）
+    UpdateConfig {
+        #[arg(long, default_value_t = u64::MAX)]
+        max_vault_balance: u64,
+        #[arg(long, default_value_t = 0)]
+        min_deposit: u64,
+    },
+    /// 暂停 deposit / top_up（当前钱包必须是配置中的管理员），取款不受影响
+    Pause,
+    /// 恢复存款
+    Unpause,
+    /// 设置存款手续费率（基点，上限 1000），0 表示不收取；需要管理员
+    SetFee { bps: u16 },
+    /// 把手续费国库中超出免租金限额的部分转给管理员
+    CollectFees,
+    /// 创建共同签名策略：之后取款需要 CO_SIGNER 一同签名
+    CreatePolicy { co_signer: Pubkey },
+    /// 更换共同签名者，省略 NEW_CO_SIGNER 时删除策略；CO_SIGNER 是当前共同签名者的钱包文件
+    UpdatePolicy {
+        co_signer: String,
+        new_co_signer: Option<Pubkey>,
+    },
+    /// 两次取款之间至少间隔 SECONDS 秒，0 删除冷却；冷却期内只能延长
+    SetCooldown { seconds: u32 },
+    /// 为金库创建元数据：LABEL 不超过 32 字节，--notes 不超过 256 字节
+    InitMetadata {
+        label: String,
+        #[arg(long, default_value = "")]
+        notes: String,
+    },
+    /// 替换金库的标签与备注，创建时间不变
+    UpdateMetadata {
+        label: String,
+        #[arg(long, default_value = "")]
+        notes: String,
+    },
+    /// 删除金库元数据，取回租金
+    CloseMetadata,
+    /// 一次取回 INDICES 各子金库的全部 lamports，空的子金库被跳过
+    Sweep {
+        #[arg(required = true)]
+        indices: Vec<u8>,
+        #[arg(long)]
+        co_signer: Option<String>,
+    },
+}
+
+pub fn run(ctx: &Context, program_id: &Pubkey, cmd: VaultCommand) -> Result<()> {
+    let signer = ctx.pubkey();
+    let co_signer = match &cmd {
+        VaultCommand::Withdraw { co_signer, .. }
+        | VaultCommand::WithdrawTo { co_signer, .. }
+        | VaultCommand::Close { co_signer }
+        | VaultCommand::TransferOwnership { co_signer, .. }
+        | VaultCommand::WithdrawToken { co_signer, .. }
+        | VaultCommand::WithdrawAsDelegate { co_signer, .. }
+        | VaultCommand::OpenStream { co_signer, .. }
+        | VaultCommand::Sweep { co_signer, .. } => {
+            co_signer.as_deref().map(read_keypair).transpose()?
+        }
+        VaultCommand::UpdatePolicy { co_signer, .. } => Some(read_keypair(co_signer)?),
+        _ => None,
+    };
+    // 取款类指令在末尾追加共同签名者；未指定时与没有策略的金库相同
+    let cosign = |ix| match &co_signer {
+        Some(keypair) => with_co_signer(ix, &keypair.pubkey()),
+        None => ix,
+    };
+    let ix = match cmd {
+        VaultCommand::Deposit { amount, lock } => {
+            deposit_locked_ix(program_id, &signer, amount, lock)
+        }
+        VaultCommand::TopUp { amount } => top_up_ix(program_id, &signer, amount),
+        VaultCommand::Withdraw { amount: None, .. } => cosign(withdraw_ix(program_id, &signer)),
+        VaultCommand::Withdraw {
+            amount: Some(amount),
+            ..
+        } => cosign(withdraw_partial_ix(program_id, &signer, amount)),
+        VaultCommand::WithdrawTo { recipient, .. } => {
+            cosign(withdraw_to_ix(program_id, &signer, &recipient))
+        }
+        VaultCommand::Close { .. } => cosign(close_vault_ix(program_id, &signer)),
+        VaultCommand::TransferOwnership { new_owner, .. } => {
+            cosign(transfer_vault_ownership_ix(program_id, &signer, &new_owner))
+        }
+        VaultCommand::DepositToken { mint, amount } => {
+            let token_program = ctx.account_owner(&mint)?;
+            deposit_token_ix(program_id, &signer, &mint, &token_program, amount)
+        }
+        VaultCommand::WithdrawToken { mint, amount, .. } => {
+            let token_program = ctx.account_owner(&mint)?;
+            cosign(withdraw_token_ix(
+                program_id,
+                &signer,
+                &mint,
+                &token_program,
+                amount,
+            ))
+        }
+        VaultCommand::Wrap { amount } => wrap_deposit_ix(program_id, &signer, amount),
+        VaultCommand::Unwrap => unwrap_withdraw_ix(program_id, &signer),
+        VaultCommand::ExtendLock { unlock_timestamp } => {
+            extend_lock_ix(program_id, &signer, unlock_timestamp)
+        }
+        VaultCommand::SetDelegate { delegate } => {
+            set_delegate_ix(program_id, &signer, delegate.as_ref())
+        }
+        VaultCommand::WithdrawAsDelegate { owner, .. } => {
+            cosign(withdraw_as_delegate_ix(program_id, &signer, &owner))
+        }
+        VaultCommand::OpenStream {
+            recipient,
+            rate_per_second,
+            ..
+        } => cosign(open_stream_ix(
+            program_id,
+            &signer,
+            &recipient,
+            rate_per_second,
+        )),
+        VaultCommand::ClaimStream { owner } => {
+            let address = stream_address(program_id, &owner).0;
+            let stream = Stream::try_from_bytes(&ctx.account_data(&address)?)?;
+            stream.claim_ix(program_id)
+        }
+        VaultCommand::InitConfig {
+            admin,
+            max_vault_balance,
+            min_deposit,
+        } => initialize_config_ix(program_id, &signer, &admin, max_vault_balance, min_deposit),
+        VaultCommand::UpdateConfig {
+            max_vault_balance,
+            min_deposit,
+        } => update_config_ix(program_id, &signer, max_vault_balance, min_deposit),
+        VaultCommand::Pause => emergency_pause_ix(program_id, &signer),
+        VaultCommand::Unpause => unpause_ix(program_id, &signer),
+        VaultCommand::SetFee { bps } => set_fee_ix(program_id, &signer, bps),
+        VaultCommand::CollectFees => collect_fees_ix(program_id, &signer),
+        VaultCommand::CreatePolicy { co_signer } => {
+            create_policy_ix(program_id, &signer, &co_signer)
+        }
+        VaultCommand::UpdatePolicy { new_co_signer, .. } => {
+            let current = co_signer.as_ref().expect("UpdatePolicy 总会读取共同签名者");
+            update_policy_ix(
+                program_id,
+                &signer,
+                &current.pubkey(),
+                new_co_signer.as_ref(),
+            )
+        }
+        VaultCommand::SetCooldown { seconds } => set_cooldown_ix(program_id, &signer, seconds),
+        VaultCommand::InitMetadata { label, notes } => {
+            init_metadata_ix(program_id, &signer, &label, &notes)
+        }
+        VaultCommand::UpdateMetadata { label, notes } => {
+            update_metadata_ix(program_id, &signer, &label, &notes)
+        }
+        VaultCommand::CloseMetadata => close_metadata_ix(program_id, &signer),
+        VaultCommand::Sweep { indices, .. } => cosign(sweep_ix(program_id, &signer, &indices)),
+    };
+    let signers: Vec<&dyn Signer> = co_signer.iter().map(|k| k as &dyn Signer).collect();
+    ctx.send_with(&[ix], &signers)
+}
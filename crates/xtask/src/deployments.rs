@@ -0,0 +1,107 @@
+//! 仓库根目录的 `deployments.json`：每个集群上各程序实际部署的地址。
+//!
+//! `cargo xtask deploy` 成功后写入，`cargo xtask idl` 据此填写 IDL 合集中的各集群地址。
+//! 文件按集群、程序名排序，便于在 PR 中审阅地址变更。
+//!
+//! ```json
+//! {
+//!   "devnet": {
+//!     "escrow": "Esc1…"
+//!   }
+//! }
+//! ```
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{Context, Result};
+
+pub const FILE: &str = "deployments.json";
+
+/// 集群 → 程序名 → 地址
+#[derive(Debug, Default, PartialEq)]
+pub struct Deployments(pub BTreeMap<String, BTreeMap<String, String>>);
+
+impl Deployments {
+    /// 文件不存在时返回空表
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json = fs::read_to_string(&path)?;
+        let table = serde_json::from_str(&json)
+            .with_context(|| format!("{} 格式不正确", path.display()))?;
+        Ok(Self(table))
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let mut json = serde_json::to_string_pretty(&self.0)?;
+        json.push('\n');
+        fs::write(root.join(FILE), json)?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, cluster: &str, program: &str, address: &str) {
+        self.0
+            .entry(cluster_name(cluster))
+            .or_default()
+            .insert(program.to_string(), address.to_string());
+    }
+
+    /// `program` 在各集群上的地址
+    pub fn addresses(&self, program: &str) -> BTreeMap<String, String> {
+        self.0
+            .iter()
+            .filter_map(|(cluster, programs)| {
+                Some((cluster.clone(), programs.get(program)?.clone()))
+            })
+            .collect()
+    }
+}
+
+/// 把 `solana --url` 接受的写法统一成集群名；自定义 RPC 地址原样保留
+pub fn cluster_name(cluster: &str) -> String {
+    let name = match cluster.trim_end_matches('/') {
+        "l" | "localhost" | "localnet" | "http://localhost:8899" | "http://127.0.0.1:8899" => {
+            "localnet"
+        }
+        "d" | "devnet" | "https://api.devnet.solana.com" => "devnet",
+        "t" | "testnet" | "https://api.testnet.solana.com" => "testnet",
+        "m" | "mainnet-beta" | "https://api.mainnet-beta.solana.com" => "mainnet-beta",
+        other => other,
+    };
+    name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cluster_aliases_share_one_entry() {
+        let mut deployments = Deployments::default();
+        deployments.record("d", "escrow", "Old");
+        deployments.record("https://api.devnet.solana.com/", "escrow", "New");
+        deployments.record("localhost", "escrow", "Local");
+        deployments.record("http://my-rpc:8899", "amm", "Amm");
+
+        let escrow = deployments.addresses("escrow");
+        assert_eq!(escrow.len(), 2);
+        assert_eq!(escrow["devnet"], "New");
+        assert_eq!(escrow["localnet"], "Local");
+        assert_eq!(deployments.addresses("amm")["http://my-rpc:8899"], "Amm");
+    }
+
+    #[test]
+    fn round_trips_through_file() {
+        let root = std::env::temp_dir().join(format!("xtask_deployments_{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        assert_eq!(Deployments::load(&root).unwrap(), Deployments::default());
+
+        let mut deployments = Deployments::default();
+        deployments.record("devnet", "vault", "Vault111");
+        deployments.save(&root).unwrap();
+        assert_eq!(Deployments::load(&root).unwrap(), deployments);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,234 @@
+//! 把各程序构建时生成的 IDL 合成一个文件，供前端与机器人统一使用。
+//!
+//! Anchor 程序的 IDL 由 `anchor build` 生成，Pinocchio 程序的由 build.rs（blueshift_idl，
+//! shank 格式）生成。合集里每个程序带上格式、源码声明的地址与 `deployments.json` 中
+//! 各集群的部署地址：
+//!
+//! ```json
+//! {
+//!   "bundleVersion": 1,
+//!   "commit": "0db21a4…",
+//!   "dirty": false,
+//!   "programs": {
+//!     "escrow": {
+//!       "artifact": "blueshift_escrow",
+//!       "format": "shank",
+//!       "address": "2222…",
+//!       "clusters": { "devnet": "Esc1…" },
+//!       "idl": { … }
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! build.rs 里写死的是占位地址，`sync-ids` 之后会与源码不一致，所以合集中 IDL 自带的地址
+//! 一律改写为源码当前声明的 ID。IDL 文件比程序源码旧时拒绝打包，避免发布过期的接口。
+
+use std::{collections::BTreeMap, fs, io, path::Path, process::Command, time::SystemTime};
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    deployments::Deployments,
+    ids,
+    programs::{IdlFormat, Program},
+};
+
+/// 合集格式的版本，字段含义变化时递增
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// 读取 `program` 的 IDL，检查是否过期，返回合集中的一项
+pub fn load(root: &Path, program: &Program, deployments: &Deployments) -> Result<Value> {
+    let format = program.idl.context("程序没有 IDL")?;
+    let path = program.idl_path(root);
+    let idl_modified = fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .with_context(|| {
+            format!(
+                "{} 不存在，先执行 cargo xtask build -p {}",
+                path.display(),
+                program.name
+            )
+        })?;
+    let source_modified = newest_modified(&program.crate_dir(root))?;
+    if idl_modified < source_modified {
+        bail!(
+            "{} 比 {} 的源码旧，先执行 cargo xtask build -p {}",
+            path.display(),
+            program.name,
+            program.name
+        );
+    }
+
+    let idl: Value = serde_json::from_str(&fs::read_to_string(&path)?)
+        .with_context(|| format!("{} 不是合法的 JSON", path.display()))?;
+    let source = fs::read_to_string(program.dir(root).join(program.id_source))?;
+    let address = ids::declared_id(&source, program.id_decl)?;
+    let clusters = deployments.addresses(program.name);
+    for (cluster, deployed) in &clusters {
+        if *deployed != address {
+            eprintln!(
+                "警告：{} 在 {cluster} 上部署于 {deployed}，与源码声明的 {address} 不一致",
+                program.name
+            );
+        }
+    }
+    Ok(entry(program, format, idl, &address, clusters))
+}
+
+/// 合集中的一项；IDL 自带的地址改写为 `address`
+fn entry(
+    program: &Program,
+    format: IdlFormat,
+    mut idl: Value,
+    address: &str,
+    clusters: BTreeMap<String, String>,
+) -> Value {
+    match format {
+        IdlFormat::Anchor => idl["address"] = json!(address),
+        IdlFormat::Shank => idl["metadata"]["address"] = json!(address),
+    }
+    json!({
+        "artifact": program.artifact,
+        "format": format.as_str(),
+        "address": address,
+        "clusters": clusters,
+        "idl": idl,
+    })
+}
+
+/// 以当前 git 提交为版本组装合集
+pub fn bundle(root: &Path, programs: Map<String, Value>) -> Value {
+    let commit = git(root, &["rev-parse", "HEAD"]);
+    let dirty = git(root, &["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.is_empty());
+    json!({
+        "bundleVersion": BUNDLE_VERSION,
+        "commit": commit,
+        "dirty": dirty,
+        "programs": programs,
+    })
+}
+
+/// git 不可用（例如源码包）时返回 `None`
+fn git(root: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// `dir` 下（不含 target）最近修改的文件时间
+fn newest_modified(dir: &Path) -> io::Result<SystemTime> {
+    let mut newest = SystemTime::UNIX_EPOCH;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let meta = entry.metadata()?;
+        let modified = if meta.is_dir() {
+            if entry.file_name() == "target" {
+                continue;
+            }
+            newest_modified(&entry.path())?
+        } else {
+            meta.modified()?
+        };
+        newest = newest.max(modified);
+    }
+    Ok(newest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::programs::PROGRAMS;
+
+    fn program(name: &str) -> &'static Program {
+        PROGRAMS
+            .iter()
+            .find(|program| program.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn entry_rewrites_address_by_format() {
+        let clusters = BTreeMap::from([("devnet".to_string(), "Dev1".to_string())]);
+        let shank = json!({ "name": "blueshift_escrow", "metadata": { "origin": "shank", "address": "2222" } });
+        let escrow = entry(
+            program("escrow"),
+            IdlFormat::Shank,
+            shank,
+            "Esc1",
+            clusters.clone(),
+        );
+        assert_eq!(escrow["idl"]["metadata"]["address"], "Esc1");
+        assert_eq!(escrow["idl"]["metadata"]["origin"], "shank");
+        assert_eq!(escrow["clusters"]["devnet"], "Dev1");
+        assert_eq!(escrow["format"], "shank");
+
+        let anchor = json!({ "address": "2222", "metadata": { "name": "blueshift_anchor_vault" } });
+        let vault = entry(
+            program("anchor-vault"),
+            IdlFormat::Anchor,
+            anchor,
+            "Vlt1",
+            clusters,
+        );
+        assert_eq!(vault["idl"]["address"], "Vlt1");
+        assert_eq!(vault["address"], "Vlt1");
+        assert_eq!(vault["artifact"], "blueshift_anchor_vault");
+    }
+
+    #[test]
+    fn stale_or_missing_idl_is_rejected() {
+        let root = std::env::temp_dir().join(format!("xtask_idl_{}", std::process::id()));
+        let escrow = program("escrow");
+        let crate_dir = escrow.crate_dir(&root);
+        fs::create_dir_all(crate_dir.join("src")).unwrap();
+        let placeholder = "22222222222222222222222222222222222222222222";
+        let bytes: Vec<String> = bs58::decode(placeholder)
+            .into_vec()
+            .unwrap()
+            .iter()
+            .map(|byte| format!("0x{byte:02x}"))
+            .collect();
+        fs::write(
+            crate_dir.join("src/lib.rs"),
+            format!(
+                "pub const ID: Address = Address::new_from_array([{}]);\n",
+                bytes.join(", ")
+            ),
+        )
+        .unwrap();
+        let deployments = Deployments::default();
+        assert!(load(&root, escrow, &deployments)
+            .unwrap_err()
+            .to_string()
+            .contains("不存在"));
+
+        let idl_path = escrow.idl_path(&root);
+        fs::create_dir_all(idl_path.parent().unwrap()).unwrap();
+        fs::write(&idl_path, r#"{ "metadata": { "address": "x" } }"#).unwrap();
+        let old = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1);
+        fs::File::options()
+            .write(true)
+            .open(&idl_path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+        assert!(load(&root, escrow, &deployments)
+            .unwrap_err()
+            .to_string()
+            .contains("比 escrow 的源码旧"));
+
+        fs::write(&idl_path, r#"{ "metadata": { "address": "x" } }"#).unwrap();
+        let entry = load(&root, escrow, &deployments).unwrap();
+        assert_eq!(entry["address"], placeholder);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,218 @@
+//! 把源码里声明的程序 ID 改成部署密钥对应的地址。
+//!
+//! 训练营的程序默认都声明 2222…2222（Blueshift 挑战要求），部署到同一个集群时必须各自换成
+//! 自己的地址。这里只做文本替换，保持原文件的排版与注释不变。
+
+use std::ops::Range;
+
+use anyhow::{bail, ensure, Context, Result};
+
+use crate::programs::IdDecl;
+
+/// 读取 solana-keygen 写出的密钥文件（64 个字节的 JSON 数组），返回后 32 字节的公钥
+pub fn keypair_pubkey(json: &str) -> Result<[u8; 32]> {
+    let bytes = json
+        .trim()
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .context("密钥文件不是 JSON 数组")?
+        .split(',')
+        .map(|byte| byte.trim().parse::<u8>())
+        .collect::<Result<Vec<_>, _>>()
+        .context("密钥文件中有非字节的元素")?;
+    ensure!(
+        bytes.len() == 64,
+        "密钥文件应有 64 个字节，实际 {}",
+        bytes.len()
+    );
+    Ok(bytes[32..].try_into().expect("长度已检查"))
+}
+
+/// `declare_id!("...")`：替换未注释的那一行中的地址，返回原地址
+pub fn patch_declare_id(source: &mut String, address: &str) -> Result<String> {
+    let span = declare_id_span(source)?;
+    let old = source[span.clone()].to_string();
+    source.replace_range(span, address);
+    Ok(old)
+}
+
+/// 未注释的 `declare_id!` 中地址所在的字节范围
+fn declare_id_span(source: &str) -> Result<Range<usize>> {
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        if line.trim_start().starts_with("declare_id!(\"") {
+            let start = offset + line.find('"').expect("以引号开头") + 1;
+            let len = source[start..]
+                .find('"')
+                .context("declare_id! 缺少右引号")?;
+            return Ok(start..start + len);
+        }
+        offset += line.len();
+    }
+    bail!("没有找到 declare_id!")
+}
+
+/// 源码中当前声明的程序 ID（base58）
+pub fn declared_id(source: &str, decl: IdDecl) -> Result<String> {
+    match decl {
+        IdDecl::Macro => Ok(source[declare_id_span(source)?].to_string()),
+        IdDecl::Bytes => {
+            let (open, close) = id_bytes_span(source)?;
+            let mut bytes = Vec::with_capacity(32);
+            let mut rest = &source[open..close];
+            while let Some(at) = rest.find("0x") {
+                let hex = rest.get(at + 2..at + 4).context("ID 字节不完整")?;
+                bytes.push(u8::from_str_radix(hex, 16).context("ID 字节不是十六进制")?);
+                rest = &rest[at + 4..];
+            }
+            ensure!(bytes.len() == 32, "ID 应有 32 个字节，实际 {}", bytes.len());
+            Ok(bs58::encode(bytes).into_string())
+        }
+        IdDecl::None => bail!("程序不声明自己的 ID"),
+    }
+}
+
+/// `pub const ID` 声明中 `[` 与 `]` 的位置
+fn id_bytes_span(source: &str) -> Result<(usize, usize)> {
+    let decl = source
+        .find("pub const ID")
+        .context("没有找到 pub const ID")?;
+    let open = decl + source[decl..].find('[').context("ID 声明缺少 [")?;
+    let close = open + source[open..].find(']').context("ID 声明缺少 ]")?;
+    Ok((open, close))
+}
+
+/// `pub const ID` 后的 32 个 `0x..` 字节：逐个替换，保留原来的换行与缩进；
+/// 声明前几行注释里出现的旧地址一并替换。返回原地址
+pub fn patch_id_bytes(source: &mut String, address: &[u8; 32]) -> Result<String> {
+    let decl = source
+        .find("pub const ID")
+        .context("没有找到 pub const ID")?;
+    let (open, close) = id_bytes_span(source)?;
+
+    let mut old = Vec::with_capacity(32);
+    let mut patched = String::with_capacity(close - open);
+    let mut rest = &source[open..close];
+    while let Some(at) = rest.find("0x") {
+        let hex = rest.get(at + 2..at + 4).context("ID 字节不完整")?;
+        old.push(u8::from_str_radix(hex, 16).context("ID 字节不是十六进制")?);
+        ensure!(old.len() <= 32, "ID 超过 32 个字节");
+        patched.push_str(&rest[..at]);
+        patched.push_str(&format!("0x{:02x}", address[old.len() - 1]));
+        rest = &rest[at + 4..];
+    }
+    ensure!(old.len() == 32, "ID 应有 32 个字节，实际 {}", old.len());
+    patched.push_str(rest);
+    source.replace_range(open..close, &patched);
+
+    // 声明上方的注释通常写着 base58 地址
+    let old = bs58::encode(&old).into_string();
+    let new = bs58::encode(address).into_string();
+    let comments_start = source[..decl]
+        .rmatch_indices('\n')
+        .nth(6)
+        .map_or(0, |(at, _)| at);
+    let comments = source[comments_start..decl].replace(&old, &new);
+    source.replace_range(comments_start..decl, &comments);
+    Ok(old)
+}
+
+/// Anchor.toml 各个 `[programs.<cluster>]` 段中 `<name> = "..."` 的地址
+pub fn patch_anchor_toml(toml: &str, name: &str, address: &str) -> String {
+    let mut in_programs = false;
+    toml.split_inclusive('\n')
+        .map(|line| {
+            let trimmed = line.trim();
+            if trimmed.starts_with('[') {
+                in_programs = trimmed.starts_with("[programs.");
+            } else if in_programs
+                && trimmed
+                    .split_once('=')
+                    .is_some_and(|(key, _)| key.trim() == name)
+            {
+                let newline = if line.ends_with('\n') { "\n" } else { "" };
+                return format!("{name} = \"{address}\"{newline}");
+            }
+            line.to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLACEHOLDER: &str = "22222222222222222222222222222222222222222222";
+
+    fn address() -> [u8; 32] {
+        core::array::from_fn(|i| i as u8 + 1)
+    }
+
+    #[test]
+    fn pubkey_is_second_half_of_keypair() {
+        let bytes: Vec<String> = (0..64).map(|i| i.to_string()).collect();
+        let json = format!("[{}]\n", bytes.join(","));
+        assert_eq!(
+            keypair_pubkey(&json).unwrap(),
+            core::array::from_fn(|i| i as u8 + 32)
+        );
+        assert!(keypair_pubkey("[1,2,3]").is_err());
+        assert!(keypair_pubkey("{}").is_err());
+    }
+
+    #[test]
+    fn declare_id_skips_commented_line() {
+        let mut source =
+            format!("// declare_id!(\"{PLACEHOLDER}1\");\ndeclare_id!(\"{PLACEHOLDER}\");\n");
+        assert_eq!(declared_id(&source, IdDecl::Macro).unwrap(), PLACEHOLDER);
+        let old = patch_declare_id(&mut source, "New1111").unwrap();
+        assert_eq!(old, PLACEHOLDER);
+        assert_eq!(
+            source,
+            format!("// declare_id!(\"{PLACEHOLDER}1\");\ndeclare_id!(\"New1111\");\n")
+        );
+    }
+
+    #[test]
+    fn id_bytes_keep_layout_and_comment() {
+        // task4 金库的写法：注释里的 base58 + 每行 8 个字节
+        let placeholder = bs58::decode(PLACEHOLDER).into_vec().unwrap();
+        let rows: Vec<String> = placeholder
+            .chunks(8)
+            .map(|row| {
+                let row: Vec<_> = row.iter().map(|byte| format!("0x{byte:02x}")).collect();
+                format!("    {},\n", row.join(", "))
+            })
+            .collect();
+        let mut source = format!(
+            "/// 程序 ID: {PLACEHOLDER}\npub const ID: Address = Address::new_from_array([\n{}]);\n\nfn f() {{ let _ = [0x01]; }}\n",
+            rows.concat()
+        );
+        let before_lines = source.lines().count();
+
+        assert_eq!(declared_id(&source, IdDecl::Bytes).unwrap(), PLACEHOLDER);
+        let old = patch_id_bytes(&mut source, &address()).unwrap();
+        assert_eq!(old, PLACEHOLDER);
+        assert_eq!(source.lines().count(), before_lines);
+        let new = bs58::encode(address()).into_string();
+        assert!(source.starts_with(&format!("/// 程序 ID: {new}\n")));
+        assert!(source.contains("    0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,\n"));
+        assert!(source.ends_with("let _ = [0x01]; }\n"));
+
+        let mut short = "pub const ID: Pubkey = [0x01, 0x02];".to_string();
+        assert!(declared_id(&short, IdDecl::Bytes).is_err());
+        assert!(patch_id_bytes(&mut short, &address()).is_err());
+    }
+
+    #[test]
+    fn anchor_toml_only_touches_program_sections() {
+        let toml = format!(
+            "[programs.localnet]\nvault = \"{PLACEHOLDER}\"\nother = \"x\"\n\n[programs.devnet]\nvault = \"{PLACEHOLDER}\"\n\n[provider]\nvault = \"keep\"\n"
+        );
+        let patched = patch_anchor_toml(&toml, "vault", "New1111");
+        assert_eq!(
+            patched,
+            "[programs.localnet]\nvault = \"New1111\"\nother = \"x\"\n\n[programs.devnet]\nvault = \"New1111\"\n\n[provider]\nvault = \"keep\"\n"
+        );
+    }
+}
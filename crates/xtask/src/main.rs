@@ -0,0 +1,302 @@
+//! xtask：仓库里所有链上程序的构建与部署入口。
+//!
+//! ```text
+//! cargo xtask build                          # 构建全部程序（anchor build / cargo build-sbf）
+//! cargo xtask keys                           # 为缺少密钥的程序生成 target/deploy/<name>-keypair.json
+//! cargo xtask sync-ids -p escrow -p amm      # 把源码中的程序 ID 改成密钥对应的地址
+//! cargo xtask deploy --cluster devnet        # keys → sync-ids → build → solana program deploy
+//! cargo xtask idl                            # 合并各程序的 IDL 到 target/idl/bundle.json
+//! ```
+//!
+//! 程序默认都声明 2222…2222，同一集群上只能部署其中一个；`sync-ids` / `deploy` 会改写
+//! 源码中的 ID（与 Anchor.toml），让每个程序使用自己的地址。e2e 测试与 bootcamp_client 的
+//! 默认地址仍是占位地址，部署后调用时用 xcli 的 `--vault-program` 等参数指定实际地址。
+//! 部署成功的地址记录在仓库根目录的 deployments.json 中，`idl` 把它们写进 IDL 合集。
+
+mod deployments;
+mod idl;
+mod ids;
+mod programs;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use serde_json::Map;
+
+use crate::{
+    deployments::Deployments,
+    programs::{IdDecl, Program, Toolchain},
+};
+
+#[derive(Parser)]
+#[command(
+    name = "xtask",
+    bin_name = "cargo xtask",
+    about = "构建、生成密钥、同步程序 ID 并部署训练营的程序"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Cmd,
+}
+
+#[derive(Args)]
+struct Selection {
+    /// 只处理这些程序（可重复）；不指定时处理全部
+    #[arg(short, long = "program", value_name = "NAME")]
+    programs: Vec<String>,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// 列出可用的程序及其当前密钥地址
+    List,
+    /// 构建 .so
+    Build {
+        #[command(flatten)]
+        selection: Selection,
+        /// 嵌入 security.txt（链上部署时建议打开）
+        #[arg(long)]
+        security_txt: bool,
+    },
+    /// 为缺少密钥的程序生成部署密钥
+    Keys {
+        #[command(flatten)]
+        selection: Selection,
+    },
+    /// 把源码中声明的程序 ID 改成部署密钥对应的地址
+    SyncIds {
+        #[command(flatten)]
+        selection: Selection,
+    },
+    /// 生成密钥、同步 ID、构建并部署
+    Deploy {
+        #[command(flatten)]
+        selection: Selection,
+        /// 目标集群：localhost / devnet / mainnet-beta 或 RPC 地址
+        #[arg(long, default_value = "localhost")]
+        cluster: String,
+        /// 付费钱包（默认使用 solana CLI 的配置）
+        #[arg(long)]
+        keypair: Option<PathBuf>,
+        /// 不重新构建，直接部署 target/deploy 下已有的 .so
+        #[arg(long)]
+        skip_build: bool,
+        /// 嵌入 security.txt
+        #[arg(long)]
+        security_txt: bool,
+    },
+    /// 把各程序的 Anchor / shank IDL 与各集群地址合并成一个带版本的文件
+    Idl {
+        #[command(flatten)]
+        selection: Selection,
+        /// 输出路径（默认 target/idl/bundle.json）
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// 跳过尚未生成 IDL 的程序，而不是报错
+        #[arg(long)]
+        allow_missing: bool,
+    },
+}
+
+fn main() -> Result<()> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let root = root.canonicalize().context("找不到仓库根目录")?;
+
+    match Cli::parse().command {
+        Cmd::List => {
+            for program in programs::PROGRAMS {
+                let address = read_address(&program.keypair(&root))
+                    .unwrap_or_else(|_| "（尚无密钥）".to_string());
+                println!("{:<14} {:<62} {address}", program.name, program.dir);
+            }
+        }
+        Cmd::Build {
+            selection,
+            security_txt,
+        } => {
+            for program in programs::select(&selection.programs)? {
+                build(&root, program, security_txt)?;
+            }
+        }
+        Cmd::Keys { selection } => {
+            for program in programs::select(&selection.programs)? {
+                let address = ensure_keypair(&root, program)?;
+                println!("{}: {address}", program.name);
+            }
+        }
+        Cmd::SyncIds { selection } => {
+            for program in programs::select(&selection.programs)? {
+                sync_id(&root, program)?;
+            }
+        }
+        Cmd::Deploy {
+            selection,
+            cluster,
+            keypair,
+            skip_build,
+            security_txt,
+        } => {
+            for program in programs::select(&selection.programs)? {
+                let address = ensure_keypair(&root, program)?;
+                sync_id(&root, program)?;
+                if !skip_build {
+                    build(&root, program, security_txt)?;
+                }
+                deploy(&root, program, &cluster, keypair.as_deref())?;
+                // 每个程序部署成功后立即记录，后面的程序失败时前面的地址也不会丢
+                let mut deployments = Deployments::load(&root)?;
+                deployments.record(&cluster, program.name, &address);
+                deployments.save(&root)?;
+            }
+        }
+        Cmd::Idl {
+            selection,
+            out,
+            allow_missing,
+        } => {
+            let deployments = Deployments::load(&root)?;
+            let mut programs = Map::new();
+            for program in programs::select(&selection.programs)? {
+                if program.idl.is_none() {
+                    if !selection.programs.is_empty() {
+                        bail!("{} 没有 IDL", program.name);
+                    }
+                    continue;
+                }
+                match idl::load(&root, program, &deployments) {
+                    Ok(entry) => {
+                        programs.insert(program.name.to_string(), entry);
+                    }
+                    Err(err) if allow_missing => eprintln!("跳过 {}: {err:#}", program.name),
+                    Err(err) => return Err(err),
+                }
+            }
+            let out = out.unwrap_or_else(|| root.join("target/idl/bundle.json"));
+            if let Some(dir) = out.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let count = programs.len();
+            let mut json = serde_json::to_string_pretty(&idl::bundle(&root, programs))?;
+            json.push('\n');
+            fs::write(&out, json)?;
+            println!("{count} 个程序的 IDL 已写入 {}", out.display());
+        }
+    }
+    Ok(())
+}
+
+/// 执行外部命令，失败时带上完整命令行
+fn run(command: &mut Command) -> Result<()> {
+    println!("$ {command:?}");
+    let status = command
+        .status()
+        .with_context(|| format!("无法启动 {:?}（是否已安装？）", command.get_program()))?;
+    if !status.success() {
+        bail!("{command:?} 退出码 {status}");
+    }
+    Ok(())
+}
+
+fn read_address(keypair: &Path) -> Result<String> {
+    let json =
+        fs::read_to_string(keypair).with_context(|| format!("读取 {} 失败", keypair.display()))?;
+    Ok(bs58::encode(ids::keypair_pubkey(&json)?).into_string())
+}
+
+/// 密钥不存在时用 solana-keygen 生成，返回其地址
+fn ensure_keypair(root: &Path, program: &Program) -> Result<String> {
+    let keypair = program.keypair(root);
+    if !keypair.exists() {
+        fs::create_dir_all(keypair.parent().expect("target/deploy"))?;
+        run(Command::new("solana-keygen")
+            .args(["new", "--no-bip39-passphrase", "--silent", "--outfile"])
+            .arg(&keypair))?;
+    }
+    read_address(&keypair)
+}
+
+fn sync_id(root: &Path, program: &Program) -> Result<()> {
+    if program.id_decl == IdDecl::None {
+        return Ok(());
+    }
+    let keypair = program.keypair(root);
+    let address = read_address(&keypair)?;
+    let bytes = ids::keypair_pubkey(&fs::read_to_string(&keypair)?)?;
+
+    let path = program.dir(root).join(program.id_source);
+    let mut source = fs::read_to_string(&path)?;
+    let old = match program.id_decl {
+        IdDecl::Macro => ids::patch_declare_id(&mut source, &address),
+        IdDecl::Bytes => ids::patch_id_bytes(&mut source, &bytes),
+        IdDecl::None => unreachable!(),
+    }
+    .with_context(|| format!("{}", path.display()))?;
+    if old == address {
+        println!("{}: {address}（未变）", program.name);
+        return Ok(());
+    }
+    fs::write(&path, source)?;
+
+    if program.toolchain == Toolchain::Anchor {
+        let anchor_toml = program.dir(root).join("Anchor.toml");
+        let toml = fs::read_to_string(&anchor_toml)?;
+        fs::write(
+            &anchor_toml,
+            ids::patch_anchor_toml(&toml, program.artifact, &address),
+        )?;
+    }
+    println!("{}: {old} → {address}", program.name);
+    Ok(())
+}
+
+fn build(root: &Path, program: &Program, security_txt: bool) -> Result<()> {
+    let dir = program.dir(root);
+    let mut command = match program.toolchain {
+        Toolchain::Anchor => {
+            let mut command = Command::new("anchor");
+            command.arg("build").current_dir(&dir);
+            if security_txt {
+                command.args(["--", "--features", "security-txt"]);
+            }
+            command
+        }
+        Toolchain::Sbf => {
+            let mut command = Command::new("cargo");
+            command
+                .arg("build-sbf")
+                .arg("--manifest-path")
+                .arg(dir.join("Cargo.toml"));
+            if security_txt {
+                command.args(["--features", "security-txt"]);
+            }
+            command
+        }
+    };
+    run(&mut command)
+}
+
+fn deploy(root: &Path, program: &Program, cluster: &str, payer: Option<&Path>) -> Result<()> {
+    let so = program.so(root);
+    if !so.exists() {
+        bail!(
+            "{} 不存在，先执行 cargo xtask build -p {}",
+            so.display(),
+            program.name
+        );
+    }
+    let mut command = Command::new("solana");
+    command.args(["program", "deploy", "--url", cluster]);
+    if let Some(payer) = payer {
+        command.arg("--keypair").arg(payer);
+    }
+    command
+        .arg("--program-id")
+        .arg(program.keypair(root))
+        .arg(&so);
+    run(&mut command)
+}
@@ -0,0 +1,259 @@
+//! 仓库中可以部署的链上程序。
+
+use std::path::{Path, PathBuf};
+
+/// 构建方式
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Toolchain {
+    /// Anchor workspace：`anchor build`，程序 ID 写在 `declare_id!` 与 Anchor.toml 中
+    Anchor,
+    /// 普通 crate：`cargo build-sbf`
+    Sbf,
+}
+
+/// 程序 ID 在源码中的声明方式
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IdDecl {
+    /// `declare_id!("...")`
+    Macro,
+    /// `pub const ID` 后跟 32 个 `0x..` 字节
+    Bytes,
+    /// 程序不声明自己的 ID（运行时使用入口传入的 program_id）
+    None,
+}
+
+/// 构建时生成的 IDL 格式，写在 `<dir>/target/idl/<artifact>.json`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IdlFormat {
+    /// `anchor build` 生成（Anchor 0.30 起的新格式）
+    Anchor,
+    /// build.rs 通过 blueshift_idl 生成的 shank 格式
+    Shank,
+}
+
+impl IdlFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Anchor => "anchor",
+            Self::Shank => "shank",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Program {
+    /// 命令行中使用的名字
+    pub name: &'static str,
+    /// 相对仓库根目录的构建目录（Anchor workspace 或 crate 目录）
+    pub dir: &'static str,
+    /// 产物名，即 `target/deploy/<artifact>.so` 与 `<artifact>-keypair.json`
+    pub artifact: &'static str,
+    pub toolchain: Toolchain,
+    /// 相对 `dir`，声明程序 ID 的源文件
+    pub id_source: &'static str,
+    pub id_decl: IdDecl,
+    /// 没有 IDL 的程序为 `None`
+    pub idl: Option<IdlFormat>,
+}
+
+pub const PROGRAMS: &[Program] = &[
+    Program {
+        name: "anchor-vault",
+        dir: "task2/blueshift_anchor_vault",
+        artifact: "blueshift_anchor_vault",
+        toolchain: Toolchain::Anchor,
+        id_source: "programs/blueshift_anchor_vault/src/lib.rs",
+        id_decl: IdDecl::Macro,
+        idl: Some(IdlFormat::Anchor),
+    },
+    Program {
+        name: "anchor-escrow",
+        dir: "task3/blueshift_anchor_escrow",
+        artifact: "blueshift_anchor_escrow",
+        toolchain: Toolchain::Anchor,
+        id_source: "programs/blueshift_anchor_escrow/src/lib.rs",
+        id_decl: IdDecl::Macro,
+        idl: Some(IdlFormat::Anchor),
+    },
+    Program {
+        name: "vault",
+        dir: "task4/blueshift_vault",
+        artifact: "blueshift_vault",
+        toolchain: Toolchain::Sbf,
+        id_source: "src/lib.rs",
+        id_decl: IdDecl::Bytes,
+        idl: None,
+    },
+    Program {
+        name: "escrow",
+        dir: "task5/blueshift_escrow",
+        artifact: "blueshift_escrow",
+        toolchain: Toolchain::Sbf,
+        id_source: "src/lib.rs",
+        id_decl: IdDecl::Bytes,
+        idl: Some(IdlFormat::Shank),
+    },
+    Program {
+        name: "amm",
+        dir: "task6/solana-pinocchio-amm-workshop-main/blueshift_native_amm",
+        artifact: "blueshift_native_amm",
+        toolchain: Toolchain::Sbf,
+        id_source: "src/lib.rs",
+        id_decl: IdDecl::Bytes,
+        idl: Some(IdlFormat::Shank),
+    },
+    Program {
+        name: "pinocchio-amm",
+        dir: "task6/solana-pinocchio-amm-workshop-main/pinocchio_amm",
+        artifact: "pinocchio_amm",
+        toolchain: Toolchain::Sbf,
+        id_source: "src/lib.rs",
+        id_decl: IdDecl::Macro,
+        idl: None,
+    },
+    Program {
+        name: "router",
+        dir: "programs/blueshift_router",
+        artifact: "blueshift_router",
+        toolchain: Toolchain::Sbf,
+        id_source: "src/lib.rs",
+        id_decl: IdDecl::Bytes,
+        idl: None,
+    },
+    Program {
+        name: "governance",
+        dir: "programs/blueshift_governance",
+        artifact: "blueshift_governance",
+        toolchain: Toolchain::Sbf,
+        id_source: "src/lib.rs",
+        id_decl: IdDecl::Bytes,
+        idl: None,
+    },
+    Program {
+        name: "treasury",
+        dir: "programs/blueshift_treasury",
+        artifact: "blueshift_treasury",
+        toolchain: Toolchain::Sbf,
+        id_source: "src/lib.rs",
+        id_decl: IdDecl::Bytes,
+        idl: None,
+    },
+    Program {
+        name: "upgrade-guard",
+        dir: "programs/blueshift_upgrade_guard",
+        artifact: "blueshift_upgrade_guard",
+        toolchain: Toolchain::Sbf,
+        id_source: "src/lib.rs",
+        id_decl: IdDecl::Bytes,
+        idl: None,
+    },
+    Program {
+        name: "mock-oracle",
+        dir: "programs/blueshift_mock_oracle",
+        artifact: "blueshift_mock_oracle",
+        toolchain: Toolchain::Sbf,
+        id_source: "src/lib.rs",
+        id_decl: IdDecl::Bytes,
+        idl: None,
+    },
+    Program {
+        name: "pxsol-ss",
+        dir: "pxsol-ss",
+        artifact: "hell_sol",
+        toolchain: Toolchain::Sbf,
+        id_source: "src/lib.rs",
+        id_decl: IdDecl::None,
+        idl: None,
+    },
+];
+
+impl Program {
+    pub fn dir(&self, root: &Path) -> PathBuf {
+        root.join(self.dir)
+    }
+
+    /// 程序 crate 的目录（Anchor workspace 中是 `programs/<name>`）
+    pub fn crate_dir(&self, root: &Path) -> PathBuf {
+        let id_source = Path::new(self.id_source);
+        let crate_dir = id_source.ancestors().nth(2).unwrap_or(Path::new(""));
+        self.dir(root).join(crate_dir)
+    }
+
+    pub fn idl_path(&self, root: &Path) -> PathBuf {
+        self.dir(root)
+            .join("target")
+            .join("idl")
+            .join(format!("{}.json", self.artifact))
+    }
+
+    pub fn keypair(&self, root: &Path) -> PathBuf {
+        self.deploy_dir(root)
+            .join(format!("{}-keypair.json", self.artifact))
+    }
+
+    pub fn so(&self, root: &Path) -> PathBuf {
+        self.deploy_dir(root).join(format!("{}.so", self.artifact))
+    }
+
+    fn deploy_dir(&self, root: &Path) -> PathBuf {
+        self.dir(root).join("target").join("deploy")
+    }
+}
+
+/// 按名字挑选程序；`names` 为空时返回全部
+pub fn select(names: &[String]) -> anyhow::Result<Vec<&'static Program>> {
+    if names.is_empty() {
+        return Ok(PROGRAMS.iter().collect());
+    }
+    names
+        .iter()
+        .map(|name| {
+            PROGRAMS
+                .iter()
+                .find(|program| program.name == name)
+                .ok_or_else(|| {
+                    let known: Vec<_> = PROGRAMS.iter().map(|program| program.name).collect();
+                    anyhow::anyhow!("未知程序 {name}，可选：{}", known.join(", "))
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 表中的路径都指向仓库里真实存在的文件
+    #[test]
+    fn table_matches_repository() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        for program in PROGRAMS {
+            let source = program.dir(&root).join(program.id_source);
+            assert!(source.is_file(), "{} 不存在", source.display());
+            let manifest = match program.toolchain {
+                Toolchain::Anchor => "Anchor.toml",
+                Toolchain::Sbf => "Cargo.toml",
+            };
+            assert!(
+                program.dir(&root).join(manifest).is_file(),
+                "{}",
+                program.name
+            );
+            assert!(
+                program.crate_dir(&root).join("Cargo.toml").is_file(),
+                "{}",
+                program.name
+            );
+        }
+    }
+
+    #[test]
+    fn select_rejects_unknown_names() {
+        assert_eq!(select(&[]).unwrap().len(), PROGRAMS.len());
+        assert_eq!(
+            select(&["amm".into()]).unwrap()[0].artifact,
+            "blueshift_native_amm"
+        );
+        assert!(select(&["nope".into()]).is_err());
+    }
+}
@@ -0,0 +1,71 @@
+#![no_main]
+
+//! Config：前 223 字节当作任意账户数据，后 41 字节是一次参数变更 (kind u8, value [u8; 32], now i64)。
+//! 读取任意账户数据不能 panic；提案时通过校验的变更，到期执行必须成功并原样写入对应字段。
+
+use blueshift_native_amm::{Config, ConfigUpdate, MAX_REBATE_TIERS, WEIGHT_ONE};
+use libfuzzer_sys::fuzz_target;
+
+const UPDATE_LEN: usize = 1 + 32 + 8;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() != Config::LEN + UPDATE_LEN {
+        return;
+    }
+    let (account, update) = data.split_at(Config::LEN);
+    let mut account = account.to_vec();
+    // Config 全部由字节数组组成（Pod，对齐为 1），任意 223 字节都是它的合法表示
+    let config = Config::from_bytes_mut(&mut account).expect("223 字节必定可以加载");
+
+    let _ = (
+        config.seed(),
+        config.reserve_x(),
+        config.reserve_y(),
+        config.pending_update(),
+    );
+    let state = config.effective_state(u64::MAX);
+    assert!(state == config.state() || state == 3);
+    if config.weight_x() <= WEIGHT_ONE {
+        assert_eq!(config.weight_x() + config.weight_y(), WEIGHT_ONE);
+    }
+    if (0..MAX_REBATE_TIERS).all(|index| config.rebate_tier(index).1 <= 10_000) {
+        assert!(config.effective_fee(u64::MAX) <= config.fee());
+    }
+
+    let kind = update[0];
+    let value: [u8; 32] = update[1..33].try_into().unwrap();
+    let now = i64::from_le_bytes(update[33..].try_into().unwrap());
+    if config.propose_update(kind, value, now).is_err() || kind == ConfigUpdate::None as u8 {
+        return;
+    }
+
+    let (_, eta, _) = config.pending_update().expect("proposal must be pending");
+    config
+        .execute_update(eta, 1)
+        .expect("a validated proposal must execute once its eta is reached");
+    assert!(config.pending_update().is_none());
+
+    let word = |offset: usize| value[offset..offset + 8].try_into().unwrap();
+    match ConfigUpdate::try_from(kind).unwrap() {
+        ConfigUpdate::None => unreachable!(),
+        ConfigUpdate::Fee => assert_eq!(config.fee(), u16::from_le_bytes([value[0], value[1]])),
+        ConfigUpdate::State => assert_eq!(config.state(), value[0]),
+        ConfigUpdate::Authority => assert_eq!(config.authority(), &value),
+        ConfigUpdate::TimelockDelay => {
+            assert_eq!(config.timelock_delay(), i64::from_le_bytes(word(0)))
+        }
+        ConfigUpdate::RebateTiers => {
+            for index in 0..MAX_REBATE_TIERS {
+                let offset = index * 10;
+                let expected = (
+                    u64::from_le_bytes(word(offset)),
+                    u16::from_le_bytes([value[offset + 8], value[offset + 9]]),
+                );
+                assert_eq!(config.rebate_tier(index), expected);
+            }
+        }
+        ConfigUpdate::WithdrawGraceSlots => {
+            assert_eq!(config.withdraw_grace_slots(), u64::from_le_bytes(word(0)))
+        }
+    }
+});
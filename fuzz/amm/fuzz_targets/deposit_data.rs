@@ -0,0 +1,22 @@
+#![no_main]
+
+//! DepositInstructionData：至少 32 字节（尾部的锁仓时长由 Deposit 单独解析），字段与输入字节一致。
+
+use blueshift_native_amm::DepositInstructionData;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let parsed = DepositInstructionData::try_from(data);
+
+    if data.len() < size_of::<DepositInstructionData>() {
+        assert!(parsed.is_err());
+        return;
+    }
+
+    let parsed = parsed.expect("long enough data must parse");
+    let word = |i: usize| data[i * 8..(i + 1) * 8].try_into().unwrap();
+    assert_eq!({ parsed.amount }, u64::from_le_bytes(word(0)));
+    assert_eq!({ parsed.max_x }, u64::from_le_bytes(word(1)));
+    assert_eq!({ parsed.max_y }, u64::from_le_bytes(word(2)));
+    assert_eq!({ parsed.expiration }, i64::from_le_bytes(word(3)));
+});
@@ -0,0 +1,40 @@
+#![no_main]
+
+//! InitializeInstructionData：完整数据或省略 authority 的精简数据，其余长度一律拒绝。
+//! 解析成功时每个字段必须与输入字节一致，省略的 authority 必须是全零。
+
+use blueshift_native_amm::InitializeInstructionData;
+use libfuzzer_sys::fuzz_target;
+
+const FULL_LEN: usize = size_of::<InitializeInstructionData>();
+const SHORT_LEN: usize = FULL_LEN - 32;
+
+fuzz_target!(|data: &[u8]| {
+    let parsed = InitializeInstructionData::try_from(data);
+
+    if data.len() != FULL_LEN && data.len() != SHORT_LEN {
+        assert!(parsed.is_err());
+        return;
+    }
+    if data[10..42] >= data[42..74] {
+        // mint_x 必须严格小于 mint_y
+        assert!(parsed.is_err());
+        return;
+    }
+
+    let parsed = parsed.expect("valid length and mint order must parse");
+    assert_eq!({ parsed.seed }, u64::from_le_bytes(data[0..8].try_into().unwrap()));
+    assert_eq!({ parsed.fee }, u16::from_le_bytes(data[8..10].try_into().unwrap()));
+    assert_eq!(&{ parsed.mint_x }[..], &data[10..42]);
+    assert_eq!(&{ parsed.mint_y }[..], &data[42..74]);
+    assert_eq!({ parsed.config_bump }[0], data[74]);
+    assert_eq!({ parsed.lp_bump }[0], data[75]);
+    assert_eq!({ parsed.weight_x }, u16::from_le_bytes(data[76..78].try_into().unwrap()));
+    assert_eq!({ parsed.canonical }, data[78]);
+    assert_eq!({ parsed.lp_decimals }, data[79]);
+    if data.len() == FULL_LEN {
+        assert_eq!(&{ parsed.authority }[..], &data[SHORT_LEN..]);
+    } else {
+        assert_eq!({ parsed.authority }, [0u8; 32]);
+    }
+});
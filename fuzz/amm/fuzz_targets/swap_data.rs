@@ -0,0 +1,22 @@
+#![no_main]
+
+//! SwapInstructionData：长度必须恰好 25 字节，is_x 只接受 0 / 1，字段与输入字节一致。
+
+use blueshift_native_amm::SwapInstructionData;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let parsed = SwapInstructionData::try_from(data);
+
+    if data.len() != size_of::<SwapInstructionData>() || data[0] > 1 {
+        assert!(parsed.is_err());
+        return;
+    }
+
+    let parsed = parsed.expect("exact length with a valid flag must parse");
+    let word = |offset: usize| data[offset..offset + 8].try_into().unwrap();
+    assert_eq!({ parsed.is_x }, data[0] == 1);
+    assert_eq!({ parsed.amount }, u64::from_le_bytes(word(1)));
+    assert_eq!({ parsed.min }, u64::from_le_bytes(word(9)));
+    assert_eq!({ parsed.expiration }, i64::from_le_bytes(word(17)));
+});
@@ -0,0 +1,22 @@
+#![no_main]
+
+//! WithdrawInstructionData：长度必须恰好 32 字节，字段与输入字节一致。
+
+use blueshift_native_amm::WithdrawInstructionData;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let parsed = WithdrawInstructionData::try_from(data);
+
+    if data.len() != size_of::<WithdrawInstructionData>() {
+        assert!(parsed.is_err());
+        return;
+    }
+
+    let parsed = parsed.expect("exact length must parse");
+    let word = |i: usize| data[i * 8..(i + 1) * 8].try_into().unwrap();
+    assert_eq!({ parsed.amount }, u64::from_le_bytes(word(0)));
+    assert_eq!({ parsed.min_x }, u64::from_le_bytes(word(1)));
+    assert_eq!({ parsed.min_y }, u64::from_le_bytes(word(2)));
+    assert_eq!({ parsed.expiration }, i64::from_le_bytes(word(3)));
+});
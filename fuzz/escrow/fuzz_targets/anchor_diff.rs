@@ -0,0 +1,14 @@
+#![no_main]
+
+//! 与 Anchor 托管的差分执行：输入字节生成带故障的场景，两边出现分歧（一边成功一边失败，
+//! 或执行后的状态不同）即视为崩溃。需要先构建两个托管程序，见 crates/escrow_diff。
+
+use escrow_diff::{run, Scenario};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let scenario = Scenario::generate(data, true);
+    if let Err(divergence) = run(&scenario) {
+        panic!("{divergence}");
+    }
+});
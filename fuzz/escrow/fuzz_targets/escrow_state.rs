@@ -0,0 +1,51 @@
+#![no_main]
+
+//! Escrow::load / load_mut：长度不是 154 字节或首字节不是判别器的一律拒绝；加载成功时字段与
+//! 固定偏移上的字节一致，经 set_inner 写回后账户数据按同样的偏移编码，判别器保持不变。
+
+use blueshift_escrow::Escrow;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() > 256 {
+        return;
+    }
+    // Escrow 对齐为 1，故意从奇数偏移开始借用，确认加载不依赖对齐
+    let mut buffer = [0u8; 257];
+    buffer[1..=data.len()].copy_from_slice(data);
+    let bytes = &mut buffer[1..=data.len()];
+
+    if data.len() != Escrow::LEN || data[0] != Escrow::DISCRIMINATOR {
+        assert!(Escrow::load(bytes).is_err());
+        assert!(Escrow::load_mut(bytes).is_err());
+        return;
+    }
+
+    let word = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    let escrow = Escrow::load(bytes).expect("exact length must load");
+    assert_eq!(escrow.seed(), word(1));
+    assert_eq!(escrow.maker.to_bytes()[..], data[9..41]);
+    assert_eq!(escrow.mint_a.to_bytes()[..], data[41..73]);
+    assert_eq!(escrow.mint_b.to_bytes()[..], data[73..105]);
+    assert_eq!(escrow.receive(), word(105));
+    assert_eq!(escrow.bump, [data[113]]);
+    assert_eq!(escrow.expiry(), word(114) as i64);
+    assert_eq!(escrow.taker.to_bytes()[..], data[122..154]);
+
+    // 交换字段后经 set_inner 写回，检查每个字段写到了自己的偏移上
+    let (maker, mint_a, mint_b) = (escrow.mint_b, escrow.maker, escrow.mint_a);
+    let (seed, receive, bump) = (escrow.receive(), escrow.seed(), escrow.bump);
+    let (expiry, taker) = (!escrow.expiry(), escrow.mint_a);
+    Escrow::load_mut(bytes)
+        .expect("exact length must load")
+        .set_inner(seed, maker, mint_a, mint_b, receive, bump, expiry, taker);
+    assert_eq!(bytes[0], Escrow::DISCRIMINATOR);
+    assert_eq!(&bytes[1..9], &seed.to_le_bytes());
+    assert_eq!(bytes[9..41], maker.to_bytes()[..]);
+    assert_eq!(bytes[41..73], mint_a.to_bytes()[..]);
+    assert_eq!(bytes[73..105], mint_b.to_bytes()[..]);
+    assert_eq!(&bytes[105..113], &receive.to_le_bytes());
+    assert_eq!(bytes[113], bump[0]);
+    assert_eq!(&bytes[114..122], &expiry.to_le_bytes());
+    assert_eq!(bytes[122..154], taker.to_bytes()[..]);
+});
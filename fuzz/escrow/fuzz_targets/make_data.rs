@@ -0,0 +1,23 @@
+#![no_main]
+
+//! MakeInstructionData：长度必须恰好 64 字节，amount 为 0 时拒绝，字段与输入字节一致。
+
+use blueshift_escrow::MakeInstructionData;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let parsed = MakeInstructionData::try_from(data);
+    let word = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+    if data.len() != 64 || word(16) == 0 {
+        assert!(parsed.is_err());
+        return;
+    }
+
+    let parsed = parsed.expect("exact length with a non-zero amount must parse");
+    assert_eq!(parsed.seed, word(0));
+    assert_eq!(parsed.receive, word(8));
+    assert_eq!(parsed.amount, word(16));
+    assert_eq!(parsed.expiry, word(24) as i64);
+    assert_eq!(parsed.taker.to_bytes()[..], data[32..64]);
+});
@@ -0,0 +1,34 @@
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use blueshift_common::{AccountCheck, ProgramAccount};
+
+use crate::{Council, Proposal};
+
+/// Approve 指令处理函数：成员对提案投赞成票，重复投票不改变结果
+pub fn approve(accounts: &[AccountView]) -> ProgramResult {
+    let [member, council, proposal] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !member.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    ProgramAccount::<Council>::check(council)?;
+    ProgramAccount::<Proposal>::check(proposal)?;
+
+    let council_data = council.try_borrow()?;
+    let council_state = Council::load(&council_data)?;
+    let index = council_state
+        .member_index(member.address())
+        .ok_or(ProgramError::IncorrectAuthority)?;
+
+    let mut proposal_data = proposal.try_borrow_mut()?;
+    let state = Proposal::load_mut(&mut proposal_data)?;
+    if state.council != council.address().to_bytes() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // 名单更换后旧提案作废，位图中的位置已经对应不上原来的成员
+    if state.epoch != council_state.epoch || state.executed != 0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    state.approvals |= 1 << index;
+    Ok(())
+}
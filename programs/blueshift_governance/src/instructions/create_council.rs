@@ -0,0 +1,57 @@
+use pinocchio::{
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    AccountView,
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+use blueshift_common::read_u64_le;
+
+use crate::Council;
+
+/// CreateCouncil 指令处理函数
+///
+/// 数据：seed (u64) + threshold (u8) + 成员地址（每个 32 字节，1..=8 个）。
+/// 付款人只负责出租金，不因此成为成员。
+pub fn create_council(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [payer, council, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !payer.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let seed = read_u64_le(data, 0).ok_or(ProgramError::InvalidInstructionData)?;
+    let threshold = *data.get(8).ok_or(ProgramError::InvalidInstructionData)?;
+    let members = &data[9..];
+
+    let (council_key, bump) = pdas::council_pda(&crate::ID.to_bytes(), seed);
+    if council.address().to_bytes() != council_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let (_, authority_bump) = pdas::governance_authority_pda(&crate::ID.to_bytes(), &council_key);
+
+    let seed_binding = seed.to_le_bytes();
+    let bump_binding = [bump];
+    let seeds = [
+        Seed::from(pdas::COUNCIL_SEED),
+        Seed::from(&seed_binding),
+        Seed::from(&bump_binding),
+    ];
+    CreateAccount {
+        from: payer,
+        to: council,
+        lamports: Rent::get()?.try_minimum_balance(Council::LEN)?,
+        space: Council::LEN as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[Signer::from(&seeds)])?;
+
+    let mut council_data = council.try_borrow_mut()?;
+    let state = Council::load_mut(&mut council_data)?;
+    state.seed = seed_binding;
+    state.bump = bump;
+    state.authority_bump = authority_bump;
+    state.set_members(threshold, members)
+}
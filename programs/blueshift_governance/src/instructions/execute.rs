@@ -0,0 +1,89 @@
+use pinocchio::{
+    cpi::{invoke_signed_with_bounds, Seed, Signer},
+    error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+    AccountView,
+    ProgramResult,
+};
+use blueshift_common::{AccountCheck, ProgramAccount};
+
+use crate::{Council, Proposal, MAX_ACCOUNTS, META_SIGNER, META_WRITABLE};
+
+/// Execute 指令处理函数
+///
+/// 赞成票达到门槛后任何人都可以执行，理事会签名地址作为 PDA 签名。
+/// 账户：council、proposal (w)、签名地址、目标程序，之后按提案中的顺序传入它引用的账户。
+/// 先标记已执行再发起 CPI，目标程序回调本程序时无法重复执行同一提案。
+pub fn execute(accounts: &[AccountView]) -> ProgramResult {
+    let [council, proposal, authority, target_program, remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    ProgramAccount::<Council>::check(council)?;
+    ProgramAccount::<Proposal>::check(proposal)?;
+
+    let council_key = council.address().to_bytes();
+    let (threshold, epoch, authority_bump) = {
+        let council_data = council.try_borrow()?;
+        let state = Council::load(&council_data)?;
+        (state.threshold, state.epoch, state.authority_bump)
+    };
+    let authority_key =
+        pdas::governance_authority_pda_with_bump(&crate::ID.to_bytes(), &council_key, authority_bump)
+            .ok_or(ProgramError::InvalidSeeds)?;
+    if authority.address().to_bytes() != authority_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut proposal_data = proposal.try_borrow_mut()?;
+    let state = Proposal::load_mut(&mut proposal_data)?;
+    if state.council != council_key || state.epoch != epoch {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if state.executed != 0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if state.approval_count() < threshold {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if target_program.address().to_bytes() != state.program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let count = state.account_count as usize;
+    if remaining.len() < count {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    state.executed = 1;
+
+    // 按提案还原指令；传入的账户必须与提案中的地址逐一对应
+    let mut metas: [InstructionAccount; MAX_ACCOUNTS] =
+        core::array::from_fn(|_| InstructionAccount::readonly(authority.address()));
+    let mut views = [authority; MAX_ACCOUNTS];
+    for (i, (stored, account)) in state.accounts[..count].iter().zip(remaining).enumerate() {
+        if stored[..32] != *account.address().as_ref() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        metas[i] = InstructionAccount::new(
+            account.address(),
+            stored[32] & META_WRITABLE != 0,
+            stored[32] & META_SIGNER != 0,
+        );
+        views[i] = account;
+    }
+
+    let bump_binding = [authority_bump];
+    let seeds = [
+        Seed::from(pdas::GOVERNANCE_AUTHORITY_SEED),
+        Seed::from(council.address().as_ref()),
+        Seed::from(&bump_binding),
+    ];
+    let data = state.instruction_data();
+    invoke_signed_with_bounds::<MAX_ACCOUNTS>(
+        &InstructionView {
+            program_id: target_program.address(),
+            accounts: &metas[..count],
+            data,
+        },
+        &views[..count],
+        &[Signer::from(&seeds)],
+    )
+}
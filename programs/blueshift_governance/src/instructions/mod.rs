@@ -0,0 +1,13 @@
+pub mod approve;
+pub mod create_council;
+pub mod execute;
+pub mod propose;
+pub mod set_members;
+pub mod version;
+
+pub use approve::approve;
+pub use create_council::create_council;
+pub use execute::execute;
+pub use propose::propose;
+pub use set_members::set_members;
+pub use version::{get_version, set_features, VERSION};
@@ -0,0 +1,77 @@
+use pinocchio::{
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    AccountView,
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+use blueshift_common::{AccountCheck, ProgramAccount};
+
+use crate::{Council, Proposal};
+
+/// Propose 指令处理函数
+///
+/// 数据：目标程序 (32) + 账户数 (u8) + 账户元信息（每个 33 字节）+ 指令数据。
+/// 提案人必须是成员，提案时自动投出赞成票；门槛为 1 时提案后即可执行。
+pub fn propose(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [proposer, council, proposal, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !proposer.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    ProgramAccount::<Council>::check(council)?;
+
+    let program_id: &[u8; 32] = data
+        .get(..32)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let account_count = *data.get(32).ok_or(ProgramError::InvalidInstructionData)? as usize;
+    let metas = data
+        .get(33..33 + account_count * 33)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let instruction_data = &data[33 + metas.len()..];
+
+    // 占用一个提案序号
+    let mut council_data = council.try_borrow_mut()?;
+    let council_state = Council::load_mut(&mut council_data)?;
+    let member = council_state
+        .member_index(proposer.address())
+        .ok_or(ProgramError::IncorrectAuthority)?;
+    let index = u64::from_le_bytes(council_state.proposal_count);
+    council_state.proposal_count = (index + 1).to_le_bytes();
+
+    let council_key = council.address().to_bytes();
+    let (proposal_key, bump) = pdas::proposal_pda(&crate::ID.to_bytes(), &council_key, index);
+    if proposal.address().to_bytes() != proposal_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let index_binding = index.to_le_bytes();
+    let bump_binding = [bump];
+    let seeds = [
+        Seed::from(pdas::PROPOSAL_SEED),
+        Seed::from(council.address().as_ref()),
+        Seed::from(&index_binding),
+        Seed::from(&bump_binding),
+    ];
+    CreateAccount {
+        from: proposer,
+        to: proposal,
+        lamports: Rent::get()?.try_minimum_balance(Proposal::LEN)?,
+        space: Proposal::LEN as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[Signer::from(&seeds)])?;
+
+    let mut proposal_data = proposal.try_borrow_mut()?;
+    let state = Proposal::load_mut(&mut proposal_data)?;
+    state.council = council_key;
+    state.index = index_binding;
+    state.epoch = council_state.epoch;
+    state.proposer = proposer.address().to_bytes();
+    state.approvals = 1 << member;
+    state.bump = bump;
+    state.set_instruction(program_id, metas, instruction_data)
+}
@@ -0,0 +1,35 @@
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use blueshift_common::{AccountCheck, ProgramAccount};
+
+use crate::Council;
+
+/// SetMembers 指令处理函数：更换成员名单与门槛
+///
+/// 只接受理事会签名地址的签名，也就是说只能由一个通过的提案（目标程序为本程序）发起。
+/// 数据：threshold (u8) + 成员地址。名单更换后尚未执行的提案全部作废。
+pub fn set_members(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [authority, council] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    ProgramAccount::<Council>::check(council)?;
+
+    let (threshold, members) = data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    let mut council_data = council.try_borrow_mut()?;
+    let state = Council::load_mut(&mut council_data)?;
+    let authority_key = pdas::governance_authority_pda_with_bump(
+        &crate::ID.to_bytes(),
+        &council.address().to_bytes(),
+        state.authority_bump,
+    )
+    .ok_or(ProgramError::InvalidSeeds)?;
+    if authority.address().to_bytes() != authority_key {
+        return Err(ProgramError::IncorrectAuthority);
+    }
+    state.set_members(*threshold, members)
+}
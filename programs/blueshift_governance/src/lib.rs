@@ -0,0 +1,80 @@
+//! 治理程序：由理事会多签持有其他程序的管理权限，不再依赖单个热钱包。
+//!
+//! | 判别器 | 指令 | 说明 |
+//! |---|---|---|
+//! | 0 | [`create_council`] | 创建理事会：成员名单（最多 8 人）与通过门槛 |
+//! | 1 | [`propose`] | 成员提交一条待执行的指令，同时投出赞成票 |
+//! | 2 | [`approve`] | 成员投赞成票 |
+//! | 3 | [`execute`] | 达到门槛后由任何人执行，理事会签名地址通过 CPI 签名 |
+//! | 4 | [`set_members`] | 更换名单，只能通过提案调用 |
+//! | `get_version` | [`get_version`] | 版本号与功能位（8 字节判别器，各程序相同） |
+//! | `set_features` | [`set_features`] | 升级权限修改功能开关 |
+//!
+//! 理事会签名地址是 PDA `["authority", council]`（见 `pdas::governance_authority_pda`）。
+//! 原生 AMM 的池子把 authority 设为它之后，ProposeUpdate / ExecuteUpdate / SyncReserves / Skim
+//! 都要经过理事会表决，参数变更另外还受 AMM 自身 timelock 的约束。
+//! Pinocchio 金库目前没有 Config 账户和管理员，今后加入时同样把管理员设为该地址即可。
+
+#![cfg_attr(not(test), no_std)]
+// 程序代码不允许 unwrap / expect / panic!，出错一律返回 ProgramError
+#![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used, clippy::panic))]
+
+use pinocchio::{
+    entrypoint,
+    nostd_panic_handler,
+    error::ProgramError,
+    Address,
+    AccountView,
+    ProgramResult,
+};
+use blueshift_version::{GET_VERSION_DISCRIMINATOR, SET_FEATURES_DISCRIMINATOR};
+
+entrypoint!(process_instruction);
+nostd_panic_handler!();
+
+// 可选的 security.txt 段（security-txt feature），披露流程见仓库根目录 SECURITY.md
+#[cfg(feature = "security-txt")]
+solana_security_txt::security_txt! {
+    name: "Blueshift Governance",
+    project_url: "https://github.com/o7reconversion/solana_bootcamp_2026",
+    contacts: "link:https://github.com/o7reconversion/solana_bootcamp_2026/security/advisories/new",
+    policy: "https://github.com/o7reconversion/solana_bootcamp_2026/blob/main/SECURITY.md",
+    source_code: "https://github.com/o7reconversion/solana_bootcamp_2026/tree/main/programs/blueshift_governance"
+}
+
+pub mod instructions;
+pub use instructions::*;
+
+pub mod state;
+pub use state::*;
+
+/// 程序 ID: Gov1111111111111111111111111111111111111111
+pub const ID: Address = Address::new_from_array([
+    0x04, 0x0c, 0xc1, 0xfa, 0xaa, 0x54, 0xd6, 0x33,
+    0xc1, 0xd9, 0xa7, 0x76, 0x66, 0x3a, 0x43, 0x76,
+    0x4f, 0x53, 0xed, 0x0b, 0xb5, 0xfe, 0x14, 0x07,
+    0xc1, 0x64, 0x5d, 0x00, 0x00, 0x00, 0x00, 0x00,
+]);
+
+/// 程序入口点
+fn process_instruction(
+    _program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // get_version / set_features 的判别器是 8 字节，与其他训练营程序相同
+    if instruction_data.starts_with(&GET_VERSION_DISCRIMINATOR) {
+        return get_version(accounts);
+    }
+    if let Some(data) = instruction_data.strip_prefix(&SET_FEATURES_DISCRIMINATOR) {
+        return set_features(data, accounts);
+    }
+    match instruction_data.split_first() {
+        Some((0, data)) => create_council(data, accounts),
+        Some((1, data)) => propose(data, accounts),
+        Some((2, _)) => approve(accounts),
+        Some((3, _)) => execute(accounts),
+        Some((4, data)) => set_members(data, accounts),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
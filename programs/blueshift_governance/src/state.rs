@@ -0,0 +1,216 @@
+//! 理事会与提案账户。
+//!
+//! 与原生 AMM 的 Config 一样，所有字段都是字节数组（对齐为 1），账户数据可以直接按结构体读取，
+//! 不需要关心 u64 的对齐。
+
+use core::mem::size_of;
+
+use blueshift_common::ProgramOwned;
+use pinocchio::{error::ProgramError, Address};
+
+/// 理事会成员上限；投票记录是一个 u8 位图，第 i 位对应 `members[i]`
+pub const MAX_MEMBERS: usize = 8;
+/// 一条提案指令最多引用的账户数
+pub const MAX_ACCOUNTS: usize = 8;
+/// 一条提案指令的数据上限（AMM ProposeUpdate 为 34 字节）
+pub const MAX_DATA: usize = 128;
+
+/// 提案账户元信息中的标志位
+pub const META_SIGNER: u8 = 1 << 0;
+pub const META_WRITABLE: u8 = 1 << 1;
+
+/// 理事会：PDA `["council", seed]`
+#[repr(C)]
+pub struct Council {
+    pub seed: [u8; 8],
+    /// 通过提案所需的赞成票数
+    pub threshold: u8,
+    pub member_count: u8,
+    pub bump: u8,
+    /// 签名地址 `["authority", council]` 的 bump
+    pub authority_bump: u8,
+    /// 下一个提案的序号
+    pub proposal_count: [u8; 8],
+    /// 每次更换成员加 1；旧成员名单下的提案随之失效
+    pub epoch: [u8; 8],
+    pub members: [[u8; 32]; MAX_MEMBERS],
+}
+
+impl ProgramOwned for Council {
+    const PROGRAM_ID: Address = crate::ID;
+    const LEN: usize = Council::LEN;
+}
+
+impl Council {
+    pub const LEN: usize = size_of::<Council>();
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+    }
+
+    /// `member` 在名单中的位置
+    pub fn member_index(&self, member: &Address) -> Option<usize> {
+        self.members[..self.member_count as usize]
+            .iter()
+            .position(|candidate| candidate.as_slice() == member.as_ref())
+    }
+
+    /// 更换名单：门槛须在 1..=成员数 之间，成员不能重复
+    pub fn set_members(&mut self, threshold: u8, members: &[u8]) -> Result<(), ProgramError> {
+        if members.is_empty() || !members.len().is_multiple_of(32) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let count = members.len() / 32;
+        if count > MAX_MEMBERS || threshold == 0 || threshold as usize > count {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        self.members = [[0; 32]; MAX_MEMBERS];
+        for (i, member) in members.chunks_exact(32).enumerate() {
+            if self.members[..i].iter().any(|existing| existing == member) {
+                return Err(ProgramError::InvalidArgument);
+            }
+            self.members[i].copy_from_slice(member);
+        }
+        self.member_count = count as u8;
+        self.threshold = threshold;
+        self.epoch = (u64::from_le_bytes(self.epoch) + 1).to_le_bytes();
+        Ok(())
+    }
+}
+
+/// 提案：PDA `["proposal", council, index]`，保存一条待执行的指令
+#[repr(C)]
+pub struct Proposal {
+    pub council: [u8; 32],
+    pub index: [u8; 8],
+    /// 创建提案时理事会的 epoch
+    pub epoch: [u8; 8],
+    pub proposer: [u8; 32],
+    /// 赞成票位图
+    pub approvals: u8,
+    pub executed: u8,
+    pub bump: u8,
+    pub account_count: u8,
+    pub data_len: [u8; 2],
+    pub program_id: [u8; 32],
+    /// 每个账户 32 字节地址 + 1 字节标志（[`META_SIGNER`] / [`META_WRITABLE`]）
+    pub accounts: [[u8; 33]; MAX_ACCOUNTS],
+    pub data: [u8; MAX_DATA],
+}
+
+impl ProgramOwned for Proposal {
+    const PROGRAM_ID: Address = crate::ID;
+    const LEN: usize = Proposal::LEN;
+}
+
+impl Proposal {
+    pub const LEN: usize = size_of::<Proposal>();
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+    }
+
+    /// 赞成票数
+    pub fn approval_count(&self) -> u8 {
+        self.approvals.count_ones() as u8
+    }
+
+    /// 存入提案指令：`metas` 为 33 字节一组的账户元信息
+    pub fn set_instruction(
+        &mut self,
+        program_id: &[u8; 32],
+        metas: &[u8],
+        data: &[u8],
+    ) -> Result<(), ProgramError> {
+        if !metas.len().is_multiple_of(33) || metas.len() / 33 > MAX_ACCOUNTS || data.len() > MAX_DATA {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        self.program_id = *program_id;
+        self.account_count = (metas.len() / 33) as u8;
+        for (slot, meta) in self.accounts.iter_mut().zip(metas.chunks_exact(33)) {
+            slot.copy_from_slice(meta);
+        }
+        self.data_len = (data.len() as u16).to_le_bytes();
+        self.data[..data.len()].copy_from_slice(data);
+        Ok(())
+    }
+
+    /// 已存入的指令数据
+    pub fn instruction_data(&self) -> &[u8] {
+        &self.data[..u16::from_le_bytes(self.data_len) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_council() -> Council {
+        Council {
+            seed: [0; 8],
+            threshold: 0,
+            member_count: 0,
+            bump: 0,
+            authority_bump: 0,
+            proposal_count: [0; 8],
+            epoch: [0; 8],
+            members: [[0; 32]; MAX_MEMBERS],
+        }
+    }
+
+    #[test]
+    fn layouts_have_no_padding() {
+        assert_eq!(Council::LEN, 8 + 4 + 8 + 8 + 32 * MAX_MEMBERS);
+        assert_eq!(
+            Proposal::LEN,
+            32 + 8 + 8 + 32 + 4 + 2 + 32 + 33 * MAX_ACCOUNTS + MAX_DATA
+        );
+    }
+
+    #[test]
+    fn set_members_validates_and_bumps_epoch() {
+        let mut council = empty_council();
+        let members = [[1u8; 32], [2u8; 32]].concat();
+        council.set_members(2, &members).unwrap();
+        assert_eq!(council.member_count, 2);
+        assert_eq!(council.member_index(&Address::new_from_array([2; 32])), Some(1));
+        assert_eq!(council.member_index(&Address::new_from_array([0; 32])), None);
+        assert_eq!(u64::from_le_bytes(council.epoch), 1);
+
+        assert!(council.set_members(3, &members).is_err());
+        assert!(council.set_members(0, &members).is_err());
+        assert!(council.set_members(1, &[[1u8; 32], [1u8; 32]].concat()).is_err());
+        assert!(council.set_members(1, &[0u8; 32 * (MAX_MEMBERS + 1)]).is_err());
+    }
+
+    #[test]
+    fn proposal_keeps_instruction() {
+        let mut bytes = [0u8; Proposal::LEN];
+        let proposal = Proposal::load_mut(&mut bytes).unwrap();
+        let meta = [[7u8; 32].as_slice(), &[META_SIGNER | META_WRITABLE]].concat();
+        proposal.set_instruction(&[9; 32], &meta, &[15, 1, 2]).unwrap();
+        assert_eq!(proposal.account_count, 1);
+        assert_eq!(proposal.accounts[0][32], META_SIGNER | META_WRITABLE);
+        assert_eq!(proposal.instruction_data(), [15, 1, 2]);
+
+        assert!(proposal.set_instruction(&[9; 32], &meta[1..], &[]).is_err());
+        assert!(proposal.set_instruction(&[9; 32], &meta, &[0; MAX_DATA + 1]).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use blueshift_oracle::PriceFeed;
+use pinocchio::{
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    AccountView,
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+/// InitFeed 指令处理函数
+///
+/// 账户：authority（签名，付租金）、price_feed（PDA `["price_feed", feed_id]`）、system_program；
+/// 数据：feed_id（32 字节）。创建后价格全为零，`set_price` 之前读取会被当作非正价格拒绝。
+pub fn init_feed(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [authority, feed, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let feed_id: [u8; 32] = data
+        .get(..32)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    let (feed_key, bump) = pdas::price_feed_pda(&crate::ID.to_bytes(), &feed_id);
+    if feed.address().to_bytes() != feed_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let bump_binding = [bump];
+    let seeds = [
+        Seed::from(pdas::PRICE_FEED_SEED),
+        Seed::from(&feed_id),
+        Seed::from(&bump_binding),
+    ];
+    CreateAccount {
+        from: authority,
+        to: feed,
+        lamports: Rent::get()?.try_minimum_balance(PriceFeed::LEN)?,
+        space: PriceFeed::LEN as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[Signer::from(&seeds)])?;
+
+    let mut feed_data = feed.try_borrow_mut()?;
+    PriceFeed::init(&mut feed_data, authority.address().to_bytes(), feed_id, bump)
+        .map_err(|_| ProgramError::AccountAlreadyInitialized)?;
+    Ok(())
+}
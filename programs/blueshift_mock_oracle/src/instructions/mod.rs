@@ -0,0 +1,7 @@
+pub mod init_feed;
+pub mod set_price;
+pub mod version;
+
+pub use init_feed::init_feed;
+pub use set_price::set_price;
+pub use version::{get_version, set_features, VERSION};
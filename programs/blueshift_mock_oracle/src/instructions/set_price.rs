@@ -0,0 +1,28 @@
+use blueshift_oracle::{Price, PriceFeed};
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+
+/// SetPrice 指令处理函数：只有创建价格账户时的 authority 可以改写
+///
+/// 账户：authority（签名）、price_feed；数据：price (i64) + conf (u64) + expo (i32) +
+/// publish_time (i64)，均为小端（[`Price::encode`]）。
+/// 不检查价格是否为正、发布时间是否早于当前时间，测试需要能写入任意（包括不合理的）报价。
+pub fn set_price(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [authority, feed] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !feed.owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    let price = Price::decode(data).ok_or(ProgramError::InvalidInstructionData)?;
+
+    let mut feed_data = feed.try_borrow_mut()?;
+    let state = PriceFeed::load_mut(&mut feed_data).map_err(|_| ProgramError::InvalidAccountData)?;
+    if *state.authority() != authority.address().to_bytes() {
+        return Err(ProgramError::IncorrectAuthority);
+    }
+    state.set(&price);
+    Ok(())
+}
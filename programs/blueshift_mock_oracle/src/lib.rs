@@ -0,0 +1,73 @@
+//! 模拟预言机：把一个可随时改写的价格存进 PDA，账户布局与 `blueshift_oracle::PriceFeed` 相同。
+//!
+//! | 判别器 | 指令 | 说明 |
+//! |---|---|---|
+//! | 0 | [`init_feed`] | 创建价格账户 `["price_feed", feed_id]`，签名者成为 authority |
+//! | 1 | [`set_price`] | authority 写入价格、置信区间、指数与发布时间 |
+//! | `get_version` | [`get_version`] | 版本号与功能位（8 字节判别器，各程序相同） |
+//! | `set_features` | [`set_features`] | 升级权限修改功能开关 |
+//!
+//! 只用于测试：Mollusk / 本地验证者里加载本程序，就能构造过期、置信区间过宽或偏离过大的价格，
+//! 检验依赖价格的代码路径，而不需要复制 mainnet 的 Pyth 账户。发布时间由调用方任意指定，
+//! 不与 Clock 比较，切勿部署到主网给真实资金使用。
+
+#![cfg_attr(not(test), no_std)]
+// 程序代码不允许 unwrap / expect / panic!，出错一律返回 ProgramError
+#![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used, clippy::panic))]
+
+use pinocchio::{
+    entrypoint,
+    nostd_panic_handler,
+    error::ProgramError,
+    Address,
+    AccountView,
+    ProgramResult,
+};
+use blueshift_version::{GET_VERSION_DISCRIMINATOR, SET_FEATURES_DISCRIMINATOR};
+
+entrypoint!(process_instruction);
+nostd_panic_handler!();
+
+// 可选的 security.txt 段（security-txt feature），披露流程见仓库根目录 SECURITY.md
+#[cfg(feature = "security-txt")]
+solana_security_txt::security_txt! {
+    name: "Blueshift Mock Oracle",
+    project_url: "https://github.com/o7reconversion/solana_bootcamp_2026",
+    contacts: "link:https://github.com/o7reconversion/solana_bootcamp_2026/security/advisories/new",
+    policy: "https://github.com/o7reconversion/solana_bootcamp_2026/blob/main/SECURITY.md",
+    source_code: "https://github.com/o7reconversion/solana_bootcamp_2026/tree/main/programs/blueshift_mock_oracle"
+}
+
+pub mod instructions;
+pub use instructions::*;
+
+#[cfg(test)]
+pub mod tests;
+
+/// 程序 ID: Mock111111111111111111111111111111111111111
+pub const ID: Address = Address::new_from_array([
+    0x05, 0x54, 0x53, 0x48, 0x46, 0x35, 0xc4, 0x65,
+    0x3c, 0xe3, 0x87, 0xf9, 0xfd, 0x58, 0xad, 0x20,
+    0x1b, 0x1a, 0x4b, 0x35, 0xa9, 0xa2, 0xfd, 0x19,
+    0x98, 0x3d, 0xa2, 0x80, 0x00, 0x00, 0x00, 0x00,
+]);
+
+/// 程序入口点
+fn process_instruction(
+    _program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // get_version / set_features 的判别器是 8 字节，与其他训练营程序相同
+    if instruction_data.starts_with(&GET_VERSION_DISCRIMINATOR) {
+        return get_version(accounts);
+    }
+    if let Some(data) = instruction_data.strip_prefix(&SET_FEATURES_DISCRIMINATOR) {
+        return set_features(data, accounts);
+    }
+    match instruction_data.split_first() {
+        Some((0, data)) => init_feed(data, accounts),
+        Some((1, data)) => set_price(data, accounts),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
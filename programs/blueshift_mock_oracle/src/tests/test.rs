@@ -0,0 +1,217 @@
+// =============================================================================
+// 模拟预言机测试 - 使用 Mollusk 测试框架
+// =============================================================================
+// 先执行 `cargo build-sbf` 生成 target/deploy/blueshift_mock_oracle.so，再执行 `cargo test`。
+// 后面几个测试演示依赖价格的程序如何用它：写入一个报价，再用 blueshift_oracle 的校验读取。
+
+use blueshift_oracle::{OracleError, Price, PriceFeed};
+use mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk};
+use solana_account::Account;
+use solana_program::program_error::ProgramError;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+// =============================================================================
+// 测试环境
+// =============================================================================
+
+// 直接取 lib.rs 中的 ID，`cargo xtask sync-ids` 改写后测试不用跟着改
+const PROGRAM_ID: Pubkey = Pubkey::new_from_array(crate::ID.to_bytes());
+const FEED_ID: [u8; 32] = [0xef; 32];
+const SOL: u64 = 1_000_000_000;
+
+struct Oracle {
+    mollusk: Mollusk,
+    authority: Pubkey,
+    feed: Pubkey,
+    accounts: Vec<(Pubkey, Account)>,
+}
+
+impl Oracle {
+    /// 加载程序并用 `authority` 创建 FEED_ID 的价格账户
+    fn new() -> Self {
+        // 省略 .so 扩展名，Mollusk 会自动添加
+        let mollusk = Mollusk::new(&PROGRAM_ID, "target/deploy/blueshift_mock_oracle");
+        let system = keyed_account_for_system_program();
+        let authority = Pubkey::new_unique();
+        let feed = Pubkey::new_from_array(pdas::price_feed_pda(&PROGRAM_ID.to_bytes(), &FEED_ID).0);
+        let mut oracle = Self {
+            mollusk,
+            authority,
+            feed,
+            accounts: vec![
+                (authority, Account::new(10 * SOL, 0, &system.0)),
+                (feed, Account::default()),
+                system.clone(),
+            ],
+        };
+        let mut data = vec![0];
+        data.extend_from_slice(&FEED_ID);
+        oracle.ok(Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &data,
+            vec![
+                AccountMeta::new(authority, true),
+                AccountMeta::new(feed, false),
+                AccountMeta::new_readonly(system.0, false),
+            ],
+        ));
+        oracle
+    }
+
+    fn account(&self, key: &Pubkey) -> Account {
+        self.accounts
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, account)| account.clone())
+            .unwrap_or_default()
+    }
+
+    fn execute(
+        &self,
+        ix: &Instruction,
+        checks: &[Check],
+    ) -> mollusk_svm::result::InstructionResult {
+        let accounts: Vec<(Pubkey, Account)> = ix
+            .accounts
+            .iter()
+            .map(|meta| (meta.pubkey, self.account(&meta.pubkey)))
+            .collect();
+        self.mollusk
+            .process_and_validate_instruction(ix, &accounts, checks)
+    }
+
+    /// 执行并要求成功，结果写回
+    fn ok(&mut self, ix: Instruction) {
+        let result = self.execute(&ix, &[Check::success()]);
+        for (key, account) in result.resulting_accounts {
+            match self.accounts.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, existing)) => *existing = account,
+                None => self.accounts.push((key, account)),
+            }
+        }
+    }
+
+    fn set_price_ix(&self, signer: Pubkey, price: &Price) -> Instruction {
+        let mut data = vec![1];
+        data.extend_from_slice(&price.encode());
+        Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &data,
+            vec![
+                AccountMeta::new(signer, true),
+                AccountMeta::new(self.feed, false),
+            ],
+        )
+    }
+
+    /// 依赖价格的程序读到的报价
+    fn price(&self) -> Price {
+        PriceFeed::load(&self.account(&self.feed).data)
+            .unwrap()
+            .price()
+    }
+
+    fn now(&self) -> i64 {
+        self.mollusk.sysvars.clock.unix_timestamp
+    }
+}
+
+// =============================================================================
+// 指令
+// =============================================================================
+
+#[test]
+fn test_init_feed_creates_pda_owned_by_program() {
+    let oracle = Oracle::new();
+    let account = oracle.account(&oracle.feed);
+    assert_eq!(account.owner, PROGRAM_ID);
+    assert_eq!(account.data.len(), PriceFeed::LEN);
+
+    let feed = PriceFeed::load(&account.data).unwrap();
+    assert_eq!(feed.authority(), &oracle.authority.to_bytes());
+    assert_eq!(feed.feed_id(), &FEED_ID);
+    // 尚未写入价格：依赖价格的程序会当作非正价格拒绝
+    assert_eq!(
+        feed.price().no_older_than(oracle.now(), 60),
+        Err(OracleError::NotPositive)
+    );
+}
+
+#[test]
+fn test_only_authority_sets_price() {
+    let mut oracle = Oracle::new();
+    let price = Price {
+        price: 150_000_000,
+        conf: 10_000,
+        expo: -6,
+        publish_time: oracle.now(),
+    };
+    oracle.ok(oracle.set_price_ix(oracle.authority, &price));
+    assert_eq!(oracle.price(), price);
+
+    let stranger = Pubkey::new_unique();
+    oracle.execute(
+        &oracle.set_price_ix(stranger, &Price { price: 1, ..price }),
+        &[Check::err(ProgramError::IncorrectAuthority)],
+    );
+}
+
+// =============================================================================
+// 依赖价格的校验
+// =============================================================================
+
+#[test]
+fn test_stale_price_is_rejected() {
+    let mut oracle = Oracle::new();
+    let now = oracle.now();
+    let price = Price {
+        price: 150_000_000,
+        conf: 10_000,
+        expo: -6,
+        publish_time: now - 120,
+    };
+    oracle.ok(oracle.set_price_ix(oracle.authority, &price));
+    assert_eq!(
+        oracle.price().no_older_than(now, 60),
+        Err(OracleError::Stale)
+    );
+
+    // 刷新发布时间后恢复可用
+    oracle.ok(oracle.set_price_ix(
+        oracle.authority,
+        &Price {
+            publish_time: now,
+            ..price
+        },
+    ));
+    assert!(oracle.price().no_older_than(now, 60).is_ok());
+}
+
+#[test]
+fn test_deviation_and_confidence_guards() {
+    let mut oracle = Oracle::new();
+    let price = Price {
+        price: 150_000_000,
+        conf: 3_000_000,
+        expo: -6,
+        publish_time: oracle.now(),
+    };
+    oracle.ok(oracle.set_price_ix(oracle.authority, &price));
+    let price = oracle.price();
+
+    // 池子价格 151 与预言机 150 偏离约 67 bps
+    assert!(price.check_deviation(151_000_000, 100).is_ok());
+    assert_eq!(
+        price.check_deviation(151_000_000, 50),
+        Err(OracleError::Deviation)
+    );
+    // 置信区间 3 / 150 = 200 bps
+    assert_eq!(
+        price.check_confidence(100),
+        Err(OracleError::ConfidenceTooWide)
+    );
+    assert!(price.check_confidence(200).is_ok());
+}
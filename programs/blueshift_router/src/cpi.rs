@@ -0,0 +1,127 @@
+//! 被调用程序的指令编码与账户布局。
+//!
+//! 这里的判别器和字节布局必须与 task4 金库、task5 托管、task6 原生 AMM 保持一致，
+//! 它们改动指令格式时要同步修改（bootcamp_client 中对应的构造函数也一样）。
+
+/// wSOL mint：So11111111111111111111111111111111111111112
+pub const NATIVE_MINT: [u8; 32] = [
+    0x06, 0x9b, 0x88, 0x57, 0xfe, 0xab, 0x81, 0x84,
+    0xfb, 0x68, 0x7f, 0x63, 0x46, 0x18, 0xc0, 0x35,
+    0xda, 0xc4, 0x39, 0xdc, 0x1a, 0xeb, 0x3b, 0x55,
+    0x98, 0xa0, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x01,
+];
+
+// task4 金库
+pub const VAULT_DEPOSIT: u8 = 0;
+pub const VAULT_WITHDRAW: u8 = 1;
+
+// task5 托管
+pub const ESCROW_MAKE: u8 = 0;
+pub const ESCROW_TAKE: u8 = 1;
+
+/// 托管账户的长度、判别器与 receive 偏移（见 task5 的 state.rs）
+pub const ESCROW_LEN: usize = 154;
+pub const ESCROW_DISCRIMINATOR: u8 = 1;
+pub const ESCROW_RECEIVE: usize = 105;
+
+// task6 原生 AMM
+pub const AMM_DEPOSIT: u8 = 1;
+pub const AMM_SWAP: u8 = 3;
+
+/// 原生 AMM Config 的长度与 mint_x / mint_y 偏移（见 task6 的 snapshots/config.layout）
+pub const AMM_CONFIG_LEN: usize = 223;
+pub const AMM_CONFIG_MINT_X: usize = 41;
+pub const AMM_CONFIG_MINT_Y: usize = 73;
+
+/// SPL Token 账户中 amount 的偏移
+pub const TOKEN_ACCOUNT_AMOUNT: usize = 64;
+
+/// 金库 Deposit：`[0, amount]`
+pub fn vault_deposit_data(amount: u64) -> [u8; 9] {
+    let mut data = [0u8; 9];
+    data[0] = VAULT_DEPOSIT;
+    data[1..].copy_from_slice(&amount.to_le_bytes());
+    data
+}
+
+/// 托管 Make：`[0, seed, receive, amount, expiry, taker]`
+pub fn escrow_make_data(
+    seed: u64,
+    receive: u64,
+    amount: u64,
+    expiry: i64,
+    taker: &[u8; 32],
+) -> [u8; 65] {
+    let mut data = [0u8; 65];
+    data[0] = ESCROW_MAKE;
+    data[1..9].copy_from_slice(&seed.to_le_bytes());
+    data[9..17].copy_from_slice(&receive.to_le_bytes());
+    data[17..25].copy_from_slice(&amount.to_le_bytes());
+    data[25..33].copy_from_slice(&expiry.to_le_bytes());
+    data[33..65].copy_from_slice(taker);
+    data
+}
+
+/// AMM Deposit：`[1, lp_amount, max_x, max_y, expiration]`
+pub fn amm_deposit_data(lp_amount: u64, max_x: u64, max_y: u64, expiration: i64) -> [u8; 33] {
+    let mut data = [0u8; 33];
+    data[0] = AMM_DEPOSIT;
+    data[1..9].copy_from_slice(&lp_amount.to_le_bytes());
+    data[9..17].copy_from_slice(&max_x.to_le_bytes());
+    data[17..25].copy_from_slice(&max_y.to_le_bytes());
+    data[25..33].copy_from_slice(&expiration.to_le_bytes());
+    data
+}
+
+/// AMM Swap：`[3, is_x, amount, min, expiration]`
+pub fn amm_swap_data(is_x: bool, amount: u64, min: u64, expiration: i64) -> [u8; 26] {
+    let mut data = [0u8; 26];
+    data[0] = AMM_SWAP;
+    data[1] = is_x as u8;
+    data[2..10].copy_from_slice(&amount.to_le_bytes());
+    data[10..18].copy_from_slice(&min.to_le_bytes());
+    data[18..26].copy_from_slice(&expiration.to_le_bytes());
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vault_deposit_is_discriminator_then_amount() {
+        let data = vault_deposit_data(1_000);
+        assert_eq!(data[0], 0);
+        assert_eq!(data[1..], 1_000u64.to_le_bytes());
+    }
+
+    #[test]
+    fn escrow_make_fields_follow_program_order() {
+        let data = escrow_make_data(7, 80, 50, 1_767_225_600, &[9; 32]);
+        assert_eq!(data[0], 0);
+        assert_eq!(data[1..9], 7u64.to_le_bytes());
+        assert_eq!(data[9..17], 80u64.to_le_bytes());
+        assert_eq!(data[17..25], 50u64.to_le_bytes());
+        assert_eq!(data[25..33], 1_767_225_600i64.to_le_bytes());
+        assert_eq!(data[33..], [9; 32]);
+    }
+
+    #[test]
+    fn amm_deposit_ends_with_expiration() {
+        let data = amm_deposit_data(100, 10, 20, i64::MAX);
+        assert_eq!(data[0], 1);
+        assert_eq!(data[1..9], 100u64.to_le_bytes());
+        assert_eq!(data[9..17], 10u64.to_le_bytes());
+        assert_eq!(data[17..25], 20u64.to_le_bytes());
+        assert_eq!(data[25..], i64::MAX.to_le_bytes());
+    }
+
+    #[test]
+    fn amm_swap_direction_is_a_single_byte() {
+        let data = amm_swap_data(false, 500, 400, 9);
+        assert_eq!(data[..2], [3, 0]);
+        assert_eq!(data[2..10], 500u64.to_le_bytes());
+        assert_eq!(data[10..18], 400u64.to_le_bytes());
+        assert_eq!(data[18..], 9i64.to_le_bytes());
+    }
+}
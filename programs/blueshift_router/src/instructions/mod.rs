@@ -0,0 +1,9 @@
+pub mod take_and_deposit;
+pub mod take_and_swap;
+pub mod vault_to_escrow;
+pub mod version;
+
+pub use take_and_deposit::take_and_deposit;
+pub use take_and_swap::take_and_swap;
+pub use vault_to_escrow::vault_to_escrow;
+pub use version::{get_version, set_features, VERSION};
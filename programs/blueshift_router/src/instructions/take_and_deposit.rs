@@ -0,0 +1,174 @@
+use pinocchio::{
+    cpi::invoke,
+    error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+    AccountView,
+    ProgramResult,
+};
+use pinocchio_associated_token_account::instructions::CreateIdempotent;
+use blueshift_common::read_u64_le;
+
+use crate::cpi::{
+    amm_deposit_data, AMM_CONFIG_LEN, AMM_CONFIG_MINT_X, AMM_CONFIG_MINT_Y, ESCROW_TAKE,
+};
+
+/// TakeAndDeposit 指令处理函数
+///
+/// 吃掉一张托管单，再把换到的代币 A 存进以它为一侧的 AMM 池子：
+/// 1. 托管 Take：taker 支付 `receive` 个代币 B，拿到金库里的代币 A；
+/// 2. taker 的 LP ATA 不存在时创建；
+/// 3. AMM Deposit：铸造 `lp_amount` 个 LP，两侧最多支付 `max_x` / `max_y`。
+///
+/// 池子另一侧的代币由 taker 自己提供。池子必须包含 mint_a，且 taker 在 mint_a 上的 ATA
+/// 必须就是传给 AMM 的那一侧，否则 Take 得到的代币不会进入池子。
+/// 指令数据与 AMM Deposit 相同：lp_amount、max_x、max_y、expiration。
+pub fn take_and_deposit(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    // 解析指令数据
+    if data.len() != 32 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let lp_amount = read_u64_le(data, 0).ok_or(ProgramError::InvalidInstructionData)?;
+    let max_x = read_u64_le(data, 8).ok_or(ProgramError::InvalidInstructionData)?;
+    let max_y = read_u64_le(data, 16).ok_or(ProgramError::InvalidInstructionData)?;
+    let expiration = read_u64_le(data, 24).ok_or(ProgramError::InvalidInstructionData)? as i64;
+
+    // 解析账户
+    let [
+        taker,
+        maker,
+        escrow,
+        mint_a,
+        mint_b,
+        escrow_vault,
+        taker_ata_a,
+        taker_ata_b,
+        maker_ata_b,
+        escrow_config,
+        treasury_ata_b,
+        offer_index,
+        config,
+        mint_lp,
+        pool_vault_x,
+        pool_vault_y,
+        taker_ata_x,
+        taker_ata_y,
+        taker_ata_lp,
+        escrow_program,
+        amm_program,
+        system_program,
+        token_program,
+        associated_token_program,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !taker.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // 池子必须由传入的 AMM 程序管理，且 mint_a 在其中一侧
+    if !config.owned_by(amm_program.address()) || config.data_len() != AMM_CONFIG_LEN {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    let proceeds_side = {
+        let config_data = config.try_borrow()?;
+        if &config_data[AMM_CONFIG_MINT_X..AMM_CONFIG_MINT_X + 32] == mint_a.address().as_ref() {
+            taker_ata_x
+        } else if &config_data[AMM_CONFIG_MINT_Y..AMM_CONFIG_MINT_Y + 32]
+            == mint_a.address().as_ref()
+        {
+            taker_ata_y
+        } else {
+            return Err(ProgramError::InvalidArgument);
+        }
+    };
+    if proceeds_side.address() != taker_ata_a.address() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // 1. 吃单（托管程序校验 escrow、金库与各 ATA）
+    let take_accounts = [
+        InstructionAccount::writable_signer(taker.address()),
+        InstructionAccount::writable(maker.address()),
+        InstructionAccount::writable(escrow.address()),
+        InstructionAccount::readonly(mint_a.address()),
+        InstructionAccount::readonly(mint_b.address()),
+        InstructionAccount::writable(escrow_vault.address()),
+        InstructionAccount::writable(taker_ata_a.address()),
+        InstructionAccount::writable(taker_ata_b.address()),
+        InstructionAccount::writable(maker_ata_b.address()),
+        InstructionAccount::readonly(system_program.address()),
+        InstructionAccount::readonly(token_program.address()),
+        InstructionAccount::readonly(associated_token_program.address()),
+        InstructionAccount::readonly(escrow_config.address()),
+        InstructionAccount::writable(treasury_ata_b.address()),
+        InstructionAccount::writable(offer_index.address()),
+    ];
+    invoke(
+        &InstructionView {
+            program_id: escrow_program.address(),
+            accounts: &take_accounts,
+            data: &[ESCROW_TAKE],
+        },
+        &[
+            taker,
+            maker,
+            escrow,
+            mint_a,
+            mint_b,
+            escrow_vault,
+            taker_ata_a,
+            taker_ata_b,
+            maker_ata_b,
+            system_program,
+            token_program,
+            associated_token_program,
+            escrow_config,
+            treasury_ata_b,
+            offer_index,
+        ],
+    )?;
+
+    // 2. AMM Deposit 要求 LP ATA 已存在
+    CreateIdempotent {
+        funding_account: taker,
+        account: taker_ata_lp,
+        wallet: taker,
+        mint: mint_lp,
+        system_program,
+        token_program,
+    }
+    .invoke()?;
+
+    // 3. 存入流动性
+    let deposit_accounts = [
+        InstructionAccount::writable_signer(taker.address()),
+        InstructionAccount::writable(mint_lp.address()),
+        InstructionAccount::writable(pool_vault_x.address()),
+        InstructionAccount::writable(pool_vault_y.address()),
+        InstructionAccount::writable(taker_ata_x.address()),
+        InstructionAccount::writable(taker_ata_y.address()),
+        InstructionAccount::writable(taker_ata_lp.address()),
+        InstructionAccount::writable(config.address()),
+        InstructionAccount::readonly(token_program.address()),
+    ];
+    invoke(
+        &InstructionView {
+            program_id: amm_program.address(),
+            accounts: &deposit_accounts,
+            data: &amm_deposit_data(lp_amount, max_x, max_y, expiration),
+        },
+        &[
+            taker,
+            mint_lp,
+            pool_vault_x,
+            pool_vault_y,
+            taker_ata_x,
+            taker_ata_y,
+            taker_ata_lp,
+            config,
+            token_program,
+        ],
+    )
+}
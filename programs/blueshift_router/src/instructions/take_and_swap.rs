@@ -0,0 +1,168 @@
+use pinocchio::{
+    cpi::invoke,
+    error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+    AccountView,
+    ProgramResult,
+};
+use blueshift_common::read_u64_le;
+
+use crate::cpi::{
+    amm_swap_data, AMM_CONFIG_LEN, AMM_CONFIG_MINT_X, AMM_CONFIG_MINT_Y, ESCROW_DISCRIMINATOR,
+    ESCROW_LEN, ESCROW_RECEIVE, ESCROW_TAKE, TOKEN_ACCOUNT_AMOUNT,
+};
+
+/// TakeAndSwap 指令处理函数
+///
+/// 托管单与 AMM 池子对同一交易对报价不同时的套利路径：
+/// 1. 托管 Take：taker 支付 `receive` 个代币 B，拿到金库里的全部代币 A；
+/// 2. AMM Swap：把这些代币 A 全部卖回池子换代币 B。
+///
+/// 卖出的最低数量取 `min_out` 与托管单 `receive` 中较大的一个，整笔交易要么让 taker 手里的
+/// 代币 B 不减少，要么整体回滚。池子必须恰好由 mint_a / mint_b 组成。
+/// 指令数据：min_out、expiration（传给 AMM）。
+pub fn take_and_swap(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    // 解析指令数据
+    if data.len() != 16 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let min_out = read_u64_le(data, 0).ok_or(ProgramError::InvalidInstructionData)?;
+    let expiration = read_u64_le(data, 8).ok_or(ProgramError::InvalidInstructionData)? as i64;
+
+    // 解析账户
+    let [
+        taker,
+        maker,
+        escrow,
+        mint_a,
+        mint_b,
+        escrow_vault,
+        taker_ata_a,
+        taker_ata_b,
+        maker_ata_b,
+        escrow_config,
+        treasury_ata_b,
+        offer_index,
+        config,
+        pool_vault_x,
+        pool_vault_y,
+        escrow_program,
+        amm_program,
+        system_program,
+        token_program,
+        associated_token_program,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !taker.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // 池子由传入的 AMM 程序管理，两侧恰好是 mint_a / mint_b；卖出 A 即输入 A 所在的一侧
+    if !config.owned_by(amm_program.address()) || config.data_len() != AMM_CONFIG_LEN {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    let is_x = {
+        let config_data = config.try_borrow()?;
+        let mint_x = &config_data[AMM_CONFIG_MINT_X..AMM_CONFIG_MINT_X + 32];
+        let mint_y = &config_data[AMM_CONFIG_MINT_Y..AMM_CONFIG_MINT_Y + 32];
+        if mint_x == mint_a.address().as_ref() && mint_y == mint_b.address().as_ref() {
+            true
+        } else if mint_y == mint_a.address().as_ref() && mint_x == mint_b.address().as_ref() {
+            false
+        } else {
+            return Err(ProgramError::InvalidArgument);
+        }
+    };
+
+    // 托管单的报价与金库余额：Take 之后这两个账户都会被关闭，先读出来
+    if !escrow.owned_by(escrow_program.address()) || escrow.data_len() != ESCROW_LEN {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    let receive = {
+        let data = escrow.try_borrow()?;
+        if data.first() != Some(&ESCROW_DISCRIMINATOR) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        read_u64_le(&data, ESCROW_RECEIVE).ok_or(ProgramError::InvalidAccountData)?
+    };
+    let offer = read_u64_le(&escrow_vault.try_borrow()?, TOKEN_ACCOUNT_AMOUNT)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    // 1. 吃单（托管程序校验 escrow、金库与各 ATA）
+    let take_accounts = [
+        InstructionAccount::writable_signer(taker.address()),
+        InstructionAccount::writable(maker.address()),
+        InstructionAccount::writable(escrow.address()),
+        InstructionAccount::readonly(mint_a.address()),
+        InstructionAccount::readonly(mint_b.address()),
+        InstructionAccount::writable(escrow_vault.address()),
+        InstructionAccount::writable(taker_ata_a.address()),
+        InstructionAccount::writable(taker_ata_b.address()),
+        InstructionAccount::writable(maker_ata_b.address()),
+        InstructionAccount::readonly(system_program.address()),
+        InstructionAccount::readonly(token_program.address()),
+        InstructionAccount::readonly(associated_token_program.address()),
+        InstructionAccount::readonly(escrow_config.address()),
+        InstructionAccount::writable(treasury_ata_b.address()),
+        InstructionAccount::writable(offer_index.address()),
+    ];
+    invoke(
+        &InstructionView {
+            program_id: escrow_program.address(),
+            accounts: &take_accounts,
+            data: &[ESCROW_TAKE],
+        },
+        &[
+            taker,
+            maker,
+            escrow,
+            mint_a,
+            mint_b,
+            escrow_vault,
+            taker_ata_a,
+            taker_ata_b,
+            maker_ata_b,
+            system_program,
+            token_program,
+            associated_token_program,
+            escrow_config,
+            treasury_ata_b,
+            offer_index,
+        ],
+    )?;
+
+    // 2. 把拿到的代币 A 全部卖回池子（AMM 校验金库与 taker 的 ATA）
+    let (taker_ata_x, taker_ata_y) = if is_x {
+        (taker_ata_a, taker_ata_b)
+    } else {
+        (taker_ata_b, taker_ata_a)
+    };
+    let swap_accounts = [
+        InstructionAccount::writable_signer(taker.address()),
+        InstructionAccount::writable(taker_ata_x.address()),
+        InstructionAccount::writable(taker_ata_y.address()),
+        InstructionAccount::writable(pool_vault_x.address()),
+        InstructionAccount::writable(pool_vault_y.address()),
+        InstructionAccount::writable(config.address()),
+        InstructionAccount::readonly(token_program.address()),
+    ];
+    invoke(
+        &InstructionView {
+            program_id: amm_program.address(),
+            accounts: &swap_accounts,
+            data: &amm_swap_data(is_x, offer, min_out.max(receive), expiration),
+        },
+        &[
+            taker,
+            taker_ata_x,
+            taker_ata_y,
+            pool_vault_x,
+            pool_vault_y,
+            config,
+            token_program,
+        ],
+    )
+}
@@ -0,0 +1,155 @@
+use pinocchio::{
+    cpi::invoke,
+    error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+    sysvars::{rent::Rent, Sysvar},
+    AccountView,
+    ProgramResult,
+};
+use pinocchio_associated_token_account::instructions::CreateIdempotent;
+use pinocchio_system::instructions::Transfer;
+use pinocchio_token::instructions::SyncNative;
+use blueshift_common::{read_i64_le, read_u64_le};
+
+use crate::cpi::{escrow_make_data, vault_deposit_data, NATIVE_MINT, VAULT_WITHDRAW};
+
+/// VaultToEscrow 指令处理函数
+///
+/// 用金库里的 SOL 挂一张托管单（卖出 wSOL 换 `mint_b`）：
+/// 1. 金库 Withdraw，全部 lamports 回到 owner；
+/// 2. 其中 `amount` 转入 owner 的 wSOL ATA 并 SyncNative；
+/// 3. 托管 Make，owner 作为 maker 存入这 `amount` 个 wSOL；
+/// 4. 剩余部分不低于免租金额时重新存回金库，否则留在 owner 钱包里。
+///
+/// 金库 Withdraw 只能整笔取出，所以第 4 步的 Deposit 对金库来说是一次新的存款。
+/// 指令数据与托管 Make 相同：seed、receive、amount、expiry、taker。
+pub fn vault_to_escrow(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    // 解析指令数据
+    if data.len() != 64 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let seed = read_u64_le(data, 0).ok_or(ProgramError::InvalidInstructionData)?;
+    let receive = read_u64_le(data, 8).ok_or(ProgramError::InvalidInstructionData)?;
+    let amount = read_u64_le(data, 16).ok_or(ProgramError::InvalidInstructionData)?;
+    let expiry = read_i64_le(data, 24).ok_or(ProgramError::InvalidInstructionData)?;
+    let taker = data[32..]
+        .first_chunk::<32>()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    if amount == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // 解析账户
+    let [
+        owner,
+        vault,
+        escrow,
+        mint_a,
+        mint_b,
+        owner_ata_a,
+        escrow_vault,
+        offer_index,
+        vault_program,
+        escrow_program,
+        system_program,
+        token_program,
+        associated_token_program,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if mint_a.address().to_bytes() != NATIVE_MINT {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // 1. 取出金库（金库程序自己校验 PDA 与 owner 签名）
+    let withdrawn = vault.lamports();
+    let vault_accounts = [
+        InstructionAccount::writable_signer(owner.address()),
+        InstructionAccount::writable(vault.address()),
+        InstructionAccount::readonly(system_program.address()),
+    ];
+    invoke(
+        &InstructionView {
+            program_id: vault_program.address(),
+            accounts: &vault_accounts,
+            data: &[VAULT_WITHDRAW],
+        },
+        &[owner, vault, system_program],
+    )?;
+    let rest = withdrawn
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+
+    // 2. 包装 SOL
+    CreateIdempotent {
+        funding_account: owner,
+        account: owner_ata_a,
+        wallet: owner,
+        mint: mint_a,
+        system_program,
+        token_program,
+    }
+    .invoke()?;
+    Transfer {
+        from: owner,
+        to: owner_ata_a,
+        lamports: amount,
+    }
+    .invoke()?;
+    SyncNative {
+        native_token: owner_ata_a,
+    }
+    .invoke()?;
+
+    // 3. 挂托管单
+    let make_accounts = [
+        InstructionAccount::writable_signer(owner.address()),
+        InstructionAccount::writable(escrow.address()),
+        InstructionAccount::readonly(mint_a.address()),
+        InstructionAccount::readonly(mint_b.address()),
+        InstructionAccount::writable(owner_ata_a.address()),
+        InstructionAccount::writable(escrow_vault.address()),
+        InstructionAccount::readonly(system_program.address()),
+        InstructionAccount::readonly(token_program.address()),
+        InstructionAccount::readonly(associated_token_program.address()),
+        InstructionAccount::writable(offer_index.address()),
+    ];
+    invoke(
+        &InstructionView {
+            program_id: escrow_program.address(),
+            accounts: &make_accounts,
+            data: &escrow_make_data(seed, receive, amount, expiry, taker),
+        },
+        &[
+            owner,
+            escrow,
+            mint_a,
+            mint_b,
+            owner_ata_a,
+            escrow_vault,
+            system_program,
+            token_program,
+            associated_token_program,
+            offer_index,
+        ],
+    )?;
+
+    // 4. 剩余的存回金库；金库账户不带数据，低于免租金额的存款会让交易失败
+    if rest >= Rent::get()?.try_minimum_balance(0)? {
+        invoke(
+            &InstructionView {
+                program_id: vault_program.address(),
+                accounts: &vault_accounts,
+                data: &vault_deposit_data(rest),
+            },
+            &[owner, vault, system_program],
+        )?;
+    }
+
+    Ok(())
+}
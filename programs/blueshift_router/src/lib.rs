@@ -0,0 +1,76 @@
+//! 路由程序：把金库、托管、原生 AMM 的指令组合成一笔交易完成的复合操作，
+//! 自身不保存任何状态，全部工作都通过 CPI 交给这三个程序。
+//!
+//! | 判别器 | 指令 | 组合 |
+//! |---|---|---|
+//! | 0 | [`vault_to_escrow`] | 金库 Withdraw → 包装 wSOL → 托管 Make → 余额存回金库 |
+//! | 1 | [`take_and_deposit`] | 托管 Take → AMM Deposit（换到的代币 A 作为流动性的一侧） |
+//! | 2 | [`take_and_swap`] | 托管 Take → AMM Swap（把换到的代币 A 卖回池子，套取两边的差价） |
+//!
+//! 另有所有训练营程序共用的 [`get_version`] / [`set_features`]（8 字节判别器，见 blueshift_version）。
+//!
+//! 训练营的三个程序都声明占位地址 2222…2222，同一条链上只能部署其中一个，
+//! 所以目标程序由调用方以账户传入，路由不写死地址。签名权限原样传给被调用的程序：
+//! 用户在外层交易签名，内层指令的账户校验完全由目标程序自己完成。
+
+#![cfg_attr(not(test), no_std)]
+// 程序代码不允许 unwrap / expect / panic!，出错一律返回 ProgramError
+#![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used, clippy::panic))]
+
+use pinocchio::{
+    entrypoint,
+    nostd_panic_handler,
+    error::ProgramError,
+    Address,
+    AccountView,
+    ProgramResult,
+};
+use blueshift_version::{GET_VERSION_DISCRIMINATOR, SET_FEATURES_DISCRIMINATOR};
+
+entrypoint!(process_instruction);
+nostd_panic_handler!();
+
+// 可选的 security.txt 段（security-txt feature），披露流程见仓库根目录 SECURITY.md
+#[cfg(feature = "security-txt")]
+solana_security_txt::security_txt! {
+    name: "Blueshift Router",
+    project_url: "https://github.com/o7reconversion/solana_bootcamp_2026",
+    contacts: "link:https://github.com/o7reconversion/solana_bootcamp_2026/security/advisories/new",
+    policy: "https://github.com/o7reconversion/solana_bootcamp_2026/blob/main/SECURITY.md",
+    source_code: "https://github.com/o7reconversion/solana_bootcamp_2026/tree/main/programs/blueshift_router"
+}
+
+pub mod cpi;
+pub mod instructions;
+pub use instructions::*;
+
+/// 程序 ID: Router1111111111111111111111111111111111111
+///
+/// 路由要和被调用的程序同时部署，不能再用 2222…2222 占位地址
+pub const ID: Address = Address::new_from_array([
+    0x06, 0x5a, 0xfb, 0x9d, 0xf9, 0xa6, 0x55, 0x63,
+    0x4a, 0x90, 0x4a, 0x28, 0x31, 0x65, 0x22, 0x1c,
+    0xe7, 0x41, 0x9a, 0x6b, 0xda, 0x2b, 0x40, 0xd1,
+    0x77, 0x98, 0xb2, 0x60, 0x00, 0x00, 0x00, 0x00,
+]);
+
+/// 程序入口点
+fn process_instruction(
+    _program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // get_version / set_features 的判别器是 8 字节，与其他训练营程序相同
+    if instruction_data.starts_with(&GET_VERSION_DISCRIMINATOR) {
+        return get_version(accounts);
+    }
+    if let Some(data) = instruction_data.strip_prefix(&SET_FEATURES_DISCRIMINATOR) {
+        return set_features(data, accounts);
+    }
+    match instruction_data.split_first() {
+        Some((0, data)) => vault_to_escrow(data, accounts),
+        Some((1, data)) => take_and_deposit(data, accounts),
+        Some((2, data)) => take_and_swap(data, accounts),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
@@ -0,0 +1,40 @@
+use blueshift_common::{AccountCheck, ProgramAccount};
+use blueshift_events::{Event, TreasuryDeposited};
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_system::instructions::Transfer;
+
+use crate::{instructions::read_amount, Treasury};
+
+/// Deposit 指令处理函数：存入 SOL
+///
+/// 账户：depositor（签名，可以是调用方程序的 PDA）、treasury、system_program；
+/// 数据：amount (u64) + source (u8)，见 [`crate::deposit_data`]。
+pub fn deposit(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [depositor, treasury, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !depositor.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let amount = read_amount(data)?;
+    let source = *data.get(8).ok_or(ProgramError::InvalidInstructionData)?;
+    ProgramAccount::<Treasury>::check(treasury)?;
+
+    Transfer {
+        from: depositor,
+        to: treasury,
+        lamports: amount,
+    }
+    .invoke()?;
+    Treasury::load_mut(&mut treasury.try_borrow_mut()?)?.record_deposit(source, amount)?;
+
+    TreasuryDeposited {
+        treasury: treasury.address().to_bytes(),
+        depositor: depositor.address().to_bytes(),
+        mint: [0; 32],
+        source,
+        amount,
+    }
+    .emit();
+    Ok(())
+}
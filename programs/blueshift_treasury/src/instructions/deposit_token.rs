@@ -0,0 +1,49 @@
+use blueshift_common::{
+    AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountCheck, ProgramAccount,
+    TOKEN_PROGRAM_ID,
+};
+use blueshift_events::{Event, TreasuryDeposited};
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{instructions::read_amount, Treasury};
+
+/// DepositToken 指令处理函数：存入 SPL 代币（旧版 Token Program）
+///
+/// 账户：depositor（签名）、depositor_token、treasury、treasury_token（国库的 ATA，需已创建）、
+/// mint、token_program；数据：amount (u64) + source (u8)。
+/// 代币存款不计入国库账户中的累计，来源只记录在事件里。
+pub fn deposit_token(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [depositor, depositor_token, treasury, treasury_token, mint, token_program] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !depositor.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if token_program.address() != &TOKEN_PROGRAM_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let amount = read_amount(data)?;
+    let source = *data.get(8).ok_or(ProgramError::InvalidInstructionData)?;
+    ProgramAccount::<Treasury>::check(treasury)?;
+    AssociatedTokenAccount::check(treasury_token, treasury, mint, token_program)?;
+
+    Transfer {
+        from: depositor_token,
+        to: treasury_token,
+        authority: depositor,
+        amount,
+    }
+    .invoke()?;
+
+    TreasuryDeposited {
+        treasury: treasury.address().to_bytes(),
+        depositor: depositor.address().to_bytes(),
+        mint: mint.address().to_bytes(),
+        source,
+        amount,
+    }
+    .emit();
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use blueshift_common::{AccountCheck, ProgramAccount};
+use blueshift_events::{Event, TreasuryDistributed};
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+
+use crate::{
+    instructions::withdraw::{available, pay},
+    Role, Treasury,
+};
+
+/// 一次分配最多的收款地址数
+pub const MAX_RECIPIENTS: usize = 8;
+
+/// Distribute 指令处理函数：把国库当前可用的全部 SOL 按份额分给收款地址
+///
+/// 账户：distributor（签名）、treasury、收款地址（1..=8 个）；
+/// 数据：每个收款地址一个 u16 (LE) 份额，单位为基点，合计必须是 10000。
+/// 按份额向下取整，零头留在国库中等下次分配。
+pub fn distribute(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [distributor, treasury, recipients @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if recipients.is_empty() || recipients.len() > MAX_RECIPIENTS {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if data.len() != recipients.len() * 2 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let share = |index: usize| u16::from_le_bytes([data[index * 2], data[index * 2 + 1]]);
+    let total_bps: u32 = (0..recipients.len()).map(|i| u32::from(share(i))).sum();
+    if total_bps != 10_000 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    ProgramAccount::<Treasury>::check(treasury)?;
+    Treasury::load(&treasury.try_borrow()?)?.require(Role::Distributor, distributor)?;
+
+    let pool = available(treasury)?;
+    let mut paid = 0u64;
+    for (index, recipient) in recipients.iter().enumerate() {
+        // pool × 10000 不会超过 u128
+        let amount = (u128::from(pool) * u128::from(share(index)) / 10_000) as u64;
+        if amount > 0 {
+            pay(treasury, recipient, amount)?;
+            paid += amount;
+        }
+    }
+
+    TreasuryDistributed {
+        treasury: treasury.address().to_bytes(),
+        authority: distributor.address().to_bytes(),
+        recipients: recipients.len() as u8,
+        amount: paid,
+    }
+    .emit();
+    Ok(())
+}
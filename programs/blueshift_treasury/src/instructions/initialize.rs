@@ -0,0 +1,53 @@
+use blueshift_common::feature_gate;
+use pinocchio::{
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    AccountView,
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{instructions::read_address, Treasury};
+
+/// Initialize 指令处理函数：每个部署只能创建一次
+///
+/// 账户：authority（升级权限，签名，付租金）、program_data、treasury、system_program；
+/// 数据：admin、withdrawer、distributor 三个地址（各 32 字节）。
+/// 只允许升级权限创建，避免部署后被别人抢先设置角色。
+pub fn initialize(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [authority, program_data, treasury, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let admin = read_address(data, 0)?;
+    let withdrawer = read_address(data, 32)?;
+    let distributor = read_address(data, 64)?;
+    feature_gate::check_upgrade_authority(&crate::ID, program_data, authority)?;
+
+    let (treasury_key, bump) = pdas::treasury_pda(&crate::ID.to_bytes());
+    if treasury.address().to_bytes() != treasury_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let bump_binding = [bump];
+    let seeds = [
+        Seed::from(pdas::TREASURY_SEED),
+        Seed::from(&bump_binding),
+    ];
+    CreateAccount {
+        from: authority,
+        to: treasury,
+        lamports: Rent::get()?.try_minimum_balance(Treasury::LEN)?,
+        space: Treasury::LEN as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[Signer::from(&seeds)])?;
+
+    let mut treasury_data = treasury.try_borrow_mut()?;
+    let state = Treasury::load_mut(&mut treasury_data)?;
+    state.bump = bump;
+    state.admin = admin;
+    state.withdrawer = withdrawer;
+    state.distributor = distributor;
+    Ok(())
+}
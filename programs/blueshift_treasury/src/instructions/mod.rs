@@ -0,0 +1,34 @@
+pub mod deposit;
+pub mod deposit_token;
+pub mod distribute;
+pub mod initialize;
+pub mod set_role;
+pub mod version;
+pub mod withdraw;
+pub mod withdraw_token;
+
+pub use deposit::deposit;
+pub use deposit_token::deposit_token;
+pub use distribute::distribute;
+pub use initialize::initialize;
+pub use set_role::set_role;
+pub use version::{get_version, set_features, VERSION};
+pub use withdraw::withdraw;
+pub use withdraw_token::withdraw_token;
+
+use blueshift_common::read_u64_le;
+use pinocchio::error::ProgramError;
+
+/// 从 `offset` 起读 32 字节地址
+pub(crate) fn read_address(data: &[u8], offset: usize) -> Result<[u8; 32], ProgramError> {
+    data.get(offset..offset + 32)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+/// 非零的 u64 (LE) 数量
+pub(crate) fn read_amount(data: &[u8]) -> Result<u64, ProgramError> {
+    read_u64_le(data, 0)
+        .filter(|amount| *amount > 0)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
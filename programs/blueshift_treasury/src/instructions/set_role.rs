@@ -0,0 +1,26 @@
+use blueshift_common::{AccountCheck, ProgramAccount};
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+
+use crate::{instructions::read_address, Role, Treasury};
+
+/// SetRole 指令处理函数：管理员把某个角色交给新地址
+///
+/// 账户：admin（签名）、treasury；数据：role (u8，见 [`Role`]) + 新地址（32 字节）。
+/// 管理员也可以转交自己，转给全零地址等于放弃管理权。
+pub fn set_role(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [admin, treasury] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let (role, holder) = data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let role = Role::try_from(*role)?;
+    let holder = read_address(holder, 0)?;
+    ProgramAccount::<Treasury>::check(treasury)?;
+
+    let mut treasury_data = treasury.try_borrow_mut()?;
+    let state = Treasury::load_mut(&mut treasury_data)?;
+    state.require(Role::Admin, admin)?;
+    state.set_holder(role, holder);
+    Ok(())
+}
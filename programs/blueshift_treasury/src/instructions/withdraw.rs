@@ -0,0 +1,58 @@
+use blueshift_common::{AccountCheck, ProgramAccount};
+use blueshift_events::{Event, TreasuryWithdrawn};
+use pinocchio::{
+    error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    AccountView,
+    ProgramResult,
+};
+
+use crate::{instructions::read_amount, Role, Treasury};
+
+/// 国库中可以取出的 lamports：保留免租金额，账户不会因此被回收
+pub(crate) fn available(treasury: &AccountView) -> Result<u64, ProgramError> {
+    let reserve = Rent::get()?.try_minimum_balance(Treasury::LEN)?;
+    Ok(treasury.lamports().saturating_sub(reserve))
+}
+
+/// 直接改 lamports 转出（国库由本程序持有，不需要 System Program）
+pub(crate) fn pay(treasury: &AccountView, recipient: &AccountView, amount: u64) -> ProgramResult {
+    let rest = treasury
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    let received = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    treasury.set_lamports(rest);
+    recipient.set_lamports(received);
+    Ok(())
+}
+
+/// Withdraw 指令处理函数：提取角色取出 SOL
+///
+/// 账户：withdrawer（签名）、treasury、recipient；数据：amount (u64)
+pub fn withdraw(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [withdrawer, treasury, recipient] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let amount = read_amount(data)?;
+    ProgramAccount::<Treasury>::check(treasury)?;
+    Treasury::load(&treasury.try_borrow()?)?.require(Role::Withdrawer, withdrawer)?;
+
+    if amount > available(treasury)? {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    pay(treasury, recipient, amount)?;
+
+    TreasuryWithdrawn {
+        treasury: treasury.address().to_bytes(),
+        authority: withdrawer.address().to_bytes(),
+        recipient: recipient.address().to_bytes(),
+        mint: [0; 32],
+        amount,
+    }
+    .emit();
+    Ok(())
+}
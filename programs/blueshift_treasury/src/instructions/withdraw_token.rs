@@ -0,0 +1,60 @@
+use blueshift_common::{
+    AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountCheck, ProgramAccount,
+    TOKEN_PROGRAM_ID,
+};
+use blueshift_events::{Event, TreasuryWithdrawn};
+use pinocchio::{
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    AccountView,
+    ProgramResult,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{instructions::read_amount, Role, Treasury};
+
+/// WithdrawToken 指令处理函数：提取角色从国库的 ATA 转出代币，国库 PDA 签名
+///
+/// 账户：withdrawer（签名）、treasury、treasury_token、recipient_token、mint、token_program；
+/// 数据：amount (u64)
+pub fn withdraw_token(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [withdrawer, treasury, treasury_token, recipient_token, mint, token_program] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if token_program.address() != &TOKEN_PROGRAM_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let amount = read_amount(data)?;
+    ProgramAccount::<Treasury>::check(treasury)?;
+    let bump = {
+        let treasury_data = treasury.try_borrow()?;
+        let state = Treasury::load(&treasury_data)?;
+        state.require(Role::Withdrawer, withdrawer)?;
+        state.bump
+    };
+    AssociatedTokenAccount::check(treasury_token, treasury, mint, token_program)?;
+
+    let bump_binding = [bump];
+    let seeds = [
+        Seed::from(pdas::TREASURY_SEED),
+        Seed::from(&bump_binding),
+    ];
+    Transfer {
+        from: treasury_token,
+        to: recipient_token,
+        authority: treasury,
+        amount,
+    }
+    .invoke_signed(&[Signer::from(&seeds)])?;
+
+    TreasuryWithdrawn {
+        treasury: treasury.address().to_bytes(),
+        authority: withdrawer.address().to_bytes(),
+        recipient: recipient_token.address().to_bytes(),
+        mint: mint.address().to_bytes(),
+        amount,
+    }
+    .emit();
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//! 国库：汇集各程序的协议手续费，存取都在同一个 PDA 上，便于审计。
+//!
+//! | 判别器 | 指令 | 说明 |
+//! |---|---|---|
+//! | 0 | [`initialize`] | 升级权限创建国库 `["treasury"]`，设置三个角色 |
+//! | 1 | [`deposit`] | 任何签名者（包括其他程序以 PDA 签名的 CPI）存入 SOL |
+//! | 2 | [`deposit_token`] | 同上，存入国库 ATA 中的 SPL 代币 |
+//! | 3 | [`withdraw`] | 提取角色取出 SOL |
+//! | 4 | [`withdraw_token`] | 提取角色取出代币 |
+//! | 5 | [`distribute`] | 分配角色把可用 SOL 按份额一次分给多个地址 |
+//! | 6 | [`set_role`] | 管理员更换某个角色 |
+//! | `get_version` | [`get_version`] | 版本号与功能位（8 字节判别器，各程序相同） |
+//! | `set_features` | [`set_features`] | 升级权限修改功能开关 |
+//!
+//! 存款数据是 `amount (u64) + source (u8)`，`source` 见 [`source`]；SOL 存款按来源累计在国库账户中，
+//! 代币存款只记录事件（`blueshift_events::TreasuryDeposited`）。金库、托管与 AMM 的手续费路径
+//! 通过 CPI 调用 Deposit 时，照 [`deposit_data`] 的布局编码即可（同路由的 `cpi` 模块）。
+//!
+//! 管理员建议设为治理理事会的签名地址（`pdas::governance_authority_pda`），
+//! 这样角色的更换也要经过理事会表决；提取与分配角色可以交给不同的地址。
+
+#![cfg_attr(not(test), no_std)]
+// 程序代码不允许 unwrap / expect / panic!，出错一律返回 ProgramError
+#![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used, clippy::panic))]
+
+use pinocchio::{
+    entrypoint,
+    nostd_panic_handler,
+    error::ProgramError,
+    Address,
+    AccountView,
+    ProgramResult,
+};
+use blueshift_version::{GET_VERSION_DISCRIMINATOR, SET_FEATURES_DISCRIMINATOR};
+
+entrypoint!(process_instruction);
+nostd_panic_handler!();
+
+// 可选的 security.txt 段（security-txt feature），披露流程见仓库根目录 SECURITY.md
+#[cfg(feature = "security-txt")]
+solana_security_txt::security_txt! {
+    name: "Blueshift Treasury",
+    project_url: "https://github.com/o7reconversion/solana_bootcamp_2026",
+    contacts: "link:https://github.com/o7reconversion/solana_bootcamp_2026/security/advisories/new",
+    policy: "https://github.com/o7reconversion/solana_bootcamp_2026/blob/main/SECURITY.md",
+    source_code: "https://github.com/o7reconversion/solana_bootcamp_2026/tree/main/programs/blueshift_treasury"
+}
+
+pub mod instructions;
+pub use instructions::*;
+
+pub mod state;
+pub use state::*;
+
+/// 程序 ID: Treasury11111111111111111111111111111111111
+pub const ID: Address = Address::new_from_array([
+    0x06, 0xe1, 0x3c, 0xfb, 0x5c, 0x64, 0x3d, 0xea,
+    0x6a, 0x28, 0xf0, 0x28, 0xeb, 0x18, 0x5a, 0xc2,
+    0x87, 0xb3, 0x08, 0x29, 0xa0, 0x42, 0xea, 0x1a,
+    0xfb, 0x8e, 0xbf, 0x50, 0x00, 0x00, 0x00, 0x00,
+]);
+
+/// Deposit 的指令数据：`[1, amount, source]`，供其他程序的 CPI 使用
+pub fn deposit_data(amount: u64, source: u8) -> [u8; 10] {
+    let mut data = [0u8; 10];
+    data[0] = 1;
+    data[1..9].copy_from_slice(&amount.to_le_bytes());
+    data[9] = source;
+    data
+}
+
+/// 程序入口点
+fn process_instruction(
+    _program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // get_version / set_features 的判别器是 8 字节，与其他训练营程序相同
+    if instruction_data.starts_with(&GET_VERSION_DISCRIMINATOR) {
+        return get_version(accounts);
+    }
+    if let Some(data) = instruction_data.strip_prefix(&SET_FEATURES_DISCRIMINATOR) {
+        return set_features(data, accounts);
+    }
+    match instruction_data.split_first() {
+        Some((0, data)) => initialize(data, accounts),
+        Some((1, data)) => deposit(data, accounts),
+        Some((2, data)) => deposit_token(data, accounts),
+        Some((3, data)) => withdraw(data, accounts),
+        Some((4, data)) => withdraw_token(data, accounts),
+        Some((5, data)) => distribute(data, accounts),
+        Some((6, data)) => set_role(data, accounts),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
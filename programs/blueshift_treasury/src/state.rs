@@ -0,0 +1,133 @@
+//! 国库账户。
+//!
+//! 与托管的 Escrow 一样用 blueshift_zerocopy 加载：多字节整数存成字节数组，对齐为 1。
+
+use core::mem::size_of;
+
+use blueshift_common::ProgramOwned;
+use blueshift_zerocopy::{layout, Pod, Zeroable};
+use pinocchio::{error::ProgramError, AccountView, Address};
+
+/// 存款来源：记录在 SOL 累计与存款事件中，只用于统计，不做校验
+pub mod source {
+    pub const VAULT: u8 = 0;
+    pub const ESCROW: u8 = 1;
+    pub const AMM: u8 = 2;
+    pub const OTHER: u8 = 255;
+
+    /// 累计数组中的位置；未知来源计入最后一格
+    pub(crate) fn index(source: u8) -> usize {
+        match source {
+            VAULT => 0,
+            ESCROW => 1,
+            AMM => 2,
+            _ => 3,
+        }
+    }
+}
+
+/// 角色编号，SetRole 的第一个字节
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// 更换角色
+    Admin = 0,
+    /// Withdraw / WithdrawToken
+    Withdrawer = 1,
+    /// Distribute
+    Distributor = 2,
+}
+
+impl TryFrom<u8> for Role {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Admin),
+            1 => Ok(Self::Withdrawer),
+            2 => Ok(Self::Distributor),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// 国库：PDA `["treasury"]`
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[bytemuck(crate = "blueshift_zerocopy::bytemuck")]
+pub struct Treasury {
+    pub bump: u8,
+    pub admin: [u8; 32],
+    pub withdrawer: [u8; 32],
+    pub distributor: [u8; 32],
+    /// 按来源（金库、托管、AMM、其他）累计存入的 lamports
+    deposited: [[u8; 8]; 4],
+}
+
+layout!(Treasury = 129 {
+    BUMP_OFFSET: bump = 0,
+    ADMIN_OFFSET: admin = 1,
+    WITHDRAWER_OFFSET: withdrawer = 33,
+    DISTRIBUTOR_OFFSET: distributor = 65,
+    DEPOSITED_OFFSET: deposited = 97,
+});
+
+impl ProgramOwned for Treasury {
+    const PROGRAM_ID: Address = crate::ID;
+    const LEN: usize = Treasury::LEN;
+}
+
+impl Treasury {
+    pub const LEN: usize = size_of::<Treasury>();
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        blueshift_zerocopy::load(bytes).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        blueshift_zerocopy::load_mut(bytes).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// 角色对应的地址
+    pub fn holder(&self, role: Role) -> &[u8; 32] {
+        match role {
+            Role::Admin => &self.admin,
+            Role::Withdrawer => &self.withdrawer,
+            Role::Distributor => &self.distributor,
+        }
+    }
+
+    pub fn set_holder(&mut self, role: Role, holder: [u8; 32]) {
+        match role {
+            Role::Admin => self.admin = holder,
+            Role::Withdrawer => self.withdrawer = holder,
+            Role::Distributor => self.distributor = holder,
+        }
+    }
+
+    /// `signer` 必须签名且是 `role` 的持有者
+    pub fn require(&self, role: Role, signer: &AccountView) -> Result<(), ProgramError> {
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if signer.address().to_bytes() != *self.holder(role) {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        Ok(())
+    }
+
+    /// 来源 `source` 累计存入的 lamports
+    pub fn deposited(&self, source: u8) -> u64 {
+        u64::from_le_bytes(self.deposited[source::index(source)])
+    }
+
+    pub fn record_deposit(&mut self, source: u8, amount: u64) -> Result<(), ProgramError> {
+        let total = self
+            .deposited(source)
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.deposited[source::index(source)] = total.to_le_bytes();
+        Ok(())
+    }
+}
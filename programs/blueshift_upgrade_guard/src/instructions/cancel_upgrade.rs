@@ -0,0 +1,16 @@
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+
+use crate::{instructions::check_admin, Guard};
+
+/// CancelUpgrade 指令处理函数：撤回待执行的升级
+///
+/// 账户：admin（签名）、guard (w)。缓冲区不会被关闭，需要时管理员另行重新提案。
+pub fn cancel_upgrade(accounts: &[AccountView]) -> ProgramResult {
+    let [admin, guard] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_admin(admin, guard)?;
+
+    let mut guard_data = guard.try_borrow_mut()?;
+    Guard::load_mut(&mut guard_data)?.cancel()
+}
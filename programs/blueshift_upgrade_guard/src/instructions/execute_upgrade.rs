@@ -0,0 +1,71 @@
+use blueshift_common::{AccountCheck, ProgramAccount};
+use pinocchio::{
+    cpi::{invoke_signed_with_bounds, Seed, Signer},
+    error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+    sysvars::{clock::Clock, Sysvar},
+    AccountView,
+    ProgramResult,
+};
+
+use crate::{
+    hash_program,
+    instructions::{LOADER, LOADER_UPGRADE},
+    parse_buffer,
+    Guard,
+};
+
+/// ExecuteUpgrade 指令处理函数：等待期满后由任何人执行，守卫 PDA 作为升级权限签名
+///
+/// 账户：guard (w)、program_data (w)、program (w)、buffer (w)、spill (w)、rent、clock、loader。
+/// 缓冲区的内容会重新哈希，与提案时的记录不一致就拒绝；
+/// 先清除提案再发起 CPI，同一提案不能执行两次。
+pub fn execute_upgrade(accounts: &[AccountView]) -> ProgramResult {
+    let [guard, program_data, program, buffer, spill, rent, clock, _loader] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    ProgramAccount::<Guard>::check(guard)?;
+
+    let buffer_hash = {
+        let buffer_data = buffer.try_borrow()?;
+        let (_, program_bytes) =
+            parse_buffer(&buffer_data).ok_or(ProgramError::InvalidAccountData)?;
+        hash_program(program_bytes)
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    let bump = {
+        let mut guard_data = guard.try_borrow_mut()?;
+        let state = Guard::load_mut(&mut guard_data)?;
+        if state.program != program.address().to_bytes() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        state.take_pending(&buffer.address().to_bytes(), &buffer_hash, now)?;
+        state.bump
+    };
+
+    let bump_binding = [bump];
+    let seeds = [
+        Seed::from(pdas::UPGRADE_GUARD_SEED),
+        Seed::from(program.address().as_ref()),
+        Seed::from(&bump_binding),
+    ];
+    let metas = [
+        InstructionAccount::writable(program_data.address()),
+        InstructionAccount::writable(program.address()),
+        InstructionAccount::writable(buffer.address()),
+        InstructionAccount::writable(spill.address()),
+        InstructionAccount::readonly(rent.address()),
+        InstructionAccount::readonly(clock.address()),
+        InstructionAccount::readonly_signer(guard.address()),
+    ];
+    invoke_signed_with_bounds::<7>(
+        &InstructionView {
+            program_id: &LOADER,
+            accounts: &metas,
+            data: &LOADER_UPGRADE.to_le_bytes(),
+        },
+        &[program_data, program, buffer, spill, rent, clock, guard],
+        &[Signer::from(&seeds)],
+    )
+}
@@ -0,0 +1,76 @@
+use blueshift_common::{feature_gate, read_u64_le};
+use pinocchio::{
+    cpi::{invoke_signed_with_bounds, Seed, Signer},
+    error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+    sysvars::{rent::Rent, Sysvar},
+    AccountView,
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    instructions::{read_address, LOADER, LOADER_SET_AUTHORITY},
+    Guard,
+};
+
+/// InitGuard 指令处理函数：把 `program` 的升级权限交给守卫 PDA
+///
+/// 账户：authority（当前升级权限，签名，付租金）、guard、program、program_data (w)、
+/// system_program、loader；数据：delay (i64) + admin 地址（32 字节）。
+/// 权限转出后只有守卫能升级，原权限密钥不再有用。
+pub fn init_guard(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [authority, guard, program, program_data, _system_program, _loader] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let delay = read_u64_le(data, 0).ok_or(ProgramError::InvalidInstructionData)? as i64;
+    let admin = read_address(data, 8)?;
+    feature_gate::check_upgrade_authority(program.address(), program_data, authority)?;
+
+    let program_key = program.address().to_bytes();
+    let (guard_key, bump) = pdas::upgrade_guard_pda(&crate::ID.to_bytes(), &program_key);
+    if guard.address().to_bytes() != guard_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let bump_binding = [bump];
+    let seeds = [
+        Seed::from(pdas::UPGRADE_GUARD_SEED),
+        Seed::from(program.address().as_ref()),
+        Seed::from(&bump_binding),
+    ];
+    CreateAccount {
+        from: authority,
+        to: guard,
+        lamports: Rent::get()?.try_minimum_balance(Guard::LEN)?,
+        space: Guard::LEN as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[Signer::from(&seeds)])?;
+
+    {
+        let mut guard_data = guard.try_borrow_mut()?;
+        let state = Guard::load_mut(&mut guard_data)?;
+        state.program = program_key;
+        state.admin = admin;
+        state.bump = bump;
+        // 新账户的等待期是 0，set_delay 的“只能延长”对初始值同样适用
+        state.set_delay(delay)?;
+    }
+
+    // SetAuthority：当前权限已经是外层交易的签名者，不需要 PDA 签名
+    let metas = [
+        InstructionAccount::writable(program_data.address()),
+        InstructionAccount::readonly_signer(authority.address()),
+        InstructionAccount::readonly(guard.address()),
+    ];
+    invoke_signed_with_bounds::<3>(
+        &InstructionView {
+            program_id: &LOADER,
+            accounts: &metas,
+            data: &LOADER_SET_AUTHORITY.to_le_bytes(),
+        },
+        &[program_data, authority, guard],
+        &[],
+    )
+}
@@ -0,0 +1,45 @@
+pub mod cancel_upgrade;
+pub mod execute_upgrade;
+pub mod init_guard;
+pub mod propose_upgrade;
+pub mod update_config;
+pub mod version;
+
+pub use cancel_upgrade::cancel_upgrade;
+pub use execute_upgrade::execute_upgrade;
+pub use init_guard::init_guard;
+pub use propose_upgrade::propose_upgrade;
+pub use update_config::update_config;
+pub use version::{get_version, set_features, VERSION};
+
+use blueshift_common::{AccountCheck, ProgramAccount};
+use pinocchio::{error::ProgramError, AccountView, Address};
+
+use crate::Guard;
+
+/// BPF Upgradeable Loader
+pub(crate) const LOADER: Address = Address::new_from_array(pdas::BPF_LOADER_UPGRADEABLE_ID);
+
+/// Loader 指令序号（bincode 编码的 u32 枚举）
+pub(crate) const LOADER_UPGRADE: u32 = 3;
+pub(crate) const LOADER_SET_AUTHORITY: u32 = 4;
+
+/// 从 `offset` 起读 32 字节地址
+pub(crate) fn read_address(data: &[u8], offset: usize) -> Result<[u8; 32], ProgramError> {
+    data.get(offset..offset + 32)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+/// 管理员指令的共同检查：admin 签名、guard 归本程序所有，且 admin 与守卫记录的一致
+pub(crate) fn check_admin(admin: &AccountView, guard: &AccountView) -> Result<(), ProgramError> {
+    if !admin.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    ProgramAccount::<Guard>::check(guard)?;
+    let guard_data = guard.try_borrow()?;
+    if Guard::load(&guard_data)?.admin != admin.address().to_bytes() {
+        return Err(ProgramError::IncorrectAuthority);
+    }
+    Ok(())
+}
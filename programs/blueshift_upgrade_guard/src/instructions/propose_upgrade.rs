@@ -0,0 +1,41 @@
+use pinocchio::{
+    error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    AccountView,
+    ProgramResult,
+};
+
+use crate::{
+    hash_program,
+    instructions::{check_admin, LOADER},
+    parse_buffer,
+    Guard,
+};
+
+/// ProposeUpgrade 指令处理函数：登记用 `buffer` 升级，等待期从现在开始计算
+///
+/// 账户：admin（签名）、guard (w)、buffer。缓冲区的权限必须已经设为守卫，
+/// 否则提案之后仍可被原权限改写，哈希就失去了意义。已有的提案被新提案替换。
+pub fn propose_upgrade(accounts: &[AccountView]) -> ProgramResult {
+    let [admin, guard, buffer] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_admin(admin, guard)?;
+    if !buffer.owned_by(&LOADER) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let buffer_hash = {
+        let buffer_data = buffer.try_borrow()?;
+        let (authority, program_bytes) =
+            parse_buffer(&buffer_data).ok_or(ProgramError::InvalidAccountData)?;
+        if authority != guard.address().to_bytes() {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        hash_program(program_bytes)
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut guard_data = guard.try_borrow_mut()?;
+    Guard::load_mut(&mut guard_data)?.propose(buffer.address().to_bytes(), buffer_hash, now)
+}
@@ -0,0 +1,26 @@
+use blueshift_common::read_u64_le;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+
+use crate::{
+    instructions::{check_admin, read_address},
+    Guard,
+};
+
+/// UpdateConfig 指令处理函数：更换管理员、修改等待期
+///
+/// 账户：admin（签名）、guard (w)；数据：新 admin 地址（32 字节）+ delay (i64)。
+/// 等待期只能延长；已登记的提案保留原来的可执行时间。
+pub fn update_config(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let [admin, guard] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let new_admin = read_address(data, 0)?;
+    let delay = read_u64_le(data, 32).ok_or(ProgramError::InvalidInstructionData)? as i64;
+    check_admin(admin, guard)?;
+
+    let mut guard_data = guard.try_borrow_mut()?;
+    let state = Guard::load_mut(&mut guard_data)?;
+    state.set_delay(delay)?;
+    state.admin = new_admin;
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use blueshift_common::feature_gate;
+use blueshift_version::{FeatureGate, Version};
+use pinocchio::{
+    cpi::{Seed, Signer},
+    sysvars::{rent::Rent, Sysvar},
+    AccountView,
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+/// 本程序的版本；守卫目前没有需要开关控制的功能
+pub const VERSION: Version = blueshift_version::version!(0);
+
+/// GetVersion 指令处理函数：账户可选传入功能开关 PDA，结果写入 return data
+pub fn get_version(accounts: &[AccountView]) -> ProgramResult {
+    feature_gate::get_version(VERSION, &crate::ID, accounts)
+}
+
+/// SetFeatures 指令处理函数：只有升级权限可以调用，开关账户不存在时先创建
+///
+/// 账户：authority（签名，付租金）、program_data、feature_gate、system_program；数据：u64 (LE) 功能位
+pub fn set_features(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    feature_gate::set_features(&crate::ID, accounts, data, |payer, gate, bump| {
+        let bump_binding = [bump];
+        let seeds = [
+            Seed::from(pdas::FEATURE_GATE_SEED),
+            Seed::from(&bump_binding),
+        ];
+        CreateAccount {
+            from: payer,
+            to: gate,
+            lamports: Rent::get()?.try_minimum_balance(FeatureGate::LEN)?,
+            space: FeatureGate::LEN as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Signer::from(&seeds)])
+    })
+}
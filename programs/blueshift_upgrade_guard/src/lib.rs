@@ -0,0 +1,80 @@
+//! 升级守卫：接管其他程序的升级权限，升级必须先提案、等满等待期才能执行。
+//!
+//! | 判别器 | 指令 | 说明 |
+//! |---|---|---|
+//! | 0 | [`init_guard`] | 当前升级权限把权限转给守卫 PDA，并设定管理员与等待期 |
+//! | 1 | [`propose_upgrade`] | 管理员登记缓冲区与程序字节的哈希，开始计时 |
+//! | 2 | [`cancel_upgrade`] | 管理员撤回待执行的升级 |
+//! | 3 | [`execute_upgrade`] | 等待期满后任何人都可以执行，守卫 PDA 签名调用 Loader 的 Upgrade |
+//! | 4 | [`update_config`] | 管理员更换管理员、延长等待期 |
+//! | `get_version` | [`get_version`] | 版本号与功能位（8 字节判别器，各程序相同） |
+//! | `set_features` | [`set_features`] | 升级权限修改功能开关 |
+//!
+//! 守卫 PDA 是 `["upgrade_guard", program]`（见 `pdas::upgrade_guard_pda`），每个受保护的程序一个。
+//! 托管与 AMM 转交升级权限之后，即使管理员密钥泄露，恶意升级也要公开挂满等待期，
+//! 用户有时间在新代码生效前撤出资金。缓冲区权限必须是守卫，提案后缓冲区内容无法再被改写；
+//! 执行时仍会重新计算哈希，与提案时的记录比对。
+//! 管理员通常设为治理理事会的签名地址，提案本身也要经过表决。
+
+#![cfg_attr(not(test), no_std)]
+// 程序代码不允许 unwrap / expect / panic!，出错一律返回 ProgramError
+#![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used, clippy::panic))]
+
+use pinocchio::{
+    entrypoint,
+    nostd_panic_handler,
+    error::ProgramError,
+    Address,
+    AccountView,
+    ProgramResult,
+};
+use blueshift_version::{GET_VERSION_DISCRIMINATOR, SET_FEATURES_DISCRIMINATOR};
+
+entrypoint!(process_instruction);
+nostd_panic_handler!();
+
+// 可选的 security.txt 段（security-txt feature），披露流程见仓库根目录 SECURITY.md
+#[cfg(feature = "security-txt")]
+solana_security_txt::security_txt! {
+    name: "Blueshift Upgrade Guard",
+    project_url: "https://github.com/o7reconversion/solana_bootcamp_2026",
+    contacts: "link:https://github.com/o7reconversion/solana_bootcamp_2026/security/advisories/new",
+    policy: "https://github.com/o7reconversion/solana_bootcamp_2026/blob/main/SECURITY.md",
+    source_code: "https://github.com/o7reconversion/solana_bootcamp_2026/tree/main/programs/blueshift_upgrade_guard"
+}
+
+pub mod instructions;
+pub use instructions::*;
+
+pub mod state;
+pub use state::*;
+
+/// 程序 ID: UpgradeGuard1111111111111111111111111111111
+pub const ID: Address = Address::new_from_array([
+    0x07, 0x20, 0x9a, 0x14, 0xcc, 0x98, 0x35, 0x41,
+    0x12, 0x86, 0x0e, 0x31, 0x7a, 0x23, 0x4e, 0xd6,
+    0xa6, 0xa8, 0x86, 0xe3, 0xe0, 0xb5, 0xcd, 0xca,
+    0x97, 0xbe, 0xf3, 0x1d, 0x00, 0x00, 0x00, 0x00,
+]);
+
+/// 程序入口点
+fn process_instruction(
+    _program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    if instruction_data.starts_with(&GET_VERSION_DISCRIMINATOR) {
+        return get_version(accounts);
+    }
+    if let Some(data) = instruction_data.strip_prefix(&SET_FEATURES_DISCRIMINATOR) {
+        return set_features(data, accounts);
+    }
+    match instruction_data.split_first() {
+        Some((0, data)) => init_guard(data, accounts),
+        Some((1, _)) => propose_upgrade(accounts),
+        Some((2, _)) => cancel_upgrade(accounts),
+        Some((3, _)) => execute_upgrade(accounts),
+        Some((4, data)) => update_config(data, accounts),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
@@ -0,0 +1,236 @@
+//! 守卫账户，以及 Upgradeable Loader 缓冲区账户的解析。
+//!
+//! 与治理程序的账户一样，字段全部是字节数组（对齐为 1），可以直接按结构体读取。
+
+use core::mem::size_of;
+
+use blueshift_common::ProgramOwned;
+use pinocchio::{error::ProgramError, Address};
+
+const DAY: i64 = 24 * 60 * 60;
+/// 等待期上限；超过它的配置多半是单位写错（把 slot 当成秒）
+pub const MAX_DELAY: i64 = 30 * DAY;
+
+/// Buffer 账户的头部：`u32` 枚举序号（1 = Buffer）+ `Option<Pubkey>` 缓冲区权限
+pub const BUFFER_METADATA_LEN: usize = 4 + 1 + 32;
+
+/// 守卫：PDA `["upgrade_guard", program]`，同时是 `program` 的升级权限
+#[repr(C)]
+pub struct Guard {
+    /// 受保护的程序
+    pub program: [u8; 32],
+    /// 可以提出 / 取消升级、修改配置的管理员，通常是治理理事会的签名地址
+    pub admin: [u8; 32],
+    /// 提案到可以执行之间至少等待的秒数
+    pub delay: [u8; 8],
+    pub bump: u8,
+    /// 是否有待执行的升级
+    pub pending: u8,
+    /// 待执行升级使用的缓冲区
+    pub buffer: [u8; 32],
+    /// 提案时缓冲区中程序字节（去掉头部）的 sha256
+    pub buffer_hash: [u8; 32],
+    /// 最早可以执行的时间（unix 秒）
+    pub eta: [u8; 8],
+}
+
+impl ProgramOwned for Guard {
+    const PROGRAM_ID: Address = crate::ID;
+    const LEN: usize = Guard::LEN;
+}
+
+impl Guard {
+    pub const LEN: usize = size_of::<Guard>();
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+    }
+
+    pub fn delay(&self) -> i64 {
+        i64::from_le_bytes(self.delay)
+    }
+
+    /// 修改等待期：只能延长，否则管理员可以先缩短等待期再立即升级，绕过 timelock
+    pub fn set_delay(&mut self, delay: i64) -> Result<(), ProgramError> {
+        if !(self.delay()..=MAX_DELAY).contains(&delay) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.delay = delay.to_le_bytes();
+        Ok(())
+    }
+
+    /// 记录升级提案，已有的提案被覆盖，等待期重新计算
+    pub fn propose(
+        &mut self,
+        buffer: [u8; 32],
+        buffer_hash: [u8; 32],
+        now: i64,
+    ) -> Result<(), ProgramError> {
+        let eta = now
+            .checked_add(self.delay())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.pending = 1;
+        self.buffer = buffer;
+        self.buffer_hash = buffer_hash;
+        self.eta = eta.to_le_bytes();
+        Ok(())
+    }
+
+    pub fn cancel(&mut self) -> Result<(), ProgramError> {
+        if self.pending == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        self.clear();
+        Ok(())
+    }
+
+    /// 执行前的检查：有提案、等待期已满、缓冲区与哈希都和提案时一致。通过后清空提案
+    pub fn take_pending(
+        &mut self,
+        buffer: &[u8; 32],
+        buffer_hash: &[u8; 32],
+        now: i64,
+    ) -> Result<(), ProgramError> {
+        if self.pending == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if now < i64::from_le_bytes(self.eta) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if self.buffer != *buffer || self.buffer_hash != *buffer_hash {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        self.clear();
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.pending = 0;
+        self.buffer = [0; 32];
+        self.buffer_hash = [0; 32];
+        self.eta = [0; 8];
+    }
+}
+
+/// Buffer 账户的权限与程序字节；数据不是 Buffer 或权限已被清空时返回 `None`
+pub fn parse_buffer(data: &[u8]) -> Option<([u8; 32], &[u8])> {
+    const BUFFER: [u8; 4] = 1u32.to_le_bytes();
+    if data.get(..4)? != BUFFER || *data.get(4)? != 1 {
+        return None;
+    }
+    let authority = data.get(5..BUFFER_METADATA_LEN)?.try_into().ok()?;
+    Some((authority, data.get(BUFFER_METADATA_LEN..)?))
+}
+
+/// 程序字节的 sha256，与 `sha256sum` 对 `cargo build-sbf` 产物（补齐到缓冲区长度）的结果一致
+#[cfg(target_os = "solana")]
+pub fn hash_program(bytes: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    let slices: [&[u8]; 1] = [bytes];
+    unsafe {
+        solana_define_syscall::definitions::sol_sha256(
+            slices.as_ptr() as *const u8,
+            slices.len() as u64,
+            hash.as_mut_ptr(),
+        )
+    };
+    hash
+}
+
+#[cfg(not(target_os = "solana"))]
+pub fn hash_program(bytes: &[u8]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(bytes).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard(delay: i64) -> Guard {
+        Guard {
+            program: [1; 32],
+            admin: [2; 32],
+            delay: delay.to_le_bytes(),
+            bump: 255,
+            pending: 0,
+            buffer: [0; 32],
+            buffer_hash: [0; 32],
+            eta: [0; 8],
+        }
+    }
+
+    #[test]
+    fn layout_has_no_padding() {
+        assert_eq!(Guard::LEN, 32 + 32 + 8 + 1 + 1 + 32 + 32 + 8);
+    }
+
+    #[test]
+    fn upgrade_waits_for_delay_and_matching_buffer() {
+        let mut guard = guard(3600);
+        guard.propose([7; 32], [8; 32], 1_000).unwrap();
+        assert_eq!(i64::from_le_bytes(guard.eta), 4_600);
+
+        assert_eq!(
+            guard.take_pending(&[7; 32], &[8; 32], 4_599),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert!(guard.take_pending(&[9; 32], &[8; 32], 4_600).is_err());
+        assert!(guard.take_pending(&[7; 32], &[0; 32], 4_600).is_err());
+        guard.take_pending(&[7; 32], &[8; 32], 4_600).unwrap();
+        // 同一提案不能执行两次
+        assert!(guard.take_pending(&[7; 32], &[8; 32], 4_600).is_err());
+        assert!(guard.cancel().is_err());
+    }
+
+    #[test]
+    fn reproposing_restarts_the_clock() {
+        let mut guard = guard(100);
+        guard.propose([7; 32], [8; 32], 0).unwrap();
+        guard.propose([7; 32], [8; 32], 90).unwrap();
+        assert!(guard.take_pending(&[7; 32], &[8; 32], 150).is_err());
+        guard.cancel().unwrap();
+        assert_eq!(guard.pending, 0);
+    }
+
+    #[test]
+    fn delay_can_only_grow() {
+        let mut guard = guard(100);
+        assert!(guard.set_delay(99).is_err());
+        assert!(guard.set_delay(MAX_DELAY + 1).is_err());
+        guard.set_delay(200).unwrap();
+        assert_eq!(guard.delay(), 200);
+    }
+
+    #[test]
+    fn parses_buffer_accounts() {
+        let mut data = [0u8; BUFFER_METADATA_LEN + 3];
+        data[..4].copy_from_slice(&1u32.to_le_bytes());
+        data[4] = 1;
+        data[5..37].copy_from_slice(&[3; 32]);
+        data[37..].copy_from_slice(&[0xaa, 0xbb, 0xcc]);
+        let (authority, program) = parse_buffer(&data).unwrap();
+        assert_eq!(authority, [3; 32]);
+        assert_eq!(program, [0xaa, 0xbb, 0xcc]);
+        assert_eq!(hash_program(program), hash_program(&[0xaa, 0xbb, 0xcc]));
+
+        // 权限为空（缓冲区已锁定）或不是 Buffer
+        data[4] = 0;
+        assert!(parse_buffer(&data).is_none());
+        data[4] = 1;
+        data[0] = 3;
+        assert!(parse_buffer(&data).is_none());
+    }
+}
@@ -0,0 +1,67 @@
+//! 链下客户端工具：从交易日志中解码本程序发出的事件（编码约定见 `events` 模块）。
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use borsh::BorshDeserialize;
+use solana_program::pubkey::Pubkey;
+
+use crate::{EVENT_VERSION, Event};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// 数据为空，缺少版本字节
+    Empty,
+    /// 事件版本与本 crate 不一致
+    UnsupportedVersion(u8),
+    /// `Program data:` 后的字段不是合法的 base64
+    Base64,
+    /// borsh 解码失败
+    Borsh,
+}
+
+/// 解码一条事件：`[EVENT_VERSION] + borsh(Event)`
+pub fn decode_event(data: &[u8]) -> Result<Event, DecodeError> {
+    let (version, payload) = data.split_first().ok_or(DecodeError::Empty)?;
+    if *version != EVENT_VERSION {
+        return Err(DecodeError::UnsupportedVersion(*version));
+    }
+    Event::try_from_slice(payload).map_err(|_| DecodeError::Borsh)
+}
+
+/// 从交易日志中取出 `program_id` 发出的全部事件（按出现顺序）。
+///
+/// 通过 `Program <id> invoke [n]` / `Program <id> success|failed` 跟踪调用栈，
+/// 其它程序（包括被 CPI 调用的程序）打印的 `Program data:` 会被忽略。
+pub fn events_from_logs<S: AsRef<str>>(
+    program_id: &Pubkey,
+    logs: &[S],
+) -> Vec<Result<Event, DecodeError>> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(data) = log.strip_prefix("Program data: ") {
+            if stack.last() == Some(&program_id.as_str()) {
+                // 本程序每次只写一个字段
+                let field = data.split(' ').next().unwrap_or_default();
+                events.push(
+                    STANDARD
+                        .decode(field)
+                        .map_err(|_| DecodeError::Base64)
+                        .and_then(|bytes| decode_event(&bytes)),
+                );
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            match (words.next(), words.next()) {
+                (Some(id), Some("invoke")) => stack.push(id),
+                (Some(_), Some("success" | "failed:")) => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
@@ -0,0 +1,46 @@
+use core::fmt;
+
+use solana_program::program_error::ProgramError;
+
+/// 账户校验失败时返回的自定义错误，以 `ProgramError::Custom(code)` 的形式交给客户端。
+/// 每种失败原因一个错误码，客户端无需解析日志就能知道是哪项检查没通过。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SsError {
+    /// 账户应当签名但没有签名
+    NotSigner = 0,
+    /// 账户应当可写但以只读方式传入
+    NotWritable = 1,
+    /// 账户的 owner 不是预期的程序（例如还没有被本程序创建）
+    InvalidOwner = 2,
+    /// 地址与预期的 PDA 不一致
+    InvalidAddress = 3,
+    /// CPI 目标不是预期的程序
+    InvalidProgram = 4,
+    /// 当日领取量超过水龙头的上限
+    DripLimitExceeded = 5,
+    /// 地址不在白名单中
+    NotWhitelisted = 6,
+    /// 白名单已满
+    WhitelistFull = 7,
+}
+
+impl From<SsError> for ProgramError {
+    fn from(error: SsError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl fmt::Display for SsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SsError::NotSigner => write!(f, "没有签名"),
+            SsError::NotWritable => write!(f, "账户不可写"),
+            SsError::InvalidOwner => write!(f, "非法的所有者"),
+            SsError::InvalidAddress => write!(f, "非法的地址"),
+            SsError::InvalidProgram => write!(f, "非法的程序"),
+            SsError::DripLimitExceeded => write!(f, "超过当日领取上限"),
+            SsError::NotWhitelisted => write!(f, "地址不在白名单中"),
+            SsError::WhitelistFull => write!(f, "白名单已满"),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    entrypoint::ProgramResult, log::sol_log_data, program_error::ProgramError, pubkey::Pubkey,
+};
+
+/// 事件格式版本。已有变体的字段发生变化时递增；只在末尾追加新变体时不需要。
+pub const EVENT_VERSION: u8 = 1;
+
+/// 程序发出的结构化事件。
+///
+/// 编码约定：一次 `sol_log_data` 只写一个字段，内容为 `[EVENT_VERSION] + borsh(Event)`，
+/// 日志中显示为 `Program data: <base64>`。borsh 枚举的第一个字节即变体序号，
+/// 因此只能在末尾追加新变体，不能调整已有变体的顺序。链下解码见 `client` 模块。
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// EmitMessage：sender 发出的一段文本
+    Message { sender: Pubkey, text: String },
+    /// Tip：打赏后的累计值
+    Tip {
+        tipper: Pubkey,
+        tip_jar: Pubkey,
+        amount: u64,
+        total: u64,
+        count: u64,
+    },
+}
+
+impl Event {
+    pub fn encode(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut data = vec![EVENT_VERSION];
+        self.serialize(&mut data)
+            .map_err(|_| ProgramError::InvalidArgument)?;
+        Ok(data)
+    }
+
+    pub fn emit(&self) -> ProgramResult {
+        sol_log_data(&[&self.encode()?]);
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::{
+    check_greeting_address, close_account, require_owner, require_signer, require_writable,
+};
+
+/// 账户：[user (signer, writable), greeting (writable)]
+pub struct CloseGreetingAccounts<'a, 'info> {
+    pub user: &'a AccountInfo<'info>,
+    pub greeting: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for CloseGreetingAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let user = next_account_info(accounts_iter)?;
+        let greeting = next_account_info(accounts_iter)?;
+
+        // 每一项检查失败都有独立的错误码（见 SsError）
+        require_signer(user)?;
+        require_writable(user)?;
+        require_writable(greeting)?;
+
+        Ok(Self { user, greeting })
+    }
+}
+
+/// 受保护的指令：只有问候账户的 user 本人可以关闭它并取回租金，无指令数据
+pub struct CloseGreeting<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: CloseGreetingAccounts<'a, 'info>,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [AccountInfo<'info>])> for CloseGreeting<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, accounts): (&'a Pubkey, &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            program_id,
+            accounts: CloseGreetingAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CloseGreeting<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &11;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+
+        // 1. greeting 必须是 user 的 PDA，且确实由本程序创建
+        check_greeting_address(self.program_id, accounts.user, accounts.greeting)?;
+        require_owner(accounts.greeting, self.program_id)?;
+
+        // 2. 关闭账户，租金退还给 user
+        close_account(accounts.greeting, accounts.user)?;
+        bslog!("close_greeting", "Goodbye, {}!", accounts.user.key);
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::{
+    check_greeting_address, create_pda_account, parse_name, require_signer, require_writable,
+};
+use crate::{GREETING_SEED, Greeting};
+
+/// 账户：[user (signer, writable), greeting (writable), system_program]
+pub struct CreateGreetingAccounts<'a, 'info> {
+    pub user: &'a AccountInfo<'info>,
+    pub greeting: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for CreateGreetingAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let user = next_account_info(accounts_iter)?;
+        let greeting = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        require_signer(user)?;
+        require_writable(user)?;
+        require_writable(greeting)?;
+
+        Ok(Self {
+            user,
+            greeting,
+            system_program,
+        })
+    }
+}
+
+/// 创建 user 的问候 PDA，并写入长度前缀的名字
+pub struct CreateGreeting<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: CreateGreetingAccounts<'a, 'info>,
+    pub name: &'a str,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])>
+    for CreateGreeting<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            program_id,
+            accounts: CreateGreetingAccounts::try_from(accounts)?,
+            name: parse_name(data)?,
+        })
+    }
+}
+
+impl<'a> CreateGreeting<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &0;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let bump = check_greeting_address(self.program_id, accounts.user, accounts.greeting)?;
+
+        // 1. 由 user 付租金，按名字长度分配空间，owner 设为本程序
+        create_pda_account(
+            self.program_id,
+            accounts.user,
+            accounts.greeting,
+            accounts.system_program,
+            Greeting::len(self.name),
+            &[GREETING_SEED, accounts.user.key.as_ref(), &[bump]],
+        )?;
+
+        // 2. 写入名字
+        Greeting::write(accounts.greeting, self.name)?;
+        bslog!("create_greeting", "Hello, {}!", self.name);
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::{check_tip_jar_address, create_pda_account, require_signer, require_writable};
+use crate::{TIP_JAR_SEED, TipJar};
+
+/// 账户：[creator (signer, writable), tip_jar (writable), system_program]
+pub struct CreateTipJarAccounts<'a, 'info> {
+    pub creator: &'a AccountInfo<'info>,
+    pub tip_jar: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for CreateTipJarAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let creator = next_account_info(accounts_iter)?;
+        let tip_jar = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        require_signer(creator)?;
+        require_writable(creator)?;
+        require_writable(tip_jar)?;
+
+        Ok(Self {
+            creator,
+            tip_jar,
+            system_program,
+        })
+    }
+}
+
+/// 为 creator 创建打赏罐 PDA，无指令数据
+pub struct CreateTipJar<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: CreateTipJarAccounts<'a, 'info>,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [AccountInfo<'info>])> for CreateTipJar<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, accounts): (&'a Pubkey, &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            program_id,
+            accounts: CreateTipJarAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CreateTipJar<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &5;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let bump = check_tip_jar_address(self.program_id, accounts.creator.key, accounts.tip_jar)?;
+
+        create_pda_account(
+            self.program_id,
+            accounts.creator,
+            accounts.tip_jar,
+            accounts.system_program,
+            TipJar::LEN,
+            &[TIP_JAR_SEED, accounts.creator.key.as_ref(), &[bump]],
+        )?;
+        TipJar::init(accounts.tip_jar, accounts.creator.key)
+    }
+}
@@ -0,0 +1,76 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::{
+    check_note_address, close_account, parse_u64, require_owner, require_signer, require_writable,
+};
+
+/// 账户：[author (signer, writable), note (writable)]
+pub struct DeleteNoteAccounts<'a, 'info> {
+    pub author: &'a AccountInfo<'info>,
+    pub note: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for DeleteNoteAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let author = next_account_info(accounts_iter)?;
+        let note = next_account_info(accounts_iter)?;
+
+        require_signer(author)?;
+        require_writable(author)?;
+        require_writable(note)?;
+
+        Ok(Self { author, note })
+    }
+}
+
+/// 删除留言，租金退还给作者。数据：id u64 (LE)
+pub struct DeleteNote<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: DeleteNoteAccounts<'a, 'info>,
+    pub id: u64,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])>
+    for DeleteNote<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            program_id,
+            accounts: DeleteNoteAccounts::try_from(accounts)?,
+            id: parse_u64(data)?,
+        })
+    }
+}
+
+impl<'a> DeleteNote<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &15;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        // PDA 由作者地址派生，签名的作者只能删除自己的留言
+        check_note_address(self.program_id, accounts.author.key, self.id, accounts.note)?;
+        require_owner(accounts.note, self.program_id)?;
+
+        close_account(accounts.note, accounts.author)?;
+        bslog!(
+            "delete_note",
+            "author={} id={}",
+            accounts.author.key,
+            self.id
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use spl_token_interface::instruction::mint_to;
+
+use super::{create_pda_account, parse_u64, require_owner, require_signer, require_writable};
+use crate::{CLAIM_SEED, Claim, FAUCET_SEED, Faucet, SECONDS_PER_DAY, SsError};
+
+/// 账户：[user (signer, writable), claim (writable), faucet, mint (writable),
+///       user_ata (writable), token_program, system_program]
+pub struct DripAccounts<'a, 'info> {
+    pub user: &'a AccountInfo<'info>,
+    pub claim: &'a AccountInfo<'info>,
+    pub faucet: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub user_ata: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for DripAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let user = next_account_info(accounts_iter)?;
+        let claim = next_account_info(accounts_iter)?;
+        let faucet = next_account_info(accounts_iter)?;
+        let mint = next_account_info(accounts_iter)?;
+        let user_ata = next_account_info(accounts_iter)?;
+        let token_program = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        require_signer(user)?;
+        require_writable(user)?;
+        require_writable(claim)?;
+        require_writable(mint)?;
+        require_writable(user_ata)?;
+        if *token_program.key != spl_token_interface::ID {
+            return Err(SsError::InvalidProgram.into());
+        }
+
+        Ok(Self {
+            user,
+            claim,
+            faucet,
+            mint,
+            user_ata,
+            token_program,
+            system_program,
+        })
+    }
+}
+
+/// 从水龙头领取测试代币，每人每天累计不超过水龙头的上限。数据：数量 u64 (LE)
+pub struct Drip<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: DripAccounts<'a, 'info>,
+    pub amount: u64,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])> for Drip<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        let amount = parse_u64(data)?;
+        if amount == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Self {
+            program_id,
+            accounts: DripAccounts::try_from(accounts)?,
+            amount,
+        })
+    }
+}
+
+impl<'a> Drip<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &18;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+
+        // 1. faucet 必须是该 mint 的水龙头
+        require_owner(accounts.faucet, self.program_id)?;
+        if Faucet::find_address(self.program_id, accounts.mint.key).0 != *accounts.faucet.key {
+            return Err(SsError::InvalidAddress.into());
+        }
+        let (cap, faucet_bump) = {
+            let data = accounts.faucet.try_borrow_data()?;
+            (Faucet::cap(&data), Faucet::bump(&data))
+        };
+
+        // 2. 首次领取时创建 user 的领取记录
+        let (claim_address, claim_bump) =
+            Claim::find_address(self.program_id, accounts.faucet.key, accounts.user.key);
+        if claim_address != *accounts.claim.key {
+            return Err(SsError::InvalidAddress.into());
+        }
+        if accounts.claim.owner != self.program_id {
+            create_pda_account(
+                self.program_id,
+                accounts.user,
+                accounts.claim,
+                accounts.system_program,
+                Claim::LEN,
+                &[
+                    CLAIM_SEED,
+                    accounts.faucet.key.as_ref(),
+                    accounts.user.key.as_ref(),
+                    &[claim_bump],
+                ],
+            )?;
+        }
+
+        // 3. 按自然日（UTC）累计，超过上限直接失败
+        let today = Clock::get()?.unix_timestamp / SECONDS_PER_DAY;
+        let claimed = Claim::record(accounts.claim, today, self.amount, cap)?;
+
+        // 4. 水龙头 PDA 作为 mint authority 签名铸币
+        invoke_signed(
+            &mint_to(
+                accounts.token_program.key,
+                accounts.mint.key,
+                accounts.user_ata.key,
+                accounts.faucet.key,
+                &[],
+                self.amount,
+            )?,
+            &[
+                accounts.mint.clone(),
+                accounts.user_ata.clone(),
+                accounts.faucet.clone(),
+            ],
+            &[&[FAUCET_SEED, accounts.mint.key.as_ref(), &[faucet_bump]]],
+        )?;
+
+        bslog!(
+            "drip",
+            "user={} amount={} today={}/{}",
+            accounts.user.key,
+            self.amount,
+            claimed,
+            cap
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    log::sol_log_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::{check_inbox_address, require_owner};
+
+/// 账户：[owner, inbox]，均只读
+pub struct EchoPayloadAccounts<'a, 'info> {
+    pub owner: &'a AccountInfo<'info>,
+    pub inbox: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for EchoPayloadAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let inbox = next_account_info(accounts_iter)?;
+
+        Ok(Self { owner, inbox })
+    }
+}
+
+/// 把收件箱中保存的数据以 `Program data: <base64>` 的形式打印到日志，无指令数据
+pub struct EchoPayload<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: EchoPayloadAccounts<'a, 'info>,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [AccountInfo<'info>])> for EchoPayload<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, accounts): (&'a Pubkey, &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            program_id,
+            accounts: EchoPayloadAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> EchoPayload<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &9;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        check_inbox_address(self.program_id, accounts.owner.key, accounts.inbox)?;
+        require_owner(accounts.inbox, self.program_id)?;
+
+        sol_log_data(&[&accounts.inbox.try_borrow_data()?]);
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use super::{
+    check_note_address, parse_text, require_owner, require_signer, require_writable,
+    resize_with_rent, split_u64,
+};
+use crate::{MAX_NOTE_LEN, Note, SsError};
+
+/// 账户：[author (signer, writable), note (writable), system_program]
+pub struct EditNoteAccounts<'a, 'info> {
+    pub author: &'a AccountInfo<'info>,
+    pub note: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for EditNoteAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let author = next_account_info(accounts_iter)?;
+        let note = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // 只有作者本人签名才能修改
+        require_signer(author)?;
+        require_writable(author)?;
+        require_writable(note)?;
+
+        Ok(Self {
+            author,
+            note,
+            system_program,
+        })
+    }
+}
+
+/// 修改留言正文，正文长度变化时调整账户大小。数据：id u64 (LE) + u32 (LE) 正文长度 + 正文
+pub struct EditNote<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: EditNoteAccounts<'a, 'info>,
+    pub id: u64,
+    pub text: &'a str,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])> for EditNote<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        let (id, text) = split_u64(data)?;
+        Ok(Self {
+            program_id,
+            accounts: EditNoteAccounts::try_from(accounts)?,
+            id,
+            text: parse_text(text, MAX_NOTE_LEN)?,
+        })
+    }
+}
+
+impl<'a> EditNote<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &14;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+
+        // 1. note 必须是 author 的 PDA、由本程序持有，且记录的作者一致
+        check_note_address(self.program_id, accounts.author.key, self.id, accounts.note)?;
+        require_owner(accounts.note, self.program_id)?;
+        if Note::author(&accounts.note.try_borrow_data()?) != accounts.author.key.as_ref() {
+            return Err(SsError::InvalidOwner.into());
+        }
+
+        // 2. 按新正文长度调整空间，租金多退少补
+        resize_with_rent(
+            accounts.note,
+            accounts.author,
+            accounts.system_program,
+            Note::len(self.text),
+        )?;
+
+        // 3. 写入正文与修改时间
+        Note::write_text(accounts.note, self.text, Clock::get()?.unix_timestamp)?;
+        bslog!("edit_note", "author={} id={}", accounts.author.key, self.id);
+        Ok(())
+    }
+}
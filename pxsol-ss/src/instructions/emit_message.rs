@@ -0,0 +1,58 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+};
+
+use super::{parse_text, require_signer};
+use crate::{Event, MAX_NOTE_LEN};
+
+/// 账户：[sender (signer)]
+pub struct EmitMessageAccounts<'a, 'info> {
+    pub sender: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for EmitMessageAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let sender = next_account_info(accounts_iter)?;
+
+        require_signer(sender)?;
+
+        Ok(Self { sender })
+    }
+}
+
+/// 发出一条 `Event::Message` 结构化事件，不写任何账户。
+/// 数据：u32 (LE) 正文长度 + 正文
+pub struct EmitMessage<'a, 'info> {
+    pub accounts: EmitMessageAccounts<'a, 'info>,
+    pub text: &'a str,
+}
+
+impl<'a, 'info> TryFrom<(&'a [u8], &'a [AccountInfo<'info>])> for EmitMessage<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: EmitMessageAccounts::try_from(accounts)?,
+            text: parse_text(data, MAX_NOTE_LEN)?,
+        })
+    }
+}
+
+impl<'a> EmitMessage<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &22;
+
+    pub fn process(&self) -> ProgramResult {
+        Event::Message {
+            sender: *self.accounts.sender.key,
+            text: self.text.into(),
+        }
+        .emit()
+    }
+}
@@ -0,0 +1,125 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use solana_system_interface::{instruction as system_instruction, program as system_program};
+
+use super::{parse_u64, require_signer, require_writable};
+use crate::{FORWARD_SEED, SsError};
+
+/// 账户：[from (writable), recipient (writable), system_program, authority (signer, 可选)]
+///
+/// - 不带 authority：from 必须签名，程序用 `invoke` 转发，签名直接沿用交易里的签名；
+/// - 带 authority：from 是 authority 的 PDA（[FORWARD_SEED, authority]），没有私钥，
+///   程序用 `invoke_signed` 提供种子代替签名。
+pub struct ForwardAccounts<'a, 'info> {
+    pub from: &'a AccountInfo<'info>,
+    pub recipient: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub authority: Option<&'a AccountInfo<'info>>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for ForwardAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let from = next_account_info(accounts_iter)?;
+        let recipient = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let authority = accounts_iter.next();
+
+        // 1. 资金两端都必须可写，否则 system program 会在 CPI 中报错，这里提前给出明确的错误
+        require_writable(from)?;
+        require_writable(recipient)?;
+        if from.key == recipient.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        // 2. 只允许 CPI 到真正的 system program
+        if *system_program.key != system_program::ID {
+            return Err(SsError::InvalidProgram.into());
+        }
+        // 3. 签名：要么 from 本身签名，要么 PDA 的 authority 签名
+        require_signer(authority.unwrap_or(from))?;
+
+        Ok(Self {
+            from,
+            recipient,
+            system_program,
+            authority,
+        })
+    }
+}
+
+/// 通过 system program CPI 把 lamports 从 from 转给 recipient。数据：u64 (LE) 数量
+pub struct Forward<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: ForwardAccounts<'a, 'info>,
+    pub amount: u64,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])> for Forward<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        let amount = parse_u64(data)?;
+        if amount == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Self {
+            program_id,
+            accounts: ForwardAccounts::try_from(accounts)?,
+            amount,
+        })
+    }
+}
+
+impl<'a> Forward<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &10;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let ix =
+            system_instruction::transfer(accounts.from.key, accounts.recipient.key, self.amount);
+        let infos = [
+            accounts.from.clone(),
+            accounts.recipient.clone(),
+            accounts.system_program.clone(),
+        ];
+
+        match accounts.authority {
+            // from 已在交易中签名，签名权限随 CPI 传递
+            None => invoke(&ix, &infos)?,
+            // from 是 PDA：校验地址后由程序用种子签名
+            Some(authority) => {
+                let (address, bump) = Pubkey::find_program_address(
+                    &[FORWARD_SEED, authority.key.as_ref()],
+                    self.program_id,
+                );
+                if address != *accounts.from.key {
+                    return Err(SsError::InvalidAddress.into());
+                }
+                invoke_signed(
+                    &ix,
+                    &infos,
+                    &[&[FORWARD_SEED, authority.key.as_ref(), &[bump]]],
+                )?;
+            }
+        }
+
+        bslog!(
+            "forward",
+            "from={} to={} amount={}",
+            accounts.from.key,
+            accounts.recipient.key,
+            self.amount
+        );
+        Ok(())
+    }
+}
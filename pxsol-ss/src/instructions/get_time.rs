@@ -0,0 +1,46 @@
+use solana_program::{
+    clock::Clock, entrypoint::ProgramResult, program::set_return_data, program_error::ProgramError,
+    rent::Rent, sysvar::Sysvar,
+};
+
+use solana_system_interface::MAX_PERMITTED_DATA_LENGTH;
+
+use super::parse_u64;
+
+/// 读取 Clock 与 Rent sysvar，把 (slot, unix_timestamp, minimum_balance(len)) 写入 return data。
+/// 不需要任何账户（sysvar 通过 syscall 读取），客户端模拟交易即可拿到结果。
+///
+/// 数据：u64 (LE) 账户长度 len，不超过账户数据上限；
+/// return data：slot u64 (LE) + unix_timestamp i64 (LE) + 租金豁免下限 u64 (LE)，共 24 字节
+pub struct GetTime {
+    pub len: u64,
+}
+
+impl TryFrom<&[u8]> for GetTime {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let len = parse_u64(data)?;
+        if len > MAX_PERMITTED_DATA_LENGTH {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Self { len })
+    }
+}
+
+impl GetTime {
+    pub const DISCRIMINATOR: &'static u8 = &12;
+    pub const RETURN_LEN: usize = 24;
+
+    pub fn process(&self) -> ProgramResult {
+        let clock = Clock::get()?;
+        let min_balance = Rent::get()?.minimum_balance(self.len as usize);
+
+        let mut out = [0u8; Self::RETURN_LEN];
+        out[..8].copy_from_slice(&clock.slot.to_le_bytes());
+        out[8..16].copy_from_slice(&clock.unix_timestamp.to_le_bytes());
+        out[16..].copy_from_slice(&min_balance.to_le_bytes());
+        set_return_data(&out);
+        Ok(())
+    }
+}
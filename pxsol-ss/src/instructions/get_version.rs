@@ -0,0 +1,16 @@
+use blueshift_version::Version;
+use solana_program::{entrypoint::ProgramResult, program::set_return_data};
+
+/// 所有训练营程序共用的 get_version：8 字节判别器，return data 为版本号与功能位
+/// （格式见 crates/blueshift_version）。本程序没有功能开关账户，不读取任何账户，enabled 恒为 0。
+pub struct GetVersion;
+
+impl GetVersion {
+    pub const DISCRIMINATOR: &'static [u8; 8] = &blueshift_version::GET_VERSION_DISCRIMINATOR;
+    pub const VERSION: Version = blueshift_version::version!(0);
+
+    pub fn process(&self) -> ProgramResult {
+        set_return_data(&Self::VERSION.encode());
+        Ok(())
+    }
+}
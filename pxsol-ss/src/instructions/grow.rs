@@ -0,0 +1,113 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::{MAX_PERMITTED_DATA_INCREASE, ProgramResult},
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use solana_system_interface::instruction as system_instruction;
+
+use super::{check_inbox_address, parse_u64, require_owner, require_signer, require_writable};
+use crate::MAX_PAYLOAD_LEN;
+
+/// 账户：[payer (signer, writable), inbox (writable), system_program]
+///
+/// 只允许扩容 payer 自己的收件箱：收件箱是原始字节缓冲区，尾部补零不会破坏布局；
+/// 其它账户（如 KV 条目、留言）的长度本身就是数据的一部分。
+pub struct GrowAccounts<'a, 'info> {
+    pub payer: &'a AccountInfo<'info>,
+    pub inbox: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for GrowAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let payer = next_account_info(accounts_iter)?;
+        let inbox = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        require_signer(payer)?;
+        require_writable(payer)?;
+        require_writable(inbox)?;
+
+        Ok(Self {
+            payer,
+            inbox,
+            system_program,
+        })
+    }
+}
+
+/// 用 realloc 把程序持有的账户扩容到 new_len 字节，租金差额由 payer 补足，新增区域清零。
+/// 数据：new_len u64 (LE)
+pub struct Grow<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: GrowAccounts<'a, 'info>,
+    pub new_len: usize,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])> for Grow<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        let new_len = parse_u64(data)?;
+        if new_len > MAX_PAYLOAD_LEN as u64 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Self {
+            program_id,
+            accounts: GrowAccounts::try_from(accounts)?,
+            new_len: new_len as usize,
+        })
+    }
+}
+
+impl<'a> Grow<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &16;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        check_inbox_address(self.program_id, accounts.payer.key, accounts.inbox)?;
+        // 只有 owner 程序才能修改账户大小
+        require_owner(accounts.inbox, self.program_id)?;
+
+        // 1. 只能变大，单条指令的增量受运行时限制
+        let old_len = accounts.inbox.data_len();
+        if self.new_len < old_len || self.new_len - old_len > MAX_PERMITTED_DATA_INCREASE {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 2. 先补足新长度所需的租金（system program 转账要求 payer 签名）
+        let required = Rent::get()?.minimum_balance(self.new_len);
+        let current = accounts.inbox.lamports();
+        if required > current {
+            invoke(
+                &system_instruction::transfer(
+                    accounts.payer.key,
+                    accounts.inbox.key,
+                    required - current,
+                ),
+                &[
+                    accounts.payer.clone(),
+                    accounts.inbox.clone(),
+                    accounts.system_program.clone(),
+                ],
+            )?;
+        }
+
+        // 3. realloc；同一交易里先缩后扩时新增区域可能残留旧数据，因此显式清零
+        accounts.inbox.resize(self.new_len)?;
+        accounts.inbox.try_borrow_mut_data()?[old_len..].fill(0);
+
+        bslog!("grow", "{} -> {} bytes", old_len, self.new_len);
+        Ok(())
+    }
+}
@@ -0,0 +1,216 @@
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use solana_system_interface::instruction as system_instruction;
+
+use crate::{Greeting, Inbox, KvEntry, MAX_KEY_LEN, MAX_NAME_LEN, Note, SsError, TipJar};
+
+/// 拆出一段长度前缀的字节：u32 (LE) 长度 + 字节，返回 (字节, 剩余数据)。
+/// 长度必须在 1..=max 之内。
+pub fn split_len_prefixed(data: &[u8], max: usize) -> Result<(&[u8], &[u8]), ProgramError> {
+    let (len, rest) = data
+        .split_first_chunk::<4>()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let len = u32::from_le_bytes(*len) as usize;
+    if len == 0 || len > max || len > rest.len() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok(rest.split_at(len))
+}
+
+/// 账户必须签名
+pub fn require_signer(account: &AccountInfo) -> ProgramResult {
+    if !account.is_signer {
+        return Err(SsError::NotSigner.into());
+    }
+    Ok(())
+}
+
+/// 账户必须以可写方式传入
+pub fn require_writable(account: &AccountInfo) -> ProgramResult {
+    if !account.is_writable {
+        return Err(SsError::NotWritable.into());
+    }
+    Ok(())
+}
+
+/// 账户必须由 `owner` 程序持有
+pub fn require_owner(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    if account.owner != owner {
+        return Err(SsError::InvalidOwner.into());
+    }
+    Ok(())
+}
+
+/// 解析长度前缀的 UTF-8 文本，长度必须与剩余数据一致
+pub fn parse_text(data: &[u8], max: usize) -> Result<&str, ProgramError> {
+    let (text, rest) = split_len_prefixed(data, max)?;
+    if !rest.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    core::str::from_utf8(text).map_err(|_| ProgramError::InvalidInstructionData)
+}
+
+/// 解析长度前缀的 UTF-8 名字
+pub fn parse_name(data: &[u8]) -> Result<&str, ProgramError> {
+    parse_text(data, MAX_NAME_LEN)
+}
+
+/// 拆出开头的 u64 (LE)，返回 (值, 剩余数据)
+pub fn split_u64(data: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+    let (value, rest) = data
+        .split_first_chunk::<8>()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    Ok((u64::from_le_bytes(*value), rest))
+}
+
+/// 校验 greeting 是 user 的问候 PDA，返回 bump
+pub fn check_greeting_address(
+    program_id: &Pubkey,
+    user: &AccountInfo,
+    greeting: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (address, bump) = Greeting::find_address(program_id, user.key);
+    if address != *greeting.key {
+        return Err(SsError::InvalidAddress.into());
+    }
+    Ok(bump)
+}
+
+/// 解析长度前缀的 key，返回 (key, 剩余数据)
+pub fn parse_key(data: &[u8]) -> Result<(&[u8], &[u8]), ProgramError> {
+    split_len_prefixed(data, MAX_KEY_LEN)
+}
+
+/// 校验 entry 是 (owner, key) 对应的 KV PDA，返回 bump
+pub fn check_kv_address(
+    program_id: &Pubkey,
+    owner: &AccountInfo,
+    entry: &AccountInfo,
+    key: &[u8],
+) -> Result<u8, ProgramError> {
+    let (address, bump) = KvEntry::find_address(program_id, owner.key, key);
+    if address != *entry.key {
+        return Err(SsError::InvalidAddress.into());
+    }
+    Ok(bump)
+}
+
+/// 校验 tip_jar 是 creator 的打赏罐 PDA，返回 bump
+pub fn check_tip_jar_address(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    tip_jar: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (address, bump) = TipJar::find_address(program_id, creator);
+    if address != *tip_jar.key {
+        return Err(SsError::InvalidAddress.into());
+    }
+    Ok(bump)
+}
+
+/// 校验 inbox 是 owner 的收件箱 PDA，返回 bump
+pub fn check_inbox_address(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    inbox: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (address, bump) = Inbox::find_address(program_id, owner);
+    if address != *inbox.key {
+        return Err(SsError::InvalidAddress.into());
+    }
+    Ok(bump)
+}
+
+/// 校验 note 是 author 第 id 条留言的 PDA，返回 bump
+pub fn check_note_address(
+    program_id: &Pubkey,
+    author: &Pubkey,
+    id: u64,
+    note: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (address, bump) = Note::find_address(program_id, author, id);
+    if address != *note.key {
+        return Err(SsError::InvalidAddress.into());
+    }
+    Ok(bump)
+}
+
+/// 读取 32 字节的地址参数，长度必须精确匹配
+pub fn parse_pubkey(data: &[u8]) -> Result<Pubkey, ProgramError> {
+    let bytes: [u8; 32] = data
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    Ok(Pubkey::new_from_array(bytes))
+}
+
+/// 读取 8 字节的 u64 (LE) 参数，长度必须精确匹配
+pub fn parse_u64(data: &[u8]) -> Result<u64, ProgramError> {
+    let bytes: [u8; 8] = data
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// 由 payer 付租金创建一个本程序持有的 PDA，`seeds` 需包含 bump
+pub fn create_pda_account<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            account.key,
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            program_id,
+        ),
+        &[payer.clone(), account.clone(), system_program.clone()],
+        &[seeds],
+    )
+}
+
+/// 将程序持有的账户调整为 `new_len` 字节，租金多退少补：
+/// 不足部分由 payer 通过 system program 转入，多余部分直接退给 payer
+pub fn resize_with_rent<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    new_len: usize,
+) -> ProgramResult {
+    let required = Rent::get()?.minimum_balance(new_len);
+    let current = account.lamports();
+    if required > current {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, required - current),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    } else if current > required {
+        // account 由本程序持有，可以直接扣减 lamports
+        **account.try_borrow_mut_lamports()? = required;
+        **payer.try_borrow_mut_lamports()? += current - required;
+    }
+    account.resize(new_len)
+}
+
+/// 关闭程序持有的账户：lamports 全部退给 destination，数据清空。
+/// lamports 归零的账户会在交易结束后被运行时回收。
+pub fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.resize(0)
+}
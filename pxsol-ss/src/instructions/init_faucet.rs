@@ -0,0 +1,118 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use spl_token_interface::instruction::{AuthorityType, set_authority};
+
+use super::{create_pda_account, parse_u64, require_owner, require_signer, require_writable};
+use crate::{FAUCET_SEED, Faucet, SsError};
+
+/// 账户：[admin (signer, writable), faucet (writable), mint (writable), system_program, token_program]
+pub struct InitFaucetAccounts<'a, 'info> {
+    pub admin: &'a AccountInfo<'info>,
+    pub faucet: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for InitFaucetAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let admin = next_account_info(accounts_iter)?;
+        let faucet = next_account_info(accounts_iter)?;
+        let mint = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let token_program = next_account_info(accounts_iter)?;
+
+        require_signer(admin)?;
+        require_writable(admin)?;
+        require_writable(faucet)?;
+        require_writable(mint)?;
+        if *token_program.key != spl_token_interface::ID {
+            return Err(SsError::InvalidProgram.into());
+        }
+        require_owner(mint, token_program.key)?;
+
+        Ok(Self {
+            admin,
+            faucet,
+            mint,
+            system_program,
+            token_program,
+        })
+    }
+}
+
+/// 创建水龙头，并把 mint 的 mint authority 从 admin 移交给水龙头 PDA。
+/// 数据：每人每日上限 u64 (LE)
+pub struct InitFaucet<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: InitFaucetAccounts<'a, 'info>,
+    pub cap: u64,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])>
+    for InitFaucet<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        let cap = parse_u64(data)?;
+        if cap == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Self {
+            program_id,
+            accounts: InitFaucetAccounts::try_from(accounts)?,
+            cap,
+        })
+    }
+}
+
+impl<'a> InitFaucet<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &17;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let (address, bump) = Faucet::find_address(self.program_id, accounts.mint.key);
+        if address != *accounts.faucet.key {
+            return Err(SsError::InvalidAddress.into());
+        }
+
+        // 1. 创建水龙头 PDA
+        create_pda_account(
+            self.program_id,
+            accounts.admin,
+            accounts.faucet,
+            accounts.system_program,
+            Faucet::LEN,
+            &[FAUCET_SEED, accounts.mint.key.as_ref(), &[bump]],
+        )?;
+        Faucet::init(accounts.faucet, accounts.admin.key, self.cap, bump)?;
+
+        // 2. admin 签名，把 mint authority 交给水龙头 PDA；此后只有本程序能铸币
+        invoke(
+            &set_authority(
+                accounts.token_program.key,
+                accounts.mint.key,
+                Some(accounts.faucet.key),
+                AuthorityType::MintTokens,
+                accounts.admin.key,
+                &[],
+            )?,
+            &[accounts.mint.clone(), accounts.admin.clone()],
+        )?;
+
+        bslog!("init_faucet", "mint={} cap={}", accounts.mint.key, self.cap);
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::{
+    check_kv_address, close_account, parse_key, require_owner, require_signer, require_writable,
+};
+
+/// 账户：[owner (signer, writable), entry (writable)]
+pub struct KvDeleteAccounts<'a, 'info> {
+    pub owner: &'a AccountInfo<'info>,
+    pub entry: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for KvDeleteAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let entry = next_account_info(accounts_iter)?;
+
+        require_signer(owner)?;
+        require_writable(owner)?;
+        require_writable(entry)?;
+
+        Ok(Self { owner, entry })
+    }
+}
+
+/// 删除 key 对应的条目，租金退还给 owner。
+/// 数据：u32 (LE) key 长度 + key
+pub struct KvDelete<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: KvDeleteAccounts<'a, 'info>,
+    pub key: &'a [u8],
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])> for KvDelete<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        let (key, rest) = parse_key(data)?;
+        if !rest.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            program_id,
+            accounts: KvDeleteAccounts::try_from(accounts)?,
+            key,
+        })
+    }
+}
+
+impl<'a> KvDelete<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &4;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        // PDA 由 owner 的地址派生，签名的 owner 只能删除自己的条目
+        check_kv_address(self.program_id, accounts.owner, accounts.entry, self.key)?;
+        require_owner(accounts.entry, self.program_id)?;
+
+        close_account(accounts.entry, accounts.owner)
+    }
+}
@@ -0,0 +1,68 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::{check_kv_address, parse_key, require_owner};
+use crate::KvEntry;
+
+/// 账户：[owner, entry]，均只读，owner 不需要签名
+pub struct KvGetAccounts<'a, 'info> {
+    pub owner: &'a AccountInfo<'info>,
+    pub entry: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for KvGetAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let entry = next_account_info(accounts_iter)?;
+
+        Ok(Self { owner, entry })
+    }
+}
+
+/// 通过 return data 返回 key 对应的值，客户端可直接模拟交易读取。
+/// 数据：u32 (LE) key 长度 + key
+pub struct KvGet<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: KvGetAccounts<'a, 'info>,
+    pub key: &'a [u8],
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])> for KvGet<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        let (key, rest) = parse_key(data)?;
+        if !rest.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            program_id,
+            accounts: KvGetAccounts::try_from(accounts)?,
+            key,
+        })
+    }
+}
+
+impl<'a> KvGet<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &3;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        check_kv_address(self.program_id, accounts.owner, accounts.entry, self.key)?;
+        require_owner(accounts.entry, self.program_id)?;
+
+        let data = accounts.entry.try_borrow_data()?;
+        set_return_data(&data[KvEntry::OWNER_LEN..]);
+        Ok(())
+    }
+}
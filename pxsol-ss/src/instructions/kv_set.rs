@@ -0,0 +1,107 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    hash::hash,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::{
+    check_kv_address, create_pda_account, parse_key, require_signer, require_writable,
+    resize_with_rent,
+};
+use crate::{KV_SEED, KvEntry, MAX_VALUE_LEN};
+
+/// 账户：[owner (signer, writable), entry (writable), system_program]
+pub struct KvSetAccounts<'a, 'info> {
+    pub owner: &'a AccountInfo<'info>,
+    pub entry: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for KvSetAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let entry = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        require_signer(owner)?;
+        require_writable(owner)?;
+        require_writable(entry)?;
+
+        Ok(Self {
+            owner,
+            entry,
+            system_program,
+        })
+    }
+}
+
+/// 写入 key 对应的值：条目不存在时创建，已存在时按新值长度扩缩容。
+/// 数据：u32 (LE) key 长度 + key + value（剩余全部字节）
+pub struct KvSet<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: KvSetAccounts<'a, 'info>,
+    pub key: &'a [u8],
+    pub value: &'a [u8],
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])> for KvSet<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        let (key, value) = parse_key(data)?;
+        if value.len() > MAX_VALUE_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            program_id,
+            accounts: KvSetAccounts::try_from(accounts)?,
+            key,
+            value,
+        })
+    }
+}
+
+impl<'a> KvSet<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let bump = check_kv_address(self.program_id, accounts.owner, accounts.entry, self.key)?;
+        let space = KvEntry::len(self.value);
+
+        if accounts.entry.owner == self.program_id {
+            // 1a. 已存在：按新值长度扩缩容，租金多退少补
+            resize_with_rent(
+                accounts.entry,
+                accounts.owner,
+                accounts.system_program,
+                space,
+            )?;
+        } else {
+            // 1b. 不存在：由 owner 付租金创建
+            create_pda_account(
+                self.program_id,
+                accounts.owner,
+                accounts.entry,
+                accounts.system_program,
+                space,
+                &[
+                    KV_SEED,
+                    accounts.owner.key.as_ref(),
+                    hash(self.key).as_ref(),
+                    &[bump],
+                ],
+            )?;
+        }
+
+        // 2. 写入 owner 与值
+        KvEntry::write(accounts.entry, accounts.owner.key, self.value)
+    }
+}
@@ -0,0 +1,51 @@
+mod close_greeting;
+mod create_greeting;
+mod create_tip_jar;
+mod delete_note;
+mod drip;
+mod echo_payload;
+mod edit_note;
+mod emit_message;
+mod forward;
+mod get_time;
+mod get_version;
+mod grow;
+mod helpers;
+mod init_faucet;
+mod kv_delete;
+mod kv_get;
+mod kv_set;
+mod post_note;
+mod store_payload;
+mod tip;
+mod update_greeting;
+mod whitelist_add;
+mod whitelist_check;
+mod whitelist_remove;
+mod withdraw_tips;
+
+pub use close_greeting::*;
+pub use create_greeting::*;
+pub use create_tip_jar::*;
+pub use delete_note::*;
+pub use drip::*;
+pub use echo_payload::*;
+pub use edit_note::*;
+pub use emit_message::*;
+pub use forward::*;
+pub use get_time::*;
+pub use get_version::*;
+pub use grow::*;
+pub use helpers::*;
+pub use init_faucet::*;
+pub use kv_delete::*;
+pub use kv_get::*;
+pub use kv_set::*;
+pub use post_note::*;
+pub use store_payload::*;
+pub use tip::*;
+pub use update_greeting::*;
+pub use whitelist_add::*;
+pub use whitelist_check::*;
+pub use whitelist_remove::*;
+pub use withdraw_tips::*;
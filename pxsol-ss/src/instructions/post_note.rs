@@ -0,0 +1,101 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use super::{
+    check_note_address, create_pda_account, parse_text, require_signer, require_writable, split_u64,
+};
+use crate::{MAX_NOTE_LEN, NOTE_SEED, Note};
+
+/// 账户：[author (signer, writable), note (writable), system_program]
+pub struct PostNoteAccounts<'a, 'info> {
+    pub author: &'a AccountInfo<'info>,
+    pub note: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for PostNoteAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let author = next_account_info(accounts_iter)?;
+        let note = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        require_signer(author)?;
+        require_writable(author)?;
+        require_writable(note)?;
+
+        Ok(Self {
+            author,
+            note,
+            system_program,
+        })
+    }
+}
+
+/// 发布一条留言。数据：id u64 (LE) + u32 (LE) 正文长度 + 正文
+pub struct PostNote<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: PostNoteAccounts<'a, 'info>,
+    pub id: u64,
+    pub text: &'a str,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])> for PostNote<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        let (id, text) = split_u64(data)?;
+        Ok(Self {
+            program_id,
+            accounts: PostNoteAccounts::try_from(accounts)?,
+            id,
+            text: parse_text(text, MAX_NOTE_LEN)?,
+        })
+    }
+}
+
+impl<'a> PostNote<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &13;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let bump =
+            check_note_address(self.program_id, accounts.author.key, self.id, accounts.note)?;
+
+        // 1. 由 author 付租金创建留言 PDA
+        create_pda_account(
+            self.program_id,
+            accounts.author,
+            accounts.note,
+            accounts.system_program,
+            Note::len(self.text),
+            &[
+                NOTE_SEED,
+                accounts.author.key.as_ref(),
+                &self.id.to_le_bytes(),
+                &[bump],
+            ],
+        )?;
+
+        // 2. 写入作者、时间戳与正文
+        Note::init(
+            accounts.note,
+            accounts.author.key,
+            self.text,
+            Clock::get()?.unix_timestamp,
+        )?;
+        bslog!("post_note", "author={} id={}", accounts.author.key, self.id);
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::{
+    check_inbox_address, create_pda_account, require_signer, require_writable, resize_with_rent,
+};
+use crate::{INBOX_SEED, MAX_PAYLOAD_LEN};
+
+/// 账户：[owner (signer, writable), inbox (writable), system_program]
+pub struct StorePayloadAccounts<'a, 'info> {
+    pub owner: &'a AccountInfo<'info>,
+    pub inbox: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for StorePayloadAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let inbox = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        require_signer(owner)?;
+        require_writable(owner)?;
+        require_writable(inbox)?;
+
+        Ok(Self {
+            owner,
+            inbox,
+            system_program,
+        })
+    }
+}
+
+/// 把判别器之后的原始数据原样保存到 owner 的收件箱，覆盖上一次的内容
+pub struct StorePayload<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: StorePayloadAccounts<'a, 'info>,
+    pub payload: &'a [u8],
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])>
+    for StorePayload<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        if data.len() > MAX_PAYLOAD_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            program_id,
+            accounts: StorePayloadAccounts::try_from(accounts)?,
+            payload: data,
+        })
+    }
+}
+
+impl<'a> StorePayload<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &8;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let bump = check_inbox_address(self.program_id, accounts.owner.key, accounts.inbox)?;
+
+        // 1. 首次保存时创建，之后按本次数据长度扩缩容
+        if accounts.inbox.owner == self.program_id {
+            resize_with_rent(
+                accounts.inbox,
+                accounts.owner,
+                accounts.system_program,
+                self.payload.len(),
+            )?;
+        } else {
+            create_pda_account(
+                self.program_id,
+                accounts.owner,
+                accounts.inbox,
+                accounts.system_program,
+                self.payload.len(),
+                &[INBOX_SEED, accounts.owner.key.as_ref(), &[bump]],
+            )?;
+        }
+
+        // 2. 原样写入
+        accounts
+            .inbox
+            .try_borrow_mut_data()?
+            .copy_from_slice(self.payload);
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use solana_system_interface::instruction as system_instruction;
+
+use super::{parse_u64, require_owner, require_signer, require_writable};
+use crate::{Event, TipJar};
+
+/// 账户：[tipper (signer, writable), tip_jar (writable), system_program]
+pub struct TipAccounts<'a, 'info> {
+    pub tipper: &'a AccountInfo<'info>,
+    pub tip_jar: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for TipAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let tipper = next_account_info(accounts_iter)?;
+        let tip_jar = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        require_signer(tipper)?;
+        require_writable(tipper)?;
+        require_writable(tip_jar)?;
+
+        Ok(Self {
+            tipper,
+            tip_jar,
+            system_program,
+        })
+    }
+}
+
+/// 任何人都可以向打赏罐转入 lamports。数据：u64 (LE) 数量
+pub struct Tip<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: TipAccounts<'a, 'info>,
+    pub amount: u64,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])> for Tip<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        let amount = parse_u64(data)?;
+        if amount == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Self {
+            program_id,
+            accounts: TipAccounts::try_from(accounts)?,
+            amount,
+        })
+    }
+}
+
+impl<'a> Tip<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &6;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        // 打赏罐必须由本程序持有，否则无法记账
+        require_owner(accounts.tip_jar, self.program_id)?;
+        if accounts.tip_jar.data_len() != TipJar::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // 1. tipper 签名，通过 system program 转入
+        invoke(
+            &system_instruction::transfer(accounts.tipper.key, accounts.tip_jar.key, self.amount),
+            &[
+                accounts.tipper.clone(),
+                accounts.tip_jar.clone(),
+                accounts.system_program.clone(),
+            ],
+        )?;
+
+        // 2. 记账并发出事件
+        let (total, count) = TipJar::record_tip(accounts.tip_jar, self.amount)?;
+        Event::Tip {
+            tipper: *accounts.tipper.key,
+            tip_jar: *accounts.tip_jar.key,
+            amount: self.amount,
+            total,
+            count,
+        }
+        .emit()?;
+        Ok(())
+    }
+}
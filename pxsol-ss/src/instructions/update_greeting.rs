@@ -0,0 +1,90 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::{
+    check_greeting_address, parse_name, require_owner, require_signer, require_writable,
+    resize_with_rent,
+};
+use crate::Greeting;
+
+/// 账户：[user (signer, writable), greeting (writable), system_program]
+pub struct UpdateGreetingAccounts<'a, 'info> {
+    pub user: &'a AccountInfo<'info>,
+    pub greeting: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for UpdateGreetingAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let user = next_account_info(accounts_iter)?;
+        let greeting = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // 只有 PDA 对应的 user 本人签名才能修改
+        require_signer(user)?;
+        require_writable(user)?;
+        require_writable(greeting)?;
+
+        Ok(Self {
+            user,
+            greeting,
+            system_program,
+        })
+    }
+}
+
+/// 修改问候账户中的名字，名字长度变化时调整账户大小
+pub struct UpdateGreeting<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: UpdateGreetingAccounts<'a, 'info>,
+    pub name: &'a str,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])>
+    for UpdateGreeting<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            program_id,
+            accounts: UpdateGreetingAccounts::try_from(accounts)?,
+            name: parse_name(data)?,
+        })
+    }
+}
+
+impl<'a> UpdateGreeting<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &1;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+
+        // 1. greeting 必须是 user 的 PDA 且由本程序持有
+        check_greeting_address(self.program_id, accounts.user, accounts.greeting)?;
+        require_owner(accounts.greeting, self.program_id)?;
+
+        // 2. 按新名字长度调整空间，租金多退少补
+        resize_with_rent(
+            accounts.greeting,
+            accounts.user,
+            accounts.system_program,
+            Greeting::len(self.name),
+        )?;
+
+        // 3. 写入新名字
+        Greeting::write(accounts.greeting, self.name)?;
+        bslog!("update_greeting", "Hello again, {}!", self.name);
+        Ok(())
+    }
+}
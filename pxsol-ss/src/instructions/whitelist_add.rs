@@ -0,0 +1,113 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::{create_pda_account, parse_pubkey, require_signer, require_writable, resize_with_rent};
+use crate::{MAX_WHITELIST_LEN, SsError, WHITELIST_SEED, Whitelist};
+
+/// 账户：[admin (signer, writable), registry (writable), system_program]
+pub struct WhitelistAddAccounts<'a, 'info> {
+    pub admin: &'a AccountInfo<'info>,
+    pub registry: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for WhitelistAddAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let admin = next_account_info(accounts_iter)?;
+        let registry = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        require_signer(admin)?;
+        require_writable(admin)?;
+        require_writable(registry)?;
+
+        Ok(Self {
+            admin,
+            registry,
+            system_program,
+        })
+    }
+}
+
+/// 把地址加入 admin 的白名单，白名单不存在时先创建。数据：地址 (32)
+pub struct WhitelistAdd<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: WhitelistAddAccounts<'a, 'info>,
+    pub key: Pubkey,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])>
+    for WhitelistAdd<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            program_id,
+            accounts: WhitelistAddAccounts::try_from(accounts)?,
+            key: parse_pubkey(data)?,
+        })
+    }
+}
+
+impl<'a> WhitelistAdd<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &19;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        // PDA 由 admin 地址派生，签名的 admin 只能修改自己的白名单
+        let (address, bump) = Whitelist::find_address(self.program_id, accounts.admin.key);
+        if address != *accounts.registry.key {
+            return Err(SsError::InvalidAddress.into());
+        }
+
+        // 1. 首次使用时创建空白名单
+        if accounts.registry.owner != self.program_id {
+            create_pda_account(
+                self.program_id,
+                accounts.admin,
+                accounts.registry,
+                accounts.system_program,
+                Whitelist::len(0),
+                &[WHITELIST_SEED, accounts.admin.key.as_ref(), &[bump]],
+            )?;
+            accounts.registry.try_borrow_mut_data()?[..Whitelist::HEADER_LEN]
+                .copy_from_slice(accounts.admin.key.as_ref());
+        }
+
+        // 2. 已存在则不重复添加
+        let count = {
+            let data = accounts.registry.try_borrow_data()?;
+            if Whitelist::position(&data, &self.key).is_some() {
+                return Ok(());
+            }
+            (data.len() - Whitelist::HEADER_LEN) / 32
+        };
+        if count >= MAX_WHITELIST_LEN {
+            return Err(SsError::WhitelistFull.into());
+        }
+
+        // 3. 扩容一个槽位并追加到末尾
+        resize_with_rent(
+            accounts.registry,
+            accounts.admin,
+            accounts.system_program,
+            Whitelist::len(count + 1),
+        )?;
+        accounts.registry.try_borrow_mut_data()?[Whitelist::len(count)..]
+            .copy_from_slice(self.key.as_ref());
+
+        bslog!("whitelist_add", "{}", self.key);
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::parse_pubkey;
+use crate::Whitelist;
+
+/// 账户：[registry]，只读
+pub struct WhitelistCheckAccounts<'a, 'info> {
+    pub registry: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for WhitelistCheckAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let registry = next_account_info(accounts_iter)?;
+
+        Ok(Self { registry })
+    }
+}
+
+/// 地址不在白名单中时失败（SsError::NotWhitelisted）。
+/// 其它程序可以 CPI 这条指令作为准入检查，也可以依赖本 crate 直接调用
+/// `Whitelist::require_whitelisted` 读取白名单账户。数据：地址 (32)
+pub struct WhitelistCheck<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: WhitelistCheckAccounts<'a, 'info>,
+    pub key: Pubkey,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])>
+    for WhitelistCheck<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            program_id,
+            accounts: WhitelistCheckAccounts::try_from(accounts)?,
+            key: parse_pubkey(data)?,
+        })
+    }
+}
+
+impl<'a> WhitelistCheck<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &21;
+
+    pub fn process(&self) -> ProgramResult {
+        Whitelist::require_whitelisted(self.accounts.registry, self.program_id, &self.key)
+    }
+}
@@ -0,0 +1,95 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::{parse_pubkey, require_owner, require_signer, require_writable, resize_with_rent};
+use crate::{SsError, Whitelist};
+
+/// 账户：[admin (signer, writable), registry (writable), system_program]
+pub struct WhitelistRemoveAccounts<'a, 'info> {
+    pub admin: &'a AccountInfo<'info>,
+    pub registry: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for WhitelistRemoveAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let admin = next_account_info(accounts_iter)?;
+        let registry = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        require_signer(admin)?;
+        require_writable(admin)?;
+        require_writable(registry)?;
+
+        Ok(Self {
+            admin,
+            registry,
+            system_program,
+        })
+    }
+}
+
+/// 把地址移出 admin 的白名单，缩容并退还多余租金。数据：地址 (32)
+pub struct WhitelistRemove<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: WhitelistRemoveAccounts<'a, 'info>,
+    pub key: Pubkey,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>])>
+    for WhitelistRemove<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, data, accounts): (&'a Pubkey, &'a [u8], &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            program_id,
+            accounts: WhitelistRemoveAccounts::try_from(accounts)?,
+            key: parse_pubkey(data)?,
+        })
+    }
+}
+
+impl<'a> WhitelistRemove<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &20;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+        if Whitelist::find_address(self.program_id, accounts.admin.key).0 != *accounts.registry.key
+        {
+            return Err(SsError::InvalidAddress.into());
+        }
+        require_owner(accounts.registry, self.program_id)?;
+
+        // 1. 用最后一个地址覆盖被删除的位置（列表无序）
+        let count = {
+            let mut data = accounts.registry.try_borrow_mut_data()?;
+            let index = Whitelist::position(&data, &self.key).ok_or(SsError::NotWhitelisted)?;
+            let count = (data.len() - Whitelist::HEADER_LEN) / 32;
+            let last = Whitelist::len(count - 1);
+            data.copy_within(last.., Whitelist::len(index));
+            count
+        };
+
+        // 2. 去掉末尾槽位，多余租金退给 admin
+        resize_with_rent(
+            accounts.registry,
+            accounts.admin,
+            accounts.system_program,
+            Whitelist::len(count - 1),
+        )?;
+
+        bslog!("whitelist_remove", "{}", self.key);
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use super::{check_tip_jar_address, require_owner, require_signer, require_writable};
+use crate::TipJar;
+
+/// 账户：[creator (signer, writable), tip_jar (writable)]
+pub struct WithdrawTipsAccounts<'a, 'info> {
+    pub creator: &'a AccountInfo<'info>,
+    pub tip_jar: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for WithdrawTipsAccounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts_iter = &mut accounts.iter();
+        let creator = next_account_info(accounts_iter)?;
+        let tip_jar = next_account_info(accounts_iter)?;
+
+        require_signer(creator)?;
+        require_writable(creator)?;
+        require_writable(tip_jar)?;
+
+        Ok(Self { creator, tip_jar })
+    }
+}
+
+/// creator 取出打赏罐中超出租金豁免的全部 lamports，无指令数据
+pub struct WithdrawTips<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub accounts: WithdrawTipsAccounts<'a, 'info>,
+}
+
+impl<'a, 'info> TryFrom<(&'a Pubkey, &'a [AccountInfo<'info>])> for WithdrawTips<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (program_id, accounts): (&'a Pubkey, &'a [AccountInfo<'info>]),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            program_id,
+            accounts: WithdrawTipsAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> WithdrawTips<'a, '_> {
+    pub const DISCRIMINATOR: &'a u8 = &7;
+
+    pub fn process(&self) -> ProgramResult {
+        let accounts = &self.accounts;
+
+        // 1. 只有打赏罐的 creator 可以提取
+        check_tip_jar_address(self.program_id, accounts.creator.key, accounts.tip_jar)?;
+        require_owner(accounts.tip_jar, self.program_id)?;
+        if TipJar::creator(&accounts.tip_jar.try_borrow_data()?) != accounts.creator.key.as_ref() {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        // 2. 保留租金豁免部分，其余全部转给 creator（打赏罐由本程序持有，直接改 lamports）
+        let rent_exempt = Rent::get()?.minimum_balance(TipJar::LEN);
+        let amount = accounts.tip_jar.lamports().saturating_sub(rent_exempt);
+        if amount == 0 {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        **accounts.tip_jar.try_borrow_mut_lamports()? -= amount;
+        **accounts.creator.try_borrow_mut_lamports()? += amount;
+
+        bslog!(
+            "withdraw_tips",
+            "creator={} amount={}",
+            accounts.creator.key,
+            amount
+        );
+        Ok(())
+    }
+}
@@ -1,10 +1,129 @@
+use blueshift_log::bslog;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
 solana_program::entrypoint!(process_instruction);
 
+// 程序日志统一为 `[pxsol-ss v<版本>] <指令>: ...`；`--features quiet` 构建时全部去掉
+blueshift_log::program!("pxsol-ss");
+
+// security.txt 元数据：发布到 devnet/mainnet 时用 `--features security-txt` 构建
+#[cfg(feature = "security-txt")]
+solana_security_txt::security_txt! {
+    name: "pxsol-ss",
+    project_url: "https://github.com/o7reconversion/solana_bootcamp_2026",
+    contacts: "link:https://github.com/o7reconversion/solana_bootcamp_2026/security/advisories/new",
+    policy: "https://github.com/o7reconversion/solana_bootcamp_2026/blob/main/SECURITY.md",
+    source_code: "https://github.com/o7reconversion/solana_bootcamp_2026/tree/main/pxsol-ss"
+}
+
+// instructions 模块：每条指令一个文件，各自负责解析账户/数据并执行
+pub mod instructions;
+pub use instructions::*;
+
+// errors 模块：账户校验的自定义错误码
+pub mod errors;
+pub use errors::*;
+
+// events 模块：sol_log_data 结构化事件及其编码约定
+pub mod events;
+pub use events::*;
+
+// client 模块：链下解码工具，不编进链上程序
+#[cfg(not(target_os = "solana"))]
+pub mod client;
+
+// state 模块：程序持有的账户数据布局
+pub mod state;
+pub use state::*;
+
+#[cfg(test)]
+pub mod tests;
+
+/// 指令路由：instruction_data 的第一个字节是判别器，剩余部分交给对应指令解析。
+/// 新增指令只需要新建一个模块并在这里加一行。
 pub fn process_instruction(
-    _program_id: &solana_program::pubkey::Pubkey,
-    _accounts: &[solana_program::account_info::AccountInfo],
-    _data: &[u8],
-) -> solana_program::entrypoint::ProgramResult {
-    solana_program::msg!("Hello Solana!");
-    Ok(())
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // get_version 与其他训练营程序共用 8 字节判别器，首字节不会与下面的单字节判别器冲突
+    if data.starts_with(GetVersion::DISCRIMINATOR) {
+        return GetVersion.process();
+    }
+    match data.split_first() {
+        // 空数据：保留最初的 Hello 行为
+        None => {
+            bslog!("hello", "Hello Solana!");
+            Ok(())
+        }
+        Some((CreateGreeting::DISCRIMINATOR, data)) => {
+            CreateGreeting::try_from((program_id, data, accounts))?.process()
+        }
+        Some((UpdateGreeting::DISCRIMINATOR, data)) => {
+            UpdateGreeting::try_from((program_id, data, accounts))?.process()
+        }
+        Some((KvSet::DISCRIMINATOR, data)) => {
+            KvSet::try_from((program_id, data, accounts))?.process()
+        }
+        Some((KvGet::DISCRIMINATOR, data)) => {
+            KvGet::try_from((program_id, data, accounts))?.process()
+        }
+        Some((KvDelete::DISCRIMINATOR, data)) => {
+            KvDelete::try_from((program_id, data, accounts))?.process()
+        }
+        Some((CreateTipJar::DISCRIMINATOR, _)) => {
+            CreateTipJar::try_from((program_id, accounts))?.process()
+        }
+        Some((Tip::DISCRIMINATOR, data)) => Tip::try_from((program_id, data, accounts))?.process(),
+        Some((WithdrawTips::DISCRIMINATOR, _)) => {
+            WithdrawTips::try_from((program_id, accounts))?.process()
+        }
+        Some((StorePayload::DISCRIMINATOR, data)) => {
+            StorePayload::try_from((program_id, data, accounts))?.process()
+        }
+        Some((EchoPayload::DISCRIMINATOR, _)) => {
+            EchoPayload::try_from((program_id, accounts))?.process()
+        }
+        Some((Forward::DISCRIMINATOR, data)) => {
+            Forward::try_from((program_id, data, accounts))?.process()
+        }
+        Some((CloseGreeting::DISCRIMINATOR, _)) => {
+            CloseGreeting::try_from((program_id, accounts))?.process()
+        }
+        Some((GetTime::DISCRIMINATOR, data)) => GetTime::try_from(data)?.process(),
+        Some((PostNote::DISCRIMINATOR, data)) => {
+            PostNote::try_from((program_id, data, accounts))?.process()
+        }
+        Some((EditNote::DISCRIMINATOR, data)) => {
+            EditNote::try_from((program_id, data, accounts))?.process()
+        }
+        Some((DeleteNote::DISCRIMINATOR, data)) => {
+            DeleteNote::try_from((program_id, data, accounts))?.process()
+        }
+        Some((Grow::DISCRIMINATOR, data)) => {
+            Grow::try_from((program_id, data, accounts))?.process()
+        }
+        Some((InitFaucet::DISCRIMINATOR, data)) => {
+            InitFaucet::try_from((program_id, data, accounts))?.process()
+        }
+        Some((Drip::DISCRIMINATOR, data)) => {
+            Drip::try_from((program_id, data, accounts))?.process()
+        }
+        Some((WhitelistAdd::DISCRIMINATOR, data)) => {
+            WhitelistAdd::try_from((program_id, data, accounts))?.process()
+        }
+        Some((WhitelistRemove::DISCRIMINATOR, data)) => {
+            WhitelistRemove::try_from((program_id, data, accounts))?.process()
+        }
+        Some((WhitelistCheck::DISCRIMINATOR, data)) => {
+            WhitelistCheck::try_from((program_id, data, accounts))?.process()
+        }
+        Some((EmitMessage::DISCRIMINATOR, data)) => {
+            EmitMessage::try_from((data, accounts))?.process()
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
 }
@@ -0,0 +1,276 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, hash::hash, program::MAX_RETURN_DATA,
+    program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::SsError;
+
+/// 问候账户 PDA 的种子：[GREETING_SEED, user]
+pub const GREETING_SEED: &[u8] = b"greeting";
+/// 名字最长字节数（UTF-8 编码后）
+pub const MAX_NAME_LEN: usize = 64;
+
+/// 问候账户：u32 (LE) 长度 + 名字字节，空间随名字长度变化
+pub struct Greeting;
+
+impl Greeting {
+    pub fn len(name: &str) -> usize {
+        4 + name.len()
+    }
+
+    pub fn find_address(program_id: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[GREETING_SEED, user.as_ref()], program_id)
+    }
+
+    /// 账户空间必须已经等于 `Greeting::len(name)`
+    pub fn write(account: &AccountInfo, name: &str) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        data[..4].copy_from_slice(&(name.len() as u32).to_le_bytes());
+        data[4..].copy_from_slice(name.as_bytes());
+        Ok(())
+    }
+}
+
+/// KV 条目 PDA 的种子：[KV_SEED, owner, sha256(key)]，任意长度的 key 都能放进 32 字节种子
+pub const KV_SEED: &[u8] = b"kv";
+pub const MAX_KEY_LEN: usize = 64;
+/// get 通过 return data 返回值，值的长度受 return data 上限约束
+pub const MAX_VALUE_LEN: usize = MAX_RETURN_DATA;
+
+/// KV 条目：owner (32) + value，空间随值的长度变化
+pub struct KvEntry;
+
+impl KvEntry {
+    pub const OWNER_LEN: usize = 32;
+
+    pub fn len(value: &[u8]) -> usize {
+        Self::OWNER_LEN + value.len()
+    }
+
+    pub fn find_address(program_id: &Pubkey, owner: &Pubkey, key: &[u8]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[KV_SEED, owner.as_ref(), hash(key).as_ref()], program_id)
+    }
+
+    /// 账户空间必须已经等于 `KvEntry::len(value)`
+    pub fn write(account: &AccountInfo, owner: &Pubkey, value: &[u8]) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        data[..Self::OWNER_LEN].copy_from_slice(owner.as_ref());
+        data[Self::OWNER_LEN..].copy_from_slice(value);
+        Ok(())
+    }
+}
+
+/// 打赏罐 PDA 的种子：[TIP_JAR_SEED, creator]
+pub const TIP_JAR_SEED: &[u8] = b"tip_jar";
+
+/// 打赏罐：creator (32) + 累计打赏 u64 (LE) + 打赏次数 u64 (LE)。
+/// 打赏的 lamports 直接存在账户余额中，租金豁免部分不可提取。
+pub struct TipJar;
+
+impl TipJar {
+    pub const LEN: usize = 32 + 8 + 8;
+
+    pub fn find_address(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[TIP_JAR_SEED, creator.as_ref()], program_id)
+    }
+
+    pub fn creator(data: &[u8]) -> &[u8] {
+        &data[..32]
+    }
+
+    pub fn init(account: &AccountInfo, creator: &Pubkey) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        data[..32].copy_from_slice(creator.as_ref());
+        data[32..].fill(0);
+        Ok(())
+    }
+
+    /// 记录一次打赏，返回 (累计打赏, 打赏次数)
+    pub fn record_tip(account: &AccountInfo, amount: u64) -> Result<(u64, u64), ProgramError> {
+        let mut data = account.try_borrow_mut_data()?;
+        let total = u64::from_le_bytes(data[32..40].try_into().unwrap())
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let count = u64::from_le_bytes(data[40..48].try_into().unwrap()) + 1;
+        data[32..40].copy_from_slice(&total.to_le_bytes());
+        data[40..48].copy_from_slice(&count.to_le_bytes());
+        Ok((total, count))
+    }
+}
+
+/// 收件箱 PDA 的种子：[INBOX_SEED, owner]
+pub const INBOX_SEED: &[u8] = b"inbox";
+/// 单次保存的最大字节数
+pub const MAX_PAYLOAD_LEN: usize = 1024;
+
+/// 收件箱：直接保存最近一次的原始指令数据，空间随数据长度变化
+pub struct Inbox;
+
+impl Inbox {
+    pub fn find_address(program_id: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[INBOX_SEED, owner.as_ref()], program_id)
+    }
+}
+
+/// forward 的 PDA 资金来源：[FORWARD_SEED, authority]，由 system program 持有
+pub const FORWARD_SEED: &[u8] = b"forward";
+
+/// 留言 PDA 的种子：[NOTE_SEED, author, id (u64 LE)]，同一作者可以有多条留言
+pub const NOTE_SEED: &[u8] = b"note";
+/// 留言正文最长字节数（UTF-8 编码后）
+pub const MAX_NOTE_LEN: usize = 280;
+
+/// 留言：author (32) + created_at i64 + updated_at i64 + u32 (LE) 正文长度 + 正文
+pub struct Note;
+
+impl Note {
+    pub const HEADER_LEN: usize = 32 + 8 + 8 + 4;
+
+    pub fn len(text: &str) -> usize {
+        Self::HEADER_LEN + text.len()
+    }
+
+    pub fn find_address(program_id: &Pubkey, author: &Pubkey, id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[NOTE_SEED, author.as_ref(), &id.to_le_bytes()], program_id)
+    }
+
+    pub fn author(data: &[u8]) -> &[u8] {
+        &data[..32]
+    }
+
+    /// 创建时写入全部字段，created_at 与 updated_at 相同
+    pub fn init(account: &AccountInfo, author: &Pubkey, text: &str, now: i64) -> ProgramResult {
+        {
+            let mut data = account.try_borrow_mut_data()?;
+            data[..32].copy_from_slice(author.as_ref());
+            data[32..40].copy_from_slice(&now.to_le_bytes());
+        }
+        Self::write_text(account, text, now)
+    }
+
+    /// 更新正文与 updated_at，账户空间必须已经等于 `Note::len(text)`
+    pub fn write_text(account: &AccountInfo, text: &str, now: i64) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        data[40..48].copy_from_slice(&now.to_le_bytes());
+        data[48..52].copy_from_slice(&(text.len() as u32).to_le_bytes());
+        data[Self::HEADER_LEN..].copy_from_slice(text.as_bytes());
+        Ok(())
+    }
+}
+
+/// 水龙头 PDA 的种子：[FAUCET_SEED, mint]，同时作为 mint 的 mint authority
+pub const FAUCET_SEED: &[u8] = b"faucet";
+/// 领取记录 PDA 的种子：[CLAIM_SEED, faucet, user]
+pub const CLAIM_SEED: &[u8] = b"claim";
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+/// 水龙头：admin (32) + 每人每日上限 u64 (LE) + bump
+pub struct Faucet;
+
+impl Faucet {
+    pub const LEN: usize = 32 + 8 + 1;
+
+    pub fn find_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[FAUCET_SEED, mint.as_ref()], program_id)
+    }
+
+    pub fn init(account: &AccountInfo, admin: &Pubkey, cap: u64, bump: u8) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        data[..32].copy_from_slice(admin.as_ref());
+        data[32..40].copy_from_slice(&cap.to_le_bytes());
+        data[40] = bump;
+        Ok(())
+    }
+
+    pub fn cap(data: &[u8]) -> u64 {
+        u64::from_le_bytes(data[32..40].try_into().unwrap())
+    }
+
+    pub fn bump(data: &[u8]) -> u8 {
+        data[40]
+    }
+}
+
+/// 领取记录：最近一次领取的日期 i64 (LE，unix_timestamp / SECONDS_PER_DAY) + 当日已领取 u64 (LE)
+pub struct Claim;
+
+impl Claim {
+    pub const LEN: usize = 8 + 8;
+
+    pub fn find_address(program_id: &Pubkey, faucet: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[CLAIM_SEED, faucet.as_ref(), user.as_ref()], program_id)
+    }
+
+    /// 记录一次领取：跨天时清零重新计数，超过上限返回错误。返回当日累计领取量
+    pub fn record(
+        account: &AccountInfo,
+        today: i64,
+        amount: u64,
+        cap: u64,
+    ) -> Result<u64, ProgramError> {
+        let mut data = account.try_borrow_mut_data()?;
+        let day = i64::from_le_bytes(data[..8].try_into().unwrap());
+        let claimed = if day == today {
+            u64::from_le_bytes(data[8..16].try_into().unwrap())
+        } else {
+            0
+        };
+        let claimed = claimed
+            .checked_add(amount)
+            .filter(|claimed| *claimed <= cap)
+            .ok_or(SsError::DripLimitExceeded)?;
+        data[..8].copy_from_slice(&today.to_le_bytes());
+        data[8..16].copy_from_slice(&claimed.to_le_bytes());
+        Ok(claimed)
+    }
+}
+
+/// 白名单 PDA 的种子：[WHITELIST_SEED, admin]
+pub const WHITELIST_SEED: &[u8] = b"whitelist";
+/// 单个白名单最多容纳的地址数
+pub const MAX_WHITELIST_LEN: usize = 256;
+
+/// 白名单：admin (32) + 地址列表（每个 32 字节，无序、不重复），空间随地址数变化
+pub struct Whitelist;
+
+impl Whitelist {
+    pub const HEADER_LEN: usize = 32;
+
+    pub fn len(count: usize) -> usize {
+        Self::HEADER_LEN + count * 32
+    }
+
+    pub fn find_address(program_id: &Pubkey, admin: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[WHITELIST_SEED, admin.as_ref()], program_id)
+    }
+
+    pub fn admin(data: &[u8]) -> &[u8] {
+        &data[..Self::HEADER_LEN]
+    }
+
+    pub fn entries(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+        data[Self::HEADER_LEN..].chunks_exact(32)
+    }
+
+    /// 地址在白名单中的位置
+    pub fn position(data: &[u8], key: &Pubkey) -> Option<usize> {
+        Self::entries(data).position(|entry| entry == key.as_ref())
+    }
+
+    /// 供其它程序直接读取本程序的白名单账户：
+    /// `registry` 必须由 `whitelist_program_id` 持有，且 `key` 在列表中
+    pub fn require_whitelisted(
+        registry: &AccountInfo,
+        whitelist_program_id: &Pubkey,
+        key: &Pubkey,
+    ) -> ProgramResult {
+        if registry.owner != whitelist_program_id {
+            return Err(SsError::InvalidOwner.into());
+        }
+        let data = registry.try_borrow_data()?;
+        if data.len() < Self::HEADER_LEN || Self::position(&data, key).is_none() {
+            return Err(SsError::NotWhitelisted.into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+// =============================================================================
+// 测试模块入口
+// =============================================================================
+
+pub mod test;
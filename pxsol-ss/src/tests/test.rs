@@ -0,0 +1,725 @@
+// =============================================================================
+// pxsol-ss 测试 - 使用 Mollusk 测试框架
+// =============================================================================
+// 先执行 `cargo build-sbf` 生成 target/deploy/hell_sol.so，再执行 `cargo test`。
+// 每个测试用 Ledger 串起多条指令：成功执行后的账户状态写回 Ledger，供下一条指令使用。
+
+use mollusk_svm::{Mollusk, program::keyed_account_for_system_program, result::Check};
+use mollusk_svm_programs_token::token;
+use solana_account::Account;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use crate::{
+    CLAIM_SEED, CloseGreeting, CreateGreeting, CreateTipJar, DeleteNote, Drip, EditNote,
+    EmitMessage, Event, FAUCET_SEED, FORWARD_SEED, Forward, GREETING_SEED, GetTime, GetVersion,
+    Grow, INBOX_SEED, InitFaucet, KV_SEED, KvDelete, KvGet, KvSet, NOTE_SEED, PostNote, SsError,
+    StorePayload, TIP_JAR_SEED, Tip, UpdateGreeting, WHITELIST_SEED, WhitelistAdd, WhitelistCheck,
+    WhitelistRemove, WithdrawTips,
+    client::{decode_event, events_from_logs},
+};
+use solana_program::{hash::hash, program_error::ProgramError};
+
+// =============================================================================
+// 测试环境
+// =============================================================================
+
+const PROGRAM_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+const SOL: u64 = 1_000_000_000;
+
+struct Ledger {
+    mollusk: Mollusk,
+    accounts: Vec<(Pubkey, Account)>,
+    system_program: Pubkey,
+}
+
+impl Ledger {
+    fn new() -> Self {
+        // 省略 .so 扩展名，Mollusk 会自动添加
+        let mut mollusk = Mollusk::new(&PROGRAM_ID, "target/deploy/hell_sol");
+        token::add_program(&mut mollusk);
+        let system = keyed_account_for_system_program();
+        let system_program = system.0;
+        Self {
+            mollusk,
+            accounts: vec![system, token::keyed_account()],
+            system_program,
+        }
+    }
+
+    fn rent(&self, len: usize) -> u64 {
+        self.mollusk.sysvars.rent.minimum_balance(len)
+    }
+
+    /// 新建一个持有 10 SOL 的钱包
+    fn wallet(&mut self) -> Pubkey {
+        let key = Pubkey::new_unique();
+        self.set(key, Account::new(10 * SOL, 0, &self.system_program));
+        key
+    }
+
+    fn set(&mut self, key: Pubkey, account: Account) {
+        match self.accounts.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => *existing = account,
+            None => self.accounts.push((key, account)),
+        }
+    }
+
+    /// 不存在的地址视为空的系统账户
+    fn get(&self, key: &Pubkey) -> Account {
+        self.accounts
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, account)| account.clone())
+            .unwrap_or_default()
+    }
+
+    fn execute(
+        &mut self,
+        ix: &Instruction,
+        checks: &[Check],
+    ) -> mollusk_svm::result::InstructionResult {
+        let accounts: Vec<(Pubkey, Account)> = ix
+            .accounts
+            .iter()
+            .map(|meta| (meta.pubkey, self.get(&meta.pubkey)))
+            .collect();
+        self.mollusk
+            .process_and_validate_instruction(ix, &accounts, checks)
+    }
+
+    /// 执行并要求成功，结果写回 Ledger
+    fn ok(&mut self, ix: Instruction) -> mollusk_svm::result::InstructionResult {
+        let result = self.execute(&ix, &[Check::success()]);
+        for (key, account) in &result.resulting_accounts {
+            self.set(*key, account.clone());
+        }
+        result
+    }
+
+    /// 执行并要求返回指定错误，Ledger 不变
+    fn fails(&mut self, ix: Instruction, err: ProgramError) {
+        self.execute(&ix, &[Check::err(err)]);
+    }
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &PROGRAM_ID).0
+}
+
+fn ix(discriminator: &u8, data: &[u8], accounts: Vec<AccountMeta>) -> Instruction {
+    let mut bytes = vec![*discriminator];
+    bytes.extend_from_slice(data);
+    Instruction::new_with_bytes(PROGRAM_ID, &bytes, accounts)
+}
+
+/// u32 (LE) 长度前缀 + 字节
+fn prefixed(bytes: &[u8]) -> Vec<u8> {
+    let mut data = (bytes.len() as u32).to_le_bytes().to_vec();
+    data.extend_from_slice(bytes);
+    data
+}
+
+fn custom(error: SsError) -> ProgramError {
+    error.into()
+}
+
+// =============================================================================
+// Hello / 问候账户
+// =============================================================================
+
+#[test]
+fn test_hello_with_empty_data() {
+    let mut ledger = Ledger::new();
+    ledger.ok(Instruction::new_with_bytes(PROGRAM_ID, &[], vec![]));
+}
+
+#[test]
+fn test_get_version_returns_package_version() {
+    let mut ledger = Ledger::new();
+    let result = ledger.ok(Instruction::new_with_bytes(
+        PROGRAM_ID,
+        GetVersion::DISCRIMINATOR,
+        vec![],
+    ));
+    let version = blueshift_version::Version::decode(&result.return_data).unwrap();
+    assert_eq!(version, GetVersion::VERSION);
+    assert_eq!(
+        version.to_string(),
+        format!("{}（支持 0x0，已启用 0x0）", env!("CARGO_PKG_VERSION"))
+    );
+}
+
+#[test]
+fn test_greeting_create_update_close() {
+    let mut ledger = Ledger::new();
+    let user = ledger.wallet();
+    let greeting = pda(&[GREETING_SEED, user.as_ref()]);
+    let system = ledger.system_program;
+    let accounts = |signer: bool| {
+        vec![
+            AccountMeta::new(user, signer),
+            AccountMeta::new(greeting, false),
+            AccountMeta::new_readonly(system, false),
+        ]
+    };
+
+    // 1. 创建：账户数据就是长度前缀的名字
+    ledger.ok(ix(
+        CreateGreeting::DISCRIMINATOR,
+        &prefixed(b"alice"),
+        accounts(true),
+    ));
+    let account = ledger.get(&greeting);
+    assert_eq!(account.data, prefixed(b"alice"));
+    assert_eq!(account.owner, PROGRAM_ID);
+    assert_eq!(account.lamports, ledger.rent(9));
+
+    // 2. 未签名不能修改
+    ledger.fails(
+        ix(
+            UpdateGreeting::DISCRIMINATOR,
+            &prefixed(b"mallory"),
+            accounts(false),
+        ),
+        custom(SsError::NotSigner),
+    );
+
+    // 3. 改成更长的名字：扩容并补足租金
+    ledger.ok(ix(
+        UpdateGreeting::DISCRIMINATOR,
+        &prefixed(b"alice in chains"),
+        accounts(true),
+    ));
+    let account = ledger.get(&greeting);
+    assert_eq!(account.data, prefixed(b"alice in chains"));
+    assert_eq!(account.lamports, ledger.rent(4 + 15));
+
+    // 4. 名字非法（长度与数据不符）
+    let mut bad = prefixed(b"bob");
+    bad.push(0);
+    ledger.fails(
+        ix(UpdateGreeting::DISCRIMINATOR, &bad, accounts(true)),
+        ProgramError::InvalidInstructionData,
+    );
+
+    // 5. 其他人无法关闭，本人关闭后租金全部退回
+    let other = ledger.wallet();
+    ledger.fails(
+        ix(
+            CloseGreeting::DISCRIMINATOR,
+            &[],
+            vec![
+                AccountMeta::new(other, true),
+                AccountMeta::new(greeting, false),
+            ],
+        ),
+        custom(SsError::InvalidAddress),
+    );
+    let before = ledger.get(&user).lamports;
+    let rent = ledger.get(&greeting).lamports;
+    ledger.ok(ix(
+        CloseGreeting::DISCRIMINATOR,
+        &[],
+        vec![
+            AccountMeta::new(user, true),
+            AccountMeta::new(greeting, false),
+        ],
+    ));
+    assert_eq!(ledger.get(&greeting).lamports, 0);
+    assert_eq!(ledger.get(&user).lamports, before + rent);
+}
+
+// =============================================================================
+// KV 存储
+// =============================================================================
+
+#[test]
+fn test_kv_set_get_delete() {
+    let mut ledger = Ledger::new();
+    let owner = ledger.wallet();
+    let key = b"color";
+    let entry = pda(&[KV_SEED, owner.as_ref(), hash(key).as_ref()]);
+    let system = ledger.system_program;
+    let set = |value: &[u8]| {
+        let mut data = prefixed(key);
+        data.extend_from_slice(value);
+        ix(
+            KvSet::DISCRIMINATOR,
+            &data,
+            vec![
+                AccountMeta::new(owner, true),
+                AccountMeta::new(entry, false),
+                AccountMeta::new_readonly(system, false),
+            ],
+        )
+    };
+    let get = ix(
+        KvGet::DISCRIMINATOR,
+        &prefixed(key),
+        vec![
+            AccountMeta::new_readonly(owner, false),
+            AccountMeta::new_readonly(entry, false),
+        ],
+    );
+
+    // 1. 首次写入创建条目，get 通过 return data 返回
+    ledger.ok(set(b"red"));
+    assert_eq!(ledger.ok(get.clone()).return_data, b"red");
+
+    // 2. 写入更长的值：扩容
+    ledger.ok(set(b"ultramarine"));
+    assert_eq!(ledger.get(&entry).data.len(), 32 + 11);
+    assert_eq!(ledger.ok(get.clone()).return_data, b"ultramarine");
+
+    // 3. 删除：租金退回，条目不可再读
+    let before = ledger.get(&owner).lamports;
+    let rent = ledger.get(&entry).lamports;
+    ledger.ok(ix(
+        KvDelete::DISCRIMINATOR,
+        &prefixed(key),
+        vec![
+            AccountMeta::new(owner, true),
+            AccountMeta::new(entry, false),
+        ],
+    ));
+    assert_eq!(ledger.get(&owner).lamports, before + rent);
+    assert_eq!(ledger.get(&entry).lamports, 0);
+}
+
+// =============================================================================
+// 打赏罐
+// =============================================================================
+
+#[test]
+fn test_tip_jar_tip_and_withdraw() {
+    let mut ledger = Ledger::new();
+    let creator = ledger.wallet();
+    let tipper = ledger.wallet();
+    let tip_jar = pda(&[TIP_JAR_SEED, creator.as_ref()]);
+    let system = ledger.system_program;
+
+    ledger.ok(ix(
+        CreateTipJar::DISCRIMINATOR,
+        &[],
+        vec![
+            AccountMeta::new(creator, true),
+            AccountMeta::new(tip_jar, false),
+            AccountMeta::new_readonly(system, false),
+        ],
+    ));
+
+    // 1. 任何人都可以打赏，累计值与次数写入打赏罐
+    for amount in [SOL, 2 * SOL] {
+        ledger.ok(ix(
+            Tip::DISCRIMINATOR,
+            &amount.to_le_bytes(),
+            vec![
+                AccountMeta::new(tipper, true),
+                AccountMeta::new(tip_jar, false),
+                AccountMeta::new_readonly(system, false),
+            ],
+        ));
+    }
+    let rent = ledger.rent(48);
+    let account = ledger.get(&tip_jar);
+    assert_eq!(account.lamports, rent + 3 * SOL);
+    assert_eq!(&account.data[..32], creator.as_ref());
+    assert_eq!(account.data[32..40], (3 * SOL).to_le_bytes());
+    assert_eq!(account.data[40..48], 2u64.to_le_bytes());
+
+    // 2. 打赏者不能提取（打赏罐地址由 creator 派生）
+    ledger.fails(
+        ix(
+            WithdrawTips::DISCRIMINATOR,
+            &[],
+            vec![
+                AccountMeta::new(tipper, true),
+                AccountMeta::new(tip_jar, false),
+            ],
+        ),
+        custom(SsError::InvalidAddress),
+    );
+
+    // 3. creator 提取全部打赏，保留租金
+    let before = ledger.get(&creator).lamports;
+    ledger.ok(ix(
+        WithdrawTips::DISCRIMINATOR,
+        &[],
+        vec![
+            AccountMeta::new(creator, true),
+            AccountMeta::new(tip_jar, false),
+        ],
+    ));
+    assert_eq!(ledger.get(&creator).lamports, before + 3 * SOL);
+    assert_eq!(ledger.get(&tip_jar).lamports, rent);
+}
+
+// =============================================================================
+// 收件箱 / realloc
+// =============================================================================
+
+#[test]
+fn test_store_payload_and_grow() {
+    let mut ledger = Ledger::new();
+    let owner = ledger.wallet();
+    let inbox = pda(&[INBOX_SEED, owner.as_ref()]);
+    let system = ledger.system_program;
+    let accounts = vec![
+        AccountMeta::new(owner, true),
+        AccountMeta::new(inbox, false),
+        AccountMeta::new_readonly(system, false),
+    ];
+
+    // 1. 原样保存，再次保存时按新长度扩缩容
+    ledger.ok(ix(
+        StorePayload::DISCRIMINATOR,
+        &[1, 2, 3, 4],
+        accounts.clone(),
+    ));
+    assert_eq!(ledger.get(&inbox).data, [1, 2, 3, 4]);
+    ledger.ok(ix(StorePayload::DISCRIMINATOR, &[9, 9], accounts.clone()));
+    assert_eq!(ledger.get(&inbox).data, [9, 9]);
+    assert_eq!(ledger.get(&inbox).lamports, ledger.rent(2));
+
+    // 2. grow：新增区域清零，租金补足
+    ledger.ok(ix(
+        Grow::DISCRIMINATOR,
+        &10u64.to_le_bytes(),
+        accounts.clone(),
+    ));
+    let account = ledger.get(&inbox);
+    assert_eq!(account.data, [9, 9, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(account.lamports, ledger.rent(10));
+
+    // 3. grow 不能缩容
+    ledger.fails(
+        ix(Grow::DISCRIMINATOR, &4u64.to_le_bytes(), accounts),
+        ProgramError::InvalidArgument,
+    );
+}
+
+// =============================================================================
+// forward：invoke 与 invoke_signed
+// =============================================================================
+
+#[test]
+fn test_forward_with_signer_and_pda() {
+    let mut ledger = Ledger::new();
+    let from = ledger.wallet();
+    let recipient = ledger.wallet();
+    let system = ledger.system_program;
+
+    // 1. from 自己签名：invoke
+    ledger.ok(ix(
+        Forward::DISCRIMINATOR,
+        &SOL.to_le_bytes(),
+        vec![
+            AccountMeta::new(from, true),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new_readonly(system, false),
+        ],
+    ));
+    assert_eq!(ledger.get(&from).lamports, 9 * SOL);
+    assert_eq!(ledger.get(&recipient).lamports, 11 * SOL);
+
+    // 2. from 未签名
+    ledger.fails(
+        ix(
+            Forward::DISCRIMINATOR,
+            &SOL.to_le_bytes(),
+            vec![
+                AccountMeta::new(from, false),
+                AccountMeta::new(recipient, false),
+                AccountMeta::new_readonly(system, false),
+            ],
+        ),
+        custom(SsError::NotSigner),
+    );
+
+    // 3. from 是 authority 的 PDA：invoke_signed
+    let authority = ledger.wallet();
+    let vault = pda(&[FORWARD_SEED, authority.as_ref()]);
+    ledger.set(vault, Account::new(5 * SOL, 0, &system));
+    ledger.ok(ix(
+        Forward::DISCRIMINATOR,
+        &(2 * SOL).to_le_bytes(),
+        vec![
+            AccountMeta::new(vault, false),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new_readonly(system, false),
+            AccountMeta::new_readonly(authority, true),
+        ],
+    ));
+    assert_eq!(ledger.get(&vault).lamports, 3 * SOL);
+    assert_eq!(ledger.get(&recipient).lamports, 13 * SOL);
+}
+
+// =============================================================================
+// get_time
+// =============================================================================
+
+#[test]
+fn test_get_time_returns_clock_and_rent() {
+    let mut ledger = Ledger::new();
+    ledger.mollusk.warp_to_slot(1_000);
+    ledger.mollusk.sysvars.clock.unix_timestamp = 1_700_000_000;
+
+    let result = ledger.ok(ix(GetTime::DISCRIMINATOR, &128u64.to_le_bytes(), vec![]));
+    let data = result.return_data;
+    assert_eq!(data.len(), GetTime::RETURN_LEN);
+    assert_eq!(data[..8], 1_000u64.to_le_bytes());
+    assert_eq!(data[8..16], 1_700_000_000i64.to_le_bytes());
+    assert_eq!(data[16..], ledger.rent(128).to_le_bytes());
+}
+
+// =============================================================================
+// 留言板
+// =============================================================================
+
+#[test]
+fn test_note_post_edit_delete() {
+    let mut ledger = Ledger::new();
+    let author = ledger.wallet();
+    let id = 7u64;
+    let note = pda(&[NOTE_SEED, author.as_ref(), &id.to_le_bytes()]);
+    let system = ledger.system_program;
+    let with_id = |text: &[u8]| {
+        let mut data = id.to_le_bytes().to_vec();
+        data.extend_from_slice(&prefixed(text));
+        data
+    };
+    let accounts = |signer: Pubkey| {
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(note, false),
+            AccountMeta::new_readonly(system, false),
+        ]
+    };
+
+    ledger.mollusk.sysvars.clock.unix_timestamp = 100;
+    ledger.ok(ix(
+        PostNote::DISCRIMINATOR,
+        &with_id(b"gm"),
+        accounts(author),
+    ));
+    let data = ledger.get(&note).data;
+    assert_eq!(&data[..32], author.as_ref());
+    assert_eq!(data[32..40], 100i64.to_le_bytes());
+    assert_eq!(&data[48..], prefixed(b"gm").as_slice());
+
+    // 其他人不能修改
+    let other = ledger.wallet();
+    ledger.fails(
+        ix(EditNote::DISCRIMINATOR, &with_id(b"rekt"), accounts(other)),
+        custom(SsError::InvalidAddress),
+    );
+
+    // 作者修改：created_at 不变，updated_at 更新
+    ledger.mollusk.sysvars.clock.unix_timestamp = 200;
+    ledger.ok(ix(
+        EditNote::DISCRIMINATOR,
+        &with_id(b"gm, solana"),
+        accounts(author),
+    ));
+    let data = ledger.get(&note).data;
+    assert_eq!(data[32..40], 100i64.to_le_bytes());
+    assert_eq!(data[40..48], 200i64.to_le_bytes());
+    assert_eq!(&data[48..], prefixed(b"gm, solana").as_slice());
+
+    ledger.ok(ix(
+        DeleteNote::DISCRIMINATOR,
+        &id.to_le_bytes(),
+        vec![
+            AccountMeta::new(author, true),
+            AccountMeta::new(note, false),
+        ],
+    ));
+    assert_eq!(ledger.get(&note).lamports, 0);
+}
+
+// =============================================================================
+// 水龙头
+// =============================================================================
+
+/// 未初始化 freeze authority、小数位为 6 的 mint
+fn mint_account(ledger: &Ledger, authority: &Pubkey) -> Account {
+    let mut data = vec![0u8; 82];
+    data[..4].copy_from_slice(&1u32.to_le_bytes());
+    data[4..36].copy_from_slice(authority.as_ref());
+    data[44] = 6; // decimals
+    data[45] = 1; // is_initialized
+    Account {
+        lamports: ledger.rent(82),
+        data,
+        owner: token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn token_account(ledger: &Ledger, mint: &Pubkey, owner: &Pubkey) -> Account {
+    let mut data = vec![0u8; 165];
+    data[..32].copy_from_slice(mint.as_ref());
+    data[32..64].copy_from_slice(owner.as_ref());
+    data[108] = 1; // AccountState::Initialized
+    Account {
+        lamports: ledger.rent(165),
+        data,
+        owner: token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[test]
+fn test_faucet_drip_daily_cap() {
+    let mut ledger = Ledger::new();
+    let admin = ledger.wallet();
+    let user = ledger.wallet();
+    let mint = Pubkey::new_unique();
+    let user_ata = Pubkey::new_unique();
+    ledger.set(mint, mint_account(&ledger, &admin));
+    ledger.set(user_ata, token_account(&ledger, &mint, &user));
+    let faucet = pda(&[FAUCET_SEED, mint.as_ref()]);
+    let claim = pda(&[CLAIM_SEED, faucet.as_ref(), user.as_ref()]);
+    let system = ledger.system_program;
+
+    // 1. 创建水龙头：mint authority 移交给水龙头 PDA
+    ledger.ok(ix(
+        InitFaucet::DISCRIMINATOR,
+        &100u64.to_le_bytes(),
+        vec![
+            AccountMeta::new(admin, true),
+            AccountMeta::new(faucet, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(system, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+    ));
+    assert_eq!(&ledger.get(&mint).data[4..36], faucet.as_ref());
+
+    let drip = |amount: u64| {
+        ix(
+            Drip::DISCRIMINATOR,
+            &amount.to_le_bytes(),
+            vec![
+                AccountMeta::new(user, true),
+                AccountMeta::new(claim, false),
+                AccountMeta::new_readonly(faucet, false),
+                AccountMeta::new(mint, false),
+                AccountMeta::new(user_ata, false),
+                AccountMeta::new_readonly(token::ID, false),
+                AccountMeta::new_readonly(system, false),
+            ],
+        )
+    };
+    let balance = |ledger: &Ledger| {
+        u64::from_le_bytes(ledger.get(&user_ata).data[64..72].try_into().unwrap())
+    };
+
+    // 2. 当日累计不超过上限
+    ledger.mollusk.sysvars.clock.unix_timestamp = 86_400 * 10;
+    ledger.ok(drip(60));
+    ledger.fails(drip(50), custom(SsError::DripLimitExceeded));
+    ledger.ok(drip(40));
+    assert_eq!(balance(&ledger), 100);
+
+    // 3. 第二天重新计数
+    ledger.mollusk.sysvars.clock.unix_timestamp = 86_400 * 11;
+    ledger.ok(drip(50));
+    assert_eq!(balance(&ledger), 150);
+}
+
+// =============================================================================
+// 白名单
+// =============================================================================
+
+#[test]
+fn test_whitelist_add_check_remove() {
+    let mut ledger = Ledger::new();
+    let admin = ledger.wallet();
+    let registry = pda(&[WHITELIST_SEED, admin.as_ref()]);
+    let system = ledger.system_program;
+    let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let admin_ix = |discriminator: &u8, key: &Pubkey| {
+        ix(
+            discriminator,
+            key.as_ref(),
+            vec![
+                AccountMeta::new(admin, true),
+                AccountMeta::new(registry, false),
+                AccountMeta::new_readonly(system, false),
+            ],
+        )
+    };
+    let check = |key: &Pubkey| {
+        ix(
+            WhitelistCheck::DISCRIMINATOR,
+            key.as_ref(),
+            vec![AccountMeta::new_readonly(registry, false)],
+        )
+    };
+
+    ledger.ok(admin_ix(WhitelistAdd::DISCRIMINATOR, &alice));
+    ledger.ok(admin_ix(WhitelistAdd::DISCRIMINATOR, &bob));
+    // 重复添加不会占用新槽位
+    ledger.ok(admin_ix(WhitelistAdd::DISCRIMINATOR, &alice));
+    assert_eq!(ledger.get(&registry).data.len(), 32 + 2 * 32);
+    ledger.ok(check(&alice));
+    ledger.ok(check(&bob));
+
+    ledger.ok(admin_ix(WhitelistRemove::DISCRIMINATOR, &alice));
+    assert_eq!(ledger.get(&registry).data.len(), 32 + 32);
+    ledger.fails(check(&alice), custom(SsError::NotWhitelisted));
+    ledger.ok(check(&bob));
+}
+
+// =============================================================================
+// 事件
+// =============================================================================
+
+#[test]
+fn test_emit_message_requires_signer() {
+    let mut ledger = Ledger::new();
+    let sender = ledger.wallet();
+    ledger.ok(ix(
+        EmitMessage::DISCRIMINATOR,
+        &prefixed(b"hi"),
+        vec![AccountMeta::new_readonly(sender, true)],
+    ));
+    ledger.fails(
+        ix(
+            EmitMessage::DISCRIMINATOR,
+            &prefixed(b"hi"),
+            vec![AccountMeta::new_readonly(sender, false)],
+        ),
+        custom(SsError::NotSigner),
+    );
+}
+
+#[test]
+fn test_decode_events_from_logs() {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+
+    let sender = Pubkey::new_unique();
+    let event = Event::Message {
+        sender,
+        text: "gm".into(),
+    };
+    let encoded = STANDARD.encode(event.encode().unwrap());
+    assert_eq!(decode_event(&event.encode().unwrap()), Ok(event.clone()));
+
+    // 只解码本程序（调用栈顶）打印的 Program data
+    let logs = [
+        format!("Program {PROGRAM_ID} invoke [1]"),
+        format!("Program data: {encoded}"),
+        "Program 11111111111111111111111111111111 invoke [2]".to_string(),
+        format!("Program data: {encoded}"),
+        "Program 11111111111111111111111111111111 success".to_string(),
+        format!("Program {PROGRAM_ID} success"),
+    ];
+    assert_eq!(events_from_logs(&PROGRAM_ID, &logs), vec![Ok(event)]);
+}
@@ -1,25 +1,64 @@
-/**
+/*!
  * Anchor 金库程序（Vault Program）
  * 
  * 这是一个简单的 Solana 程序，允许用户：
- * 1. 将 SOL（lamports）存入个人金库
- * 2. 从个人金库中提取所有 SOL
+ * 1. 将 SOL（lamports）存入个人金库，之后可以继续追加
+ * 2. 从个人金库中提取所有 SOL，或只提取一部分（金库保持免租金）；close_vault 一并回收所有附属账户的租金
+ * 3. 存款时设定锁定期，到期前不能取款；锁定期只能延长
+ * 4. 指定一个代理人（例如恢复密钥），由它代为取出金库中的全部 SOL
+ * 5. 开启流式支付：按每秒固定速率把金库余额陆续释放给收款人，任何人都可以触发结算
+ * 6. 管理员通过全局配置账户限制最低存款与单个金库的余额上限，紧急情况下暂停新的存款；
+ *    还可以按基点收取 SOL 存款手续费，在同一笔交易中转入手续费国库 PDA
+ * 7. 设置共同签名策略（2-of-2）：之后每次取款都需要所有者与共同签名者同时签名
+ * 8. 存取 SPL 代币：金库 PDA 的 ATA 保存代币，SPL Token 与 Token-2022（含转账手续费等扩展）都支持
+ * 9. 在金库内把 SOL 包装成 wSOL（或解包回 SOL），方便对接只接受代币的协议
+ * 10. 把金库中的 SOL 整体迁移到另一个所有者的金库（例如更换钱包密钥）
+ * 11. 为金库附上可选的标签与备注（元数据账户），方便钱包显示
+ * 12. 设置取款冷却：每 N 秒最多取款一次，降低私钥泄露后被一次次转空的风险
+ * 13. 按序号派生多个子金库地址（例如每个付款方一个）接收 SOL，之后用 sweep 在一笔交易中取回全部子金库
  * 
  * 核心概念：
  * - PDA（程序派生地址）：使用用户公钥派生的确定性地址
- * - CPI（跨程序调用）：调用系统程序进行转账
+ * - CPI（跨程序调用）：调用系统程序进行转账，代币用 TokenInterface 的 transfer_checked
+ * - 事件 CPI：以 event_authority PDA 签名自调用发出事件（与其他训练营程序的事件格式相同）
  * - 租金豁免：确保账户有足够余额以免被清除
+ * - 时钟：取款时读取 Clock sysvar 的 unix_timestamp 判断锁定期
  */
 
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, spl_token::native_mint, CloseAccount, SyncNative, Token},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use blueshift_events::{cpi::emit_event, VaultDepositedV2, VaultWithdrawnV2};
 
 // ⚠️ 重要：此程序 ID 必须设置为指定值以通过测试
 // declare_id!("22222222222222222222222222222222222222222221");
 declare_id!("22222222222222222222222222222222222222222222");
+// security.txt：让浏览器展示漏洞披露方式；只在 `--features security-txt` 构建链上程序时嵌入
+#[cfg(all(feature = "security-txt", not(feature = "no-entrypoint")))]
+solana_security_txt::security_txt! {
+    name: "Blueshift Anchor Vault",
+    project_url: "https://github.com/o7reconversion/solana_bootcamp_2026",
+    contacts: "link:https://github.com/o7reconversion/solana_bootcamp_2026/security/advisories/new",
+    policy: "https://github.com/o7reconversion/solana_bootcamp_2026/blob/main/SECURITY.md",
+    source_code: "https://github.com/o7reconversion/solana_bootcamp_2026/tree/main/task2/blueshift_anchor_vault"
+}
+
 /**
  * 程序模块
- * 包含两个核心指令：deposit 和 withdraw
+ * 核心指令：deposit（首笔存款）、top_up（追加存款）、withdraw（全部取出）
+ * 与 withdraw_partial（部分取出）、withdraw_to（取给他人）、close_vault（关闭金库）、
+ * transfer_vault_ownership（转移金库）、extend_lock（延长锁定期）、
+ * set_delegate 与 withdraw_as_delegate（代理人取款）、open_stream 与 claim_stream（流式支付）、
+ * initialize_config 与 update_config（全局存款限制）、emergency_pause 与 unpause（暂停存款）、
+ * set_fee 与 collect_fees（存款手续费）、
+ * create_policy 与 update_policy（共同签名）、deposit_token 与 withdraw_token（代币金库）、
+ * wrap_deposit 与 unwrap_withdraw（wSOL 包装）、init_metadata、update_metadata 与 close_metadata（元数据）、
+ * set_cooldown（取款冷却）、sweep（子金库归集），
+ * 以及各程序通用的 get_version
  */
 #[program]
 pub mod blueshift_anchor_vault {
@@ -33,6 +72,8 @@ pub mod blueshift_anchor_vault {
      * 参数：
      * - ctx: 包含所有必需账户的上下文
      * - amount: 要存入的 lamports 数量
+     * - lock_seconds: 锁定秒数，0 表示不锁定；到期前 withdraw / withdraw_partial 都会失败。
+     *   状态账户已存在时取原解锁时间与 now + lock_seconds 中较晚的一个
      * 
      * 返回：
      * - Result<()>: 成功返回 Ok(())，失败返回错误
@@ -40,8 +81,13 @@ pub mod blueshift_anchor_vault {
      * 安全检查：
      * 1. 金库必须为空（防止重复存款）
      * 2. 存款金额必须大于免租金最低限额
+     * 3. 已初始化全局配置时，金额不低于 min_deposit、不超过 max_vault_balance
+     * 4. 管理员暂停存款期间返回 VaultPaused
+     *
+     * 手续费：全局配置的 fee_bps 不为 0 时，amount 中按比例扣下的部分转入 treasury，
+     * 金库只收到其余部分；免租金、min_deposit 与余额上限都按实际入账的金额检查
      */
-    pub fn deposit(ctx: Context<VaultAction>, amount: u64) -> Result<()> {
+    pub fn deposit(ctx: Context<VaultAction>, amount: u64, lock_seconds: u32) -> Result<()> {
         // ========================================
         // 步骤 1: 验证金库为空
         // ========================================
@@ -54,16 +100,25 @@ pub mod blueshift_anchor_vault {
         );
 
         // ========================================
-        // 步骤 2: 验证存款金额
+        // 步骤 2: 扣除手续费并验证存款金额
         // ========================================
+        // 没有全局配置或 fee_bps 为 0 时手续费为 0，金库收到全部 amount
+        let config = GlobalConfig::load(&ctx.accounts.config)?;
+        let fee = config.as_ref().map_or(0, |config| config.fee(amount));
+        let net = amount - fee;
+
         // require_gt! 宏检查第一个值是否大于第二个值
-        // 确保存款金额超过免租金最低限额（Rent::get()?.minimum_balance(0)）
+        // 确保存入金库的金额超过免租金最低限额（Rent::get()?.minimum_balance(0)）
         // 这是必要的，因为 Solana 账户需要保持一定余额才能存活
         require_gt!(
-            amount,
+            net,
             Rent::get()?.minimum_balance(0),
             VaultError::InvalidAmount
         );
+        if let Some(config) = &config {
+            config.ensure_active()?;
+            config.check_deposit(net)?;
+        }
 
         // ========================================
         // 步骤 3: 执行转账（CPI 调用）
@@ -80,7 +135,108 @@ pub mod blueshift_anchor_vault {
                     to: ctx.accounts.vault.to_account_info(),     // 转入账户（金库）
                 },
             ),
-            amount,  // 转账金额
+            net,  // 转账金额（已扣除手续费）
+        )?;
+        pay_deposit_fee(ctx.accounts, fee)?;
+
+        // ========================================
+        // 步骤 4: 记录解锁时间
+        // ========================================
+        // vault_state 由 init_if_needed 创建，withdraw 时随金库一起关闭；
+        // 但金库也可能在状态账户还在时被清空，此时只能延后、不能提前原有的解锁时间，
+        // 否则用 lock_seconds = 0 重新存款就能绕开尚未到期的锁
+        let now = Clock::get()?.unix_timestamp;
+        let vault_state = &mut ctx.accounts.vault_state;
+        vault_state.unlock_timestamp = vault_state
+            .unlock_timestamp
+            .max(now.saturating_add(i64::from(lock_seconds)));
+
+        // ========================================
+        // 步骤 5: 发出存款事件（事件 CPI）
+        // ========================================
+        // 以 event_authority PDA 签名自调用，事件数据写在内部指令里，
+        // 编码与 Pinocchio 金库写进日志的 VaultDepositedV2 完全相同
+        emit_event(
+            &crate::ID,
+            &ctx.accounts.event_authority,
+            ctx.bumps.event_authority,
+            &VaultDepositedV2 {
+                owner: ctx.accounts.signer.key().to_bytes(),
+                vault: ctx.accounts.vault.key().to_bytes(),
+                amount: net,
+                timestamp: now,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /**
+     * 追加存款指令
+     *
+     * 功能：向已有余额的金库继续存入 amount 个 lamports，不必先取出再存
+     *
+     * 参数：
+     * - ctx: 与 deposit 相同的账户
+     * - amount: 追加的 lamports 数量
+     *
+     * 安全检查：
+     * 1. 金库必须已有余额（空金库请用 deposit，由它检查首笔存款高于免租金限额），
+     *    否则 InvalidAmount
+     * 2. amount 大于 0；金库已经免租金，追加多少都不会破坏这一点
+     * 3. 已初始化全局配置时，追加后的余额不超过 max_vault_balance（min_deposit 只约束首笔存款）
+     * 4. 与 deposit 一样受暂停开关约束
+     *
+     * 手续费与 deposit 相同，余额上限按扣除手续费后的金额检查
+     */
+    pub fn top_up(ctx: Context<VaultAction>, amount: u64) -> Result<()> {
+        // ========================================
+        // 步骤 1: 验证金库已存在、金额非零
+        // ========================================
+        require_neq!(
+            ctx.accounts.vault.lamports(),
+            0,
+            VaultError::InvalidAmount
+        );
+        require_neq!(amount, 0, VaultError::InvalidAmount);
+        let mut fee = 0;
+        if let Some(config) = GlobalConfig::load(&ctx.accounts.config)? {
+            config.ensure_active()?;
+            fee = config.fee(amount);
+            config.check_balance(ctx.accounts.vault.lamports().saturating_add(amount - fee))?;
+        }
+        let net = amount - fee;
+
+        // ========================================
+        // 步骤 2: 执行转账（签名者 → 金库，不需要 PDA 签名）
+        // ========================================
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            net,
+        )?;
+        pay_deposit_fee(ctx.accounts, fee)?;
+
+        // ========================================
+        // 步骤 3: 发出存款事件（事件 CPI）
+        // ========================================
+        // 与 deposit 共用 VaultDepositedV2，索引器按事件累加即可得到金库余额
+        // （记录的是实际入账的金额，不含手续费）
+        emit_event(
+            &crate::ID,
+            &ctx.accounts.event_authority,
+            ctx.bumps.event_authority,
+            &VaultDepositedV2 {
+                owner: ctx.accounts.signer.key().to_bytes(),
+                vault: ctx.accounts.vault.key().to_bytes(),
+                amount: net,
+                timestamp: Clock::get()?.unix_timestamp,
+            },
         )?;
 
         Ok(())
@@ -100,6 +256,7 @@ pub mod blueshift_anchor_vault {
      * 安全检查：
      * 1. 金库必须有余额（不能从空金库取款）
      * 2. 使用 PDA 签名确保只有金库所有者可以取款
+     * 3. 存在共同签名策略时，共同签名者作为 remaining_accounts 传入并签名
      */
     pub fn withdraw(ctx: Context<VaultAction>) -> Result<()> {
         // ========================================
@@ -112,6 +269,13 @@ pub mod blueshift_anchor_vault {
             0,
             VaultError::InvalidAmount
         );
+        // 锁定期内拒绝取款（VaultLocked）
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.vault_state.ensure_unlocked(now)?;
+        // 设置了共同签名策略时，共同签名者也必须签名（CoSignerRequired）
+        VaultPolicy::enforce(&ctx.accounts.policy, ctx.remaining_accounts)?;
+        // 设置了取款冷却时，距上次取款不足间隔则拒绝（WithdrawTooSoon）
+        WithdrawCooldown::record(&ctx.accounts.cooldown, now)?;
 
         // ========================================
         // 步骤 2: 创建 PDA 签名者种子
@@ -128,6 +292,9 @@ pub mod blueshift_anchor_vault {
         // ========================================
         // 步骤 3: 执行转账（带 PDA 签名的 CPI 调用）
         // ========================================
+        // 先记下金额，事件中要用到（转账后金库余额为 0）
+        let amount = ctx.accounts.vault.lamports();
+
         // 使用 new_with_signer 允许 PDA 作为签名者执行转账
         // 这是关键的安全机制：只有知道正确种子的程序才能代表 PDA 签署交易
         transfer(
@@ -143,89 +310,2482 @@ pub mod blueshift_anchor_vault {
                 &[signer_seeds]
             ),
             // 转账金库中的所有 lamports
-            ctx.accounts.vault.lamports()
+            amount
+        )?;
+
+        // ========================================
+        // 步骤 4: 发出取款事件（事件 CPI）
+        // ========================================
+        emit_event(
+            &crate::ID,
+            &ctx.accounts.event_authority,
+            ctx.bumps.event_authority,
+            &VaultWithdrawnV2 {
+                owner: signer_key.to_bytes(),
+                vault: ctx.accounts.vault.key().to_bytes(),
+                amount,
+                timestamp: now,
+            },
         )?;
 
+        // ========================================
+        // 步骤 5: 关闭状态账户，租金退还签名者
+        // ========================================
+        ctx.accounts
+            .vault_state
+            .close(ctx.accounts.signer.to_account_info())?;
+
         Ok(())
     }
-}
 
-/**
- * VaultAction 账户结构
- * 
- * 这个结构定义了 deposit 和 withdraw 指令需要的所有账户
- * 使用相同的结构使代码更简洁、更易维护
- */
-#[derive(Accounts)]
-pub struct VaultAction<'info> {
     /**
-     * 签名者账户
-     * 
-     * - Signer<'info>: 确保此账户已签署交易
-     * - #[account(mut)]: 标记为可变，因为我们将修改其 lamports 余额
-     * 
-     * 这是金库的所有者，也是唯一可以存取金库资金的人
+     * 部分取款指令
+     *
+     * 功能：从金库中取出 amount 个 lamports，金库继续保留剩余余额
+     *
+     * 参数：
+     * - ctx: 与 deposit / withdraw 相同的账户
+     * - amount: 要取出的 lamports 数量
+     *
+     * 安全检查：
+     * 1. 金库非空、amount 大于 0（否则 InvalidAmount，与 withdraw 一致）
+     * 2. 锁定期已过（否则 VaultLocked），共同签名者已签名（与 withdraw 相同）
+     * 3. 取款后金库余额不低于免租金最低限额（否则 BelowRentMinimum），
+     *    想全部取出时应使用 withdraw，由它清空并让运行时回收账户
      */
-    #[account(mut)]
-    pub signer: Signer<'info>,
+    pub fn withdraw_partial(ctx: Context<VaultAction>, amount: u64) -> Result<()> {
+        // ========================================
+        // 步骤 1: 验证金库与金额
+        // ========================================
+        let balance = ctx.accounts.vault.lamports();
+        require_neq!(balance, 0, VaultError::InvalidAmount);
+        require_neq!(amount, 0, VaultError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.vault_state.ensure_unlocked(now)?;
+        VaultPolicy::enforce(&ctx.accounts.policy, ctx.remaining_accounts)?;
+        WithdrawCooldown::record(&ctx.accounts.cooldown, now)?;
+
+        // ========================================
+        // 步骤 2: 验证剩余余额仍然免租金
+        // ========================================
+        // amount 超过余额时 checked_sub 返回 None，同样视为低于限额
+        let remaining = balance
+            .checked_sub(amount)
+            .ok_or(VaultError::BelowRentMinimum)?;
+        require_gte!(
+            remaining,
+            Rent::get()?.minimum_balance(0),
+            VaultError::BelowRentMinimum
+        );
+
+        // ========================================
+        // 步骤 3: PDA 签名转账（与 withdraw 相同的种子）
+        // ========================================
+        let signer_key = ctx.accounts.signer.key();
+        let signer_seeds: &[&[u8]] = &[
+            b"vault",
+            signer_key.as_ref(),
+            &[ctx.bumps.vault]
+        ];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.signer.to_account_info(),
+                },
+                &[signer_seeds]
+            ),
+            amount
+        )?;
+
+        // ========================================
+        // 步骤 4: 发出取款事件（事件 CPI）
+        // ========================================
+        // 与 withdraw 共用 VaultWithdrawnV2，amount 是本次取出的数量而不是金库原有余额
+        emit_event(
+            &crate::ID,
+            &ctx.accounts.event_authority,
+            ctx.bumps.event_authority,
+            &VaultWithdrawnV2 {
+                owner: signer_key.to_bytes(),
+                vault: ctx.accounts.vault.key().to_bytes(),
+                amount,
+                timestamp: now,
+            },
+        )?;
+
+        Ok(())
+    }
 
     /**
-     * 金库账户（PDA）
-     * 
-     * - SystemAccount<'info>: 系统拥有的账户类型
-     * - #[account(mut)]: 标记为可变，因为我们将修改其 lamports 余额
-     * - seeds: 定义 PDA 的派生种子
-     *   - b"vault": 字符串字面量作为固定种子
-     *   - signer.key().as_ref(): 签名者的公钥作为唯一标识
-     * - bump: 自动找到并验证 bump seed
-     * 
-     * PDA 的优势：
-     * 1. 确定性地址：给定相同的种子，总是生成相同的地址
-     * 2. 程序控制：只有程序可以签署代表 PDA 的交易
-     * 3. 无私钥：PDA 没有对应的私钥，更安全
+     * 取款给他人指令
+     *
+     * 功能：与 withdraw 相同，把金库中的全部 lamports 取出，但直接转给 recipient
+     *
+     * 安全检查：
+     * 1. 仍然需要金库所有者签名，recipient 不需要签名
+     * 2. 金库非空（否则 InvalidAmount）、锁定期已过（否则 VaultLocked）
+     * 3. 共同签名策略同样适用，否则取给他人就能绕开第二个签名
+     *
+     * 锁定功能之前存入的金库没有状态账户，视为未锁定；
+     * 状态账户存在时关闭它，租金退还签名者而不是 recipient，租金是签名者存款时付的
      */
-    #[account(
-        mut,
-        seeds = [b"vault", signer.key().as_ref()],
-        bump,
-    )]
-    pub vault: SystemAccount<'info>,
+    pub fn withdraw_to(ctx: Context<WithdrawTo>) -> Result<()> {
+        // ========================================
+        // 步骤 1: 验证金库与锁定期
+        // ========================================
+        let amount = ctx.accounts.vault.lamports();
+        require_neq!(amount, 0, VaultError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        if let Some(state) = VaultState::load(&ctx.accounts.vault_state)? {
+            state.ensure_unlocked(now)?;
+        }
+        VaultPolicy::enforce(&ctx.accounts.policy, ctx.remaining_accounts)?;
+        WithdrawCooldown::record(&ctx.accounts.cooldown, now)?;
+
+        // ========================================
+        // 步骤 2: PDA 签名转账给 recipient
+        // ========================================
+        let signer_key = ctx.accounts.signer.key();
+        let signer_seeds: &[&[u8]] = &[
+            b"vault",
+            signer_key.as_ref(),
+            &[ctx.bumps.vault]
+        ];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.recipient.to_account_info(),
+                },
+                &[signer_seeds]
+            ),
+            amount
+        )?;
+
+        // ========================================
+        // 步骤 3: 发出取款事件
+        // ========================================
+        emit_event(
+            &crate::ID,
+            &ctx.accounts.event_authority,
+            ctx.bumps.event_authority,
+            &VaultWithdrawnV2 {
+                owner: signer_key.to_bytes(),
+                vault: ctx.accounts.vault.key().to_bytes(),
+                amount,
+                timestamp: now,
+            },
+        )?;
+
+        // ========================================
+        // 步骤 4: 关闭状态账户（如果有），租金退还签名者
+        // ========================================
+        close_if_present(
+            &ctx.accounts.vault_state,
+            &ctx.accounts.signer.to_account_info(),
+        )?;
+
+        Ok(())
+    }
 
     /**
-     * 系统程序
-     * 
-     * - Program<'info, System>: Solana 的系统程序类型
-     * 
-     * 需要包含系统程序因为我们要使用它的转账指令（CPI）
-     * 系统程序是 Solana 的核心程序，负责创建账户、转账等基本操作
+     * 关闭金库指令
+     *
+     * 功能：把金库中的全部 lamports（包括免租金储备）转回签名者，并关闭状态账户与
+     * 尚未结束的流，租金同样退还签名者。执行后金库余额为 0，由运行时回收，
+     * 不会留下任何属于这个金库的 SOL 账户（共同签名策略除外，它需要两个签名才能删除）；
+     * 代币金库（金库 PDA 的 ATA）不在此列，先用 withdraw_token 取出
+     *
+     * 与 withdraw 的区别：
+     * 1. 金库已经为空、只剩状态账户时也可以调用，用来回收状态账户的租金
+     * 2. 同时关闭 Stream，之后 claim_stream 不会再对这个金库结算
+     *
+     * 安全检查：
+     * 1. 状态账户存在时锁定期必须已过（否则 VaultLocked）
+     * 2. 存在共同签名策略时需要共同签名者签名
+     * 3. 金库与状态账户都不存在时没有可关闭的内容（InvalidAmount）
      */
-    pub system_program: Program<'info, System>,
-}
+    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+        // ========================================
+        // 步骤 1: 验证锁定期与共同签名
+        // ========================================
+        let now = Clock::get()?.unix_timestamp;
+        let state = VaultState::load(&ctx.accounts.vault_state)?;
+        if let Some(state) = &state {
+            state.ensure_unlocked(now)?;
+        }
+        VaultPolicy::enforce(&ctx.accounts.policy, ctx.remaining_accounts)?;
+        let amount = ctx.accounts.vault.lamports();
+        require!(amount > 0 || state.is_some(), VaultError::InvalidAmount);
+
+        // ========================================
+        // 步骤 2: PDA 签名转出全部余额
+        // ========================================
+        let signer_key = ctx.accounts.signer.key();
+        if amount > 0 {
+            // 只关闭状态账户时不算取款，不受冷却限制
+            WithdrawCooldown::record(&ctx.accounts.cooldown, now)?;
+            let signer_seeds: &[&[u8]] = &[
+                b"vault",
+                signer_key.as_ref(),
+                &[ctx.bumps.vault]
+            ];
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: ctx.accounts.signer.to_account_info(),
+                    },
+                    &[signer_seeds]
+                ),
+                amount
+            )?;
+
+            emit_event(
+                &crate::ID,
+                &ctx.accounts.event_authority,
+                ctx.bumps.event_authority,
+                &VaultWithdrawnV2 {
+                    owner: signer_key.to_bytes(),
+                    vault: ctx.accounts.vault.key().to_bytes(),
+                    amount,
+                    timestamp: now,
+                },
+            )?;
+        }
+
+        // ========================================
+        // 步骤 3: 关闭状态账户与流，租金退还签名者
+        // ========================================
+        let signer = ctx.accounts.signer.to_account_info();
+        close_if_present(&ctx.accounts.vault_state, &signer)?;
+        close_if_present(&ctx.accounts.stream, &signer)?;
+
+        // 金库必须归零：余额为 0 的系统账户在交易结束后被回收
+        require_eq!(ctx.accounts.vault.lamports(), 0, VaultError::InvalidAmount);
+
+        Ok(())
+    }
 
-/**
- * 自定义错误枚举
- * 
- * 定义程序可能返回的错误类型
- * #[error_code] 宏会自动为每个错误分配唯一的错误码
- */
-#[error_code]
-pub enum VaultError {
     /**
-     * 金库已存在错误
-     * 
-     * 当用户尝试向已有余额的金库存款时触发
-     * 这防止了意外的重复存款
+     * 转移金库指令
+     *
+     * 功能：把签名者金库中的全部 lamports 原子地转入 new_owner 派生的金库，
+     * 之后由 new_owner 按自己的状态账户与策略管理这些资金
+     *
+     * 安全检查：
+     * 1. 原金库不能为空（否则 InvalidAmount）
+     * 2. 目标金库必须为空（否则 VaultAlreadyExists），不会与 new_owner 已有的存款合并；
+     *    new_owner 等于签名者时两个金库是同一个账户，同样由这一条拒绝
+     * 3. 锁定期与共同签名策略与 withdraw 相同：锁定中的资金不能借转移提前取出
+     *
+     * 原所有者的状态账户与流随金库一起关闭，租金退还签名者；
+     * 代理人与锁定期不会迁移，代币金库（金库 PDA 的 ATA）也不在此列
      */
-    #[msg("金库已存在，不能重复存款")]
-    VaultAlreadyExists,
+    pub fn transfer_vault_ownership(
+        ctx: Context<TransferVaultOwnership>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        // ========================================
+        // 步骤 1: 验证两个金库、锁定期与共同签名
+        // ========================================
+        let amount = ctx.accounts.vault.lamports();
+        require_neq!(amount, 0, VaultError::InvalidAmount);
+        require_eq!(
+            ctx.accounts.new_vault.lamports(),
+            0,
+            VaultError::VaultAlreadyExists
+        );
+        let now = Clock::get()?.unix_timestamp;
+        if let Some(state) = VaultState::load(&ctx.accounts.vault_state)? {
+            state.ensure_unlocked(now)?;
+        }
+        VaultPolicy::enforce(&ctx.accounts.policy, ctx.remaining_accounts)?;
+        WithdrawCooldown::record(&ctx.accounts.cooldown, now)?;
+
+        // ========================================
+        // 步骤 2: 原金库 PDA 签名，全部余额转入新金库
+        // ========================================
+        let signer_key = ctx.accounts.signer.key();
+        let signer_seeds: &[&[u8]] = &[
+            b"vault",
+            signer_key.as_ref(),
+            &[ctx.bumps.vault]
+        ];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.new_vault.to_account_info(),
+                },
+                &[signer_seeds]
+            ),
+            amount
+        )?;
+
+        // ========================================
+        // 步骤 3: 两侧各发出一条事件，索引器按原有方式记账
+        // ========================================
+        emit_event(
+            &crate::ID,
+            &ctx.accounts.event_authority,
+            ctx.bumps.event_authority,
+            &VaultWithdrawnV2 {
+                owner: signer_key.to_bytes(),
+                vault: ctx.accounts.vault.key().to_bytes(),
+                amount,
+                timestamp: now,
+            },
+        )?;
+        emit_event(
+            &crate::ID,
+            &ctx.accounts.event_authority,
+            ctx.bumps.event_authority,
+            &VaultDepositedV2 {
+                owner: new_owner.to_bytes(),
+                vault: ctx.accounts.new_vault.key().to_bytes(),
+                amount,
+                timestamp: now,
+            },
+        )?;
+
+        // ========================================
+        // 步骤 4: 关闭原所有者的状态账户与流
+        // ========================================
+        let signer = ctx.accounts.signer.to_account_info();
+        close_if_present(&ctx.accounts.vault_state, &signer)?;
+        close_if_present(&ctx.accounts.stream, &signer)?;
+
+        Ok(())
+    }
 
     /**
-     * 无效金额错误
-     * 
-     * 可能的情况：
-     * 1. 存款金额小于或等于免租金最低限额
-     * 2. 尝试从空金库取款
+     * 代币存款指令
+     *
+     * 功能：把 amount 个 mint 代币从签名者的代币账户转入金库 PDA 的 ATA（首次存入时创建，签名者付租金）
+     *
+     * 安全检查：
+     * 1. amount 大于 0（否则 InvalidAmount）
+     * 2. 管理员暂停存款期间返回 VaultPaused；全局配置的 lamports 限额不约束代币
+     * 3. 用 transfer_checked 并校验 mint 与小数位，Token-2022 要求带扩展的 mint 必须这样转账
+     *
+     * 带转账手续费的 mint 实际到账少于 amount，日志中记录到账数量；
+     * 需要额外账户的转账钩子（transfer hook）不支持
      */
-    #[msg("无效的金额")]
-    InvalidAmount,
+    pub fn deposit_token(ctx: Context<DepositToken>, amount: u64) -> Result<()> {
+        // ========================================
+        // 步骤 1: 验证金额与暂停开关
+        // ========================================
+        require_neq!(amount, 0, VaultError::InvalidAmount);
+        if let Some(config) = GlobalConfig::load(&ctx.accounts.config)? {
+            config.ensure_active()?;
+        }
+
+        // ========================================
+        // 步骤 2: 签名者 → 金库 ATA（签名者本人授权，不需要 PDA 签名）
+        // ========================================
+        let before = ctx.accounts.token_vault.amount;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.signer.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        // ========================================
+        // 步骤 3: 重新读取余额，得到扣除手续费后的到账数量
+        // ========================================
+        ctx.accounts.token_vault.reload()?;
+        let received = ctx.accounts.token_vault.amount.saturating_sub(before);
+        msg!("存入代币 {}，到账 {}", amount, received);
+
+        Ok(())
+    }
+
+    /**
+     * 代币取款指令
+     *
+     * 功能：从金库 PDA 的 ATA 取出 amount 个代币到签名者的 ATA（不存在时创建）
+     *
+     * 安全检查：
+     * 1. amount 大于 0 且不超过金库中的代币余额（否则 InvalidAmount）
+     * 2. 锁定期与共同签名策略与 SOL 取款相同
+     * 3. 由金库 PDA 签名 transfer_checked，种子与 SOL 金库相同
+     *
+     * 代币账户不会被关闭：Token-2022 账户上有未提取的转账手续费时无法关闭
+     */
+    pub fn withdraw_token(ctx: Context<WithdrawToken>, amount: u64) -> Result<()> {
+        // ========================================
+        // 步骤 1: 验证金额、锁定期与共同签名
+        // ========================================
+        require_neq!(amount, 0, VaultError::InvalidAmount);
+        require_gte!(
+            ctx.accounts.token_vault.amount,
+            amount,
+            VaultError::InvalidAmount
+        );
+        let now = Clock::get()?.unix_timestamp;
+        if let Some(state) = VaultState::load(&ctx.accounts.vault_state)? {
+            state.ensure_unlocked(now)?;
+        }
+        VaultPolicy::enforce(&ctx.accounts.policy, ctx.remaining_accounts)?;
+        WithdrawCooldown::record(&ctx.accounts.cooldown, now)?;
+
+        // ========================================
+        // 步骤 2: 金库 PDA 签名转账
+        // ========================================
+        let signer_key = ctx.accounts.signer.key();
+        let signer_seeds: &[&[u8]] = &[
+            b"vault",
+            signer_key.as_ref(),
+            &[ctx.bumps.vault]
+        ];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_token.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                &[signer_seeds]
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        Ok(())
+    }
+
+    /**
+     * 包装指令
+     *
+     * 功能：把金库中的 amount lamports 转入金库 PDA 的 wSOL ATA 并同步余额，
+     * 之后这部分资金以 wSOL 代币的形式留在金库中，可以用 withdraw_token 取出
+     *
+     * 安全检查：
+     * 1. amount 大于 0 且不超过金库余额（否则 InvalidAmount）
+     * 2. 剩余余额仍然免租金（否则 BelowRentMinimum），不能把金库包装到 0：
+     *    空金库会让 deposit 重新通过“金库为空”的检查
+     *
+     * 资金始终归金库 PDA 所有，所以不检查锁定期与共同签名；
+     * lamports 毕竟离开了金库账户，冷却与其他取款指令一样检查并记录。
+     * wSOL ATA 首次使用时由签名者支付租金
+     */
+    pub fn wrap_deposit(ctx: Context<WrapSol>, amount: u64) -> Result<()> {
+        // ========================================
+        // 步骤 1: 验证金额与剩余余额
+        // ========================================
+        let balance = ctx.accounts.vault.lamports();
+        require_neq!(amount, 0, VaultError::InvalidAmount);
+        let remaining = balance
+            .checked_sub(amount)
+            .ok_or(VaultError::InvalidAmount)?;
+        require_gte!(
+            remaining,
+            Rent::get()?.minimum_balance(0),
+            VaultError::BelowRentMinimum
+        );
+        WithdrawCooldown::record(&ctx.accounts.cooldown, Clock::get()?.unix_timestamp)?;
+
+        // ========================================
+        // 步骤 2: 金库 PDA 签名，把 lamports 转入 wSOL ATA
+        // ========================================
+        let signer_key = ctx.accounts.signer.key();
+        let signer_seeds: &[&[u8]] = &[
+            b"vault",
+            signer_key.as_ref(),
+            &[ctx.bumps.vault]
+        ];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.wsol_vault.to_account_info(),
+                },
+                &[signer_seeds]
+            ),
+            amount
+        )?;
+
+        // ========================================
+        // 步骤 3: sync_native 让代币余额跟上 lamports
+        // ========================================
+        token::sync_native(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SyncNative {
+                account: ctx.accounts.wsol_vault.to_account_info(),
+            },
+        ))?;
+        msg!("包装 {} lamports 为 wSOL", amount);
+
+        Ok(())
+    }
+
+    /**
+     * 解包指令
+     *
+     * 功能：关闭金库 PDA 的 wSOL ATA，全部 wSOL 连同账户租金以 SOL 回到金库
+     *
+     * 原生 mint 的代币账户只能整体解包，所以不接受金额参数；
+     * 和 wrap_deposit 一样资金不离开金库，取出 SOL 仍然走 withdraw 等指令。
+     * 冷却同样检查并记录，包装与解包不能用来在冷却期内搬动资金
+     */
+    pub fn unwrap_withdraw(ctx: Context<UnwrapSol>) -> Result<()> {
+        WithdrawCooldown::record(&ctx.accounts.cooldown, Clock::get()?.unix_timestamp)?;
+        let amount = ctx.accounts.wsol_vault.amount;
+        let signer_key = ctx.accounts.signer.key();
+        let signer_seeds: &[&[u8]] = &[
+            b"vault",
+            signer_key.as_ref(),
+            &[ctx.bumps.vault]
+        ];
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.wsol_vault.to_account_info(),
+                destination: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[signer_seeds]
+        ))?;
+        msg!("解包 {} wSOL 回到金库", amount);
+
+        Ok(())
+    }
+
+    /**
+     * 延长锁定期指令
+     *
+     * 功能：把解锁时间改为 unlock_timestamp（unix 秒）
+     *
+     * 安全检查：
+     * 1. 只能延后，不能提前（否则 LockShortened）；等于当前值时不变
+     * 2. 状态账户由 signer 派生，只能修改自己的金库
+     *
+     * 已过期的锁也可以重新设定为未来的时间
+     */
+    pub fn extend_lock(ctx: Context<ExtendLock>, unlock_timestamp: i64) -> Result<()> {
+        ctx.accounts.vault_state.extend(unlock_timestamp)
+    }
+
+    /**
+     * 设置代理人指令
+     *
+     * 功能：指定可以代为取款的公钥；传入 None 取消代理
+     *
+     * 代理人记录在状态账户中，金库被 withdraw 清空时随状态账户一起清除，
+     * 重新存款后需要再次设置
+     */
+    pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.vault_state.delegate = delegate;
+        Ok(())
+    }
+
+    /**
+     * 代理人取款指令
+     *
+     * 功能：由代理人签名，把 owner 金库中的全部 lamports 转给代理人
+     *
+     * 安全检查：
+     * 1. 签名者必须是状态账户中记录的代理人（否则 NotDelegate）
+     * 2. 金库非空（否则 InvalidAmount）
+     * 3. 锁定期同样约束代理人（否则 VaultLocked）
+     * 4. 存在共同签名策略时还需要共同签名者签名
+     *
+     * 状态账户随之关闭，租金退还给当初付款的 owner
+     */
+    pub fn withdraw_as_delegate(ctx: Context<DelegateWithdraw>) -> Result<()> {
+        // ========================================
+        // 步骤 1: 验证金库与锁定期（代理人在账户约束中已校验）
+        // ========================================
+        let amount = ctx.accounts.vault.lamports();
+        require_neq!(amount, 0, VaultError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.vault_state.ensure_unlocked(now)?;
+        // 代理人取款同样需要共同签名者，否则设置代理人就能绕过策略；冷却也按所有者的设置
+        VaultPolicy::enforce(&ctx.accounts.policy, ctx.remaining_accounts)?;
+        WithdrawCooldown::record(&ctx.accounts.cooldown, now)?;
+
+        // ========================================
+        // 步骤 2: PDA 签名转账给代理人，种子仍然由 owner 派生
+        // ========================================
+        let owner_key = ctx.accounts.owner.key();
+        let signer_seeds: &[&[u8]] = &[
+            b"vault",
+            owner_key.as_ref(),
+            &[ctx.bumps.vault]
+        ];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.delegate.to_account_info(),
+                },
+                &[signer_seeds]
+            ),
+            amount
+        )?;
+
+        // ========================================
+        // 步骤 3: 发出取款事件，owner 仍是金库所有者
+        // ========================================
+        emit_event(
+            &crate::ID,
+            &ctx.accounts.event_authority,
+            ctx.bumps.event_authority,
+            &VaultWithdrawnV2 {
+                owner: owner_key.to_bytes(),
+                vault: ctx.accounts.vault.key().to_bytes(),
+                amount,
+                timestamp: now,
+            },
+        )?;
+
+        // ========================================
+        // 步骤 4: 关闭状态账户，租金退还 owner
+        // ========================================
+        ctx.accounts
+            .vault_state
+            .close(ctx.accounts.owner.to_account_info())?;
+
+        Ok(())
+    }
+
+    /**
+     * 开启流式支付指令
+     *
+     * 功能：从现在起，金库每秒向 recipient 释放 rate_per_second 个 lamports，
+     * 实际转账由 claim_stream 完成
+     *
+     * 安全检查：
+     * 1. rate_per_second 大于 0（否则 InvalidAmount）
+     * 2. 每个金库同时只能有一个流（Stream PDA 已存在时 init 失败）
+     * 3. 存在共同签名策略时需要共同签名者签名；之后的 claim_stream 不再需要
+     *
+     * 所有者仍然可以随时用 withdraw 取走余额，流随之在下一次结算时关闭
+     */
+    pub fn open_stream(
+        ctx: Context<OpenStream>,
+        recipient: Pubkey,
+        rate_per_second: u64,
+    ) -> Result<()> {
+        require_neq!(rate_per_second, 0, VaultError::InvalidAmount);
+        VaultPolicy::enforce(&ctx.accounts.policy, ctx.remaining_accounts)?;
+        // 开启流等同于一次取款：否则高速率的流可以绕过冷却一次取空金库
+        let now = Clock::get()?.unix_timestamp;
+        WithdrawCooldown::record(&ctx.accounts.cooldown, now)?;
+
+        let stream = &mut ctx.accounts.stream;
+        stream.owner = ctx.accounts.signer.key();
+        stream.recipient = recipient;
+        stream.rate_per_second = rate_per_second;
+        stream.last_claimed = now;
+        Ok(())
+    }
+
+    /**
+     * 流式支付结算指令
+     *
+     * 功能：把上次结算以来累计的 lamports 转给收款人；任何人都可以调用（只需支付手续费）
+     *
+     * 安全检查：
+     * 1. recipient 必须是流中记录的收款人（否则 WrongRecipient）
+     * 2. 锁定期内不释放（VaultLocked），累计的金额在解锁后一并结算
+     * 3. 自上次结算以来没有累计任何金额时返回 InvalidAmount
+     * 4. 每次结算都是一次取款，受所有者设置的冷却约束（否则 WithdrawTooSoon）；
+     *    冷却期内累计的金额留到下一次结算
+     *
+     * 金库保留免租金最低限额；可用余额不足以支付累计金额时，支付全部可用余额并关闭流，
+     * 租金退还所有者。收款人账户尚不存在时，首次结算的金额必须达到免租金限额
+     */
+    pub fn claim_stream(ctx: Context<ClaimStream>) -> Result<()> {
+        // ========================================
+        // 步骤 1: 检查锁定期
+        // ========================================
+        // 状态账户不存在（锁定功能之前的金库，或已被 withdraw 关闭）时视为未锁定
+        let now = Clock::get()?.unix_timestamp;
+        if let Some(state) = VaultState::load(&ctx.accounts.vault_state)? {
+            state.ensure_unlocked(now)?;
+        }
+
+        // ========================================
+        // 步骤 2: 计算本次可以支付的金额
+        // ========================================
+        let accrued = ctx.accounts.stream.accrued(now);
+        require_neq!(accrued, 0, VaultError::InvalidAmount);
+        WithdrawCooldown::record(&ctx.accounts.cooldown, now)?;
+        let available = ctx
+            .accounts
+            .vault
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        let amount = accrued.min(available);
+        let drained = amount < accrued;
+
+        // ========================================
+        // 步骤 3: PDA 签名转账给收款人
+        // ========================================
+        let owner_key = ctx.accounts.owner.key();
+        if amount > 0 {
+            let signer_seeds: &[&[u8]] = &[
+                b"vault",
+                owner_key.as_ref(),
+                &[ctx.bumps.vault]
+            ];
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: ctx.accounts.recipient.to_account_info(),
+                    },
+                    &[signer_seeds]
+                ),
+                amount
+            )?;
+
+            emit_event(
+                &crate::ID,
+                &ctx.accounts.event_authority,
+                ctx.bumps.event_authority,
+                &VaultWithdrawnV2 {
+                    owner: owner_key.to_bytes(),
+                    vault: ctx.accounts.vault.key().to_bytes(),
+                    amount,
+                    timestamp: now,
+                },
+            )?;
+        }
+
+        // ========================================
+        // 步骤 4: 记录结算时间，金库已耗尽时关闭流
+        // ========================================
+        if drained {
+            ctx.accounts
+                .stream
+                .close(ctx.accounts.owner.to_account_info())?;
+        } else {
+            ctx.accounts.stream.last_claimed = now;
+        }
+
+        Ok(())
+    }
+
+    /**
+     * 初始化全局配置指令
+     *
+     * 功能：创建 ["config"] 配置账户，记录管理员与存款限制；每个部署只能执行一次
+     *
+     * 安全检查：
+     * 1. 签名者必须是本程序的升级权限（与国库 initialize 相同），避免部署后被别人抢先设置
+     * 2. min_deposit 不超过 max_vault_balance（否则 InvalidConfig）
+     *
+     * 配置初始化之前 deposit / top_up 不受这两项限制
+     */
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        admin: Pubkey,
+        max_vault_balance: u64,
+        min_deposit: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = admin;
+        config.set_limits(max_vault_balance, min_deposit)
+    }
+
+    /**
+     * 更新全局配置指令
+     *
+     * 功能：由管理员修改存款限制，只影响之后的存款，已有金库的余额不受影响
+     */
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        max_vault_balance: u64,
+        min_deposit: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .config
+            .set_limits(max_vault_balance, min_deposit)
+    }
+
+    /**
+     * 紧急暂停指令
+     *
+     * 功能：由管理员暂停 deposit 与 top_up，例如程序即将升级或怀疑遭到攻击时
+     *
+     * 取款、代理人取款与流式支付结算不受影响，用户随时可以取回资金；
+     * 已经暂停时再次调用不报错
+     */
+    pub fn emergency_pause(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.config.paused = true;
+        msg!("存款已暂停");
+        Ok(())
+    }
+
+    /**
+     * 恢复存款指令
+     *
+     * 功能：由管理员解除 emergency_pause，账户与 update_config 相同
+     */
+    pub fn unpause(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.config.paused = false;
+        msg!("存款已恢复");
+        Ok(())
+    }
+
+    /**
+     * 设置存款手续费指令
+     *
+     * 功能：由管理员设置 deposit / top_up 的手续费率（基点，1 bp = 0.01%），0 表示不收取；
+     * 只影响之后的存款
+     *
+     * 安全检查：
+     * 1. 签名者必须是配置中的管理员（否则 Unauthorized）
+     * 2. fee_bps 不超过 MAX_FEE_BPS（否则 FeeTooHigh）
+     *
+     * treasury 余额不足免租金限额时由管理员补足：之后每笔手续费都是在已免租金的账户上累加，
+     * 再小的手续费也不会让存款因租金检查失败
+     */
+    pub fn set_fee(ctx: Context<ManageFees>, fee_bps: u16) -> Result<()> {
+        require_gte!(GlobalConfig::MAX_FEE_BPS, fee_bps, VaultError::FeeTooHigh);
+
+        let rent_minimum = Rent::get()?.minimum_balance(0);
+        let shortfall = rent_minimum.saturating_sub(ctx.accounts.treasury.lamports());
+        if fee_bps > 0 && shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+
+        ctx.accounts.config.fee_bps = fee_bps;
+        msg!("存款手续费设为 {} bps", fee_bps);
+        Ok(())
+    }
+
+    /**
+     * 提取手续费指令
+     *
+     * 功能：把 treasury 中超出免租金限额的 lamports 全部转给管理员，账户本身保留，
+     * 之后的手续费继续累加在上面；没有可提取的余额时返回 InvalidAmount
+     */
+    pub fn collect_fees(ctx: Context<ManageFees>) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let amount = treasury
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        require_neq!(amount, 0, VaultError::InvalidAmount);
+
+        // treasury 是系统账户，由本程序以 PDA 签名转出
+        let signer_seeds: &[&[&[u8]]] = &[&[b"treasury", &[ctx.bumps.treasury]]];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: treasury.to_account_info(),
+                    to: ctx.accounts.admin.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        msg!("提取手续费 {} lamports", amount);
+        Ok(())
+    }
+
+    /**
+     * 创建共同签名策略指令
+     *
+     * 功能：为签名者的金库指定共同签名者，此后 withdraw、withdraw_partial、withdraw_to、
+     * withdraw_as_delegate 与 open_stream 都需要两个签名
+     *
+     * 安全检查：
+     * 1. 共同签名者不能是所有者本人（否则 InvalidCoSigner）
+     * 2. 每个金库只有一个策略（Policy PDA 已存在时 init 失败），修改使用 update_policy
+     *
+     * 创建时不需要共同签名者签名：在此之前所有者本来就可以单独取走全部余额
+     */
+    pub fn create_policy(ctx: Context<CreatePolicy>, co_signer: Pubkey) -> Result<()> {
+        require_keys_neq!(co_signer, ctx.accounts.signer.key(), VaultError::InvalidCoSigner);
+        ctx.accounts.policy.co_signer = co_signer;
+        Ok(())
+    }
+
+    /**
+     * 更新共同签名策略指令
+     *
+     * 功能：更换共同签名者；传入 None 删除策略，租金退还所有者
+     *
+     * 安全检查：
+     * 1. 所有者与当前共同签名者都必须签名（否则 CoSignerRequired），任何一方都不能单独解除
+     * 2. 新的共同签名者不能是所有者本人（否则 InvalidCoSigner）
+     */
+    pub fn update_policy(ctx: Context<UpdatePolicy>, co_signer: Option<Pubkey>) -> Result<()> {
+        match co_signer {
+            Some(co_signer) => {
+                require_keys_neq!(co_signer, ctx.accounts.signer.key(), VaultError::InvalidCoSigner);
+                ctx.accounts.policy.co_signer = co_signer;
+                Ok(())
+            }
+            None => ctx
+                .accounts
+                .policy
+                .close(ctx.accounts.signer.to_account_info()),
+        }
+    }
+
+    /**
+     * 设置取款冷却指令
+     *
+     * 功能：设定两次取款之间至少间隔 interval_seconds 秒；传入 0 删除冷却账户，租金退还签名者。
+     * 计为取款的指令：withdraw、withdraw_partial、withdraw_to、withdraw_as_delegate、
+     * close_vault（转出余额时）、transfer_vault_ownership、withdraw_token 与 open_stream
+     *
+     * 安全检查：
+     * 1. 延长间隔随时可以；缩短或删除时，当前冷却必须已经结束（否则 WithdrawTooSoon），
+     *    否则拿到私钥的人可以先删除冷却再连续取款
+     * 2. 冷却账户由 signer 派生，只能修改自己的金库
+     *
+     * 新建时上次取款时间为 0，第一次取款不受限制
+     */
+    pub fn set_cooldown(ctx: Context<SetCooldown>, interval_seconds: u32) -> Result<()> {
+        let cooldown = &mut ctx.accounts.cooldown;
+        let interval = i64::from(interval_seconds);
+        if interval < cooldown.interval {
+            cooldown.ensure_ready(Clock::get()?.unix_timestamp)?;
+        }
+        if interval == 0 {
+            return cooldown.close(ctx.accounts.signer.to_account_info());
+        }
+        cooldown.interval = interval;
+        Ok(())
+    }
+
+    /**
+     * 创建元数据指令
+     *
+     * 功能：为签名者的金库创建元数据账户，记录 UTF-8 标签、备注与创建时间；
+     * 账户按实际内容分配空间，签名者支付租金
+     *
+     * 安全检查：
+     * 1. 标签不超过 MAX_LABEL_LEN 字节、备注不超过 MAX_NOTES_LEN 字节（否则 MetadataTooLong）
+     * 2. 每个金库只有一个元数据账户（已存在时 init 失败），修改使用 update_metadata
+     *
+     * 元数据与金库余额无关，金库为空时也可以创建
+     */
+    pub fn init_metadata(
+        ctx: Context<InitMetadata>,
+        label: String,
+        notes: String,
+    ) -> Result<()> {
+        VaultMetadata::check(&label, &notes)?;
+        let metadata = &mut ctx.accounts.metadata;
+        metadata.created_at = Clock::get()?.unix_timestamp;
+        metadata.label = label;
+        metadata.notes = notes;
+        Ok(())
+    }
+
+    /**
+     * 更新元数据指令
+     *
+     * 功能：替换标签与备注，账户随新内容 realloc：变长时签名者补足租金，变短时多余的租金退回签名者。
+     * created_at 保持不变，长度限制与 init_metadata 相同
+     */
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        label: String,
+        notes: String,
+    ) -> Result<()> {
+        VaultMetadata::check(&label, &notes)?;
+        let metadata = &mut ctx.accounts.metadata;
+        metadata.label = label;
+        metadata.notes = notes;
+        Ok(())
+    }
+
+    /**
+     * 关闭元数据指令
+     *
+     * 功能：删除元数据账户，租金退还签名者；账户结构中的 close 约束完成全部工作
+     */
+    pub fn close_metadata(_ctx: Context<CloseMetadata>) -> Result<()> {
+        Ok(())
+    }
+
+    /**
+     * 子金库归集指令
+     *
+     * 功能：在一次调用中取出多个子金库的全部 lamports，转给签名者，
+     * 省去逐个子金库发交易
+     *
+     * 子金库 ["vault", signer, index] 没有专门的存款指令，也不受全局配置的余额上限约束：
+     * 付款方（或中继者）直接用系统转账把 SOL 转到子金库地址，所有者之后统一归集
+     *
+     * 参数：
+     * - indices: 要归集的子金库序号；对应的子金库按相同顺序作为 remaining_accounts 传入（可写），
+     *   金库设置了共同签名策略时，共同签名者追加在这些子金库之后
+     *
+     * 安全检查：
+     * 1. 每个子金库的地址必须由 ["vault", signer, indices[i]] 派生（否则 InvalidSubVault），
+     *    只能归集签名者自己的子金库
+     * 2. 共同签名策略与取款冷却同样适用，整次归集只算一次取款
+     * 3. 空的子金库直接跳过，但至少要有一个子金库有余额（否则 InvalidAmount）
+     *
+     * 每个取出余额的子金库各发出一条 VaultWithdrawnV2 事件
+     */
+    pub fn sweep<'info>(
+        ctx: Context<'_, '_, '_, 'info, Sweep<'info>>,
+        indices: Vec<u8>,
+    ) -> Result<()> {
+        // ========================================
+        // 步骤 1: 验证账户数量、共同签名与冷却
+        // ========================================
+        require_gte!(
+            ctx.remaining_accounts.len(),
+            indices.len(),
+            ErrorCode::AccountNotEnoughKeys
+        );
+        let now = Clock::get()?.unix_timestamp;
+        VaultPolicy::enforce(&ctx.accounts.policy, ctx.remaining_accounts)?;
+        WithdrawCooldown::record(&ctx.accounts.cooldown, now)?;
+
+        // ========================================
+        // 步骤 2: 逐个校验子金库地址，PDA 签名转给签名者
+        // ========================================
+        let signer_key = ctx.accounts.signer.key();
+        let mut total: u64 = 0;
+        for (index, vault) in indices.iter().zip(ctx.remaining_accounts) {
+            let (address, bump) = Pubkey::find_program_address(
+                &[b"vault", signer_key.as_ref(), &[*index]],
+                &crate::ID,
+            );
+            require_keys_eq!(vault.key(), address, VaultError::InvalidSubVault);
+            let amount = vault.lamports();
+            if amount == 0 {
+                continue;
+            }
+
+            let signer_seeds: &[&[u8]] = &[
+                b"vault",
+                signer_key.as_ref(),
+                &[*index],
+                &[bump]
+            ];
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: vault.clone(),
+                        to: ctx.accounts.signer.to_account_info(),
+                    },
+                    &[signer_seeds]
+                ),
+                amount
+            )?;
+            emit_event(
+                &crate::ID,
+                &ctx.accounts.event_authority,
+                ctx.bumps.event_authority,
+                &VaultWithdrawnV2 {
+                    owner: signer_key.to_bytes(),
+                    vault: address.to_bytes(),
+                    amount,
+                    timestamp: now,
+                },
+            )?;
+            total = total.saturating_add(amount);
+        }
+
+        // ========================================
+        // 步骤 3: 没有取出任何 lamports 视为无效调用
+        // ========================================
+        require_neq!(total, 0, VaultError::InvalidAmount);
+
+        Ok(())
+    }
+
+    /**
+     * 版本查询指令
+     *
+     * 所有训练营程序共用的 get_version（判别器相同），
+     * return data 为版本号与功能位，格式见 crates/blueshift_version
+     *
+     * Anchor 金库没有功能开关账户，enabled 恒为 0；调用方多传的账户会被忽略
+     */
+    pub fn get_version(_ctx: Context<GetVersion>) -> Result<()> {
+        anchor_lang::solana_program::program::set_return_data(&VERSION.encode());
+        Ok(())
+    }
+}
+
+/// 本程序的版本（Cargo.toml 中的 version），没有定义功能位
+pub const VERSION: blueshift_version::Version = blueshift_version::version!(0);
+
+/**
+ * GetVersion 账户结构：不需要任何账户
+ */
+#[derive(Accounts)]
+pub struct GetVersion {}
+
+/**
+ * VaultAction 账户结构
+ * 
+ * 这个结构定义了 deposit、top_up、withdraw 与 withdraw_partial 指令需要的所有账户
+ * 使用相同的结构使代码更简洁、更易维护
+ *
+ * vault_state 放在 system_program 之后，前三个账户的顺序与 Pinocchio 金库（task4）保持一致
+ *
+ * #[event_cpi] 在末尾追加两个账户：event_authority（["__event_authority"] PDA）
+ * 与 program（本程序），发出事件的自调用需要它们
+ */
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VaultAction<'info> {
+    /**
+     * 签名者账户
+     * 
+     * - Signer<'info>: 确保此账户已签署交易
+     * - #[account(mut)]: 标记为可变，因为我们将修改其 lamports 余额
+     * 
+     * 这是金库的所有者，也是唯一可以存取金库资金的人
+     */
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /**
+     * 金库账户（PDA）
+     * 
+     * - SystemAccount<'info>: 系统拥有的账户类型
+     * - #[account(mut)]: 标记为可变，因为我们将修改其 lamports 余额
+     * - seeds: 定义 PDA 的派生种子
+     *   - b"vault": 字符串字面量作为固定种子
+     *   - signer.key().as_ref(): 签名者的公钥作为唯一标识
+     * - bump: 自动找到并验证 bump seed
+     * 
+     * PDA 的优势：
+     * 1. 确定性地址：给定相同的种子，总是生成相同的地址
+     * 2. 程序控制：只有程序可以签署代表 PDA 的交易
+     * 3. 无私钥：PDA 没有对应的私钥，更安全
+     */
+    #[account(
+        mut,
+        seeds = [b"vault", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /**
+     * 系统程序
+     * 
+     * - Program<'info, System>: Solana 的系统程序类型
+     * 
+     * 需要包含系统程序因为我们要使用它的转账指令（CPI）
+     * 系统程序是 Solana 的核心程序，负责创建账户、转账等基本操作
+     */
+    pub system_program: Program<'info, System>,
+
+    /**
+     * 金库状态账户（PDA）
+     *
+     * - seeds: ["vault_state", signer]
+     * - init_if_needed: 首次使用时由 signer 付租金创建；锁定功能上线前存入的金库
+     *   在下一次操作时补建，解锁时间为 0，即不锁定
+     *
+     * 金库本身必须保持为无数据的系统账户才能用 System 程序转账，
+     * 所以解锁时间单独存放在这个账户里
+     */
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + VaultState::INIT_SPACE,
+        seeds = [b"vault_state", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    /**
+     * 全局配置账户（PDA）
+     *
+     * - seeds: ["config"]，地址固定，传错地址无法绕过限制
+     * - 尚未初始化时账户为空，deposit / top_up 不做额外限制；
+     *   初始化后由 GlobalConfig::load 检查所有者与判别器再读取
+     */
+    /// CHECK: 地址由 seeds 校验，内容在 GlobalConfig::load 中校验
+    #[account(
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /**
+     * 共同签名策略账户（PDA）
+     *
+     * - seeds: ["policy", signer]
+     * - 不存在时取款只需所有者签名；存在时由 VaultPolicy::enforce 要求共同签名者
+     *   出现在 remaining_accounts 中并签名。存款类指令不读取它
+     */
+    /// CHECK: 地址由 seeds 校验，内容在 VaultPolicy::load 中校验
+    #[account(
+        seeds = [b"policy", signer.key().as_ref()],
+        bump,
+    )]
+    pub policy: UncheckedAccount<'info>,
+
+    /**
+     * 取款冷却账户（PDA）
+     *
+     * - seeds: ["cooldown", signer]
+     * - 不存在时不限制取款频率；存在时由 WithdrawCooldown::record 检查间隔并记录本次取款时间。
+     *   存款类指令不读取它，但同样要传入（mut，因为取款时要写入）
+     */
+    /// CHECK: 地址由 seeds 校验，内容在 WithdrawCooldown::record 中校验
+    #[account(
+        mut,
+        seeds = [b"cooldown", signer.key().as_ref()],
+        bump,
+    )]
+    pub cooldown: UncheckedAccount<'info>,
+
+    /**
+     * 手续费国库（PDA）
+     *
+     * - seeds: ["treasury"]，全程序只有一个，deposit / top_up 扣下的手续费转到这里
+     * - 取款类指令不使用它，但账户列表相同，同样要传入
+     */
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+}
+
+/**
+ * WithdrawTo 账户结构
+ *
+ * 与 VaultAction 的区别：多了不需要签名的 recipient；
+ * vault_state 可能不存在（锁定功能之前的金库；取款不会创建状态账户）
+ */
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawTo<'info> {
+    /// 金库所有者，必须签名；接收状态账户退还的租金
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// 收款人，可以是任意系统账户（包括尚不存在的地址）
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: 地址由 seeds 校验；存在时由 VaultState::load 检查所有者与判别器
+    #[account(
+        mut,
+        seeds = [b"vault_state", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault_state: UncheckedAccount<'info>,
+
+    /// CHECK: 与 VaultAction 中的 policy 相同
+    #[account(
+        seeds = [b"policy", signer.key().as_ref()],
+        bump,
+    )]
+    pub policy: UncheckedAccount<'info>,
+
+    /// CHECK: 与 VaultAction 中的 cooldown 相同
+    #[account(
+        mut,
+        seeds = [b"cooldown", signer.key().as_ref()],
+        bump,
+    )]
+    pub cooldown: UncheckedAccount<'info>,
+}
+
+/**
+ * CloseVault 账户结构
+ *
+ * 状态账户与流可能不存在，所以都是 UncheckedAccount：地址由 seeds 校验，
+ * 存在时在指令中检查所有者后再读取或关闭
+ */
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    /// 金库所有者，接收全部余额与退还的租金
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: 地址由 seeds 校验；存在时由 VaultState::load 检查所有者与判别器
+    #[account(
+        mut,
+        seeds = [b"vault_state", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault_state: UncheckedAccount<'info>,
+
+    /// CHECK: 地址由 seeds 校验；存在时由 close_if_present 检查所有者后关闭
+    #[account(
+        mut,
+        seeds = [b"stream", signer.key().as_ref()],
+        bump,
+    )]
+    pub stream: UncheckedAccount<'info>,
+
+    /// CHECK: 与 VaultAction 中的 policy 相同
+    #[account(
+        seeds = [b"policy", signer.key().as_ref()],
+        bump,
+    )]
+    pub policy: UncheckedAccount<'info>,
+
+    /// CHECK: 与 VaultAction 中的 cooldown 相同
+    #[account(
+        mut,
+        seeds = [b"cooldown", signer.key().as_ref()],
+        bump,
+    )]
+    pub cooldown: UncheckedAccount<'info>,
+}
+
+/**
+ * TransferVaultOwnership 账户结构
+ *
+ * 在 CloseVault 的基础上多了 new_vault：地址由指令参数 new_owner 派生，
+ * new_owner 不需要签名，也不需要事先存在
+ */
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(new_owner: Pubkey)]
+pub struct TransferVaultOwnership<'info> {
+    /// 原所有者，接收状态账户与流退还的租金
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", new_owner.as_ref()],
+        bump,
+    )]
+    pub new_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: 与 CloseVault 中的 vault_state 相同
+    #[account(
+        mut,
+        seeds = [b"vault_state", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault_state: UncheckedAccount<'info>,
+
+    /// CHECK: 与 CloseVault 中的 stream 相同
+    #[account(
+        mut,
+        seeds = [b"stream", signer.key().as_ref()],
+        bump,
+    )]
+    pub stream: UncheckedAccount<'info>,
+
+    /// CHECK: 与 VaultAction 中的 policy 相同
+    #[account(
+        seeds = [b"policy", signer.key().as_ref()],
+        bump,
+    )]
+    pub policy: UncheckedAccount<'info>,
+
+    /// CHECK: 与 VaultAction 中的 cooldown 相同
+    #[account(
+        mut,
+        seeds = [b"cooldown", signer.key().as_ref()],
+        bump,
+    )]
+    pub cooldown: UncheckedAccount<'info>,
+}
+
+/**
+ * DepositToken 账户结构
+ *
+ * 代币存放在金库 PDA 的 ATA 中：金库 PDA 是代币账户的 authority，
+ * 它本身不需要存在（没有 SOL 存款时余额为 0 也可以）。
+ * token_program 可以是 SPL Token 或 Token-2022，mint 与两个代币账户必须属于同一个
+ */
+#[derive(Accounts)]
+pub struct DepositToken<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 签名者自己的代币账户，不要求是 ATA
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = signer,
+        token::token_program = token_program,
+    )]
+    pub user_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: 与 VaultAction 中的 config 相同，这里只读取暂停开关
+    #[account(
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * WithdrawToken 账户结构
+ *
+ * 收款账户固定为签名者的 ATA，不存在时由签名者付租金创建
+ */
+#[derive(Accounts)]
+pub struct WithdrawToken<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: 地址由 seeds 校验；可能不存在，存在时由 VaultState::load 读取锁定期
+    #[account(
+        seeds = [b"vault_state", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault_state: UncheckedAccount<'info>,
+
+    /// CHECK: 与 VaultAction 中的 policy 相同
+    #[account(
+        seeds = [b"policy", signer.key().as_ref()],
+        bump,
+    )]
+    pub policy: UncheckedAccount<'info>,
+
+    /// CHECK: 与 VaultAction 中的 cooldown 相同
+    #[account(
+        mut,
+        seeds = [b"cooldown", signer.key().as_ref()],
+        bump,
+    )]
+    pub cooldown: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * WrapSol 账户结构
+ *
+ * wSOL 固定使用 SPL Token 的原生 mint（Token-2022 的原生 mint 不在这里处理），
+ * wsol_vault 是金库 PDA 在该 mint 下的 ATA，与 deposit_token 存入 wSOL 时是同一个账户
+ */
+#[derive(Accounts)]
+pub struct WrapSol<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(address = native_mint::ID)]
+    pub native_mint: Account<'info, token::Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = native_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub wsol_vault: Account<'info, token::TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: 与 VaultAction 中的 cooldown 相同
+    #[account(
+        mut,
+        seeds = [b"cooldown", signer.key().as_ref()],
+        bump,
+    )]
+    pub cooldown: UncheckedAccount<'info>,
+}
+
+/**
+ * UnwrapSol 账户结构：wSOL ATA 必须已经存在，关闭后租金退回金库
+ */
+#[derive(Accounts)]
+pub struct UnwrapSol<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(address = native_mint::ID)]
+    pub native_mint: Account<'info, token::Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = native_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub wsol_vault: Account<'info, token::TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: 与 VaultAction 中的 cooldown 相同
+    #[account(
+        mut,
+        seeds = [b"cooldown", signer.key().as_ref()],
+        bump,
+    )]
+    pub cooldown: UncheckedAccount<'info>,
+}
+
+/**
+ * ExtendLock 账户结构：只修改状态账户，不涉及转账
+ */
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_state", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+/**
+ * SetDelegate 账户结构：只有金库所有者可以修改自己的代理人
+ */
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_state", signer.key().as_ref()],
+        bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+/**
+ * DelegateWithdraw 账户结构
+ *
+ * 签名者是代理人，金库与状态账户都由 owner 派生；
+ * vault_state 的约束保证签名者正是 owner 指定的代理人
+ */
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DelegateWithdraw<'info> {
+    /// 代理人，接收金库中的 lamports
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+
+    /// 金库所有者，不需要签名；用于派生 PDA，并接收状态账户退还的租金
+    #[account(mut)]
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_state", owner.key().as_ref()],
+        bump,
+        constraint = vault_state.delegate == Some(delegate.key()) @ VaultError::NotDelegate,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    /// CHECK: owner 的共同签名策略，可能不存在
+    #[account(
+        seeds = [b"policy", owner.key().as_ref()],
+        bump,
+    )]
+    pub policy: UncheckedAccount<'info>,
+
+    /// CHECK: 与 VaultAction 中的 cooldown 相同
+    #[account(
+        mut,
+        seeds = [b"cooldown", owner.key().as_ref()],
+        bump,
+    )]
+    pub cooldown: UncheckedAccount<'info>,
+}
+
+/**
+ * OpenStream 账户结构：所有者签名并支付 Stream 账户的租金
+ */
+#[derive(Accounts)]
+pub struct OpenStream<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Stream::INIT_SPACE,
+        seeds = [b"stream", signer.key().as_ref()],
+        bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: 与 VaultAction 中的 policy 相同
+    #[account(
+        seeds = [b"policy", signer.key().as_ref()],
+        bump,
+    )]
+    pub policy: UncheckedAccount<'info>,
+
+    /// CHECK: 与 VaultAction 中的 cooldown 相同
+    #[account(
+        mut,
+        seeds = [b"cooldown", signer.key().as_ref()],
+        bump,
+    )]
+    pub cooldown: UncheckedAccount<'info>,
+}
+
+/**
+ * ClaimStream 账户结构
+ *
+ * 不需要任何特定账户签名，手续费付款人就是触发者；
+ * 金库、状态账户与流都由 owner 派生
+ */
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimStream<'info> {
+    /// 金库所有者，流关闭时接收退还的租金
+    #[account(mut)]
+    pub owner: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: 地址由 seeds 校验；可能不存在，存在时在指令中检查所有者与判别器后读取锁定期
+    #[account(
+        seeds = [b"vault_state", owner.key().as_ref()],
+        bump,
+    )]
+    pub vault_state: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stream", owner.key().as_ref()],
+        bump,
+        has_one = recipient @ VaultError::WrongRecipient,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    /// CHECK: owner 的取款冷却，与 VaultAction 中的 cooldown 相同
+    #[account(
+        mut,
+        seeds = [b"cooldown", owner.key().as_ref()],
+        bump,
+    )]
+    pub cooldown: UncheckedAccount<'info>,
+}
+
+/**
+ * InitializeConfig 账户结构
+ *
+ * program_data 是本程序在 Upgradeable Loader 下的 ProgramData（[program_id] PDA），
+ * 签名者必须是其中记录的升级权限
+ */
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ VaultError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GlobalConfig::INIT_SPACE,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * UpdateConfig 账户结构：只有配置中记录的管理员可以修改，
+ * update_config、emergency_pause 与 unpause 共用
+ */
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ VaultError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+/**
+ * ManageFees 账户结构：set_fee 与 collect_fees 共用，
+ * 与 UpdateConfig 一样只有管理员可以调用，管理员为 treasury 补足租金或收取手续费
+ */
+#[derive(Accounts)]
+pub struct ManageFees<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ VaultError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * CreatePolicy 账户结构：所有者签名并支付策略账户的租金
+ */
+#[derive(Accounts)]
+pub struct CreatePolicy<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + VaultPolicy::INIT_SPACE,
+        seeds = [b"policy", signer.key().as_ref()],
+        bump,
+    )]
+    pub policy: Account<'info, VaultPolicy>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * UpdatePolicy 账户结构
+ *
+ * 所有者与策略中记录的共同签名者都要签名；策略删除时租金退还所有者
+ */
+#[derive(Accounts)]
+pub struct UpdatePolicy<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub co_signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"policy", signer.key().as_ref()],
+        bump,
+        has_one = co_signer @ VaultError::CoSignerRequired,
+    )]
+    pub policy: Account<'info, VaultPolicy>,
+}
+
+/**
+ * SetCooldown 账户结构：首次设置时由签名者付租金创建冷却账户
+ */
+#[derive(Accounts)]
+pub struct SetCooldown<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + WithdrawCooldown::INIT_SPACE,
+        seeds = [b"cooldown", signer.key().as_ref()],
+        bump,
+    )]
+    pub cooldown: Account<'info, WithdrawCooldown>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * InitMetadata 账户结构：空间由指令参数的实际长度决定
+ */
+#[derive(Accounts)]
+#[instruction(label: String, notes: String)]
+pub struct InitMetadata<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = VaultMetadata::space(&label, &notes),
+        seeds = [b"metadata", signer.key().as_ref()],
+        bump,
+    )]
+    pub metadata: Account<'info, VaultMetadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * UpdateMetadata 账户结构
+ *
+ * realloc 按新内容调整空间，租金差额由签名者补足或退回
+ */
+#[derive(Accounts)]
+#[instruction(label: String, notes: String)]
+pub struct UpdateMetadata<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"metadata", signer.key().as_ref()],
+        bump,
+        realloc = VaultMetadata::space(&label, &notes),
+        realloc::payer = signer,
+        realloc::zero = false,
+    )]
+    pub metadata: Account<'info, VaultMetadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * CloseMetadata 账户结构：close 约束把租金退还签名者
+ */
+#[derive(Accounts)]
+pub struct CloseMetadata<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"metadata", signer.key().as_ref()],
+        bump,
+        close = signer,
+    )]
+    pub metadata: Account<'info, VaultMetadata>,
+}
+
+/**
+ * Sweep 账户结构
+ *
+ * 子金库数量不固定，放在 remaining_accounts 中，由 sweep 按序号逐个校验地址；
+ * 共同签名策略与取款冷却与所有者的主金库共用
+ */
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Sweep<'info> {
+    /// 子金库所有者，接收归集的全部 lamports
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: 与 VaultAction 中的 policy 相同
+    #[account(
+        seeds = [b"policy", signer.key().as_ref()],
+        bump,
+    )]
+    pub policy: UncheckedAccount<'info>,
+
+    /// CHECK: 与 VaultAction 中的 cooldown 相同
+    #[account(
+        mut,
+        seeds = [b"cooldown", signer.key().as_ref()],
+        bump,
+    )]
+    pub cooldown: UncheckedAccount<'info>,
+}
+
+/**
+ * 关闭本程序拥有的账户：lamports 转给 destination，所有者改回 System 程序并清空数据。
+ * 账户不存在（没有数据）时什么都不做
+ *
+ * Anchor 的 `close` 约束只能用于 Account<T>，这里的账户可能不存在，只能手动关闭
+ */
+fn close_if_present<'info>(
+    info: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    if info.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    let total = destination
+        .lamports()
+        .checked_add(info.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **destination.try_borrow_mut_lamports()? = total;
+    **info.try_borrow_mut_lamports()? = 0;
+    info.assign(&System::id());
+    info.resize(0)?;
+    Ok(())
+}
+
+/**
+ * 把 deposit / top_up 扣下的手续费从签名者转入 treasury；手续费为 0 时不发起转账
+ */
+fn pay_deposit_fee(accounts: &VaultAction, fee: u64) -> Result<()> {
+    if fee == 0 {
+        return Ok(());
+    }
+    transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            Transfer {
+                from: accounts.signer.to_account_info(),
+                to: accounts.treasury.to_account_info(),
+            },
+        ),
+        fee,
+    )
+}
+
+/**
+ * 金库状态
+ *
+ * 与金库一一对应，记录最早可以取款的时间与可选的代理人
+ */
+#[account]
+#[derive(InitSpace)]
+pub struct VaultState {
+    /// 解锁时间（unix 秒）；Clock 的 unix_timestamp 达到它之后才能取款
+    pub unlock_timestamp: i64,
+    /// 可以调用 withdraw_as_delegate 的公钥，None 表示没有代理人
+    pub delegate: Option<Pubkey>,
+}
+
+impl VaultState {
+    /// 读取可能不存在的状态账户（锁定功能之前的金库，或已被关闭）；不存在时返回 None
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        Ok(Some(Self::try_deserialize(&mut &info.try_borrow_data()?[..])?))
+    }
+
+    /// 当前时间 `now` 仍早于解锁时间时返回 VaultLocked
+    pub fn ensure_unlocked(&self, now: i64) -> Result<()> {
+        require_gte!(now, self.unlock_timestamp, VaultError::VaultLocked);
+        Ok(())
+    }
+
+    /// 改为更晚（或相同）的解锁时间，提前则返回 LockShortened
+    pub fn extend(&mut self, unlock_timestamp: i64) -> Result<()> {
+        require_gte!(
+            unlock_timestamp,
+            self.unlock_timestamp,
+            VaultError::LockShortened
+        );
+        self.unlock_timestamp = unlock_timestamp;
+        Ok(())
+    }
+}
+
+/**
+ * 流式支付
+ *
+ * 金库余额按 rate_per_second 的速率逐秒释放给 recipient
+ */
+#[account]
+#[derive(InitSpace)]
+pub struct Stream {
+    /// 金库所有者；PDA 无法反推种子，keeper 等链下程序靠它找到对应的金库
+    pub owner: Pubkey,
+    pub recipient: Pubkey,
+    /// 每秒释放的 lamports
+    pub rate_per_second: u64,
+    /// 上次结算的时间（unix 秒），开启时为开启时间
+    pub last_claimed: i64,
+}
+
+impl Stream {
+    /// 截至 `now` 累计尚未支付的 lamports；时钟回拨时为 0，乘积溢出时取 u64::MAX
+    pub fn accrued(&self, now: i64) -> u64 {
+        let elapsed = u64::try_from(now.saturating_sub(self.last_claimed)).unwrap_or(0);
+        self.rate_per_second.saturating_mul(elapsed)
+    }
+}
+
+/**
+ * 全局配置
+ *
+ * 整个程序只有一个，限制首笔存款的最小金额与单个金库的最大余额，并记录是否暂停存款
+ */
+#[account]
+#[derive(InitSpace)]
+pub struct GlobalConfig {
+    /// 可以调用 update_config、emergency_pause 与 unpause 的管理员
+    pub admin: Pubkey,
+    /// 单个金库余额上限（lamports），u64::MAX 表示不限制
+    pub max_vault_balance: u64,
+    /// 首笔存款的最小金额（lamports）；免租金最低限额的要求始终存在
+    pub min_deposit: u64,
+    /// 为 true 时拒绝 deposit 与 top_up，取款照常
+    pub paused: bool,
+    /// deposit / top_up 的手续费率（基点），由 set_fee 修改，初始化时为 0
+    pub fee_bps: u16,
+}
+
+impl GlobalConfig {
+    /// 手续费率上限：10%
+    pub const MAX_FEE_BPS: u16 = 1_000;
+
+    /// 读取 VaultAction 中的配置账户；尚未初始化时返回 None
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        Ok(Some(Self::try_deserialize(&mut &info.try_borrow_data()?[..])?))
+    }
+
+    /// 暂停期间返回 VaultPaused
+    pub fn ensure_active(&self) -> Result<()> {
+        require!(!self.paused, VaultError::VaultPaused);
+        Ok(())
+    }
+
+    /// 向空金库首次存入 `amount`：低于 min_deposit 返回 BelowMinDeposit，超出上限返回 AboveMaxBalance
+    pub fn check_deposit(&self, amount: u64) -> Result<()> {
+        require_gte!(amount, self.min_deposit, VaultError::BelowMinDeposit);
+        self.check_balance(amount)
+    }
+
+    /// 存款后金库余额为 `balance`，超出上限返回 AboveMaxBalance
+    pub fn check_balance(&self, balance: u64) -> Result<()> {
+        require_gte!(self.max_vault_balance, balance, VaultError::AboveMaxBalance);
+        Ok(())
+    }
+
+    /// 存入 `amount` 时扣下的手续费，向下取整
+    pub fn fee(&self, amount: u64) -> u64 {
+        // fee_bps 不超过 10_000，结果不会大于 amount
+        (u128::from(amount) * u128::from(self.fee_bps) / 10_000) as u64
+    }
+
+    /// 同时设置两项限制，最小存款不能高于余额上限
+    pub fn set_limits(&mut self, max_vault_balance: u64, min_deposit: u64) -> Result<()> {
+        require_gte!(max_vault_balance, min_deposit, VaultError::InvalidConfig);
+        self.max_vault_balance = max_vault_balance;
+        self.min_deposit = min_deposit;
+        Ok(())
+    }
+}
+
+/**
+ * 共同签名策略
+ *
+ * 与金库一一对应；账户存在即表示取款需要 co_signer 一同签名
+ */
+#[account]
+#[derive(InitSpace)]
+pub struct VaultPolicy {
+    /// 第二个必需的签名者
+    pub co_signer: Pubkey,
+}
+
+impl VaultPolicy {
+    /// 读取取款指令中的策略账户；尚未创建（或已删除）时返回 None
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        Ok(Some(Self::try_deserialize(&mut &info.try_borrow_data()?[..])?))
+    }
+
+    /// 策略存在时，`signers` 中必须有共同签名者且已签名，否则返回 CoSignerRequired
+    pub fn enforce(info: &AccountInfo, signers: &[AccountInfo]) -> Result<()> {
+        let Some(policy) = Self::load(info)? else {
+            return Ok(());
+        };
+        require!(
+            signers
+                .iter()
+                .any(|account| account.is_signer && account.key() == policy.co_signer),
+            VaultError::CoSignerRequired
+        );
+        Ok(())
+    }
+}
+
+/**
+ * 取款冷却
+ *
+ * 与金库一一对应；账户存在即表示两次取款之间至少间隔 interval 秒
+ */
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawCooldown {
+    /// 两次取款之间的最短间隔（秒）
+    pub interval: i64,
+    /// 上一次取款的 unix_timestamp，从未取款时为 0
+    pub last_withdrawal: i64,
+}
+
+impl WithdrawCooldown {
+    /// 距离上次取款已满 interval 秒；否则在日志中写明还需等待的秒数并返回 WithdrawTooSoon
+    fn ensure_ready(&self, now: i64) -> Result<()> {
+        let ready_at = self.last_withdrawal.saturating_add(self.interval);
+        if now < ready_at {
+            msg!("取款冷却中，还需等待 {} 秒", ready_at - now);
+            return err!(VaultError::WithdrawTooSoon);
+        }
+        Ok(())
+    }
+
+    /**
+     * 取款类指令调用：账户不存在时什么都不做；
+     * 存在时检查冷却并把本次取款时间写回账户
+     */
+    pub fn record(info: &AccountInfo, now: i64) -> Result<()> {
+        if info.data_is_empty() {
+            return Ok(());
+        }
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let mut data = info.try_borrow_mut_data()?;
+        let mut cooldown = Self::try_deserialize(&mut &data[..])?;
+        cooldown.ensure_ready(now)?;
+        cooldown.last_withdrawal = now;
+        cooldown.try_serialize(&mut &mut data[..])
+    }
+}
+
+/**
+ * 金库元数据
+ *
+ * 只供链下显示，程序的其他指令不会读取；长度可变，字段顺序固定为 created_at、label、notes
+ */
+#[account]
+pub struct VaultMetadata {
+    /// 创建时 Clock 的 unix_timestamp，update_metadata 不会修改
+    pub created_at: i64,
+    /// 钱包中显示的名称
+    pub label: String,
+    /// 任意补充说明
+    pub notes: String,
+}
+
+impl VaultMetadata {
+    /// 标签的最大字节数（UTF-8 编码后，而不是字符数）
+    pub const MAX_LABEL_LEN: usize = 32;
+    /// 备注的最大字节数
+    pub const MAX_NOTES_LEN: usize = 256;
+
+    /// 判别器 + created_at + 两个带 u32 长度前缀的字符串
+    pub fn space(label: &str, notes: &str) -> usize {
+        8 + 8 + 4 + label.len() + 4 + notes.len()
+    }
+
+    /// 长度超过上限时返回 MetadataTooLong；String 参数反序列化时已保证是合法的 UTF-8
+    fn check(label: &str, notes: &str) -> Result<()> {
+        require!(
+            label.len() <= Self::MAX_LABEL_LEN && notes.len() <= Self::MAX_NOTES_LEN,
+            VaultError::MetadataTooLong
+        );
+        Ok(())
+    }
+}
+
+/**
+ * 自定义错误枚举
+ * 
+ * 定义程序可能返回的错误类型
+ * #[error_code] 宏会自动为每个错误分配唯一的错误码
+ */
+#[error_code]
+pub enum VaultError {
+    /**
+     * 金库已存在错误
+     * 
+     * 当用户尝试向已有余额的金库存款时触发
+     * 这防止了意外的重复存款；确实要追加时使用 top_up
+     */
+    #[msg("金库已存在，不能重复存款")]
+    VaultAlreadyExists,
+
+    /**
+     * 无效金额错误
+     * 
+     * 可能的情况：
+     * 1. 存款金额小于或等于免租金最低限额
+     * 2. 尝试从空金库取款，或向空金库追加存款
+     * 3. 部分取款或追加存款的金额为 0
+     */
+    #[msg("无效的金额")]
+    InvalidAmount,
+
+    /**
+     * 部分取款后余额不足错误
+     *
+     * withdraw_partial 取款后金库余额会低于免租金最低限额（包括 amount 超过余额）。
+     * 追加在末尾，前两个错误码保持不变
+     */
+    #[msg("取款后金库余额低于免租金限额，全部取出请使用 withdraw")]
+    BelowRentMinimum,
+
+    /**
+     * 金库锁定错误
+     *
+     * 当前时间早于存款时设定（或 extend_lock 延后）的解锁时间
+     */
+    #[msg("金库仍在锁定期内")]
+    VaultLocked,
+
+    /**
+     * 缩短锁定期错误
+     *
+     * extend_lock 传入的解锁时间早于当前记录的解锁时间
+     */
+    #[msg("解锁时间只能延后，不能提前")]
+    LockShortened,
+
+    /**
+     * 代理人错误
+     *
+     * withdraw_as_delegate 的签名者不是金库状态中记录的代理人，或者没有设置代理人
+     */
+    #[msg("签名者不是该金库的代理人")]
+    NotDelegate,
+
+    /**
+     * 收款人错误
+     *
+     * claim_stream 传入的 recipient 与流中记录的收款人不同
+     */
+    #[msg("收款人与流式支付记录的不一致")]
+    WrongRecipient,
+
+    /**
+     * 权限错误
+     *
+     * initialize_config 的签名者不是升级权限，或 update_config / emergency_pause / unpause
+     * 的签名者不是配置中的管理员
+     */
+    #[msg("签名者没有修改全局配置的权限")]
+    Unauthorized,
+
+    /**
+     * 配置错误
+     *
+     * min_deposit 高于 max_vault_balance，任何存款都无法同时满足
+     */
+    #[msg("最小存款不能高于金库余额上限")]
+    InvalidConfig,
+
+    /**
+     * 存款不足错误
+     *
+     * 首笔存款低于全局配置的 min_deposit
+     */
+    #[msg("存款金额低于全局配置的最小存款")]
+    BelowMinDeposit,
+
+    /**
+     * 余额超限错误
+     *
+     * 存款或追加后金库余额超过全局配置的 max_vault_balance
+     */
+    #[msg("存款后金库余额超过全局配置的上限")]
+    AboveMaxBalance,
+
+    /**
+     * 缺少共同签名错误
+     *
+     * 金库设置了共同签名策略，但共同签名者没有签名；
+     * update_policy 传入的 co_signer 与策略中记录的不一致时也返回它
+     */
+    #[msg("该金库需要共同签名者一同签名")]
+    CoSignerRequired,
+
+    /**
+     * 共同签名者错误
+     *
+     * 共同签名者与金库所有者相同，策略形同虚设
+     */
+    #[msg("共同签名者不能是金库所有者")]
+    InvalidCoSigner,
+
+    /**
+     * 暂停错误
+     *
+     * 管理员调用了 emergency_pause，恢复之前不接受 deposit 与 top_up
+     */
+    #[msg("存款已被管理员暂停")]
+    VaultPaused,
+
+    /**
+     * 元数据过长错误
+     *
+     * init_metadata 或 update_metadata 的标签超过 32 字节，或备注超过 256 字节
+     */
+    #[msg("金库标签或备注超出长度上限")]
+    MetadataTooLong,
+
+    /**
+     * 取款冷却错误
+     *
+     * 距上次取款不足冷却间隔，或在冷却期内缩短、删除冷却；
+     * 还需等待的秒数写在错误之前的程序日志中
+     */
+    #[msg("取款过于频繁，冷却时间未到")]
+    WithdrawTooSoon,
+
+    /**
+     * 手续费率错误
+     *
+     * set_fee 的 fee_bps 超过 MAX_FEE_BPS（1000 bps，即 10%）
+     */
+    #[msg("存款手续费率超出上限")]
+    FeeTooHigh,
+
+    /**
+     * 子金库地址错误
+     *
+     * sweep 的 remaining_accounts 中某个账户不是签名者对应序号的子金库
+     */
+    #[msg("账户不是签名者对应序号的子金库")]
+    InvalidSubVault,
 }
\ No newline at end of file
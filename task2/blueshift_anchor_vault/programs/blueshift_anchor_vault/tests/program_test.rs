@@ -0,0 +1,2906 @@
+//! solana-program-test 套件：加载 `anchor build` 生成的 blueshift_anchor_vault.so，
+//! 在真实运行时的租金、时钟与 System 程序 CPI 下校验日志与交易后的账户状态。
+//!
+//! 先在 task2/blueshift_anchor_vault 执行 `anchor build`，再执行 `cargo test -p blueshift_anchor_vault`。
+
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::{
+    associated_token::{
+        self, get_associated_token_address_with_program_id,
+        spl_associated_token_account::instruction::create_associated_token_account,
+    },
+    token::spl_token::{self, native_mint},
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            transfer_fee::{TransferFee, TransferFeeConfig},
+            BaseStateWithExtensionsMut, ExtensionType, StateWithExtensions, StateWithExtensionsMut,
+        },
+    },
+};
+use blueshift_anchor_vault::{
+    accounts, instruction, Stream, VaultError, VaultMetadata, VaultState, ID,
+};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    bpf_loader_upgradeable,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+const DEPOSIT: u64 = 1_000_000_000;
+
+/// 一笔交易的执行结果、程序日志与实际扣除的手续费
+struct Outcome {
+    result: Result<(), TransactionError>,
+    logs: Vec<String>,
+    fee: u64,
+}
+
+impl Outcome {
+    fn logged(&self, line: &str) -> bool {
+        self.logs.iter().any(|log| log.contains(line))
+    }
+
+    fn custom_error(&self) -> Option<u32> {
+        match self.result {
+            Err(TransactionError::InstructionError(0, InstructionError::Custom(code))) => {
+                Some(code)
+            }
+            _ => None,
+        }
+    }
+}
+
+async fn start() -> (ProgramTestContext, Keypair) {
+    // ProgramTest 在 SBF_OUT_DIR 中查找 <程序名>.so
+    std::env::set_var(
+        "SBF_OUT_DIR",
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy"),
+    );
+    let context = ProgramTest::new("blueshift_anchor_vault", ID, None)
+        .start_with_context()
+        .await;
+    let signer = context.payer.insecure_clone();
+    (context, signer)
+}
+
+fn vault_address(signer: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(pdas::vault_pda(&ID.to_bytes(), &signer.to_bytes()).0)
+}
+
+fn vault_state_address(signer: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(pdas::vault_state_pda(&ID.to_bytes(), &signer.to_bytes()).0)
+}
+
+fn config_address() -> Pubkey {
+    Pubkey::new_from_array(pdas::vault_config_pda(&ID.to_bytes()).0)
+}
+
+fn policy_address(owner: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(pdas::policy_pda(&ID.to_bytes(), &owner.to_bytes()).0)
+}
+
+fn cooldown_address(owner: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(pdas::cooldown_pda(&ID.to_bytes(), &owner.to_bytes()).0)
+}
+
+fn treasury_address() -> Pubkey {
+    Pubkey::new_from_array(pdas::treasury_pda(&ID.to_bytes()).0)
+}
+
+fn event_authority() -> Pubkey {
+    Pubkey::new_from_array(pdas::event_authority_pda(&ID.to_bytes()).0)
+}
+
+fn vault_ix(signer: &Pubkey, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::VaultAction {
+            signer: *signer,
+            vault: vault_address(signer),
+            system_program: system_program::ID,
+            vault_state: vault_state_address(signer),
+            config: config_address(),
+            policy: policy_address(signer),
+            cooldown: cooldown_address(signer),
+            treasury: treasury_address(),
+            event_authority: event_authority(),
+            program: ID,
+        }
+        .to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+async fn send(context: &mut ProgramTestContext, signer: &Keypair, ix: Instruction) -> Outcome {
+    send_signed(context, &[signer], ix).await
+}
+
+/// 由多个钱包签名，第一个付手续费
+async fn send_signed(
+    context: &mut ProgramTestContext,
+    signers: &[&Keypair],
+    ix: Instruction,
+) -> Outcome {
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&[ix], Some(&signers[0].pubkey()), signers, blockhash);
+    let fee = context
+        .banks_client
+        .get_fee_for_message(tx.message.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    let processed = context
+        .banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    Outcome {
+        result: processed.result,
+        logs: processed
+            .metadata
+            .map(|meta| meta.log_messages)
+            .unwrap_or_default(),
+        fee,
+    }
+}
+
+async fn lamports(context: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    context.banks_client.get_balance(*address).await.unwrap()
+}
+
+#[tokio::test]
+async fn deposit_then_withdraw_after_slots_pass() {
+    let (mut context, signer) = start().await;
+    let vault = vault_address(&signer.pubkey());
+    let before = lamports(&mut context, &signer.pubkey()).await;
+
+    let deposit = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+    assert!(deposit.logged("Program log: Instruction: Deposit"));
+    // 转账经由 System 程序的 CPI 完成，调用深度为 2
+    assert!(deposit.logged(&format!("Program {} invoke [2]", system_program::ID)));
+    // 随后以 event_authority 签名自调用发出 VaultDepositedV2
+    assert!(deposit.logged(&format!("Program {ID} invoke [2]")));
+
+    let account = context
+        .banks_client
+        .get_account(vault)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.lamports, DEPOSIT);
+    assert_eq!(account.owner, system_program::ID);
+    assert!(account.data.is_empty());
+
+    // 金库高于免租金限额，跨过若干 slot 后余额不变
+    context.warp_to_slot(1_000).unwrap();
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    assert!(clock.slot >= 1_000);
+    assert_eq!(lamports(&mut context, &vault).await, DEPOSIT);
+
+    let withdraw = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::Withdraw {}),
+    )
+    .await;
+    assert_eq!(withdraw.result, Ok(()));
+    assert!(withdraw.logged("Program log: Instruction: Withdraw"));
+
+    // 金库清空后账户被运行时回收，状态账户关闭并退还租金，signer 只损失两笔手续费
+    assert!(context
+        .banks_client
+        .get_account(vault)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(context
+        .banks_client
+        .get_account(vault_state_address(&signer.pubkey()))
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        lamports(&mut context, &signer.pubkey()).await,
+        before - deposit.fee - withdraw.fee
+    );
+}
+
+#[tokio::test]
+async fn deposit_must_exceed_rent_minimum() {
+    let (mut context, signer) = start().await;
+    let minimum = context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(0);
+
+    let at_minimum = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: minimum,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(
+        at_minimum.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+    assert!(at_minimum.logged("Error Code: InvalidAmount"));
+
+    let above_minimum = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: minimum + 1,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(above_minimum.result, Ok(()));
+    assert_eq!(
+        lamports(&mut context, &vault_address(&signer.pubkey())).await,
+        minimum + 1
+    );
+}
+
+#[tokio::test]
+async fn second_deposit_is_rejected() {
+    let (mut context, signer) = start().await;
+    let first = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(first.result, Ok(()));
+
+    let second = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT + 1,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(
+        second.custom_error(),
+        Some(u32::from(VaultError::VaultAlreadyExists))
+    );
+    assert_eq!(
+        lamports(&mut context, &vault_address(&signer.pubkey())).await,
+        DEPOSIT
+    );
+}
+
+#[tokio::test]
+async fn withdraw_from_empty_vault_is_rejected() {
+    let (mut context, signer) = start().await;
+    let withdraw = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::Withdraw {}),
+    )
+    .await;
+    assert_eq!(
+        withdraw.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+}
+
+#[tokio::test]
+async fn partial_withdraw_keeps_vault_rent_exempt() {
+    let (mut context, signer) = start().await;
+    let vault = vault_address(&signer.pubkey());
+    let minimum = context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(0);
+    let deposit = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+
+    let partial = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::WithdrawPartial {
+                amount: DEPOSIT / 4,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(partial.result, Ok(()));
+    assert!(partial.logged("Program log: Instruction: WithdrawPartial"));
+    assert_eq!(lamports(&mut context, &vault).await, DEPOSIT - DEPOSIT / 4);
+
+    // 恰好留下免租金限额可以，再多取 1 lamport 就不行
+    let too_much = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::WithdrawPartial {
+                amount: DEPOSIT - DEPOSIT / 4 - minimum + 1,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(
+        too_much.custom_error(),
+        Some(u32::from(VaultError::BelowRentMinimum))
+    );
+    let exact = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::WithdrawPartial {
+                amount: DEPOSIT - DEPOSIT / 4 - minimum,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(exact.result, Ok(()));
+    assert_eq!(lamports(&mut context, &vault).await, minimum);
+
+    // withdraw 仍然清空金库并回收账户
+    let close = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::Withdraw {}),
+    )
+    .await;
+    assert_eq!(close.result, Ok(()));
+    assert!(context
+        .banks_client
+        .get_account(vault)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn partial_withdraw_rejects_zero_and_empty_vault() {
+    let (mut context, signer) = start().await;
+    let empty = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::WithdrawPartial { amount: 1 }),
+    )
+    .await;
+    assert_eq!(
+        empty.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+
+    let deposit = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+    let zero = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::WithdrawPartial { amount: 0 }),
+    )
+    .await;
+    assert_eq!(
+        zero.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+}
+
+#[tokio::test]
+async fn top_up_adds_to_existing_vault_repeatedly() {
+    let (mut context, signer) = start().await;
+    let vault = vault_address(&signer.pubkey());
+
+    // 空金库不能追加，首笔存款仍然走 deposit
+    let empty = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::TopUp { amount: DEPOSIT }),
+    )
+    .await;
+    assert_eq!(
+        empty.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+
+    let deposit = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+
+    // 同一 blockhash 下内容相同的交易会被当成重复交易，每笔金额都不同
+    let mut expected = DEPOSIT;
+    for amount in [1, 1_000, DEPOSIT / 2] {
+        let top_up = send(
+            &mut context,
+            &signer,
+            vault_ix(&signer.pubkey(), instruction::TopUp { amount }),
+        )
+        .await;
+        assert_eq!(top_up.result, Ok(()));
+        assert!(top_up.logged("Program log: Instruction: TopUp"));
+        assert!(top_up.logged(&format!("Program {ID} invoke [2]")));
+        expected += amount;
+        assert_eq!(lamports(&mut context, &vault).await, expected);
+    }
+
+    let zero = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::TopUp { amount: 0 }),
+    )
+    .await;
+    assert_eq!(
+        zero.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+    // deposit 对已有余额的金库仍然拒绝
+    let again = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT + 1,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(
+        again.custom_error(),
+        Some(u32::from(VaultError::VaultAlreadyExists))
+    );
+
+    // withdraw 一次取回全部追加后的余额，外加关闭状态账户退还的租金
+    let before = lamports(&mut context, &signer.pubkey()).await;
+    let state_rent = lamports(&mut context, &vault_state_address(&signer.pubkey())).await;
+    let withdraw = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::Withdraw {}),
+    )
+    .await;
+    assert_eq!(withdraw.result, Ok(()));
+    assert_eq!(
+        lamports(&mut context, &signer.pubkey()).await,
+        before + expected + state_rent - withdraw.fee
+    );
+}
+
+/// 状态账户中记录的解锁时间
+async fn unlock_timestamp(context: &mut ProgramTestContext, signer: &Pubkey) -> i64 {
+    let account = context
+        .banks_client
+        .get_account(vault_state_address(signer))
+        .await
+        .unwrap()
+        .unwrap();
+    VaultState::try_deserialize(&mut account.data.as_slice())
+        .unwrap()
+        .unlock_timestamp
+}
+
+/// 把时钟推进到 `unix_timestamp`，同时换一个 slot 让后续交易拿到新的 blockhash
+async fn set_time(context: &mut ProgramTestContext, slot: u64, unix_timestamp: i64) {
+    context.warp_to_slot(slot).unwrap();
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+#[tokio::test]
+async fn locked_vault_rejects_withdrawals_until_unlock() {
+    let (mut context, signer) = start().await;
+    let vault = vault_address(&signer.pubkey());
+
+    let deposit = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 3_600,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+    let unlock = unlock_timestamp(&mut context, &signer.pubkey()).await;
+    let now = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    // 记录的是存款那一刻加上锁定秒数
+    assert!(unlock > now && unlock <= now + 3_600);
+
+    let withdraw = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::Withdraw {}),
+    )
+    .await;
+    assert_eq!(
+        withdraw.custom_error(),
+        Some(u32::from(VaultError::VaultLocked))
+    );
+    let partial = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::WithdrawPartial { amount: 1 }),
+    )
+    .await;
+    assert_eq!(
+        partial.custom_error(),
+        Some(u32::from(VaultError::VaultLocked))
+    );
+    // 锁定期内仍然可以追加
+    let top_up = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::TopUp { amount: 1 }),
+    )
+    .await;
+    assert_eq!(top_up.result, Ok(()));
+
+    // 差 1 秒仍然锁定，到点即可取款
+    set_time(&mut context, 100, unlock - 1).await;
+    let early = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::Withdraw {}),
+    )
+    .await;
+    assert_eq!(
+        early.custom_error(),
+        Some(u32::from(VaultError::VaultLocked))
+    );
+
+    set_time(&mut context, 200, unlock).await;
+    let partial = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::WithdrawPartial { amount: 1 }),
+    )
+    .await;
+    assert_eq!(partial.result, Ok(()));
+    let withdraw = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::Withdraw {}),
+    )
+    .await;
+    assert_eq!(withdraw.result, Ok(()));
+    assert_eq!(lamports(&mut context, &vault).await, 0);
+    assert!(context
+        .banks_client
+        .get_account(vault_state_address(&signer.pubkey()))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn redeposit_keeps_existing_lock() {
+    let (mut context, signer) = start().await;
+    let vault = vault_address(&signer.pubkey());
+    let deposit_ix = |lock_seconds| {
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds,
+            },
+        )
+    };
+
+    let locked = send(&mut context, &signer, deposit_ix(3_600)).await;
+    assert_eq!(locked.result, Ok(()));
+    let unlock = unlock_timestamp(&mut context, &signer.pubkey()).await;
+
+    // 金库被清空而状态账户仍在：直接把金库改成空的系统账户
+    context.set_account(&vault, &Account::default().into());
+    context.warp_to_slot(100).unwrap();
+
+    // 用 lock_seconds = 0 重新存款不能提前解锁
+    let again = send(&mut context, &signer, deposit_ix(0)).await;
+    assert_eq!(again.result, Ok(()));
+    assert_eq!(
+        unlock_timestamp(&mut context, &signer.pubkey()).await,
+        unlock
+    );
+    let withdraw = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::Withdraw {}),
+    )
+    .await;
+    assert_eq!(
+        withdraw.custom_error(),
+        Some(u32::from(VaultError::VaultLocked))
+    );
+    assert_eq!(lamports(&mut context, &vault).await, DEPOSIT);
+}
+
+#[tokio::test]
+async fn extend_lock_only_moves_unlock_later() {
+    let (mut context, signer) = start().await;
+    let extend_ix = |unlock_timestamp| Instruction {
+        program_id: ID,
+        accounts: accounts::ExtendLock {
+            signer: signer.pubkey(),
+            vault_state: vault_state_address(&signer.pubkey()),
+        }
+        .to_account_metas(None),
+        data: instruction::ExtendLock { unlock_timestamp }.data(),
+    };
+    // 还没有存款时状态账户不存在
+    let missing = send(&mut context, &signer, extend_ix(1)).await;
+    assert!(missing.result.is_err());
+
+    let deposit = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 60,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+
+    let unlock = unlock_timestamp(&mut context, &signer.pubkey()).await;
+    let shorter = send(&mut context, &signer, extend_ix(unlock - 1)).await;
+    assert_eq!(
+        shorter.custom_error(),
+        Some(u32::from(VaultError::LockShortened))
+    );
+    let longer = send(&mut context, &signer, extend_ix(unlock + 7_200)).await;
+    assert_eq!(longer.result, Ok(()));
+    assert!(longer.logged("Program log: Instruction: ExtendLock"));
+    assert_eq!(
+        unlock_timestamp(&mut context, &signer.pubkey()).await,
+        unlock + 7_200
+    );
+
+    // 原来的解锁时间已过，但延长后的锁仍然生效
+    set_time(&mut context, 100, unlock + 3_600).await;
+    let withdraw = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::Withdraw {}),
+    )
+    .await;
+    assert_eq!(
+        withdraw.custom_error(),
+        Some(u32::from(VaultError::VaultLocked))
+    );
+}
+
+#[tokio::test]
+async fn delegate_can_sweep_vault_to_itself() {
+    let (mut context, owner) = start().await;
+    let (recovery, stranger) = (Keypair::new(), Keypair::new());
+    let vault = vault_address(&owner.pubkey());
+    let state = vault_state_address(&owner.pubkey());
+    // 代理人与第三方各自支付手续费
+    for (wallet, lamports) in [(&recovery, 10_000_000), (&stranger, 10_000_001)] {
+        let fund = send(
+            &mut context,
+            &owner,
+            system_instruction::transfer(&owner.pubkey(), &wallet.pubkey(), lamports),
+        )
+        .await;
+        assert_eq!(fund.result, Ok(()));
+    }
+    let deposit = send(
+        &mut context,
+        &owner,
+        vault_ix(
+            &owner.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+
+    let delegate_ix = |delegate: &Keypair| Instruction {
+        program_id: ID,
+        accounts: accounts::DelegateWithdraw {
+            delegate: delegate.pubkey(),
+            owner: owner.pubkey(),
+            vault,
+            system_program: system_program::ID,
+            vault_state: state,
+            policy: policy_address(&owner.pubkey()),
+            cooldown: cooldown_address(&owner.pubkey()),
+            event_authority: event_authority(),
+            program: ID,
+        }
+        .to_account_metas(None),
+        data: instruction::WithdrawAsDelegate {}.data(),
+    };
+    let set_delegate_ix = |delegate: Option<Pubkey>| Instruction {
+        program_id: ID,
+        accounts: accounts::SetDelegate {
+            signer: owner.pubkey(),
+            vault_state: state,
+        }
+        .to_account_metas(None),
+        data: instruction::SetDelegate { delegate }.data(),
+    };
+
+    // 没有设置代理人时谁都不能代取
+    let unset = send(&mut context, &recovery, delegate_ix(&recovery)).await;
+    assert_eq!(
+        unset.custom_error(),
+        Some(u32::from(VaultError::NotDelegate))
+    );
+
+    let set = send(
+        &mut context,
+        &owner,
+        set_delegate_ix(Some(recovery.pubkey())),
+    )
+    .await;
+    assert_eq!(set.result, Ok(()));
+    let stranger_try = send(&mut context, &stranger, delegate_ix(&stranger)).await;
+    assert_eq!(
+        stranger_try.custom_error(),
+        Some(u32::from(VaultError::NotDelegate))
+    );
+
+    let owner_before = lamports(&mut context, &owner.pubkey()).await;
+    let state_rent = lamports(&mut context, &state).await;
+    let recovery_before = lamports(&mut context, &recovery.pubkey()).await;
+    context.warp_to_slot(100).unwrap();
+    let sweep = send(&mut context, &recovery, delegate_ix(&recovery)).await;
+    assert_eq!(sweep.result, Ok(()));
+    assert!(sweep.logged("Program log: Instruction: WithdrawAsDelegate"));
+    assert!(sweep.logged(&format!("Program {ID} invoke [2]")));
+
+    // 余额归代理人，状态账户的租金退还 owner
+    assert_eq!(
+        lamports(&mut context, &recovery.pubkey()).await,
+        recovery_before + DEPOSIT - sweep.fee
+    );
+    assert_eq!(
+        lamports(&mut context, &owner.pubkey()).await,
+        owner_before + state_rent
+    );
+    assert_eq!(lamports(&mut context, &vault).await, 0);
+    assert!(context
+        .banks_client
+        .get_account(state)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn cleared_delegate_loses_access() {
+    let (mut context, owner) = start().await;
+    let recovery = Keypair::new();
+    let fund = send(
+        &mut context,
+        &owner,
+        system_instruction::transfer(&owner.pubkey(), &recovery.pubkey(), 10_000_000),
+    )
+    .await;
+    assert_eq!(fund.result, Ok(()));
+    let deposit = send(
+        &mut context,
+        &owner,
+        vault_ix(
+            &owner.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+
+    let state = vault_state_address(&owner.pubkey());
+    for delegate in [Some(recovery.pubkey()), None] {
+        let set = send(
+            &mut context,
+            &owner,
+            Instruction {
+                program_id: ID,
+                accounts: accounts::SetDelegate {
+                    signer: owner.pubkey(),
+                    vault_state: state,
+                }
+                .to_account_metas(None),
+                data: instruction::SetDelegate { delegate }.data(),
+            },
+        )
+        .await;
+        assert_eq!(set.result, Ok(()));
+    }
+
+    let sweep = send(
+        &mut context,
+        &recovery,
+        Instruction {
+            program_id: ID,
+            accounts: accounts::DelegateWithdraw {
+                delegate: recovery.pubkey(),
+                owner: owner.pubkey(),
+                vault: vault_address(&owner.pubkey()),
+                system_program: system_program::ID,
+                vault_state: state,
+                policy: policy_address(&owner.pubkey()),
+                cooldown: cooldown_address(&owner.pubkey()),
+                event_authority: event_authority(),
+                program: ID,
+            }
+            .to_account_metas(None),
+            data: instruction::WithdrawAsDelegate {}.data(),
+        },
+    )
+    .await;
+    assert_eq!(
+        sweep.custom_error(),
+        Some(u32::from(VaultError::NotDelegate))
+    );
+    assert_eq!(
+        lamports(&mut context, &vault_address(&owner.pubkey())).await,
+        DEPOSIT
+    );
+}
+
+fn withdraw_to_ix(signer: &Pubkey, recipient: Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::WithdrawTo {
+            signer: *signer,
+            recipient,
+            vault: vault_address(signer),
+            system_program: system_program::ID,
+            vault_state: vault_state_address(signer),
+            policy: policy_address(signer),
+            cooldown: cooldown_address(signer),
+            event_authority: event_authority(),
+            program: ID,
+        }
+        .to_account_metas(None),
+        data: instruction::WithdrawTo {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn withdraw_to_pays_recipient_and_refunds_state_rent() {
+    let (mut context, signer) = start().await;
+    let recipient = Pubkey::new_unique();
+    let vault = vault_address(&signer.pubkey());
+    let state = vault_state_address(&signer.pubkey());
+
+    // 还没有存款：金库为空
+    let missing = send(
+        &mut context,
+        &signer,
+        withdraw_to_ix(&signer.pubkey(), recipient),
+    )
+    .await;
+    assert_eq!(
+        missing.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+
+    let deposit = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+
+    let before = lamports(&mut context, &signer.pubkey()).await;
+    let state_rent = lamports(&mut context, &state).await;
+    context.warp_to_slot(100).unwrap();
+    let withdraw = send(
+        &mut context,
+        &signer,
+        withdraw_to_ix(&signer.pubkey(), recipient),
+    )
+    .await;
+    assert_eq!(withdraw.result, Ok(()));
+    assert!(withdraw.logged("Program log: Instruction: WithdrawTo"));
+
+    // 收款人此前不存在，收到金库全部余额；签名者只拿回状态账户的租金
+    assert_eq!(lamports(&mut context, &recipient).await, DEPOSIT);
+    assert_eq!(
+        lamports(&mut context, &signer.pubkey()).await,
+        before + state_rent - withdraw.fee
+    );
+    assert_eq!(lamports(&mut context, &vault).await, 0);
+    assert!(context
+        .banks_client
+        .get_account(state)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn withdraw_to_accepts_vault_without_state() {
+    let (mut context, signer) = start().await;
+    let recipient = Pubkey::new_unique();
+    let vault = vault_address(&signer.pubkey());
+
+    // 锁定功能之前的金库只有 lamports，没有状态账户
+    let fund = send(
+        &mut context,
+        &signer,
+        system_instruction::transfer(&signer.pubkey(), &vault, DEPOSIT),
+    )
+    .await;
+    assert_eq!(fund.result, Ok(()));
+
+    let withdraw = send(
+        &mut context,
+        &signer,
+        withdraw_to_ix(&signer.pubkey(), recipient),
+    )
+    .await;
+    assert_eq!(withdraw.result, Ok(()));
+    assert_eq!(lamports(&mut context, &recipient).await, DEPOSIT);
+    assert_eq!(lamports(&mut context, &vault).await, 0);
+    assert!(context
+        .banks_client
+        .get_account(vault_state_address(&signer.pubkey()))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+fn close_vault_ix(signer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CloseVault {
+            signer: *signer,
+            vault: vault_address(signer),
+            system_program: system_program::ID,
+            vault_state: vault_state_address(signer),
+            stream: stream_address(signer),
+            policy: policy_address(signer),
+            cooldown: cooldown_address(signer),
+            event_authority: event_authority(),
+            program: ID,
+        }
+        .to_account_metas(None),
+        data: instruction::CloseVault {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn close_vault_reclaims_every_account() {
+    let (mut context, signer) = start().await;
+    let owner = signer.pubkey();
+
+    // 什么都没有时没有可关闭的内容
+    let empty = send(&mut context, &signer, close_vault_ix(&owner)).await;
+    assert_eq!(
+        empty.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+
+    let deposit = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &owner,
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+    let open = send(
+        &mut context,
+        &signer,
+        open_stream_ix(&owner, Pubkey::new_unique(), 1),
+    )
+    .await;
+    assert_eq!(open.result, Ok(()));
+
+    let rent = lamports(&mut context, &vault_state_address(&owner)).await
+        + lamports(&mut context, &stream_address(&owner)).await;
+    let before = lamports(&mut context, &owner).await;
+    let close = send(&mut context, &signer, close_vault_ix(&owner)).await;
+    assert_eq!(close.result, Ok(()));
+
+    // 余额与两个附属账户的租金全部回到签名者，三个账户都不复存在
+    assert_eq!(
+        lamports(&mut context, &owner).await,
+        before + DEPOSIT + rent - close.fee
+    );
+    for address in [
+        vault_address(&owner),
+        vault_state_address(&owner),
+        stream_address(&owner),
+    ] {
+        assert!(context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    // 关闭后可以重新存款
+    let reopen = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &owner,
+            instruction::Deposit {
+                amount: DEPOSIT + 1,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(reopen.result, Ok(()));
+}
+
+fn transfer_vault_ownership_ix(signer: &Pubkey, new_owner: Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::TransferVaultOwnership {
+            signer: *signer,
+            vault: vault_address(signer),
+            new_vault: vault_address(&new_owner),
+            system_program: system_program::ID,
+            vault_state: vault_state_address(signer),
+            stream: stream_address(signer),
+            policy: policy_address(signer),
+            cooldown: cooldown_address(signer),
+            event_authority: event_authority(),
+            program: ID,
+        }
+        .to_account_metas(None),
+        data: instruction::TransferVaultOwnership { new_owner }.data(),
+    }
+}
+
+#[tokio::test]
+async fn ownership_transfer_moves_the_whole_vault() {
+    let (mut context, signer) = start().await;
+    let owner = signer.pubkey();
+    let new_owner = Pubkey::new_unique();
+
+    let deposit = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &owner,
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 3_600,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+
+    // 锁定中的资金不能借转移提前取出
+    let locked = send(
+        &mut context,
+        &signer,
+        transfer_vault_ownership_ix(&owner, new_owner),
+    )
+    .await;
+    assert_eq!(
+        locked.custom_error(),
+        Some(u32::from(VaultError::VaultLocked))
+    );
+    let unlock = unlock_timestamp(&mut context, &owner).await;
+    set_time(&mut context, 100, unlock).await;
+
+    // 转给自己时目标金库就是原金库，不为空
+    let to_self = send(
+        &mut context,
+        &signer,
+        transfer_vault_ownership_ix(&owner, owner),
+    )
+    .await;
+    assert_eq!(
+        to_self.custom_error(),
+        Some(u32::from(VaultError::VaultAlreadyExists))
+    );
+
+    let state_rent = lamports(&mut context, &vault_state_address(&owner)).await;
+    let before = lamports(&mut context, &owner).await;
+    let moved = send(
+        &mut context,
+        &signer,
+        transfer_vault_ownership_ix(&owner, new_owner),
+    )
+    .await;
+    assert_eq!(moved.result, Ok(()));
+    assert_eq!(
+        lamports(&mut context, &vault_address(&new_owner)).await,
+        DEPOSIT
+    );
+    assert_eq!(lamports(&mut context, &vault_address(&owner)).await, 0);
+    assert_eq!(
+        lamports(&mut context, &owner).await,
+        before + state_rent - moved.fee
+    );
+    assert!(context
+        .banks_client
+        .get_account(vault_state_address(&owner))
+        .await
+        .unwrap()
+        .is_none());
+
+    // 重新存款后也不能转入已有余额的金库
+    let refill = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &owner,
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(refill.result, Ok(()));
+    // 换一个 blockhash，避免与上面成功的转移成为同一笔交易
+    set_time(&mut context, 200, unlock).await;
+    let occupied = send(
+        &mut context,
+        &signer,
+        transfer_vault_ownership_ix(&owner, new_owner),
+    )
+    .await;
+    assert_eq!(
+        occupied.custom_error(),
+        Some(u32::from(VaultError::VaultAlreadyExists))
+    );
+}
+
+fn stream_address(owner: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(pdas::stream_pda(&ID.to_bytes(), &owner.to_bytes()).0)
+}
+
+fn open_stream_ix(owner: &Pubkey, recipient: Pubkey, rate_per_second: u64) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::OpenStream {
+            signer: *owner,
+            stream: stream_address(owner),
+            system_program: system_program::ID,
+            policy: policy_address(owner),
+            cooldown: cooldown_address(owner),
+        }
+        .to_account_metas(None),
+        data: instruction::OpenStream {
+            recipient,
+            rate_per_second,
+        }
+        .data(),
+    }
+}
+
+fn claim_stream_ix(owner: &Pubkey, recipient: Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::ClaimStream {
+            owner: *owner,
+            recipient,
+            vault: vault_address(owner),
+            system_program: system_program::ID,
+            vault_state: vault_state_address(owner),
+            stream: stream_address(owner),
+            cooldown: cooldown_address(owner),
+            event_authority: event_authority(),
+            program: ID,
+        }
+        .to_account_metas(None),
+        data: instruction::ClaimStream {}.data(),
+    }
+}
+
+async fn stream_opened_at(context: &mut ProgramTestContext, owner: &Pubkey) -> i64 {
+    let account = context
+        .banks_client
+        .get_account(stream_address(owner))
+        .await
+        .unwrap()
+        .unwrap();
+    Stream::try_deserialize(&mut account.data.as_slice())
+        .unwrap()
+        .last_claimed
+}
+
+#[tokio::test]
+async fn stream_releases_accrued_lamports_until_drained() {
+    const RATE: u64 = 1_000_000;
+    let (mut context, owner) = start().await;
+    let (recipient, cranker) = (Pubkey::new_unique(), Keypair::new());
+    let vault = vault_address(&owner.pubkey());
+    let minimum = context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(0);
+    let fund = send(
+        &mut context,
+        &owner,
+        system_instruction::transfer(&owner.pubkey(), &cranker.pubkey(), 10_000_000),
+    )
+    .await;
+    assert_eq!(fund.result, Ok(()));
+    let deposit = send(
+        &mut context,
+        &owner,
+        vault_ix(
+            &owner.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+
+    let zero_rate = send(
+        &mut context,
+        &owner,
+        open_stream_ix(&owner.pubkey(), recipient, 0),
+    )
+    .await;
+    assert_eq!(
+        zero_rate.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+    let open = send(
+        &mut context,
+        &owner,
+        open_stream_ix(&owner.pubkey(), recipient, RATE),
+    )
+    .await;
+    assert_eq!(open.result, Ok(()));
+    let start_time = stream_opened_at(&mut context, &owner.pubkey()).await;
+
+    // 任何人都可以结算，但收款人必须与流中记录的一致
+    set_time(&mut context, 100, start_time + 10).await;
+    let wrong = send(
+        &mut context,
+        &cranker,
+        claim_stream_ix(&owner.pubkey(), Pubkey::new_unique()),
+    )
+    .await;
+    assert_eq!(
+        wrong.custom_error(),
+        Some(u32::from(VaultError::WrongRecipient))
+    );
+    let claim = send(
+        &mut context,
+        &cranker,
+        claim_stream_ix(&owner.pubkey(), recipient),
+    )
+    .await;
+    assert_eq!(claim.result, Ok(()));
+    assert!(claim.logged("Program log: Instruction: ClaimStream"));
+    assert_eq!(lamports(&mut context, &recipient).await, 10 * RATE);
+    assert_eq!(lamports(&mut context, &vault).await, DEPOSIT - 10 * RATE);
+
+    // 同一秒内再次结算没有累计金额（换一个付款人，避免与上一笔交易重复）
+    let again = send(
+        &mut context,
+        &owner,
+        claim_stream_ix(&owner.pubkey(), recipient),
+    )
+    .await;
+    assert_eq!(
+        again.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+
+    // 累计金额超过可用余额：支付到只剩免租金限额为止，流关闭并把租金退还所有者
+    let owner_before = lamports(&mut context, &owner.pubkey()).await;
+    let stream_rent = lamports(&mut context, &stream_address(&owner.pubkey())).await;
+    set_time(&mut context, 200, start_time + 10_000).await;
+    let drain = send(
+        &mut context,
+        &cranker,
+        claim_stream_ix(&owner.pubkey(), recipient),
+    )
+    .await;
+    assert_eq!(drain.result, Ok(()));
+    assert_eq!(lamports(&mut context, &vault).await, minimum);
+    assert_eq!(lamports(&mut context, &recipient).await, DEPOSIT - minimum);
+    assert_eq!(
+        lamports(&mut context, &owner.pubkey()).await,
+        owner_before + stream_rent
+    );
+    assert!(context
+        .banks_client
+        .get_account(stream_address(&owner.pubkey()))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn locked_vault_holds_stream_until_unlock() {
+    const RATE: u64 = 1_000_000;
+    let (mut context, owner) = start().await;
+    let recipient = Pubkey::new_unique();
+    let deposit = send(
+        &mut context,
+        &owner,
+        vault_ix(
+            &owner.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 100,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+    let unlock = unlock_timestamp(&mut context, &owner.pubkey()).await;
+    let open = send(
+        &mut context,
+        &owner,
+        open_stream_ix(&owner.pubkey(), recipient, RATE),
+    )
+    .await;
+    assert_eq!(open.result, Ok(()));
+    let start_time = stream_opened_at(&mut context, &owner.pubkey()).await;
+
+    set_time(&mut context, 100, unlock - 1).await;
+    let locked = send(
+        &mut context,
+        &owner,
+        claim_stream_ix(&owner.pubkey(), recipient),
+    )
+    .await;
+    assert_eq!(
+        locked.custom_error(),
+        Some(u32::from(VaultError::VaultLocked))
+    );
+
+    // 锁定期内累计的金额在解锁后一并支付
+    set_time(&mut context, 200, unlock + 5).await;
+    let claim = send(
+        &mut context,
+        &owner,
+        claim_stream_ix(&owner.pubkey(), recipient),
+    )
+    .await;
+    assert_eq!(claim.result, Ok(()));
+    assert_eq!(
+        lamports(&mut context, &recipient).await,
+        (unlock + 5 - start_time) as u64 * RATE
+    );
+}
+
+/// 把本程序 ProgramData 中的升级权限改为 `authority`；账户不存在时只写入元数据部分
+async fn set_upgrade_authority(context: &mut ProgramTestContext, authority: &Pubkey) {
+    let address = Pubkey::new_from_array(pdas::program_data_pda(&ID.to_bytes()).0);
+    let mut account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap_or_else(|| Account {
+            lamports: 1_000_000_000,
+            data: vec![0; 45],
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        });
+    // UpgradeableLoaderState::ProgramData：u32 枚举序号 3、u64 slot、Option<Pubkey> 升级权限
+    account.data[..4].copy_from_slice(&3u32.to_le_bytes());
+    account.data[12] = 1;
+    account.data[13..45].copy_from_slice(authority.as_ref());
+    context.set_account(&address, &account.into());
+}
+
+fn initialize_config_ix(
+    authority: &Pubkey,
+    admin: Pubkey,
+    max_vault_balance: u64,
+    min_deposit: u64,
+) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::InitializeConfig {
+            authority: *authority,
+            program_data: Pubkey::new_from_array(pdas::program_data_pda(&ID.to_bytes()).0),
+            config: config_address(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::InitializeConfig {
+            admin,
+            max_vault_balance,
+            min_deposit,
+        }
+        .data(),
+    }
+}
+
+fn update_config_ix(admin: &Pubkey, max_vault_balance: u64, min_deposit: u64) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateConfig {
+            admin: *admin,
+            config: config_address(),
+        }
+        .to_account_metas(None),
+        data: instruction::UpdateConfig {
+            max_vault_balance,
+            min_deposit,
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn config_limits_deposits_and_top_ups() {
+    let (mut context, signer) = start().await;
+    let authority = Keypair::new();
+    let fund = send(
+        &mut context,
+        &signer,
+        system_instruction::transfer(&signer.pubkey(), &authority.pubkey(), 100_000_000),
+    )
+    .await;
+    assert_eq!(fund.result, Ok(()));
+    set_upgrade_authority(&mut context, &authority.pubkey()).await;
+
+    // 只有升级权限可以初始化；管理员可以是另一个地址
+    let stranger = send(
+        &mut context,
+        &signer,
+        initialize_config_ix(&signer.pubkey(), signer.pubkey(), 2 * DEPOSIT, DEPOSIT / 2),
+    )
+    .await;
+    assert_eq!(
+        stranger.custom_error(),
+        Some(u32::from(VaultError::Unauthorized))
+    );
+    let inverted = send(
+        &mut context,
+        &authority,
+        initialize_config_ix(&authority.pubkey(), signer.pubkey(), 1, 2),
+    )
+    .await;
+    assert_eq!(
+        inverted.custom_error(),
+        Some(u32::from(VaultError::InvalidConfig))
+    );
+    let init = send(
+        &mut context,
+        &authority,
+        initialize_config_ix(
+            &authority.pubkey(),
+            signer.pubkey(),
+            2 * DEPOSIT,
+            DEPOSIT / 2,
+        ),
+    )
+    .await;
+    assert_eq!(init.result, Ok(()));
+    let again = send(
+        &mut context,
+        &authority,
+        initialize_config_ix(&authority.pubkey(), signer.pubkey(), 2 * DEPOSIT, 0),
+    )
+    .await;
+    assert!(again.result.is_err());
+
+    let deposit = |amount| {
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount,
+                lock_seconds: 0,
+            },
+        )
+    };
+    let too_small = send(&mut context, &signer, deposit(DEPOSIT / 4)).await;
+    assert_eq!(
+        too_small.custom_error(),
+        Some(u32::from(VaultError::BelowMinDeposit))
+    );
+    let too_large = send(&mut context, &signer, deposit(2 * DEPOSIT + 1)).await;
+    assert_eq!(
+        too_large.custom_error(),
+        Some(u32::from(VaultError::AboveMaxBalance))
+    );
+    let ok = send(&mut context, &signer, deposit(DEPOSIT)).await;
+    assert_eq!(ok.result, Ok(()));
+
+    // 追加不受最小存款限制，但余额不能超过上限
+    let over = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::TopUp {
+                amount: DEPOSIT + 1,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(
+        over.custom_error(),
+        Some(u32::from(VaultError::AboveMaxBalance))
+    );
+    let up_to_cap = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::TopUp { amount: DEPOSIT }),
+    )
+    .await;
+    assert_eq!(up_to_cap.result, Ok(()));
+
+    // 升级权限不是管理员，不能修改；管理员放开上限后可以继续追加
+    let not_admin = send(
+        &mut context,
+        &authority,
+        update_config_ix(&authority.pubkey(), u64::MAX, 0),
+    )
+    .await;
+    assert_eq!(
+        not_admin.custom_error(),
+        Some(u32::from(VaultError::Unauthorized))
+    );
+    let update = send(
+        &mut context,
+        &signer,
+        update_config_ix(&signer.pubkey(), u64::MAX, 0),
+    )
+    .await;
+    assert_eq!(update.result, Ok(()));
+    let top_up = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::TopUp { amount: 1 }),
+    )
+    .await;
+    assert_eq!(top_up.result, Ok(()));
+    assert_eq!(
+        lamports(&mut context, &vault_address(&signer.pubkey())).await,
+        2 * DEPOSIT + 1
+    );
+}
+
+fn pause_ix(admin: &Pubkey, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateConfig {
+            admin: *admin,
+            config: config_address(),
+        }
+        .to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+#[tokio::test]
+async fn pause_blocks_deposits_but_not_withdrawals() {
+    let (mut context, signer) = start().await;
+    set_upgrade_authority(&mut context, &signer.pubkey()).await;
+    let init = send(
+        &mut context,
+        &signer,
+        initialize_config_ix(&signer.pubkey(), signer.pubkey(), u64::MAX, 0),
+    )
+    .await;
+    assert_eq!(init.result, Ok(()));
+    let deposit = |amount| {
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount,
+                lock_seconds: 0,
+            },
+        )
+    };
+    assert_eq!(
+        send(&mut context, &signer, deposit(DEPOSIT)).await.result,
+        Ok(())
+    );
+
+    let pause = send(
+        &mut context,
+        &signer,
+        pause_ix(&signer.pubkey(), instruction::EmergencyPause {}),
+    )
+    .await;
+    assert_eq!(pause.result, Ok(()));
+    assert!(pause.logged("存款已暂停"));
+
+    // 暂停期间不能追加或新存，但可以取回
+    let top_up = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::TopUp { amount: 1 }),
+    )
+    .await;
+    assert_eq!(
+        top_up.custom_error(),
+        Some(u32::from(VaultError::VaultPaused))
+    );
+    let withdraw = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::Withdraw {}),
+    )
+    .await;
+    assert_eq!(withdraw.result, Ok(()));
+    assert_eq!(
+        lamports(&mut context, &vault_address(&signer.pubkey())).await,
+        0
+    );
+    let again = send(&mut context, &signer, deposit(DEPOSIT + 1)).await;
+    assert_eq!(
+        again.custom_error(),
+        Some(u32::from(VaultError::VaultPaused))
+    );
+
+    let unpause = send(
+        &mut context,
+        &signer,
+        pause_ix(&signer.pubkey(), instruction::Unpause {}),
+    )
+    .await;
+    assert_eq!(unpause.result, Ok(()));
+    assert_eq!(
+        send(&mut context, &signer, deposit(DEPOSIT + 2))
+            .await
+            .result,
+        Ok(())
+    );
+}
+
+fn fee_ix(admin: &Pubkey, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::ManageFees {
+            admin: *admin,
+            config: config_address(),
+            treasury: treasury_address(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+#[tokio::test]
+async fn deposit_fee_is_forwarded_to_treasury() {
+    let (mut context, signer) = start().await;
+    let minimum = context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(0);
+    set_upgrade_authority(&mut context, &signer.pubkey()).await;
+    let init = send(
+        &mut context,
+        &signer,
+        initialize_config_ix(&signer.pubkey(), signer.pubkey(), u64::MAX, 0),
+    )
+    .await;
+    assert_eq!(init.result, Ok(()));
+
+    // 只有管理员可以设置，费率不能超过 10%
+    let stranger = Keypair::new();
+    let fund = send(
+        &mut context,
+        &signer,
+        system_instruction::transfer(&signer.pubkey(), &stranger.pubkey(), 100_000_000),
+    )
+    .await;
+    assert_eq!(fund.result, Ok(()));
+    let not_admin = send(
+        &mut context,
+        &stranger,
+        fee_ix(&stranger.pubkey(), instruction::SetFee { fee_bps: 100 }),
+    )
+    .await;
+    assert_eq!(
+        not_admin.custom_error(),
+        Some(u32::from(VaultError::Unauthorized))
+    );
+    let too_high = send(
+        &mut context,
+        &signer,
+        fee_ix(&signer.pubkey(), instruction::SetFee { fee_bps: 1_001 }),
+    )
+    .await;
+    assert_eq!(
+        too_high.custom_error(),
+        Some(u32::from(VaultError::FeeTooHigh))
+    );
+
+    // 1%：管理员先把 treasury 补到免租金限额
+    let set = send(
+        &mut context,
+        &signer,
+        fee_ix(&signer.pubkey(), instruction::SetFee { fee_bps: 100 }),
+    )
+    .await;
+    assert_eq!(set.result, Ok(()));
+    assert!(set.logged("存款手续费设为 100 bps"));
+    assert_eq!(lamports(&mut context, &treasury_address()).await, minimum);
+
+    // 存款人付出全部金额，金库收到扣除手续费后的部分；追加的小额手续费向下取整
+    let before = lamports(&mut context, &stranger.pubkey()).await;
+    let deposit = send(
+        &mut context,
+        &stranger,
+        vault_ix(
+            &stranger.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+    let state_rent = context
+        .banks_client
+        .get_account(vault_state_address(&stranger.pubkey()))
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    assert_eq!(
+        lamports(&mut context, &stranger.pubkey()).await,
+        before - DEPOSIT - state_rent - deposit.fee
+    );
+    let top_up = send(
+        &mut context,
+        &stranger,
+        vault_ix(&stranger.pubkey(), instruction::TopUp { amount: 199 }),
+    )
+    .await;
+    assert_eq!(top_up.result, Ok(()));
+    assert_eq!(
+        lamports(&mut context, &vault_address(&stranger.pubkey())).await,
+        DEPOSIT - DEPOSIT / 100 + 198
+    );
+    assert_eq!(
+        lamports(&mut context, &treasury_address()).await,
+        minimum + DEPOSIT / 100 + 1
+    );
+
+    // 提取后 treasury 回到免租金限额，没有余额时再提取失败
+    let stranger_collect = send(
+        &mut context,
+        &stranger,
+        fee_ix(&stranger.pubkey(), instruction::CollectFees {}),
+    )
+    .await;
+    assert_eq!(
+        stranger_collect.custom_error(),
+        Some(u32::from(VaultError::Unauthorized))
+    );
+    let before = lamports(&mut context, &signer.pubkey()).await;
+    let collect = send(
+        &mut context,
+        &signer,
+        fee_ix(&signer.pubkey(), instruction::CollectFees {}),
+    )
+    .await;
+    assert_eq!(collect.result, Ok(()));
+    assert_eq!(
+        lamports(&mut context, &signer.pubkey()).await,
+        before + DEPOSIT / 100 + 1 - collect.fee
+    );
+    assert_eq!(lamports(&mut context, &treasury_address()).await, minimum);
+    // 换一个 slot，否则与上一笔完全相同的交易会被当作重复交易拒绝
+    context.warp_to_slot(100).unwrap();
+    let empty = send(
+        &mut context,
+        &signer,
+        fee_ix(&signer.pubkey(), instruction::CollectFees {}),
+    )
+    .await;
+    assert_eq!(
+        empty.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+}
+
+fn create_policy_ix(owner: &Pubkey, co_signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreatePolicy {
+            signer: *owner,
+            policy: policy_address(owner),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::CreatePolicy { co_signer }.data(),
+    }
+}
+
+fn update_policy_ix(owner: &Pubkey, co_signer: &Pubkey, new: Option<Pubkey>) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::UpdatePolicy {
+            signer: *owner,
+            co_signer: *co_signer,
+            policy: policy_address(owner),
+        }
+        .to_account_metas(None),
+        data: instruction::UpdatePolicy { co_signer: new }.data(),
+    }
+}
+
+/// 共同签名者作为 remaining_accounts 追加在末尾
+fn co_signed(mut ix: Instruction, co_signer: &Pubkey) -> Instruction {
+    ix.accounts
+        .push(AccountMeta::new_readonly(*co_signer, true));
+    ix
+}
+
+#[tokio::test]
+async fn policy_requires_co_signer_for_withdrawals() {
+    let (mut context, signer) = start().await;
+    let co_signer = Keypair::new();
+    let deposit = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+
+    let self_policy = send(
+        &mut context,
+        &signer,
+        create_policy_ix(&signer.pubkey(), signer.pubkey()),
+    )
+    .await;
+    assert_eq!(
+        self_policy.custom_error(),
+        Some(u32::from(VaultError::InvalidCoSigner))
+    );
+    let create = send(
+        &mut context,
+        &signer,
+        create_policy_ix(&signer.pubkey(), co_signer.pubkey()),
+    )
+    .await;
+    assert_eq!(create.result, Ok(()));
+
+    // 只有所有者签名：各条取款路径都被拒绝
+    let alone = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::Withdraw {}),
+    )
+    .await;
+    assert_eq!(
+        alone.custom_error(),
+        Some(u32::from(VaultError::CoSignerRequired))
+    );
+    let partial = send(
+        &mut context,
+        &signer,
+        vault_ix(&signer.pubkey(), instruction::WithdrawPartial { amount: 1 }),
+    )
+    .await;
+    assert_eq!(
+        partial.custom_error(),
+        Some(u32::from(VaultError::CoSignerRequired))
+    );
+    let stream = send(
+        &mut context,
+        &signer,
+        open_stream_ix(&signer.pubkey(), Pubkey::new_unique(), 1),
+    )
+    .await;
+    assert_eq!(
+        stream.custom_error(),
+        Some(u32::from(VaultError::CoSignerRequired))
+    );
+    // 传入共同签名者的地址但它没有签名，同样不行
+    let mut unsigned = vault_ix(&signer.pubkey(), instruction::Withdraw {});
+    unsigned
+        .accounts
+        .push(AccountMeta::new_readonly(co_signer.pubkey(), false));
+    let unsigned = send(&mut context, &signer, unsigned).await;
+    assert_eq!(
+        unsigned.custom_error(),
+        Some(u32::from(VaultError::CoSignerRequired))
+    );
+
+    // 两个签名都在时可以取款
+    let partial = send_signed(
+        &mut context,
+        &[&signer, &co_signer],
+        co_signed(
+            vault_ix(&signer.pubkey(), instruction::WithdrawPartial { amount: 1 }),
+            &co_signer.pubkey(),
+        ),
+    )
+    .await;
+    assert_eq!(partial.result, Ok(()));
+    assert_eq!(
+        lamports(&mut context, &vault_address(&signer.pubkey())).await,
+        DEPOSIT - 1
+    );
+}
+
+#[tokio::test]
+async fn policy_changes_need_both_signatures() {
+    let (mut context, signer) = start().await;
+    let (co_signer, next) = (Keypair::new(), Keypair::new());
+    let create = send(
+        &mut context,
+        &signer,
+        create_policy_ix(&signer.pubkey(), co_signer.pubkey()),
+    )
+    .await;
+    assert_eq!(create.result, Ok(()));
+
+    // 冒充的共同签名者不能修改策略
+    let impostor = Keypair::new();
+    let hijack = send_signed(
+        &mut context,
+        &[&signer, &impostor],
+        update_policy_ix(&signer.pubkey(), &impostor.pubkey(), None),
+    )
+    .await;
+    assert_eq!(
+        hijack.custom_error(),
+        Some(u32::from(VaultError::CoSignerRequired))
+    );
+
+    let rotate = send_signed(
+        &mut context,
+        &[&signer, &co_signer],
+        update_policy_ix(&signer.pubkey(), &co_signer.pubkey(), Some(next.pubkey())),
+    )
+    .await;
+    assert_eq!(rotate.result, Ok(()));
+
+    // 旧的共同签名者已经失效，删除策略需要新的共同签名者
+    let stale = send_signed(
+        &mut context,
+        &[&signer, &co_signer],
+        update_policy_ix(&signer.pubkey(), &co_signer.pubkey(), None),
+    )
+    .await;
+    assert_eq!(
+        stale.custom_error(),
+        Some(u32::from(VaultError::CoSignerRequired))
+    );
+    let remove = send_signed(
+        &mut context,
+        &[&signer, &next],
+        update_policy_ix(&signer.pubkey(), &next.pubkey(), None),
+    )
+    .await;
+    assert_eq!(remove.result, Ok(()));
+    assert!(context
+        .banks_client
+        .get_account(policy_address(&signer.pubkey()))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+fn set_cooldown_ix(owner: &Pubkey, interval_seconds: u32) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::SetCooldown {
+            signer: *owner,
+            cooldown: cooldown_address(owner),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::SetCooldown { interval_seconds }.data(),
+    }
+}
+
+#[tokio::test]
+async fn cooldown_spaces_out_withdrawals() {
+    let (mut context, signer) = start().await;
+    let owner = signer.pubkey();
+    let partial = |amount| vault_ix(&owner, instruction::WithdrawPartial { amount });
+
+    let deposit = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &owner,
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+    let set = send(&mut context, &signer, set_cooldown_ix(&owner, 60)).await;
+    assert_eq!(set.result, Ok(()));
+    let base = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+
+    // 第一次取款不受限制，之后 60 秒内的取款被拒绝，日志写明还需等待多久
+    let first = send(&mut context, &signer, partial(1)).await;
+    assert_eq!(first.result, Ok(()));
+    set_time(&mut context, 100, base + 45).await;
+    let too_soon = send(&mut context, &signer, partial(2)).await;
+    assert_eq!(
+        too_soon.custom_error(),
+        Some(u32::from(VaultError::WithdrawTooSoon))
+    );
+    assert!(too_soon.logged("还需等待 15 秒"));
+
+    // 冷却期内只能延长，不能缩短或删除
+    for interval in [10, 0] {
+        let shorten = send(&mut context, &signer, set_cooldown_ix(&owner, interval)).await;
+        assert_eq!(
+            shorten.custom_error(),
+            Some(u32::from(VaultError::WithdrawTooSoon))
+        );
+    }
+    let extend = send(&mut context, &signer, set_cooldown_ix(&owner, 120)).await;
+    assert_eq!(extend.result, Ok(()));
+    set_time(&mut context, 200, base + 60).await;
+    let still_waiting = send(&mut context, &signer, partial(3)).await;
+    assert_eq!(
+        still_waiting.custom_error(),
+        Some(u32::from(VaultError::WithdrawTooSoon))
+    );
+
+    // 冷却结束后可以取款，也可以删除冷却；删除后连续取款不再受限
+    set_time(&mut context, 300, base + 120).await;
+    let after = send(&mut context, &signer, partial(4)).await;
+    assert_eq!(after.result, Ok(()));
+    set_time(&mut context, 400, base + 240).await;
+    let remove = send(&mut context, &signer, set_cooldown_ix(&owner, 0)).await;
+    assert_eq!(remove.result, Ok(()));
+    assert!(context
+        .banks_client
+        .get_account(cooldown_address(&owner))
+        .await
+        .unwrap()
+        .is_none());
+    for amount in [5, 6] {
+        let free = send(&mut context, &signer, partial(amount)).await;
+        assert_eq!(free.result, Ok(()));
+    }
+}
+
+fn metadata_address(owner: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(pdas::metadata_pda(&ID.to_bytes(), &owner.to_bytes()).0)
+}
+
+/// InitMetadata 与 UpdateMetadata 的账户相同，只有指令数据不同
+fn metadata_ix(owner: &Pubkey, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::InitMetadata {
+            signer: *owner,
+            metadata: metadata_address(owner),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+async fn metadata(context: &mut ProgramTestContext, owner: &Pubkey) -> (VaultMetadata, Account) {
+    let account = context
+        .banks_client
+        .get_account(metadata_address(owner))
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = VaultMetadata::try_deserialize(&mut account.data.as_slice()).unwrap();
+    (metadata, account)
+}
+
+#[tokio::test]
+async fn metadata_resizes_with_its_contents() {
+    let (mut context, signer) = start().await;
+    let owner = signer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    // 标签按 UTF-8 字节计数：11 个汉字是 33 字节
+    let too_long = send(
+        &mut context,
+        &signer,
+        metadata_ix(
+            &owner,
+            instruction::InitMetadata {
+                label: "一二三四五六七八九十一".into(),
+                notes: String::new(),
+            },
+        ),
+    )
+    .await;
+    assert_eq!(
+        too_long.custom_error(),
+        Some(u32::from(VaultError::MetadataTooLong))
+    );
+
+    let init = send(
+        &mut context,
+        &signer,
+        metadata_ix(
+            &owner,
+            instruction::InitMetadata {
+                label: "储蓄".into(),
+                notes: String::new(),
+            },
+        ),
+    )
+    .await;
+    assert_eq!(init.result, Ok(()));
+    let (created, account) = metadata(&mut context, &owner).await;
+    assert_eq!(created.label, "储蓄");
+    assert_eq!(account.data.len(), VaultMetadata::space("储蓄", ""));
+    let now = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    assert!(created.created_at <= now);
+
+    // 变长时补足租金，变短时退回多余的租金；创建时间不变
+    let notes = "x".repeat(VaultMetadata::MAX_NOTES_LEN);
+    for (label, notes) in [("旅行基金", notes.as_str()), ("旅行", "")] {
+        let update = send(
+            &mut context,
+            &signer,
+            metadata_ix(
+                &owner,
+                instruction::UpdateMetadata {
+                    label: label.into(),
+                    notes: notes.into(),
+                },
+            ),
+        )
+        .await;
+        assert_eq!(update.result, Ok(()));
+        let (updated, account) = metadata(&mut context, &owner).await;
+        assert_eq!(
+            (updated.label.as_str(), updated.notes.as_str()),
+            (label, notes)
+        );
+        assert_eq!(updated.created_at, created.created_at);
+        assert_eq!(account.data.len(), VaultMetadata::space(label, notes));
+        assert_eq!(account.lamports, rent.minimum_balance(account.data.len()));
+    }
+
+    let close = send(
+        &mut context,
+        &signer,
+        Instruction {
+            program_id: ID,
+            accounts: accounts::CloseMetadata {
+                signer: owner,
+                metadata: metadata_address(&owner),
+            }
+            .to_account_metas(None),
+            data: instruction::CloseMetadata {}.data(),
+        },
+    )
+    .await;
+    assert_eq!(close.result, Ok(()));
+    assert!(context
+        .banks_client
+        .get_account(metadata_address(&owner))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+/// 代币金库测试的转账手续费：1%
+const FEE_BPS: u16 = 100;
+const TOKENS: u64 = 1_000_000;
+
+/// Token-2022 mint，带 TransferFeeConfig 扩展（FEE_BPS、无上限），铸币权限为 `authority`
+fn transfer_fee_mint(authority: &Pubkey) -> Account {
+    let len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::TransferFeeConfig,
+    ])
+    .unwrap();
+    let mut data = vec![0; len];
+    let mut state =
+        StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(&mut data)
+            .unwrap();
+    let fee = TransferFee {
+        epoch: 0.into(),
+        maximum_fee: u64::MAX.into(),
+        transfer_fee_basis_points: FEE_BPS.into(),
+    };
+    let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+    config.older_transfer_fee = fee;
+    config.newer_transfer_fee = fee;
+    state.base = spl_token_2022::state::Mint {
+        mint_authority: COption::Some(*authority),
+        supply: 0,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    Account {
+        lamports: Rent::default().minimum_balance(len),
+        data,
+        owner: spl_token_2022::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn token_2022_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, &spl_token_2022::ID)
+}
+
+async fn token_balance(context: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    let account = context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .unwrap();
+    StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+        .unwrap()
+        .base
+        .amount
+}
+
+fn deposit_token_ix(owner: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::DepositToken {
+            signer: *owner,
+            vault: vault_address(owner),
+            mint: *mint,
+            user_token: token_2022_ata(owner, mint),
+            token_vault: token_2022_ata(&vault_address(owner), mint),
+            config: config_address(),
+            token_program: spl_token_2022::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::DepositToken { amount }.data(),
+    }
+}
+
+fn withdraw_token_ix(owner: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::WithdrawToken {
+            signer: *owner,
+            vault: vault_address(owner),
+            mint: *mint,
+            user_token: token_2022_ata(owner, mint),
+            token_vault: token_2022_ata(&vault_address(owner), mint),
+            vault_state: vault_state_address(owner),
+            policy: policy_address(owner),
+            cooldown: cooldown_address(owner),
+            token_program: spl_token_2022::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::WithdrawToken { amount }.data(),
+    }
+}
+
+#[tokio::test]
+async fn token_2022_transfer_fee_mint_round_trip() {
+    let (mut context, signer) = start().await;
+    let owner = signer.pubkey();
+    let mint = Pubkey::new_unique();
+    context.set_account(&mint, &transfer_fee_mint(&owner).into());
+
+    // 签名者的 ATA 由 ATA 程序创建，Token-2022 会按 mint 的扩展分配 TransferFeeAmount
+    let user_token = token_2022_ata(&owner, &mint);
+    let create = send(
+        &mut context,
+        &signer,
+        create_associated_token_account(&owner, &owner, &mint, &spl_token_2022::ID),
+    )
+    .await;
+    assert_eq!(create.result, Ok(()));
+    let mint_to = send(
+        &mut context,
+        &signer,
+        spl_token_2022::instruction::mint_to(
+            &spl_token_2022::ID,
+            &mint,
+            &user_token,
+            &owner,
+            &[],
+            TOKENS,
+        )
+        .unwrap(),
+    )
+    .await;
+    assert_eq!(mint_to.result, Ok(()));
+
+    // 存入时扣除 1% 手续费，金库 ATA 实际到账 99%
+    let deposit = send(
+        &mut context,
+        &signer,
+        deposit_token_ix(&owner, &mint, TOKENS),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+    let fee = TOKENS * u64::from(FEE_BPS) / 10_000;
+    let received = TOKENS - fee;
+    assert!(deposit.logged(&format!("到账 {received}")));
+    let token_vault = token_2022_ata(&vault_address(&owner), &mint);
+    assert_eq!(token_balance(&mut context, &token_vault).await, received);
+    assert_eq!(token_balance(&mut context, &user_token).await, 0);
+
+    // 只能按金库实际持有的数量取出
+    let too_much = send(
+        &mut context,
+        &signer,
+        withdraw_token_ix(&owner, &mint, TOKENS),
+    )
+    .await;
+    assert_eq!(
+        too_much.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+    let withdraw = send(
+        &mut context,
+        &signer,
+        withdraw_token_ix(&owner, &mint, received),
+    )
+    .await;
+    assert_eq!(withdraw.result, Ok(()));
+    assert_eq!(token_balance(&mut context, &token_vault).await, 0);
+    assert_eq!(
+        token_balance(&mut context, &user_token).await,
+        received - received * u64::from(FEE_BPS) / 10_000
+    );
+}
+
+/// SPL Token 的原生 mint；测试验证器的创世账户里不一定有它，直接写入
+fn native_mint_account() -> Account {
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: COption::None,
+        supply: 0,
+        decimals: native_mint::DECIMALS,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn wsol_vault_address(owner: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(
+        &vault_address(owner),
+        &native_mint::ID,
+        &spl_token::ID,
+    )
+}
+
+fn wrap_deposit_ix(owner: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::WrapSol {
+            signer: *owner,
+            vault: vault_address(owner),
+            native_mint: native_mint::ID,
+            wsol_vault: wsol_vault_address(owner),
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            cooldown: cooldown_address(owner),
+        }
+        .to_account_metas(None),
+        data: instruction::WrapDeposit { amount }.data(),
+    }
+}
+
+fn unwrap_withdraw_ix(owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::UnwrapSol {
+            signer: *owner,
+            vault: vault_address(owner),
+            native_mint: native_mint::ID,
+            wsol_vault: wsol_vault_address(owner),
+            token_program: spl_token::ID,
+            cooldown: cooldown_address(owner),
+        }
+        .to_account_metas(None),
+        data: instruction::UnwrapWithdraw {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn wrap_and_unwrap_keep_sol_inside_the_vault() {
+    let (mut context, signer) = start().await;
+    let owner = signer.pubkey();
+    context.set_account(&native_mint::ID, &native_mint_account().into());
+
+    let deposit = send(
+        &mut context,
+        &signer,
+        vault_ix(
+            &owner,
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+
+    // 超过余额、剩下不足免租金的零头或者整个金库，都不能包装
+    let too_much = send(&mut context, &signer, wrap_deposit_ix(&owner, DEPOSIT + 1)).await;
+    assert_eq!(
+        too_much.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+    let dust = send(&mut context, &signer, wrap_deposit_ix(&owner, DEPOSIT - 1)).await;
+    assert_eq!(
+        dust.custom_error(),
+        Some(u32::from(VaultError::BelowRentMinimum))
+    );
+    let everything = send(&mut context, &signer, wrap_deposit_ix(&owner, DEPOSIT)).await;
+    assert_eq!(
+        everything.custom_error(),
+        Some(u32::from(VaultError::BelowRentMinimum))
+    );
+
+    // 包装的 lamports 变成金库 PDA 持有的 wSOL
+    let wsol_vault = wsol_vault_address(&owner);
+    let wrap = send(&mut context, &signer, wrap_deposit_ix(&owner, DEPOSIT / 4)).await;
+    assert_eq!(wrap.result, Ok(()));
+    assert_eq!(token_balance(&mut context, &wsol_vault).await, DEPOSIT / 4);
+    assert_eq!(
+        lamports(&mut context, &vault_address(&owner)).await,
+        DEPOSIT - DEPOSIT / 4
+    );
+
+    // 解包关闭 wSOL ATA，本金与 ATA 租金都回到金库
+    let ata_rent = lamports(&mut context, &wsol_vault).await - DEPOSIT / 4;
+    let unwrap = send(&mut context, &signer, unwrap_withdraw_ix(&owner)).await;
+    assert_eq!(unwrap.result, Ok(()));
+    assert_eq!(lamports(&mut context, &wsol_vault).await, 0);
+    assert_eq!(
+        lamports(&mut context, &vault_address(&owner)).await,
+        DEPOSIT + ata_rent
+    );
+}
+
+/// 存入 DEPOSIT（不锁定）
+async fn deposit_unlocked(context: &mut ProgramTestContext, signer: &Keypair) {
+    let deposit = send(
+        context,
+        signer,
+        vault_ix(
+            &signer.pubkey(),
+            instruction::Deposit {
+                amount: DEPOSIT,
+                lock_seconds: 0,
+            },
+        ),
+    )
+    .await;
+    assert_eq!(deposit.result, Ok(()));
+}
+
+/// 设置 60 秒冷却并部分取款 1 lamport，返回这次取款的时间
+async fn start_cooldown(context: &mut ProgramTestContext, signer: &Keypair) -> i64 {
+    let owner = signer.pubkey();
+    let set = send(context, signer, set_cooldown_ix(&owner, 60)).await;
+    assert_eq!(set.result, Ok(()));
+    let first = send(
+        context,
+        signer,
+        vault_ix(&owner, instruction::WithdrawPartial { amount: 1 }),
+    )
+    .await;
+    assert_eq!(first.result, Ok(()));
+    context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+}
+
+/// 冷却开始 30 秒后 `ix` 被拒绝，满 60 秒后成功
+async fn assert_waits_for_cooldown(
+    context: &mut ProgramTestContext,
+    signers: &[&Keypair],
+    ix: Instruction,
+    base: i64,
+) {
+    set_time(context, 1_000, base + 30).await;
+    let too_soon = send_signed(context, signers, ix.clone()).await;
+    assert_eq!(
+        too_soon.custom_error(),
+        Some(u32::from(VaultError::WithdrawTooSoon))
+    );
+    assert!(too_soon.logged("还需等待 30 秒"));
+    set_time(context, 2_000, base + 60).await;
+    let ready = send_signed(context, signers, ix).await;
+    assert_eq!(ready.result, Ok(()));
+}
+
+#[tokio::test]
+async fn cooldown_applies_to_withdraw_to() {
+    let (mut context, signer) = start().await;
+    deposit_unlocked(&mut context, &signer).await;
+    let base = start_cooldown(&mut context, &signer).await;
+
+    let recipient = Pubkey::new_unique();
+    let ix = withdraw_to_ix(&signer.pubkey(), recipient);
+    assert_waits_for_cooldown(&mut context, &[&signer], ix, base).await;
+    assert_eq!(lamports(&mut context, &recipient).await, DEPOSIT - 1);
+}
+
+#[tokio::test]
+async fn cooldown_applies_to_withdraw_as_delegate() {
+    let (mut context, owner) = start().await;
+    let delegate = Keypair::new();
+    deposit_unlocked(&mut context, &owner).await;
+    let set_delegate = send(
+        &mut context,
+        &owner,
+        Instruction {
+            program_id: ID,
+            accounts: accounts::SetDelegate {
+                signer: owner.pubkey(),
+                vault_state: vault_state_address(&owner.pubkey()),
+            }
+            .to_account_metas(None),
+            data: instruction::SetDelegate {
+                delegate: Some(delegate.pubkey()),
+            }
+            .data(),
+        },
+    )
+    .await;
+    assert_eq!(set_delegate.result, Ok(()));
+    let base = start_cooldown(&mut context, &owner).await;
+
+    // 冷却记录在 owner 名下，代理人同样要等
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::DelegateWithdraw {
+            delegate: delegate.pubkey(),
+            owner: owner.pubkey(),
+            vault: vault_address(&owner.pubkey()),
+            system_program: system_program::ID,
+            vault_state: vault_state_address(&owner.pubkey()),
+            policy: policy_address(&owner.pubkey()),
+            cooldown: cooldown_address(&owner.pubkey()),
+            event_authority: event_authority(),
+            program: ID,
+        }
+        .to_account_metas(None),
+        data: instruction::WithdrawAsDelegate {}.data(),
+    };
+    assert_waits_for_cooldown(&mut context, &[&owner, &delegate], ix, base).await;
+    assert_eq!(
+        lamports(&mut context, &delegate.pubkey()).await,
+        DEPOSIT - 1
+    );
+}
+
+#[tokio::test]
+async fn cooldown_applies_to_claim_stream() {
+    let (mut context, owner) = start().await;
+    let recipient = Pubkey::new_unique();
+    deposit_unlocked(&mut context, &owner).await;
+    // 先开流再设置冷却，开流本身不占用这次冷却
+    let open = send(
+        &mut context,
+        &owner,
+        open_stream_ix(&owner.pubkey(), recipient, 1_000_000),
+    )
+    .await;
+    assert_eq!(open.result, Ok(()));
+    let base = start_cooldown(&mut context, &owner).await;
+
+    let ix = claim_stream_ix(&owner.pubkey(), recipient);
+    assert_waits_for_cooldown(&mut context, &[&owner], ix, base).await;
+    assert!(lamports(&mut context, &recipient).await > 0);
+}
+
+#[tokio::test]
+async fn cooldown_applies_to_wrap_deposit() {
+    let (mut context, signer) = start().await;
+    let owner = signer.pubkey();
+    context.set_account(&native_mint::ID, &native_mint_account().into());
+    deposit_unlocked(&mut context, &signer).await;
+    let base = start_cooldown(&mut context, &signer).await;
+
+    let ix = wrap_deposit_ix(&owner, DEPOSIT / 4);
+    assert_waits_for_cooldown(&mut context, &[&signer], ix, base).await;
+    assert_eq!(
+        token_balance(&mut context, &wsol_vault_address(&owner)).await,
+        DEPOSIT / 4
+    );
+}
+
+#[tokio::test]
+async fn cooldown_applies_to_unwrap_withdraw() {
+    let (mut context, signer) = start().await;
+    let owner = signer.pubkey();
+    context.set_account(&native_mint::ID, &native_mint_account().into());
+    deposit_unlocked(&mut context, &signer).await;
+    // 还没有冷却账户时包装不受限制
+    let wrap = send(&mut context, &signer, wrap_deposit_ix(&owner, DEPOSIT / 4)).await;
+    assert_eq!(wrap.result, Ok(()));
+    let base = start_cooldown(&mut context, &signer).await;
+
+    let ix = unwrap_withdraw_ix(&owner);
+    assert_waits_for_cooldown(&mut context, &[&signer], ix, base).await;
+    assert_eq!(lamports(&mut context, &wsol_vault_address(&owner)).await, 0);
+}
+
+fn indexed_vault_address(owner: &Pubkey, index: u8) -> Pubkey {
+    Pubkey::new_from_array(pdas::indexed_vault_pda(&ID.to_bytes(), &owner.to_bytes(), index).0)
+}
+
+/// 子金库没有存款指令：付款方直接用系统转账把 SOL 转到子金库地址
+fn fund_indexed_ix(payer: &Pubkey, owner: &Pubkey, index: u8, amount: u64) -> Instruction {
+    system_instruction::transfer(payer, &indexed_vault_address(owner, index), amount)
+}
+
+/// `vaults` 按顺序作为 remaining_accounts，通常就是 `indices` 对应的子金库
+fn sweep_ix(signer: &Pubkey, indices: &[u8], vaults: &[Pubkey]) -> Instruction {
+    let mut accounts = accounts::Sweep {
+        signer: *signer,
+        system_program: system_program::ID,
+        policy: policy_address(signer),
+        cooldown: cooldown_address(signer),
+        event_authority: event_authority(),
+        program: ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(vaults.iter().map(|vault| AccountMeta::new(*vault, false)));
+    Instruction {
+        program_id: ID,
+        accounts,
+        data: instruction::Sweep {
+            indices: indices.to_vec(),
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn sweep_collects_every_sub_vault_in_one_call() {
+    let (mut context, signer) = start().await;
+    let owner = signer.pubkey();
+    for (index, amount) in [(0, DEPOSIT), (1, 2 * DEPOSIT), (7, 3 * DEPOSIT), (7, 1)] {
+        let fund = send(
+            &mut context,
+            &signer,
+            fund_indexed_ix(&owner, &owner, index, amount),
+        )
+        .await;
+        assert_eq!(fund.result, Ok(()));
+    }
+    deposit_unlocked(&mut context, &signer).await;
+
+    let vaults: Vec<Pubkey> = [0, 1, 2, 7]
+        .into_iter()
+        .map(|index| indexed_vault_address(&owner, index))
+        .collect();
+    // 序号与地址必须对应：1 号子金库不能当作 0 号，主金库也不能冒充子金库
+    for impostor in [vaults[1], vault_address(&owner)] {
+        let rejected = send(&mut context, &signer, sweep_ix(&owner, &[0], &[impostor])).await;
+        assert_eq!(
+            rejected.custom_error(),
+            Some(u32::from(VaultError::InvalidSubVault))
+        );
+    }
+
+    // 2 号子金库从未收到转账，直接跳过
+    let before = lamports(&mut context, &owner).await;
+    let sweep = send(
+        &mut context,
+        &signer,
+        sweep_ix(&owner, &[0, 1, 2, 7], &vaults),
+    )
+    .await;
+    assert_eq!(sweep.result, Ok(()));
+    assert_eq!(
+        lamports(&mut context, &owner).await,
+        before + 6 * DEPOSIT + 1 - sweep.fee
+    );
+    for vault in &vaults {
+        assert_eq!(lamports(&mut context, vault).await, 0);
+    }
+    // 主金库不受影响
+    assert_eq!(
+        lamports(&mut context, &vault_address(&owner)).await,
+        DEPOSIT
+    );
+
+    // 所有子金库都已为空，没有可归集的余额
+    let empty = send(
+        &mut context,
+        &signer,
+        sweep_ix(&owner, &[0, 1], &vaults[..2]),
+    )
+    .await;
+    assert_eq!(
+        empty.custom_error(),
+        Some(u32::from(VaultError::InvalidAmount))
+    );
+}
+
+#[tokio::test]
+async fn sweep_respects_policy_and_cooldown() {
+    let (mut context, owner) = start().await;
+    let co_signer = Keypair::new();
+    let vault = indexed_vault_address(&owner.pubkey(), 3);
+    deposit_unlocked(&mut context, &owner).await;
+    let fund = send(
+        &mut context,
+        &owner,
+        fund_indexed_ix(&owner.pubkey(), &owner.pubkey(), 3, DEPOSIT),
+    )
+    .await;
+    assert_eq!(fund.result, Ok(()));
+    // 冷却与策略都与主金库共用
+    let base = start_cooldown(&mut context, &owner).await;
+    let policy = send(
+        &mut context,
+        &owner,
+        create_policy_ix(&owner.pubkey(), co_signer.pubkey()),
+    )
+    .await;
+    assert_eq!(policy.result, Ok(()));
+
+    // 共同签名者追加在子金库之后
+    let ix = sweep_ix(&owner.pubkey(), &[3], &[vault]);
+    let alone = send(&mut context, &owner, ix.clone()).await;
+    assert_eq!(
+        alone.custom_error(),
+        Some(u32::from(VaultError::CoSignerRequired))
+    );
+
+    let ix = co_signed(ix, &co_signer.pubkey());
+    assert_waits_for_cooldown(&mut context, &[&owner, &co_signer], ix, base).await;
+    assert_eq!(lamports(&mut context, &vault).await, 0);
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*; // 引入 Anchor 预导入内容（宏与常用类型）。
 // 托管程序的错误定义。 // 章节标题行，避免空行。
-#[error_code] // 标记该枚举为 Anchor 错误集合。
+#[error_code(offset = 6100)] // 标记该枚举为 Anchor 错误集合，编号从 6100 起（与 blueshift_errors 的托管范围一致）。
 pub enum EscrowError { // 托管相关错误枚举开始。
     #[msg("Invalid amount")] // 当数量非法时的错误消息。
     InvalidAmount, // 数量必须大于 0 或满足约束。
@@ -10,4 +10,12 @@ pub enum EscrowError { // 托管相关错误枚举开始。
     InvalidMintA, // mint A 与 escrow 配置不一致。
     #[msg("Invalid mint b")] // 当 mint B 不匹配时的错误消息。
     InvalidMintB, // mint B 与 escrow 配置不一致。
+    #[msg("Lamport balance below rent-exempt threshold")] // 占位：Anchor 版由框架返回 ConstraintRentExempt。
+    NotRentExempt, // 保留编号 6104，使之后的错误与 Pinocchio 托管编号一致。
+    #[msg("Escrow has expired")] // 托管已过期时的错误消息。
+    EscrowExpired, // make 的过期时间不在未来，或 take 时已到期。
+    #[msg("Escrow has not expired")] // 未过期却由他人退款时的错误消息。
+    EscrowNotExpired, // 未过期时只有 maker 可以 refund。
+    #[msg("Invalid taker")] // 签名者不是指定接受者时的错误消息。
+    InvalidTaker, // 托管指定了 taker，签名者与之不符。
 } 
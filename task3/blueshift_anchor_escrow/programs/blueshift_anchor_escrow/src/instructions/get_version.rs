@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*; // 引入 Anchor 预导入模块。
+use anchor_lang::solana_program::program::set_return_data; // 写入 return data 的系统调用封装。
+use blueshift_version::{features, Version}; // 版本格式与功能位，各训练营程序共用。
+// 本程序的版本：Cargo.toml 中的 version，功能位与 Pinocchio 托管一致（同样支持 Token-2022）。
+pub const VERSION: Version = blueshift_version::version!(features::escrow::TOKEN_2022);
+// get_version 不需要任何账户；调用方多传的账户（例如功能开关）会被忽略。
+#[derive(Accounts)] // 生成账户校验代码。
+pub struct GetVersion {} // 空账户结构。
+// get_version 处理器：return data 为版本号与功能位，格式见 crates/blueshift_version。
+pub fn handler(_ctx: Context<GetVersion>) -> Result<()> { // 不读取任何账户。
+    // Anchor 版没有功能开关账户，enabled 恒为 0。
+    set_return_data(&VERSION.encode()); // 写入 22 字节的版本信息。
+    Ok(()) // 返回成功。
+} 
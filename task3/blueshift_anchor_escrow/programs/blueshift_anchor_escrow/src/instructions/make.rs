@@ -5,12 +5,14 @@ use anchor_spl::{ // 引入 Anchor SPL 辅助与 Token 接口。
         transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked, // CPI 方法与账户类型。
     }, // token_interface 导入结束。
 }; // anchor_spl 导入结束。
+use blueshift_events::{cpi::emit_event, EscrowMade}; // 共享的事件 CPI 与事件定义。
 
 use crate::{ // 引入当前 crate 的内容。
     errors::EscrowError, // 自定义错误定义。
     state::{Escrow}, // Escrow 状态与 PDA 种子常量。
 }; // crate 导入结束。
 // make 指令的账户上下文。 
+#[event_cpi] // 追加 event_authority 与 program 账户，用于事件 CPI。
 #[derive(Accounts)] // 派生账户校验逻辑。
 #[instruction(seed: u64)] // make 使用 seed 作为 PDA 种子参数。
 pub struct Make<'info> { // Make 账户结构体开始。
@@ -55,13 +57,15 @@ pub struct Make<'info> { // Make 账户结构体开始。
 } 
 // Make 辅助方法实现。
 impl<'info> Make<'info> { // Make 的 impl 开始。
-    pub fn populate_escrow(&mut self, seed: u64, receive: u64, bump: u8) -> Result<()> { // 填充 escrow 字段。
+    pub fn populate_escrow(&mut self, seed: u64, receive: u64, bump: u8, expiry: i64, taker: Pubkey) -> Result<()> { // 填充 escrow 字段。
         self.escrow.seed = seed; // 保存 seed 用于后续 PDA 推导。
         self.escrow.maker = self.maker.key(); // 保存 maker 公钥。
         self.escrow.mint_a = self.mint_a.key(); // 保存 mint A 公钥。
         self.escrow.mint_b = self.mint_b.key(); // 保存 mint B 公钥。
         self.escrow.receive = receive; // 保存期望接收的 Token B 数量。
         self.escrow.bump = bump; // 保存 PDA bump。
+        self.escrow.expiry = expiry; // 保存过期时间。
+        self.escrow.taker = taker; // 保存指定的接受者（全零为不限）。
         Ok(()) 
     } // populate_escrow 结束。
     // 将 maker 的 Token A 存入金库。
@@ -83,13 +87,29 @@ impl<'info> Make<'info> { // Make 的 impl 开始。
     } 
 } 
 // make 指令处理器。 
-pub fn handler(ctx: Context<Make>, seed: u64, receive: u64, amount: u64) -> Result<()> { // make 入口逻辑。
+pub fn handler(ctx: Context<Make>, seed: u64, receive: u64, amount: u64, expiry: i64, taker: Pubkey) -> Result<()> { // make 入口逻辑。
     // 校验数量参数。 // 校验说明。
     require_gt!(receive, 0, EscrowError::InvalidAmount); // receive 必须大于 0。
     require_gt!(amount, 0, EscrowError::InvalidAmount); // deposit 必须大于 0。
+    require!(expiry == 0 || expiry > Clock::get()?.unix_timestamp, EscrowError::EscrowExpired); // 过期时间必须在未来（0 为永不过期）。
     // 写入 Escrow 数据。 // 状态初始化说明。
-    ctx.accounts.populate_escrow(seed, receive, ctx.bumps.escrow)?; // 持久化 escrow 字段。
+    ctx.accounts.populate_escrow(seed, receive, ctx.bumps.escrow, expiry, taker)?; // 持久化 escrow 字段。
     // 存入 Token。 // 转账说明。
     ctx.accounts.deposit_tokens(amount)?; // 将 maker 的 Token A 存入金库。
+    // 发出事件（与 Pinocchio 托管的 EscrowMade 字节相同）。
+    emit_event( // 以 event_authority 签名自调用。
+        &crate::ID, // 自调用本程序。
+        &ctx.accounts.event_authority, // 事件 CPI 签名者。
+        ctx.bumps.event_authority, // event_authority 的 bump。
+        &EscrowMade { // 挂单事件。
+            escrow: ctx.accounts.escrow.key().to_bytes(), // escrow PDA。
+            maker: ctx.accounts.maker.key().to_bytes(), // maker 公钥。
+            mint_a: ctx.accounts.mint_a.key().to_bytes(), // mint A。
+            mint_b: ctx.accounts.mint_b.key().to_bytes(), // mint B。
+            seed, // escrow seed。
+            amount, // 存入的 Token A 数量。
+            receive, // 期望换回的 Token B 数量。
+        }, // EscrowMade 结束。
+    )?; // 传播 CPI 错误。
     Ok(()) // 返回成功。
 } 
@@ -6,23 +6,28 @@ use anchor_spl::{
         TransferChecked, // TransferChecked CPI 账户结构。
     }, 
 }; 
+use blueshift_events::{cpi::emit_event, EscrowRefunded}; // 共享的事件 CPI 与事件定义。
 
 use crate::{ 
     errors::EscrowError, // 自定义错误定义。
     state::{Escrow, ESCROW_SEED}, // Escrow 状态与 PDA 种子。
 }; 
 // refund 指令的账户上下文。 
+#[event_cpi] // 追加 event_authority 与 program 账户，用于事件 CPI。
 #[derive(Accounts)] // 派生账户校验逻辑。
 pub struct Refund<'info> { // Refund 账户结构体开始。
-    #[account(mut)] // maker 需要可变并签名。
-    pub maker: Signer<'info>, // maker 签名者账户。
+    #[account(mut)] // caller 签名并在需要时支付 maker ATA 的租金。
+    pub caller: Signer<'info>, // 调用者：maker 本人，或托管过期后的任何人。
+    #[account(mut)] // maker 接收代币与租金，不要求签名。
+    pub maker: SystemAccount<'info>, // maker 系统账户。
     #[account( // escrow PDA 账户约束。
         mut, // escrow 将被关闭，需要可变。
         close = maker, // 关闭 escrow 并把租金返还给 maker。
         seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()], // PDA 种子。
         bump = escrow.bump, // 校验 PDA bump。
         has_one = maker @ EscrowError::InvalidMaker, // 校验 maker 与 escrow 一致。
-        has_one = mint_a @ EscrowError::InvalidMintA // 校验 mint A 与 escrow 一致。
+        has_one = mint_a @ EscrowError::InvalidMintA, // 校验 mint A 与 escrow 一致。
+        constraint = caller.key() == maker.key() || escrow.is_expired(Clock::get()?.unix_timestamp) @ EscrowError::EscrowNotExpired // 非 maker 只能退款已过期的托管。
     )] 
     pub escrow: Account<'info, Escrow>, // escrow PDA 账户。
     #[account(mint::token_program = token_program)] // mint A 必须属于 token_program。
@@ -36,7 +41,7 @@ pub struct Refund<'info> { // Refund 账户结构体开始。
     pub vault: InterfaceAccount<'info, TokenAccount>, // 金库存放 Token A。
     #[account( // maker 的 ATA（mint A）。
         init_if_needed, // 若不存在则创建。
-        payer = maker, // 由 maker 支付创建费用。
+        payer = caller, // 由 caller 支付创建费用。
         associated_token::mint = mint_a, // ATA mint 为 mint A。
         associated_token::authority = maker, // ATA 权限为 maker。
         associated_token::token_program = token_program // ATA 使用指定 token_program。
@@ -84,5 +89,16 @@ pub fn handler(ctx: Context<Refund>) -> Result<()> { // refund 入口逻辑。
         }, // CloseAccount 账户结束。
         signer, // PDA 签名种子。
     ))?; // 传播 CPI 错误。
+    // 发出事件（与 Pinocchio 托管的 EscrowRefunded 字节相同）。
+    emit_event( // 以 event_authority 签名自调用。
+        &crate::ID, // 自调用本程序。
+        &ctx.accounts.event_authority, // 事件 CPI 签名者。
+        ctx.bumps.event_authority, // event_authority 的 bump。
+        &EscrowRefunded { // 撤单事件。
+            escrow: ctx.accounts.escrow.key().to_bytes(), // escrow PDA。
+            maker: ctx.accounts.maker.key().to_bytes(), // maker 公钥。
+            amount: vault_amount, // 退回 maker 的 Token A。
+        }, // EscrowRefunded 结束。
+    )?; // 传播 CPI 错误。
     Ok(()) // 返回成功。
 } 
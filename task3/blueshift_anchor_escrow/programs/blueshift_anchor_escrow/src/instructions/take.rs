@@ -6,12 +6,14 @@ use anchor_spl::{
         TransferChecked, // TransferChecked CPI 账户结构。
     }, 
 }; 
+use blueshift_events::{cpi::emit_event, EscrowTaken}; // 共享的事件 CPI 与事件定义。
 // 本 crate 内部导入。 
 use crate::{ 
     errors::EscrowError, // 自定义错误定义。
     state::{Escrow}, // Escrow 状态与 PDA 种子。
 }; // crate 导入结束。
 // take 指令的账户上下文。
+#[event_cpi] // 追加 event_authority 与 program 账户，用于事件 CPI。
 #[derive(Accounts)] // 派生账户校验逻辑。
 pub struct Take<'info> { 
   #[account(mut)] // taker 需可变，用于支付租金并签名。
@@ -123,9 +125,26 @@ impl<'info> Take<'info> { // Take 的 impl 开始。
 } // Take 的 impl 结束。
 // take 指令处理器。 
 pub fn handler(ctx: Context<Take>) -> Result<()> { // take 入口逻辑。
+    require!(!ctx.accounts.escrow.is_expired(Clock::get()?.unix_timestamp), EscrowError::EscrowExpired); // 过期的托管只能 refund。
+    require!(ctx.accounts.escrow.allows_taker(&ctx.accounts.taker.key()), EscrowError::InvalidTaker); // 指定了接受者时只有它可以 take。
+    let amount = ctx.accounts.vault.amount; // 事件用：金库关闭前的 Token A 余额。
+    let receive = ctx.accounts.escrow.receive; // 事件用：支付给 maker 的 Token B 数量。
     // 将 Token B 转给 maker。 
     ctx.accounts.transfer_to_maker()?; // 执行 Token B 转账。
     // 提取 Token A 并关闭金库。 
     ctx.accounts.withdraw_and_close_vault()?; // 转出 Token A 并关闭金库。
+    // 发出事件（与 Pinocchio 托管的 EscrowTaken 字节相同）。
+    emit_event( // 以 event_authority 签名自调用。
+        &crate::ID, // 自调用本程序。
+        &ctx.accounts.event_authority, // 事件 CPI 签名者。
+        ctx.bumps.event_authority, // event_authority 的 bump。
+        &EscrowTaken { // 吃单事件。
+            escrow: ctx.accounts.escrow.key().to_bytes(), // escrow PDA。
+            maker: ctx.accounts.maker.key().to_bytes(), // maker 公钥。
+            taker: ctx.accounts.taker.key().to_bytes(), // taker 公钥。
+            amount, // taker 取走的 Token A。
+            receive, // maker 收到的 Token B。
+        }, // EscrowTaken 结束。
+    )?; // 传播 CPI 错误。
     Ok(()) // 返回成功。
 } // take 处理器结束。
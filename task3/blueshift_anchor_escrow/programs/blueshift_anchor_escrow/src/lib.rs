@@ -6,14 +6,23 @@ mod state; // 状态定义模块。
 use instructions::*; // 使用重新导出的指令账户类型。
 // 程序 ID 声明。 
 declare_id!("22222222222222222222222222222222222222222222"); 
+// security.txt 元数据（启用 security-txt feature 时嵌入）。
+#[cfg(all(feature = "security-txt", not(feature = "no-entrypoint")))]
+solana_security_txt::security_txt! {
+    name: "Blueshift Anchor Escrow",
+    project_url: "https://github.com/o7reconversion/solana_bootcamp_2026",
+    contacts: "link:https://github.com/o7reconversion/solana_bootcamp_2026/security/advisories/new",
+    policy: "https://github.com/o7reconversion/solana_bootcamp_2026/blob/main/SECURITY.md",
+    source_code: "https://github.com/o7reconversion/solana_bootcamp_2026/tree/main/task3/blueshift_anchor_escrow"
+}
 // 程序入口模块。 
 #[program] // Anchor 程序宏，生成入口函数。
 pub mod anchor_escrow { // 程序模块名。
     use super::*; // 将外层作用域内容引入当前模块。
     // 指令：make（鉴别器 = 0）。
     #[instruction(discriminator = 0)] // make 指令自定义鉴别器。
-    pub fn make(ctx: Context<Make>, seed: u64, deposit: u64, receive: u64) -> Result<()> { // make 入口函数。
-        instructions::make::handler(ctx, seed, deposit, receive) // 调用 make 处理器。
+    pub fn make(ctx: Context<Make>, seed: u64, receive: u64, amount: u64, expiry: i64, taker: Pubkey) -> Result<()> { // make 入口函数，参数顺序与 handler 一致。
+        instructions::make::handler(ctx, seed, receive, amount, expiry, taker) // 调用 make 处理器。
     } 
     // 指令：take（鉴别器 = 1）。 
     #[instruction(discriminator = 1)] 
@@ -25,4 +34,8 @@ pub mod anchor_escrow { // 程序模块名。
     pub fn refund(ctx: Context<Refund>) -> Result<()> { // refund 入口函数。
         instructions::refund::handler(ctx) // 调用 refund 处理器。
     } 
+    // 指令：get_version（默认鉴别器 sha256("global:get_version")[..8]，与其他训练营程序相同）。
+    pub fn get_version(ctx: Context<GetVersion>) -> Result<()> { // get_version 入口函数。
+        instructions::get_version::handler(ctx) // 调用 get_version 处理器。
+    } 
 } 
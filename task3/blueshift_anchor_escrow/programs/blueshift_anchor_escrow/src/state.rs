@@ -11,4 +11,14 @@ pub struct Escrow { // Escrow 链上状态开始。
     pub mint_b: Pubkey, // maker 期望获得的 Token B 的 Mint。
     pub receive: u64, // maker 期望接收的 Token B 数量。
     pub bump: u8, // PDA bump，用于派生 escrow 地址。
+    pub expiry: i64, // 过期时间（Unix 时间戳），0 表示永不过期；放在末尾，之前字段的偏移不变。
+    pub taker: Pubkey, // 指定的接受者，全零表示任何人都可以 take（场外约定成交）。
 } // Escrow 结构体结束
+impl Escrow { // Escrow 辅助方法。
+    pub fn is_expired(&self, now: i64) -> bool { // now 为 Clock 的 unix_timestamp。
+        self.expiry != 0 && now >= self.expiry // 设置了过期时间且已经到期。
+    } // is_expired 结束。
+    pub fn allows_taker(&self, taker: &Pubkey) -> bool { // taker 能否接受本托管。
+        self.taker == Pubkey::default() || self.taker == *taker // 未指定接受者，或正是指定的接受者。
+    } // allows_taker 结束。
+} // impl 结束。
@@ -0,0 +1,392 @@
+//! solana-program-test 套件：加载 `anchor build` 生成的 blueshift_anchor_escrow.so，
+//! 与运行时内置的 SPL Token / ATA 程序一起执行 make → take / refund，
+//! 校验日志、代币余额、账户关闭后的租金去向以及托管账户的数据布局。
+//!
+//! 先在 task3/blueshift_anchor_escrow 执行 `anchor build`，再执行 `cargo test -p blueshift_anchor_escrow`。
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address},
+    token::{self, spl_token},
+};
+use blueshift_anchor_escrow::{accounts, instruction, ID};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    instruction::{Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+const SEED: u64 = 7;
+const AMOUNT: u64 = 1_000_000;
+const RECEIVE: u64 = 2_500_000;
+/// EscrowError 从 6100 开始编号（`#[error_code(offset = 6100)]`）
+const INVALID_AMOUNT: u32 = 6100;
+/// 未过期时由第三方调用 Refund
+const ESCROW_NOT_EXPIRED: u32 = 6106;
+/// 1 字节账户判别器 + seed + maker + mint_a + mint_b + receive + bump + expiry + taker
+const ESCROW_LEN: usize = 1 + 8 + 32 * 3 + 8 + 1 + 8 + 32;
+
+struct Outcome {
+    result: Result<(), TransactionError>,
+    logs: Vec<String>,
+    fee: u64,
+}
+
+impl Outcome {
+    fn logged(&self, line: &str) -> bool {
+        self.logs.iter().any(|log| log.contains(line))
+    }
+}
+
+/// maker 持有 10 * AMOUNT 个代币 A，taker 持有 10 * RECEIVE 个代币 B
+struct Trade {
+    context: ProgramTestContext,
+    maker: Keypair,
+    taker: Keypair,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    escrow: Pubkey,
+    vault: Pubkey,
+}
+
+fn packed<T: Pack>(state: T) -> Account {
+    let mut data = vec![0; T::LEN];
+    T::pack(state, &mut data).unwrap();
+    Account {
+        lamports: Rent::default().minimum_balance(T::LEN),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// #[event_cpi] 追加的事件 CPI 签名者
+fn event_authority() -> Pubkey {
+    Pubkey::new_from_array(pdas::event_authority_pda(&ID.to_bytes()).0)
+}
+
+fn add_token_account(test: &mut ProgramTest, owner: &Pubkey, mint: &Pubkey, amount: u64) {
+    let account = spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    };
+    test.add_account(get_associated_token_address(owner, mint), packed(account));
+}
+
+impl Trade {
+    async fn start() -> Self {
+        // ProgramTest 在 SBF_OUT_DIR 中查找 <程序名>.so
+        std::env::set_var(
+            "SBF_OUT_DIR",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy"),
+        );
+        let mut test = ProgramTest::new("blueshift_anchor_escrow", ID, None);
+
+        let (maker, taker) = (Keypair::new(), Keypair::new());
+        for wallet in [&maker, &taker] {
+            test.add_account(
+                wallet.pubkey(),
+                Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program::ID),
+            );
+        }
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        for mint in [mint_a, mint_b] {
+            let state = spl_token::state::Mint {
+                mint_authority: COption::Some(Pubkey::new_unique()),
+                supply: 10 * (AMOUNT + RECEIVE),
+                decimals: 6,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            };
+            test.add_account(mint, packed(state));
+        }
+        add_token_account(&mut test, &maker.pubkey(), &mint_a, 10 * AMOUNT);
+        add_token_account(&mut test, &taker.pubkey(), &mint_b, 10 * RECEIVE);
+
+        let escrow = Pubkey::new_from_array(
+            pdas::escrow_pda(&ID.to_bytes(), &maker.pubkey().to_bytes(), SEED).0,
+        );
+        Self {
+            context: test.start_with_context().await,
+            vault: get_associated_token_address(&escrow, &mint_a),
+            maker,
+            taker,
+            mint_a,
+            mint_b,
+            escrow,
+        }
+    }
+
+    fn make_ix(&self, amount: u64) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::Make {
+                maker: self.maker.pubkey(),
+                escrow: self.escrow,
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                maker_ata_a: get_associated_token_address(&self.maker.pubkey(), &self.mint_a),
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            }
+            .to_account_metas(None),
+            data: instruction::Make {
+                seed: SEED,
+                receive: RECEIVE,
+                amount,
+                expiry: 0,
+                taker: Pubkey::default(),
+            }
+            .data(),
+        }
+    }
+
+    fn take_ix(&self) -> Instruction {
+        let taker = self.taker.pubkey();
+        Instruction {
+            program_id: ID,
+            accounts: accounts::Take {
+                taker,
+                maker: self.maker.pubkey(),
+                escrow: self.escrow,
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                vault: self.vault,
+                taker_ata_a: get_associated_token_address(&taker, &self.mint_a),
+                taker_ata_b: get_associated_token_address(&taker, &self.mint_b),
+                maker_ata_b: get_associated_token_address(&self.maker.pubkey(), &self.mint_b),
+                associated_token_program: associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            }
+            .to_account_metas(None),
+            data: instruction::Take {}.data(),
+        }
+    }
+
+    /// `caller` 签名并支付 ATA 租金；未过期时只有 maker 本人可以退款
+    fn refund_ix(&self, caller: &Pubkey, maker: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::Refund {
+                caller: *caller,
+                maker: *maker,
+                escrow: self.escrow,
+                mint_a: self.mint_a,
+                vault: self.vault,
+                maker_ata_a: get_associated_token_address(maker, &self.mint_a),
+                associated_token_program: associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            }
+            .to_account_metas(None),
+            data: instruction::Refund {}.data(),
+        }
+    }
+
+    async fn send(&mut self, ix: Instruction, signer: &Keypair) -> Outcome {
+        let banks = &mut self.context.banks_client;
+        let blockhash = banks.get_latest_blockhash().await.unwrap();
+        let tx =
+            Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+        let fee = banks
+            .get_fee_for_message(tx.message.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        let processed = banks.process_transaction_with_metadata(tx).await.unwrap();
+        Outcome {
+            result: processed.result,
+            logs: processed
+                .metadata
+                .map(|meta| meta.log_messages)
+                .unwrap_or_default(),
+            fee,
+        }
+    }
+
+    async fn make(&mut self) -> Outcome {
+        let maker = self.maker.insecure_clone();
+        self.send(self.make_ix(AMOUNT), &maker).await
+    }
+
+    async fn account(&mut self, address: &Pubkey) -> Option<Account> {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+    }
+
+    async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.context
+            .banks_client
+            .get_balance(*address)
+            .await
+            .unwrap()
+    }
+
+    /// `owner` 在 `mint` 上的 ATA 余额，ATA 不存在时为 None
+    async fn balance(&mut self, owner: &Pubkey, mint: &Pubkey) -> Option<u64> {
+        let account = self
+            .account(&get_associated_token_address(owner, mint))
+            .await?;
+        Some(
+            spl_token::state::Account::unpack(&account.data)
+                .unwrap()
+                .amount,
+        )
+    }
+}
+
+#[tokio::test]
+async fn make_then_take() {
+    let mut trade = Trade::start().await;
+    let (maker, taker) = (trade.maker.pubkey(), trade.taker.pubkey());
+    let (escrow_address, vault, mint_a, mint_b) =
+        (trade.escrow, trade.vault, trade.mint_a, trade.mint_b);
+    let maker_lamports = trade.lamports(&maker).await;
+
+    let make = trade.make().await;
+    assert_eq!(make.result, Ok(()));
+    assert!(make.logged("Program log: Instruction: Make"));
+    // 金库由 ATA 程序创建，代币经 Token 程序的 CPI 转入
+    assert!(make.logged(&format!("Program {} invoke [2]", associated_token::ID)));
+    assert!(make.logged(&format!("Program {} invoke [2]", token::ID)));
+    // EscrowMade 通过自调用发出
+    assert!(make.logged(&format!("Program {ID} invoke [2]")));
+
+    // 托管账户：1 字节判别器后按字段顺序排列，占用空间恰好免租金
+    let escrow = trade.account(&escrow_address).await.unwrap();
+    assert_eq!(escrow.owner, ID);
+    assert_eq!(escrow.data.len(), ESCROW_LEN);
+    assert_eq!(escrow.lamports, Rent::default().minimum_balance(ESCROW_LEN));
+    assert_eq!(escrow.data[0], 1);
+    assert_eq!(escrow.data[1..9], SEED.to_le_bytes());
+    assert_eq!(escrow.data[9..41], maker.to_bytes());
+    assert_eq!(escrow.data[41..73], mint_a.to_bytes());
+    assert_eq!(escrow.data[73..105], mint_b.to_bytes());
+    assert_eq!(escrow.data[105..113], RECEIVE.to_le_bytes());
+    // expiry = 0：永不过期
+    assert_eq!(escrow.data[114..122], 0i64.to_le_bytes());
+    // taker 全零：任何人都可以接受
+    assert_eq!(escrow.data[122..154], [0; 32]);
+
+    assert_eq!(trade.balance(&escrow_address, &mint_a).await, Some(AMOUNT));
+    assert_eq!(trade.balance(&maker, &mint_a).await, Some(9 * AMOUNT));
+
+    let taker_keypair = trade.taker.insecure_clone();
+    let take = trade.send(trade.take_ix(), &taker_keypair).await;
+    assert_eq!(take.result, Ok(()));
+    assert!(take.logged("Program log: Instruction: Take"));
+
+    assert_eq!(trade.balance(&taker, &mint_a).await, Some(AMOUNT));
+    assert_eq!(
+        trade.balance(&taker, &mint_b).await,
+        Some(10 * RECEIVE - RECEIVE)
+    );
+    assert_eq!(trade.balance(&maker, &mint_b).await, Some(RECEIVE));
+    // 托管账户与金库都已关闭，租金退回 maker：maker 只损失 make 的手续费
+    assert!(trade.account(&escrow_address).await.is_none());
+    assert!(trade.account(&vault).await.is_none());
+    assert_eq!(trade.lamports(&maker).await, maker_lamports - make.fee);
+}
+
+#[tokio::test]
+async fn make_then_refund() {
+    let mut trade = Trade::start().await;
+    let maker = trade.maker.insecure_clone();
+    let (escrow, vault, mint_a) = (trade.escrow, trade.vault, trade.mint_a);
+    let maker_lamports = trade.lamports(&maker.pubkey()).await;
+
+    let make = trade.make().await;
+    assert_eq!(make.result, Ok(()));
+
+    let refund = trade
+        .send(trade.refund_ix(&maker.pubkey(), &maker.pubkey()), &maker)
+        .await;
+    assert_eq!(refund.result, Ok(()));
+    assert!(refund.logged("Program log: Instruction: Refund"));
+
+    assert_eq!(
+        trade.balance(&maker.pubkey(), &mint_a).await,
+        Some(10 * AMOUNT)
+    );
+    assert!(trade.account(&escrow).await.is_none());
+    assert!(trade.account(&vault).await.is_none());
+    assert_eq!(
+        trade.lamports(&maker.pubkey()).await,
+        maker_lamports - make.fee - refund.fee
+    );
+}
+
+#[tokio::test]
+async fn make_rejects_zero_amount() {
+    let mut trade = Trade::start().await;
+    let (maker, escrow) = (trade.maker.insecure_clone(), trade.escrow);
+
+    let make = trade.send(trade.make_ix(0), &maker).await;
+    assert_eq!(
+        make.result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(INVALID_AMOUNT)
+        ))
+    );
+    assert!(make.logged("Error Code: InvalidAmount"));
+    assert!(trade.account(&escrow).await.is_none());
+}
+
+#[tokio::test]
+async fn stranger_cannot_refund() {
+    let mut trade = Trade::start().await;
+    assert_eq!(trade.make().await.result, Ok(()));
+
+    // taker 冒充 maker：escrow PDA 的种子包含真正的 maker，约束校验失败
+    let taker = trade.taker.insecure_clone();
+    let refund = trade
+        .send(trade.refund_ix(&taker.pubkey(), &taker.pubkey()), &taker)
+        .await;
+    assert!(refund.result.is_err());
+
+    // 以真正的 maker 为收款方代为退款：托管未过期，只有 maker 本人可以调用
+    let maker = trade.maker.pubkey();
+    let crank = trade
+        .send(trade.refund_ix(&taker.pubkey(), &maker), &taker)
+        .await;
+    assert_eq!(
+        crank.result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ESCROW_NOT_EXPIRED)
+        ))
+    );
+
+    let (escrow, mint_a) = (trade.escrow, trade.mint_a);
+    assert!(trade.account(&escrow).await.is_some());
+    assert_eq!(trade.balance(&escrow, &mint_a).await, Some(AMOUNT));
+}
@@ -213,7 +213,7 @@ describe("anchor_escrow", () => {
     );
 
     const makeSig = await program.methods
-      .make(seed, receiveAmount, depositAmount)
+      .make(seed, receiveAmount, depositAmount, new BN(0), PublicKey.default)
       .accounts({
         maker,
         escrow: escrowPda,
@@ -231,6 +231,7 @@ describe("anchor_escrow", () => {
     const refundSig = await program.methods
       .refund()
       .accounts({
+        caller: maker,
         maker,
         escrow: escrowPda,
         mintA,
@@ -318,7 +319,7 @@ describe("anchor_escrow", () => {
     );
 
     const makeSig = await program.methods
-      .make(seed, receiveAmount, depositAmount)
+      .make(seed, receiveAmount, depositAmount, new BN(0), PublicKey.default)
       .accounts({
         maker,
         escrow: escrowPda,
@@ -1,18 +1,20 @@
 use pinocchio::{
     error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
     AccountView,
     ProgramResult,
 };
 use pinocchio_system::instructions::Transfer;
-use core::mem::size_of;
+use blueshift_events::{Event, VaultDepositedV2};
+use blueshift_log::bslog;
 
 /// Deposit 指令处理函数
 pub fn deposit(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
-    // 解析指令数据
-    if data.len() != size_of::<u64>() {
-        return Err(ProgramError::InvalidInstructionData);
-    }
-    let amount = u64::from_le_bytes(data.try_into().unwrap());
+    // 解析指令数据：长度必须正好是一个 u64，转换失败即返回错误
+    let amount = u64::from_le_bytes(
+        data.try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
     if amount == 0 {
         return Err(ProgramError::InsufficientFunds);
     }
@@ -35,8 +37,8 @@ pub fn deposit(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
         return Err(ProgramError::AccountAlreadyInitialized);
     }
     
-    let (vault_key, _) = pinocchio::Address::find_program_address(&[b"vault", owner.address().as_ref()], &crate::ID);
-    if vault.address() != &vault_key {
+    let (vault_key, _) = pdas::vault_pda(&crate::ID.to_bytes(), &owner.address().to_bytes());
+    if vault.address().to_bytes() != vault_key {
         return Err(ProgramError::InvalidSeeds);
     }
     
@@ -48,5 +50,14 @@ pub fn deposit(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
     }
     .invoke()?;
     
+    VaultDepositedV2 {
+        owner: owner.address().to_bytes(),
+        vault: vault.address().to_bytes(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    }
+    .emit();
+    bslog!("deposit", "lamports={}", amount);
+    
     Ok(())
 }
\ No newline at end of file
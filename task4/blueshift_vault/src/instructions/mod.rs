@@ -1,5 +1,13 @@
 pub mod deposit;
+pub mod provide_liquidity;
+pub mod remove_liquidity;
+pub mod sweep_tokens;
+pub mod version;
 pub mod withdraw;
 
 pub use deposit::deposit;
+pub use provide_liquidity::provide_liquidity;
+pub use remove_liquidity::remove_liquidity;
+pub use sweep_tokens::sweep_tokens;
+pub use version::{get_version, set_features, VERSION};
 pub use withdraw::withdraw;
\ No newline at end of file
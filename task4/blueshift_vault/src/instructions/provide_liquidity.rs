@@ -0,0 +1,314 @@
+use pinocchio::{
+    cpi::{invoke_signed, Seed, Signer},
+    error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+    AccountView,
+    ProgramResult,
+};
+use pinocchio_associated_token_account::instructions::CreateIdempotent;
+use pinocchio_system::instructions::Transfer;
+use pinocchio_token::instructions::{CloseAccount, SyncNative};
+use blueshift_events::{Event, VaultLiquidityProvided};
+use blueshift_log::bslog;
+
+/// wSOL mint：So11111111111111111111111111111111111111112
+pub(crate) const NATIVE_MINT: [u8; 32] = [
+    0x06, 0x9b, 0x88, 0x57, 0xfe, 0xab, 0x81, 0x84,
+    0xfb, 0x68, 0x7f, 0x63, 0x46, 0x18, 0xc0, 0x35,
+    0xda, 0xc4, 0x39, 0xdc, 0x1a, 0xeb, 0x3b, 0x55,
+    0x98, 0xa0, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x01,
+];
+
+// 原生 AMM（task6）Config 中用到的字段，偏移见 task6 的 snapshots/config.layout
+const CONFIG_LEN: usize = 223;
+const CONFIG_MINT_X: usize = 41;
+const CONFIG_MINT_Y: usize = 73;
+const CONFIG_RESERVE_X: usize = 190;
+const CONFIG_RESERVE_Y: usize = 198;
+
+// SPL Token 布局中的余额 / 供应量
+pub(crate) const TOKEN_AMOUNT: usize = 64;
+const MINT_SUPPLY: usize = 36;
+
+// 原生 AMM 的指令判别器
+const AMM_DEPOSIT: u8 = 1;
+const AMM_SWAP: u8 = 3;
+
+/// 指令数据：lamports、swap 最少换得、最少 LP、AMM 订单过期时间
+struct ProvideLiquidityData {
+    lamports: u64,
+    min_swap_out: u64,
+    min_lp: u64,
+    expiration: i64,
+}
+
+impl TryFrom<&[u8]> for ProvideLiquidityData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let data: &[u8; 32] = data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        let word = |i: usize| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&data[i * 8..i * 8 + 8]);
+            bytes
+        };
+        Ok(Self {
+            lamports: u64::from_le_bytes(word(0)),
+            min_swap_out: u64::from_le_bytes(word(1)),
+            min_lp: u64::from_le_bytes(word(2)),
+            expiration: i64::from_le_bytes(word(3)),
+        })
+    }
+}
+
+/// 从账户数据 `offset` 处读取 u64
+pub(crate) fn read_u64(account: &AccountView, offset: usize) -> Result<u64, ProgramError> {
+    let data = account.try_borrow()?;
+    let bytes = data
+        .get(offset..offset + 8)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let mut word = [0u8; 8];
+    word.copy_from_slice(bytes);
+    Ok(u64::from_le_bytes(word))
+}
+
+/// 校验池子：Config 归传入的 AMM 程序所有、mint 与 Config 一致且一侧是 wSOL，
+/// 代币程序是 SPL Token。返回 wSOL 是否在 x 侧
+pub(crate) fn check_pool(
+    config: &AccountView,
+    amm_program: &AccountView,
+    mint_x: &AccountView,
+    mint_y: &AccountView,
+    token_program: &AccountView,
+) -> Result<bool, ProgramError> {
+    if !config.owned_by(amm_program.address()) || config.data_len() != CONFIG_LEN {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    {
+        let config_data = config.try_borrow()?;
+        if &config_data[CONFIG_MINT_X..CONFIG_MINT_X + 32] != mint_x.address().as_ref()
+            || &config_data[CONFIG_MINT_Y..CONFIG_MINT_Y + 32] != mint_y.address().as_ref()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
+    let sol_is_x = match (
+        mint_x.address().to_bytes() == NATIVE_MINT,
+        mint_y.address().to_bytes() == NATIVE_MINT,
+    ) {
+        (true, false) => true,
+        (false, true) => false,
+        _ => return Err(ProgramError::InvalidArgument),
+    };
+    if token_program.address() != &pinocchio_token::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(sol_is_x)
+}
+
+/// ProvideLiquidity 指令处理函数
+///
+/// 把金库中的 `lamports` 变成原生 AMM 中 wSOL 池子的流动性，一笔交易内完成：
+/// 1. 从金库转入金库 PDA 的 wSOL ATA 并 SyncNative；
+/// 2. 一半 wSOL 通过 AMM Swap 换成池子另一侧的代币；
+/// 3. 按池子当前比例计算可铸造的 LP，调用 AMM Deposit，LP 存入金库 PDA 的 ATA；
+/// 4. 关闭 wSOL ATA，没存进池子的零头连同租金退回金库。
+///
+/// 金库 PDA 在 CPI 中签名，作为 AMM 的 user。AMM 程序地址由调用方传入（各程序共用
+/// 占位地址，部署后才有真实地址），这里只校验 Config 归它所有；金库 PDA 的签名
+/// 只出现在 owner 签名的交易里，而 owner 本来就可以 withdraw 全部余额，权限没有扩大。
+/// 空池子（LP 供应量为 0）会由首笔存款定价，金库不做首笔存款。
+/// 撤出用 RemoveLiquidity，LP 与零头代币用 SweepTokens 转给 owner。
+pub fn provide_liquidity(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let data = ProvideLiquidityData::try_from(data)?;
+    if data.lamports < 2 {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    // 解析账户
+    let [
+        owner,
+        vault,
+        config,
+        mint_lp,
+        mint_x,
+        mint_y,
+        pool_vault_x,
+        pool_vault_y,
+        vault_x_ata,
+        vault_y_ata,
+        vault_lp_ata,
+        amm_program,
+        token_program,
+        _associated_token_program,
+        system_program,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // 金库校验与 withdraw 相同
+    if !owner.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !vault.owned_by(&pinocchio_system::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if data.lamports > vault.lamports() {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    let (vault_key, bump) = pdas::vault_pda(&crate::ID.to_bytes(), &owner.address().to_bytes());
+    if vault.address().to_bytes() != vault_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // 池子：Config 归传入的 AMM 程序所有，且一侧是 wSOL
+    let sol_is_x = check_pool(config, amm_program, mint_x, mint_y, token_program)?;
+
+    // 金库 PDA 的 ATA（owner 付租金），已存在时 CreateIdempotent 不做任何事
+    for (ata, mint) in [(vault_x_ata, mint_x), (vault_y_ata, mint_y), (vault_lp_ata, mint_lp)] {
+        CreateIdempotent {
+            funding_account: owner,
+            account: ata,
+            wallet: vault,
+            mint,
+            system_program,
+            token_program,
+        }
+        .invoke()?;
+    }
+    let (sol_ata, token_ata) = if sol_is_x {
+        (vault_x_ata, vault_y_ata)
+    } else {
+        (vault_y_ata, vault_x_ata)
+    };
+
+    let bump_binding = [bump];
+    let seeds = [
+        Seed::from(pdas::VAULT_SEED),
+        Seed::from(owner.address().as_ref()),
+        Seed::from(&bump_binding),
+    ];
+    let signers = [Signer::from(&seeds)];
+
+    // 1. 包装 SOL
+    Transfer {
+        from: vault,
+        to: sol_ata,
+        lamports: data.lamports,
+    }
+    .invoke_signed(&signers)?;
+    SyncNative {
+        native_token: sol_ata,
+    }
+    .invoke()?;
+
+    // 2. 一半 wSOL 换成另一侧代币：[3, is_x, amount, min, expiration]
+    let mut swap_data = [0u8; 26];
+    swap_data[0] = AMM_SWAP;
+    swap_data[1] = sol_is_x as u8;
+    swap_data[2..10].copy_from_slice(&(data.lamports / 2).to_le_bytes());
+    swap_data[10..18].copy_from_slice(&data.min_swap_out.to_le_bytes());
+    swap_data[18..26].copy_from_slice(&data.expiration.to_le_bytes());
+    let swap_accounts = [
+        InstructionAccount::writable_signer(vault.address()),
+        InstructionAccount::writable(vault_x_ata.address()),
+        InstructionAccount::writable(vault_y_ata.address()),
+        InstructionAccount::writable(pool_vault_x.address()),
+        InstructionAccount::writable(pool_vault_y.address()),
+        InstructionAccount::writable(config.address()),
+        InstructionAccount::readonly(token_program.address()),
+    ];
+    invoke_signed(
+        &InstructionView {
+            program_id: amm_program.address(),
+            accounts: &swap_accounts,
+            data: &swap_data,
+        },
+        &[vault, vault_x_ata, vault_y_ata, pool_vault_x, pool_vault_y, config, token_program],
+        &signers,
+    )?;
+
+    // 3. 按 swap 之后的储备计算 LP：两侧各自能支撑的 LP 取较小者（向下取整），
+    //    AMM 按 LP 向上取整收取的数量因此不会超过金库持有的余额
+    let (x, y) = if sol_is_x {
+        (read_u64(sol_ata, TOKEN_AMOUNT)?, read_u64(token_ata, TOKEN_AMOUNT)?)
+    } else {
+        (read_u64(token_ata, TOKEN_AMOUNT)?, read_u64(sol_ata, TOKEN_AMOUNT)?)
+    };
+    let reserve_x = read_u64(config, CONFIG_RESERVE_X)?;
+    let reserve_y = read_u64(config, CONFIG_RESERVE_Y)?;
+    let supply = read_u64(mint_lp, MINT_SUPPLY)?;
+    if supply == 0 || reserve_x == 0 || reserve_y == 0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let lp_for = |amount: u64, reserve: u64| amount as u128 * supply as u128 / reserve as u128;
+    let lp = u64::try_from(lp_for(x, reserve_x).min(lp_for(y, reserve_y)))
+        .map_err(|_| ProgramError::ArithmeticOverflow)?;
+    if lp == 0 || lp < data.min_lp {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // [1, amount, max_x, max_y, expiration]
+    let mut deposit_data = [0u8; 33];
+    deposit_data[0] = AMM_DEPOSIT;
+    deposit_data[1..9].copy_from_slice(&lp.to_le_bytes());
+    deposit_data[9..17].copy_from_slice(&x.to_le_bytes());
+    deposit_data[17..25].copy_from_slice(&y.to_le_bytes());
+    deposit_data[25..33].copy_from_slice(&data.expiration.to_le_bytes());
+    let deposit_accounts = [
+        InstructionAccount::writable_signer(vault.address()),
+        InstructionAccount::writable(mint_lp.address()),
+        InstructionAccount::writable(pool_vault_x.address()),
+        InstructionAccount::writable(pool_vault_y.address()),
+        InstructionAccount::writable(vault_x_ata.address()),
+        InstructionAccount::writable(vault_y_ata.address()),
+        InstructionAccount::writable(vault_lp_ata.address()),
+        InstructionAccount::writable(config.address()),
+        InstructionAccount::readonly(token_program.address()),
+    ];
+    invoke_signed(
+        &InstructionView {
+            program_id: amm_program.address(),
+            accounts: &deposit_accounts,
+            data: &deposit_data,
+        },
+        &[
+            vault,
+            mint_lp,
+            pool_vault_x,
+            pool_vault_y,
+            vault_x_ata,
+            vault_y_ata,
+            vault_lp_ata,
+            config,
+            token_program,
+        ],
+        &signers,
+    )?;
+
+    // 4. wSOL 零头与 ATA 租金退回金库；租金不少于系统账户的免租金额，金库不会低于免租线
+    CloseAccount {
+        account: sol_ata,
+        destination: vault,
+        authority: vault,
+    }
+    .invoke_signed(&signers)?;
+
+    VaultLiquidityProvided {
+        owner: owner.address().to_bytes(),
+        vault: vault.address().to_bytes(),
+        pool: config.address().to_bytes(),
+        lamports: data.lamports,
+        lp,
+    }
+    .emit();
+    bslog!("provide_liquidity", "lamports={} lp={}", data.lamports, lp);
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use pinocchio::{
+    cpi::{invoke_signed, Seed, Signer},
+    error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+    AccountView,
+    ProgramResult,
+};
+use pinocchio_associated_token_account::instructions::CreateIdempotent;
+use pinocchio_token::instructions::CloseAccount;
+use blueshift_events::{Event, VaultLiquidityRemoved};
+use blueshift_log::bslog;
+
+use super::provide_liquidity::{check_pool, read_u64, TOKEN_AMOUNT};
+
+// 原生 AMM 的 Withdraw 判别器
+const AMM_WITHDRAW: u8 = 2;
+
+/// 指令数据：赎回的 LP、两侧最少取回、AMM 订单过期时间
+struct RemoveLiquidityData {
+    lp: u64,
+    min_x: u64,
+    min_y: u64,
+    expiration: i64,
+}
+
+impl TryFrom<&[u8]> for RemoveLiquidityData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let data: &[u8; 32] = data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        let word = |i: usize| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&data[i * 8..i * 8 + 8]);
+            bytes
+        };
+        Ok(Self {
+            lp: u64::from_le_bytes(word(0)),
+            min_x: u64::from_le_bytes(word(1)),
+            min_y: u64::from_le_bytes(word(2)),
+            expiration: i64::from_le_bytes(word(3)),
+        })
+    }
+}
+
+/// RemoveLiquidity 指令处理函数
+///
+/// ProvideLiquidity 的反向操作，账户列表与它相同：
+/// 1. 金库 PDA 签名调用 AMM Withdraw，赎回 `lp` 个 LP，两侧代币进入金库 PDA 的 ATA；
+/// 2. 关闭 wSOL ATA，wSOL 解包后连同租金回到金库的 SOL 余额，owner 之后照常 withdraw。
+///
+/// 另一侧的代币留在金库 PDA 的 ATA 中，用 SweepTokens 转到 owner 的 ATA。
+pub fn remove_liquidity(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    let data = RemoveLiquidityData::try_from(data)?;
+    if data.lp == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // 解析账户
+    let [
+        owner,
+        vault,
+        config,
+        mint_lp,
+        mint_x,
+        mint_y,
+        pool_vault_x,
+        pool_vault_y,
+        vault_x_ata,
+        vault_y_ata,
+        vault_lp_ata,
+        amm_program,
+        token_program,
+        _associated_token_program,
+        system_program,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // 金库校验与 withdraw 相同
+    if !owner.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !vault.owned_by(&pinocchio_system::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let (vault_key, bump) = pdas::vault_pda(&crate::ID.to_bytes(), &owner.address().to_bytes());
+    if vault.address().to_bytes() != vault_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let sol_is_x = check_pool(config, amm_program, mint_x, mint_y, token_program)?;
+
+    // ProvideLiquidity 结束时关闭了 wSOL ATA，这里重新创建（owner 付租金）
+    for (ata, mint) in [(vault_x_ata, mint_x), (vault_y_ata, mint_y)] {
+        CreateIdempotent {
+            funding_account: owner,
+            account: ata,
+            wallet: vault,
+            mint,
+            system_program,
+            token_program,
+        }
+        .invoke()?;
+    }
+    let (sol_ata, token_ata) = if sol_is_x {
+        (vault_x_ata, vault_y_ata)
+    } else {
+        (vault_y_ata, vault_x_ata)
+    };
+    let token_before = read_u64(token_ata, TOKEN_AMOUNT)?;
+
+    let bump_binding = [bump];
+    let seeds = [
+        Seed::from(pdas::VAULT_SEED),
+        Seed::from(owner.address().as_ref()),
+        Seed::from(&bump_binding),
+    ];
+    let signers = [Signer::from(&seeds)];
+
+    // 1. [2, amount, min_x, min_y, expiration]；LP 不足、滑点与过期都由 AMM 检查
+    let mut withdraw_data = [0u8; 33];
+    withdraw_data[0] = AMM_WITHDRAW;
+    withdraw_data[1..9].copy_from_slice(&data.lp.to_le_bytes());
+    withdraw_data[9..17].copy_from_slice(&data.min_x.to_le_bytes());
+    withdraw_data[17..25].copy_from_slice(&data.min_y.to_le_bytes());
+    withdraw_data[25..33].copy_from_slice(&data.expiration.to_le_bytes());
+    let withdraw_accounts = [
+        InstructionAccount::writable_signer(vault.address()),
+        InstructionAccount::writable(mint_lp.address()),
+        InstructionAccount::writable(pool_vault_x.address()),
+        InstructionAccount::writable(pool_vault_y.address()),
+        InstructionAccount::writable(vault_x_ata.address()),
+        InstructionAccount::writable(vault_y_ata.address()),
+        InstructionAccount::writable(vault_lp_ata.address()),
+        InstructionAccount::writable(config.address()),
+        InstructionAccount::readonly(token_program.address()),
+    ];
+    invoke_signed(
+        &InstructionView {
+            program_id: amm_program.address(),
+            accounts: &withdraw_accounts,
+            data: &withdraw_data,
+        },
+        &[
+            vault,
+            mint_lp,
+            pool_vault_x,
+            pool_vault_y,
+            vault_x_ata,
+            vault_y_ata,
+            vault_lp_ata,
+            config,
+            token_program,
+        ],
+        &signers,
+    )?;
+
+    // 2. 解包：wSOL 与 ATA 租金全部回到金库
+    let lamports = read_u64(sol_ata, TOKEN_AMOUNT)?;
+    let amount = read_u64(token_ata, TOKEN_AMOUNT)?.saturating_sub(token_before);
+    CloseAccount {
+        account: sol_ata,
+        destination: vault,
+        authority: vault,
+    }
+    .invoke_signed(&signers)?;
+
+    VaultLiquidityRemoved {
+        owner: owner.address().to_bytes(),
+        vault: vault.address().to_bytes(),
+        pool: config.address().to_bytes(),
+        lp: data.lp,
+        lamports,
+        amount,
+    }
+    .emit();
+    bslog!("remove_liquidity", "lp={} lamports={} amount={}", data.lp, lamports, amount);
+
+    Ok(())
+}
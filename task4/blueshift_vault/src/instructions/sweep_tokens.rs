@@ -0,0 +1,87 @@
+use pinocchio::{
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    AccountView,
+    ProgramResult,
+};
+use pinocchio_associated_token_account::instructions::CreateIdempotent;
+use pinocchio_token::instructions::{CloseAccount, Transfer};
+use blueshift_log::bslog;
+
+use super::provide_liquidity::{read_u64, TOKEN_AMOUNT};
+
+/// SweepTokens 指令处理函数
+///
+/// 把金库 PDA 的某个代币 ATA（LP、ProvideLiquidity 的零头、RemoveLiquidity 取回的代币）
+/// 全部转到 owner 的 ATA，然后关闭金库的 ATA，租金退给 owner。
+/// owner 的 ATA 不存在时由 owner 付租金创建；金库 ATA 的所有者由 Token 程序在转账时校验。
+pub fn sweep_tokens(accounts: &[AccountView]) -> ProgramResult {
+    // 解析账户
+    let [
+        owner,
+        vault,
+        vault_ata,
+        owner_ata,
+        mint,
+        token_program,
+        _associated_token_program,
+        system_program,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // 金库校验与 withdraw 相同
+    if !owner.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (vault_key, bump) = pdas::vault_pda(&crate::ID.to_bytes(), &owner.address().to_bytes());
+    if vault.address().to_bytes() != vault_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if token_program.address() != &pinocchio_token::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // CreateIdempotent 同时校验 owner_ata 就是 owner 在该 mint 下的 ATA
+    CreateIdempotent {
+        funding_account: owner,
+        account: owner_ata,
+        wallet: owner,
+        mint,
+        system_program,
+        token_program,
+    }
+    .invoke()?;
+
+    let bump_binding = [bump];
+    let seeds = [
+        Seed::from(pdas::VAULT_SEED),
+        Seed::from(owner.address().as_ref()),
+        Seed::from(&bump_binding),
+    ];
+    let signers = [Signer::from(&seeds)];
+
+    let amount = read_u64(vault_ata, TOKEN_AMOUNT)?;
+    if amount > 0 {
+        Transfer {
+            from: vault_ata,
+            to: owner_ata,
+            authority: vault,
+            amount,
+        }
+        .invoke_signed(&signers)?;
+    }
+    CloseAccount {
+        account: vault_ata,
+        destination: owner,
+        authority: vault,
+    }
+    .invoke_signed(&signers)?;
+
+    bslog!("sweep_tokens", "amount={}", amount);
+
+    Ok(())
+}
@@ -1,10 +1,13 @@
 use pinocchio::{
     cpi::{Seed, Signer},
     error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
     AccountView,
     ProgramResult,
 };
 use pinocchio_system::instructions::Transfer;
+use blueshift_events::{Event, VaultWithdrawnV2};
+use blueshift_log::bslog;
 
 /// Withdraw 指令处理函数
 pub fn withdraw(accounts: &[AccountView]) -> ProgramResult {
@@ -26,27 +29,37 @@ pub fn withdraw(accounts: &[AccountView]) -> ProgramResult {
         return Err(ProgramError::InsufficientFunds);
     }
     
-    let (vault_key, bump) = pinocchio::Address::find_program_address(&[b"vault", owner.address().as_ref()], &crate::ID);
-    if vault.address() != &vault_key {
+    let (vault_key, bump) = pdas::vault_pda(&crate::ID.to_bytes(), &owner.address().to_bytes());
+    if vault.address().to_bytes() != vault_key {
         return Err(ProgramError::InvalidSeeds);
     }
     
     // 创建 PDA 签名种子
     let bump_binding = [bump];
     let seeds = [
-        Seed::from(b"vault"),
+        Seed::from(pdas::VAULT_SEED),
         Seed::from(owner.address().as_ref()),
         Seed::from(&bump_binding),
     ];
     let signers = [Signer::from(&seeds)];
     
     // 执行转账（使用 PDA 签名）
+    let amount = vault.lamports();
     Transfer {
         from: vault,
         to: owner,
-        lamports: vault.lamports(),
+        lamports: amount,
     }
     .invoke_signed(&signers)?;
     
+    VaultWithdrawnV2 {
+        owner: owner.address().to_bytes(),
+        vault: vault.address().to_bytes(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    }
+    .emit();
+    bslog!("withdraw", "lamports={}", amount);
+    
     Ok(())
 }
\ No newline at end of file
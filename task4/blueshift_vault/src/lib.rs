@@ -1,4 +1,6 @@
 #![no_std]
+// 程序代码不允许 unwrap / expect / panic!，出错一律返回 ProgramError
+#![deny(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
 
 use pinocchio::{
     entrypoint,
@@ -8,10 +10,24 @@ use pinocchio::{
     AccountView,
     ProgramResult,
 };
+use blueshift_version::{GET_VERSION_DISCRIMINATOR, SET_FEATURES_DISCRIMINATOR};
 
 entrypoint!(process_instruction);
 nostd_panic_handler!();
 
+// 文本日志前缀 `[vault v<版本>]`；`--features quiet` 构建时 bslog! 全部去掉
+blueshift_log::program!("vault");
+
+// 可选的 security.txt 段（security-txt feature），披露流程见仓库根目录 SECURITY.md
+#[cfg(feature = "security-txt")]
+solana_security_txt::security_txt! {
+    name: "Blueshift Pinocchio Vault",
+    project_url: "https://github.com/o7reconversion/solana_bootcamp_2026",
+    contacts: "link:https://github.com/o7reconversion/solana_bootcamp_2026/security/advisories/new",
+    policy: "https://github.com/o7reconversion/solana_bootcamp_2026/blob/main/SECURITY.md",
+    source_code: "https://github.com/o7reconversion/solana_bootcamp_2026/tree/main/task4/blueshift_vault"
+}
+
 pub mod instructions;
 pub use instructions::*;
 
@@ -29,9 +45,19 @@ fn process_instruction(
     accounts: &[AccountView],
     instruction_data: &[u8],
 ) -> ProgramResult {
+    // get_version / set_features 的判别器是 8 字节，与其他训练营程序相同
+    if instruction_data.starts_with(&GET_VERSION_DISCRIMINATOR) {
+        return get_version(accounts);
+    }
+    if let Some(data) = instruction_data.strip_prefix(&SET_FEATURES_DISCRIMINATOR) {
+        return set_features(data, accounts);
+    }
     match instruction_data.split_first() {
         Some((0, data)) => deposit(data, accounts),
         Some((1, _)) => withdraw(accounts),
+        Some((2, data)) => provide_liquidity(data, accounts),
+        Some((3, data)) => remove_liquidity(data, accounts),
+        Some((4, _)) => sweep_tokens(accounts),
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
\ No newline at end of file
@@ -0,0 +1,291 @@
+// =============================================================================
+// 构建脚本 - 生成 JSON IDL
+// =============================================================================
+// Anchor 版托管由 `anchor build` 生成 IDL；Pinocchio 版没有宏可以读取，
+// 这里按 src/instructions 中的 TryFrom 实现手写一份，构建时写入 target/idl/blueshift_escrow.json。
+//
+// 修改账户顺序、指令数据布局或 Escrow / Config / OfferIndex 结构体时，需要同步修改本文件。
+// 错误直接取自 blueshift_errors，不会与程序返回的错误码不一致。
+
+use blueshift_errors::{AccountError, EscrowError};
+use blueshift_idl::{Account, Field, Idl, Instruction, Type, TypeDef};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let idl = Idl::new(
+        "blueshift_escrow",
+        "22222222222222222222222222222222222222222222",
+    )
+    .doc("Pinocchio 托管：maker 存入代币 A，taker 支付代币 B 换走金库中的代币 A")
+    // Make：对应 src/instructions/make.rs
+    .instruction(
+        Instruction::new("make", 0)
+            .doc("创建托管账户与金库 ATA，并把 amount 个代币 A 存入金库")
+            .account(Account::new("maker").writable().signer())
+            .account(
+                Account::new("escrow")
+                    .writable()
+                    .doc("PDA: [\"escrow\", maker, seed (u64 小端)]"),
+            )
+            .account(
+                Account::new("mint_a").doc("wSOL 时 amount lamports 直接从 maker 包装进金库"),
+            )
+            .account(Account::new("mint_b"))
+            .account(
+                Account::new("maker_ata_a")
+                    .writable()
+                    .doc("mint_a 是 wSOL 时不使用，可以不存在"),
+            )
+            .account(
+                Account::new("vault")
+                    .writable()
+                    .doc("escrow 持有的代币 A ATA"),
+            )
+            .account(Account::new("system_program"))
+            .account(
+                Account::new("token_program")
+                    .doc("Token Program 或 Token-2022，必须是两个 mint 的 owner"),
+            )
+            .account(Account::new("associated_token_program"))
+            .account(
+                Account::new("offer_index")
+                    .writable()
+                    .doc("PDA: [\"offers\", maker]，登记新托管单的 seed；不存在时由 maker 付费创建"),
+            )
+            .arg("seed", Type::U64)
+            .arg_with(Field::new("receive", Type::U64).doc("maker 希望收到的代币 B 数量"))
+            .arg_with(Field::new("amount", Type::U64).doc("存入的代币 A 数量，必须大于 0"))
+            .arg_with(
+                Field::new("expiry", Type::I64)
+                    .doc("过期时间（Unix 时间戳），0 表示永不过期，否则必须晚于当前时间"),
+            )
+            .arg_with(
+                Field::new("taker", Type::PublicKey)
+                    .doc("指定的接受者，全零表示任何人都可以 take"),
+            ),
+    )
+    // Take：对应 src/instructions/take.rs
+    .instruction(
+        Instruction::new("take", 1)
+            .doc("taker 向 maker 支付 receive 个代币 B，取走金库中的全部代币 A 并关闭托管；托管过期或 taker 不是指定的接受者时拒绝")
+            .account(Account::new("taker").writable().signer())
+            .account(Account::new("maker").writable())
+            .account(Account::new("escrow").writable())
+            .account(
+                Account::new("mint_a").doc("金库上有未提取的 Token-2022 转账手续费时必须可写，关闭前归集到 mint"),
+            )
+            .account(
+                Account::new("mint_b").doc("带 Token-2022 转账手续费时由 taker 多付，maker 到账的正好是 receive；wSOL 时 taker 直接付 lamports 给 maker"),
+            )
+            .account(Account::new("vault").writable())
+            .account(
+                Account::new("taker_ata_a")
+                    .writable()
+                    .doc("不存在时由 taker 付费创建；mint_a 是 wSOL 时不使用，金库的 SOL 直接转给 taker"),
+            )
+            .account(
+                Account::new("taker_ata_b")
+                    .writable()
+                    .doc("mint_b 是 wSOL 时不使用"),
+            )
+            .account(
+                Account::new("maker_ata_b")
+                    .writable()
+                    .doc("不存在时由 taker 付费创建；mint_b 是 wSOL 时不使用"),
+            )
+            .account(Account::new("system_program"))
+            .account(
+                Account::new("token_program")
+                    .doc("Token Program 或 Token-2022，必须是两个 mint 的 owner"),
+            )
+            .account(Account::new("associated_token_program"))
+            .account(
+                Account::new("config").doc("PDA: [\"config\"]，尚未由 init_config 创建时不收协议手续费"),
+            )
+            .account(
+                Account::new("treasury_ata_b")
+                    .writable()
+                    .doc("Config.treasury 在 mint_b 上的 ATA，协议手续费不为 0 时必须已创建"),
+            )
+            .account(
+                Account::new("offer_index")
+                    .writable()
+                    .doc("PDA: [\"offers\", maker]，关闭托管时注销其 seed；不存在时跳过"),
+            )
+            .arg_with(
+                Field::new("max_receive", Type::U64)
+                    .doc("taker 最多支付的代币 B，托管中的 receive 超过它时拒绝；可以省略（指令数据为空），省略时不限制"),
+            ),
+    )
+    // TakePartial：对应 src/instructions/take_partial.rs，账户与 Take 相同
+    .instruction(
+        Instruction::new("take_partial", 3)
+            .doc("taker 买走金库中的 amount 个代币 A，按比例（向上取整）支付代币 B；买完时与 take 相同，没买完时本次支付必须小于剩余的 receive")
+            .account(Account::new("taker").writable().signer())
+            .account(Account::new("maker").writable())
+            .account(Account::new("escrow").writable())
+            .account(
+                Account::new("mint_a").doc("金库上有未提取的 Token-2022 转账手续费时必须可写，关闭前归集到 mint"),
+            )
+            .account(Account::new("mint_b").doc("与 take 相同"))
+            .account(Account::new("vault").writable())
+            .account(
+                Account::new("taker_ata_a")
+                    .writable()
+                    .doc("不存在时由 taker 付费创建；mint_a 是 wSOL 且没有买完时，本指令创建的 ATA 随即关闭、taker 收到 lamports，原有的 ATA 收到 wSOL"),
+            )
+            .account(Account::new("taker_ata_b").writable().doc("与 take 相同"))
+            .account(
+                Account::new("maker_ata_b")
+                    .writable()
+                    .doc("与 take 相同"),
+            )
+            .account(Account::new("system_program"))
+            .account(
+                Account::new("token_program")
+                    .doc("Token Program 或 Token-2022，必须是两个 mint 的 owner"),
+            )
+            .account(Account::new("associated_token_program"))
+            .account(Account::new("config").doc("与 take 相同"))
+            .account(Account::new("treasury_ata_b").writable().doc("与 take 相同"))
+            .account(Account::new("offer_index").writable().doc("与 take 相同，全部买走时注销"))
+            .arg_with(
+                Field::new("amount", Type::U64).doc("想要的代币 A 数量，0 < amount <= 金库余额"),
+            )
+            .arg_with(
+                Field::new("max_receive", Type::U64)
+                    .doc("本次最多支付的代币 B，与 take 相同可以省略"),
+            ),
+    )
+    // Refund：对应 src/instructions/refund.rs
+    .instruction(
+        Instruction::new("refund", 2)
+            .doc("把金库中的代币 A 退还 maker 并关闭托管；maker 随时可以调用，过期后任何人都可以调用")
+            .account(
+                Account::new("caller")
+                    .writable()
+                    .signer()
+                    .doc("不是 maker 时要求托管已过期；maker_ata_a 不存在时由它付费创建"),
+            )
+            .account(Account::new("maker").writable())
+            .account(Account::new("escrow").writable())
+            .account(
+                Account::new("mint_a").doc("金库上有未提取的 Token-2022 转账手续费时必须可写，关闭前归集到 mint"),
+            )
+            .account(Account::new("vault").writable())
+            .account(
+                Account::new("maker_ata_a")
+                    .writable()
+                    .doc("mint_a 是 wSOL 时不使用，金库的 SOL 直接退回 maker"),
+            )
+            .account(Account::new("system_program"))
+            .account(
+                Account::new("token_program")
+                    .doc("Token Program 或 Token-2022，必须是 mint_a 的 owner"),
+            )
+            .account(Account::new("associated_token_program"))
+            .account(Account::new("offer_index").writable().doc("与 take 相同")),
+    )
+    // InitConfig：对应 src/instructions/init_config.rs
+    .instruction(
+        Instruction::new("init_config", 4)
+            .doc("升级权限创建全局配置，之后 take / take_partial 从 maker 收到的代币 B 中扣下协议手续费")
+            .account(
+                Account::new("authority")
+                    .writable()
+                    .signer()
+                    .doc("程序的升级权限，支付 config 的租金"),
+            )
+            .account(Account::new("program_data").doc("本程序在 Upgradeable Loader 下的 ProgramData"))
+            .account(Account::new("config").writable().doc("PDA: [\"config\"]，只能创建一次"))
+            .account(Account::new("system_program"))
+            .arg_with(
+                Field::new("fee_bps", Type::U16).doc("协议手续费率（基点），不超过 1000"),
+            )
+            .arg_with(
+                Field::new("treasury", Type::PublicKey).doc("手续费接收方，手续费转入它在 mint_b 上的 ATA"),
+            ),
+    )
+    // UpdateOffer：对应 src/instructions/update_offer.rs
+    .instruction(
+        Instruction::new("update_offer", 5)
+            .doc("maker 修改未过期托管的 mint_b 与 receive，金库与托管账户保持不变")
+            .account(Account::new("maker").signer())
+            .account(Account::new("escrow").writable())
+            .account(Account::new("mint_a").doc("必须是托管中记录的 mint_a，用来核对代币程序"))
+            .account(Account::new("mint_b").doc("新的代币 B，与原来相同时只修改 receive"))
+            .account(
+                Account::new("token_program")
+                    .doc("Token Program 或 Token-2022，必须是两个 mint 的 owner"),
+            )
+            .arg_with(Field::new("receive", Type::U64).doc("新的报价，必须大于 0")),
+    )
+    // TopUp：对应 src/instructions/top_up.rs
+    .instruction(
+        Instruction::new("top_up", 6)
+            .doc("maker 向未过期托管的金库追加代币 A，并把整单报价改为 receive")
+            .account(
+                Account::new("maker")
+                    .writable()
+                    .signer()
+                    .doc("mint_a 是原生 SOL 时直接从这里付 lamports"),
+            )
+            .account(Account::new("escrow").writable())
+            .account(Account::new("mint_a"))
+            .account(Account::new("maker_ata_a").writable().doc("mint_a 是原生 SOL 时不使用"))
+            .account(Account::new("vault").writable().doc("escrow 在 mint_a 上的 ATA"))
+            .account(
+                Account::new("token_program")
+                    .doc("Token Program 或 Token-2022，必须是 mint_a 的 owner"),
+            )
+            .account(
+                Account::new("system_program")
+                    .optional()
+                    .doc("mint_a 是原生 SOL 时必须传入，用于把 lamports 转进 wSOL 金库"),
+            )
+            .arg_with(Field::new("amount", Type::U64).doc("追加的代币 A 数量，必须大于 0"))
+            .arg_with(Field::new("receive", Type::U64).doc("追加之后整个金库的报价，不是增量")),
+    )
+    // Escrow 账户：src/state.rs，共 Escrow::LEN = 154 字节，字段之间没有填充
+    .account(
+        TypeDef::new("Escrow")
+            .field_with(
+                Field::new("discriminator", Type::array(Type::U8, 1))
+                    .doc("固定为 1，与 Anchor 版 #[account(discriminator = 1)] 相同"),
+            )
+            .field("seed", Type::U64)
+            .field("maker", Type::PublicKey)
+            .field("mint_a", Type::PublicKey)
+            .field("mint_b", Type::PublicKey)
+            .field("receive", Type::U64)
+            .field("bump", Type::array(Type::U8, 1))
+            .field("expiry", Type::I64)
+            .field("taker", Type::PublicKey),
+    )
+    // Config 账户：src/state.rs，共 Config::LEN = 35 字节
+    .account(
+        TypeDef::new("Config")
+            .field("treasury", Type::PublicKey)
+            .field("fee_bps", Type::U16)
+            .field("bump", Type::array(Type::U8, 1)),
+    )
+    // OfferIndex 账户：src/state.rs，共 OfferIndex::LEN = 131 字节
+    .account(
+        TypeDef::new("OfferIndex")
+            .field_with(Field::new("discriminator", Type::array(Type::U8, 1)).doc("固定为 2"))
+            .field("bump", Type::array(Type::U8, 1))
+            .field_with(Field::new("count", Type::U8).doc("已登记的 seed 个数"))
+            .field_with(
+                Field::new("seeds", Type::array(Type::U64, 16))
+                    .doc("未成交托管单的 seed，前 count 个有效，顺序不固定"),
+            ),
+    )
+    .errors(EscrowError::ALL, EscrowError::code)
+    .errors(AccountError::ALL, AccountError::code);
+
+    if let Err(err) = idl.write() {
+        // IDL 只是附带产物，写入失败不应阻止程序构建
+        println!("cargo:warning=写入 IDL 失败: {err}");
+    }
+}
@@ -10,52 +10,28 @@
 //
 // 本模块通过 Trait 和零大小类型（ZST）实现类型安全的账户验证
 
-use pinocchio::{AccountView, Address, ProgramResult};
-use pinocchio::cpi::{Seed, Signer};
+use pinocchio::{AccountView, ProgramResult};
 use pinocchio::error::ProgramError;
+use pinocchio::cpi::{Seed, Signer};
 use pinocchio::sysvars::rent::Rent;
 use pinocchio::sysvars::Sysvar;
 use pinocchio_associated_token_account::instructions::Create;
 use pinocchio_system::instructions::CreateAccount;
-use crate::errors::EscrowError;
+use crate::state::{Config, Escrow, OfferIndex};
 
 // =============================================================================
-// AccountCheck Trait - 基础账户验证
+// 账户校验 - 来自 blueshift_common
 // =============================================================================
-// 定义账户验证的统一接口
+// AccountCheck / SignerAccount / SystemAccount / MintInterface / TokenAccountInterface /
+// AssociatedTokenAccount 的校验逻辑由各原生程序共享（原生 AMM 也在用），
+// 这里重新导出，指令中的用法保持不变。
 //
-// 对应 Anchor 的约束：
-// - 所有账户验证的基础
-// - 可以组合多个约束使用
-pub trait AccountCheck {
-    // 验证账户是否符合要求
-    // 如果不符合，返回相应的错误
-    fn check(account: &AccountView) -> Result<(), ProgramError>;
-}
-
-// =============================================================================
-// AssociatedTokenAccountCheck Trait - ATA 验证
-// =============================================================================
-// 验证关联代币账户（ATA）的接口
-//
-// 对应 Anchor 的约束：
-// - associated_token::authority = xxx
-// - associated_token::mint = xxx
-// - associated_token::token_program = xxx
-//
-// ATA 是通过 PDA 派生的特殊代币账户，地址由以下决定：
-// - owner 的地址
-// - token_program 的地址
-// - mint 的地址
-pub trait AssociatedTokenAccountCheck{
-    // 验证账户是否是指定 owner、mint 和 token_program 的 ATA
-    fn check(
-        account: &AccountView,
-        authority: &AccountView,    // 对应 Anchor 中的 authority 约束
-        mint: &AccountView,          // 对应 Anchor 中的 mint 约束
-        token_program: &AccountView, // 对应 Anchor 中的 token_program 约束
-    ) -> Result<(), ProgramError>;
-}
+// 创建和关闭账户需要 CPI，与本程序的 pinocchio 版本绑定，所以仍留在本模块。
+pub use blueshift_common::{
+    AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountCheck, MintInterface,
+    ProgramOwned, SignerAccount, SystemAccount, TokenAccountInterface, TOKEN_2022_PROGRAM_ID,
+    TOKEN_2022_MINT_DISCRIMINATOR, TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR,
+};
 
 // =============================================================================
 // AssociatedTokenAccountInit Trait - ATA 创建
@@ -89,249 +65,6 @@ pub trait AssociatedTokenAccountInit{
     ) -> ProgramResult;
 }
 
-// =============================================================================
-// SignerAccount - 签名者账户验证
-// =============================================================================
-// 对应 Anchor 的约束：#[account(signer)]
-//
-// Anchor 版本：
-//   #[account(signer)]
-//   pub maker: Signer<'info>,
-//
-// Pinocchio 版本：
-//   使用 SignerAccount 类型 + AccountCheck trait 验证
-//
-// 功能：
-// - 验证账户是否签名（is_signer()）
-// - 必须由私钥持有者签名（或 PDA 签名）
-//
-// 使用场景：
-// - 需要授权操作的账户（如创建者、接受者）
-// - 需要支付费用的账户
-pub struct SignerAccount;
-
-impl AccountCheck for SignerAccount {
-    fn check(account: &AccountView) -> Result<(), ProgramError> {
-        // is_signer() 检查账户是否在交易的签名者列表中
-        // 对应 Anchor 的 Signer 类型自动进行的验证
-        if !account.is_signer() {
-            return Err(EscrowError::NotSigner.into());
-        }
-        Ok(())
-    }
-}
-
-// =============================================================================
-// SystemAccount - 系统账户验证
-// =============================================================================
-// 对应 Anchor 的约束：SystemAccount<'info>
-//
-// Anchor 版本：
-//   pub maker: SystemAccount<'info>,
-//
-// 功能：
-// - 验证账户由 System Program 拥有
-// - 不要求签名
-//
-// 使用场景：
-// - 接收资金的普通账户（如 Take 指令中的 maker）
-// - 只读的系统账户
-pub struct SystemAccount;
-
-impl AccountCheck for SystemAccount {
-    fn check(account: &AccountView) -> Result<(), ProgramError> {
-        // owned_by() 检查账户的 owner 是否为指定程序
-        // System Program 的 ID 是固定的
-        if !account.owned_by(&pinocchio_system::ID) {
-            return Err(EscrowError::InvalidOwner.into());
-        }
-
-        Ok(())
-    }
-}
-
-// =============================================================================
-// Token-2022 Program 常量
-// =============================================================================
-// Token-2022 是新版 Token Program，与原版兼容但增加了扩展功能
-
-// Token-2022 Program ID
-// TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb
-pub const TOKEN_2022_PROGRAM_ID: Address = Address::new_from_array(
-    [
-    0x06, 0xdd, 0xf6, 0xe1, 0xee, 0x75, 0x8f, 0xde, 0x18, 0x42, 0x5d, 0xbc, 0xe4, 0x6c, 0xcd, 0xda,
-    0xb6, 0x1a, 0xfc, 0x4d, 0x83, 0xb9, 0x0d, 0x27, 0xfe, 0xbd, 0xf9, 0x28, 0xd8, 0xa1, 0x8b, 0xfc,
-]);
-
-// Token-2022 账户中判别器的偏移量
-// Token-2022 在账户数据的第 165 字节存储判别器
-const TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET: usize = 165;
-
-// Token-2022 Mint 账户的判别器值
-pub const TOKEN_2022_MINT_DISCRIMINATOR: u8 = 0x01;
-
-// Token-2022 Token Account 的判别器值
-pub const TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR: u8 = 0x02;
-
-// =============================================================================
-// MintInterface - Mint 账户验证（支持 Token Program 和 Token-2022）
-// =============================================================================
-// 对应 Anchor 的约束：InterfaceAccount<'info, Mint>
-//
-// Anchor 版本：
-//   pub mint_a: InterfaceAccount<'info, Mint>,
-//
-// 功能：
-// - 验证账户由 Token Program 或 Token-2022 Program 拥有
-// - 验证账户数据长度正确
-// - 支持 Token Program 的两个版本
-//
-// 为什么需要 Interface？
-// - Token Program 有两个版本（旧版和 Token-2022）
-// - InterfaceAccount 可以同时支持两个版本
-// - 提供更好的兼容性
-pub struct MintInterface;
-
-impl AccountCheck for MintInterface {
-    fn check(account: &AccountView) -> Result<(), ProgramError> {
-        // 检查是否由 Token-2022 Program 拥有
-        if !account.owned_by(&TOKEN_2022_PROGRAM_ID) {
-            // 如果不是 Token-2022，检查是否是旧版 Token Program
-            if !account.owned_by(&pinocchio_token::ID) {
-                return Err(EscrowError::InvalidOwner.into());
-            } else {
-                // 旧版 Token Program 的 Mint 账户长度验证
-                if account.data_len().ne(&pinocchio_token::state::Mint::LEN) {
-                    return Err(EscrowError::InvalidAccountData.into());
-                }
-            }
-        } else {
-            // Token-2022 的 Mint 账户验证
-            let data = account.try_borrow()?;
-
-            // Token-2022 账户可能更长（因为有扩展）
-            // 如果长度等于旧版长度，说明没有扩展，直接通过
-            if data.len().ne(&pinocchio_token::state::Mint::LEN) {
-                // 如果长度小于判别器偏移量，数据无效
-                if data.len().le(&TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET) {
-                    return Err(EscrowError::InvalidAccountData.into());
-                }
-                // 检查判别器是否为 Mint 类型（0x01）
-                if data[TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET].ne(&TOKEN_2022_MINT_DISCRIMINATOR) {
-                    return Err(EscrowError::InvalidAccountData.into());
-                }
-            }
-        }
-
-        Ok(())
-    }
-}
-
-// =============================================================================
-// TokenAccountInterface - Token Account 验证
-// =============================================================================
-// 对应 Anchor 的约束：InterfaceAccount<'info, TokenAccount>
-//
-// Anchor 版本：
-//   pub vault: InterfaceAccount<'info, TokenAccount>,
-//
-// 功能：
-// - 验证账户由 Token Program 或 Token-2022 Program 拥有
-// - 验证账户数据长度和判别器
-// - 支持两个版本的 Token Program
-pub struct TokenAccountInterface;
-
-impl AccountCheck for TokenAccountInterface {
-    fn check(account: &AccountView) -> Result<(), ProgramError> {
-        // 检查是否由 Token-2022 Program 拥有
-        if !account.owned_by(&TOKEN_2022_PROGRAM_ID) {
-            // 如果不是 Token-2022，检查是否是旧版 Token Program
-            if !account.owned_by(&pinocchio_token::ID) {
-                return Err(EscrowError::InvalidOwner.into());
-            } else {
-                // 旧版 Token Account 长度验证
-                if account.data_len().ne(&pinocchio_token::state::TokenAccount::LEN) {
-                    return Err(EscrowError::InvalidAccountData.into());
-                }
-            }
-        } else {
-            // Token-2022 Token Account 验证
-            let data = account.try_borrow()?;
-
-            if data.len().ne(&pinocchio_token::state::TokenAccount::LEN) {
-                // 检查长度是否足够包含判别器
-                if data.len().le(&TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET) {
-                    return Err(EscrowError::InvalidAccountData.into());
-                }
-                // 检查判别器是否为 Token Account 类型（0x02）
-                if data[TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET]
-                    .ne(&TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR)
-                {
-                    return Err(EscrowError::InvalidAccountData.into());
-                }
-            }
-        }
-
-        Ok(())
-    }
-}
-
-// =============================================================================
-// AssociatedTokenAccount - 关联代币账户验证
-// =============================================================================
-// 对应 Anchor 的约束：
-// - associated_token::authority = xxx
-// - associated_token::mint = xxx
-// - associated_token::token_program = xxx
-//
-// Anchor 版本：
-//   #[account(
-//       associated_token::authority = maker,
-//       associated_token::mint = mint_a,
-//       associated_token::token_program = token_program
-//   )]
-//   pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
-//
-// 验证逻辑：
-// 1. 先验证是否是有效的 Token Account
-// 2. 计算 ATA 的 PDA 地址
-// 3. 验证计算出的地址与传入的账户地址是否匹配
-pub struct AssociatedTokenAccount;
-
-impl AssociatedTokenAccountCheck for AssociatedTokenAccount {
-    fn check(
-        account: &AccountView,
-        authority: &AccountView,
-        mint: &AccountView,
-        token_program: &AccountView,
-    ) -> Result<(), ProgramError> {
-        // 先验证账户是否是有效的 Token Account
-        TokenAccountInterface::check(account)?;
-
-        // 计算 ATA 的 PDA 地址
-        // ATA 的派生种子：[authority, token_program, mint]
-        let (pda, _bump) = Address::find_program_address(
-            &[
-                authority.address().as_ref(),    // 所有者地址
-                token_program.address().as_ref(),  // Token Program 地址
-                mint.address().as_ref(),           // Mint 地址
-            ],
-            &pinocchio_associated_token_account::ID,  // ATA Program ID
-        );
-
-        // 将计算出的 PDA 地址转换为 Pinocchio 的 Address 类型
-        let pda_address = Address::new_from_array(pda.to_bytes());
-
-        // 验证计算出的 PDA 地址是否与传入的账户地址匹配
-        // 这确保传入的账户确实是正确的 ATA
-        if pda_address.ne(account.address()) {
-            return Err(EscrowError::InvalidAddress.into());
-        }
-
-        Ok(())
-    }
-}
-
 // =============================================================================
 // AssociatedTokenAccount 创建实现
 // =============================================================================
@@ -388,37 +121,32 @@ impl AssociatedTokenAccountInit for AssociatedTokenAccount {
 // =============================================================================
 // 对应 Anchor 的约束：Account<'info, Escrow>
 //
-// Anchor 版本：
-//   #[account(
-//       mut,
-//       seeds = [...],
-//       bump = escrow.bump,
-//   )]
-//   pub escrow: Account<'info, Escrow>,
-//
-// 功能：
-// - 验证账户由本程序拥有（owner == program_id）
-// - 验证账户数据长度正确
+// 校验逻辑（owner == 本程序、数据长度 == Escrow::LEN）在 blueshift_common::ProgramAccount 中，
+// 通过为 Escrow 实现 ProgramOwned 指定程序 ID 和长度。
 //
 // 注意：
 // - PDA 验证（seeds、bump）需要在指令中单独进行
-pub struct ProgramAccount;
+pub type ProgramAccount = blueshift_common::ProgramAccount<Escrow>;
 
-impl AccountCheck for ProgramAccount {
-    fn check(account: &AccountView) -> Result<(), ProgramError> {
-        // 验证账户由本程序拥有
-        // 对应 Anchor 的 Account<T> 自动进行的 owner 检查
-        if !account.owned_by(&crate::ID) {
-            return Err(EscrowError::InvalidOwner.into());
-        }
+impl ProgramOwned for Escrow {
+    const PROGRAM_ID: pinocchio::Address = crate::ID;
+    const LEN: usize = Escrow::LEN;
+}
 
-        // 验证账户数据长度是否匹配 Escrow 结构体
-        if account.data_len().ne(&crate::state::Escrow::LEN) {
-            return Err(EscrowError::InvalidAccountData.into());
-        }
+// 全局配置账户 ["config"]：同样只校验 owner 与长度，地址由 Take 与 PDA 比对
+pub type ConfigAccount = blueshift_common::ProgramAccount<Config>;
 
-        Ok(())
-    }
+impl ProgramOwned for Config {
+    const PROGRAM_ID: pinocchio::Address = crate::ID;
+    const LEN: usize = Config::LEN;
+}
+
+// 创建者的挂单索引 ["offers", maker]：owner 与长度在这里校验，判别器由 OfferIndex::load 检查
+pub type OfferIndexAccount = blueshift_common::ProgramAccount<OfferIndex>;
+
+impl ProgramOwned for OfferIndex {
+    const PROGRAM_ID: pinocchio::Address = crate::ID;
+    const LEN: usize = OfferIndex::LEN;
 }
 
 // =============================================================================
@@ -504,6 +232,15 @@ pub trait AccountClose {
     fn close(account: &AccountView, destination: &AccountView) -> ProgramResult;
 }
 
+// 关闭账户后目标账户的余额：两者之和，溢出时返回错误而不是 panic
+// verify/escrow 中的 Kani 证明检查它既不溢出也不丢失 lamports
+#[inline(always)]
+pub fn merged_lamports(destination: u64, closed: u64) -> Result<u64, ProgramError> {
+    destination
+        .checked_add(closed)
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
 impl AccountClose for ProgramAccount {
     fn close(account: &AccountView, destination: &AccountView) -> ProgramResult {
         {
@@ -515,7 +252,7 @@ impl AccountClose for ProgramAccount {
 
         // 将账户的 lamports 转给目标账户
         // 对应 Anchor 的 close = destination 约束
-        destination.set_lamports(destination.lamports()+account.lamports());
+        destination.set_lamports(merged_lamports(destination.lamports(), account.lamports())?);
 
         // 将账户大小缩减到 1 字节（只剩下 0xff 标记）
         account.resize(1)?;
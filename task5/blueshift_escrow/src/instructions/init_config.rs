@@ -0,0 +1,86 @@
+// =============================================================================
+// InitConfig 指令 - 创建全局配置（协议手续费）
+// =============================================================================
+// 升级权限创建 Config PDA ["config"]，记录协议手续费的接收方与费率：
+//
+//   账户：authority（升级权限，签名并支付租金）、program_data、config、system_program
+//   数据：fee_bps (u16 小端) + treasury (32 字节)
+//
+// 每个部署只能创建一次（账户已存在时 CreateAccount 失败）；
+// 只允许升级权限调用，避免部署后被别人抢先设置接收方。
+// Take / TakePartial 按 Config 中的费率从创建者收到的代币 B 里扣下手续费，
+// 转入 treasury 在 mint_b 上的 ATA；配置不存在时不收手续费。
+
+use pinocchio::{AccountView, Address, ProgramResult};
+use pinocchio::cpi::Seed;
+use pinocchio::error::ProgramError;
+use blueshift_common::feature_gate;
+use blueshift_log::bslog;
+use crate::{Config, EscrowError, ProgramAccount, ProgramAccountInit};
+
+// =============================================================================
+// InitConfig 指令主结构体
+// =============================================================================
+pub struct InitConfig<'info> {
+    pub authority: &'info AccountView,
+    pub config: &'info AccountView,
+    pub fee_bps: u16,
+    pub treasury: Address,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountView])> for InitConfig<'info> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'info [u8], &'info [AccountView])) -> Result<Self, Self::Error> {
+        let [authority, program_data, config, _system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 指令数据：u16 费率 + 32 字节地址
+        if data.len() != size_of::<u16>() + size_of::<Address>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let fee_bps = u16::from_le_bytes([data[0], data[1]]);
+        let treasury = data[2..]
+            .first_chunk::<32>()
+            .map(|bytes| Address::new_from_array(*bytes))
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        if fee_bps > Config::MAX_FEE_BPS {
+            return Err(EscrowError::FeeTooHigh.into());
+        }
+
+        // 签名者必须是本程序当前的升级权限
+        feature_gate::check_upgrade_authority(&crate::ID, program_data, authority)?;
+
+        Ok(Self {
+            authority,
+            config,
+            fee_bps,
+            treasury,
+        })
+    }
+}
+
+impl<'info> InitConfig<'info> {
+    // 指令判别器：TakePartial = 3 之后的下一个
+    pub const DISCRIMINATOR: &'info u8 = &4;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let (config_key, bump) = pdas::escrow_config_pda(&crate::ID.to_bytes());
+        if config_key != self.config.address().to_bytes() {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let bump = [bump];
+        let seeds = [
+            Seed::from(pdas::CONFIG_SEED),
+            Seed::from(&bump),
+        ];
+        ProgramAccount::init::<Config>(self.authority, self.config, &seeds, Config::LEN)?;
+
+        Config::load_mut(&mut self.config.try_borrow_mut()?)?.set_inner(self.treasury, self.fee_bps, bump);
+
+        bslog!("init_config", "fee_bps={}", self.fee_bps);
+        Ok(())
+    }
+}
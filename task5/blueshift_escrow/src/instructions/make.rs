@@ -2,15 +2,20 @@
 // Make 指令 - Pinocchio 版本
 // =============================================================================
 // 本指令用于创建一个新的托管交易
-// 创建者将代币 A 存入金库，并指定希望获得的代币 B 数量
+// 创建者将代币 A 存入金库，并指定希望获得的代币 B 数量，以及可选的过期时间和指定的接受者
+// 新托管单的 seed 同时登记到创建者的挂单索引 ["offers", maker]（Anchor 版没有这个账户）
+// 代币 A 是原生 SOL（mint_a 为 wSOL）时直接从创建者的 lamports 包装进金库，maker_ata_a 不会被使用
 //
 // 与 Anchor 版本的对应关系见下方各部分注释
 
-use pinocchio::{Address, AccountView, ProgramResult};
+use pinocchio::{AccountView, Address, ProgramResult};
 use pinocchio::cpi::Seed;
 use pinocchio::error::ProgramError;
-use pinocchio_token::instructions::Transfer;
-use crate::{AccountCheck, SignerAccount, MintInterface, AssociatedTokenAccount, AssociatedTokenAccountCheck, ProgramAccount, Escrow, ProgramAccountInit, AssociatedTokenAccountInit};
+use pinocchio::sysvars::{clock::Clock, Sysvar};
+use blueshift_common::{read_i64_le, read_u64_le};
+use blueshift_events::{Event, EscrowMade};
+use blueshift_log::bslog;
+use crate::{check_token_program, is_native_mint, register_offer, wrap_lamports, mint_decimals, TransferChecked, EscrowError, AccountCheck, SignerAccount, MintInterface, AssociatedTokenAccount, AssociatedTokenAccountCheck, ProgramAccount, Escrow, ProgramAccountInit, AssociatedTokenAccountInit};
 
 // =============================================================================
 // MakeAccounts 账户结构体
@@ -76,6 +81,10 @@ pub struct MakeAccounts<'info> {
     // 代币程序
     // 对应 Anchor: pub token_program: Interface<'info, TokenInterface>
     pub token_program: &'info AccountView,
+
+    // 创建者的挂单索引（PDA ["offers", maker]，可写）
+    // Anchor 版没有对应账户；第一次挂单时由 maker 付租金创建
+    pub offer_index: &'info AccountView,
 }
 
 // =============================================================================
@@ -93,7 +102,7 @@ impl<'info> TryFrom<&'info [AccountView]> for MakeAccounts<'info> {
     fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
         // 解构账户数组
         // 对应 Anchor 自动按字段名顺序解析账户
-        let [maker, escrow, mint_a, mint_b, maker_ata_a, vault, system_program, token_program, _] = accounts else {
+        let [maker, escrow, mint_a, mint_b, maker_ata_a, vault, system_program, token_program, _, offer_index] = accounts else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
@@ -127,6 +136,11 @@ impl<'info> TryFrom<&'info [AccountView]> for MakeAccounts<'info> {
         // 对应 Anchor: pub mint_b: InterfaceAccount<'info, Mint>
         MintInterface::check(mint_b)?;
 
+        // 验证 token_program 是 Token Program 或 Token-2022，且两个 mint 都由它持有
+        // 对应 Anchor: Interface<'info, TokenInterface> 与 mint::token_program = token_program
+        check_token_program(token_program, mint_a)?;
+        check_token_program(token_program, mint_b)?;
+
         // 验证 maker_ata_a 是正确的 ATA
         // 对应 Anchor: #[account(
         //     mut,
@@ -140,7 +154,11 @@ impl<'info> TryFrom<&'info [AccountView]> for MakeAccounts<'info> {
         // 1. 验证账户是有效的 Token Account
         // 2. 计算 ATA 的 PDA 地址：[authority, token_program, mint]
         // 3. 验证计算出的地址与传入的账户地址匹配
-        AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
+        //
+        // 卖出原生 SOL 时代币 A 来自 maker 的 lamports，maker_ata_a 可以不存在，不做检查
+        if !is_native_mint(mint_a) {
+            AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
+        }
 
         // 注意：escrow 和 vault 的验证在 try_from 中跳过
         // 因为它们会在后续的 init 过程中创建
@@ -156,6 +174,7 @@ impl<'info> TryFrom<&'info [AccountView]> for MakeAccounts<'info> {
             vault,
             system_program,
             token_program,
+            offer_index,
         })
     }
 }
@@ -171,6 +190,8 @@ impl<'info> TryFrom<&'info [AccountView]> for MakeAccounts<'info> {
 //       seed: u64,      // ← 这些参数由 Anchor 自动解析
 //       receive: u64,
 //       amount: u64,
+//       expiry: i64,
+//       taker: Pubkey,
 //   ) -> Result<()> {
 //
 // Pinocchio 版本：
@@ -188,6 +209,14 @@ pub struct MakeInstructionData {
     // 实际存入的代币 A 数量
     // 对应 Anchor: handler 参数 amount
     pub amount: u64,
+
+    // 过期时间（Unix 时间戳），0 表示永不过期
+    // 对应 Anchor: handler 参数 expiry
+    pub expiry: i64,
+
+    // 指定的接受者，全零表示任何人都可以 Take
+    // 对应 Anchor: handler 参数 taker
+    pub taker: Address,
 }
 
 // =============================================================================
@@ -200,17 +229,24 @@ impl<'info> TryFrom<&'info [u8]> for MakeInstructionData {
     // 从字节数组解析指令数据
     // 对应 Anchor 自动将 instruction_data 解析为函数参数
     fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
-        // 验证数据长度：3 个 u64 = 24 字节
+        // 验证数据长度：3 个 u64 + 1 个 i64 + 1 个地址 = 64 字节
         // 对应 Anchor 自动验证参数类型
-        if data.len() != size_of::<u64>() * 3 {
+        if data.len() != size_of::<u64>() * 4 + size_of::<Address>() {
             return Err(ProgramError::InvalidInstructionData);
         }
 
-        // 解析三个 u64 值（小端序）
+        // 解析三个 u64、一个 i64（小端序）与末尾的 32 字节地址
         // 对应 Anchor 自动反序列化参数
-        let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
-        let receive = u64::from_le_bytes(data[8..16].try_into().unwrap());
-        let amount = u64::from_le_bytes(data[16..24].try_into().unwrap());
+        // read_u64_le 越界返回 None 而不是 panic（长度已校验，这里只是不留 panic 路径）
+        let seed = read_u64_le(data, 0).ok_or(ProgramError::InvalidInstructionData)?;
+        let receive = read_u64_le(data, 8).ok_or(ProgramError::InvalidInstructionData)?;
+        let amount = read_u64_le(data, 16).ok_or(ProgramError::InvalidInstructionData)?;
+        let expiry = read_i64_le(data, 24).ok_or(ProgramError::InvalidInstructionData)?;
+        let taker = data
+            .get(32..)
+            .and_then(|rest| rest.first_chunk::<32>())
+            .map(|bytes| Address::new_from_array(*bytes))
+            .ok_or(ProgramError::InvalidInstructionData)?;
 
         // =====================================================================
         // 业务逻辑验证
@@ -230,6 +266,8 @@ impl<'info> TryFrom<&'info [u8]> for MakeInstructionData {
             seed,
             receive,
             amount,
+            expiry,
+            taker,
         })
     }
 }
@@ -276,6 +314,14 @@ impl<'info> TryFrom<(&'info [u8], &'info [AccountView])> for Make<'info> {
         // 对应 Anchor 的参数解析
         let instruction_data = MakeInstructionData::try_from(data)?;
 
+        // 过期时间必须在未来：已经过期的挂单没人能成交，直接拒绝，不创建任何账户
+        // 对应 Anchor: require!(expiry == 0 || expiry > now, EscrowError::EscrowExpired)
+        if instruction_data.expiry != 0
+            && instruction_data.expiry <= Clock::get()?.unix_timestamp
+        {
+            return Err(EscrowError::EscrowExpired.into());
+        }
+
         // =====================================================================
         // 账户初始化
         // =====================================================================
@@ -298,13 +344,11 @@ impl<'info> TryFrom<(&'info [u8], &'info [AccountView])> for Make<'info> {
 
         // 计算 PDA 地址和 bump
         // 对应 Anchor 的 seeds 和 bump 自动处理
-        let (_, bump) = Address::find_program_address(
-            &[
-                b"escrow",                                      // 固定前缀
-                accounts.maker.address().as_ref(),             // 创建者地址
-                &instruction_data.seed.to_le_bytes(),          // 随机种子
-            ],
-            &crate::ID,  // 程序 ID
+        // 种子布局 ["escrow", maker, seed] 定义在 pdas crate，客户端与测试共用
+        let (_, bump) = pdas::escrow_pda(
+            &crate::ID.to_bytes(),                 // 程序 ID
+            &accounts.maker.address().to_bytes(),  // 创建者地址
+            instruction_data.seed,                 // 随机种子
         );
 
         // 构造 PDA 签名种子
@@ -313,7 +357,7 @@ impl<'info> TryFrom<(&'info [u8], &'info [AccountView])> for Make<'info> {
         let seed_binding = instruction_data.seed.to_le_bytes();
         let bump_binding = [bump];
         let escrow_seeds = [
-            Seed::from(b"escrow"),                           // 种子 1: "escrow"
+            Seed::from(pdas::ESCROW_SEED),                   // 种子 1: "escrow"
             Seed::from(accounts.maker.address().as_ref().as_ref()),  // 种子 2: maker 地址
             Seed::from(&seed_binding),                       // 种子 3: seed 的字节数组
             Seed::from(&bump_binding),                       // 种子 4: bump
@@ -385,7 +429,7 @@ impl<'info> Make<'info> {
     // 对应 Anchor 的 handler 函数（make_anchor.rs:209-230）
     //
     // Anchor 版本：
-    //   pub fn handler(ctx: Context<Make>, seed: u64, receive: u64, amount: u64) -> Result<()> {
+    //   pub fn handler(ctx: Context<Make>, seed: u64, receive: u64, amount: u64, expiry: i64, taker: Pubkey) -> Result<()> {
     //       // 验证参数（已在 try_from 中完成）
     //       ctx.accounts.populate_escrow(seed, receive, ctx.bumps.escrow)?;
     //       ctx.accounts.deposit_token(amount)?;
@@ -408,9 +452,10 @@ impl<'info> Make<'info> {
         // 获取托管账户的可变借用
         let mut data = self.accounts.escrow.try_borrow_mut()?;
 
-        // 将字节数组解析为 Escrow 结构体
-        // unsafe transmute 将字节指针转换为结构体指针
-        let escrow = Escrow::load_mut(data.as_mut())?;
+        // 将字节数组解析为 Escrow 结构体并写入账户判别器
+        // 零拷贝借用（blueshift_zerocopy），不复制数据；刚创建的账户还没有判别器，不能用 load_mut
+        // 对应 Anchor 的 init 约束写入 #[account(discriminator = 1)]
+        let escrow = Escrow::init(data.as_mut())?;
 
         // 设置托管账户的所有字段
         // 对应 Anchor 的 set_inner 方法（make_anchor.rs:143-152）
@@ -421,6 +466,8 @@ impl<'info> Make<'info> {
             self.accounts.mint_b.address().clone(),       // mint_b：代币 B mint
             self.instruction_data.receive.clone(),        // receive：期望数量
             [self.bump],                                 // bump：PDA bump 种子
+            self.instruction_data.expiry,                 // expiry：过期时间，0 为永不过期
+            self.instruction_data.taker,                  // taker：指定的接受者，全零为不限
         );
 
         // =====================================================================
@@ -436,17 +483,62 @@ impl<'info> Make<'info> {
         //       self.mint_a.decimals  // ← Anchor 自动传递 decimals
         //   )
         //
-        // Pinocchio 版本使用 Transfer 指令（不需要 decimals）
-        // 因为 Transfer 指令使用 Token Program 的基本转账功能
-
-        // 转账代币 A 从创建者 ATA 到金库
-        // 对应 Anchor 的 transfer_checked CPI 调用
-        Transfer {
-            from: self.accounts.maker_ata_a,   // 从：创建者的代币 A ATA
-            to: self.accounts.vault,           // 到：金库账户
-            authority: self.accounts.maker,    // 权限：创建者必须签名
-            amount: self.instruction_data.amount  // 转账数量
-        }.invoke()?;  // 调用 Token Program 执行转账
+        // Pinocchio 版本同样使用 TransferChecked（token_interface.rs），
+        // 发往传入的 token_program，Token Program 与 Token-2022 都适用
+        //
+        // 代币 A 带转账手续费时，金库实际收到 amount - 手续费；
+        // Take / Refund 按金库余额结算，不依赖这里的 amount
+
+        // 原生 SOL：maker 直接把 amount lamports 转进 wSOL 金库并 SyncNative（Anchor 版没有这条路径）
+        if is_native_mint(self.accounts.mint_a) {
+            wrap_lamports(
+                self.accounts.maker,
+                self.accounts.vault,
+                self.accounts.token_program,
+                self.instruction_data.amount,
+            )?;
+        } else {
+            // 转账代币 A 从创建者 ATA 到金库
+            // 对应 Anchor 的 transfer_checked CPI 调用
+            TransferChecked {
+                from: self.accounts.maker_ata_a,   // 从：创建者的代币 A ATA
+                mint: self.accounts.mint_a,        // 代币 A 的 Mint
+                to: self.accounts.vault,           // 到：金库账户
+                authority: self.accounts.maker,    // 权限：创建者必须签名
+                token_program: self.accounts.token_program,
+                amount: self.instruction_data.amount,  // 转账数量
+                decimals: mint_decimals(self.accounts.mint_a)?,
+            }.invoke()?;  // 调用代币程序执行转账
+        }
+
+        // =====================================================================
+        // 步骤 3: 登记到创建者的挂单索引
+        // =====================================================================
+        // Anchor 版没有这一步；索引已满（MAX_OPEN_OFFERS 个未成交托管单）时返回 TooManyOffers，
+        // 整笔交易回滚，托管账户与金库都不会留下
+        register_offer(self.accounts.maker, self.accounts.offer_index, self.instruction_data.seed)?;
+
+        // =====================================================================
+        // 步骤 4: 发出事件
+        // =====================================================================
+        // 对应 Anchor 的 emit!，链下通过 blueshift_events 解析 `Program data`
+        EscrowMade {
+            escrow: self.accounts.escrow.address().to_bytes(),
+            maker: self.accounts.maker.address().to_bytes(),
+            mint_a: self.accounts.mint_a.address().to_bytes(),
+            mint_b: self.accounts.mint_b.address().to_bytes(),
+            seed: self.instruction_data.seed,
+            amount: self.instruction_data.amount,
+            receive: self.instruction_data.receive,
+        }.emit();
+        bslog!(
+            "make",
+            "seed={} amount={} receive={} expiry={}",
+            self.instruction_data.seed,
+            self.instruction_data.amount,
+            self.instruction_data.receive,
+            self.instruction_data.expiry
+        );
 
         Ok(())
     }
@@ -1,9 +1,23 @@
 mod make;
+mod init_config;
+mod offer_index;
 mod helpers;
 mod take;
+mod take_partial;
 mod refund;
+mod top_up;
+mod update_offer;
+mod token_interface;
+mod version;
 
 pub use refund::*;
 pub use helpers::*;
 pub use take::*;
-pub use make::*;
\ No newline at end of file
+pub use take_partial::*;
+pub use make::*;
+pub use init_config::*;
+pub use offer_index::*;
+pub use update_offer::*;
+pub use top_up::*;
+pub use token_interface::*;
+pub use version::*;
\ No newline at end of file
@@ -0,0 +1,82 @@
+// =============================================================================
+// 挂单索引 - 登记与注销创建者的未成交托管单
+// =============================================================================
+// OfferIndex PDA ["offers", maker] 的维护集中在这里：
+//
+// - register_offer：Make 创建托管账户之后登记 seed，索引还不存在时由 maker 付租金创建
+// - unregister_offer：Take / Refund 关闭托管账户时注销 seed；
+//   索引不存在（创建者的挂单都早于索引上线）时什么都不做
+//
+// 两者都先确认传入的账户正是该 maker 的索引 PDA，别人的索引或随便一个空账户都会被拒绝，
+// maker 本身已经由托管 PDA 的校验确认过。
+
+use pinocchio::{AccountView, ProgramResult};
+use pinocchio::cpi::Seed;
+use pinocchio::error::ProgramError;
+use crate::{AccountCheck, EscrowError, OfferIndex, OfferIndexAccount, ProgramAccount, ProgramAccountInit};
+
+// 在 Make 中登记新托管单的 seed；索引已满时返回 TooManyOffers
+pub fn register_offer(maker: &AccountView, offer_index: &AccountView, seed: u64) -> ProgramResult {
+    if offer_index.data_len() == 0 {
+        // 第一次挂单：搜索 bump 并创建索引账户，租金由 maker 支付
+        let (index_key, bump) = pdas::offer_index_pda(&crate::ID.to_bytes(), &maker.address().to_bytes());
+        if index_key != offer_index.address().to_bytes() {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump = [bump];
+        let seeds = [
+            Seed::from(pdas::OFFER_INDEX_SEED),
+            Seed::from(maker.address().as_ref()),
+            Seed::from(&bump),
+        ];
+        ProgramAccount::init::<OfferIndex>(maker, offer_index, &seeds, OfferIndex::LEN)?;
+        OfferIndex::init(&mut offer_index.try_borrow_mut()?, bump)?;
+    }
+
+    with_offer_index(maker, offer_index, |index| {
+        if !index.insert(seed) {
+            return Err(EscrowError::TooManyOffers.into());
+        }
+        Ok(())
+    })
+}
+
+// 在关闭托管账户时注销 seed；索引上线前创建的托管单没有登记过，找不到时直接忽略
+pub fn unregister_offer(maker: &AccountView, offer_index: &AccountView, seed: u64) -> ProgramResult {
+    if offer_index.data_len() == 0 {
+        // 与 Take 的 config 相同：账户不存在也要确认地址，不能随便传一个空账户跳过注销
+        let (index_key, _) = pdas::offer_index_pda(&crate::ID.to_bytes(), &maker.address().to_bytes());
+        if index_key != offer_index.address().to_bytes() {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        return Ok(());
+    }
+
+    with_offer_index(maker, offer_index, |index| {
+        index.remove(seed);
+        Ok(())
+    })
+}
+
+// 校验已存在的索引账户（owner、长度、判别器，以及按保存的 bump 重算的地址）后交给 f 修改
+fn with_offer_index(
+    maker: &AccountView,
+    offer_index: &AccountView,
+    f: impl FnOnce(&mut OfferIndex) -> ProgramResult,
+) -> ProgramResult {
+    OfferIndexAccount::check(offer_index)?;
+    let mut data = offer_index.try_borrow_mut()?;
+    let index = OfferIndex::load_mut(&mut data)?;
+
+    let index_key = pdas::offer_index_pda_with_bump(
+        &crate::ID.to_bytes(),
+        &maker.address().to_bytes(),
+        index.bump[0],
+    )
+    .ok_or(ProgramError::InvalidSeeds)?;
+    if index_key != offer_index.address().to_bytes() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    f(index)
+}
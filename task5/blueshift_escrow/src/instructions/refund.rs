@@ -2,22 +2,25 @@
 // Refund 指令 - Pinocchio 版本
 // =============================================================================
 // 本指令用于取消托管交易并退还代币
-// 只有创建者可以调用此指令，将存入的代币 A 取回
+// 创建者随时可以调用此指令取回存入的代币 A；托管单过期后任何人都可以调用，
+// 代币和租金仍然全部退还给创建者，调用者只负责签名并在需要时垫付 ATA 的租金
 //
 // 执行流程：
-// 1. 验证调用者是托管交易的创建者
-// 2. 从金库中将代币 A 转移回创建者
-// 3. 关闭金库账户，将剩余 lamports 返还给创建者
-// 4. 关闭托管账户，将租金返还给创建者
+// 1. 验证调用者是托管交易的创建者，或托管单已经过期
+// 2. 从金库中将代币 A 转移回创建者（原生 SOL 跳过这一步）
+// 3. 关闭金库账户，将剩余 lamports 返还给创建者；原生 SOL 的金库关闭时包装的 SOL 一并退回
+// 4. 从创建者的挂单索引 ["offers", maker] 中注销，关闭托管账户，将租金返还给创建者
 //
 // 与 Anchor 版本的对应关系见下方各部分注释
 
 use pinocchio::{AccountView, ProgramResult};
 use pinocchio::cpi::{Seed, Signer};
 use pinocchio::error::ProgramError;
-use pinocchio_token::instructions::{CloseAccount, Transfer};
-use solana_address::Address;
-use crate::{AccountCheck, AccountClose, AssociatedTokenAccount, AssociatedTokenAccountInit, Escrow, MintInterface, ProgramAccount, SignerAccount};
+use pinocchio::sysvars::{clock::Clock, Sysvar};
+use blueshift_common::read_u64_le;
+use blueshift_events::{Event, EscrowRefunded};
+use blueshift_log::bslog;
+use crate::{check_token_program, is_native_mint, unregister_offer, harvest_withheld_fees, mint_decimals, CloseAccount, TransferChecked, AccountCheck, AccountClose, AssociatedTokenAccount, AssociatedTokenAccountInit, Escrow, EscrowError, MintInterface, ProgramAccount, SignerAccount};
 
 // =============================================================================
 // RefundAccount 账户结构体
@@ -27,7 +30,8 @@ use crate::{AccountCheck, AccountClose, AssociatedTokenAccount, AssociatedTokenA
 // Anchor 版本（refund_anchor.rs:9-48）：
 //   #[derive(Accounts)]
 //   pub struct Refund<'info> {
-//       #[account(mut)] pub maker: Signer<'info>,
+//       #[account(mut)] pub caller: Signer<'info>,
+//       #[account(mut)] pub maker: SystemAccount<'info>,
 //       #[account(mut, close = maker, seeds = [...], bump = escrow.bump,
 //                has_one = maker, has_one = mint_a)]
 //       pub escrow: Box<Account<'info, Escrow>>,
@@ -36,7 +40,7 @@ use crate::{AccountCheck, AccountClose, AssociatedTokenAccount, AssociatedTokenA
 //       #[account(mut, associated_token::mint = mint_a,
 //                associated_token::authority = escrow, ...)]
 //       pub vault: InterfaceAccount<'info, TokenAccount>,
-//       #[account(init_if_needed, payer = maker, ...)]
+//       #[account(init_if_needed, payer = caller, ...)]
 //       pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
 //       pub associated_token_program: Program<'info, AssociatedToken>,
 //       pub token_program: Interface<'info, TokenInterface>,
@@ -46,15 +50,21 @@ use crate::{AccountCheck, AccountClose, AssociatedTokenAccount, AssociatedTokenA
 // Pinocchio 版本差异：
 // - 使用生命周期参数 'info
 // - 每个字段都是 &AccountView 引用
-// - 账户数量更少（7个 vs Anchor 的 8个）
+// - 账户数量更少（8个 vs Anchor 的 9个）
 //   - 不需要 associated_token_program（Pinocchio 自动处理）
 pub struct RefundAccount<'info> {
-    // 创建者账户（必须签名）
-    // 对应 Anchor: #[account(mut)] pub maker: Signer<'info>
+    // 调用者（必须签名，支付 maker_ata_a 的创建费用）
+    // 对应 Anchor: #[account(mut)] pub caller: Signer<'info>
     //
     // 安全性：
-    // - 必须签名验证身份
-    // - 只有创建者才能退款
+    // - 调用者不是创建者时，process 要求托管单已经过期
+    // - 调用者拿不到任何代币或 lamports，只能替创建者完成退款
+    pub caller: &'info AccountView,
+
+    // 创建者账户（接收退回的代币与租金，不要求签名）
+    // 对应 Anchor: #[account(mut)] pub maker: SystemAccount<'info>
+    //
+    // 托管 PDA 由 maker 派生，传入别人的地址时 PDA 校验失败
     pub maker: &'info AccountView,
 
     // 托管账户（PDA，将被关闭）
@@ -85,7 +95,7 @@ pub struct RefundAccount<'info> {
     pub vault: &'info AccountView,
 
     // 创建者的代币 A ATA（可能不存在）
-    // 对应 Anchor: #[account(init_if_needed, payer = maker,
+    // 对应 Anchor: #[account(init_if_needed, payer = caller,
     //            associated_token::mint = mint_a,
     //            associated_token::authority = maker, ...)]
     //            pub maker_ata_a: InterfaceAccount<'info, TokenAccount>
@@ -98,6 +108,9 @@ pub struct RefundAccount<'info> {
     // 代币程序
     // 对应 Anchor: pub token_program: Interface<'info, TokenInterface>
     pub token_program: &'info AccountView,
+
+    // 创建者的挂单索引 ["offers", maker]（Anchor 版没有），可写；不存在时跳过注销
+    pub offer_index: &'info AccountView,
 }
 
 // =============================================================================
@@ -112,7 +125,7 @@ impl<'info> TryFrom<&'info [AccountView]> for RefundAccount<'info> {
     fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
         // 解构账户数组
         // 对应 Anchor 自动按字段名顺序解析账户
-        let [maker, escrow, mint_a, vault, maker_ata_a, system_program, token_program, _] = accounts else {
+        let [caller, maker, escrow, mint_a, vault, maker_ata_a, system_program, token_program, _, offer_index] = accounts else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
@@ -121,10 +134,10 @@ impl<'info> TryFrom<&'info [AccountView]> for RefundAccount<'info> {
         // =====================================================================
         // 对应 Anchor 的各种 #[account(...)] 约束
 
-        // 验证 maker 是签名者
-        // 对应 Anchor: pub maker: Signer<'info>
-        // Signer 类型自动验证账户已签名
-        SignerAccount::check(maker)?;
+        // 验证调用者是签名者
+        // 对应 Anchor: pub caller: Signer<'info>
+        // Signer 类型自动验证账户已签名；是否允许非创建者退款在 process 中检查
+        SignerAccount::check(caller)?;
 
         // 验证 escrow 是本程序拥有的账户
         // 对应 Anchor: pub escrow: Box<Account<'info, Escrow>>
@@ -135,6 +148,9 @@ impl<'info> TryFrom<&'info [AccountView]> for RefundAccount<'info> {
         // 对应 Anchor: pub mint_a: InterfaceAccount<'info, Mint>
         MintInterface::check(mint_a)?;
 
+        // mint_a 必须由传入的 token_program（Token Program 或 Token-2022）持有
+        check_token_program(token_program, mint_a)?;
+
         // 跳过 ATA 验证
         // 原因：vault 和 maker_ata_a 的验证会在 CPI 调用中自动进行
         // Token Program 会验证账户的所有者和权限
@@ -144,6 +160,7 @@ impl<'info> TryFrom<&'info [AccountView]> for RefundAccount<'info> {
 
         // 返回验证通过的账户结构
         Ok(Self {
+            caller,
             maker,
             escrow,
             mint_a,
@@ -151,6 +168,7 @@ impl<'info> TryFrom<&'info [AccountView]> for RefundAccount<'info> {
             maker_ata_a,
             system_program,
             token_program,
+            offer_index,
         })
     }
 }
@@ -187,7 +205,7 @@ impl<'info> TryFrom<&'info [AccountView]> for crate::Refund<'info> {
         // Anchor 版本（refund_anchor.rs:85-94）：
         //   #[account(
         //       init_if_needed,           // ← 如果账户不存在则创建
-        //       payer = maker,            // ← maker 支付创建费用
+        //       payer = caller,           // ← 调用者支付创建费用
         //       associated_token::mint = mint_a,
         //       associated_token::authority = maker,
         //       associated_token::token_program = token_program
@@ -203,14 +221,18 @@ impl<'info> TryFrom<&'info [AccountView]> for crate::Refund<'info> {
         // helpers.rs 中的 init_if_needed 实现：
         // - 先尝试验证账户（check）
         // - 如果验证失败，说明账户不存在，调用 init 创建
-        AssociatedTokenAccount::init_if_needed(
-            accounts.maker_ata_a,     // 要创建/验证的账户
-            accounts.mint_a,          // mint 账户
-            accounts.maker,           // payer：对应 Anchor 的 payer = maker
-            accounts.maker,           // owner：对应 Anchor 的 authority = maker
-            accounts.system_program,  // System Program
-            accounts.token_program,   // Token Program
-        )?;
+        //
+        // 原生 SOL 以 lamports 退回创建者的钱包，不需要 wSOL ATA
+        if !is_native_mint(accounts.mint_a) {
+            AssociatedTokenAccount::init_if_needed(
+                accounts.maker_ata_a,     // 要创建/验证的账户
+                accounts.mint_a,          // mint 账户
+                accounts.caller,          // payer：对应 Anchor 的 payer = caller
+                accounts.maker,           // owner：对应 Anchor 的 authority = maker
+                accounts.system_program,  // System Program
+                accounts.token_program,   // Token Program
+            )?;
+        }
 
         // 返回完整的指令结构
         Ok(Self {
@@ -258,7 +280,7 @@ impl<'info> Refund<'info> {
             let data = self.accounts.escrow.try_borrow()?;
 
             // 将字节数组解析为 Escrow 结构体
-            // unsafe transmute 将字节指针转换为结构体指针
+            // 零拷贝借用（blueshift_zerocopy），长度不符时返回 InvalidAccountData
             let escrow = Escrow::load(&data)?;
 
             // =================================================================
@@ -282,28 +304,39 @@ impl<'info> Refund<'info> {
 
             // 重新计算 PDA 地址以验证账户有效性
             // 使用托管账户中存储的种子和 bump
-            let escrow_key = Address::create_program_address(
-                &[
-                    b"escrow",                                    // 固定前缀
-                    self.accounts.maker.address().as_ref(),     // 创建者地址
-                    &escrow.seed.to_le_bytes(),                  // 从账户中读取的 seed
-                    &escrow.bump,                                // 从账户中读取的 bump
-                ],
-                &crate::ID  // 程序 ID
-            )?;
+            let escrow_key = pdas::escrow_pda_with_bump(
+                &crate::ID.to_bytes(),                      // 程序 ID
+                &self.accounts.maker.address().to_bytes(),  // 创建者地址
+                escrow.seed(),                              // 从账户中读取的 seed
+                escrow.bump[0],                             // 从账户中读取的 bump
+            )
+            .ok_or(ProgramError::InvalidSeeds)?;
 
             // 验证计算出的 PDA 地址是否与传入的账户地址匹配
             // 这确保：
             // 1. 账户确实是使用正确的种子派生的
             // 2. 账户数据未被篡改
-            // 3. 只有创建者（通过 has_one 隐式验证）能退款
-            if &escrow_key != self.accounts.escrow.address() {
+            // 3. maker 就是托管单的创建者（通过 has_one 隐式验证）
+            if escrow_key != self.accounts.escrow.address().to_bytes() {
                 return Err(ProgramError::InvalidAccountOwner);
             }
 
+            // 对应 Anchor: has_one = mint_a；是否按原生 SOL 退款由 mint_a 决定，必须是托管单记录的代币
+            if escrow.mint_a != *self.accounts.mint_a.address() {
+                return Err(EscrowError::InvalidMintA.into());
+            }
+
+            // 创建者以外的调用者只能清理已过期的托管单
+            // 对应 Anchor: require!(caller == maker || escrow.is_expired(now), EscrowError::EscrowNotExpired)
+            if self.accounts.caller.address() != self.accounts.maker.address()
+                && !escrow.is_expired(Clock::get()?.unix_timestamp)
+            {
+                return Err(EscrowError::EscrowNotExpired.into());
+            }
+
             // 提取需要的字段
             // 注意：不需要 mint_b 和 receive 字段
-            (escrow.seed, escrow.bump)
+            (escrow.seed(), escrow.bump)
         }; // ← data 在这里自动释放，借用结束
 
         // =====================================================================
@@ -326,7 +359,7 @@ impl<'info> Refund<'info> {
         let seed_binding = seed.to_le_bytes();
         let bump_binding = bump;
         let escrow_seeds = [
-            Seed::from(b"escrow"),                           // 种子 1: "escrow"
+            Seed::from(pdas::ESCROW_SEED),                   // 种子 1: "escrow"
             Seed::from(self.accounts.maker.address().as_ref()),  // 种子 2: maker 地址
             Seed::from(&seed_binding),                       // 种子 3: seed 的字节数组
             Seed::from(&bump_binding),                       // 种子 4: bump
@@ -351,7 +384,8 @@ impl<'info> Refund<'info> {
 
             // 读取 amount 字段（偏移量 64，长度 8）
             // Token Account 结构体的第 9 个字段是 amount
-            u64::from_le_bytes(vault_data[64..72].try_into().unwrap())
+            // 数据不足 72 字节时返回错误，而不是切片越界 panic
+            read_u64_le(&vault_data, 64).ok_or(ProgramError::InvalidAccountData)?
         }; // ← vault_data 在这里自动释放
 
         // =====================================================================
@@ -372,21 +406,27 @@ impl<'info> Refund<'info> {
         //       self.mint_a.decimals  // ← Anchor 自动传递 decimals
         //   )
         //
-        // Pinocchio 版本使用 Transfer 指令（不需要 decimals）
-
-        // 转账代币 A 从金库回创建者的 ATA
-        // 将创建者存入的代币全部退还
-        Transfer {
-            from: self.accounts.vault,        // 从：金库账户
-            to: self.accounts.maker_ata_a,    // 到：创建者的代币 A ATA
-            authority: self.accounts.escrow,  // 权限：escrow PDA（需要签名）
-            amount,                           // 转账数量：金库中的全部代币
-        }.invoke_signed(&[signer.clone()])?;  // ← 使用 PDA 签名调用
-
-        // invoke_signed 说明：
-        // - 金库的 authority 是 escrow PDA，没有私钥
-        // - 需要使用 invoke_signed 提供 PDA 签名
-        // - signer 包含派生 PDA 的所有种子
+        // Pinocchio 版本同样使用 TransferChecked（token_interface.rs）
+
+        // 原生 SOL 金库不转 token：下面关闭金库时包装的 SOL 连同租金一起以 lamports 回到创建者
+        if !is_native_mint(self.accounts.mint_a) {
+            // 转账代币 A 从金库回创建者的 ATA
+            // 将创建者存入的代币全部退还（带转账手续费的代币会再被扣一次）
+            TransferChecked {
+                from: self.accounts.vault,        // 从：金库账户
+                mint: self.accounts.mint_a,       // 代币 A 的 Mint
+                to: self.accounts.maker_ata_a,    // 到：创建者的代币 A ATA
+                authority: self.accounts.escrow,  // 权限：escrow PDA（需要签名）
+                token_program: self.accounts.token_program,
+                amount,                           // 转账数量：金库中的全部代币
+                decimals: mint_decimals(self.accounts.mint_a)?,
+            }.invoke_signed(&[signer.clone()])?;  // ← 使用 PDA 签名调用
+
+            // invoke_signed 说明：
+            // - 金库的 authority 是 escrow PDA，没有私钥
+            // - 需要使用 invoke_signed 提供 PDA 签名
+            // - signer 包含派生 PDA 的所有种子
+        }
 
         // =====================================================================
         // 步骤 2: 关闭金库账户
@@ -399,12 +439,16 @@ impl<'info> Refund<'info> {
         // Pinocchio 版本：
         //   CloseAccount { ... }.invoke_signed(&[signer])
 
+        // 金库上还记着未提取的转账手续费时 Token-2022 拒绝关闭，先归集到 mint_a
+        harvest_withheld_fees(self.accounts.token_program, self.accounts.mint_a, self.accounts.vault)?;
+
         // 关闭金库账户
         // 将金库账户的 lamports 返还给创建者
         CloseAccount {
             account: self.accounts.vault,       // 要关闭的账户：金库
             destination: self.accounts.maker,   // 接收 lamports 的账户：创建者
             authority: self.accounts.escrow,    // 权限：escrow PDA（金库的 owner）
+            token_program: self.accounts.token_program,
         }.invoke_signed(&[signer.clone()])?;  // ← 使用 PDA 签名调用
 
         // close_account 说明：
@@ -428,6 +472,9 @@ impl<'info> Refund<'info> {
         // Pinocchio 版本：
         // 手动调用 ProgramAccount::close()
 
+        // 托管单撤销后不再挂单，从创建者的挂单索引中注销
+        unregister_offer(self.accounts.maker, self.accounts.offer_index, seed)?;
+
         // 关闭托管账户
         // 将托管账户的租金（lamports）返还给创建者
         ProgramAccount::close(
@@ -454,6 +501,14 @@ impl<'info> Refund<'info> {
         // - 所有账户已关闭，租金已返还
         // - 该托管交易无法再被 Take 或再次 Refund
 
+        // 记录退款，amount 为退还给创建者的代币 A 数量
+        EscrowRefunded {
+            escrow: self.accounts.escrow.address().to_bytes(),
+            maker: self.accounts.maker.address().to_bytes(),
+            amount,
+        }.emit();
+        bslog!("refund", "amount={}", amount);
+
         Ok(())
     }
 }
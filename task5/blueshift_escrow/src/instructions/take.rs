@@ -3,14 +3,26 @@
 // =============================================================================
 // 本指令用于接受一个现有的托管交易
 // 接受者向创建者发送代币 B，并从金库中获得代币 A
+// 托管单设置了过期时间且已经到期时拒绝成交；指定了接受者时只有该地址可以成交
+// 全局配置 ["config"] 存在时，接受者支付的代币 B 中按费率扣下协议手续费转入国库，
+// 账户列表末尾因此多出 config 与 treasury_ata_b 两个账户，之后是创建者的挂单索引 offer_index，
+// 托管账户关闭时从中注销该托管单
+// 任一侧是原生 SOL（mint 为 wSOL）时这一侧直接收付 lamports：接受者拿到的是 SOL 而不是 wSOL，
+// 创建者收到的 SOL 直接进钱包，对应的 taker_ata_a / taker_ata_b / maker_ata_b 不会被创建或使用
+// 指令数据可选带上 max_receive (u64 小端)：成交时托管账户中的 receive 超过它就拒绝，
+// 创建者用 UpdateOffer / TopUp 抢在吃单之前提价时，接受者不会按新价格成交；数据为空时不限制
 //
 // 与 Anchor 版本的对应关系见下方各部分注释
 
-use pinocchio::{Address, AccountView, ProgramResult};
+use pinocchio::{AccountView, ProgramResult};
 use pinocchio::cpi::{Seed, Signer};
 use pinocchio::error::ProgramError;
-use pinocchio_token::instructions::{CloseAccount, Transfer};
-use crate::{AccountCheck, SignerAccount, MintInterface, AssociatedTokenAccount, AssociatedTokenAccountCheck, ProgramAccount, AssociatedTokenAccountInit, Escrow, AccountClose};
+use pinocchio::sysvars::{clock::Clock, Sysvar};
+use blueshift_common::read_u64_le;
+use blueshift_events::{Event, EscrowTaken};
+use blueshift_log::bslog;
+use pinocchio_system::instructions::Transfer;
+use crate::{amount_before_fee, unregister_offer, check_token_program, harvest_withheld_fees, is_native_mint, wrap_lamports, merged_lamports, mint_decimals, CloseAccount, Config, ConfigAccount, TransferChecked, AccountCheck, SignerAccount, MintInterface, AssociatedTokenAccount, AssociatedTokenAccountCheck, ProgramAccount, AssociatedTokenAccountInit, Escrow, EscrowError, AccountClose};
 
 // =============================================================================
 // TakeAccounts 账户结构体
@@ -105,6 +117,17 @@ pub struct TakeAccounts<'info> {
     // 代币程序
     // 对应 Anchor: pub token_program: Interface<'info, TokenInterface>
     pub token_program: &'info AccountView,
+
+    // 全局配置 ["config"]（Anchor 版没有协议手续费）
+    // 尚未由 InitConfig 创建时不收手续费
+    pub config: &'info AccountView,
+
+    // 协议手续费的接收账户：Config.treasury 在 mint_b 上的 ATA，需已创建
+    // 只有手续费不为 0 时才校验和使用
+    pub treasury_ata_b: &'info AccountView,
+
+    // 创建者的挂单索引 ["offers", maker]（Anchor 版没有），可写；不存在时跳过注销
+    pub offer_index: &'info AccountView,
 }
 
 // =============================================================================
@@ -119,7 +142,7 @@ impl<'info> TryFrom<&'info [AccountView]> for TakeAccounts<'info> {
     fn try_from(accounts: &'info [AccountView]) -> Result<Self, Self::Error> {
         // 解构账户数组
         // 对应 Anchor 自动按字段名顺序解析账户
-        let [taker, maker, escrow, mint_a, mint_b, vault, taker_ata_a, taker_ata_b, maker_ata_b, system_program, token_program, _] = accounts else {
+        let [taker, maker, escrow, mint_a, mint_b, vault, taker_ata_a, taker_ata_b, maker_ata_b, system_program, token_program, _, config, treasury_ata_b, offer_index] = accounts else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
@@ -145,12 +168,19 @@ impl<'info> TryFrom<&'info [AccountView]> for TakeAccounts<'info> {
         // 对应 Anchor: pub mint_b: Box<InterfaceAccount<'info,Mint>>
         MintInterface::check(mint_b)?;
 
+        // 两个 mint 都必须由传入的 token_program（Token Program 或 Token-2022）持有
+        check_token_program(token_program, mint_a)?;
+        check_token_program(token_program, mint_b)?;
+
         // 验证 taker_ata_b 是正确的 ATA
         // 对应 Anchor: #[account(init_if_needed, payer = taker,
         //            associated_token::mint = mint_b,
         //            associated_token::authority = taker, ...)]
         // 注意：这里只验证，不创建（创建在后续的 init_if_needed 中）
-        AssociatedTokenAccount::check(taker_ata_b, taker, mint_b, token_program)?;
+        // 用原生 SOL 支付时代币 B 直接从 taker 的 lamports 扣，不需要这个账户
+        if !is_native_mint(mint_b) {
+            AssociatedTokenAccount::check(taker_ata_b, taker, mint_b, token_program)?;
+        }
 
         // 验证 vault 是正确的 ATA（由 escrow 拥有）
         // 对应 Anchor: #[account(mut, associated_token::mint = mint_a,
@@ -173,16 +203,143 @@ impl<'info> TryFrom<&'info [AccountView]> for TakeAccounts<'info> {
             vault,
             system_program,
             token_program,
+            config,
+            treasury_ata_b,
+            offer_index,
         })
     }
 }
 
+impl<'info> TakeAccounts<'info> {
+    // =========================================================================
+    // 协议手续费
+    // =========================================================================
+    // 从创建者应收的 amount 个代币 B 中按 Config 的费率扣下手续费，由接受者直接转入
+    // treasury_ata_b；返回扣下的数量，创建者实际收到 amount - 手续费。
+    // Config 不存在或手续费为 0 时不转账，返回 0
+    pub fn pay_protocol_fee(&self, amount: u64) -> Result<u64, ProgramError> {
+        // 配置尚未创建：确认传入的确实是 ["config"]，而不是随便一个空账户
+        if self.config.data_len() == 0 {
+            let (config_key, _) = pdas::escrow_config_pda(&crate::ID.to_bytes());
+            if config_key != self.config.address().to_bytes() {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            return Ok(0);
+        }
+
+        // 本程序持有的 Config 长度的账户只能由 InitConfig 在 ["config"] 上创建
+        ConfigAccount::check(self.config)?;
+        let (fee, treasury) = {
+            let data = self.config.try_borrow()?;
+            let config = Config::load(&data)?;
+            (config.fee(amount)?, config.treasury)
+        };
+        if fee == 0 {
+            return Ok(0);
+        }
+
+        AssociatedTokenAccount::check_address(
+            self.treasury_ata_b,
+            &treasury,
+            self.mint_b.address(),
+            self.token_program.address(),
+        )?;
+        // 原生 SOL：手续费由接受者的 lamports 包装进国库的 wSOL ATA（该 ATA 必须已经存在）
+        if is_native_mint(self.mint_b) {
+            wrap_lamports(self.taker, self.treasury_ata_b, self.token_program, fee)?;
+            return Ok(fee);
+        }
+        TransferChecked {
+            from: self.taker_ata_b,
+            mint: self.mint_b,
+            to: self.treasury_ata_b,
+            authority: self.taker,
+            token_program: self.token_program,
+            amount: amount_before_fee(self.mint_b, fee)?,
+            decimals: mint_decimals(self.mint_b)?,
+        }.invoke()?;
+
+        Ok(fee)
+    }
+
+    // =========================================================================
+    // 支付创建者
+    // =========================================================================
+    // 接受者把扣除协议手续费之后的 amount 个代币 B 付给创建者；转账手续费由接受者承担。
+    // 原生 SOL 直接从接受者转 lamports 到创建者的钱包
+    pub fn pay_maker(&self, amount: u64) -> ProgramResult {
+        if is_native_mint(self.mint_b) {
+            return Transfer {
+                from: self.taker,
+                to: self.maker,
+                lamports: amount,
+            }.invoke();
+        }
+        TransferChecked {
+            from: self.taker_ata_b,
+            mint: self.mint_b,
+            to: self.maker_ata_b,
+            authority: self.taker,
+            token_program: self.token_program,
+            amount: amount_before_fee(self.mint_b, amount)?,
+            decimals: mint_decimals(self.mint_b)?,
+        }.invoke()
+    }
+
+    // =========================================================================
+    // 取出原生 SOL 金库
+    // =========================================================================
+    // 金库是 wSOL 时不转 token：关闭金库，全部 lamports（包装的 SOL + 金库租金）先回到 escrow，
+    // 再从本程序持有的 escrow 直接把 amount lamports 划给接受者；金库租金留在 escrow 中，
+    // 随之后关闭 escrow 一起退给创建者
+    pub fn release_native_vault(&self, amount: u64, signer: &Signer) -> ProgramResult {
+        CloseAccount {
+            account: self.vault,
+            destination: self.escrow,
+            authority: self.escrow,
+            token_program: self.token_program,
+        }.invoke_signed(&[signer.clone()])?;
+
+        let remaining = self.escrow.lamports().checked_sub(amount).ok_or(ProgramError::InsufficientFunds)?;
+        self.escrow.set_lamports(remaining);
+        self.taker.set_lamports(merged_lamports(self.taker.lamports(), amount)?);
+        Ok(())
+    }
+}
+
 // =============================================================================
 // Take 指令主结构体
 // =============================================================================
 // 对应 Anchor 的 Context<Take>
 pub struct Take<'info> {
     pub accounts: TakeAccounts<'info>,
+    // 接受者最多支付的代币 B（不含转账手续费）；指令数据为空时是 u64::MAX，即不限制
+    pub max_receive: u64,
+}
+
+// =============================================================================
+// 接受者的价格上限
+// =============================================================================
+// Take 的指令数据为空（旧客户端）或只有 max_receive；TakePartial 在 amount 之后同样可选
+#[inline(always)]
+pub fn parse_max_receive(data: &[u8]) -> Result<u64, ProgramError> {
+    match data.len() {
+        0 => Ok(u64::MAX),
+        8 => read_u64_le(data, 0).ok_or(ProgramError::InvalidInstructionData),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountView])> for Take<'info> {
+    type Error = ProgramError;
+
+    // 先解析指令数据，数据不合法时不替接受者创建任何 ATA
+    fn try_from((data, accounts): (&'info [u8], &'info [AccountView])) -> Result<Self, Self::Error> {
+        let max_receive = parse_max_receive(data)?;
+        let mut take = Self::try_from(accounts)?;
+        take.max_receive = max_receive;
+        Ok(take)
+    }
 }
 
 // =============================================================================
@@ -225,30 +382,37 @@ impl<'info> TryFrom<&'info [AccountView]> for Take<'info> {
         // helpers.rs 中的 init_if_needed 实现：
         // - 先尝试验证账户（check）
         // - 如果验证失败，说明账户不存在，调用 init 创建
-        AssociatedTokenAccount::init_if_needed(
-            accounts.taker_ata_a,     // 要创建/验证的账户
-            accounts.mint_a,          // mint 账户
-            accounts.taker,           // payer：对应 Anchor 的 payer = taker
-            accounts.taker,           // owner：对应 Anchor 的 authority = taker
-            accounts.system_program,  // System Program
-            accounts.token_program,   // Token Program
-        )?;
+        // 原生 SOL 直接以 lamports 交给接受者，不需要 wSOL ATA
+        if !is_native_mint(accounts.mint_a) {
+            AssociatedTokenAccount::init_if_needed(
+                accounts.taker_ata_a,     // 要创建/验证的账户
+                accounts.mint_a,          // mint 账户
+                accounts.taker,           // payer：对应 Anchor 的 payer = taker
+                accounts.taker,           // owner：对应 Anchor 的 authority = taker
+                accounts.system_program,  // System Program
+                accounts.token_program,   // Token Program
+            )?;
+        }
 
         // 创建创建者的代币 B ATA（如果不存在）
         // 对应 Anchor: pub maker_ata_b 的 init_if_needed 约束
         // （take_anchor.rs:112-119）
-        AssociatedTokenAccount::init_if_needed(
-            accounts.maker_ata_b,     // 要创建/验证的账户
-            accounts.mint_b,          // mint 账户
-            accounts.taker,           // payer：对应 Anchor 的 payer = taker
-            accounts.maker,           // owner：对应 Anchor 的 authority = maker
-            accounts.system_program,  // System Program
-            accounts.token_program,   // Token Program
-        )?;
+        // 同样，收原生 SOL 的创建者不需要 wSOL ATA
+        if !is_native_mint(accounts.mint_b) {
+            AssociatedTokenAccount::init_if_needed(
+                accounts.maker_ata_b,     // 要创建/验证的账户
+                accounts.mint_b,          // mint 账户
+                accounts.taker,           // payer：对应 Anchor 的 payer = taker
+                accounts.maker,           // owner：对应 Anchor 的 authority = maker
+                accounts.system_program,  // System Program
+                accounts.token_program,   // Token Program
+            )?;
+        }
 
         // 返回完整的指令结构
         Ok(Self {
             accounts,
+            max_receive: u64::MAX,
         })
     }
 }
@@ -291,7 +455,7 @@ impl<'info> Take<'info> {
             let data = self.accounts.escrow.try_borrow()?;
 
             // 将字节数组解析为 Escrow 结构体
-            // unsafe transmute 将字节指针转换为结构体指针
+            // 零拷贝借用（blueshift_zerocopy），长度不符时返回 InvalidAccountData
             let escrow = Escrow::load(&data)?;
 
             // =================================================================
@@ -315,29 +479,54 @@ impl<'info> Take<'info> {
 
             // 重新计算 PDA 地址以验证账户有效性
             // 使用托管账户中存储的种子和 bump
-            let escrow_key = Address::create_program_address(
-                &[
-                    b"escrow",                                    // 固定前缀
-                    self.accounts.maker.address().as_ref(),     // 创建者地址
-                    &escrow.seed.to_le_bytes(),                  // 从账户中读取的 seed
-                    &escrow.bump,                                // 从账户中读取的 bump
-                ],
-                &crate::ID  // 程序 ID
-            )?;
+            let escrow_key = pdas::escrow_pda_with_bump(
+                &crate::ID.to_bytes(),                      // 程序 ID
+                &self.accounts.maker.address().to_bytes(),  // 创建者地址
+                escrow.seed(),                              // 从账户中读取的 seed
+                escrow.bump[0],                             // 从账户中读取的 bump
+            )
+            .ok_or(ProgramError::InvalidSeeds)?;
 
             // 验证计算出的 PDA 地址是否与传入的账户地址匹配
             // 这确保：
             // 1. 账户确实是使用正确的种子派生的
             // 2. 账户数据未被篡改
-            if &escrow_key != self.accounts.escrow.address() {
+            if escrow_key != self.accounts.escrow.address().to_bytes() {
                 return Err(ProgramError::InvalidAccountOwner);
             }
 
+            // 两个 mint 必须与托管单记录的一致
+            // 对应 Anchor: has_one = mint_a / has_one = mint_b
+            // 原生 SOL 的收付方式由传入的 mint 决定，换成 wSOL 就能用 lamports 冒充代币 B，必须核对
+            if escrow.mint_a != *self.accounts.mint_a.address() {
+                return Err(EscrowError::InvalidMintA.into());
+            }
+            if escrow.mint_b != *self.accounts.mint_b.address() {
+                return Err(EscrowError::InvalidMintB.into());
+            }
+
+            // 过期的托管单不能再成交，只能由 Refund 退还给创建者
+            // 对应 Anchor: require!(!escrow.is_expired(now), EscrowError::EscrowExpired)
+            if escrow.is_expired(Clock::get()?.unix_timestamp) {
+                return Err(EscrowError::EscrowExpired.into());
+            }
+
+            // 私下约定的托管单：签名的 taker 必须是创建者指定的接受者
+            // 对应 Anchor: require!(escrow.allows_taker(&taker), EscrowError::InvalidTaker)
+            if !escrow.allows_taker(self.accounts.taker.address()) {
+                return Err(EscrowError::InvalidTaker.into());
+            }
+
             // 提取需要的字段
             // 这些值在后续步骤中会用到
-            (escrow.seed, escrow.receive, escrow.bump)
+            (escrow.seed(), escrow.receive(), escrow.bump)
         }; // ← data 在这里自动释放，借用结束
 
+        // 创建者在吃单之前改了价：按接受者签名时看到的价格上限拒绝
+        if receive > self.max_receive {
+            return Err(EscrowError::ReceiveAboveMax.into());
+        }
+
         // =====================================================================
         // 构造 PDA 签名种子
         // =====================================================================
@@ -358,7 +547,7 @@ impl<'info> Take<'info> {
         let seed_binding = seed.to_le_bytes();
         let bump_binding = bump;
         let escrow_seeds = [
-            Seed::from(b"escrow"),                           // 种子 1: "escrow"
+            Seed::from(pdas::ESCROW_SEED),                   // 种子 1: "escrow"
             Seed::from(self.accounts.maker.address().as_ref()),  // 种子 2: maker 地址
             Seed::from(&seed_binding),                       // 种子 3: seed 的字节数组
             Seed::from(&bump_binding),                       // 种子 4: bump
@@ -383,7 +572,8 @@ impl<'info> Take<'info> {
 
             // 读取 amount 字段（偏移量 64，长度 8）
             // Token Account 结构体的第 9 个字段是 amount
-            u64::from_le_bytes(vault_data[64..72].try_into().unwrap())
+            // 数据不足 72 字节时返回错误，而不是切片越界 panic
+            read_u64_le(&vault_data, 64).ok_or(ProgramError::InvalidAccountData)?
         }; // ← vault_data 在这里自动释放
 
         // =====================================================================
@@ -402,58 +592,71 @@ impl<'info> Take<'info> {
         // 但在 Pinocchio 中，我们需要先释放借用才能执行 CPI
         // 所以上面的代码块确保借用在 CPI 前释放
 
-        // =====================================================================
-        // 步骤 1: 从金库转移代币 A 给接受者
-        // =====================================================================
-        // 对应 Anchor: ctx.accounts.withdraw_and_close_vault()
-        //              中的 transfer_checked 调用（take_anchor.rs:187-201）
-        //
-        // Anchor 版本使用 transfer_checked：
-        //   transfer_checked(
-        //       CpiContext::new_with_signer(...),
-        //       self.vault.amount,
-        //       self.mint_a.decimals  // ← Anchor 自动传递 decimals
-        //   )
-        //
-        // Pinocchio 版本使用 Transfer 指令（不需要 decimals）
-
-        // 转账代币 A 从金库到接受者的 ATA
-        Transfer {
-            from: self.accounts.vault,        // 从：金库账户
-            to: self.accounts.taker_ata_a,    // 到：接受者的代币 A ATA
-            authority: self.accounts.escrow,  // 权限：escrow PDA（需要签名）
-            amount,                           // 转账数量：金库中的全部代币
-        }.invoke_signed(&[signer.clone()])?;  // ← 使用 PDA 签名调用
-
-        // invoke_signed 说明：
-        // - 金库的 authority 是 escrow PDA，没有私钥
-        // - 需要使用 invoke_signed 提供 PDA 签名
-        // - signer 包含派生 PDA 的所有种子
-
-        // =====================================================================
-        // 步骤 2: 关闭金库账户
-        // =====================================================================
-        // 对应 Anchor: ctx.accounts.withdraw_and_close_vault()
-        //              中的 close_account 调用（take_anchor.rs:205-213）
-        //
-        // Anchor 版本：
-        //   close_account(CpiContext::new_with_signer(...))
-        //
-        // Pinocchio 版本：
-        //   CloseAccount { ... }.invoke_signed(&[signer])
-
-        // 关闭金库账户
-        // 将金库账户的 lamports 返还给创建者
-        CloseAccount {
-            account: self.accounts.vault,       // 要关闭的账户：金库
-            destination: self.accounts.maker,   // 接收 lamports 的账户：创建者
-            authority: self.accounts.escrow,    // 权限：escrow PDA（金库的 owner）
-        }.invoke_signed(&[signer.clone()])?;  // ← 使用 PDA 签名调用
-
-        // close_account 说明：
-        // 1. 验证账户余额为 0（代币已全部转出）
-        // 2. 将账户的 lamports 转给 destination
-        // 3. 将账户数据清零，账户可以被重新分配
+        // 原生 SOL 金库不转 token：关闭金库后直接把 amount lamports 划给接受者（代替步骤 1、2）
+        if is_native_mint(self.accounts.mint_a) {
+            self.accounts.release_native_vault(amount, &signer)?;
+        } else {
+            // =====================================================================
+            // 步骤 1: 从金库转移代币 A 给接受者
+            // =====================================================================
+            // 对应 Anchor: ctx.accounts.withdraw_and_close_vault()
+            //              中的 transfer_checked 调用（take_anchor.rs:187-201）
+            //
+            // Anchor 版本使用 transfer_checked：
+            //   transfer_checked(
+            //       CpiContext::new_with_signer(...),
+            //       self.vault.amount,
+            //       self.mint_a.decimals  // ← Anchor 自动传递 decimals
+            //   )
+            //
+            // Pinocchio 版本同样使用 TransferChecked（token_interface.rs）
+
+            // 转账代币 A 从金库到接受者的 ATA
+            TransferChecked {
+                from: self.accounts.vault,        // 从：金库账户
+                mint: self.accounts.mint_a,       // 代币 A 的 Mint
+                to: self.accounts.taker_ata_a,    // 到：接受者的代币 A ATA
+                authority: self.accounts.escrow,  // 权限：escrow PDA（需要签名）
+                token_program: self.accounts.token_program,
+                amount,                           // 转账数量：金库中的全部代币
+                decimals: mint_decimals(self.accounts.mint_a)?,
+            }.invoke_signed(&[signer.clone()])?;  // ← 使用 PDA 签名调用
+
+            // invoke_signed 说明：
+            // - 金库的 authority 是 escrow PDA，没有私钥
+            // - 需要使用 invoke_signed 提供 PDA 签名
+            // - signer 包含派生 PDA 的所有种子
+
+            // =====================================================================
+            // 步骤 2: 关闭金库账户
+            // =====================================================================
+            // 对应 Anchor: ctx.accounts.withdraw_and_close_vault()
+            //              中的 close_account 调用（take_anchor.rs:205-213）
+            //
+            // Anchor 版本：
+            //   close_account(CpiContext::new_with_signer(...))
+            //
+            // Pinocchio 版本：
+            //   CloseAccount { ... }.invoke_signed(&[signer])
+            //
+            // 金库收款时被扣下的手续费仍记在金库上，Token-2022 不允许关闭这样的账户，
+            // 先把它们归集到 mint_a（旧版代币或没有手续费时跳过）
+            harvest_withheld_fees(self.accounts.token_program, self.accounts.mint_a, self.accounts.vault)?;
+
+            // 关闭金库账户
+            // 将金库账户的 lamports 返还给创建者
+            CloseAccount {
+                account: self.accounts.vault,       // 要关闭的账户：金库
+                destination: self.accounts.maker,   // 接收 lamports 的账户：创建者
+                authority: self.accounts.escrow,    // 权限：escrow PDA（金库的 owner）
+                token_program: self.accounts.token_program,
+            }.invoke_signed(&[signer.clone()])?;  // ← 使用 PDA 签名调用
+
+            // close_account 说明：
+            // 1. 验证账户余额为 0（代币已全部转出）
+            // 2. 将账户的 lamports 转给 destination
+            // 3. 将账户数据清零，账户可以被重新分配
+        }
 
         // =====================================================================
         // 步骤 3: 从接受者转账代币 B 给创建者
@@ -468,16 +671,15 @@ impl<'info> Take<'info> {
         //       self.mint_b.decimals
         //   )
         //
-        // Pinocchio 版本使用 Transfer 指令
+        // Anchor 版本按 receive 转账，代币 B 带转账手续费时创建者实际收到的会少于 receive；
+        // 这里由接受者承担转账手续费：按当前 epoch 的费率反推转出数量。
+        //
+        // 接受者总共支付 receive：其中协议手续费转入国库，其余转给创建者
+        let fee = self.accounts.pay_protocol_fee(receive)?;
+        let maker_receive = receive.checked_sub(fee).ok_or(ProgramError::ArithmeticOverflow)?;
 
-        // 转账代币 B 从接受者到创建者
-        // 数量：托管账户中记录的期望数量（receive 字段）
-        Transfer {
-            from: self.accounts.taker_ata_b,    // 从：接受者的代币 B ATA
-            to: self.accounts.maker_ata_b,      // 到：创建者的代币 B ATA
-            authority: self.accounts.taker,     // 权限：接受者必须签名
-            amount: receive,                    // 转账数量：托管账户中记录的数量
-        }.invoke()?;  // ← 普通调用，接受者已签名
+        // 转账代币 B 从接受者到创建者（taker_ata_b → maker_ata_b，原生 SOL 则是 taker → maker）
+        self.accounts.pay_maker(maker_receive)?;
 
         // =====================================================================
         // 步骤 4: 关闭托管账户
@@ -495,6 +697,9 @@ impl<'info> Take<'info> {
         // Pinocchio 版本：
         // 手动调用 ProgramAccount::close()
 
+        // 托管单已经成交，从创建者的挂单索引中注销（Anchor 版没有索引）
+        unregister_offer(self.accounts.maker, self.accounts.offer_index, seed)?;
+
         // 关闭托管账户
         // 将托管账户的租金（lamports）返还给创建者
         ProgramAccount::close(
@@ -519,6 +724,16 @@ impl<'info> Take<'info> {
         //
         // 托管交易已完成，无法再次执行
 
+        // 记录成交：amount 为接受者拿到的代币 A，receive 为创建者收到的代币 B
+        EscrowTaken {
+            escrow: self.accounts.escrow.address().to_bytes(),
+            maker: self.accounts.maker.address().to_bytes(),
+            taker: self.accounts.taker.address().to_bytes(),
+            amount,
+            receive,
+        }.emit();
+        bslog!("take", "amount={} receive={} fee={}", amount, receive, fee);
+
         Ok(())
     }
 }
@@ -0,0 +1,227 @@
+// =============================================================================
+// TakePartial 指令 - 部分成交
+// =============================================================================
+// 接受者只买走金库中的一部分代币 A，按比例支付代币 B：
+//
+//   支付的代币 B = ceil(receive × amount / 金库余额)
+//
+// 托管账户中的 receive 同步减去本次支付的数量，剩余部分仍按原价格挂单；
+// amount 等于金库余额时与 Take 完全相同（支付剩余的 receive，关闭金库与托管账户，注销挂单索引）。
+// 向上取整让零头归创建者：无论拆成多少笔，创建者收到的代币 B 都不少于按比例应得的数量。
+// 不是最后一笔时，本次支付必须小于剩余的 receive：receive 小于金库余额时每笔至少付 1，
+// 否则几笔零碎成交就能把 receive 付到 0，剩下的代币 A 随后被 Take 免费拿走。
+//
+// 协议手续费与 Take 相同，按本次支付的代币 B 计算。
+//
+// 原生 SOL：代币 B 是 SOL 时与 Take 一样直接付 lamports；代币 A 是 SOL 时金库还要继续挂单，
+// 不能关闭，买走的部分以 wSOL 转入接受者的 ATA。该 ATA 原本不存在时由接受者付租金创建，
+// 转账后随即由接受者签名关闭，接受者最终收到 lamports，租金也退回；接受者原有的 wSOL ATA
+// 保持不动，买走的部分以 wSOL 留在其中（关闭它会连带解包其中原有的 wSOL）。
+//
+// 账户与 Take 完全相同（包括挂单索引 offer_index）。指令数据在 amount 之后可选带上
+// max_receive：本次应付的代币 B 超过它时拒绝，与 Take 的价格上限相同。
+//
+// Anchor 版托管没有对应指令，这里的检查按 Take 的 has_one 约束补全：
+// 除了 PDA 之外，mint_a / mint_b 也必须与托管账户中记录的一致。
+
+use pinocchio::{AccountView, ProgramResult};
+use pinocchio::cpi::{Seed, Signer};
+use pinocchio::error::ProgramError;
+use pinocchio::sysvars::{clock::Clock, Sysvar};
+use blueshift_common::read_u64_le;
+use blueshift_events::{Event, EscrowTaken};
+use blueshift_log::bslog;
+use crate::{is_native_mint, mint_decimals, AssociatedTokenAccount, AssociatedTokenAccountCheck, AssociatedTokenAccountInit, CloseAccount, Escrow, EscrowError, Take, TransferChecked};
+
+// =============================================================================
+// 按比例计算应付的代币 B
+// =============================================================================
+// 参数：
+//   receive: 托管账户中剩余的期望数量
+//   amount: 本次买走的代币 A，0 < amount <= vault_amount
+//   vault_amount: 金库当前余额
+//
+// 返回 ceil(receive × amount / vault_amount)，结果不超过 receive；
+// amount 为 0 或超过金库余额时返回 EscrowError::InvalidAmount，
+// amount 小于金库余额（还有剩余）而结果已经付清 receive 时同样返回 InvalidAmount
+// verify/escrow 中的 Kani 证明检查它既不会少付也不会超过 receive，且不会留下免费的代币 A
+#[inline(always)]
+pub fn partial_receive(receive: u64, amount: u64, vault_amount: u64) -> Result<u64, ProgramError> {
+    if amount == 0 || amount > vault_amount {
+        return Err(EscrowError::InvalidAmount.into());
+    }
+    // u128 中间结果不会溢出；amount <= vault_amount，所以商不超过 receive
+    let owed = (receive as u128 * amount as u128).div_ceil(vault_amount as u128) as u64;
+    if amount < vault_amount && owed >= receive {
+        return Err(EscrowError::InvalidAmount.into());
+    }
+    Ok(owed)
+}
+
+// =============================================================================
+// TakePartial 指令主结构体
+// =============================================================================
+// take: 复用 Take 的账户校验与 init_if_needed（taker_ata_a / maker_ata_b）
+// amount: 指令数据，接受者想要的代币 A 数量（u64 小端）
+pub struct TakePartial<'info> {
+    pub take: Take<'info>,
+    pub amount: u64,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountView])> for TakePartial<'info> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'info [u8], &'info [AccountView])) -> Result<Self, Self::Error> {
+        // 指令数据：amount (u64)，之后可选 max_receive (u64)
+        let (amount, rest) = data
+            .split_at_checked(size_of::<u64>())
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let amount = read_u64_le(amount, 0).ok_or(ProgramError::InvalidInstructionData)?;
+
+        // 其余数据交给 Take 解析成价格上限（先于任何 ATA 创建）
+        Ok(Self {
+            take: Take::try_from((rest, accounts))?,
+            amount,
+        })
+    }
+}
+
+// =============================================================================
+// TakePartial 指令的业务逻辑实现
+// =============================================================================
+impl<'info> TakePartial<'info> {
+    // 指令判别器：Make = 0、Take = 1、Refund = 2 之后的下一个
+    pub const DISCRIMINATOR: &'info u8 = &3;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.take.accounts;
+
+        // =====================================================================
+        // 读取并校验托管账户（与 Take 相同的 PDA、过期与接受者校验，另外核对两个 mint）
+        // =====================================================================
+        let (seed, receive, bump) = {
+            let data = accounts.escrow.try_borrow()?;
+            let escrow = Escrow::load(&data)?;
+
+            let escrow_key = pdas::escrow_pda_with_bump(
+                &crate::ID.to_bytes(),
+                &accounts.maker.address().to_bytes(),
+                escrow.seed(),
+                escrow.bump[0],
+            )
+            .ok_or(ProgramError::InvalidSeeds)?;
+            if escrow_key != accounts.escrow.address().to_bytes() {
+                return Err(ProgramError::InvalidAccountOwner);
+            }
+            if escrow.mint_a != *accounts.mint_a.address() {
+                return Err(EscrowError::InvalidMintA.into());
+            }
+            if escrow.mint_b != *accounts.mint_b.address() {
+                return Err(EscrowError::InvalidMintB.into());
+            }
+            if escrow.is_expired(Clock::get()?.unix_timestamp) {
+                return Err(EscrowError::EscrowExpired.into());
+            }
+            if !escrow.allows_taker(accounts.taker.address()) {
+                return Err(EscrowError::InvalidTaker.into());
+            }
+
+            (escrow.seed(), escrow.receive(), escrow.bump)
+        };
+
+        // 金库余额（Token Account 偏移 64..72）
+        let vault_amount = {
+            let vault_data = accounts.vault.try_borrow()?;
+            read_u64_le(&vault_data, 64).ok_or(ProgramError::InvalidAccountData)?
+        };
+
+        // 本次应付的代币 B；同时拒绝 0、超过金库余额以及会把 receive 付清却留下代币 A 的数量
+        let owed = partial_receive(receive, self.amount, vault_amount)?;
+        if owed > self.take.max_receive {
+            return Err(EscrowError::ReceiveAboveMax.into());
+        }
+
+        // 全部买走：与 Take 完全相同，关闭金库与托管账户
+        if self.amount == vault_amount {
+            return self.take.process();
+        }
+
+        // =====================================================================
+        // 步骤 1: 从金库转出 amount 个代币 A 给接受者（escrow PDA 签名）
+        // =====================================================================
+        let seed_binding = seed.to_le_bytes();
+        let escrow_seeds = [
+            Seed::from(pdas::ESCROW_SEED),
+            Seed::from(accounts.maker.address().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump),
+        ];
+        // Take 不会为原生 SOL 创建 taker_ata_a，部分成交时补上；
+        // 记下它是不是本指令创建的，转账后只关闭自己创建的 ATA
+        let created_ata_a = is_native_mint(accounts.mint_a)
+            && AssociatedTokenAccount::check(
+                accounts.taker_ata_a,
+                accounts.taker,
+                accounts.mint_a,
+                accounts.token_program,
+            )
+            .is_err();
+        if created_ata_a {
+            AssociatedTokenAccount::init(
+                accounts.taker_ata_a,
+                accounts.mint_a,
+                accounts.taker,
+                accounts.taker,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
+        TransferChecked {
+            from: accounts.vault,
+            mint: accounts.mint_a,
+            to: accounts.taker_ata_a,
+            authority: accounts.escrow,
+            token_program: accounts.token_program,
+            amount: self.amount,
+            decimals: mint_decimals(accounts.mint_a)?,
+        }.invoke_signed(&[Signer::from(&escrow_seeds)])?;
+
+        // 原生 SOL：接受者是交易签名者，关闭刚创建的 wSOL ATA，拿到的是 lamports 而不是 wSOL；
+        // 原有的 ATA 里可能还有接受者自己的 wSOL，保持原样
+        if created_ata_a {
+            CloseAccount {
+                account: accounts.taker_ata_a,
+                destination: accounts.taker,
+                authority: accounts.taker,
+                token_program: accounts.token_program,
+            }.invoke_signed(&[])?;
+        }
+
+        // =====================================================================
+        // 步骤 2: 接受者按比例支付代币 B 给创建者
+        // =====================================================================
+        // 与 Take 相同：协议手续费转入国库，转账手续费由接受者承担，两者之外的部分归创建者
+        let fee = accounts.pay_protocol_fee(owed)?;
+        let maker_receive = owed.checked_sub(fee).ok_or(ProgramError::ArithmeticOverflow)?;
+        accounts.pay_maker(maker_receive)?;
+
+        // =====================================================================
+        // 步骤 3: 更新托管账户中剩余的期望数量
+        // =====================================================================
+        // 金库与托管账户都保留，剩余的代币 A 可以继续被 Take / TakePartial 买走，
+        // 或由创建者 Refund 取回
+        Escrow::load_mut(&mut accounts.escrow.try_borrow_mut()?)?.set_receive(receive - owed);
+
+        // 与 Take 共用 EscrowTaken：amount / receive 为本次成交的数量
+        EscrowTaken {
+            escrow: accounts.escrow.address().to_bytes(),
+            maker: accounts.maker.address().to_bytes(),
+            taker: accounts.taker.address().to_bytes(),
+            amount: self.amount,
+            receive: owed,
+        }.emit();
+        bslog!("take_partial", "amount={} receive={} fee={} remaining={}", self.amount, owed, fee, vault_amount - self.amount);
+
+        Ok(())
+    }
+}
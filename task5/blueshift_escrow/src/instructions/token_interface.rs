@@ -0,0 +1,320 @@
+// =============================================================================
+// Token 接口 - 同时支持旧版 Token Program 与 Token-2022
+// =============================================================================
+// pinocchio_token 的 Transfer / CloseAccount 固定发往旧版 Token Program，
+// Token-2022 的代币用它们转账会因为账户 owner 不符而失败。本模块改为：
+//
+// 1. 由 mint 的 owner 识别代币程序，并要求传入的 token_program 与之一致
+//    （对应 Anchor: Interface<'info, TokenInterface> + mint::token_program = token_program）
+// 2. 转账使用 TransferChecked，decimals 从 mint 读取，两个程序都支持这条指令
+// 3. 手动构造 CPI，program_id 取传入的 token_program，同一份代码适用于两个程序
+//
+// 转账手续费（Token-2022 的 TransferFeeConfig 扩展）：
+// 收款方到账 = 转账数量 - 手续费，手续费暂存在收款账户的 withheld_amount 中。
+// - Take / TakePartial 按 pre_fee_amount 反推接受者应付的代币 B，创建者到账的仍是约定数量
+// - 金库收到的代币 A 已扣掉手续费，之后一律按金库余额结算
+// - 带有未提取手续费的账户 Token-2022 不允许关闭，关闭金库前先把手续费归集到 mint，
+//   此时 mint_a 必须以可写方式传入（没有手续费的代币不受影响）
+//
+// 原生 SOL（mint 为 NATIVE_MINT，只属于旧版 Token Program）：
+// 托管内部仍用 wSOL 金库记账，进出托管时直接收付 lamports，用户不需要自己包装 / 解包：
+// - wrap_lamports：系统转账把 lamports 打进 wSOL 账户，再 SyncNative 同步代币余额
+// - 取出 wSOL 金库中的 SOL 则由各指令关闭金库完成（原生账户有余额也可以关闭，lamports 全部转出）
+
+use pinocchio::{AccountView, ProgramResult};
+use pinocchio::cpi::{invoke_signed, Signer};
+use pinocchio::error::ProgramError;
+use pinocchio::instruction::{InstructionAccount, InstructionView};
+use pinocchio::sysvars::{clock::Clock, Sysvar};
+use pinocchio_system::instructions::Transfer;
+use blueshift_common::{read_u64_le, NATIVE_MINT, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID};
+
+// Mint 中 decimals 的偏移：mint_authority（COption<Pubkey>，36 字节）+ supply（u64）
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+// Token-2022 扩展区的起始偏移：165 字节的基础布局 + 1 字节账户类型
+// Mint 只有 82 字节，带扩展时会补零到 165 字节，所以两种账户的扩展区位置相同
+const EXTENSIONS_OFFSET: usize = 166;
+
+// 扩展类型：mint 上的 TransferFeeConfig，token 账户上的 TransferFeeAmount
+const TRANSFER_FEE_CONFIG: u16 = 1;
+const TRANSFER_FEE_AMOUNT: u16 = 2;
+
+// TransferFeeConfig 中 older / newer 两档费率的偏移（每档 epoch u64 + maximum_fee u64 + 基点 u16），
+// 前面是 transfer_fee_config_authority、withdraw_withheld_authority 与 withheld_amount
+const OLDER_TRANSFER_FEE: usize = 72;
+const NEWER_TRANSFER_FEE: usize = 90;
+
+// 两个代币程序共用的指令判别器
+const CLOSE_ACCOUNT: u8 = 9;
+const TRANSFER_CHECKED: u8 = 12;
+// 只有旧版 Token Program 有原生 mint，这里只对它使用
+const SYNC_NATIVE: u8 = 17;
+// Token-2022 的 TransferFeeExtension 指令及其子指令
+const TRANSFER_FEE_EXTENSION: u8 = 26;
+const HARVEST_WITHHELD_TOKENS_TO_MINT: u8 = 4;
+
+// 费率上限：10_000 基点 = 100%
+const MAX_FEE_BASIS_POINTS: u64 = 10_000;
+
+// =============================================================================
+// 代币程序校验
+// =============================================================================
+// token_program 必须是两个代币程序之一，且 mint 由它持有；
+// 托管只有一个 token_program 账户，所以两种代币必须属于同一个代币程序
+pub fn check_token_program(token_program: &AccountView, mint: &AccountView) -> ProgramResult {
+    let program = token_program.address();
+    if *program != TOKEN_PROGRAM_ID && *program != TOKEN_2022_PROGRAM_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if !mint.owned_by(program) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    Ok(())
+}
+
+// 读取 mint 的 decimals，TransferChecked 要求与 mint 一致
+pub fn mint_decimals(mint: &AccountView) -> Result<u8, ProgramError> {
+    let data = mint.try_borrow()?;
+    data.get(MINT_DECIMALS_OFFSET).copied().ok_or(ProgramError::InvalidAccountData)
+}
+
+// 在 Token-2022 账户的扩展区（type u16 + length u16 + value 的 TLV 序列）中查找扩展；
+// 旧版账户没有扩展区，直接返回 None
+fn find_extension(data: &[u8], extension: u16) -> Option<&[u8]> {
+    let mut offset = EXTENSIONS_OFFSET;
+    while let Some(header) = data.get(offset..offset + 4) {
+        let kind = u16::from_le_bytes([header[0], header[1]]);
+        let len = u16::from_le_bytes([header[2], header[3]]) as usize;
+        // 类型 0 表示后面都是未使用的空间
+        if kind == 0 {
+            return None;
+        }
+        let value = data.get(offset + 4..offset + 4 + len)?;
+        if kind == extension {
+            return Some(value);
+        }
+        offset += 4 + len;
+    }
+    None
+}
+
+// =============================================================================
+// TransferFee - 当前 epoch 生效的转账手续费
+// =============================================================================
+// 计算方式与 Token-2022 的 TransferFee::calculate_fee / calculate_pre_fee_amount 一致
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferFee {
+    // 单笔手续费上限
+    pub maximum_fee: u64,
+    // 费率（基点）
+    pub basis_points: u16,
+}
+
+impl TransferFee {
+    // 从 mint 数据读取第 epoch 个纪元生效的费率；mint 没有 TransferFeeConfig 时返回 None
+    // newer 一档从它记录的 epoch 起生效，在此之前仍按 older 一档收取
+    pub fn from_mint_data(data: &[u8], epoch: u64) -> Option<Self> {
+        let config = find_extension(data, TRANSFER_FEE_CONFIG)?;
+        let offset = if epoch >= read_u64_le(config, NEWER_TRANSFER_FEE)? {
+            NEWER_TRANSFER_FEE
+        } else {
+            OLDER_TRANSFER_FEE
+        };
+        let basis_points = config.get(offset + 16..offset + 18)?;
+        Some(Self {
+            maximum_fee: read_u64_le(config, offset + 8)?,
+            basis_points: u16::from_le_bytes([basis_points[0], basis_points[1]]),
+        })
+    }
+
+    // 转出 amount 时被扣下的手续费：ceil(amount × 基点 / 10_000)，不超过 maximum_fee
+    pub fn fee(&self, amount: u64) -> u64 {
+        if self.basis_points == 0 || amount == 0 {
+            return 0;
+        }
+        let fee = (amount as u128 * self.basis_points as u128).div_ceil(MAX_FEE_BASIS_POINTS as u128);
+        fee.min(self.maximum_fee as u128) as u64
+    }
+
+    // 为了让收款方到账 net，需要转出的数量；超过 u64 时返回 None
+    pub fn pre_fee_amount(&self, net: u64) -> Option<u64> {
+        let basis_points = self.basis_points as u64;
+        if basis_points == 0 || net == 0 {
+            return Some(net);
+        }
+        if basis_points >= MAX_FEE_BASIS_POINTS {
+            return net.checked_add(self.maximum_fee);
+        }
+        let gross = (net as u128 * MAX_FEE_BASIS_POINTS as u128)
+            .div_ceil((MAX_FEE_BASIS_POINTS - basis_points) as u128);
+        // 按比例算出的手续费已经超过上限时，只需多付 maximum_fee
+        if gross - net as u128 >= self.maximum_fee as u128 {
+            return net.checked_add(self.maximum_fee);
+        }
+        u64::try_from(gross).ok()
+    }
+}
+
+// 让收款方实际收到 net 个 mint 代币时需要转出的数量；没有转账手续费时就是 net
+pub fn amount_before_fee(mint: &AccountView, net: u64) -> Result<u64, ProgramError> {
+    if !mint.owned_by(&TOKEN_2022_PROGRAM_ID) {
+        return Ok(net);
+    }
+    let epoch = Clock::get()?.epoch;
+    let fee = TransferFee::from_mint_data(&mint.try_borrow()?, epoch);
+    match fee {
+        Some(fee) => fee.pre_fee_amount(net).ok_or(ProgramError::ArithmeticOverflow),
+        None => Ok(net),
+    }
+}
+
+// =============================================================================
+// TransferChecked - 转账 CPI
+// =============================================================================
+// 账户：from（可写）、mint、to（可写）、authority（签名）
+// 数据：[12, amount (u64 小端), decimals]
+pub struct TransferChecked<'a> {
+    pub from: &'a AccountView,
+    pub mint: &'a AccountView,
+    pub to: &'a AccountView,
+    pub authority: &'a AccountView,
+    pub token_program: &'a AccountView,
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+impl TransferChecked<'_> {
+    // authority 已经是外层交易的签名者
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    // authority 是 PDA 时由 signers 提供签名
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::writable(self.from.address()),
+            InstructionAccount::readonly(self.mint.address()),
+            InstructionAccount::writable(self.to.address()),
+            InstructionAccount::readonly_signer(self.authority.address()),
+        ];
+        let mut data = [0u8; 10];
+        data[0] = TRANSFER_CHECKED;
+        data[1..9].copy_from_slice(&self.amount.to_le_bytes());
+        data[9] = self.decimals;
+
+        invoke_signed(
+            &InstructionView {
+                program_id: self.token_program.address(),
+                accounts: &accounts,
+                data: &data,
+            },
+            &[self.from, self.mint, self.to, self.authority],
+            signers,
+        )
+    }
+}
+
+// =============================================================================
+// CloseAccount - 关闭 token 账户 CPI
+// =============================================================================
+// 账户：account（可写）、destination（可写，接收 lamports）、authority（签名）
+// 数据：[9]
+pub struct CloseAccount<'a> {
+    pub account: &'a AccountView,
+    pub destination: &'a AccountView,
+    pub authority: &'a AccountView,
+    pub token_program: &'a AccountView,
+}
+
+impl CloseAccount<'_> {
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let accounts = [
+            InstructionAccount::writable(self.account.address()),
+            InstructionAccount::writable(self.destination.address()),
+            InstructionAccount::readonly_signer(self.authority.address()),
+        ];
+
+        invoke_signed(
+            &InstructionView {
+                program_id: self.token_program.address(),
+                accounts: &accounts,
+                data: &[CLOSE_ACCOUNT],
+            },
+            &[self.account, self.destination, self.authority],
+            signers,
+        )
+    }
+}
+
+// =============================================================================
+// 原生 SOL
+// =============================================================================
+// mint 是 wSOL 时，这一侧按 lamports 收付（check_token_program 已保证它属于旧版 Token Program）
+#[inline(always)]
+pub fn is_native_mint(mint: &AccountView) -> bool {
+    *mint.address() == NATIVE_MINT
+}
+
+// 由 from（签名者）向 wSOL 账户 account 转入 lamports 并同步余额，代币数量随之增加 lamports 个；
+// account 必须已经是 wSOL 的 token 账户
+pub fn wrap_lamports(
+    from: &AccountView,
+    account: &AccountView,
+    token_program: &AccountView,
+    lamports: u64,
+) -> ProgramResult {
+    Transfer {
+        from,
+        to: account,
+        lamports,
+    }.invoke()?;
+
+    let accounts = [InstructionAccount::writable(account.address())];
+    invoke_signed(
+        &InstructionView {
+            program_id: token_program.address(),
+            accounts: &accounts,
+            data: &[SYNC_NATIVE],
+        },
+        &[account],
+        &[],
+    )
+}
+
+// =============================================================================
+// 关闭前归集手续费
+// =============================================================================
+// account 中有未提取的转账手续费时，调用 HarvestWithheldTokensToMint 把它们移到 mint，
+// 否则随后的 CloseAccount 会被 Token-2022 拒绝。这条指令不需要签名，但 mint 必须可写；
+// 旧版代币与没有手续费的账户什么都不做
+pub fn harvest_withheld_fees(
+    token_program: &AccountView,
+    mint: &AccountView,
+    account: &AccountView,
+) -> ProgramResult {
+    if *token_program.address() != TOKEN_2022_PROGRAM_ID {
+        return Ok(());
+    }
+    let withheld = {
+        let data = account.try_borrow()?;
+        find_extension(&data, TRANSFER_FEE_AMOUNT).and_then(|value| read_u64_le(value, 0))
+    };
+    if withheld.unwrap_or(0) == 0 {
+        return Ok(());
+    }
+
+    let accounts = [
+        InstructionAccount::writable(mint.address()),
+        InstructionAccount::writable(account.address()),
+    ];
+    invoke_signed(
+        &InstructionView {
+            program_id: token_program.address(),
+            accounts: &accounts,
+            data: &[TRANSFER_FEE_EXTENSION, HARVEST_WITHHELD_TOKENS_TO_MINT],
+        },
+        &[mint, account],
+        &[],
+    )
+}
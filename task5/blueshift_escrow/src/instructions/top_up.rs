@@ -0,0 +1,140 @@
+// =============================================================================
+// TopUp 指令 - 向未成交的托管追加代币 A
+// =============================================================================
+// 创建者把 amount 个代币 A 追加到已有托管的金库，同时把报价改为新的 receive，
+// 两步在同一条指令中完成，不会出现"金库已经变多、报价还是旧的"这种可以被吃单的中间状态：
+//
+//   账户：maker（签名）、escrow（可写）、mint_a、maker_ata_a（可写）、vault（可写）、token_program，
+//         代币 A 是原生 SOL 时末尾还要有 system_program
+//   数据：amount (u64 小端) + receive (u64 小端)，两者都必须大于 0
+//
+// receive 是追加之后整个金库的报价，不是增量；部分成交按金库余额与 receive 的比例结算，
+// 所以追加后的单价由创建者自己决定。过期的托管单不能追加，只能 Refund。
+// 与 UpdateOffer 一样，加仓可能抢在吃单之前执行：Take 的 max_receive 限制的是整单总价
+// （金库只会变多），TakePartial 的 max_receive 限制的是本次支付，单价变差时这笔成交被拒绝。
+//
+// 原生 SOL：与 Make 相同，maker（此时必须可写）直接把 amount lamports 转进 wSOL 金库并
+// SyncNative，不经过 maker_ata_a；系统转账的 CPI 要求 System Program 出现在账户列表中。
+
+use pinocchio::{AccountView, ProgramResult};
+use pinocchio::error::ProgramError;
+use pinocchio::sysvars::{clock::Clock, Sysvar};
+use blueshift_common::read_u64_le;
+use blueshift_events::{Event, EscrowToppedUp};
+use blueshift_log::bslog;
+use crate::{check_token_program, is_native_mint, wrap_lamports, mint_decimals, AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountCheck, Escrow, EscrowError, MintInterface, ProgramAccount, SignerAccount, TransferChecked};
+
+// =============================================================================
+// TopUp 指令主结构体
+// =============================================================================
+pub struct TopUp<'info> {
+    pub maker: &'info AccountView,
+    pub escrow: &'info AccountView,
+    pub mint_a: &'info AccountView,
+    pub maker_ata_a: &'info AccountView,
+    pub vault: &'info AccountView,
+    pub token_program: &'info AccountView,
+    pub amount: u64,
+    pub receive: u64,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountView])> for TopUp<'info> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'info [u8], &'info [AccountView])) -> Result<Self, Self::Error> {
+        let [maker, escrow, mint_a, maker_ata_a, vault, token_program, rest @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        check_token_program(token_program, mint_a)?;
+
+        // 金库必须是 escrow 在 mint_a 上的 ATA，追加的代币不能转到别处
+        AssociatedTokenAccount::check(vault, escrow, mint_a, token_program)?;
+        if is_native_mint(mint_a) && rest.is_empty() {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        if data.len() != size_of::<u64>() * 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let amount = read_u64_le(data, 0).ok_or(ProgramError::InvalidInstructionData)?;
+        let receive = read_u64_le(data, 8).ok_or(ProgramError::InvalidInstructionData)?;
+        if amount == 0 || receive == 0 {
+            return Err(EscrowError::InvalidAmount.into());
+        }
+
+        Ok(Self {
+            maker,
+            escrow,
+            mint_a,
+            maker_ata_a,
+            vault,
+            token_program,
+            amount,
+            receive,
+        })
+    }
+}
+
+impl<'info> TopUp<'info> {
+    // 指令判别器：UpdateOffer = 5 之后的下一个
+    pub const DISCRIMINATOR: &'info u8 = &6;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 先校验并改写托管账户，借用在转账 CPI 之前释放
+        {
+            let mut data = self.escrow.try_borrow_mut()?;
+            let escrow = Escrow::load_mut(&mut data)?;
+
+            // 与 UpdateOffer 相同：托管 PDA 由 maker 派生，只有创建者本人能追加
+            let escrow_key = pdas::escrow_pda_with_bump(
+                &crate::ID.to_bytes(),
+                &self.maker.address().to_bytes(),
+                escrow.seed(),
+                escrow.bump[0],
+            )
+            .ok_or(ProgramError::InvalidSeeds)?;
+            if escrow_key != self.escrow.address().to_bytes() {
+                return Err(ProgramError::InvalidAccountOwner);
+            }
+            if escrow.maker != *self.maker.address() {
+                return Err(EscrowError::InvalidMaker.into());
+            }
+            if escrow.mint_a != *self.mint_a.address() {
+                return Err(EscrowError::InvalidMintA.into());
+            }
+            if escrow.is_expired(Clock::get()?.unix_timestamp) {
+                return Err(EscrowError::EscrowExpired.into());
+            }
+
+            escrow.set_receive(self.receive);
+        }
+
+        // 追加的代币 A 由创建者签名转入金库；带转账手续费的代币按金库实际到账结算
+        if is_native_mint(self.mint_a) {
+            wrap_lamports(self.maker, self.vault, self.token_program, self.amount)?;
+        } else {
+            TransferChecked {
+                from: self.maker_ata_a,
+                mint: self.mint_a,
+                to: self.vault,
+                authority: self.maker,
+                token_program: self.token_program,
+                amount: self.amount,
+                decimals: mint_decimals(self.mint_a)?,
+            }.invoke()?;
+        }
+
+        EscrowToppedUp {
+            escrow: self.escrow.address().to_bytes(),
+            maker: self.maker.address().to_bytes(),
+            amount: self.amount,
+            receive: self.receive,
+        }.emit();
+        bslog!("top_up", "amount={} receive={}", self.amount, self.receive);
+        Ok(())
+    }
+}
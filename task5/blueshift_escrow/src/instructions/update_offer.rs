@@ -0,0 +1,112 @@
+// =============================================================================
+// UpdateOffer 指令 - 修改挂单报价
+// =============================================================================
+// 创建者修改未成交托管单要求的代币 B（mint_b 与 receive），金库与托管账户原样保留，
+// 省去 Refund + Make 时关闭、重新创建两个账户的租金与手续费：
+//
+//   账户：maker（签名）、escrow（可写）、mint_a、mint_b（新的代币 B，可以与原来相同）、token_program
+//   数据：receive (u64 小端)，必须大于 0
+//
+// 只改报价，不改存入的代币 A、过期时间与指定的接受者；已经过期的托管单只能 Refund。
+// 注意：Take 按执行时托管账户中的 receive 付款，改单可能抢在吃单之前执行。taker 在 Take /
+// TakePartial 的数据中带上 max_receive，提价之后的吃单返回 ReceiveAboveMax 而不是按新价格成交；
+// 换了 mint_b 的旧 Take 会因 mint_b 与托管账户不符失败。
+
+use pinocchio::{AccountView, ProgramResult};
+use pinocchio::error::ProgramError;
+use pinocchio::sysvars::{clock::Clock, Sysvar};
+use blueshift_common::read_u64_le;
+use blueshift_events::{Event, EscrowUpdated};
+use blueshift_log::bslog;
+use crate::{check_token_program, AccountCheck, Escrow, EscrowError, MintInterface, ProgramAccount, SignerAccount};
+
+// =============================================================================
+// UpdateOffer 指令主结构体
+// =============================================================================
+pub struct UpdateOffer<'info> {
+    pub maker: &'info AccountView,
+    pub escrow: &'info AccountView,
+    pub mint_b: &'info AccountView,
+    pub receive: u64,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountView])> for UpdateOffer<'info> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'info [u8], &'info [AccountView])) -> Result<Self, Self::Error> {
+        let [maker, escrow, mint_a, mint_b, token_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        MintInterface::check(mint_b)?;
+
+        // 与 Make 相同：两种代币必须属于同一个代币程序，否则之后的 Take 无法成交
+        check_token_program(token_program, mint_a)?;
+        check_token_program(token_program, mint_b)?;
+
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let receive = read_u64_le(data, 0).ok_or(ProgramError::InvalidInstructionData)?;
+        // 改成 0 等于把金库白送给第一个 taker，直接拒绝
+        if receive == 0 {
+            return Err(EscrowError::InvalidAmount.into());
+        }
+
+        // mint_a 只用来核对代币程序，必须就是托管单存入的代币
+        if Escrow::load(&escrow.try_borrow()?)?.mint_a != *mint_a.address() {
+            return Err(EscrowError::InvalidMintA.into());
+        }
+
+        Ok(Self {
+            maker,
+            escrow,
+            mint_b,
+            receive,
+        })
+    }
+}
+
+impl<'info> UpdateOffer<'info> {
+    // 指令判别器：InitConfig = 4 之后的下一个
+    pub const DISCRIMINATOR: &'info u8 = &5;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.escrow.try_borrow_mut()?;
+        let escrow = Escrow::load_mut(&mut data)?;
+
+        // 托管 PDA 由 maker 派生：传入别人的托管单或别人的地址都会在这里失败
+        let escrow_key = pdas::escrow_pda_with_bump(
+            &crate::ID.to_bytes(),
+            &self.maker.address().to_bytes(),
+            escrow.seed(),
+            escrow.bump[0],
+        )
+        .ok_or(ProgramError::InvalidSeeds)?;
+        if escrow_key != self.escrow.address().to_bytes() {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if escrow.maker != *self.maker.address() {
+            return Err(EscrowError::InvalidMaker.into());
+        }
+
+        if escrow.is_expired(Clock::get()?.unix_timestamp) {
+            return Err(EscrowError::EscrowExpired.into());
+        }
+
+        escrow.set_mint_b(*self.mint_b.address());
+        escrow.set_receive(self.receive);
+
+        EscrowUpdated {
+            escrow: self.escrow.address().to_bytes(),
+            maker: self.maker.address().to_bytes(),
+            mint_b: self.mint_b.address().to_bytes(),
+            receive: self.receive,
+        }.emit();
+        bslog!("update_offer", "receive={}", self.receive);
+        Ok(())
+    }
+}
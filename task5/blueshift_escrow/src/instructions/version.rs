@@ -0,0 +1,42 @@
+// =============================================================================
+// GetVersion / SetFeatures - 版本查询与功能开关
+// =============================================================================
+// 两条指令的判别器是 8 字节（与 Anchor 指令 get_version / set_features 相同），
+// 所有训练营程序都用同一段数据调用，格式定义在 blueshift_version。
+//
+// - get_version：账户可选传入功能开关 PDA ["feature_gate"]，
+//   return data 为版本号 + 本构建支持的功能位 + 开关中已打开的功能位
+// - set_features：只有程序的升级权限可以调用，账户不存在时先创建
+//
+// 新的代码路径需要分阶段上线时，先用
+// blueshift_common::feature_gate::require_feature 检查 features::escrow 中对应的位，
+// 部署后再由升级权限按集群打开。
+
+use blueshift_common::feature_gate;
+use blueshift_version::{features, FeatureGate, Version};
+use pinocchio::{cpi::Seed, AccountView, ProgramResult};
+
+use crate::{ProgramAccount, ProgramAccountInit};
+
+/// 本程序的版本：Cargo.toml 中的 version 加上已实现的功能
+pub const VERSION: Version = blueshift_version::version!(features::escrow::SUPPORTED);
+
+/// GetVersion 指令处理函数
+pub fn get_version(accounts: &[AccountView]) -> ProgramResult {
+    feature_gate::get_version(VERSION, &crate::ID, accounts)
+}
+
+/// SetFeatures 指令处理函数
+///
+/// 账户：authority（升级权限，签名并支付租金）、program_data、feature_gate、system_program；
+/// 数据：u64 (LE) 新的功能位，整体覆盖旧值
+pub fn set_features(data: &[u8], accounts: &[AccountView]) -> ProgramResult {
+    feature_gate::set_features(&crate::ID, accounts, data, |payer, gate, bump| {
+        let bump_binding = [bump];
+        let seeds = [
+            Seed::from(pdas::FEATURE_GATE_SEED),
+            Seed::from(&bump_binding),
+        ];
+        ProgramAccount::init::<FeatureGate>(payer, gate, &seeds, FeatureGate::LEN)
+    })
+}
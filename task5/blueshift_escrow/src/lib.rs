@@ -3,25 +3,55 @@
 // =============================================================================
 // 这是一个使用 Pinocchio 框架编写的 Solana 智能合约，实现无需信任的代币交换托管系统
 
+// 链上构建不链接标准库；单元测试（Mollusk）仍需要 std
+#![cfg_attr(not(test), no_std)]
+// 程序代码禁止 unwrap / expect / panic!：出错必须返回具体的 ProgramError，
+// 否则链上只能看到一句 `** PANICKED **`（crates/e2e/tests/panic_audit.rs 会检查 .so 中的 panic 信息）
+#![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used, clippy::panic))]
+
 // 导入 Pinocchio 核心组件：
 // - AccountView: 用于读取和操作账户数据的视图
 // - Address: 表示 Solana 地址（公钥/程序 ID）
 // - entrypoint: 宏，用于定义程序的入口点
+// - nostd_panic_handler: 宏，no_std 下的 panic 处理函数
 // - ProgramResult: 程序执行结果的类型别名（Result<(), ProgramError>）
-use pinocchio::{AccountView, Address, entrypoint, ProgramResult};
+use pinocchio::{AccountView, Address, entrypoint, nostd_panic_handler, ProgramResult};
 
 // 导入错误类型，用于处理程序运行时的错误情况
 use pinocchio::error::ProgramError;
 
+// 把 Pinocchio 的错误换算成 Anchor 版托管返回的错误码
+use blueshift_errors::AnchorCompat;
+
+// get_version / set_features 的 8 字节判别器，所有训练营程序共用
+use blueshift_version::{GET_VERSION_DISCRIMINATOR, SET_FEATURES_DISCRIMINATOR};
+
 // 声明程序的入口点函数
 // Solana 运行时会调用这个函数来执行程序逻辑
 entrypoint!(process_instruction);
+// no_std 下由它提供 panic_handler：打印 panic 位置后中止
+nostd_panic_handler!();
+
+// 文本日志：bslog! 打印的每一行都以 `[escrow v<版本>] <指令>:` 开头
+// `--features quiet` 构建时所有 bslog! 在编译期去掉，不消耗 CU
+blueshift_log::program!("escrow");
+
+// 嵌入 security.txt：区块浏览器据此展示漏洞披露的联系方式
+// 只在 `cargo build-sbf --features security-txt` 时编入，测试构建不受影响
+#[cfg(feature = "security-txt")]
+solana_security_txt::security_txt! {
+    name: "Blueshift Pinocchio Escrow",
+    project_url: "https://github.com/o7reconversion/solana_bootcamp_2026",
+    contacts: "link:https://github.com/o7reconversion/solana_bootcamp_2026/security/advisories/new",
+    policy: "https://github.com/o7reconversion/solana_bootcamp_2026/blob/main/SECURITY.md",
+    source_code: "https://github.com/o7reconversion/solana_bootcamp_2026/tree/main/task5/blueshift_escrow"
+}
 
 // =============================================================================
 // 模块声明与导出
 // =============================================================================
 
-// instructions 模块：包含所有指令处理器（Make, Take, Refund）
+// instructions 模块：包含所有指令处理器（Make, Take, TakePartial, Refund, InitConfig, UpdateOffer, TopUp, GetVersion, SetFeatures）
 pub mod instructions;
 pub use instructions::*;
 
@@ -70,11 +100,32 @@ pub const ID: Address = Address::new_from_array(
 // 每个指令都有一个唯一的字节（DISCRIMINATOR）作为标识
 // Solana 运行时会将 instruction_data 的第一个字节与判别器匹配
 // 来决定调用哪个指令处理器
+//
+// 错误码兼容：
+// 返回前把错误换算成 Anchor 托管在同一检查上返回的编号（缺签名 → 3010、seeds 不对 → 2006 等），
+// 客户端用同一张 BootcampError 表即可解读两个实现的错误，见 blueshift_errors::AnchorCompat
 fn process_instruction(
+    program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    dispatch(program_id, accounts, instruction_data).map_err(AnchorCompat::anchor_compat)
+}
+
+fn dispatch(
     _program_id: &Address,
     accounts: &[AccountView],
     instruction_data: &[u8],
 ) -> ProgramResult {
+    // GetVersion / SetFeatures 使用 8 字节判别器（与 Anchor 程序一致），
+    // 首字节 0xa8 / 0x9c 不会与下面的单字节判别器冲突，先单独匹配
+    if instruction_data.starts_with(&GET_VERSION_DISCRIMINATOR) {
+        return get_version(accounts);
+    }
+    if let Some(data) = instruction_data.strip_prefix(&SET_FEATURES_DISCRIMINATOR) {
+        return set_features(data, accounts);
+    }
+
     // split_first() 将 instruction_data 分成第一个字节（判别器）和剩余数据
     // 使用模式匹配来路由到对应的指令处理器
     match instruction_data.split_first() {
@@ -82,16 +133,40 @@ fn process_instruction(
         // - 解析: 传入指令数据和账户列表
         // - Make::try_from: 验证账户并解析指令数据
         // - .process(): 执行业务逻辑
+        // - 新托管单的 seed 登记到创建者的挂单索引 ["offers", maker]，Take / Refund 关闭时注销
         Some((Make::DISCRIMINATOR, data)) => Make::try_from((data, accounts))?.process(),
 
         // Take 指令：接受托管交易
-        // - 无额外数据，只需要账户列表
-        Some((Take::DISCRIMINATOR, _)) => Take::try_from(accounts)?.process(),
+        // - 数据为空，或为接受者的价格上限 max_receive（u64）
+        // - 托管单过期后返回 EscrowExpired，receive 超过 max_receive 时返回 ReceiveAboveMax
+        Some((Take::DISCRIMINATOR, data)) => Take::try_from((data, accounts))?.process(),
+
+        // TakePartial 指令：部分成交
+        // - 数据为想要的代币 A 数量（u64），之后可选 max_receive（u64）；账户与 Take 相同
+        Some((TakePartial::DISCRIMINATOR, data)) => {
+            TakePartial::try_from((data, accounts))?.process()
+        }
 
         // Refund 指令：取消托管交易并退款
         // - 无额外数据，只需要账户列表
+        // - 第一个账户是调用者：maker 随时可以退款，过期后任何人都可以替 maker 退款
         Some((Refund::DISCRIMINATOR, _)) => Refund::try_from(accounts)?.process(),
 
+        // InitConfig 指令：升级权限创建全局配置 ["config"]
+        // - 数据为协议手续费率（u16）与国库地址（32 字节）
+        // - 之后 Take / TakePartial 按该费率从创建者收到的代币 B 中扣下手续费
+        Some((InitConfig::DISCRIMINATOR, data)) => InitConfig::try_from((data, accounts))?.process(),
+
+        // UpdateOffer 指令：创建者修改挂单报价
+        // - 数据为新的 receive（u64），新的 mint_b 由账户传入
+        // - 金库与托管账户保持不变，过期的托管单返回 EscrowExpired
+        Some((UpdateOffer::DISCRIMINATOR, data)) => UpdateOffer::try_from((data, accounts))?.process(),
+
+        // TopUp 指令：创建者向金库追加代币 A
+        // - 数据为追加数量与追加后的整单报价（两个 u64）
+        // - 与 UpdateOffer 一样只允许创建者调用，过期后拒绝
+        Some((TopUp::DISCRIMINATOR, data)) => TopUp::try_from((data, accounts))?.process(),
+
         // 如果判别器不匹配任何已知指令，返回错误
         _ => Err(ProgramError::InvalidInstructionData)
     }
@@ -6,6 +6,7 @@
 use pinocchio::Address;
 use pinocchio::error::ProgramError;
 use core::mem::size_of;
+use blueshift_zerocopy::{layout, Pod, Zeroable};
 
 // =============================================================================
 // Escrow 托管账户结构体
@@ -21,12 +22,28 @@ use core::mem::size_of;
 // - 确保结构体在内存中按 C 语言规则布局
 // - 保证字段顺序和内存对齐与预期一致
 // - 这对于序列化/反序列化非常重要
+//
+// 零拷贝（blueshift_zerocopy）：
+// - 派生 Pod / Zeroable：所有字段都是纯数据，借用时由 bytemuck 检查长度与对齐，没有 unsafe 转换
+// - 多字节整数存成 [u8; 8]，结构体对齐为 1、没有填充字节，
+//   账户数据不需要 8 字节对齐就能直接借用为 &Escrow
+// - 文件末尾的 layout! 在编译期核对长度与各字段偏移
+//
+// 账户判别器：
+// - 第一个字节固定为 Escrow::DISCRIMINATOR（1），由 Make 通过 init 写入，load / load_mut 每次都检查
+// - 长度恰好 154 字节但不是 Make 创建的数据（全零、已关闭账户的 0xff 等）不会被当作托管单
+// - 取值与 Anchor 版 #[account(discriminator = 1)] 相同，两个实现的账户数据逐字节一致
 #[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[bytemuck(crate = "blueshift_zerocopy::bytemuck")]
 pub struct Escrow {
-    // 种子：用于派生 PDA 的随机数
+    // 账户判别器：固定为 DISCRIMINATOR，通过 load / load_mut 借用时已经检查过
+    discriminator: [u8; 1],
+
+    // 种子：用于派生 PDA 的随机数（u64 小端，通过 seed() 读取）
     // 确保每个托管账户都有唯一的地址
     // 客户端和程序使用相同的种子 + maker + mint_a 可以派生出相同的 PDA
-    pub seed: u64,
+    seed: [u8; 8],
 
     // 创建者：发起托管交易的用户地址
     // 用于验证只有创建者才能执行退款操作
@@ -40,15 +57,24 @@ pub struct Escrow {
     // 接受者需要发送这个类型的代币来完成交易
     pub mint_b: Address,
 
-    // 期望数量：创建者希望获得的代币 B 的数量
+    // 期望数量：创建者希望获得的代币 B 的数量（u64 小端，通过 receive() 读取）
     // 接受者必须发送至少这个数量的代币 B 才能接受交易
-    pub receive: u64,
+    receive: [u8; 8],
 
     // Bump 种子：PDA 派生时找到的有效 bump 值
     // Solana 使用 "find_program_address" 查找 PDA，会返回一个 bump 值
     // 验证签名时需要提供这个 bump 值（通常追加在 seeds 后面）
     // 使用 [u8; 1] 而不是 u8 是为了确保内存布局
-    pub bump: [u8;1]
+    pub bump: [u8;1],
+
+    // 过期时间：Unix 时间戳（i64 小端，通过 expiry() 读取），0 表示永不过期
+    // 到期后 Take / TakePartial 拒绝成交，任何人都可以调用 Refund 把代币 A 退还给创建者
+    // 放在 bump 之后而不是中间，已有字段的偏移保持不变
+    expiry: [u8; 8],
+
+    // 指定的接受者：非零时只有该地址签名才能 Take / TakePartial（私下约定的场外成交，
+    // 不会被第三方抢先吃单）；全零表示任何人都可以接受
+    pub taker: Address,
 }
 
 // =============================================================================
@@ -59,19 +85,18 @@ impl Escrow {
     // 常量：账户数据长度
     // ------------------------------------------------------------------------
     // 这是 Escrow 结构体在链上账户中占用的总字节数
-    // 计算方式：每个字段的大小之和
-    // - u64: 8 字节
+    // 计算方式：每个字段的大小之和（对齐为 1，没有填充）
+    // - [u8; 8]: 8 字节
     // - Address: 32 字节
     // - [u8; 1]: 1 字节
-    // 总计：8 + 32 + 32 + 32 + 8 + 1 = 113 字节
+    // 总计：1 + 8 + 32 + 32 + 32 + 8 + 1 + 8 + 32 = 154 字节（由下方 layout! 在编译期核对）
     //
     // 用途：创建账户时需要指定空间大小，客户端和程序都需要知道这个值
-    pub const LEN: usize = size_of::<u64>()                     // seed: 8 字节
-        + size_of::<Address>()                                  // maker: 32 字节
-        + size_of::<Address>()                                  // mint_a: 32 字节
-        + size_of::<Address>()                                  // mint_b: 32 字节
-        + size_of::<u64>()                                      // receive: 8 字节
-        + size_of::<[u8;1]>();                                  // bump: 1 字节
+    pub const LEN: usize = size_of::<Escrow>();
+
+    // 账户判别器：与 Anchor 版 #[account(discriminator = 1)] 相同
+    // 新建账户的数据全为 0，关闭后第一个字节是 0xff，都不等于它
+    pub const DISCRIMINATOR: u8 = 1;
 
     // ------------------------------------------------------------------------
     // 加载可变引用
@@ -83,24 +108,37 @@ impl Escrow {
     //
     // 返回：
     //   成功：返回 Escrow 的可变引用
-    //   失败：返回 InvalidAccountData 错误
+    //   失败：长度不是 154 字节或判别器不符时返回 InvalidAccountData 错误
     //
     // 安全性：
-    //   使用 unsafe 代码块和 transmute 将字节指针转换为结构体指针
-    //   这是因为我们需要直接操作原始内存，避免复制开销
-    //   前提条件：字节数组必须足够大且内存布局正确
+    //   由 blueshift_zerocopy（bytemuck）完成指针转换，这里没有 unsafe 代码
+    //   Escrow 是 Pod 且对齐为 1，长度正确的任意字节都能安全地当作 Escrow 使用
     //
     // #[inline(always)]:
     //   强制编译器内联此函数，消除函数调用开销
     //   对于这种小型辅助函数，内联能提高性能
     #[inline(always)]
     pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        // 验证字节长度是否匹配
-        if bytes.len() != Escrow::LEN {
-            return Err(ProgramError::InvalidAccountData);
+        let escrow: &mut Self = blueshift_zerocopy::load_mut(bytes).map_err(|_| ProgramError::InvalidAccountData)?;
+        escrow.check_discriminator()?;
+        Ok(escrow)
+    }
+
+    // ------------------------------------------------------------------------
+    // 初始化新账户
+    // ------------------------------------------------------------------------
+    // Make 刚创建的账户数据全为 0，load_mut 会因判别器不符而拒绝；
+    // init 要求判别器仍为 0（没有初始化过），写入 DISCRIMINATOR 后返回可变引用
+    //
+    // 失败：长度不符返回 InvalidAccountData，已经初始化过返回 AccountAlreadyInitialized
+    #[inline(always)]
+    pub fn init(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        let escrow: &mut Self = blueshift_zerocopy::load_mut(bytes).map_err(|_| ProgramError::InvalidAccountData)?;
+        if escrow.discriminator != [0] {
+            return Err(ProgramError::AccountAlreadyInitialized);
         }
-        // 将字节指针转换为 Escrow 指针，然后解引用为可变引用
-        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+        escrow.discriminator = [Self::DISCRIMINATOR];
+        Ok(escrow)
     }
 
     // ------------------------------------------------------------------------
@@ -120,12 +158,50 @@ impl Escrow {
     //   例如：验证托管状态、检查创建者等
     #[inline(always)]
     pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
-        // 验证字节长度是否匹配
-        if bytes.len() != Escrow::LEN {
+        let escrow: &Self = blueshift_zerocopy::load(bytes).map_err(|_| ProgramError::InvalidAccountData)?;
+        escrow.check_discriminator()?;
+        Ok(escrow)
+    }
+
+    // 长度正确只说明字节数对得上，判别器才说明这是 Make 写入的托管单
+    #[inline(always)]
+    fn check_discriminator(&self) -> Result<(), ProgramError> {
+        if self.discriminator != [Self::DISCRIMINATOR] {
             return Err(ProgramError::InvalidAccountData);
         }
-        // 将只读字节指针转换为只读 Escrow 指针，然后解引用为引用
-        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------------
+    // Getter 方法：读取按小端存储的整数字段
+    // ------------------------------------------------------------------------
+
+    #[inline(always)]
+    pub fn seed(&self) -> u64 {
+        u64::from_le_bytes(self.seed)
+    }
+
+    #[inline(always)]
+    pub fn receive(&self) -> u64 {
+        u64::from_le_bytes(self.receive)
+    }
+
+    #[inline(always)]
+    pub fn expiry(&self) -> i64 {
+        i64::from_le_bytes(self.expiry)
+    }
+
+    // 当前时间 now（Clock 的 unix_timestamp）是否已到过期时间；expiry 为 0 时永远返回 false
+    #[inline(always)]
+    pub fn is_expired(&self, now: i64) -> bool {
+        let expiry = self.expiry();
+        expiry != 0 && now >= expiry
+    }
+
+    // taker 能否接受本托管：没有指定接受者（全零）时任何人都可以
+    #[inline(always)]
+    pub fn allows_taker(&self, taker: &Address) -> bool {
+        self.taker == Address::default() || self.taker == *taker
     }
 
     // ------------------------------------------------------------------------
@@ -141,7 +217,7 @@ impl Escrow {
 
     #[inline(always)]
     pub fn set_seed(&mut self, seed: u64) {
-        self.seed = seed;
+        self.seed = seed.to_le_bytes();
     }
 
     #[inline(always)]
@@ -161,7 +237,7 @@ impl Escrow {
 
     #[inline(always)]
     pub fn set_receive(&mut self, receive: u64) {
-        self.receive = receive;
+        self.receive = receive.to_le_bytes();
     }
 
     #[inline(always)]
@@ -169,6 +245,16 @@ impl Escrow {
         self.bump = bump;
     }
 
+    #[inline(always)]
+    pub fn set_expiry(&mut self, expiry: i64) {
+        self.expiry = expiry.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn set_taker(&mut self, taker: Address) {
+        self.taker = taker;
+    }
+
     // ------------------------------------------------------------------------
     // 批量设置方法
     // ------------------------------------------------------------------------
@@ -181,17 +267,240 @@ impl Escrow {
     //   mint_b: 请求的代币 mint 地址
     //   receive: 请求的代币数量
     //   bump: PDA bump 种子
+    //   expiry: 过期时间戳，0 表示永不过期
+    //   taker: 指定的接受者，全零表示不限
     //
     // 用途：
     //   在创建托管账户时，一次性初始化所有字段
     //   比逐个调用 setter 方法更高效
     #[inline(always)]
-    pub fn set_inner(&mut self, seed: u64, maker: Address, mint_a: Address, mint_b: Address, receive: u64, bump: [u8;1]) {
-        self.seed = seed;
+    pub fn set_inner(&mut self, seed: u64, maker: Address, mint_a: Address, mint_b: Address, receive: u64, bump: [u8;1], expiry: i64, taker: Address) {
+        self.seed = seed.to_le_bytes();
         self.maker = maker;
         self.mint_a = mint_a;
         self.mint_b = mint_b;
-        self.receive = receive;
+        self.receive = receive.to_le_bytes();
+        self.bump = bump;
+        self.expiry = expiry.to_le_bytes();
+        self.taker = taker;
+    }
+}
+
+// =============================================================================
+// 布局核对
+// =============================================================================
+// 编译期检查 Escrow 为 154 字节、对齐为 1，且各字段在以下偏移上；
+// 同时生成 Escrow::SEED_OFFSET 等常量，与 snapshots/escrow.layout 中的偏移一致
+layout!(Escrow = 154 {
+    DISCRIMINATOR_OFFSET: discriminator = 0,
+    SEED_OFFSET: seed = 1,
+    MAKER_OFFSET: maker = 9,
+    MINT_A_OFFSET: mint_a = 41,
+    MINT_B_OFFSET: mint_b = 73,
+    RECEIVE_OFFSET: receive = 105,
+    BUMP_OFFSET: bump = 113,
+    EXPIRY_OFFSET: expiry = 114,
+    TAKER_OFFSET: taker = 122,
+});
+
+// =============================================================================
+// Config 全局配置账户
+// =============================================================================
+// PDA ["config"]，整个程序只有一个，由升级权限通过 InitConfig 创建：
+// - treasury: 协议手续费的接收方，Take 把手续费转入它在 mint_b 上的 ATA
+//   （通常是国库程序的 ["treasury"] PDA，之后由国库的提取角色取出）
+// - fee_bps: 从创建者收到的代币 B 中扣下的比例（基点），不超过 MAX_FEE_BPS
+//
+// 账户不存在时 Take 不收手续费，创建者收到全部 receive
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[bytemuck(crate = "blueshift_zerocopy::bytemuck")]
+pub struct Config {
+    pub treasury: Address,
+
+    // 手续费率（u16 小端，通过 fee_bps() 读取）
+    fee_bps: [u8; 2],
+
+    pub bump: [u8; 1],
+}
+
+impl Config {
+    // 32 + 2 + 1 = 35 字节
+    pub const LEN: usize = size_of::<Config>();
+
+    // 手续费率上限：1_000 基点，即 10%
+    pub const MAX_FEE_BPS: u16 = 1_000;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        blueshift_zerocopy::load_mut(bytes).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        blueshift_zerocopy::load(bytes).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    #[inline(always)]
+    pub fn fee_bps(&self) -> u16 {
+        u16::from_le_bytes(self.fee_bps)
+    }
+
+    // 成交 amount 个代币 B 时的协议手续费，见 protocol_fee
+    #[inline(always)]
+    pub fn fee(&self, amount: u64) -> Result<u64, ProgramError> {
+        protocol_fee(amount, self.fee_bps())
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, treasury: Address, fee_bps: u16, bump: [u8; 1]) {
+        self.treasury = treasury;
+        self.fee_bps = fee_bps.to_le_bytes();
         self.bump = bump;
     }
-}
\ No newline at end of file
+}
+
+// =============================================================================
+// 协议手续费
+// =============================================================================
+// floor(amount × fee_bps / 10_000)：向下取整，零头留给创建者
+// fee_bps 超过 10_000 时返回 ArithmeticOverflow（InitConfig 已限制在 MAX_FEE_BPS 以内），
+// 所以手续费永远不会超过 amount
+#[inline(always)]
+pub fn protocol_fee(amount: u64, fee_bps: u16) -> Result<u64, ProgramError> {
+    if fee_bps > 10_000 {
+        return Err(ProgramError::ArithmeticOverflow);
+    }
+    (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .and_then(|scaled| u64::try_from(scaled / 10_000).ok())
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+layout!(Config = 35 {
+    TREASURY_OFFSET: treasury = 0,
+    FEE_BPS_OFFSET: fee_bps = 32,
+    BUMP_OFFSET: bump = 34,
+});
+
+// =============================================================================
+// OfferIndex 创建者的挂单索引
+// =============================================================================
+// PDA ["offers", maker]，每个创建者一个，记录其所有未成交托管单的 seed：
+// - Make 登记新托管单的 seed，索引不存在时由 maker 付租金创建
+// - Take / Refund（以及全部买走的 TakePartial）关闭托管账户时注销对应的 seed
+// - TakePartial 部分成交、UpdateOffer、TopUp 不改变索引
+//
+// 客户端读一个账户就能列出某个 maker 的全部挂单（托管地址由 seed 派生），
+// 不用对整个程序做 getProgramAccounts 扫描。
+//
+// 容量固定为 MAX_OPEN_OFFERS，账户不需要 realloc；登记满了的 Make 返回 TooManyOffers。
+// 索引上线之前创建的托管单不在索引中，注销时找不到 seed 直接忽略。
+// 索引账户本身不会关闭，租金留在账户里，下次 Make 时复用
+pub const MAX_OPEN_OFFERS: usize = 16;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[bytemuck(crate = "blueshift_zerocopy::bytemuck")]
+pub struct OfferIndex {
+    // 账户判别器：固定为 OfferIndex::DISCRIMINATOR，与 Escrow 的 1 区分
+    discriminator: [u8; 1],
+
+    pub bump: [u8; 1],
+
+    // 已登记的 seed 个数，seeds[..count] 有效
+    count: [u8; 1],
+
+    // 未成交托管单的 seed（u64 小端），顺序不固定：注销时用最后一个填补空位
+    seeds: [[u8; 8]; MAX_OPEN_OFFERS],
+}
+
+impl OfferIndex {
+    // 1 + 1 + 1 + 8 × 16 = 131 字节
+    pub const LEN: usize = size_of::<OfferIndex>();
+
+    pub const DISCRIMINATOR: u8 = 2;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        let index: &mut Self = blueshift_zerocopy::load_mut(bytes).map_err(|_| ProgramError::InvalidAccountData)?;
+        index.check_discriminator()?;
+        Ok(index)
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        let index: &Self = blueshift_zerocopy::load(bytes).map_err(|_| ProgramError::InvalidAccountData)?;
+        index.check_discriminator()?;
+        Ok(index)
+    }
+
+    // 与 Escrow::init 相同：只接受刚创建、全为 0 的账户，写入判别器与 bump
+    #[inline(always)]
+    pub fn init(bytes: &mut [u8], bump: [u8; 1]) -> Result<&mut Self, ProgramError> {
+        let index: &mut Self = blueshift_zerocopy::load_mut(bytes).map_err(|_| ProgramError::InvalidAccountData)?;
+        if index.discriminator != [0] {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        index.discriminator = [Self::DISCRIMINATOR];
+        index.bump = bump;
+        Ok(index)
+    }
+
+    // count 超过容量说明数据被改坏了，同样当作不是索引账户
+    #[inline(always)]
+    fn check_discriminator(&self) -> Result<(), ProgramError> {
+        if self.discriminator != [Self::DISCRIMINATOR] || self.count() > MAX_OPEN_OFFERS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn count(&self) -> usize {
+        self.count[0] as usize
+    }
+
+    // 已登记的 seed，按存储顺序
+    #[inline(always)]
+    pub fn seeds(&self) -> impl Iterator<Item = u64> + '_ {
+        self.seeds[..self.count()].iter().map(|seed| u64::from_le_bytes(*seed))
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, seed: u64) -> bool {
+        self.seeds().any(|open| open == seed)
+    }
+
+    // 登记 seed；已经满了返回 false（同一 maker 的 seed 由 PDA 保证唯一，这里不再查重）
+    #[inline(always)]
+    pub fn insert(&mut self, seed: u64) -> bool {
+        let count = self.count();
+        if count >= MAX_OPEN_OFFERS {
+            return false;
+        }
+        self.seeds[count] = seed.to_le_bytes();
+        self.count = [count as u8 + 1];
+        true
+    }
+
+    // 注销 seed：把最后一个移到空位上并清零末尾；没有登记过时返回 false
+    #[inline(always)]
+    pub fn remove(&mut self, seed: u64) -> bool {
+        let count = self.count();
+        let Some(position) = self.seeds().position(|open| open == seed) else {
+            return false;
+        };
+        self.seeds[position] = self.seeds[count - 1];
+        self.seeds[count - 1] = [0; 8];
+        self.count = [count as u8 - 1];
+        true
+    }
+}
+
+layout!(OfferIndex = 131 {
+    DISCRIMINATOR_OFFSET: discriminator = 0,
+    BUMP_OFFSET: bump = 1,
+    COUNT_OFFSET: count = 2,
+    SEEDS_OFFSET: seeds = 3,
+});
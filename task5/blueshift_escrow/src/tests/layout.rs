@@ -0,0 +1,126 @@
+// =============================================================================
+// 账户布局快照测试
+// =============================================================================
+// 用已知取值写出 Escrow 账户数据，与 snapshots/escrow.layout 逐字段比对。
+// 字段顺序或宽度一旦改变，已部署的托管账户就会被错误解析，这里会先失败。
+
+use pinocchio::Address;
+use pinocchio::error::ProgramError;
+
+use crate::state::{protocol_fee, Config, Escrow, OfferIndex, MAX_OPEN_OFFERS};
+
+#[test]
+fn test_escrow_layout_matches_snapshot() {
+    let mut data = [0u8; Escrow::LEN];
+    let escrow = Escrow::init(&mut data).unwrap();
+    escrow.set_inner(
+        0x0807_0605_0403_0201,
+        Address::new_from_array([0x11; 32]),
+        Address::new_from_array([0x22; 32]),
+        Address::new_from_array([0x33; 32]),
+        1_000_000,
+        [0xfe],
+        1_767_225_600,
+        Address::new_from_array([0x44; 32]),
+    );
+
+    layout_snapshot::assert_layout(&data, include_str!("../../snapshots/escrow.layout"));
+}
+
+#[test]
+fn test_config_layout_matches_snapshot() {
+    let mut data = [0u8; Config::LEN];
+    Config::load_mut(&mut data)
+        .unwrap()
+        .set_inner(Address::new_from_array([0x55; 32]), 50, [0xfd]);
+
+    layout_snapshot::assert_layout(&data, include_str!("../../snapshots/config.layout"));
+}
+
+#[test]
+fn test_offer_index_layout_matches_snapshot() {
+    let mut data = [0u8; OfferIndex::LEN];
+    let index = OfferIndex::init(&mut data, [0xfc]).unwrap();
+    for seed in [7, 0x0807_0605_0403_0201, 9] {
+        assert!(index.insert(seed));
+    }
+    assert!(index.remove(7));
+
+    layout_snapshot::assert_layout(&data, include_str!("../../snapshots/offer_index.layout"));
+}
+
+// 登记满了拒绝，注销不存在的 seed 什么都不改；注销后空出的位置可以再登记
+#[test]
+fn test_offer_index_insert_and_remove() {
+    let mut data = [0u8; OfferIndex::LEN];
+    let index = OfferIndex::init(&mut data, [0xfc]).unwrap();
+    for seed in 0..MAX_OPEN_OFFERS as u64 {
+        assert!(index.insert(seed));
+    }
+    assert!(!index.insert(100));
+    assert_eq!(index.count(), MAX_OPEN_OFFERS);
+
+    assert!(!index.remove(100));
+    assert!(index.remove(3));
+    assert!(!index.contains(3));
+    assert!(index.insert(100));
+    assert!(index.contains(100));
+
+    // 全部注销后回到空索引
+    let open: Vec<u64> = index.seeds().collect();
+    for seed in open {
+        assert!(index.remove(seed));
+    }
+    assert_eq!(index.count(), 0);
+    assert!(data[OfferIndex::SEEDS_OFFSET..].iter().all(|byte| *byte == 0));
+
+    // 托管账户的数据不能当作索引加载，count 越界的数据也不行
+    assert!(OfferIndex::load(&[Escrow::DISCRIMINATOR; OfferIndex::LEN]).is_err());
+    data[OfferIndex::COUNT_OFFSET] = MAX_OPEN_OFFERS as u8 + 1;
+    assert!(OfferIndex::load(&data).is_err());
+}
+
+// 协议手续费向下取整、不超过成交数量；费率超过 100% 时拒绝而不是溢出
+#[test]
+fn test_protocol_fee_rounds_down() {
+    assert_eq!(protocol_fee(1_000_000, 50), Ok(5_000));
+    assert_eq!(protocol_fee(199, 50), Ok(0));
+    assert_eq!(protocol_fee(u64::MAX, 10_000), Ok(u64::MAX));
+    assert_eq!(protocol_fee(u64::MAX, Config::MAX_FEE_BPS), Ok(u64::MAX / 10));
+    assert!(protocol_fee(1, 10_001).is_err());
+}
+
+// Escrow 对齐为 1：从账户数据的任意偏移都能加载，长度不符时返回错误而不是越界读取
+#[test]
+fn test_escrow_loads_from_unaligned_data() {
+    let mut buffer = [0u8; Escrow::LEN + 1];
+    buffer[1 + Escrow::DISCRIMINATOR_OFFSET] = Escrow::DISCRIMINATOR;
+    buffer[1 + Escrow::RECEIVE_OFFSET..1 + Escrow::BUMP_OFFSET]
+        .copy_from_slice(&1_000_000u64.to_le_bytes());
+    let escrow = Escrow::load(&buffer[1..]).unwrap();
+    assert_eq!(escrow.receive(), 1_000_000);
+    assert_eq!(escrow.seed(), 0);
+
+    assert!(Escrow::load(&buffer).is_err());
+    assert!(Escrow::load_mut(&mut buffer[2..]).is_err());
+}
+
+// 长度正确但判别器不符的数据不是托管单：未初始化（全零）、已关闭（0xff）或其它账户类型一律拒绝；
+// init 只接受未初始化的数据，不能把已有的托管单重新初始化
+#[test]
+fn test_escrow_rejects_wrong_discriminator() {
+    let mut data = [0u8; Escrow::LEN];
+    assert_eq!(Escrow::load(&data).err(), Some(ProgramError::InvalidAccountData));
+    assert!(Escrow::load_mut(&mut data).is_err());
+
+    Escrow::init(&mut data).unwrap();
+    assert_eq!(data[Escrow::DISCRIMINATOR_OFFSET], Escrow::DISCRIMINATOR);
+    assert!(Escrow::load(&data).is_ok());
+    assert_eq!(Escrow::init(&mut data).err(), Some(ProgramError::AccountAlreadyInitialized));
+
+    for tag in [0xff, 2] {
+        data[Escrow::DISCRIMINATOR_OFFSET] = tag;
+        assert!(Escrow::load(&data).is_err());
+        assert!(Escrow::init(&mut data).is_err());
+    }
+}
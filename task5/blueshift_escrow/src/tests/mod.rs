@@ -2,4 +2,6 @@
 // 测试模块入口
 // =============================================================================
 
-pub mod test;
\ No newline at end of file
+pub mod layout;
+pub mod test;
+pub mod transfer_fee;
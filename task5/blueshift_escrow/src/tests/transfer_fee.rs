@@ -0,0 +1,76 @@
+// =============================================================================
+// Token-2022 转账手续费测试
+// =============================================================================
+// TransferFee 的读取与计算必须与 Token-2022 一致：反推的转出数量扣掉手续费后，
+// 收款方到账的不能少于约定数量，否则 Take 之后创建者会少收代币 B。
+
+use crate::TransferFee;
+
+// 构造一个带 TransferFeeConfig 扩展的 Token-2022 mint：
+// 82 字节 mint + 补零到 165 + 账户类型 0x01 + TLV(type 1, len 108)
+fn mint_with_fee(older: (u64, u64, u16), newer: (u64, u64, u16)) -> [u8; 278] {
+    let mut data = [0u8; 278];
+    data[165] = 0x01;
+    data[166..168].copy_from_slice(&1u16.to_le_bytes());
+    data[168..170].copy_from_slice(&108u16.to_le_bytes());
+    for ((epoch, maximum_fee, basis_points), offset) in [(older, 170 + 72), (newer, 170 + 90)] {
+        data[offset..offset + 8].copy_from_slice(&epoch.to_le_bytes());
+        data[offset + 8..offset + 16].copy_from_slice(&maximum_fee.to_le_bytes());
+        data[offset + 16..offset + 18].copy_from_slice(&basis_points.to_le_bytes());
+    }
+    data
+}
+
+#[test]
+fn test_transfer_fee_picks_epoch_schedule() {
+    let data = mint_with_fee((0, 1_000, 50), (10, 5_000, 100));
+
+    assert_eq!(
+        TransferFee::from_mint_data(&data, 9),
+        Some(TransferFee { maximum_fee: 1_000, basis_points: 50 })
+    );
+    assert_eq!(
+        TransferFee::from_mint_data(&data, 10),
+        Some(TransferFee { maximum_fee: 5_000, basis_points: 100 })
+    );
+
+    // 旧版 mint 与没有扩展的 Token-2022 mint 都不收手续费
+    assert_eq!(TransferFee::from_mint_data(&[0u8; 82], 10), None);
+    let mut plain = [0u8; 170];
+    plain[165] = 0x01;
+    assert_eq!(TransferFee::from_mint_data(&plain, 10), None);
+}
+
+#[test]
+fn test_pre_fee_amount_covers_fee() {
+    let fees = [
+        TransferFee { maximum_fee: u64::MAX, basis_points: 1 },
+        TransferFee { maximum_fee: u64::MAX, basis_points: 250 },
+        TransferFee { maximum_fee: 7, basis_points: 9_999 },
+        TransferFee { maximum_fee: 1_000, basis_points: 10_000 },
+    ];
+    for fee in fees {
+        for net in [1, 2, 99, 10_000, 123_456_789, u32::MAX as u64] {
+            let gross = fee.pre_fee_amount(net).unwrap();
+            assert!(gross - fee.fee(gross) >= net, "{fee:?} net={net} gross={gross}");
+        }
+    }
+
+    // 费率为 0 或到账数量为 0 时不用多付
+    let fee = TransferFee { maximum_fee: 10, basis_points: 0 };
+    assert_eq!(fee.pre_fee_amount(500), Some(500));
+    let fee = TransferFee { maximum_fee: 10, basis_points: 100 };
+    assert_eq!(fee.pre_fee_amount(0), Some(0));
+
+    // 手续费封顶：多付的不超过 maximum_fee
+    assert_eq!(fee.pre_fee_amount(1_000_000), Some(1_000_010));
+    // 1% 费率：到账 10_000 需要转出 10_102（手续费 ceil(101.02) = 102）
+    let fee = TransferFee { maximum_fee: u64::MAX, basis_points: 100 };
+    assert_eq!(fee.pre_fee_amount(10_000), Some(10_102));
+    assert_eq!(fee.fee(10_102), 102);
+
+    assert_eq!(
+        TransferFee { maximum_fee: 1, basis_points: 10_000 }.pre_fee_amount(u64::MAX),
+        None
+    );
+}
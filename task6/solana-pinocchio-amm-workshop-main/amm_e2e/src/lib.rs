@@ -0,0 +1,685 @@
+//! blueshift_native_amm 的指令构造与 LiteSVM 环境辅助函数。
+//!
+//! 账户顺序与数据布局与链上程序 `src/instructions/*.rs` 中的 `TryFrom` 实现一一对应。
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::Transaction,
+};
+pub use token_fixtures::litesvm::{mint_supply, token_balance};
+use token_fixtures::TokenProgram;
+
+pub const PROGRAM_ID: Pubkey = pubkey!("22222222222222222222222222222222222222222222");
+
+pub use pdas::{CONFIG_SEED, MINT_LP_SEED};
+pub const TOKEN_PROGRAM_ID: Pubkey = Pubkey::new_from_array(token_fixtures::TOKEN_PROGRAM_ID);
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::new_from_array(token_fixtures::ASSOCIATED_TOKEN_PROGRAM_ID);
+
+/// 构建产物路径：../blueshift_native_amm/target/deploy/blueshift_native_amm.so
+pub fn program_so_path() -> String {
+    format!(
+        "{}/../blueshift_native_amm/target/deploy/blueshift_native_amm.so",
+        env!("CARGO_MANIFEST_DIR")
+    )
+}
+
+/// 加载 AMM 程序的 LiteSVM（SPL Token 与 ATA 程序由 LiteSVM 内置）
+pub fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(PROGRAM_ID, program_so_path())
+        .expect("请先在 blueshift_native_amm 中执行 cargo build-sbf");
+    svm
+}
+
+/// 规范池的 config 与 mint_lp 地址
+pub struct PoolAddresses {
+    pub config: Pubkey,
+    pub config_bump: u8,
+    pub mint_lp: Pubkey,
+    pub lp_bump: u8,
+}
+
+impl PoolAddresses {
+    pub fn canonical(mint_x: &Pubkey, mint_y: &Pubkey) -> Self {
+        let program_id = PROGRAM_ID.to_bytes();
+        let (config, config_bump) =
+            pdas::amm_canonical_config_pda(&program_id, &mint_x.to_bytes(), &mint_y.to_bytes());
+        let (mint_lp, lp_bump) = pdas::amm_mint_lp_pda(&program_id, &config);
+        Self {
+            config: Pubkey::new_from_array(config),
+            config_bump,
+            mint_lp: Pubkey::new_from_array(mint_lp),
+            lp_bump,
+        }
+    }
+}
+
+/// Initialize：规范 50/50 池
+pub fn initialize_ix(
+    payer: &Pubkey,
+    pool: &PoolAddresses,
+    mint_x: &Pubkey,
+    mint_y: &Pubkey,
+    fee: u16,
+    authority: &Pubkey,
+) -> Instruction {
+    let mut data = vec![0u8];
+    data.extend_from_slice(&0u64.to_le_bytes()); // seed（规范池忽略）
+    data.extend_from_slice(&fee.to_le_bytes());
+    data.extend_from_slice(mint_x.as_ref());
+    data.extend_from_slice(mint_y.as_ref());
+    data.push(pool.config_bump);
+    data.push(pool.lp_bump);
+    data.extend_from_slice(&0u16.to_le_bytes()); // weight_x = 0 → 50/50
+    data.push(1); // canonical
+    data.push(0); // lp_decimals = 0 → 默认 6 位
+    data.extend_from_slice(authority.as_ref());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(pool.mint_lp, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// 用户侧的代币账户
+pub struct UserAtas {
+    pub x: Pubkey,
+    pub y: Pubkey,
+    pub lp: Pubkey,
+}
+
+/// 池子金库（config 的 ATA）
+pub struct Vaults {
+    pub x: Pubkey,
+    pub y: Pubkey,
+}
+
+impl Vaults {
+    pub fn of(pool: &PoolAddresses, mint_x: &Pubkey, mint_y: &Pubkey) -> Self {
+        let ata = |mint: &Pubkey| {
+            Pubkey::new_from_array(token_fixtures::ata_address(
+                &pool.config.to_bytes(),
+                &mint.to_bytes(),
+                TokenProgram::Token,
+            ))
+        };
+        Self {
+            x: ata(mint_x),
+            y: ata(mint_y),
+        }
+    }
+}
+
+/// Initialize 并由 `payer` 出资同时创建两个金库 ATA
+pub fn initialize_with_vaults_ix(
+    payer: &Pubkey,
+    pool: &PoolAddresses,
+    mint_x: &Pubkey,
+    mint_y: &Pubkey,
+    fee: u16,
+    authority: &Pubkey,
+) -> Instruction {
+    let vaults = Vaults::of(pool, mint_x, mint_y);
+    let mut ix = initialize_ix(payer, pool, mint_x, mint_y, fee, authority);
+    ix.accounts.truncate(3);
+    ix.accounts.extend([
+        AccountMeta::new_readonly(*mint_x, false),
+        AccountMeta::new_readonly(*mint_y, false),
+        AccountMeta::new(vaults.x, false),
+        AccountMeta::new(vaults.y, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+    ]);
+    ix
+}
+
+pub fn deposit_ix(
+    user: &Pubkey,
+    pool: &PoolAddresses,
+    vaults: &Vaults,
+    atas: &UserAtas,
+    amount: u64,
+    max_x: u64,
+    max_y: u64,
+) -> Instruction {
+    let mut data = vec![1u8];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&max_x.to_le_bytes());
+    data.extend_from_slice(&max_y.to_le_bytes());
+    data.extend_from_slice(&i64::MAX.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(pool.mint_lp, false),
+            AccountMeta::new(vaults.x, false),
+            AccountMeta::new(vaults.y, false),
+            AccountMeta::new(atas.x, false),
+            AccountMeta::new(atas.y, false),
+            AccountMeta::new(atas.lp, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn withdraw_ix(
+    user: &Pubkey,
+    pool: &PoolAddresses,
+    vaults: &Vaults,
+    atas: &UserAtas,
+    amount: u64,
+    min_x: u64,
+    min_y: u64,
+) -> Instruction {
+    let mut data = vec![2u8];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&min_x.to_le_bytes());
+    data.extend_from_slice(&min_y.to_le_bytes());
+    data.extend_from_slice(&i64::MAX.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(pool.mint_lp, false),
+            AccountMeta::new(vaults.x, false),
+            AccountMeta::new(vaults.y, false),
+            AccountMeta::new(atas.x, false),
+            AccountMeta::new(atas.y, false),
+            AccountMeta::new(atas.lp, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn swap_ix(
+    user: &Pubkey,
+    pool: &PoolAddresses,
+    vaults: &Vaults,
+    atas: &UserAtas,
+    is_x: bool,
+    amount: u64,
+    min: u64,
+) -> Instruction {
+    let mut data = vec![3u8, is_x as u8];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&min.to_le_bytes());
+    data.extend_from_slice(&i64::MAX.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(atas.x, false),
+            AccountMeta::new(atas.y, false),
+            AccountMeta::new(vaults.x, false),
+            AccountMeta::new(vaults.y, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// 多资产池的 MultiConfig、mint_lp 与金库；金库为 config 的 ATA，顺序与 `mints` 一致
+pub struct MultiPoolAddresses {
+    pub seed: u64,
+    pub config: Pubkey,
+    pub config_bump: u8,
+    pub mint_lp: Pubkey,
+    pub lp_bump: u8,
+    pub mints: Vec<Pubkey>,
+    pub vaults: Vec<Pubkey>,
+}
+
+impl MultiPoolAddresses {
+    pub fn new(seed: u64, mints: &[Pubkey]) -> Self {
+        let program_id = PROGRAM_ID.to_bytes();
+        let (config, config_bump) = pdas::amm_multi_config_pda(&program_id, seed);
+        let (mint_lp, lp_bump) = pdas::amm_mint_lp_pda(&program_id, &config);
+        let vaults = mints
+            .iter()
+            .map(|mint| {
+                Pubkey::new_from_array(token_fixtures::ata_address(
+                    &config,
+                    &mint.to_bytes(),
+                    TokenProgram::Token,
+                ))
+            })
+            .collect();
+        Self {
+            seed,
+            config: Pubkey::new_from_array(config),
+            config_bump,
+            mint_lp: Pubkey::new_from_array(mint_lp),
+            lp_bump,
+            mints: mints.to_vec(),
+            vaults,
+        }
+    }
+
+    /// 第 `index` 个代币的记账储备。布局见 src/state.rs：state、seed、authority、token_count、
+    /// fee、bump 与 MAX_POOL_TOKENS (8) 个 mint 之后是 8 个 u64 储备
+    pub fn reserve(&self, svm: &LiteSVM, index: usize) -> u64 {
+        let data = svm
+            .get_account(&self.config)
+            .expect("MultiConfig 不存在")
+            .data;
+        let offset = 45 + 8 * 32 + index * 8;
+        u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+    }
+}
+
+/// InitializeMulti：金库需另行创建
+pub fn initialize_multi_ix(
+    payer: &Pubkey,
+    pool: &MultiPoolAddresses,
+    fee: u16,
+    authority: &Pubkey,
+) -> Instruction {
+    let mut data = vec![4u8];
+    data.extend_from_slice(&pool.seed.to_le_bytes());
+    data.extend_from_slice(&fee.to_le_bytes());
+    data.push(pool.config_bump);
+    data.push(pool.lp_bump);
+    data.extend_from_slice(authority.as_ref());
+    data.push(pool.mints.len() as u8);
+    for mint in &pool.mints {
+        data.extend_from_slice(mint.as_ref());
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(pool.mint_lp, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// DepositMulti / WithdrawMulti 共用的账户：固定账户之后是金库，再是用户代币账户
+fn multi_liquidity_ix(
+    discriminator: u8,
+    user: &Pubkey,
+    pool: &MultiPoolAddresses,
+    user_lp: &Pubkey,
+    user_atas: &[Pubkey],
+    amount: u64,
+    limits: &[u64],
+) -> Instruction {
+    let mut data = vec![discriminator];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&i64::MAX.to_le_bytes());
+    for limit in limits {
+        data.extend_from_slice(&limit.to_le_bytes());
+    }
+
+    let mut accounts = vec![
+        AccountMeta::new(*user, true),
+        AccountMeta::new(pool.mint_lp, false),
+        AccountMeta::new(*user_lp, false),
+        AccountMeta::new(pool.config, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    accounts.extend(
+        pool.vaults
+            .iter()
+            .map(|vault| AccountMeta::new(*vault, false)),
+    );
+    accounts.extend(user_atas.iter().map(|ata| AccountMeta::new(*ata, false)));
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
+/// DepositMulti：首次存入时按 `max` 决定初始价格
+pub fn deposit_multi_ix(
+    user: &Pubkey,
+    pool: &MultiPoolAddresses,
+    user_lp: &Pubkey,
+    user_atas: &[Pubkey],
+    amount: u64,
+    max: &[u64],
+) -> Instruction {
+    multi_liquidity_ix(5, user, pool, user_lp, user_atas, amount, max)
+}
+
+pub fn withdraw_multi_ix(
+    user: &Pubkey,
+    pool: &MultiPoolAddresses,
+    user_lp: &Pubkey,
+    user_atas: &[Pubkey],
+    amount: u64,
+    min: &[u64],
+) -> Instruction {
+    multi_liquidity_ix(6, user, pool, user_lp, user_atas, amount, min)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn swap_multi_ix(
+    user: &Pubkey,
+    pool: &MultiPoolAddresses,
+    index_in: u8,
+    index_out: u8,
+    user_in: &Pubkey,
+    user_out: &Pubkey,
+    amount: u64,
+    min: u64,
+) -> Instruction {
+    let mut data = vec![7u8, index_in, index_out];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&min.to_le_bytes());
+    data.extend_from_slice(&i64::MAX.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new(pool.vaults[index_in as usize], false),
+            AccountMeta::new(pool.vaults[index_out as usize], false),
+            AccountMeta::new(*user_in, false),
+            AccountMeta::new(*user_out, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// SyncReservesMulti：记账储备对齐到金库余额
+pub fn sync_reserves_multi_ix(authority: &Pubkey, pool: &MultiPoolAddresses) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(pool.config, false),
+    ];
+    accounts.extend(
+        pool.vaults
+            .iter()
+            .map(|vault| AccountMeta::new_readonly(*vault, false)),
+    );
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: vec![19],
+    }
+}
+
+/// SkimMulti：金库中超出记账储备的部分转到 `recipients`（顺序与 mints 一致）
+pub fn skim_multi_ix(
+    authority: &Pubkey,
+    pool: &MultiPoolAddresses,
+    recipients: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(pool.config, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    accounts.extend(
+        pool.vaults
+            .iter()
+            .map(|vault| AccountMeta::new(*vault, false)),
+    );
+    accounts.extend(recipients.iter().map(|to| AccountMeta::new(*to, false)));
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: vec![20],
+    }
+}
+
+/// 集中流动性池及其金库。金库为 pool PDA 的 ATA，需在 ClmmInitializePool 之前创建。
+pub struct ClmmPoolAddresses {
+    pub pool: Pubkey,
+    pub vault_x: Pubkey,
+    pub vault_y: Pubkey,
+    pub tick_spacing: u16,
+}
+
+impl ClmmPoolAddresses {
+    pub fn new(mint_x: &Pubkey, mint_y: &Pubkey, tick_spacing: u16) -> Self {
+        let (pool, _) = pdas::clmm_pool_pda(
+            &PROGRAM_ID.to_bytes(),
+            &mint_x.to_bytes(),
+            &mint_y.to_bytes(),
+            tick_spacing,
+        );
+        let ata = |mint: &Pubkey| {
+            Pubkey::new_from_array(token_fixtures::ata_address(
+                &pool,
+                &mint.to_bytes(),
+                TokenProgram::Token,
+            ))
+        };
+        Self {
+            pool: Pubkey::new_from_array(pool),
+            vault_x: ata(mint_x),
+            vault_y: ata(mint_y),
+            tick_spacing,
+        }
+    }
+
+    pub fn tick(&self, tick: i32) -> Pubkey {
+        let (address, _) = pdas::clmm_tick_pda(&PROGRAM_ID.to_bytes(), &self.pool.to_bytes(), tick);
+        Pubkey::new_from_array(address)
+    }
+
+    /// 包含 `tick` 的位图字（与链上 `bitmap_position` 相同的压缩方式）
+    pub fn bitmap(&self, tick: i32) -> Pubkey {
+        let word_pos = (tick.div_euclid(self.tick_spacing as i32) >> 8) as i16;
+        let (address, _) =
+            pdas::clmm_tick_bitmap_pda(&PROGRAM_ID.to_bytes(), &self.pool.to_bytes(), word_pos);
+        Pubkey::new_from_array(address)
+    }
+
+    pub fn position(&self, owner: &Pubkey, tick_lower: i32, tick_upper: i32) -> Pubkey {
+        let (address, _) = pdas::clmm_position_pda(
+            &PROGRAM_ID.to_bytes(),
+            &self.pool.to_bytes(),
+            &owner.to_bytes(),
+            tick_lower,
+            tick_upper,
+        );
+        Pubkey::new_from_array(address)
+    }
+}
+
+/// ClmmPool 账户中的价格与活跃流动性
+pub struct ClmmPoolState {
+    pub sqrt_price: u128,
+    pub tick_current: i32,
+    pub liquidity: u128,
+}
+
+impl ClmmPoolState {
+    /// 布局见 src/clmm/state.rs：type、bump、4 个地址、fee、tick_spacing 之后依次为
+    /// sqrt_price (u128)、tick_current (i32)、liquidity (u128)
+    pub fn read(svm: &LiteSVM, pool: &Pubkey) -> Self {
+        let data = svm.get_account(pool).expect("CLMM 池不存在").data;
+        let field = |offset: usize, len: usize| &data[offset..offset + len];
+        Self {
+            sqrt_price: u128::from_le_bytes(field(134, 16).try_into().unwrap()),
+            tick_current: i32::from_le_bytes(field(150, 4).try_into().unwrap()),
+            liquidity: u128::from_le_bytes(field(154, 16).try_into().unwrap()),
+        }
+    }
+}
+
+/// ClmmInitializePool：`sqrt_price` 为 Q64.64 的初始价格
+pub fn clmm_initialize_pool_ix(
+    payer: &Pubkey,
+    clmm: &ClmmPoolAddresses,
+    mint_x: &Pubkey,
+    mint_y: &Pubkey,
+    fee: u16,
+    sqrt_price: u128,
+) -> Instruction {
+    let mut data = vec![8u8];
+    data.extend_from_slice(&fee.to_le_bytes());
+    data.extend_from_slice(&clmm.tick_spacing.to_le_bytes());
+    data.extend_from_slice(&sqrt_price.to_le_bytes());
+    data.extend_from_slice(mint_x.as_ref());
+    data.extend_from_slice(mint_y.as_ref());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(clmm.pool, false),
+            AccountMeta::new_readonly(clmm.vault_x, false),
+            AccountMeta::new_readonly(clmm.vault_y, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn clmm_open_position_ix(
+    owner: &Pubkey,
+    clmm: &ClmmPoolAddresses,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Instruction {
+    let mut data = vec![9u8];
+    data.extend_from_slice(&tick_lower.to_le_bytes());
+    data.extend_from_slice(&tick_upper.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(clmm.pool, false),
+            AccountMeta::new(clmm.position(owner, tick_lower, tick_upper), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// ClmmIncreaseLiquidity：tick 与位图账户按区间两端推导，首次使用时由 owner 付费创建
+#[allow(clippy::too_many_arguments)]
+pub fn clmm_increase_liquidity_ix(
+    owner: &Pubkey,
+    clmm: &ClmmPoolAddresses,
+    user_x: &Pubkey,
+    user_y: &Pubkey,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+    max_x: u64,
+    max_y: u64,
+) -> Instruction {
+    let mut data = vec![10u8];
+    data.extend_from_slice(&liquidity.to_le_bytes());
+    data.extend_from_slice(&max_x.to_le_bytes());
+    data.extend_from_slice(&max_y.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(clmm.pool, false),
+            AccountMeta::new(clmm.position(owner, tick_lower, tick_upper), false),
+            AccountMeta::new(clmm.tick(tick_lower), false),
+            AccountMeta::new(clmm.tick(tick_upper), false),
+            AccountMeta::new(clmm.bitmap(tick_lower), false),
+            AccountMeta::new(clmm.bitmap(tick_upper), false),
+            AccountMeta::new(clmm.vault_x, false),
+            AccountMeta::new(clmm.vault_y, false),
+            AccountMeta::new(*user_x, false),
+            AccountMeta::new(*user_y, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// ClmmSwap（精确输入、不限价）：`remaining` 为价格经过的位图字与会被穿越的 tick 账户
+#[allow(clippy::too_many_arguments)]
+pub fn clmm_swap_ix(
+    user: &Pubkey,
+    clmm: &ClmmPoolAddresses,
+    user_x: &Pubkey,
+    user_y: &Pubkey,
+    x_to_y: bool,
+    amount: u64,
+    min: u64,
+    remaining: &[Pubkey],
+) -> Instruction {
+    let mut data = vec![12u8, x_to_y as u8];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&min.to_le_bytes());
+    data.extend_from_slice(&0u128.to_le_bytes());
+    data.extend_from_slice(&i64::MAX.to_le_bytes());
+
+    let mut accounts = vec![
+        AccountMeta::new(*user, true),
+        AccountMeta::new(clmm.pool, false),
+        AccountMeta::new(clmm.vault_x, false),
+        AccountMeta::new(clmm.vault_y, false),
+        AccountMeta::new(*user_x, false),
+        AccountMeta::new(*user_y, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    accounts.extend(
+        remaining
+            .iter()
+            .map(|account| AccountMeta::new(*account, false)),
+    );
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
+/// 以 `payer` 付费并签名发送单条指令
+pub fn send(
+    svm: &mut LiteSVM,
+    ix: Instruction,
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> Result<(), String> {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &all_signers,
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)
+        .map(|_| ())
+        .map_err(|failed| format!("{:?}\n{}", failed.err, failed.meta.logs.join("\n")))
+}
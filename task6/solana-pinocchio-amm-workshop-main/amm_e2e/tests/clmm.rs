@@ -0,0 +1,208 @@
+//! 集中流动性池：两个相邻区间的仓位共用 tick 100，swap 穿越该 tick 时切换活跃流动性。
+
+use amm_e2e::*;
+use litesvm::LiteSVM;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use token_fixtures::{litesvm as fixtures, TokenProgram};
+
+const FEE_BPS: u16 = 30;
+const TICK_SPACING: u16 = 10;
+/// 1.0 的 Q64.64 sqrt price，对应 tick 0
+const SQRT_PRICE_ONE: u128 = 1 << 64;
+/// 仓位 A：[-100, 100)，包含初始价格
+const LIQUIDITY_A: u128 = 1_000_000_000_000;
+/// 仓位 B：[100, 200)，初始价格在其下方，只存入 x
+const LIQUIDITY_B: u128 = 2_000_000_000_000;
+const BALANCE: u64 = 20_000_000_000;
+
+struct ClmmPool {
+    mint_x: Pubkey,
+    mint_y: Pubkey,
+    addresses: ClmmPoolAddresses,
+}
+
+fn setup_pool(svm: &mut LiteSVM) -> ClmmPool {
+    let admin = Keypair::new();
+    svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+
+    // 池子要求 mint_x < mint_y
+    let mint_a = fixtures::create_mint(svm, &admin.pubkey(), 6, TokenProgram::Token);
+    let mint_b = fixtures::create_mint(svm, &admin.pubkey(), 6, TokenProgram::Token);
+    let (mint_x, mint_y) = if mint_a < mint_b {
+        (mint_a, mint_b)
+    } else {
+        (mint_b, mint_a)
+    };
+
+    let addresses = ClmmPoolAddresses::new(&mint_x, &mint_y, TICK_SPACING);
+    fixtures::create_ata(svm, &addresses.pool, &mint_x, TokenProgram::Token);
+    fixtures::create_ata(svm, &addresses.pool, &mint_y, TokenProgram::Token);
+    send(
+        svm,
+        clmm_initialize_pool_ix(
+            &admin.pubkey(),
+            &addresses,
+            &mint_x,
+            &mint_y,
+            FEE_BPS,
+            SQRT_PRICE_ONE,
+        ),
+        &admin,
+        &[],
+    )
+    .unwrap();
+
+    ClmmPool {
+        mint_x,
+        mint_y,
+        addresses,
+    }
+}
+
+fn create_user(svm: &mut LiteSVM, pool: &ClmmPool, x: u64, y: u64) -> (Keypair, Pubkey, Pubkey) {
+    let user = Keypair::new();
+    svm.airdrop(&user.pubkey(), 10_000_000_000).unwrap();
+
+    let owner = user.pubkey();
+    let x_ata = fixtures::mint_to(svm, &pool.mint_x, &owner, x, TokenProgram::Token);
+    let y_ata = fixtures::mint_to(svm, &pool.mint_y, &owner, y, TokenProgram::Token);
+    (user, x_ata, y_ata)
+}
+
+fn add_position(
+    svm: &mut LiteSVM,
+    pool: &ClmmPool,
+    (owner, x_ata, y_ata): &(Keypair, Pubkey, Pubkey),
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+) {
+    send(
+        svm,
+        clmm_open_position_ix(&owner.pubkey(), &pool.addresses, tick_lower, tick_upper),
+        owner,
+        &[],
+    )
+    .unwrap();
+    send(
+        svm,
+        clmm_increase_liquidity_ix(
+            &owner.pubkey(),
+            &pool.addresses,
+            x_ata,
+            y_ata,
+            tick_lower,
+            tick_upper,
+            liquidity,
+            u64::MAX,
+            u64::MAX,
+        ),
+        owner,
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_swap_crosses_initialized_tick() {
+    let mut svm = setup_svm();
+    let pool = setup_pool(&mut svm);
+    let vault_x = pool.addresses.vault_x;
+    let vault_y = pool.addresses.vault_y;
+
+    // 1. 存入两个仓位：A 跨越当前价格，B 在当前价格上方
+    let lp = create_user(&mut svm, &pool, BALANCE, BALANCE);
+    add_position(&mut svm, &pool, &lp, -100, 100, LIQUIDITY_A);
+    add_position(&mut svm, &pool, &lp, 100, 200, LIQUIDITY_B);
+
+    // 数量按 Δx = L(1/√Pa - 1/√Pb)、Δy = L(√Pb - √Pa) 向上取整
+    assert_eq!(token_balance(&svm, &vault_x), 4_987_272_071 + 9_924_798_377);
+    assert_eq!(token_balance(&svm, &vault_y), 4_987_272_071);
+    let state = ClmmPoolState::read(&svm, &pool.addresses.pool);
+    assert_eq!(state.tick_current, 0);
+    assert_eq!(state.liquidity, LIQUIDITY_A);
+
+    // 2. y -> x 推高价格：先在 A 中走到 tick 100，穿越后在 B 中用完剩余输入
+    let (trader, trader_x, trader_y) = create_user(&mut svm, &pool, 0, 10_000_000_000);
+    let word_0 = pool.addresses.bitmap(0);
+    let tick_100 = pool.addresses.tick(100);
+
+    // 缺少将被穿越的 tick 账户时必须失败
+    assert!(send(
+        &mut svm,
+        clmm_swap_ix(
+            &trader.pubkey(),
+            &pool.addresses,
+            &trader_x,
+            &trader_y,
+            false,
+            10_000_000_000,
+            0,
+            &[word_0],
+        ),
+        &trader,
+        &[],
+    )
+    .is_err());
+
+    send(
+        &mut svm,
+        clmm_swap_ix(
+            &trader.pubkey(),
+            &pool.addresses,
+            &trader_x,
+            &trader_y,
+            false,
+            10_000_000_000,
+            0,
+            &[word_0, tick_100],
+        ),
+        &trader,
+        &[],
+    )
+    .unwrap();
+
+    // A 的 x 全部换出（向下取整少 1），其余来自 B
+    assert_eq!(token_balance(&svm, &trader_y), 0);
+    assert_eq!(
+        token_balance(&svm, &trader_x),
+        4_987_272_070 + 4_896_325_787
+    );
+    let state = ClmmPoolState::read(&svm, &pool.addresses.pool);
+    assert_eq!(state.tick_current, 149);
+    assert_eq!(state.liquidity, LIQUIDITY_B);
+
+    // 3. x -> y 反向穿越 tick 100，活跃流动性恢复为 A
+    send(
+        &mut svm,
+        clmm_swap_ix(
+            &trader.pubkey(),
+            &pool.addresses,
+            &trader_x,
+            &trader_y,
+            true,
+            7_000_000_000,
+            0,
+            &[word_0, tick_100],
+        ),
+        &trader,
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        token_balance(&svm, &trader_x),
+        4_987_272_070 + 4_896_325_787 - 7_000_000_000
+    );
+    assert_eq!(
+        token_balance(&svm, &trader_y),
+        4_957_730_374 + 2_099_210_496
+    );
+    let state = ClmmPoolState::read(&svm, &pool.addresses.pool);
+    assert_eq!(state.tick_current, 58);
+    assert_eq!(state.liquidity, LIQUIDITY_A);
+    assert_eq!(
+        token_balance(&svm, &vault_y),
+        4_987_272_071 + 10_000_000_000 - 4_957_730_374 - 2_099_210_496
+    );
+}
@@ -0,0 +1,295 @@
+//! initialize → deposit → swap → withdraw 的完整流程，使用真实的 SPL Token 程序与 ATA。
+
+use amm_e2e::*;
+use litesvm::LiteSVM;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use token_fixtures::{litesvm as fixtures, TokenProgram};
+
+const FEE_BPS: u16 = 30;
+const LP_AMOUNT: u64 = 1_000_000;
+const INITIAL_X: u64 = 1_000_000_000;
+const INITIAL_Y: u64 = 4_000_000_000;
+const SWAP_IN: u64 = 10_000_000;
+
+struct Pool {
+    mint_x: Pubkey,
+    mint_y: Pubkey,
+    addresses: PoolAddresses,
+    vaults: Vaults,
+}
+
+fn create_user(svm: &mut LiteSVM, pool: &Pool, x: u64, y: u64) -> (Keypair, UserAtas) {
+    let user = Keypair::new();
+    svm.airdrop(&user.pubkey(), 10_000_000_000).unwrap();
+
+    let owner = user.pubkey();
+    let atas = UserAtas {
+        x: fixtures::mint_to(svm, &pool.mint_x, &owner, x, TokenProgram::Token),
+        y: fixtures::mint_to(svm, &pool.mint_y, &owner, y, TokenProgram::Token),
+        lp: fixtures::create_ata(svm, &owner, &pool.addresses.mint_lp, TokenProgram::Token),
+    };
+    (user, atas)
+}
+
+fn setup_pool(svm: &mut LiteSVM) -> Pool {
+    let admin = Keypair::new();
+    svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+
+    // 规范池要求 mint_x < mint_y
+    let mint_a = fixtures::create_mint(svm, &admin.pubkey(), 6, TokenProgram::Token);
+    let mint_b = fixtures::create_mint(svm, &admin.pubkey(), 6, TokenProgram::Token);
+    let (mint_x, mint_y) = if mint_a < mint_b {
+        (mint_a, mint_b)
+    } else {
+        (mint_b, mint_a)
+    };
+
+    let addresses = PoolAddresses::canonical(&mint_x, &mint_y);
+    send(
+        svm,
+        initialize_ix(
+            &admin.pubkey(),
+            &addresses,
+            &mint_x,
+            &mint_y,
+            FEE_BPS,
+            &admin.pubkey(),
+        ),
+        &admin,
+        &[],
+    )
+    .unwrap();
+
+    let vaults = Vaults {
+        x: fixtures::create_ata(svm, &addresses.config, &mint_x, TokenProgram::Token),
+        y: fixtures::create_ata(svm, &addresses.config, &mint_y, TokenProgram::Token),
+    };
+
+    Pool {
+        mint_x,
+        mint_y,
+        addresses,
+        vaults,
+    }
+}
+
+#[test]
+fn test_initialize_deposit_swap_withdraw() {
+    let mut svm = setup_svm();
+    let pool = setup_pool(&mut svm);
+    assert_eq!(mint_supply(&svm, &pool.addresses.mint_lp), 0);
+
+    // 1. 首次存入：按 max_x / max_y 决定初始价格
+    let (lp, lp_atas) = create_user(&mut svm, &pool, INITIAL_X, INITIAL_Y);
+    send(
+        &mut svm,
+        deposit_ix(
+            &lp.pubkey(),
+            &pool.addresses,
+            &pool.vaults,
+            &lp_atas,
+            LP_AMOUNT,
+            INITIAL_X,
+            INITIAL_Y,
+        ),
+        &lp,
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(token_balance(&svm, &pool.vaults.x), INITIAL_X);
+    assert_eq!(token_balance(&svm, &pool.vaults.y), INITIAL_Y);
+    assert_eq!(token_balance(&svm, &lp_atas.lp), LP_AMOUNT);
+    assert_eq!(mint_supply(&svm, &pool.addresses.mint_lp), LP_AMOUNT);
+
+    // 2. 兑换 X -> Y：输出按扣除手续费后的恒定乘积计算
+    let (trader, trader_atas) = create_user(&mut svm, &pool, SWAP_IN, 0);
+    let in_after_fee = SWAP_IN as u128 * (10_000 - FEE_BPS as u128) / 10_000;
+    let expected_out =
+        (INITIAL_Y as u128 * in_after_fee / (INITIAL_X as u128 + in_after_fee)) as u64;
+
+    // 最小输出高于报价时必须失败
+    assert!(send(
+        &mut svm,
+        swap_ix(
+            &trader.pubkey(),
+            &pool.addresses,
+            &pool.vaults,
+            &trader_atas,
+            true,
+            SWAP_IN,
+            expected_out + 1,
+        ),
+        &trader,
+        &[],
+    )
+    .is_err());
+
+    send(
+        &mut svm,
+        swap_ix(
+            &trader.pubkey(),
+            &pool.addresses,
+            &pool.vaults,
+            &trader_atas,
+            true,
+            SWAP_IN,
+            expected_out,
+        ),
+        &trader,
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(token_balance(&svm, &trader_atas.x), 0);
+    assert_eq!(token_balance(&svm, &trader_atas.y), expected_out);
+    assert_eq!(token_balance(&svm, &pool.vaults.x), INITIAL_X + SWAP_IN);
+    assert_eq!(
+        token_balance(&svm, &pool.vaults.y),
+        INITIAL_Y - expected_out
+    );
+
+    // 手续费留在池内：k 严格增大
+    let k_before = INITIAL_X as u128 * INITIAL_Y as u128;
+    let k_after = (INITIAL_X + SWAP_IN) as u128 * (INITIAL_Y - expected_out) as u128;
+    assert!(k_after > k_before);
+
+    // 3. 全额提取：LP 取回全部储备（含手续费），LP 供应量归零
+    send(
+        &mut svm,
+        withdraw_ix(
+            &lp.pubkey(),
+            &pool.addresses,
+            &pool.vaults,
+            &lp_atas,
+            LP_AMOUNT,
+            INITIAL_X,
+            0,
+        ),
+        &lp,
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(mint_supply(&svm, &pool.addresses.mint_lp), 0);
+    assert_eq!(token_balance(&svm, &lp_atas.lp), 0);
+    assert_eq!(token_balance(&svm, &lp_atas.x), INITIAL_X + SWAP_IN);
+    assert_eq!(token_balance(&svm, &lp_atas.y), INITIAL_Y - expected_out);
+    assert_eq!(token_balance(&svm, &pool.vaults.x), 0);
+    assert_eq!(token_balance(&svm, &pool.vaults.y), 0);
+}
+
+#[test]
+fn test_partial_withdraw_is_proportional() {
+    let mut svm = setup_svm();
+    let pool = setup_pool(&mut svm);
+
+    let (lp, lp_atas) = create_user(&mut svm, &pool, INITIAL_X, INITIAL_Y);
+    send(
+        &mut svm,
+        deposit_ix(
+            &lp.pubkey(),
+            &pool.addresses,
+            &pool.vaults,
+            &lp_atas,
+            LP_AMOUNT,
+            INITIAL_X,
+            INITIAL_Y,
+        ),
+        &lp,
+        &[],
+    )
+    .unwrap();
+
+    // 第二位 LP 按当前比例存入 1/4 的份额
+    let (second, second_atas) = create_user(&mut svm, &pool, INITIAL_X, INITIAL_Y);
+    send(
+        &mut svm,
+        deposit_ix(
+            &second.pubkey(),
+            &pool.addresses,
+            &pool.vaults,
+            &second_atas,
+            LP_AMOUNT / 4,
+            INITIAL_X,
+            INITIAL_Y,
+        ),
+        &second,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        token_balance(&svm, &second_atas.x),
+        INITIAL_X - INITIAL_X / 4
+    );
+    assert_eq!(
+        token_balance(&svm, &second_atas.y),
+        INITIAL_Y - INITIAL_Y / 4
+    );
+
+    // 提取一半 LP：取回初始储备的 1/2
+    send(
+        &mut svm,
+        withdraw_ix(
+            &lp.pubkey(),
+            &pool.addresses,
+            &pool.vaults,
+            &lp_atas,
+            LP_AMOUNT / 2,
+            0,
+            0,
+        ),
+        &lp,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(token_balance(&svm, &lp_atas.x), INITIAL_X / 2);
+    assert_eq!(token_balance(&svm, &lp_atas.y), INITIAL_Y / 2);
+    assert_eq!(
+        mint_supply(&svm, &pool.addresses.mint_lp),
+        LP_AMOUNT + LP_AMOUNT / 4 - LP_AMOUNT / 2
+    );
+}
+
+#[test]
+fn test_sponsored_initialize_keeps_authority_separate() {
+    let mut svm = setup_svm();
+    let sponsor = Keypair::new();
+    svm.airdrop(&sponsor.pubkey(), 10_000_000_000).unwrap();
+    // DAO 的管理地址既不签名也不付费
+    let dao = Pubkey::new_unique();
+
+    let mint_a = fixtures::create_mint(&mut svm, &sponsor.pubkey(), 6, TokenProgram::Token);
+    let mint_b = fixtures::create_mint(&mut svm, &sponsor.pubkey(), 6, TokenProgram::Token);
+    let (mint_x, mint_y) = if mint_a < mint_b {
+        (mint_a, mint_b)
+    } else {
+        (mint_b, mint_a)
+    };
+    let addresses = PoolAddresses::canonical(&mint_x, &mint_y);
+
+    send(
+        &mut svm,
+        initialize_with_vaults_ix(
+            &sponsor.pubkey(),
+            &addresses,
+            &mint_x,
+            &mint_y,
+            FEE_BPS,
+            &dao,
+        ),
+        &sponsor,
+        &[],
+    )
+    .unwrap();
+
+    // Config 记录的是 DAO 地址（state: u8, seed: [u8; 8] 之后）
+    let config = svm.get_account(&addresses.config).unwrap();
+    assert_eq!(&config.data[9..41], dao.as_ref());
+    assert!(svm.get_account(&dao).is_none());
+
+    // 金库 ATA 已由 sponsor 出资创建
+    let vaults = Vaults::of(&addresses, &mint_x, &mint_y);
+    assert_eq!(token_balance(&svm, &vaults.x), 0);
+    assert_eq!(token_balance(&svm, &vaults.y), 0);
+}
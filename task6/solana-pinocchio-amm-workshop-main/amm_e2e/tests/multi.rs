@@ -0,0 +1,325 @@
+//! 三币多资产池：initialize → deposit → swap → withdraw，以及 SyncReservesMulti / SkimMulti。
+
+use amm_e2e::*;
+use litesvm::LiteSVM;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use token_fixtures::{litesvm as fixtures, TokenProgram};
+
+const FEE_BPS: u16 = 30;
+const SEED: u64 = 7;
+const LP_AMOUNT: u64 = 1_000_000;
+/// 首次存入的三种代币数量，决定初始价格
+const INITIAL: [u64; 3] = [1_000_000_000, 2_000_000_000, 4_000_000_000];
+const SWAP_IN: u64 = 10_000_000;
+
+struct MultiPool {
+    admin: Keypair,
+    addresses: MultiPoolAddresses,
+}
+
+fn setup_pool(svm: &mut LiteSVM) -> MultiPool {
+    let admin = Keypair::new();
+    svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+
+    let mints: Vec<Pubkey> = (0..3)
+        .map(|_| fixtures::create_mint(svm, &admin.pubkey(), 6, TokenProgram::Token))
+        .collect();
+    let addresses = MultiPoolAddresses::new(SEED, &mints);
+    send(
+        svm,
+        initialize_multi_ix(&admin.pubkey(), &addresses, FEE_BPS, &admin.pubkey()),
+        &admin,
+        &[],
+    )
+    .unwrap();
+    for mint in &mints {
+        fixtures::create_ata(svm, &addresses.config, mint, TokenProgram::Token);
+    }
+
+    MultiPool { admin, addresses }
+}
+
+/// 返回用户、LP 账户与按 mints 顺序排列的代币账户
+fn create_user(
+    svm: &mut LiteSVM,
+    pool: &MultiPool,
+    amounts: [u64; 3],
+) -> (Keypair, Pubkey, Vec<Pubkey>) {
+    let user = Keypair::new();
+    svm.airdrop(&user.pubkey(), 10_000_000_000).unwrap();
+
+    let owner = user.pubkey();
+    let atas = pool
+        .addresses
+        .mints
+        .iter()
+        .zip(amounts)
+        .map(|(mint, amount)| fixtures::mint_to(svm, mint, &owner, amount, TokenProgram::Token))
+        .collect();
+    let lp = fixtures::create_ata(svm, &owner, &pool.addresses.mint_lp, TokenProgram::Token);
+    (user, lp, atas)
+}
+
+fn vault_balances(svm: &LiteSVM, pool: &MultiPool) -> Vec<u64> {
+    pool.addresses
+        .vaults
+        .iter()
+        .map(|vault| token_balance(svm, vault))
+        .collect()
+}
+
+fn reserves(svm: &LiteSVM, pool: &MultiPool) -> Vec<u64> {
+    (0..3).map(|i| pool.addresses.reserve(svm, i)).collect()
+}
+
+#[test]
+fn test_multi_initialize_deposit_swap_withdraw() {
+    let mut svm = setup_svm();
+    let pool = setup_pool(&mut svm);
+    assert_eq!(mint_supply(&svm, &pool.addresses.mint_lp), 0);
+
+    // 1. 首次存入：max 中任一为 0 时无法定价，必须失败
+    let (lp, lp_ata, lp_atas) = create_user(&mut svm, &pool, INITIAL);
+    assert!(send(
+        &mut svm,
+        deposit_multi_ix(
+            &lp.pubkey(),
+            &pool.addresses,
+            &lp_ata,
+            &lp_atas,
+            LP_AMOUNT,
+            &[INITIAL[0], 0, INITIAL[2]],
+        ),
+        &lp,
+        &[],
+    )
+    .is_err());
+
+    send(
+        &mut svm,
+        deposit_multi_ix(
+            &lp.pubkey(),
+            &pool.addresses,
+            &lp_ata,
+            &lp_atas,
+            LP_AMOUNT,
+            &INITIAL,
+        ),
+        &lp,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(vault_balances(&svm, &pool), INITIAL);
+    assert_eq!(reserves(&svm, &pool), INITIAL);
+    assert_eq!(token_balance(&svm, &lp_ata), LP_AMOUNT);
+    assert_eq!(mint_supply(&svm, &pool.addresses.mint_lp), LP_AMOUNT);
+
+    // 2. 后续存入按储备比例：1/4 份额存入每种代币的 1/4
+    let (second, second_lp, second_atas) = create_user(&mut svm, &pool, INITIAL);
+    send(
+        &mut svm,
+        deposit_multi_ix(
+            &second.pubkey(),
+            &pool.addresses,
+            &second_lp,
+            &second_atas,
+            LP_AMOUNT / 4,
+            &INITIAL,
+        ),
+        &second,
+        &[],
+    )
+    .unwrap();
+    let pooled = INITIAL.map(|amount| amount + amount / 4);
+    assert_eq!(reserves(&svm, &pool), pooled);
+    for (ata, amount) in second_atas.iter().zip(INITIAL) {
+        assert_eq!(token_balance(&svm, ata), amount - amount / 4);
+    }
+
+    // 3. 代币 0 换代币 2：只用这两种储备做恒定乘积，代币 1 不受影响
+    let (trader, _, trader_atas) = create_user(&mut svm, &pool, [SWAP_IN, 0, 0]);
+    let in_after_fee = SWAP_IN as u128 * (10_000 - FEE_BPS as u128) / 10_000;
+    let expected_out =
+        (pooled[2] as u128 * in_after_fee / (pooled[0] as u128 + in_after_fee)) as u64;
+
+    assert!(send(
+        &mut svm,
+        swap_multi_ix(
+            &trader.pubkey(),
+            &pool.addresses,
+            0,
+            2,
+            &trader_atas[0],
+            &trader_atas[2],
+            SWAP_IN,
+            expected_out + 1,
+        ),
+        &trader,
+        &[],
+    )
+    .is_err());
+
+    send(
+        &mut svm,
+        swap_multi_ix(
+            &trader.pubkey(),
+            &pool.addresses,
+            0,
+            2,
+            &trader_atas[0],
+            &trader_atas[2],
+            SWAP_IN,
+            expected_out,
+        ),
+        &trader,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(token_balance(&svm, &trader_atas[0]), 0);
+    assert_eq!(token_balance(&svm, &trader_atas[2]), expected_out);
+    let after_swap = [pooled[0] + SWAP_IN, pooled[1], pooled[2] - expected_out];
+    assert_eq!(reserves(&svm, &pool), after_swap);
+    assert_eq!(vault_balances(&svm, &pool), after_swap);
+
+    // 4. 部分提取按份额向下取整；最后一位 LP 全额提取时取走全部储备
+    let supply = LP_AMOUNT + LP_AMOUNT / 4;
+    send(
+        &mut svm,
+        withdraw_multi_ix(
+            &lp.pubkey(),
+            &pool.addresses,
+            &lp_ata,
+            &lp_atas,
+            LP_AMOUNT,
+            &[0; 3],
+        ),
+        &lp,
+        &[],
+    )
+    .unwrap();
+    let withdrawn =
+        after_swap.map(|reserve| (reserve as u128 * LP_AMOUNT as u128 / supply as u128) as u64);
+    for (ata, amount) in lp_atas.iter().zip(withdrawn) {
+        assert_eq!(token_balance(&svm, ata), amount);
+    }
+    assert_eq!(token_balance(&svm, &lp_ata), 0);
+
+    send(
+        &mut svm,
+        withdraw_multi_ix(
+            &second.pubkey(),
+            &pool.addresses,
+            &second_lp,
+            &second_atas,
+            LP_AMOUNT / 4,
+            &[0; 3],
+        ),
+        &second,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(mint_supply(&svm, &pool.addresses.mint_lp), 0);
+    assert_eq!(reserves(&svm, &pool), [0; 3]);
+    assert_eq!(vault_balances(&svm, &pool), [0; 3]);
+}
+
+#[test]
+fn test_multi_sync_reserves_and_skim() {
+    let mut svm = setup_svm();
+    let pool = setup_pool(&mut svm);
+    let admin = &pool.admin;
+    let (lp, lp_ata, lp_atas) = create_user(&mut svm, &pool, INITIAL);
+    send(
+        &mut svm,
+        deposit_multi_ix(
+            &lp.pubkey(),
+            &pool.addresses,
+            &lp_ata,
+            &lp_atas,
+            LP_AMOUNT,
+            &INITIAL,
+        ),
+        &lp,
+        &[],
+    )
+    .unwrap();
+    let recipients: Vec<Pubkey> = pool
+        .addresses
+        .mints
+        .iter()
+        .map(|mint| fixtures::create_ata(&mut svm, &admin.pubkey(), mint, TokenProgram::Token))
+        .collect();
+
+    // 1. 直接转入金库 1 的代币不计入储备
+    let donation = 5_000_000;
+    fixtures::mint_to(
+        &mut svm,
+        &pool.addresses.mints[1],
+        &pool.addresses.config,
+        donation,
+        TokenProgram::Token,
+    );
+    assert_eq!(reserves(&svm, &pool), INITIAL);
+
+    // 2. 只有 authority 可以 skim；skim 只转出超出储备的部分
+    let (outsider, _, outsider_atas) = create_user(&mut svm, &pool, [0; 3]);
+    assert!(send(
+        &mut svm,
+        skim_multi_ix(&outsider.pubkey(), &pool.addresses, &outsider_atas),
+        &outsider,
+        &[],
+    )
+    .is_err());
+
+    send(
+        &mut svm,
+        skim_multi_ix(&admin.pubkey(), &pool.addresses, &recipients),
+        admin,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(token_balance(&svm, &recipients[0]), 0);
+    assert_eq!(token_balance(&svm, &recipients[1]), donation);
+    assert_eq!(token_balance(&svm, &recipients[2]), 0);
+    assert_eq!(vault_balances(&svm, &pool), INITIAL);
+    assert_eq!(reserves(&svm, &pool), INITIAL);
+
+    // 3. 再次捐赠到金库 2，由 authority sync 并入储备
+    fixtures::mint_to(
+        &mut svm,
+        &pool.addresses.mints[2],
+        &pool.addresses.config,
+        donation,
+        TokenProgram::Token,
+    );
+    assert!(send(
+        &mut svm,
+        sync_reserves_multi_ix(&outsider.pubkey(), &pool.addresses),
+        &outsider,
+        &[],
+    )
+    .is_err());
+    assert_eq!(reserves(&svm, &pool), INITIAL);
+
+    send(
+        &mut svm,
+        sync_reserves_multi_ix(&admin.pubkey(), &pool.addresses),
+        admin,
+        &[],
+    )
+    .unwrap();
+    let synced = [INITIAL[0], INITIAL[1], INITIAL[2] + donation];
+    assert_eq!(reserves(&svm, &pool), synced);
+
+    // sync 之后没有超额，skim 不再转出任何代币（同一笔交易需换新的 blockhash）
+    svm.expire_blockhash();
+    send(
+        &mut svm,
+        skim_multi_ix(&admin.pubkey(), &pool.addresses, &recipients),
+        admin,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(token_balance(&svm, &recipients[2]), 0);
+    assert_eq!(vault_balances(&svm, &pool), synced);
+}
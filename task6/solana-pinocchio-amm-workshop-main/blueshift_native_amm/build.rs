@@ -0,0 +1,447 @@
+// 生成 target/idl/blueshift_native_amm.json（shank 格式），供 justfile 的 idl-* 命令与客户端代码生成使用。
+//
+// 账户顺序与参数布局按各指令的 TryFrom 手写，改动指令时同步修改这里；错误取自 blueshift_errors。
+// multi / batch / CLMM swap 的尾部数组和剩余账户数量不定，只在 docs 中说明。
+
+use blueshift_errors::{AccountError, AmmError};
+use blueshift_idl::{Account, Field, Idl, Instruction, Type, TypeDef};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let mut idl = Idl::new(
+        "blueshift_native_amm",
+        "22222222222222222222222222222222222222222222",
+    )
+    .doc("Pinocchio 原生 AMM：恒定乘积 / 加权池、多资产池与集中流动性池");
+    for instruction in pool_instructions()
+        .into_iter()
+        .chain(multi_instructions())
+        .chain(clmm_instructions())
+        .chain(admin_instructions())
+    {
+        idl = idl.instruction(instruction);
+    }
+    for account in accounts() {
+        idl = idl.account(account);
+    }
+    let idl = idl
+        .ty(TypeDef::new("RebateTier")
+            .doc("手续费返还档位：LP 持有量达到 threshold 时返还 rebate_bps")
+            .field("threshold", Type::U64)
+            .field("rebate_bps", Type::U16))
+        .ty(TypeDef::new("SwapBatchLeg")
+            .field("is_x", Type::Bool)
+            .field("amount", Type::U64)
+            .field("min", Type::U64))
+        .errors(AmmError::ALL, AmmError::code)
+        .errors(AccountError::ALL, AccountError::code);
+
+    if let Err(err) = idl.write() {
+        println!("cargo:warning=IDL 未写入: {err}");
+    }
+}
+
+/// 双币池：initialize / deposit / withdraw / swap / swap_batch
+fn pool_instructions() -> Vec<Instruction> {
+    let liquidity_accounts = |instruction: Instruction| {
+        instruction
+            .account(Account::new("user").writable().signer())
+            .account(Account::new("mint_lp").writable())
+            .account(Account::new("vault_x").writable())
+            .account(Account::new("vault_y").writable())
+            .account(Account::new("user_x_ata").writable())
+            .account(Account::new("user_y_ata").writable())
+            .account(Account::new("user_lp_ata").writable())
+            .account(Account::new("config").writable())
+            .account(Account::new("token_program"))
+            .account(
+                Account::new("lp_lock")
+                    .writable()
+                    .optional()
+                    .doc("锁仓记录 PDA: [\"lp_lock\", config, user]"),
+            )
+            .account(
+                Account::new("lock_vault")
+                    .writable()
+                    .optional()
+                    .doc("config 持有的 LP 代币账户"),
+            )
+    };
+
+    vec![
+        Instruction::new("initialize", 0)
+            .doc("创建 Config 与 mint_lp；追加 mint / 金库账户时同时创建两个金库 ATA")
+            .account(Account::new("payer").writable().signer())
+            .account(
+                Account::new("mint_lp")
+                    .writable()
+                    .doc("PDA: [\"mint_lp\", config]"),
+            )
+            .account(Account::new("config").writable().doc(
+                "规范池 PDA: [\"config\", mint_x, mint_y]；seeded 池: [\"config\", seed, mint_x, mint_y]",
+            ))
+            .account(Account::new("mint_x").optional())
+            .account(Account::new("mint_y").optional())
+            .account(Account::new("vault_x").writable().optional())
+            .account(Account::new("vault_y").writable().optional())
+            .account(Account::new("system_program"))
+            .account(Account::new("token_program"))
+            .account(Account::new("associated_token_program").optional())
+            .arg_with(Field::new("seed", Type::U64).doc("规范池忽略"))
+            .arg_with(Field::new("fee", Type::U16).doc("基点"))
+            .arg_with(Field::new("mint_x", Type::PublicKey).doc("必须按字节序小于 mint_y"))
+            .arg("mint_y", Type::PublicKey)
+            .arg("config_bump", Type::array(Type::U8, 1))
+            .arg("lp_bump", Type::array(Type::U8, 1))
+            .arg_with(Field::new("weight_x", Type::U16).doc("mint_x 的权重（基点），0 表示 50/50"))
+            .arg_with(Field::new("canonical", Type::U8).doc("1: 规范池，0: seeded 池"))
+            .arg_with(Field::new("lp_decimals", Type::U8).doc("0 表示默认的 6 位"))
+            .arg_with(Field::new("authority", Type::PublicKey).doc("省略时为全零地址（无管理员）")),
+        liquidity_accounts(Instruction::new("deposit", 1))
+            .doc("按比例存入 X / Y 并铸造 LP；lock_duration 非 0 时 LP 锁入 lock_vault")
+            .doc("锁仓时按加成从 reward_vault 划出奖励 LP 一并锁入，奖励池不足时只发放剩余部分")
+            .account(
+                Account::new("reward_vault")
+                    .writable()
+                    .optional()
+                    .doc("reward_authority 持有的 LP 代币账户（奖励池），锁仓存款时必须传入"),
+            )
+            .account(
+                Account::new("reward_authority")
+                    .optional()
+                    .doc("奖励池 PDA: [\"lp_rewards\", config]"),
+            )
+            .account(
+                Account::new("system_program")
+                    .optional()
+                    .doc("首次锁仓创建 lp_lock 时需要"),
+            )
+            .arg_with(Field::new("amount", Type::U64).doc("铸造的 LP 数量"))
+            .arg("max_x", Type::U64)
+            .arg("max_y", Type::U64)
+            .arg("expiration", Type::I64)
+            .arg_with(Field::new("lock_duration", Type::I64).doc("锁仓秒数，0 表示不锁仓")),
+        liquidity_accounts(Instruction::new("withdraw", 2))
+            .doc("销毁 LP 按比例取回 X / Y；传入 lp_lock / lock_vault 时赎回到期的锁仓")
+            .arg_with(Field::new("amount", Type::U64).doc("销毁的 LP 数量"))
+            .arg("min_x", Type::U64)
+            .arg("min_y", Type::U64)
+            .arg("expiration", Type::I64),
+        Instruction::new("swap", 3)
+            .doc("精确输入兑换；追加 user_lp_ata 与 mint_lp 时按 LP 持有量享受返还费率")
+            .account(Account::new("user").signer())
+            .account(Account::new("user_x_ata").writable())
+            .account(Account::new("user_y_ata").writable())
+            .account(Account::new("vault_x").writable())
+            .account(Account::new("vault_y").writable())
+            .account(Account::new("config").writable())
+            .account(Account::new("token_program"))
+            .account(Account::new("user_lp_ata").optional())
+            .account(Account::new("mint_lp").optional())
+            .arg_with(Field::new("is_x", Type::Bool).doc("true: 卖出 X 换 Y"))
+            .arg("amount", Type::U64)
+            .arg_with(Field::new("min", Type::U64).doc("最少收到的数量"))
+            .arg("expiration", Type::I64),
+        Instruction::new("swap_batch", 14)
+            .doc("同一池子里依次执行多笔兑换，每笔基于上一笔后的储备报价")
+            .doc("参数之后紧跟 count 个 SwapBatchLeg（各 17 字节）")
+            .doc("剩余账户：每笔兑换依次传入 [user (signer), user_x_ata (mut), user_y_ata (mut)]")
+            .account(Account::new("vault_x").writable())
+            .account(Account::new("vault_y").writable())
+            .account(Account::new("config").writable())
+            .account(Account::new("token_program"))
+            .arg("expiration", Type::I64)
+            .arg("count", Type::U8),
+    ]
+}
+
+/// 多资产池（等权重几何平均），金库与用户代币账户按 MultiConfig::mints 的顺序作为剩余账户传入
+fn multi_instructions() -> Vec<Instruction> {
+    let multi_liquidity = |name, discriminant, bound| {
+        Instruction::new(name, discriminant)
+            .doc("剩余账户：token_count 个金库 (mut)，再是 token_count 个用户代币账户 (mut)")
+            .doc(bound)
+            .account(Account::new("user").signer())
+            .account(Account::new("mint_lp").writable())
+            .account(Account::new("user_lp_ata").writable())
+            .account(Account::new("config").writable())
+            .account(Account::new("token_program"))
+            .arg("amount", Type::U64)
+            .arg("expiration", Type::I64)
+    };
+
+    vec![
+        Instruction::new("initialize_multi", 4)
+            .doc("创建 MultiConfig 与 mint_lp，金库由客户端预先创建")
+            .doc("参数之后紧跟 token_count 个 mint 地址（各 32 字节），2 <= token_count <= 8")
+            .account(Account::new("initializer").writable().signer())
+            .account(Account::new("mint_lp").writable())
+            .account(
+                Account::new("config")
+                    .writable()
+                    .doc("PDA: [\"multi_config\", seed, config_bump]"),
+            )
+            .account(Account::new("system_program"))
+            .account(Account::new("token_program"))
+            .arg("seed", Type::U64)
+            .arg("fee", Type::U16)
+            .arg("config_bump", Type::array(Type::U8, 1))
+            .arg("lp_bump", Type::array(Type::U8, 1))
+            .arg("authority", Type::PublicKey)
+            .arg("token_count", Type::U8),
+        multi_liquidity(
+            "deposit_multi",
+            5,
+            "参数之后紧跟 token_count 个 u64 小端的 max 数组",
+        ),
+        multi_liquidity(
+            "withdraw_multi",
+            6,
+            "参数之后紧跟 token_count 个 u64 小端的 min 数组",
+        ),
+        Instruction::new("swap_multi", 7)
+            .account(Account::new("user").signer())
+            .account(Account::new("config").writable())
+            .account(Account::new("vault_in").writable())
+            .account(Account::new("vault_out").writable())
+            .account(Account::new("user_in_ata").writable())
+            .account(Account::new("user_out_ata").writable())
+            .account(Account::new("token_program"))
+            .arg("index_in", Type::U8)
+            .arg("index_out", Type::U8)
+            .arg("amount", Type::U64)
+            .arg("min", Type::U64)
+            .arg("expiration", Type::I64),
+    ]
+}
+
+/// 集中流动性池，价格为 Q64.64 的 sqrt price
+fn clmm_instructions() -> Vec<Instruction> {
+    let position_accounts = |instruction: Instruction| {
+        instruction
+            .account(Account::new("pool").writable())
+            .account(Account::new("position").writable())
+            .account(Account::new("tick_lower").writable())
+            .account(Account::new("tick_upper").writable())
+            .account(Account::new("bitmap_lower").writable())
+            .account(Account::new("bitmap_upper").writable())
+    };
+
+    vec![
+        Instruction::new("clmm_initialize_pool", 8)
+            .doc("创建集中流动性池，两个金库由客户端预先创建且 owner 为 pool PDA")
+            .account(Account::new("payer").writable().signer())
+            .account(
+                Account::new("pool")
+                    .writable()
+                    .doc("PDA: [\"clmm_pool\", mint_x, mint_y, tick_spacing]"),
+            )
+            .account(Account::new("vault_x"))
+            .account(Account::new("vault_y"))
+            .account(Account::new("system_program"))
+            .arg("fee", Type::U16)
+            .arg("tick_spacing", Type::U16)
+            .arg("sqrt_price", Type::U128)
+            .arg("mint_x", Type::PublicKey)
+            .arg("mint_y", Type::PublicKey),
+        Instruction::new("clmm_open_position", 9)
+            .doc("在 [tick_lower, tick_upper) 区间开一个空仓位")
+            .account(Account::new("owner").writable().signer())
+            .account(Account::new("pool"))
+            .account(
+                Account::new("position")
+                    .writable()
+                    .doc("PDA: [\"position\", pool, owner, tick_lower, tick_upper]"),
+            )
+            .account(Account::new("system_program"))
+            .arg("tick_lower", Type::I32)
+            .arg("tick_upper", Type::I32),
+        position_accounts(
+            Instruction::new("clmm_increase_liquidity", 10)
+                .doc("向仓位添加流动性，tick / 位图账户首次使用时由 owner 付费创建")
+                .account(Account::new("owner").writable().signer()),
+        )
+        .account(Account::new("vault_x").writable())
+        .account(Account::new("vault_y").writable())
+        .account(Account::new("user_x_ata").writable())
+        .account(Account::new("user_y_ata").writable())
+        .account(Account::new("token_program"))
+        .account(Account::new("system_program"))
+        .arg("liquidity", Type::U128)
+        .arg("max_x", Type::U64)
+        .arg("max_y", Type::U64),
+        position_accounts(
+            Instruction::new("clmm_decrease_liquidity", 11)
+                .doc("移除流动性，本金计入 tokens_owed，通过 clmm_collect 领取")
+                .account(Account::new("owner").signer()),
+        )
+        .arg("liquidity", Type::U128)
+        .arg("min_x", Type::U64)
+        .arg("min_y", Type::U64),
+        Instruction::new("clmm_swap", 12)
+            .doc("精确输入兑换")
+            .doc("剩余账户：价格经过的全部位图账户（未创建的传 PDA 地址）与会被穿越的 tick 账户")
+            .account(Account::new("user").signer())
+            .account(Account::new("pool").writable())
+            .account(Account::new("vault_x").writable())
+            .account(Account::new("vault_y").writable())
+            .account(Account::new("user_x_ata").writable())
+            .account(Account::new("user_y_ata").writable())
+            .account(Account::new("token_program"))
+            .arg_with(Field::new("x_to_y", Type::U8).doc("1: x -> y，0: y -> x"))
+            .arg("amount", Type::U64)
+            .arg("min", Type::U64)
+            .arg_with(Field::new("sqrt_price_limit", Type::U128).doc("0 表示不限制"))
+            .arg("expiration", Type::I64),
+        Instruction::new("clmm_collect", 13)
+            .doc("结算手续费，并把 tokens_owed 转给 owner")
+            .account(Account::new("owner").signer())
+            .account(Account::new("pool"))
+            .account(Account::new("position").writable())
+            .account(Account::new("tick_lower"))
+            .account(Account::new("tick_upper"))
+            .account(Account::new("vault_x").writable())
+            .account(Account::new("vault_y").writable())
+            .account(Account::new("user_x_ata").writable())
+            .account(Account::new("user_y_ata").writable())
+            .account(Account::new("token_program")),
+    ]
+}
+
+/// 只有 Config / MultiConfig 的 authority 可以调用的管理指令
+fn admin_instructions() -> Vec<Instruction> {
+    vec![
+        Instruction::new("propose_update", 15)
+            .doc("提出参数变更，timelock_delay 秒后才能执行；kind = 0 撤销提案")
+            .account(Account::new("authority").signer())
+            .account(Account::new("config").writable())
+            .arg_with(Field::new("kind", Type::U8).doc(
+                "1: fee, 2: state, 3: authority, 4: timelock_delay, 5: rebate_tiers, 6: withdraw_grace_slots",
+            ))
+            .arg_with(
+                Field::new("value", Type::array(Type::U8, 32))
+                    .doc("新值按 kind 小端编码，不足 32 字节补 0"),
+            ),
+        Instruction::new("execute_update", 16)
+            .doc("timelock 到期后执行提案")
+            .account(Account::new("authority").signer())
+            .account(Account::new("config").writable()),
+        Instruction::new("sync_reserves", 17)
+            .doc("把记账储备对齐到金库实际余额")
+            .account(Account::new("authority").signer())
+            .account(Account::new("config").writable())
+            .account(Account::new("vault_x"))
+            .account(Account::new("vault_y")),
+        Instruction::new("skim", 18)
+            .doc("把金库中超出记账储备的部分转到 to_x / to_y")
+            .account(Account::new("authority").signer())
+            .account(Account::new("config"))
+            .account(Account::new("vault_x").writable())
+            .account(Account::new("vault_y").writable())
+            .account(Account::new("to_x").writable())
+            .account(Account::new("to_y").writable())
+            .account(Account::new("token_program")),
+        Instruction::new("sync_reserves_multi", 19)
+            .doc("多资产池的 sync_reserves；剩余账户：token_count 个金库")
+            .account(Account::new("authority").signer())
+            .account(Account::new("config").writable()),
+        Instruction::new("skim_multi", 20)
+            .doc("多资产池的 skim；剩余账户：token_count 个金库 (mut)，再是 token_count 个接收账户 (mut)")
+            .account(Account::new("authority").signer())
+            .account(Account::new("config"))
+            .account(Account::new("token_program")),
+    ]
+}
+
+/// 程序拥有的账户；字段都是字节数组，按 1 字节对齐，没有填充
+fn accounts() -> Vec<TypeDef> {
+    vec![
+        TypeDef::new("Config")
+            .field_with(
+                Field::new("state", Type::U8)
+                    .doc("0: Uninitialized, 1: Initialized, 2: Disabled, 3: WithdrawOnly"),
+            )
+            .field("seed", Type::U64)
+            .field("authority", Type::PublicKey)
+            .field("mint_x", Type::PublicKey)
+            .field("mint_y", Type::PublicKey)
+            .field("fee", Type::U16)
+            .field("weight_x", Type::U16)
+            .field("config_bump", Type::array(Type::U8, 1))
+            .field("canonical", Type::U8)
+            .field("timelock_delay", Type::I64)
+            .field("pending_kind", Type::U8)
+            .field("pending_eta", Type::I64)
+            .field("pending_value", Type::array(Type::U8, 32))
+            .field("rebate_tiers", Type::array(Type::Defined("RebateTier"), 3))
+            .field("reserve_x", Type::U64)
+            .field("reserve_y", Type::U64)
+            .field("lp_decimals", Type::U8)
+            .field("disabled_slot", Type::U64)
+            .field("withdraw_grace_slots", Type::U64),
+        TypeDef::new("MultiConfig")
+            .field("state", Type::U8)
+            .field("seed", Type::U64)
+            .field("authority", Type::PublicKey)
+            .field("token_count", Type::U8)
+            .field("fee", Type::U16)
+            .field("config_bump", Type::array(Type::U8, 1))
+            .field_with(
+                Field::new("mints", Type::array(Type::PublicKey, 8))
+                    .doc("只有前 token_count 个有效"),
+            )
+            .field_with(
+                Field::new("reserves", Type::array(Type::U64, 8))
+                    .doc("记账储备，与 mints 一一对应"),
+            ),
+        TypeDef::new("LpLock")
+            .field("owner", Type::PublicKey)
+            .field("config", Type::PublicKey)
+            .field_with(Field::new("amount", Type::U64).doc("锁定的 LP，含从奖励池发放的奖励"))
+            .field("unlock_ts", Type::I64)
+            .field("boost_bps", Type::U16)
+            .field("bump", Type::array(Type::U8, 1)),
+        TypeDef::new("ClmmPool")
+            .field_with(Field::new("account_type", Type::U8).doc("固定为 1"))
+            .field("bump", Type::array(Type::U8, 1))
+            .field("mint_x", Type::PublicKey)
+            .field("mint_y", Type::PublicKey)
+            .field("vault_x", Type::PublicKey)
+            .field("vault_y", Type::PublicKey)
+            .field("fee", Type::U16)
+            .field("tick_spacing", Type::U16)
+            .field("sqrt_price", Type::U128)
+            .field("tick_current", Type::I32)
+            .field("liquidity", Type::U128)
+            .field("fee_growth_global_x", Type::U128)
+            .field("fee_growth_global_y", Type::U128),
+        TypeDef::new("TickState")
+            .doc("PDA: [\"tick\", pool, tick (i32 小端)]")
+            .field_with(Field::new("account_type", Type::U8).doc("固定为 2"))
+            .field("pool", Type::PublicKey)
+            .field("tick", Type::I32)
+            .field("liquidity_gross", Type::U128)
+            .field("liquidity_net", Type::I128)
+            .field("fee_growth_outside_x", Type::U128)
+            .field("fee_growth_outside_y", Type::U128),
+        TypeDef::new("TickBitmap")
+            .doc("PDA: [\"tick_bitmap\", pool, word_pos (i16 小端)]")
+            .field_with(Field::new("account_type", Type::U8).doc("固定为 3"))
+            .field("pool", Type::PublicKey)
+            .field("word_pos", Type::I16)
+            .field("bits", Type::array(Type::U8, 32)),
+        TypeDef::new("Position")
+            .field_with(Field::new("account_type", Type::U8).doc("固定为 4"))
+            .field("bump", Type::array(Type::U8, 1))
+            .field("pool", Type::PublicKey)
+            .field("owner", Type::PublicKey)
+            .field("tick_lower", Type::I32)
+            .field("tick_upper", Type::I32)
+            .field("liquidity", Type::U128)
+            .field("fee_growth_inside_last_x", Type::U128)
+            .field("fee_growth_inside_last_y", Type::U128)
+            .field("tokens_owed_x", Type::U64)
+            .field("tokens_owed_y", Type::U64),
+    ]
+}
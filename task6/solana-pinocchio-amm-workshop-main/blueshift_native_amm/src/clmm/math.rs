@@ -0,0 +1,456 @@
+//! 集中流动性的定点数学（Uniswap v3 模型，价格使用 Q64.64 的 sqrt price）。
+//!
+//! - `price = 1.0001 ^ tick`，`sqrt_price = sqrt(price) * 2^64`
+//! - Δx = L * (1/√Pa - 1/√Pb)，Δy = L * (√Pb - √Pa)
+//! - 手续费增长量 `fee_growth` 为 Q64.64，允许回绕（只关心差值）
+//!
+//! 所有舍入方向都对池子有利：用户付出向上取整，用户收到向下取整。
+
+use pinocchio::program_error::ProgramError;
+
+/// Q64.64 中的 1.0
+pub const Q64: u128 = 1 << 64;
+
+pub const MIN_TICK: i32 = -443_636;
+pub const MAX_TICK: i32 = 443_636;
+/// sqrt_price_at_tick(MIN_TICK)
+pub const MIN_SQRT_PRICE: u128 = 4_295_048_016;
+/// sqrt_price_at_tick(MAX_TICK)
+pub const MAX_SQRT_PRICE: u128 = 79_226_673_521_066_979_257_578_248_091;
+
+/// 单个 tick 位图账户覆盖的 tick 数（以 tick_spacing 压缩后）
+pub const TICKS_PER_WORD: i32 = 256;
+
+/// `(a * b) / d`，中间结果为 256 位，避免 u128 乘法溢出。
+pub fn mul_div(a: u128, b: u128, d: u128, round_up: bool) -> Result<u128, ProgramError> {
+    if d == 0 {
+        return Err(ProgramError::ArithmeticOverflow);
+    }
+
+    // 1. 256 位乘积 (hi, lo)
+    let (a1, a0) = (a >> 64, a & (Q64 - 1));
+    let (b1, b0) = (b >> 64, b & (Q64 - 1));
+    let (mid, mid_carry) = (a0 * b1).overflowing_add(a1 * b0);
+    let (lo, lo_carry) = (a0 * b0).overflowing_add(mid << 64);
+    let hi = a1 * b1 + (mid >> 64) + ((mid_carry as u128) << 64) + lo_carry as u128;
+
+    // 2. 256 / 128 长除法，商必须能放进 u128
+    let (quotient, remainder) = if hi == 0 {
+        (lo / d, lo % d)
+    } else {
+        if hi >= d {
+            return Err(ProgramError::ArithmeticOverflow);
+        }
+        let mut remainder = hi;
+        let mut quotient = 0u128;
+        for i in (0..128).rev() {
+            let carry = remainder >> 127;
+            remainder = (remainder << 1) | ((lo >> i) & 1);
+            if carry == 1 || remainder >= d {
+                remainder = remainder.wrapping_sub(d);
+                quotient |= 1 << i;
+            }
+        }
+        (quotient, remainder)
+    };
+
+    if round_up && remainder != 0 {
+        quotient.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)
+    } else {
+        Ok(quotient)
+    }
+}
+
+/// `sqrt(1.0001) ^ -(2^i)`，Q64.64
+const TICK_RATIOS: [u128; 19] = [
+    0xfffcb933bd6fad38,
+    0xfff97272373d4132,
+    0xfff2e50f5f656933,
+    0xffe5caca7e10e4e6,
+    0xffcb9843d60f615a,
+    0xff973b41fa98c081,
+    0xff2ea16466c96a38,
+    0xfe5dee046a99a2a8,
+    0xfcbe86c7900a88af,
+    0xf987a7253ac41317,
+    0xf3392b0822b70006,
+    0xe7159475a2c29b74,
+    0xd097f3bdfd2022b9,
+    0xa9f746462d870fe0,
+    0x70d869a156d2a1b9,
+    0x31be135f97d08fda,
+    0x9aa508b5b7a84e2,
+    0x5d6af8dedb8119,
+    0x2216e584f5fa,
+];
+
+/// tick -> Q64.64 sqrt price
+pub fn sqrt_price_at_tick(tick: i32) -> Result<u128, ProgramError> {
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let abs_tick = tick.unsigned_abs();
+
+    let mut ratio = Q64;
+    for (i, factor) in TICK_RATIOS.iter().enumerate() {
+        if abs_tick & (1 << i) != 0 {
+            ratio = (ratio * factor) >> 64;
+        }
+    }
+
+    // 正 tick 取倒数：2^128 / ratio
+    if tick > 0 {
+        ratio = u128::MAX / ratio;
+    }
+    Ok(ratio)
+}
+
+/// Q64.64 sqrt price -> 满足 `sqrt_price_at_tick(tick) <= sqrt_price` 的最大 tick
+pub fn tick_at_sqrt_price(sqrt_price: u128) -> Result<i32, ProgramError> {
+    if !(MIN_SQRT_PRICE..=MAX_SQRT_PRICE).contains(&sqrt_price) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+    while low < high {
+        let mid = low + (high - low + 1) / 2;
+        if sqrt_price_at_tick(mid)? <= sqrt_price {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Ok(low)
+}
+
+/// Δx = L * (√Pb - √Pa) / (√Pa * √Pb)
+pub fn amount_x_delta(
+    sqrt_price_a: u128,
+    sqrt_price_b: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<u128, ProgramError> {
+    let (lower, upper) = if sqrt_price_a < sqrt_price_b {
+        (sqrt_price_a, sqrt_price_b)
+    } else {
+        (sqrt_price_b, sqrt_price_a)
+    };
+    if lower == 0 {
+        return Err(ProgramError::ArithmeticOverflow);
+    }
+    let scaled = mul_div(liquidity, upper - lower, upper, round_up)?;
+    mul_div(scaled, Q64, lower, round_up)
+}
+
+/// Δy = L * (√Pb - √Pa)
+pub fn amount_y_delta(
+    sqrt_price_a: u128,
+    sqrt_price_b: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<u128, ProgramError> {
+    let diff = sqrt_price_a.abs_diff(sqrt_price_b);
+    mul_div(liquidity, diff, Q64, round_up)
+}
+
+/// 输入 amount_in 后的新价格。x 输入价格下降，y 输入价格上升；
+/// 两种情况都让价格移动得更少（对池子有利）。
+pub fn next_sqrt_price_from_input(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount_in: u128,
+    x_to_y: bool,
+) -> Result<u128, ProgramError> {
+    if liquidity == 0 {
+        return Err(ProgramError::ArithmeticOverflow);
+    }
+    if x_to_y {
+        // √P' = L / (L / √P + Δx)
+        let denominator = mul_div(liquidity, Q64, sqrt_price, false)?
+            .checked_add(amount_in)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        mul_div(liquidity, Q64, denominator, true)
+    } else {
+        // √P' = √P + Δy / L
+        sqrt_price
+            .checked_add(mul_div(amount_in, Q64, liquidity, false)?)
+            .ok_or(ProgramError::ArithmeticOverflow)
+    }
+}
+
+pub struct SwapStep {
+    pub sqrt_price_next: u128,
+    pub amount_in: u128,
+    pub amount_out: u128,
+    pub fee_amount: u128,
+}
+
+/// 在单个没有 tick 穿越的区间内执行精确输入的 swap，手续费以基点计。
+pub fn compute_swap_step(
+    sqrt_price: u128,
+    sqrt_price_target: u128,
+    liquidity: u128,
+    amount_remaining: u128,
+    fee: u16,
+    x_to_y: bool,
+) -> Result<SwapStep, ProgramError> {
+    let fee = fee as u128;
+    let amount_remaining_less_fee = mul_div(amount_remaining, 10_000 - fee, 10_000, false)?;
+
+    let amount_to_target = if x_to_y {
+        amount_x_delta(sqrt_price_target, sqrt_price, liquidity, true)?
+    } else {
+        amount_y_delta(sqrt_price, sqrt_price_target, liquidity, true)?
+    };
+
+    let (sqrt_price_next, amount_in) = if amount_remaining_less_fee >= amount_to_target {
+        (sqrt_price_target, amount_to_target)
+    } else {
+        let next =
+            next_sqrt_price_from_input(sqrt_price, liquidity, amount_remaining_less_fee, x_to_y)?;
+        let amount_in = if x_to_y {
+            amount_x_delta(next, sqrt_price, liquidity, true)?
+        } else {
+            amount_y_delta(sqrt_price, next, liquidity, true)?
+        };
+        (next, amount_in)
+    };
+
+    let amount_out = if x_to_y {
+        amount_y_delta(sqrt_price_next, sqrt_price, liquidity, false)?
+    } else {
+        amount_x_delta(sqrt_price, sqrt_price_next, liquidity, false)?
+    };
+
+    // 未到达目标价时，剩余输入全部作为手续费留在池内
+    let fee_amount = if sqrt_price_next != sqrt_price_target {
+        amount_remaining
+            .checked_sub(amount_in)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+    } else {
+        mul_div(amount_in, fee, 10_000 - fee, true)?
+    };
+
+    Ok(SwapStep {
+        sqrt_price_next,
+        amount_in,
+        amount_out,
+        fee_amount,
+    })
+}
+
+/// 区间内的累计手续费增长（Q64.64，回绕运算）。
+pub fn fee_growth_inside(
+    tick_current: i32,
+    tick_lower: i32,
+    lower_outside: u128,
+    tick_upper: i32,
+    upper_outside: u128,
+    global: u128,
+) -> u128 {
+    let below = if tick_current >= tick_lower {
+        lower_outside
+    } else {
+        global.wrapping_sub(lower_outside)
+    };
+    let above = if tick_current < tick_upper {
+        upper_outside
+    } else {
+        global.wrapping_sub(upper_outside)
+    };
+    global.wrapping_sub(below).wrapping_sub(above)
+}
+
+/// 压缩后的 tick 所在的位图字与位
+pub fn bitmap_position(tick: i32, tick_spacing: u16) -> (i16, u8) {
+    let compressed = tick.div_euclid(tick_spacing as i32);
+    ((compressed >> 8) as i16, (compressed & 0xff) as u8)
+}
+
+/// 位图字中第 `bit` 位对应的 tick
+pub fn tick_from_bitmap(word_pos: i16, bit: u8, tick_spacing: u16) -> i32 {
+    (word_pos as i32 * TICKS_PER_WORD + bit as i32) * tick_spacing as i32
+}
+
+/// 在单个 256 位字内查找下一个已初始化的位。
+/// `lte` 为 true 时向下查找 `<= bit`，否则向上查找 `>= bit`；
+/// 返回 (位, 是否找到)，未找到时返回该方向上的字边界。
+pub fn next_initialized_bit(bits: &[u8; 32], bit: u8, lte: bool) -> (u8, bool) {
+    let is_set = |b: u8| bits[(b / 8) as usize] & (1 << (b % 8)) != 0;
+    if lte {
+        for b in (0..=bit).rev() {
+            if is_set(b) {
+                return (b, true);
+            }
+        }
+        (0, false)
+    } else {
+        for b in bit..=u8::MAX {
+            if is_set(b) {
+                return (b, true);
+            }
+        }
+        (u8::MAX, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Uniswap v3 TickMath 的 Q64.96 结果右移 32 位换算到 Q64.64，允许 ±1 的截断误差
+    fn assert_q96(sqrt_price: u128, q96: u128) {
+        assert!(
+            sqrt_price.abs_diff(q96 >> 32) <= 1,
+            "{sqrt_price} vs {}",
+            q96 >> 32
+        );
+    }
+
+    #[test]
+    fn sqrt_price_at_tick_matches_uniswap_v3() -> Result<(), ProgramError> {
+        assert_eq!(sqrt_price_at_tick(0)?, Q64);
+        assert_eq!(sqrt_price_at_tick(MIN_TICK)?, MIN_SQRT_PRICE);
+        assert_eq!(sqrt_price_at_tick(MAX_TICK)?, MAX_SQRT_PRICE);
+        assert_q96(
+            sqrt_price_at_tick(1)?,
+            79_232_123_823_359_799_118_286_999_568,
+        );
+        assert_q96(
+            sqrt_price_at_tick(-1)?,
+            79_224_201_403_219_477_170_569_942_574,
+        );
+        // 相邻 tick 单调递增
+        assert!(sqrt_price_at_tick(MIN_TICK + 1)? > MIN_SQRT_PRICE);
+        assert!(sqrt_price_at_tick(MAX_TICK - 1)? < MAX_SQRT_PRICE);
+
+        assert_eq!(
+            sqrt_price_at_tick(MIN_TICK - 1),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            sqrt_price_at_tick(MAX_TICK + 1),
+            Err(ProgramError::InvalidArgument)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn tick_at_sqrt_price_rounds_down() -> Result<(), ProgramError> {
+        assert_eq!(tick_at_sqrt_price(MIN_SQRT_PRICE)?, MIN_TICK);
+        assert_eq!(tick_at_sqrt_price(MAX_SQRT_PRICE)?, MAX_TICK);
+        assert_eq!(tick_at_sqrt_price(MAX_SQRT_PRICE - 1)?, MAX_TICK - 1);
+        for tick in [MIN_TICK + 1, -100, -1, 0, 1, 100, MAX_TICK - 1] {
+            let sqrt_price = sqrt_price_at_tick(tick)?;
+            assert_eq!(tick_at_sqrt_price(sqrt_price)?, tick);
+            assert_eq!(tick_at_sqrt_price(sqrt_price + 1)?, tick);
+            assert_eq!(tick_at_sqrt_price(sqrt_price - 1)?, tick - 1);
+        }
+
+        assert_eq!(
+            tick_at_sqrt_price(MIN_SQRT_PRICE - 1),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            tick_at_sqrt_price(MAX_SQRT_PRICE + 1),
+            Err(ProgramError::InvalidArgument)
+        );
+        Ok(())
+    }
+
+    // 以下向量取自 Uniswap v3 SwapMath.spec：L = 2e18，输入 1e18，手续费 600 pips = 6 bps
+    const LIQUIDITY: u128 = 2_000_000_000_000_000_000;
+    const AMOUNT: u128 = 1_000_000_000_000_000_000;
+    const FEE_BPS: u16 = 6;
+
+    #[test]
+    fn swap_step_capped_at_target_price() -> Result<(), ProgramError> {
+        // 目标价 sqrt(1.01)：输入足够，停在目标价
+        let target = 18_538_748_355_542_988_169;
+        let step = compute_swap_step(Q64, target, LIQUIDITY, AMOUNT, FEE_BPS, false)?;
+        assert_eq!(step.sqrt_price_next, target);
+        // v3 为 ...055；Q64.64 的目标价比 Q64.96 少 32 位精度
+        assert_eq!(step.amount_in, 9_975_124_224_178_054);
+        assert_eq!(step.amount_out, 9_925_619_580_021_728);
+        assert_eq!(step.fee_amount, 5_988_667_735_148);
+        assert!(step.amount_in + step.fee_amount < AMOUNT);
+
+        // x 换 y 方向：目标价 sqrt(0.99)
+        let target = 18_354_278_608_861_996_862;
+        let step = compute_swap_step(Q64, target, LIQUIDITY, AMOUNT, FEE_BPS, true)?;
+        assert_eq!(step.sqrt_price_next, target);
+        assert_eq!(step.amount_in, 10_075_630_518_424_152);
+        assert_eq!(step.amount_out, 10_025_125_786_760_090);
+        assert_eq!(step.fee_amount, 6_049_007_715_684);
+        Ok(())
+    }
+
+    #[test]
+    fn swap_step_spends_entire_input() -> Result<(), ProgramError> {
+        // 目标价 sqrt(10) 很远：输入全部用完，价格停在目标价之前
+        let target = 58_333_726_687_135_158_848;
+        let step = compute_swap_step(Q64, target, LIQUIDITY, AMOUNT, FEE_BPS, false)?;
+        assert!(step.sqrt_price_next < target);
+        assert_eq!(step.sqrt_price_next, 27_664_582_087_342_214_558);
+        assert_eq!(step.amount_in, 999_400_000_000_000_000);
+        assert_eq!(step.amount_out, 666_399_946_655_997_866);
+        assert_eq!(step.fee_amount, 600_000_000_000_000);
+        assert_eq!(step.amount_in + step.fee_amount, AMOUNT);
+        Ok(())
+    }
+
+    #[test]
+    fn swap_step_rounds_in_favour_of_pool() -> Result<(), ProgramError> {
+        let target = 58_333_726_687_135_158_848;
+        let step = compute_swap_step(Q64, target, LIQUIDITY, AMOUNT, FEE_BPS, false)?;
+        // 输入向上取整、输出向下取整
+        let exact_in = amount_y_delta(Q64, step.sqrt_price_next, LIQUIDITY, false)?;
+        let exact_out = amount_x_delta(Q64, step.sqrt_price_next, LIQUIDITY, true)?;
+        assert!(step.amount_in >= exact_in);
+        assert!(step.amount_out < exact_out);
+
+        // 只输入 1 个最小单位：价格不动，全部算作手续费，不付出任何代币
+        for x_to_y in [false, true] {
+            let target = if x_to_y {
+                MIN_SQRT_PRICE
+            } else {
+                MAX_SQRT_PRICE
+            };
+            let step = compute_swap_step(Q64, target, LIQUIDITY, 1, FEE_BPS, x_to_y)?;
+            assert_eq!(step.sqrt_price_next, Q64);
+            assert_eq!(
+                (step.amount_in, step.amount_out, step.fee_amount),
+                (0, 0, 1)
+            );
+        }
+        Ok(())
+    }
+
+    // 以下用例取自 Uniswap v3 Tick.spec 的 getFeeGrowthInside，区间为 [-2, 2)，全局增长 15
+    #[test]
+    fn fee_growth_inside_outside_range() {
+        // 两端都未初始化（outside = 0）
+        assert_eq!(fee_growth_inside(0, -2, 0, 2, 0, 15), 15);
+        assert_eq!(fee_growth_inside(4, -2, 0, 2, 0, 15), 0);
+        assert_eq!(fee_growth_inside(-4, -2, 0, 2, 0, 15), 0);
+    }
+
+    #[test]
+    fn fee_growth_inside_subtracts_outside() {
+        // 只有上界记录了 outside
+        assert_eq!(fee_growth_inside(0, -2, 0, 2, 2, 15), 13);
+        assert_eq!(fee_growth_inside(0, -2, 0, 2, 3, 15), 12);
+        // 只有下界记录了 outside
+        assert_eq!(fee_growth_inside(0, -2, 2, 2, 0, 15), 13);
+        assert_eq!(fee_growth_inside(0, -2, 3, 2, 0, 15), 12);
+        // 两端都记录了 outside
+        assert_eq!(fee_growth_inside(0, -2, 2, 2, 4, 15), 9);
+        assert_eq!(fee_growth_inside(0, -2, 3, 2, 1, 15), 11);
+    }
+
+    #[test]
+    fn fee_growth_inside_wraps() {
+        // 下界的 outside 接近 u128::MAX，差值按回绕计算
+        assert_eq!(fee_growth_inside(0, -2, u128::MAX - 3, 2, 3, 15), 16);
+        assert_eq!(fee_growth_inside(0, -2, u128::MAX - 2, 2, 5, 15), 13);
+    }
+}
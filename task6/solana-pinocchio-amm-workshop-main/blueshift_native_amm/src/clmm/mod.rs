@@ -0,0 +1,6 @@
+//! 集中流动性（tick 区间）池，与简单池并列存在，互不共享账户。
+
+pub mod math;
+pub mod state;
+
+pub use state::*;
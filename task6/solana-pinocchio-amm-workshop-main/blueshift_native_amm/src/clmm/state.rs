@@ -0,0 +1,409 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::{AccountInfo, Ref, RefMut},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+pub use pdas::{CLMM_POOL_SEED, POSITION_SEED, TICK_BITMAP_SEED, TICK_SEED};
+
+/// 集中流动性账户共用同一个程序 owner，首字节用于区分账户类型，
+/// 防止把一种账户当作另一种账户传入。
+#[repr(u8)]
+pub enum ClmmAccount {
+    Pool = 1u8,
+    Tick = 2u8,
+    TickBitmap = 3u8,
+    Position = 4u8,
+}
+
+/// 为定长、1 字节对齐的账户结构生成 load / load_mut 等方法
+macro_rules! impl_clmm_account {
+    ($ty:ident, $kind:expr) => {
+        impl $ty {
+            pub const LEN: usize = size_of::<$ty>();
+
+            #[inline(always)]
+            pub fn load<'a>(account_info: &'a AccountInfo) -> Result<Ref<'a, Self>, ProgramError> {
+                Self::check(account_info)?;
+                Ok(Ref::map(account_info.try_borrow_data()?, |data| unsafe {
+                    Self::from_bytes_unchecked(data)
+                }))
+            }
+
+            #[inline(always)]
+            pub fn load_mut<'a>(
+                account_info: &'a AccountInfo,
+            ) -> Result<RefMut<'a, Self>, ProgramError> {
+                Self::check(account_info)?;
+                Ok(RefMut::map(
+                    account_info.try_borrow_mut_data()?,
+                    |data| unsafe { Self::from_bytes_unchecked_mut(data) },
+                ))
+            }
+
+            /// 刚创建的账户（全零数据）写入类型标记后以可变引用返回
+            #[inline(always)]
+            pub fn init<'a>(
+                account_info: &'a AccountInfo,
+            ) -> Result<RefMut<'a, Self>, ProgramError> {
+                if account_info.data_len() != Self::LEN {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                let mut data = account_info.try_borrow_mut_data()?;
+                if data[0] != 0 {
+                    return Err(ProgramError::AccountAlreadyInitialized);
+                }
+                data[0] = $kind as u8;
+                Ok(RefMut::map(data, |data| unsafe {
+                    Self::from_bytes_unchecked_mut(data)
+                }))
+            }
+
+            #[inline(always)]
+            fn check(account_info: &AccountInfo) -> Result<(), ProgramError> {
+                if account_info.data_len() != Self::LEN {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                if account_info.owner().ne(&crate::ID) {
+                    return Err(ProgramError::InvalidAccountOwner);
+                }
+                if unsafe { account_info.borrow_data_unchecked() }[0] != $kind as u8 {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                Ok(())
+            }
+
+            /// # Safety
+            ///
+            /// The caller must ensure that `bytes` contains a valid representation of the
+            /// account. The struct has an alignment of 1 byte.
+            #[inline(always)]
+            pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+                unsafe { &*(bytes.as_ptr() as *const $ty) }
+            }
+
+            /// # Safety
+            ///
+            /// The caller must ensure that `bytes` contains a valid representation of the
+            /// account. The struct has an alignment of 1 byte.
+            #[inline(always)]
+            pub unsafe fn from_bytes_unchecked_mut(bytes: &mut [u8]) -> &mut Self {
+                unsafe { &mut *(bytes.as_mut_ptr() as *mut $ty) }
+            }
+        }
+    };
+}
+
+/// 集中流动性池。PDA: [CLMM_POOL_SEED, mint_x, mint_y, tick_spacing]，要求 mint_x < mint_y。
+#[repr(C)]
+pub struct ClmmPool {
+    account_type: u8,
+    pub bump: [u8; 1],
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+    pub vault_x: Pubkey,
+    pub vault_y: Pubkey,
+    fee: [u8; 2],
+    tick_spacing: [u8; 2],
+    sqrt_price: [u8; 16],
+    tick_current: [u8; 4],
+    liquidity: [u8; 16],
+    fee_growth_global_x: [u8; 16],
+    fee_growth_global_y: [u8; 16],
+}
+
+impl_clmm_account!(ClmmPool, ClmmAccount::Pool);
+
+impl ClmmPool {
+    #[inline(always)]
+    pub fn fee(&self) -> u16 {
+        u16::from_le_bytes(self.fee)
+    }
+    #[inline(always)]
+    pub fn tick_spacing(&self) -> u16 {
+        u16::from_le_bytes(self.tick_spacing)
+    }
+    #[inline(always)]
+    pub fn sqrt_price(&self) -> u128 {
+        u128::from_le_bytes(self.sqrt_price)
+    }
+    #[inline(always)]
+    pub fn tick_current(&self) -> i32 {
+        i32::from_le_bytes(self.tick_current)
+    }
+    /// 当前价格所在区间内的活跃流动性
+    #[inline(always)]
+    pub fn liquidity(&self) -> u128 {
+        u128::from_le_bytes(self.liquidity)
+    }
+    #[inline(always)]
+    pub fn fee_growth_global_x(&self) -> u128 {
+        u128::from_le_bytes(self.fee_growth_global_x)
+    }
+    #[inline(always)]
+    pub fn fee_growth_global_y(&self) -> u128 {
+        u128::from_le_bytes(self.fee_growth_global_y)
+    }
+
+    #[inline(always)]
+    pub fn set_fee(&mut self, fee: u16) -> Result<(), ProgramError> {
+        if fee.ge(&10_000) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        self.fee = fee.to_le_bytes();
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn set_tick_spacing(&mut self, tick_spacing: u16) -> Result<(), ProgramError> {
+        if tick_spacing == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.tick_spacing = tick_spacing.to_le_bytes();
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn set_price(&mut self, sqrt_price: u128, tick_current: i32) {
+        self.sqrt_price = sqrt_price.to_le_bytes();
+        self.tick_current = tick_current.to_le_bytes();
+    }
+    #[inline(always)]
+    pub fn set_liquidity(&mut self, liquidity: u128) {
+        self.liquidity = liquidity.to_le_bytes();
+    }
+    #[inline(always)]
+    pub fn set_fee_growth_global(&mut self, x: u128, y: u128) {
+        self.fee_growth_global_x = x.to_le_bytes();
+        self.fee_growth_global_y = y.to_le_bytes();
+    }
+}
+
+/// 单个已初始化 tick。PDA: [TICK_SEED, pool, tick]。
+#[repr(C)]
+pub struct TickState {
+    account_type: u8,
+    pub pool: Pubkey,
+    tick: [u8; 4],
+    liquidity_gross: [u8; 16],
+    liquidity_net: [u8; 16],
+    fee_growth_outside_x: [u8; 16],
+    fee_growth_outside_y: [u8; 16],
+}
+
+impl_clmm_account!(TickState, ClmmAccount::Tick);
+
+impl TickState {
+    #[inline(always)]
+    pub fn tick(&self) -> i32 {
+        i32::from_le_bytes(self.tick)
+    }
+    /// 引用该 tick 的所有仓位流动性之和，为 0 表示未初始化
+    #[inline(always)]
+    pub fn liquidity_gross(&self) -> u128 {
+        u128::from_le_bytes(self.liquidity_gross)
+    }
+    /// 从左到右穿越该 tick 时活跃流动性的变化量
+    #[inline(always)]
+    pub fn liquidity_net(&self) -> i128 {
+        i128::from_le_bytes(self.liquidity_net)
+    }
+    #[inline(always)]
+    pub fn fee_growth_outside_x(&self) -> u128 {
+        u128::from_le_bytes(self.fee_growth_outside_x)
+    }
+    #[inline(always)]
+    pub fn fee_growth_outside_y(&self) -> u128 {
+        u128::from_le_bytes(self.fee_growth_outside_y)
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, pool: Pubkey, tick: i32) {
+        self.pool = pool;
+        self.tick = tick.to_le_bytes();
+    }
+
+    /// 仓位在 [lower, upper) 上增减 `delta` 流动性时更新 tick，返回是否在
+    /// 初始化/未初始化之间翻转（需要同步位图）。
+    pub fn update(
+        &mut self,
+        tick_current: i32,
+        delta: i128,
+        upper: bool,
+        fee_growth_global_x: u128,
+        fee_growth_global_y: u128,
+    ) -> Result<bool, ProgramError> {
+        let gross_before = self.liquidity_gross();
+        let gross_after =
+            gross_before.checked_add_signed(delta).ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // 约定：tick 初始化时其下方的手续费增长都记在“外侧”
+        if gross_before == 0 && self.tick() <= tick_current {
+            self.fee_growth_outside_x = fee_growth_global_x.to_le_bytes();
+            self.fee_growth_outside_y = fee_growth_global_y.to_le_bytes();
+        }
+
+        let net = if upper {
+            self.liquidity_net().checked_sub(delta)
+        } else {
+            self.liquidity_net().checked_add(delta)
+        }
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        self.liquidity_gross = gross_after.to_le_bytes();
+        self.liquidity_net = net.to_le_bytes();
+
+        // 不再被引用的 tick 清空累计值，下次初始化时重新记账
+        if gross_after == 0 {
+            self.fee_growth_outside_x = [0; 16];
+            self.fee_growth_outside_y = [0; 16];
+        }
+
+        Ok((gross_after == 0) != (gross_before == 0))
+    }
+
+    /// swap 穿越该 tick：翻转外侧手续费增长，返回 liquidity_net
+    pub fn cross(&mut self, fee_growth_global_x: u128, fee_growth_global_y: u128) -> i128 {
+        self.fee_growth_outside_x =
+            fee_growth_global_x.wrapping_sub(self.fee_growth_outside_x()).to_le_bytes();
+        self.fee_growth_outside_y =
+            fee_growth_global_y.wrapping_sub(self.fee_growth_outside_y()).to_le_bytes();
+        self.liquidity_net()
+    }
+}
+
+/// 256 个（压缩后）tick 的初始化位图。PDA: [TICK_BITMAP_SEED, pool, word_pos]。
+#[repr(C)]
+pub struct TickBitmap {
+    account_type: u8,
+    pub pool: Pubkey,
+    word_pos: [u8; 2],
+    pub bits: [u8; 32],
+}
+
+impl_clmm_account!(TickBitmap, ClmmAccount::TickBitmap);
+
+impl TickBitmap {
+    #[inline(always)]
+    pub fn word_pos(&self) -> i16 {
+        i16::from_le_bytes(self.word_pos)
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, pool: Pubkey, word_pos: i16) {
+        self.pool = pool;
+        self.word_pos = word_pos.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn flip(&mut self, bit: u8) {
+        self.bits[(bit / 8) as usize] ^= 1 << (bit % 8);
+    }
+}
+
+/// LP 仓位。PDA: [POSITION_SEED, pool, owner, tick_lower, tick_upper]。
+#[repr(C)]
+pub struct Position {
+    account_type: u8,
+    pub bump: [u8; 1],
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    tick_lower: [u8; 4],
+    tick_upper: [u8; 4],
+    liquidity: [u8; 16],
+    fee_growth_inside_last_x: [u8; 16],
+    fee_growth_inside_last_y: [u8; 16],
+    tokens_owed_x: [u8; 8],
+    tokens_owed_y: [u8; 8],
+}
+
+impl_clmm_account!(Position, ClmmAccount::Position);
+
+impl Position {
+    #[inline(always)]
+    pub fn tick_lower(&self) -> i32 {
+        i32::from_le_bytes(self.tick_lower)
+    }
+    #[inline(always)]
+    pub fn tick_upper(&self) -> i32 {
+        i32::from_le_bytes(self.tick_upper)
+    }
+    #[inline(always)]
+    pub fn liquidity(&self) -> u128 {
+        u128::from_le_bytes(self.liquidity)
+    }
+    #[inline(always)]
+    pub fn tokens_owed_x(&self) -> u64 {
+        u64::from_le_bytes(self.tokens_owed_x)
+    }
+    #[inline(always)]
+    pub fn tokens_owed_y(&self) -> u64 {
+        u64::from_le_bytes(self.tokens_owed_y)
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        pool: Pubkey,
+        owner: Pubkey,
+        tick_lower: i32,
+        tick_upper: i32,
+        bump: [u8; 1],
+    ) {
+        self.pool = pool;
+        self.owner = owner;
+        self.tick_lower = tick_lower.to_le_bytes();
+        self.tick_upper = tick_upper.to_le_bytes();
+        self.bump = bump;
+    }
+
+    /// 结算截至目前的手续费并应用流动性变化
+    pub fn update(
+        &mut self,
+        delta: i128,
+        fee_growth_inside_x: u128,
+        fee_growth_inside_y: u128,
+    ) -> Result<(), ProgramError> {
+        let liquidity = self.liquidity();
+        let owed_x = super::math::mul_div(
+            fee_growth_inside_x.wrapping_sub(u128::from_le_bytes(self.fee_growth_inside_last_x)),
+            liquidity,
+            super::math::Q64,
+            false,
+        )?;
+        let owed_y = super::math::mul_div(
+            fee_growth_inside_y.wrapping_sub(u128::from_le_bytes(self.fee_growth_inside_last_y)),
+            liquidity,
+            super::math::Q64,
+            false,
+        )?;
+
+        self.liquidity = liquidity
+            .checked_add_signed(delta)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .to_le_bytes();
+        self.fee_growth_inside_last_x = fee_growth_inside_x.to_le_bytes();
+        self.fee_growth_inside_last_y = fee_growth_inside_y.to_le_bytes();
+        self.credit(owed_x, owed_y)
+    }
+
+    /// 增加可领取的代币（手续费或移除流动性后的本金）
+    pub fn credit(&mut self, x: u128, y: u128) -> Result<(), ProgramError> {
+        let owed_x = (self.tokens_owed_x() as u128)
+            .checked_add(x)
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let owed_y = (self.tokens_owed_y() as u128)
+            .checked_add(y)
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.tokens_owed_x = owed_x.to_le_bytes();
+        self.tokens_owed_y = owed_y.to_le_bytes();
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn clear_owed(&mut self) {
+        self.tokens_owed_x = [0; 8];
+        self.tokens_owed_y = [0; 8];
+    }
+}
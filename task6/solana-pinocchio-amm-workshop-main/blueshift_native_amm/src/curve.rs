@@ -0,0 +1,206 @@
+//! deposit / withdraw / swap 共用的曲线数学层。
+//!
+//! 恒定乘积部分移植自 `docs/reference/constant_product_curve.rs`，改动如下：
+//!
+//! - 两个方向的 swap 都从输入中扣除手续费（参考实现的 Y -> X 方向漏扣）；
+//! - 所有舍入方向对池子有利：存入向上取整，取出与 swap 输出向下取整；
+//! - 错误统一为 `ProgramError`，不再需要在调用处逐个 `map_err`。
+//!
+//! 按 LP 份额存入/取出对任何不变量都是等比例的，由 [`Curve`] 的默认方法实现；
+//! 各曲线只需要给出 swap 的输出。
+
+use pinocchio::program_error::ProgramError;
+
+use crate::{Config, weighted_math::weighted_swap_out};
+
+/// 按 LP 份额等比例计算单个代币数量：`balance * lp / supply`。
+/// 存款向上取整（对池子有利），取款向下取整。
+pub fn proportional_amount(
+    balance: u64,
+    lp: u64,
+    supply: u64,
+    round_up: bool,
+) -> Result<u64, ProgramError> {
+    if supply == 0 {
+        return Err(ProgramError::ArithmeticOverflow);
+    }
+    let numerator = (balance as u128)
+        .checked_mul(lp as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let mut amount = numerator / supply as u128;
+    if round_up && numerator % supply as u128 != 0 {
+        amount += 1;
+    }
+    u64::try_from(amount).map_err(|_| ProgramError::ArithmeticOverflow)
+}
+
+/// 恒定乘积 swap 输出：`out = B_out * A_in' / (B_in + A_in')`，`A_in'` 为扣除手续费后的输入。
+pub fn constant_product_out(
+    balance_in: u64,
+    balance_out: u64,
+    amount_in: u64,
+    fee: u16,
+) -> Result<u64, ProgramError> {
+    let amount_in_after_fee = (amount_in as u128)
+        .checked_mul(10_000 - fee as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        / 10_000;
+    let denominator = (balance_in as u128)
+        .checked_add(amount_in_after_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if denominator == 0 {
+        return Err(ProgramError::ArithmeticOverflow);
+    }
+    let out = (balance_out as u128)
+        .checked_mul(amount_in_after_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        / denominator;
+    u64::try_from(out).map_err(|_| ProgramError::ArithmeticOverflow)
+}
+
+/// 两币池的定价曲线
+pub trait Curve {
+    /// 当前储备 (x, y)
+    fn reserves(&self) -> (u64, u64);
+
+    /// 输入 `amount_in` 个 X（`is_x`）或 Y 时可得到的另一种代币数量
+    fn swap_out(&self, is_x: bool, amount_in: u64) -> Result<u64, ProgramError>;
+
+    /// 铸造 `lp` 个 LP 需要存入的 (x, y)，向上取整
+    fn deposit_amounts(&self, lp: u64, supply: u64) -> Result<(u64, u64), ProgramError> {
+        let (x, y) = self.reserves();
+        Ok((
+            proportional_amount(x, lp, supply, true)?,
+            proportional_amount(y, lp, supply, true)?,
+        ))
+    }
+
+    /// 销毁 `lp` 个 LP 可取回的 (x, y)，向下取整；全额销毁时取走全部储备
+    fn withdraw_amounts(&self, lp: u64, supply: u64) -> Result<(u64, u64), ProgramError> {
+        let (x, y) = self.reserves();
+        if lp == supply {
+            return Ok((x, y));
+        }
+        Ok((
+            proportional_amount(x, lp, supply, false)?,
+            proportional_amount(y, lp, supply, false)?,
+        ))
+    }
+
+    /// 带滑点保护的 swap，返回 (用户存入, 金库支付)
+    fn swap(&self, is_x: bool, amount_in: u64, min: u64) -> Result<(u64, u64), ProgramError> {
+        if amount_in == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let out = self.swap_out(is_x, amount_in)?;
+        if out == 0 || out < min {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok((amount_in, out))
+    }
+}
+
+/// 恒定乘积曲线 `x * y = k`
+pub struct ConstantProduct {
+    pub reserve_x: u64,
+    pub reserve_y: u64,
+    pub fee: u16,
+}
+
+impl Curve for ConstantProduct {
+    fn reserves(&self) -> (u64, u64) {
+        (self.reserve_x, self.reserve_y)
+    }
+
+    fn swap_out(&self, is_x: bool, amount_in: u64) -> Result<u64, ProgramError> {
+        if is_x {
+            constant_product_out(self.reserve_x, self.reserve_y, amount_in, self.fee)
+        } else {
+            constant_product_out(self.reserve_y, self.reserve_x, amount_in, self.fee)
+        }
+    }
+}
+
+/// 加权恒定均值曲线 `x^w_x * y^w_y = k`
+pub struct Weighted {
+    pub reserve_x: u64,
+    pub reserve_y: u64,
+    pub weight_x: u16,
+    pub weight_y: u16,
+    pub fee: u16,
+}
+
+impl Curve for Weighted {
+    fn reserves(&self) -> (u64, u64) {
+        (self.reserve_x, self.reserve_y)
+    }
+
+    fn swap_out(&self, is_x: bool, amount_in: u64) -> Result<u64, ProgramError> {
+        if is_x {
+            weighted_swap_out(
+                self.reserve_x,
+                self.weight_x,
+                self.reserve_y,
+                self.weight_y,
+                amount_in,
+                self.fee,
+            )
+        } else {
+            weighted_swap_out(
+                self.reserve_y,
+                self.weight_y,
+                self.reserve_x,
+                self.weight_x,
+                amount_in,
+                self.fee,
+            )
+        }
+    }
+}
+
+/// 按池子配置选择曲线：加权池使用恒定均值，50/50 池使用恒定乘积
+pub enum PoolCurve {
+    ConstantProduct(ConstantProduct),
+    Weighted(Weighted),
+}
+
+impl PoolCurve {
+    pub fn from_config(config: &Config, reserve_x: u64, reserve_y: u64) -> Self {
+        Self::with_fee(config, reserve_x, reserve_y, config.fee())
+    }
+
+    /// 使用指定手续费（例如 LP 持有者的返还后费率）代替池子的基础费率
+    pub fn with_fee(config: &Config, reserve_x: u64, reserve_y: u64, fee: u16) -> Self {
+        if config.is_weighted() {
+            Self::Weighted(Weighted {
+                reserve_x,
+                reserve_y,
+                weight_x: config.weight_x(),
+                weight_y: config.weight_y(),
+                fee,
+            })
+        } else {
+            Self::ConstantProduct(ConstantProduct {
+                reserve_x,
+                reserve_y,
+                fee,
+            })
+        }
+    }
+}
+
+impl Curve for PoolCurve {
+    fn reserves(&self) -> (u64, u64) {
+        match self {
+            Self::ConstantProduct(curve) => curve.reserves(),
+            Self::Weighted(curve) => curve.reserves(),
+        }
+    }
+
+    fn swap_out(&self, is_x: bool, amount_in: u64) -> Result<u64, ProgramError> {
+        match self {
+            Self::ConstantProduct(curve) => curve.swap_out(is_x, amount_in),
+            Self::Weighted(curve) => curve.swap_out(is_x, amount_in),
+        }
+    }
+}
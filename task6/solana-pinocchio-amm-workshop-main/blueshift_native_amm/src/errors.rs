@@ -0,0 +1,4 @@
+/// AMM 自定义错误，以 `ProgramError::Custom(code)` 返回给客户端。
+///
+/// 定义在 `blueshift_errors` 中，占用 6200 起的编号，与金库、托管的错误码互不重叠。
+pub use blueshift_errors::AmmError;
@@ -0,0 +1,135 @@
+use blueshift_common::{AccountCheck, SignerAccount};
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+};
+use pinocchio_token::instructions::Transfer;
+
+use super::helpers::position_fee_growth_inside;
+use crate::clmm::{CLMM_POOL_SEED, ClmmPool, Position};
+
+/// 结算仓位手续费，并把 tokens_owed（手续费 + 已移除的本金）转给 owner
+pub struct ClmmCollectAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub pool: &'a AccountInfo,
+    pub position: &'a AccountInfo,
+    pub tick_lower: &'a AccountInfo,
+    pub tick_upper: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub user_x_ata: &'a AccountInfo,
+    pub user_y_ata: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClmmCollectAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let mut account_iter = accounts.iter();
+        Ok(Self {
+            owner: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            pool: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            position: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            tick_lower: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            tick_upper: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            vault_x: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            vault_y: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            user_x_ata: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            user_y_ata: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            token_program: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+        })
+    }
+}
+
+pub struct ClmmCollect<'a> {
+    pub accounts: ClmmCollectAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClmmCollect<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = ClmmCollectAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ClmmCollect<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &13;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let pool_key = accounts.pool.key();
+
+        SignerAccount::check(accounts.owner)?;
+
+        // 1. 结算截至目前的手续费，取出并清零可领取余额
+        let (owed_x, owed_y, bump, mint_x, mint_y, tick_spacing) = {
+            let pool = ClmmPool::load(accounts.pool)?;
+            if pool.vault_x.ne(accounts.vault_x.key()) || pool.vault_y.ne(accounts.vault_y.key()) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let mut position = Position::load_mut(accounts.position)?;
+            if position.pool.ne(pool_key) || position.owner.ne(accounts.owner.key()) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let (inside_x, inside_y) = position_fee_growth_inside(
+                &pool,
+                pool_key,
+                accounts.tick_lower,
+                accounts.tick_upper,
+                position.tick_lower(),
+                position.tick_upper(),
+            )?;
+            position.update(0, inside_x, inside_y)?;
+
+            let owed = (position.tokens_owed_x(), position.tokens_owed_y());
+            position.clear_owed();
+            (
+                owed.0,
+                owed.1,
+                pool.bump,
+                pool.mint_x,
+                pool.mint_y,
+                pool.tick_spacing(),
+            )
+        };
+
+        // 2. pool PDA 签名从金库转出
+        let spacing_binding = tick_spacing.to_le_bytes();
+        let pool_seeds = [
+            Seed::from(CLMM_POOL_SEED),
+            Seed::from(&mint_x),
+            Seed::from(&mint_y),
+            Seed::from(&spacing_binding),
+            Seed::from(&bump),
+        ];
+        let signer = Signer::from(&pool_seeds);
+
+        if owed_x > 0 {
+            Transfer {
+                from: accounts.vault_x,
+                to: accounts.user_x_ata,
+                authority: accounts.pool,
+                amount: owed_x,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+        }
+        if owed_y > 0 {
+            Transfer {
+                from: accounts.vault_y,
+                to: accounts.user_y_ata,
+                authority: accounts.pool,
+                amount: owed_y,
+            }
+            .invoke_signed(&[signer])?;
+        }
+
+        Ok(())
+    }
+}
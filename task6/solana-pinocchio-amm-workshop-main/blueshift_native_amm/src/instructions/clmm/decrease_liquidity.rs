@@ -0,0 +1,104 @@
+use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+
+use super::helpers::ModifyPosition;
+use crate::clmm::Position;
+
+/// 从仓位移除流动性。移除的本金计入仓位的 tokens_owed，通过 ClmmCollect 领取。
+pub struct ClmmDecreaseLiquidityAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub pool: &'a AccountInfo,
+    pub position: &'a AccountInfo,
+    pub tick_lower: &'a AccountInfo,
+    pub tick_upper: &'a AccountInfo,
+    pub bitmap_lower: &'a AccountInfo,
+    pub bitmap_upper: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClmmDecreaseLiquidityAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let mut account_iter = accounts.iter();
+        Ok(Self {
+            owner: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            pool: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            position: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            tick_lower: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            tick_upper: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            bitmap_lower: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            bitmap_upper: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+        })
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct ClmmDecreaseLiquidityInstructionData {
+    pub liquidity: u128,
+    pub min_x: u64,
+    pub min_y: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for ClmmDecreaseLiquidityInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+pub struct ClmmDecreaseLiquidity<'a> {
+    pub accounts: ClmmDecreaseLiquidityAccounts<'a>,
+    pub instruction_data: ClmmDecreaseLiquidityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ClmmDecreaseLiquidity<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = ClmmDecreaseLiquidityAccounts::try_from(accounts)?;
+        let instruction_data = ClmmDecreaseLiquidityInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> ClmmDecreaseLiquidity<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &11;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let data = self.instruction_data;
+
+        let delta = i128::try_from(data.liquidity).map_err(|_| ProgramError::ArithmeticOverflow)?;
+        if delta == 0 || data.liquidity > Position::load(accounts.position)?.liquidity() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 1. 更新仓位、tick 与池子状态，得到移除的数量
+        let (x, y) = ModifyPosition {
+            owner: accounts.owner,
+            pool: accounts.pool,
+            position: accounts.position,
+            tick_lower: accounts.tick_lower,
+            tick_upper: accounts.tick_upper,
+            bitmap_lower: accounts.bitmap_lower,
+            bitmap_upper: accounts.bitmap_upper,
+        }
+        .apply(-delta)?;
+
+        // 2. 滑点检查
+        if x < data.min_x as u128 || y < data.min_y as u128 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 3. 计入可领取余额
+        Position::load_mut(accounts.position)?.credit(x, y)
+    }
+}
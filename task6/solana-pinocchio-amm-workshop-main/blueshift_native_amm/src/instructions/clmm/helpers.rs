@@ -0,0 +1,271 @@
+use blueshift_common::{AccountCheck, SignerAccount};
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{Sysvar, rent::Rent},
+};
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::clmm::{
+    ClmmPool, Position, TICK_BITMAP_SEED, TICK_SEED, TickBitmap, TickState,
+    math::{
+        amount_x_delta, amount_y_delta, bitmap_position, fee_growth_inside, sqrt_price_at_tick,
+    },
+};
+
+/// 以 PDA 签名创建由本程序持有的账户（seeds 需包含 bump）
+pub(crate) fn create_program_account(
+    payer: &AccountInfo,
+    account: &AccountInfo,
+    space: usize,
+    seeds: &[Seed],
+) -> ProgramResult {
+    CreateAccount {
+        from: payer,
+        to: account,
+        lamports: Rent::get()?.minimum_balance(space),
+        space: space as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[Signer::from(seeds)])
+}
+
+/// 确保 tick 账户存在；首次使用时在规范 PDA 上创建
+fn ensure_tick(
+    payer: &AccountInfo,
+    pool: &Pubkey,
+    account: &AccountInfo,
+    tick: i32,
+) -> ProgramResult {
+    if !account.data_is_empty() {
+        return Ok(());
+    }
+    let tick_binding = tick.to_le_bytes();
+    let (address, bump) = pdas::clmm_tick_pda(&crate::ID, pool, tick);
+    if address.ne(account.key()) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let bump = [bump];
+    create_program_account(
+        payer,
+        account,
+        TickState::LEN,
+        &[
+            Seed::from(TICK_SEED),
+            Seed::from(pool),
+            Seed::from(&tick_binding),
+            Seed::from(&bump),
+        ],
+    )?;
+    TickState::init(account)?.set_inner(*pool, tick);
+    Ok(())
+}
+
+/// 确保位图账户存在；首次使用时在规范 PDA 上创建
+fn ensure_bitmap(
+    payer: &AccountInfo,
+    pool: &Pubkey,
+    account: &AccountInfo,
+    word_pos: i16,
+) -> ProgramResult {
+    if !account.data_is_empty() {
+        return Ok(());
+    }
+    let word_binding = word_pos.to_le_bytes();
+    let (address, bump) = pdas::clmm_tick_bitmap_pda(&crate::ID, pool, word_pos);
+    if address.ne(account.key()) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let bump = [bump];
+    create_program_account(
+        payer,
+        account,
+        TickBitmap::LEN,
+        &[
+            Seed::from(TICK_BITMAP_SEED),
+            Seed::from(pool),
+            Seed::from(&word_binding),
+            Seed::from(&bump),
+        ],
+    )?;
+    TickBitmap::init(account)?.set_inner(*pool, word_pos);
+    Ok(())
+}
+
+/// 更新单个 tick，翻转时同步位图
+#[allow(clippy::too_many_arguments)]
+fn update_tick(
+    payer: &AccountInfo,
+    pool: &ClmmPool,
+    pool_key: &Pubkey,
+    tick_info: &AccountInfo,
+    bitmap_info: &AccountInfo,
+    tick: i32,
+    delta: i128,
+    upper: bool,
+) -> ProgramResult {
+    if delta > 0 {
+        ensure_tick(payer, pool_key, tick_info, tick)?;
+    }
+    let flipped = {
+        let mut tick_state = TickState::load_mut(tick_info)?;
+        if tick_state.pool.ne(pool_key) || tick_state.tick() != tick {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        tick_state.update(
+            pool.tick_current(),
+            delta,
+            upper,
+            pool.fee_growth_global_x(),
+            pool.fee_growth_global_y(),
+        )?
+    };
+
+    if flipped {
+        let (word_pos, bit) = bitmap_position(tick, pool.tick_spacing());
+        ensure_bitmap(payer, pool_key, bitmap_info, word_pos)?;
+        let mut bitmap = TickBitmap::load_mut(bitmap_info)?;
+        if bitmap.pool.ne(pool_key) || bitmap.word_pos() != word_pos {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        bitmap.flip(bit);
+    }
+    Ok(())
+}
+
+/// 读取仓位区间内的手续费增长
+pub(crate) fn position_fee_growth_inside(
+    pool: &ClmmPool,
+    pool_key: &Pubkey,
+    tick_lower_info: &AccountInfo,
+    tick_upper_info: &AccountInfo,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<(u128, u128), ProgramError> {
+    let lower = TickState::load(tick_lower_info)?;
+    let upper = TickState::load(tick_upper_info)?;
+    if lower.pool.ne(pool_key)
+        || upper.pool.ne(pool_key)
+        || lower.tick() != tick_lower
+        || upper.tick() != tick_upper
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok((
+        fee_growth_inside(
+            pool.tick_current(),
+            tick_lower,
+            lower.fee_growth_outside_x(),
+            tick_upper,
+            upper.fee_growth_outside_x(),
+            pool.fee_growth_global_x(),
+        ),
+        fee_growth_inside(
+            pool.tick_current(),
+            tick_lower,
+            lower.fee_growth_outside_y(),
+            tick_upper,
+            upper.fee_growth_outside_y(),
+            pool.fee_growth_global_y(),
+        ),
+    ))
+}
+
+/// 集中流动性的核心：按 `delta` 增减仓位流动性，更新两端 tick、位图与池子的活跃流动性，
+/// 返回对应的代币数量（增加时向上取整，减少时向下取整）。
+pub(crate) struct ModifyPosition<'a> {
+    pub owner: &'a AccountInfo,
+    pub pool: &'a AccountInfo,
+    pub position: &'a AccountInfo,
+    pub tick_lower: &'a AccountInfo,
+    pub tick_upper: &'a AccountInfo,
+    pub bitmap_lower: &'a AccountInfo,
+    pub bitmap_upper: &'a AccountInfo,
+}
+
+impl ModifyPosition<'_> {
+    pub(crate) fn apply(&self, delta: i128) -> Result<(u128, u128), ProgramError> {
+        SignerAccount::check(self.owner)?;
+        let pool_key = self.pool.key();
+        let (tick_lower, tick_upper) = {
+            let position = Position::load(self.position)?;
+            if position.pool.ne(pool_key) || position.owner.ne(self.owner.key()) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            (position.tick_lower(), position.tick_upper())
+        };
+
+        let mut pool = ClmmPool::load_mut(self.pool)?;
+
+        // 1. 更新两端 tick（lower 与 upper 的位图可能是同一个账户，依次借用）
+        update_tick(
+            self.owner,
+            &pool,
+            pool_key,
+            self.tick_lower,
+            self.bitmap_lower,
+            tick_lower,
+            delta,
+            false,
+        )?;
+        update_tick(
+            self.owner,
+            &pool,
+            pool_key,
+            self.tick_upper,
+            self.bitmap_upper,
+            tick_upper,
+            delta,
+            true,
+        )?;
+
+        // 2. 结算仓位手续费并应用流动性变化
+        let (inside_x, inside_y) = position_fee_growth_inside(
+            &pool,
+            pool_key,
+            self.tick_lower,
+            self.tick_upper,
+            tick_lower,
+            tick_upper,
+        )?;
+        Position::load_mut(self.position)?.update(delta, inside_x, inside_y)?;
+
+        // 3. 根据当前价格相对区间的位置计算代币数量
+        let round_up = delta > 0;
+        let liquidity = delta.unsigned_abs();
+        let sqrt_lower = sqrt_price_at_tick(tick_lower)?;
+        let sqrt_upper = sqrt_price_at_tick(tick_upper)?;
+        let tick_current = pool.tick_current();
+
+        let amounts = if tick_current < tick_lower {
+            // 价格在区间下方：全部是 x
+            (
+                amount_x_delta(sqrt_lower, sqrt_upper, liquidity, round_up)?,
+                0,
+            )
+        } else if tick_current < tick_upper {
+            // 价格在区间内：x、y 都有，并影响活跃流动性
+            let sqrt_price = pool.sqrt_price();
+            let active = pool
+                .liquidity()
+                .checked_add_signed(delta)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            pool.set_liquidity(active);
+            (
+                amount_x_delta(sqrt_price, sqrt_upper, liquidity, round_up)?,
+                amount_y_delta(sqrt_lower, sqrt_price, liquidity, round_up)?,
+            )
+        } else {
+            // 价格在区间上方：全部是 y
+            (
+                0,
+                amount_y_delta(sqrt_lower, sqrt_upper, liquidity, round_up)?,
+            )
+        };
+
+        Ok(amounts)
+    }
+}
@@ -0,0 +1,144 @@
+use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+use pinocchio_token::instructions::Transfer;
+
+use super::helpers::ModifyPosition;
+use crate::clmm::ClmmPool;
+
+/// 向仓位添加流动性。tick / 位图账户首次使用时由 owner 付费创建。
+pub struct ClmmIncreaseLiquidityAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub pool: &'a AccountInfo,
+    pub position: &'a AccountInfo,
+    pub tick_lower: &'a AccountInfo,
+    pub tick_upper: &'a AccountInfo,
+    pub bitmap_lower: &'a AccountInfo,
+    pub bitmap_upper: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub user_x_ata: &'a AccountInfo,
+    pub user_y_ata: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClmmIncreaseLiquidityAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let mut account_iter = accounts.iter();
+        Ok(Self {
+            owner: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            pool: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            position: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            tick_lower: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            tick_upper: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            bitmap_lower: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            bitmap_upper: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            vault_x: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            vault_y: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            user_x_ata: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            user_y_ata: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            token_program: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            system_program: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+        })
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct ClmmIncreaseLiquidityInstructionData {
+    pub liquidity: u128,
+    pub max_x: u64,
+    pub max_y: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for ClmmIncreaseLiquidityInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+pub struct ClmmIncreaseLiquidity<'a> {
+    pub accounts: ClmmIncreaseLiquidityAccounts<'a>,
+    pub instruction_data: ClmmIncreaseLiquidityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ClmmIncreaseLiquidity<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = ClmmIncreaseLiquidityAccounts::try_from(accounts)?;
+        let instruction_data = ClmmIncreaseLiquidityInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> ClmmIncreaseLiquidity<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &10;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let data = self.instruction_data;
+
+        let delta = i128::try_from(data.liquidity).map_err(|_| ProgramError::ArithmeticOverflow)?;
+        if delta == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 1. 金库必须是池子记录的金库
+        {
+            let pool = ClmmPool::load(accounts.pool)?;
+            if pool.vault_x.ne(accounts.vault_x.key()) || pool.vault_y.ne(accounts.vault_y.key()) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        // 2. 更新仓位、tick 与池子状态，得到需要存入的数量
+        let (x, y) = ModifyPosition {
+            owner: accounts.owner,
+            pool: accounts.pool,
+            position: accounts.position,
+            tick_lower: accounts.tick_lower,
+            tick_upper: accounts.tick_upper,
+            bitmap_lower: accounts.bitmap_lower,
+            bitmap_upper: accounts.bitmap_upper,
+        }
+        .apply(delta)?;
+
+        // 3. 滑点保护检查
+        if x > data.max_x as u128 || y > data.max_y as u128 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 4. 执行代币转移 (用户 -> 金库)
+        if x > 0 {
+            Transfer {
+                from: accounts.user_x_ata,
+                to: accounts.vault_x,
+                authority: accounts.owner,
+                amount: x as u64,
+            }
+            .invoke()?;
+        }
+        if y > 0 {
+            Transfer {
+                from: accounts.user_y_ata,
+                to: accounts.vault_y,
+                authority: accounts.owner,
+                amount: y as u64,
+            }
+            .invoke()?;
+        }
+
+        Ok(())
+    }
+}
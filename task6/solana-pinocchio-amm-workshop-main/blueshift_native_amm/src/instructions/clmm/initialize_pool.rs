@@ -0,0 +1,137 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use pinocchio_token::state::TokenAccount;
+
+use super::helpers::create_program_account;
+use crate::clmm::{
+    CLMM_POOL_SEED, ClmmPool,
+    math::{MAX_TICK, tick_at_sqrt_price},
+};
+
+/// 创建集中流动性池。两个金库需由客户端预先创建，owner 为 pool PDA。
+pub struct ClmmInitializePoolAccounts<'a> {
+    pub payer: &'a AccountInfo,
+    pub pool: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClmmInitializePoolAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let mut account_iter = accounts.iter();
+        Ok(Self {
+            payer: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            pool: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            vault_x: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            vault_y: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            system_program: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+        })
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct ClmmInitializePoolInstructionData {
+    pub fee: u16,
+    pub tick_spacing: u16,
+    /// 初始价格，Q64.64 的 sqrt price
+    pub sqrt_price: u128,
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+}
+
+impl<'a> TryFrom<&'a [u8]> for ClmmInitializePoolInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+pub struct ClmmInitializePool<'a> {
+    pub accounts: ClmmInitializePoolAccounts<'a>,
+    pub instruction_data: ClmmInitializePoolInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ClmmInitializePool<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = ClmmInitializePoolAccounts::try_from(accounts)?;
+        let instruction_data = ClmmInitializePoolInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> ClmmInitializePool<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &8;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let data = self.instruction_data;
+
+        // 1. 参数校验：mint 有序、tick_spacing 合理
+        if data.mint_x >= data.mint_y {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if data.tick_spacing == 0 || data.tick_spacing as i32 > MAX_TICK {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let tick_current = tick_at_sqrt_price(data.sqrt_price)?;
+
+        // 2. 校验 pool 地址为规范 PDA
+        let spacing_binding = data.tick_spacing.to_le_bytes();
+        let (address, bump) =
+            pdas::clmm_pool_pda(&crate::ID, &data.mint_x, &data.mint_y, data.tick_spacing);
+        if address.ne(accounts.pool.key()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        // 3. 金库必须由 pool PDA 持有且 mint 匹配
+        for (vault, mint) in [(accounts.vault_x, &data.mint_x), (accounts.vault_y, &data.mint_y)] {
+            let vault = TokenAccount::from_account_info(vault)?;
+            if vault.owner().ne(&address) || vault.mint().ne(mint) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        // 4. 创建并初始化 pool 账户
+        let bump = [bump];
+        create_program_account(
+            accounts.payer,
+            accounts.pool,
+            ClmmPool::LEN,
+            &[
+                Seed::from(CLMM_POOL_SEED),
+                Seed::from(&data.mint_x),
+                Seed::from(&data.mint_y),
+                Seed::from(&spacing_binding),
+                Seed::from(&bump),
+            ],
+        )?;
+
+        let mut pool = ClmmPool::init(accounts.pool)?;
+        pool.bump = bump;
+        pool.mint_x = data.mint_x;
+        pool.mint_y = data.mint_y;
+        pool.vault_x = *accounts.vault_x.key();
+        pool.vault_y = *accounts.vault_y.key();
+        pool.set_fee(data.fee)?;
+        pool.set_tick_spacing(data.tick_spacing)?;
+        pool.set_price(data.sqrt_price, tick_current);
+
+        Ok(())
+    }
+}
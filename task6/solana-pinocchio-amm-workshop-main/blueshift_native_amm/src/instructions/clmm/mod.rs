@@ -0,0 +1,14 @@
+pub mod collect;
+pub mod decrease_liquidity;
+mod helpers;
+pub mod increase_liquidity;
+pub mod initialize_pool;
+pub mod open_position;
+pub mod swap;
+
+pub use collect::*;
+pub use decrease_liquidity::*;
+pub use increase_liquidity::*;
+pub use initialize_pool::*;
+pub use open_position::*;
+pub use swap::*;
@@ -0,0 +1,132 @@
+use blueshift_common::{AccountCheck, SignerAccount};
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+};
+
+use super::helpers::create_program_account;
+use crate::clmm::{
+    ClmmPool, POSITION_SEED, Position,
+    math::{MAX_TICK, MIN_TICK},
+};
+
+/// 为 owner 在 [tick_lower, tick_upper) 区间开一个空仓位
+pub struct ClmmOpenPositionAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub pool: &'a AccountInfo,
+    pub position: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClmmOpenPositionAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let mut account_iter = accounts.iter();
+        Ok(Self {
+            owner: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            pool: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            position: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            system_program: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+        })
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct ClmmOpenPositionInstructionData {
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+}
+
+impl<'a> TryFrom<&'a [u8]> for ClmmOpenPositionInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+pub struct ClmmOpenPosition<'a> {
+    pub accounts: ClmmOpenPositionAccounts<'a>,
+    pub instruction_data: ClmmOpenPositionInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ClmmOpenPosition<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = ClmmOpenPositionAccounts::try_from(accounts)?;
+        let instruction_data = ClmmOpenPositionInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> ClmmOpenPosition<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &9;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let (tick_lower, tick_upper) = (
+            self.instruction_data.tick_lower,
+            self.instruction_data.tick_upper,
+        );
+
+        SignerAccount::check(accounts.owner)?;
+
+        // 1. 区间必须有效且对齐 tick_spacing
+        let tick_spacing = ClmmPool::load(accounts.pool)?.tick_spacing() as i32;
+        if tick_lower >= tick_upper
+            || tick_lower < MIN_TICK
+            || tick_upper > MAX_TICK
+            || tick_lower % tick_spacing != 0
+            || tick_upper % tick_spacing != 0
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. 在规范 PDA 上创建仓位
+        let lower_binding = tick_lower.to_le_bytes();
+        let upper_binding = tick_upper.to_le_bytes();
+        let (address, bump) = pdas::clmm_position_pda(
+            &crate::ID,
+            accounts.pool.key(),
+            accounts.owner.key(),
+            tick_lower,
+            tick_upper,
+        );
+        if address.ne(accounts.position.key()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bump = [bump];
+        create_program_account(
+            accounts.owner,
+            accounts.position,
+            Position::LEN,
+            &[
+                Seed::from(POSITION_SEED),
+                Seed::from(accounts.pool.key()),
+                Seed::from(accounts.owner.key()),
+                Seed::from(&lower_binding),
+                Seed::from(&upper_binding),
+                Seed::from(&bump),
+            ],
+        )?;
+
+        Position::init(accounts.position)?.set_inner(
+            *accounts.pool.key(),
+            *accounts.owner.key(),
+            tick_lower,
+            tick_upper,
+            bump,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::clmm::{
+    CLMM_POOL_SEED, ClmmPool, TickBitmap, TickState,
+    math::{
+        MAX_SQRT_PRICE, MAX_TICK, MIN_SQRT_PRICE, MIN_TICK, Q64, bitmap_position,
+        compute_swap_step, mul_div, next_initialized_bit, sqrt_price_at_tick, tick_at_sqrt_price,
+        tick_from_bitmap,
+    },
+};
+
+/// 精确输入 swap。`remaining` 需按需包含价格经过的所有位图账户
+/// （尚未创建的位图传入其 PDA 地址）以及会被穿越的 tick 账户。
+pub struct ClmmSwapAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub pool: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub user_x_ata: &'a AccountInfo,
+    pub user_y_ata: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub remaining: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClmmSwapAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            user,
+            pool,
+            vault_x,
+            vault_y,
+            user_x_ata,
+            user_y_ata,
+            token_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Ok(Self {
+            user,
+            pool,
+            vault_x,
+            vault_y,
+            user_x_ata,
+            user_y_ata,
+            token_program,
+            remaining,
+        })
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct ClmmSwapInstructionData {
+    /// 1: x -> y（价格下降），0: y -> x（价格上升）
+    pub x_to_y: u8,
+    pub amount: u64,
+    pub min: u64,
+    /// 价格限制（Q64.64），0 表示不限制
+    pub sqrt_price_limit: u128,
+    pub expiration: i64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for ClmmSwapInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+pub struct ClmmSwap<'a> {
+    pub accounts: ClmmSwapAccounts<'a>,
+    pub instruction_data: ClmmSwapInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ClmmSwap<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = ClmmSwapAccounts::try_from(accounts)?;
+        let instruction_data = ClmmSwapInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> ClmmSwap<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &12;
+
+    /// 读取位图字：已创建的账户直接读取，未创建的必须以其 PDA 地址出现在 remaining 中
+    fn bitmap_word(&self, pool: &Pubkey, word_pos: i16) -> Result<[u8; 32], ProgramError> {
+        for account in self.accounts.remaining {
+            if account.data_len() == TickBitmap::LEN && account.owner().eq(&crate::ID) {
+                let bitmap = TickBitmap::load(account)?;
+                if bitmap.pool.eq(pool) && bitmap.word_pos() == word_pos {
+                    return Ok(bitmap.bits);
+                }
+            }
+        }
+        let (address, _) = pdas::clmm_tick_bitmap_pda(&crate::ID, pool, word_pos);
+        if self
+            .accounts
+            .remaining
+            .iter()
+            .any(|account| account.key().eq(&address) && account.data_is_empty())
+        {
+            return Ok([0; 32]);
+        }
+        Err(ProgramError::NotEnoughAccountKeys)
+    }
+
+    fn tick_account(&self, pool: &Pubkey, tick: i32) -> Result<&'a AccountInfo, ProgramError> {
+        for account in self.accounts.remaining {
+            if account.data_len() == TickState::LEN && account.owner().eq(&crate::ID) {
+                let tick_state = TickState::load(account)?;
+                if tick_state.pool.eq(pool) && tick_state.tick() == tick {
+                    return Ok(account);
+                }
+            }
+        }
+        Err(ProgramError::NotEnoughAccountKeys)
+    }
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let data = self.instruction_data;
+        let x_to_y = data.x_to_y != 0;
+        let pool_key = accounts.pool.key();
+
+        // 1. 验证过期时间与参数
+        let clock = Clock::get()?;
+        if clock.unix_timestamp > data.expiration {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if data.amount == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let (amount_in, amount_out, bump, mint_x, mint_y, tick_spacing) = {
+            let mut pool = ClmmPool::load_mut(accounts.pool)?;
+            if pool.vault_x.ne(accounts.vault_x.key()) || pool.vault_y.ne(accounts.vault_y.key()) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let sqrt_price_limit = match (data.sqrt_price_limit, x_to_y) {
+                (0, true) => MIN_SQRT_PRICE,
+                (0, false) => MAX_SQRT_PRICE,
+                (limit, _) => limit,
+            };
+            let limit_ok = if x_to_y {
+                sqrt_price_limit < pool.sqrt_price() && sqrt_price_limit >= MIN_SQRT_PRICE
+            } else {
+                sqrt_price_limit > pool.sqrt_price() && sqrt_price_limit <= MAX_SQRT_PRICE
+            };
+            if !limit_ok {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            let tick_spacing = pool.tick_spacing();
+            let mut sqrt_price = pool.sqrt_price();
+            let mut tick = pool.tick_current();
+            let mut liquidity = pool.liquidity();
+            let mut fee_growth_x = pool.fee_growth_global_x();
+            let mut fee_growth_y = pool.fee_growth_global_y();
+            let mut remaining = data.amount as u128;
+            let mut amount_out = 0u128;
+
+            // 2. 逐个区间推进价格，直到输入耗尽或到达价格限制
+            while remaining > 0 && sqrt_price != sqrt_price_limit {
+                // 2.1 在当前位图字内找下一个已初始化的 tick
+                let search_tick = if x_to_y {
+                    tick
+                } else {
+                    tick + tick_spacing as i32
+                };
+                let (word_pos, bit) = bitmap_position(search_tick, tick_spacing);
+                let bits = self.bitmap_word(pool_key, word_pos)?;
+                let (next_bit, initialized) = next_initialized_bit(&bits, bit, x_to_y);
+                let tick_next =
+                    tick_from_bitmap(word_pos, next_bit, tick_spacing).clamp(MIN_TICK, MAX_TICK);
+
+                // 2.2 在本区间内 swap
+                let sqrt_price_next = sqrt_price_at_tick(tick_next)?;
+                let target = if x_to_y {
+                    sqrt_price_next.max(sqrt_price_limit)
+                } else {
+                    sqrt_price_next.min(sqrt_price_limit)
+                };
+                let step = compute_swap_step(
+                    sqrt_price,
+                    target,
+                    liquidity,
+                    remaining,
+                    pool.fee(),
+                    x_to_y,
+                )?;
+                remaining -= step.amount_in + step.fee_amount;
+                amount_out += step.amount_out;
+
+                // 2.3 手续费按活跃流动性分摊到 fee_growth_global
+                if liquidity > 0 {
+                    let growth = mul_div(step.fee_amount, Q64, liquidity, false)?;
+                    if x_to_y {
+                        fee_growth_x = fee_growth_x.wrapping_add(growth);
+                    } else {
+                        fee_growth_y = fee_growth_y.wrapping_add(growth);
+                    }
+                }
+
+                // 2.4 到达区间边界则穿越 tick，更新活跃流动性
+                if step.sqrt_price_next == sqrt_price_next {
+                    if initialized {
+                        let mut tick_state =
+                            TickState::load_mut(self.tick_account(pool_key, tick_next)?)?;
+                        let net = tick_state.cross(fee_growth_x, fee_growth_y);
+                        let net = if x_to_y { -net } else { net };
+                        liquidity = liquidity
+                            .checked_add_signed(net)
+                            .ok_or(ProgramError::ArithmeticOverflow)?;
+                    }
+                    tick = if x_to_y { tick_next - 1 } else { tick_next };
+                } else {
+                    tick = tick_at_sqrt_price(step.sqrt_price_next)?;
+                }
+                sqrt_price = step.sqrt_price_next;
+            }
+
+            pool.set_price(sqrt_price, tick);
+            pool.set_liquidity(liquidity);
+            pool.set_fee_growth_global(fee_growth_x, fee_growth_y);
+
+            (
+                data.amount as u128 - remaining,
+                amount_out,
+                pool.bump,
+                pool.mint_x,
+                pool.mint_y,
+                tick_spacing,
+            )
+        };
+
+        // 3. 滑点保护检查
+        let amount_out = u64::try_from(amount_out).map_err(|_| ProgramError::ArithmeticOverflow)?;
+        if amount_out == 0 || amount_out < data.min {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 4. 执行原子转账 (pool PDA 签名从金库转出)
+        let spacing_binding = tick_spacing.to_le_bytes();
+        let pool_seeds = [
+            Seed::from(CLMM_POOL_SEED),
+            Seed::from(&mint_x),
+            Seed::from(&mint_y),
+            Seed::from(&spacing_binding),
+            Seed::from(&bump),
+        ];
+        let (user_in, vault_in, vault_out, user_out) = if x_to_y {
+            (
+                accounts.user_x_ata,
+                accounts.vault_x,
+                accounts.vault_y,
+                accounts.user_y_ata,
+            )
+        } else {
+            (
+                accounts.user_y_ata,
+                accounts.vault_y,
+                accounts.vault_x,
+                accounts.user_x_ata,
+            )
+        };
+
+        Transfer {
+            from: user_in,
+            to: vault_in,
+            authority: accounts.user,
+            amount: amount_in as u64,
+        }
+        .invoke()?;
+
+        Transfer {
+            from: vault_out,
+            to: user_out,
+            authority: accounts.pool,
+            amount: amount_out,
+        }
+        .invoke_signed(&[Signer::from(&pool_seeds)])?;
+
+        Ok(())
+    }
+}
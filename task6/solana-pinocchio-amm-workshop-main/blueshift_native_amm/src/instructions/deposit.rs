@@ -1,17 +1,24 @@
-use constant_product_curve::ConstantProduct;
+use blueshift_events::{Event, LiquidityDeposited};
+use blueshift_log::bslog;
 use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
-    instruction::{Seed, Signer},
     program_error::ProgramError,
     sysvars::{Sysvar, clock::Clock},
 };
 use pinocchio_token::{
     instructions::{MintTo, Transfer},
-    state::{Mint, TokenAccount},
+    state::Mint,
 };
 
-use crate::{CONFIG_SEED, Config};
+use super::{
+    lp_lock::{LockAccounts, RewardAccounts},
+    multi_helpers::check_vault,
+};
+use crate::{
+    Config, LpLock,
+    curve::{Curve, PoolCurve},
+};
 
 pub struct DepositAccounts<'a> {
     pub user: &'a AccountInfo,
@@ -23,6 +30,9 @@ pub struct DepositAccounts<'a> {
     pub user_lp_ata: &'a AccountInfo,
     pub config: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
+    // 可选：锁仓存款时追加 lp_lock, lock_vault, reward_vault, reward_authority, system_program
+    pub lock: Option<LockAccounts<'a>>,
+    pub rewards: Option<RewardAccounts<'a>>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
@@ -40,6 +50,20 @@ impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
             user_lp_ata: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
             config: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
             token_program: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            lock: match (account_iter.next(), account_iter.next()) {
+                (Some(lp_lock), Some(lock_vault)) => Some(LockAccounts {
+                    lp_lock,
+                    lock_vault,
+                }),
+                _ => None,
+            },
+            rewards: match (account_iter.next(), account_iter.next()) {
+                (Some(reward_vault), Some(reward_authority)) => Some(RewardAccounts {
+                    reward_vault,
+                    reward_authority,
+                }),
+                _ => None,
+            },
         })
     }
 }
@@ -53,6 +77,17 @@ pub struct DepositInstructionData {
     pub expiration: i64,
 }
 
+/// 可选的锁仓时长（秒），跟在 DepositInstructionData 之后；缺省或为 0 表示不锁仓
+fn lock_duration(data: &[u8]) -> Result<i64, ProgramError> {
+    match data.get(size_of::<DepositInstructionData>()..) {
+        None | Some([]) => Ok(0),
+        Some(bytes) => bytes
+            .try_into()
+            .map(i64::from_le_bytes)
+            .map_err(|_| ProgramError::InvalidInstructionData),
+    }
+}
+
 impl<'a> TryFrom<&'a [u8]> for DepositInstructionData {
     type Error = ProgramError;
 
@@ -68,6 +103,7 @@ impl<'a> TryFrom<&'a [u8]> for DepositInstructionData {
 pub struct Deposit<'a> {
     pub accounts: DepositAccounts<'a>,
     pub instruction_data: DepositInstructionData,
+    pub lock_duration: i64,
 }
 
 impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Deposit<'a> {
@@ -76,11 +112,19 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Deposit<'a> {
     fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
         let accounts = DepositAccounts::try_from(accounts)?;
         let instruction_data = DepositInstructionData::try_from(data)?;
+        let lock_duration = lock_duration(data)?;
+        if lock_duration != 0 {
+            LpLock::boost_for(lock_duration)?;
+            if accounts.lock.is_none() || accounts.rewards.is_none() {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+        }
 
         // Return the initialized struct
         Ok(Self {
             accounts,
             instruction_data,
+            lock_duration,
         })
     }
 }
@@ -105,26 +149,25 @@ impl<'a> Deposit<'a> {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        // 3. 反序列化代币账户信息 (使用 Pinocchio-token 提供的 unchecked 方法提升性能)
+        if data.amount == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 3. 金库必须由 config PDA 持有且 mint 匹配；比例按记账储备计算，直接转入金库的代币不计入
         let mint_lp = unsafe { Mint::from_account_info_unchecked(accounts.mint_lp)? };
-        let vault_x = unsafe { TokenAccount::from_account_info_unchecked(accounts.vault_x)? };
-        let vault_y = unsafe { TokenAccount::from_account_info_unchecked(accounts.vault_y)? };
+        check_vault(accounts.vault_x, accounts.config, config.mint_x())?;
+        check_vault(accounts.vault_y, accounts.config, config.mint_y())?;
+        let (reserve_x, reserve_y) = (config.reserve_x(), config.reserve_y());
 
         // 4. 计算存款金额 (x, y)
         let (x, y) = if mint_lp.supply() == 0 {
-            // 初始流动性：使用用户指定的 max 值
+            // 初始流动性：没有既有比例，由用户给出的数量决定初始价格
             (data.max_x, data.max_y)
         } else {
-            // 后续流动性：基于比例计算
-            let amounts = ConstantProduct::xy_deposit_amounts_from_l(
-                vault_x.amount(),
-                vault_y.amount(),
-                mint_lp.supply(),
-                data.amount,
-                6, // 假设 LP 小数位为 6
-            )
-            .map_err(|_| ProgramError::ArithmeticOverflow)?;
-            (amounts.x, amounts.y)
+            // 后续流动性：按请求的 LP 占总供应量的比例从当前储备计算，向上取整，
+            // max_x / max_y 只作为保护用户的上限
+            PoolCurve::from_config(&config, reserve_x, reserve_y)
+                .deposit_amounts(data.amount, mint_lp.supply())?
         };
 
         // 5. 滑点保护检查
@@ -149,30 +192,58 @@ impl<'a> Deposit<'a> {
         }
         .invoke()?;
 
-        // 7. 签署并执行 MintTo (Config PDA -> 用户)
-        let seed_binding = config.seed().to_le_bytes();
-        let mint_x = config.mint_x(); // Returns &Pubkey
-        let mint_y = config.mint_y(); // Returns &Pubkey
-        let bump = config.config_bump(); // Returns [u8; 1]
-
-        // 2. Now create the seeds using references to those stable variables
-        let config_seeds = [
-            Seed::from(CONFIG_SEED),
-            Seed::from(&seed_binding),
-            Seed::from(mint_x.as_ref()),
-            Seed::from(mint_y.as_ref()),
-            Seed::from(&bump), // Reference to the local variable 'bump'
-        ];
-        let signer = Signer::from(&config_seeds);
+        // 7. 锁仓存款：LP 铸造到 config 持有的 lock vault，按加成从奖励池划入奖励 LP，
+        //    两者都记入用户的锁仓记录；请求锁仓却缺少锁仓账户时报错，不会退化成普通存款
+        let recipient = if self.lock_duration == 0 {
+            accounts.user_lp_ata
+        } else {
+            let (Some(lock), Some(rewards)) = (&accounts.lock, &accounts.rewards) else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            lock.check_vault(accounts.config, accounts.mint_lp)?;
+            let mut lp_lock = lock.load_or_create(accounts.user, accounts.config)?;
+            lp_lock.lock(data.amount, clock.unix_timestamp, self.lock_duration)?;
+            let bonus = rewards.pay_bonus(
+                accounts.config,
+                accounts.mint_lp,
+                lock.lock_vault,
+                data.amount,
+                lp_lock.boost_bps(),
+            )?;
+            lp_lock.credit(bonus)?;
+            lock.lock_vault
+        };
+
+        // 8. 签署并执行 MintTo (Config PDA -> 用户 / lock vault)
+        let config_seeds = config.signer_seeds();
+        let signer = config_seeds.signer();
 
         MintTo {
             mint: accounts.mint_lp,
-            account: accounts.user_lp_ata,
+            account: recipient,
             mint_authority: accounts.config,
             amount: data.amount,
         }
         .invoke_signed(&[signer])?;
 
+        // 9. 更新记账储备
+        drop(config);
+        Config::load_mut(accounts.config)?.set_reserves(
+            reserve_x.checked_add(x).ok_or(ProgramError::ArithmeticOverflow)?,
+            reserve_y.checked_add(y).ok_or(ProgramError::ArithmeticOverflow)?,
+        );
+
+        // 10. 发出事件（锁仓存款同样记在用户名下）
+        LiquidityDeposited {
+            config: *accounts.config.key(),
+            user: *accounts.user.key(),
+            lp_amount: data.amount,
+            amount_x: x,
+            amount_y: y,
+        }
+        .emit();
+        bslog!("deposit", "lp={} x={} y={}", data.amount, x, y);
+
         Ok(())
     }
 }
@@ -0,0 +1,172 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::{
+    instructions::{MintTo, Transfer},
+    state::Mint,
+};
+
+use super::multi_helpers::{check_vault, read_u64};
+use crate::{
+    AmmState, MAX_POOL_TOKENS, MULTI_CONFIG_SEED, MultiConfig, curve::proportional_amount,
+};
+
+/// 账户顺序：固定账户之后依次是 `token_count` 个金库，再是 `token_count` 个用户代币账户，
+/// 顺序与 `MultiConfig::mints()` 一致。
+pub struct DepositMultiAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub mint_lp: &'a AccountInfo,
+    pub user_lp_ata: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub remaining: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for DepositMultiAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, mint_lp, user_lp_ata, config, token_program, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Ok(Self {
+            user,
+            mint_lp,
+            user_lp_ata,
+            config,
+            token_program,
+            remaining,
+        })
+    }
+}
+
+/// 定长头部，后面紧跟 `token_count` 个小端 u64 的 max 数组。
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct DepositMultiInstructionData {
+    pub amount: u64,
+    pub expiration: i64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for DepositMultiInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() < size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+pub struct DepositMulti<'a> {
+    pub accounts: DepositMultiAccounts<'a>,
+    pub instruction_data: DepositMultiInstructionData,
+    pub max_amounts: &'a [u8],
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for DepositMulti<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = DepositMultiAccounts::try_from(accounts)?;
+        let instruction_data = DepositMultiInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            max_amounts: &data[size_of::<DepositMultiInstructionData>()..],
+        })
+    }
+}
+
+impl<'a> DepositMulti<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &5;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let data = &self.instruction_data;
+
+        // 1. 过期检查
+        let clock = Clock::get()?;
+        if clock.unix_timestamp > data.expiration {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 2. 加载 MultiConfig 并验证状态
+        let config = MultiConfig::load(accounts.config)?;
+        if config.state() != AmmState::Initialized as u8 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mints = config.mints();
+        let count = mints.len();
+        if accounts.remaining.len() != count * 2
+            || self.max_amounts.len() != count * size_of::<u64>()
+        {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (vaults, user_atas) = accounts.remaining.split_at(count);
+
+        // 3. 按 LP 份额计算每个代币的存入量并转账 (用户 -> 金库)；比例按记账储备计算
+        let supply = unsafe { Mint::from_account_info_unchecked(accounts.mint_lp)? }.supply();
+        let mut reserves = [0u64; MAX_POOL_TOKENS];
+        for i in 0..count {
+            check_vault(&vaults[i], accounts.config, &mints[i])?;
+            let reserve = config.reserve(i);
+            let max = read_u64(self.max_amounts, i)?;
+
+            let amount = if supply == 0 {
+                // 初始流动性：使用用户指定的 max 值，决定初始价格
+                if max == 0 {
+                    return Err(ProgramError::InvalidArgument);
+                }
+                max
+            } else {
+                // 向上取整，保证存入不少于 LP 份额对应的资产
+                proportional_amount(reserve, data.amount, supply, true)?
+            };
+
+            // 滑点保护检查
+            if amount > max {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            Transfer {
+                from: &user_atas[i],
+                to: &vaults[i],
+                authority: accounts.user,
+                amount,
+            }
+            .invoke()?;
+
+            reserves[i] = reserve.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        // 4. 签署并执行 MintTo (MultiConfig PDA -> 用户)
+        let seed_binding = config.seed().to_le_bytes();
+        let bump = config.config_bump();
+        let config_seeds =
+            [Seed::from(MULTI_CONFIG_SEED), Seed::from(&seed_binding), Seed::from(&bump)];
+
+        MintTo {
+            mint: accounts.mint_lp,
+            account: accounts.user_lp_ata,
+            mint_authority: accounts.config,
+            amount: data.amount,
+        }
+        .invoke_signed(&[Signer::from(&config_seeds)])?;
+
+        // 5. 更新记账储备
+        drop(config);
+        let mut config = MultiConfig::load_mut(accounts.config)?;
+        for (i, reserve) in reserves[..count].iter().enumerate() {
+            config.set_reserve(i, *reserve);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use blueshift_common::{AccountCheck, SignerAccount};
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::Config;
+
+/*
+    timelock 到期后由 authority 执行待生效的参数变更，执行后提案清空。
+*/
+
+pub struct ExecuteUpdateAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ExecuteUpdateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        SignerAccount::check(authority)?;
+        Ok(Self { authority, config })
+    }
+}
+
+pub struct ExecuteUpdate<'a> {
+    pub accounts: ExecuteUpdateAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ExecuteUpdate<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = ExecuteUpdateAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ExecuteUpdate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &16;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+
+        // 1. 只有当前 authority 可以执行
+        let mut config = Config::load_mut(accounts.config)?;
+        if config.has_authority() != Some(*accounts.authority.key()) {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+
+        // 2. 延迟未到期时拒绝执行
+        let clock = Clock::get()?;
+        config.execute_update(clock.unix_timestamp, clock.slot)
+    }
+}
@@ -1,5 +1,8 @@
-use std::mem::MaybeUninit;
+use core::mem::MaybeUninit;
 
+use blueshift_common::{AccountCheck, SignerAccount};
+use blueshift_events::{Event, PoolInitialized};
+use blueshift_log::bslog;
 use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
@@ -7,17 +10,33 @@ use pinocchio::{
     program_error::ProgramError,
     sysvars::{Sysvar, rent::Rent},
 };
+use pinocchio_associated_token_account::instructions::Create;
 use pinocchio_system::instructions::CreateAccount;
 use pinocchio_token::{instructions::InitializeMint2, state::Mint};
 
-use crate::{CONFIG_SEED, Config, MINT_LP_SEED};
+use crate::{CONFIG_SEED, Config, DEFAULT_LP_DECIMALS, MINT_LP_SEED, WEIGHT_ONE};
 
 /// 初始化 Config 账户，并存储 AMM 正常运行所需的所有信息。
 /// 创建 mint_lp 铸币账户，并将 mint_authority 分配给 config 账户。
+///
+/// payer 只负责支付租金，与池子的 authority（来自指令数据）无关，
+/// 部署服务可以代 DAO 创建池子而无需持有管理密钥。
 pub struct InitializeAccounts<'a> {
-    pub initializer: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
     pub mint_lp: &'a AccountInfo,
     pub config: &'a AccountInfo,
+    // 可选：同时由 payer 出资创建两个金库 ATA
+    pub vaults: Option<VaultAccounts<'a>>,
+}
+
+/// 创建金库 ATA 所需的账户：[mint_x, mint_y, vault_x, vault_y, system_program, token_program]
+pub struct VaultAccounts<'a> {
+    pub mint_x: &'a AccountInfo,
+    pub mint_y: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
@@ -26,14 +45,30 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
         let mut accounts_iter = accounts.iter();
 
-        let initializer = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let payer = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
         let mint_lp = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
         let config = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
 
+        // 追加满 6 个账户时才创建金库；只追加 system/token program 的旧调用方式保持不变
+        let vaults = match accounts_iter.as_slice() {
+            [mint_x, mint_y, vault_x, vault_y, system_program, token_program, ..] => {
+                Some(VaultAccounts {
+                    mint_x,
+                    mint_y,
+                    vault_x,
+                    vault_y,
+                    system_program,
+                    token_program,
+                })
+            }
+            _ => None,
+        };
+
         Ok(Self {
-            initializer,
+            payer,
             mint_lp,
             config,
+            vaults,
         })
     }
 }
@@ -46,6 +81,12 @@ pub struct InitializeInstructionData {
     pub mint_y: [u8; 32],
     pub config_bump: [u8; 1],
     pub lp_bump: [u8; 1],
+    /// mint_x 的权重（基点），传 0 表示 50/50 的普通恒定乘积池
+    pub weight_x: u16,
+    /// 1: 规范池（PDA 由排序后的 mint 对派生，忽略 seed）；0: 非规范的 seeded 池
+    pub canonical: u8,
+    /// LP mint 的小数位数，传 0 表示默认的 6 位
+    pub lp_decimals: u8,
     pub authority: [u8; 32],
 }
 
@@ -60,12 +101,12 @@ impl TryFrom<&[u8]> for InitializeInstructionData {
         const INITIALIZE_DATA_LEN: usize =
             INITIALIZE_DATA_LEN_WITH_AUTHORITY - size_of::<[u8; 32]>();
 
-        match data.len() {
+        let instruction_data = match data.len() {
             // 数据完整
             // 如果数据长度刚好等于结构体大小，直接从内存指针读取。
-            INITIALIZE_DATA_LEN_WITH_AUTHORITY => {
-                Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
-            }
+            INITIALIZE_DATA_LEN_WITH_AUTHORITY => unsafe {
+                (data.as_ptr() as *const Self).read_unaligned()
+            },
             // 数据精简，手动补齐
             INITIALIZE_DATA_LEN => {
                 // 1. 在栈上开辟一块未初始化的内存区域，大小等于完整结构体
@@ -84,11 +125,19 @@ impl TryFrom<&[u8]> for InitializeInstructionData {
 
                     // 4. 现在内存里已经有一个完美的“模拟”数据流了，直接读成结构体
                     // Now transmute to the struct
-                    Ok((raw.as_ptr() as *const Self).read_unaligned())
+                    (raw.as_ptr() as *const Self).read_unaligned()
                 }
             }
-            _ => Err(ProgramError::InvalidInstructionData),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        // 5. mint 必须按字节序严格递增，同一对 mint 不能以交换后的顺序重复初始化，
+        //    客户端也可以确定性地推导地址
+        if instruction_data.mint_x >= instruction_data.mint_y {
+            return Err(ProgramError::InvalidInstructionData);
         }
+
+        Ok(instruction_data)
     }
 }
 
@@ -120,20 +169,42 @@ impl<'a> Initialize<'a> {
         let accounts = &self.accounts;
         let rent = Rent::get()?;
 
+        SignerAccount::check(accounts.payer)?;
+
         // --- 1. 创建 Config 账户 ---
         let config_lamports = rent.minimum_balance(Config::LEN); // 动态计算
-        let seed_binding = instruction_data.seed.to_le_bytes();
-        let config_seeds = [
+        let canonical = instruction_data.canonical == 1;
+        let seed = if canonical { 0 } else { instruction_data.seed };
+        if canonical {
+            // 规范池：只能使用规范 bump，保证每个 mint 对只有一个池
+            let (address, bump) =
+                Config::find_canonical_address(&instruction_data.mint_x, &instruction_data.mint_y);
+            if address.ne(accounts.config.key()) || [bump] != instruction_data.config_bump {
+                return Err(ProgramError::InvalidSeeds);
+            }
+        }
+        let seed_binding = seed.to_le_bytes();
+        let canonical_seeds = [
+            Seed::from(CONFIG_SEED),
+            Seed::from(instruction_data.mint_x.as_ref()),
+            Seed::from(instruction_data.mint_y.as_ref()),
+            Seed::from(&instruction_data.config_bump),
+        ];
+        let seeded_seeds = [
             Seed::from(CONFIG_SEED),
             Seed::from(seed_binding.as_ref()),
             Seed::from(instruction_data.mint_x.as_ref()),
             Seed::from(instruction_data.mint_y.as_ref()),
             Seed::from(&instruction_data.config_bump),
         ];
-        let config_signer = Signer::from(&config_seeds);
+        let config_signer = if canonical {
+            Signer::from(&canonical_seeds)
+        } else {
+            Signer::from(&seeded_seeds)
+        };
         // 计算 Config 账户所需的租金空间 (使用我们在 state.rs 定义的 LEN)
         CreateAccount {
-            from: accounts.initializer,
+            from: accounts.payer,
             to: accounts.config,
             lamports: config_lamports, // 实际开发中应根据 Rent 计算，这里简化
             space: Config::LEN as u64,
@@ -145,13 +216,23 @@ impl<'a> Initialize<'a> {
         // 获取账户内存的可变引用
         let config_account = unsafe { Config::load_mut_unchecked(accounts.config)? };
         config_account.set_inner(
-            instruction_data.seed,
+            seed,
             instruction_data.authority, // 将 [u8;32] 转为 Pubkey
             instruction_data.mint_x,
             instruction_data.mint_y,
             instruction_data.fee,
+            match instruction_data.weight_x {
+                0 => WEIGHT_ONE / 2,
+                weight_x => weight_x,
+            },
             instruction_data.config_bump,
+            canonical,
         )?;
+        let lp_decimals = match instruction_data.lp_decimals {
+            0 => DEFAULT_LP_DECIMALS,
+            lp_decimals => lp_decimals,
+        };
+        config_account.set_lp_decimals(lp_decimals);
 
         // --- 3. 创建 Mint LP 账户 ---
         let mint_space = size_of::<Mint>();
@@ -164,7 +245,7 @@ impl<'a> Initialize<'a> {
 
         // Mint 账户固定大小为 82 字节
         CreateAccount {
-            from: accounts.initializer,
+            from: accounts.payer,
             to: accounts.mint_lp,
             lamports: mint_lamports, // 同样应根据 Rent 计算
             space: mint_space as u64,
@@ -175,12 +256,45 @@ impl<'a> Initialize<'a> {
         // --- 4. 初始化 Mint LP (设置 Mint Authority) ---
         InitializeMint2 {
             mint: accounts.mint_lp,
-            decimals: 6,                           // 通常 LP 代币使用 6 位小数
+            decimals: lp_decimals, // 与底层资产匹配，例如 9 位小数的资产对
             mint_authority: accounts.config.key(), // 权限交给 Config PDA
             freeze_authority: None,
         }
         .invoke()?;
 
+        // --- 5. 可选：由 payer 出资创建 config 持有的金库 ATA ---
+        if let Some(vaults) = &accounts.vaults {
+            if vaults.mint_x.key().ne(&instruction_data.mint_x)
+                || vaults.mint_y.key().ne(&instruction_data.mint_y)
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            for (vault, mint) in [(vaults.vault_x, vaults.mint_x), (vaults.vault_y, vaults.mint_y)]
+            {
+                Create {
+                    funding_account: accounts.payer,
+                    account: vault,
+                    wallet: accounts.config,
+                    mint,
+                    system_program: vaults.system_program,
+                    token_program: vaults.token_program,
+                }
+                .invoke()?;
+            }
+        }
+
+        // --- 6. 发出事件（规范池的 seed 记为 0） ---
+        PoolInitialized {
+            config: *accounts.config.key(),
+            authority: instruction_data.authority,
+            mint_x: instruction_data.mint_x,
+            mint_y: instruction_data.mint_y,
+            seed,
+            fee: instruction_data.fee,
+        }
+        .emit();
+        bslog!("initialize", "seed={} fee={}", seed, instruction_data.fee);
+
         Ok(())
     }
 }
@@ -0,0 +1,164 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{Sysvar, rent::Rent},
+};
+use pinocchio_system::instructions::CreateAccount;
+use pinocchio_token::{instructions::InitializeMint2, state::Mint};
+
+use super::multi_helpers::pubkeys_from_bytes;
+use crate::{MAX_POOL_TOKENS, MINT_LP_SEED, MULTI_CONFIG_SEED, MultiConfig};
+
+/// 初始化多资产池的 MultiConfig 账户，并创建 mint_lp。
+/// 金库（每个 mint 一个、owner 为 config PDA 的代币账户）由客户端预先创建。
+pub struct InitializeMultiAccounts<'a> {
+    pub initializer: &'a AccountInfo,
+    pub mint_lp: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitializeMultiAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let mut accounts_iter = accounts.iter();
+
+        let initializer = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let mint_lp = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let config = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        Ok(Self {
+            initializer,
+            mint_lp,
+            config,
+        })
+    }
+}
+
+/// 定长头部，后面紧跟 `token_count` 个 32 字节的 mint 地址。
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct InitializeMultiInstructionData {
+    pub seed: u64,
+    pub fee: u16,
+    pub config_bump: [u8; 1],
+    pub lp_bump: [u8; 1],
+    pub authority: [u8; 32],
+    pub token_count: u8,
+}
+
+impl TryFrom<&[u8]> for InitializeMultiInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() < size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+pub struct InitializeMulti<'a> {
+    pub accounts: InitializeMultiAccounts<'a>,
+    pub instruction_data: InitializeMultiInstructionData,
+    pub mints: &'a [Pubkey],
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for InitializeMulti<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = InitializeMultiAccounts::try_from(accounts)?;
+        let instruction_data = InitializeMultiInstructionData::try_from(data)?;
+
+        let token_count = instruction_data.token_count as usize;
+        if !(2..=MAX_POOL_TOKENS).contains(&token_count) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let mints = pubkeys_from_bytes(
+            &data[size_of::<InitializeMultiInstructionData>()..],
+            token_count,
+        )?;
+
+        // 同一个 mint 不能出现两次，否则两个金库会共享同一种资产
+        for (i, mint) in mints.iter().enumerate() {
+            if mints[i + 1..].contains(mint) {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            mints,
+        })
+    }
+}
+
+impl<'a> InitializeMulti<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &4;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let instruction_data = &self.instruction_data;
+        let accounts = &self.accounts;
+        let rent = Rent::get()?;
+
+        // --- 1. 创建 MultiConfig 账户 ---
+        let seed_binding = instruction_data.seed.to_le_bytes();
+        let config_bump = instruction_data.config_bump;
+        let config_seeds = [
+            Seed::from(MULTI_CONFIG_SEED),
+            Seed::from(seed_binding.as_ref()),
+            Seed::from(&config_bump),
+        ];
+        CreateAccount {
+            from: accounts.initializer,
+            to: accounts.config,
+            lamports: rent.minimum_balance(MultiConfig::LEN),
+            space: MultiConfig::LEN as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Signer::from(&config_seeds)])?;
+
+        // --- 2. 初始化 MultiConfig 数据 ---
+        let config_account = unsafe { MultiConfig::load_mut_unchecked(accounts.config)? };
+        config_account.set_inner(
+            instruction_data.seed,
+            instruction_data.authority,
+            self.mints,
+            instruction_data.fee,
+            config_bump,
+        )?;
+
+        // --- 3. 创建 Mint LP 账户 (种子与双币池一致，以 config 地址区分) ---
+        let mint_space = size_of::<Mint>();
+        let lp_bump = instruction_data.lp_bump;
+        let mint_lp_seeds = [
+            Seed::from(MINT_LP_SEED),
+            Seed::from(accounts.config.key().as_ref()),
+            Seed::from(&lp_bump),
+        ];
+        CreateAccount {
+            from: accounts.initializer,
+            to: accounts.mint_lp,
+            lamports: rent.minimum_balance(mint_space),
+            space: mint_space as u64,
+            owner: &pinocchio_token::ID,
+        }
+        .invoke_signed(&[Signer::from(&mint_lp_seeds)])?;
+
+        // --- 4. 初始化 Mint LP ---
+        InitializeMint2 {
+            mint: accounts.mint_lp,
+            decimals: 6,
+            mint_authority: accounts.config.key(),
+            freeze_authority: None,
+        }
+        .invoke()?;
+
+        Ok(())
+    }
+}
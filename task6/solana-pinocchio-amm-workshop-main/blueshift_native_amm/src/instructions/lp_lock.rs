@@ -0,0 +1,129 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::{AccountInfo, RefMut},
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{Sysvar, rent::Rent},
+};
+use pinocchio_system::instructions::CreateAccount;
+use pinocchio_token::{instructions::Transfer, state::TokenAccount};
+
+use crate::{LP_LOCK_SEED, LP_REWARDS_SEED, LpLock};
+
+/// 锁仓相关的可选账户，位于 deposit / withdraw 固定账户之后
+pub struct LockAccounts<'a> {
+    pub lp_lock: &'a AccountInfo,
+    pub lock_vault: &'a AccountInfo,
+}
+
+impl<'a> LockAccounts<'a> {
+    /// 校验 lock vault 是由 config PDA 持有的 LP 代币账户
+    pub(crate) fn check_vault(&self, config: &AccountInfo, mint_lp: &AccountInfo) -> ProgramResult {
+        let vault = TokenAccount::from_account_info(self.lock_vault)?;
+        if vault.owner().ne(config.key()) || vault.mint().ne(mint_lp.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    /// 加载用户的锁仓记录；首次锁仓时在 [LP_LOCK_SEED, config, user] 上创建
+    pub(crate) fn load_or_create(
+        &self,
+        user: &AccountInfo,
+        config: &AccountInfo,
+    ) -> Result<RefMut<'a, LpLock>, ProgramError> {
+        if self.lp_lock.data_is_empty() {
+            let (address, bump) = pdas::amm_lp_lock_pda(&crate::ID, config.key(), user.key());
+            if address.ne(self.lp_lock.key()) {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            let bump = [bump];
+            let seeds = [
+                Seed::from(LP_LOCK_SEED),
+                Seed::from(config.key()),
+                Seed::from(user.key()),
+                Seed::from(&bump),
+            ];
+            CreateAccount {
+                from: user,
+                to: self.lp_lock,
+                lamports: Rent::get()?.minimum_balance(LpLock::LEN),
+                space: LpLock::LEN as u64,
+                owner: &crate::ID,
+            }
+            .invoke_signed(&[Signer::from(&seeds)])?;
+
+            let mut lock = LpLock::load_mut(self.lp_lock)?;
+            lock.set_inner(*user.key(), *config.key(), bump);
+            return Ok(lock);
+        }
+        self.load(user, config)
+    }
+
+    /// 加载已存在的锁仓记录并校验归属
+    pub(crate) fn load(
+        &self,
+        user: &AccountInfo,
+        config: &AccountInfo,
+    ) -> Result<RefMut<'a, LpLock>, ProgramError> {
+        let lock = LpLock::load_mut(self.lp_lock)?;
+        if lock.owner().ne(user.key()) || lock.config().ne(config.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(lock)
+    }
+}
+
+/// 锁仓奖励池账户，锁仓存款时跟在 lock vault 之后。
+///
+/// 奖励池是 PDA [LP_REWARDS_SEED, config] 持有的 LP 代币账户，由协议方（或任何人）直接转入 LP 补充；
+/// 锁仓加成只从这里支付，不会稀释其他 LP 的份额
+pub struct RewardAccounts<'a> {
+    pub reward_vault: &'a AccountInfo,
+    pub reward_authority: &'a AccountInfo,
+}
+
+impl RewardAccounts<'_> {
+    /// 按加成把奖励 LP 从奖励池划入 lock vault，奖励池不足时只划出剩余部分；返回实际划出的数量
+    pub(crate) fn pay_bonus(
+        &self,
+        config: &AccountInfo,
+        mint_lp: &AccountInfo,
+        lock_vault: &AccountInfo,
+        amount: u64,
+        boost_bps: u16,
+    ) -> Result<u64, ProgramError> {
+        let (authority, bump) = pdas::amm_lp_rewards_pda(&crate::ID, config.key());
+        if authority.ne(self.reward_authority.key()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let available = {
+            let vault = TokenAccount::from_account_info(self.reward_vault)?;
+            if vault.owner().ne(&authority) || vault.mint().ne(mint_lp.key()) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            vault.amount()
+        };
+
+        let bonus = lock_bonus(amount, boost_bps).min(available);
+        if bonus == 0 {
+            return Ok(0);
+        }
+        let bump = [bump];
+        let seeds = [Seed::from(LP_REWARDS_SEED), Seed::from(config.key()), Seed::from(&bump)];
+        Transfer {
+            from: self.reward_vault,
+            to: lock_vault,
+            authority: self.reward_authority,
+            amount: bonus,
+        }
+        .invoke_signed(&[Signer::from(&seeds)])?;
+        Ok(bonus)
+    }
+}
+
+/// 锁仓 `amount` 个 LP 应得的奖励：`amount * boost_bps / 10000`，向下取整
+pub(crate) fn lock_bonus(amount: u64, boost_bps: u16) -> u64 {
+    // boost_bps 不超过 10000，结果不会超过 amount
+    (amount as u128 * boost_bps as u128 / 10_000) as u64
+}
@@ -1,9 +1,33 @@
+pub mod clmm;
 pub mod deposit;
+pub mod deposit_multi;
+pub mod execute_update;
 pub mod initialize;
+pub mod initialize_multi;
+pub mod lp_lock;
+mod multi_helpers;
+pub mod propose_update;
+pub mod skim;
 pub mod swap;
+pub mod swap_batch;
+pub mod swap_multi;
+pub mod sync_reserves;
+pub mod version;
 pub mod withdraw;
+pub mod withdraw_multi;
 
+pub use clmm::*;
 pub use deposit::*;
+pub use deposit_multi::*;
+pub use execute_update::*;
 pub use initialize::*;
+pub use initialize_multi::*;
+pub use propose_update::*;
+pub use skim::*;
 pub use swap::*;
+pub use swap_batch::*;
+pub use swap_multi::*;
+pub use sync_reserves::*;
+pub use version::*;
 pub use withdraw::*;
+pub use withdraw_multi::*;
@@ -0,0 +1,41 @@
+use blueshift_common::{AssociatedTokenAccount, TOKEN_PROGRAM_ID, read_u64_le};
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey,
+};
+use pinocchio_token::state::TokenAccount;
+
+/// 将尾部 `[u8; 32] * n` 字节切片解释为 Pubkey 切片（Pubkey 对齐为 1）。
+pub(crate) fn pubkeys_from_bytes(data: &[u8], count: usize) -> Result<&[Pubkey], ProgramError> {
+    if data.len() != count * size_of::<Pubkey>() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok(unsafe { core::slice::from_raw_parts(data.as_ptr() as *const Pubkey, count) })
+}
+
+/// 读取第 `index` 个小端 u64 (用于每个代币的 max/min 数组)。
+pub(crate) fn read_u64(data: &[u8], index: usize) -> Result<u64, ProgramError> {
+    read_u64_le(data, index * size_of::<u64>()).ok_or(ProgramError::InvalidInstructionData)
+}
+
+/// 校验金库是 config PDA 持有的 mint ATA，且账户内的 owner / mint 一致，返回金库余额。
+pub(crate) fn vault_amount(
+    vault: &AccountInfo,
+    config: &AccountInfo,
+    mint: &Pubkey,
+) -> Result<u64, ProgramError> {
+    AssociatedTokenAccount::check_address(vault, config.key(), mint, &TOKEN_PROGRAM_ID)?;
+    let vault = TokenAccount::from_account_info(vault)?;
+    if vault.owner().ne(config.key()) || vault.mint().ne(mint) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(vault.amount())
+}
+
+/// 只校验金库归属，定价使用 Config 中的记账储备。
+pub(crate) fn check_vault(
+    vault: &AccountInfo,
+    config: &AccountInfo,
+    mint: &Pubkey,
+) -> ProgramResult {
+    vault_amount(vault, config, mint).map(|_| ())
+}
@@ -0,0 +1,90 @@
+use blueshift_common::{AccountCheck, SignerAccount};
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::Config;
+
+/*
+    由池子 authority 提出 fee / state / authority / timelock 延迟的变更。
+
+    提案只记录在 Config 中，需等待 `timelock_delay` 秒后通过 ExecuteUpdate 生效，
+    让 LP 在不利的参数变更生效前有时间撤出。再次提案会覆盖旧提案，kind = 0 表示撤销。
+*/
+
+pub struct ProposeUpdateAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ProposeUpdateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        SignerAccount::check(authority)?;
+        Ok(Self { authority, config })
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct ProposeUpdateInstructionData {
+    /// `ConfigUpdate` 的取值
+    pub kind: u8,
+    pub value: [u8; 32],
+}
+
+impl<'a> TryFrom<&'a [u8]> for ProposeUpdateInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { *(data.as_ptr() as *const Self) })
+    }
+}
+
+pub struct ProposeUpdate<'a> {
+    pub accounts: ProposeUpdateAccounts<'a>,
+    pub instruction_data: ProposeUpdateInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ProposeUpdate<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = ProposeUpdateAccounts::try_from(accounts)?;
+        let instruction_data = ProposeUpdateInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> ProposeUpdate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &15;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let data = &self.instruction_data;
+
+        // 1. 只有池子的 authority 可以提案；没有 authority 的池子参数不可变
+        let mut config = Config::load_mut(accounts.config)?;
+        if config.has_authority() != Some(*accounts.authority.key()) {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+
+        // 2. 记录提案，最早执行时间为当前时间 + timelock_delay
+        let clock = Clock::get()?;
+        config.propose_update(data.kind, data.value, clock.unix_timestamp)
+    }
+}
@@ -0,0 +1,193 @@
+use blueshift_common::{AccountCheck, SignerAccount};
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+};
+use pinocchio_token::instructions::Transfer;
+
+use super::multi_helpers::vault_amount;
+use crate::{Config, MULTI_CONFIG_SEED, MultiConfig};
+
+/*
+    将金库中超出记账储备的部分（直接转入的代币）转出到 authority 指定的代币账户，
+    记账储备保持不变。
+
+    多资产池使用 SkimMulti：固定账户之后依次是 token_count 个金库，
+    再是 token_count 个接收账户，顺序与 MultiConfig::mints 一致。
+*/
+
+pub struct SkimAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub to_x: &'a AccountInfo,
+    pub to_y: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SkimAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, vault_x, vault_y, to_x, to_y, token_program, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        SignerAccount::check(authority)?;
+        Ok(Self {
+            authority,
+            config,
+            vault_x,
+            vault_y,
+            to_x,
+            to_y,
+            token_program,
+        })
+    }
+}
+
+pub struct Skim<'a> {
+    pub accounts: SkimAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Skim<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = SkimAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Skim<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &18;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+
+        // 1. 校验 authority
+        let config = Config::load(accounts.config)?;
+        if config.has_authority() != Some(*accounts.authority.key()) {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+
+        // 2. 超出记账储备的部分
+        let excess_x = vault_amount(accounts.vault_x, accounts.config, config.mint_x())?
+            .saturating_sub(config.reserve_x());
+        let excess_y = vault_amount(accounts.vault_y, accounts.config, config.mint_y())?
+            .saturating_sub(config.reserve_y());
+
+        // 3. Config PDA 签名转出
+        let config_seeds = config.signer_seeds();
+        let signer = config_seeds.signer();
+
+        if excess_x > 0 {
+            Transfer {
+                from: accounts.vault_x,
+                to: accounts.to_x,
+                authority: accounts.config,
+                amount: excess_x,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+        }
+
+        if excess_y > 0 {
+            Transfer {
+                from: accounts.vault_y,
+                to: accounts.to_y,
+                authority: accounts.config,
+                amount: excess_y,
+            }
+            .invoke_signed(&[signer])?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct SkimMultiAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub remaining: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SkimMultiAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, token_program, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        SignerAccount::check(authority)?;
+        Ok(Self {
+            authority,
+            config,
+            token_program,
+            remaining,
+        })
+    }
+}
+
+pub struct SkimMulti<'a> {
+    pub accounts: SkimMultiAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SkimMulti<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = SkimMultiAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> SkimMulti<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &20;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+
+        // 1. 校验 authority
+        let config = MultiConfig::load(accounts.config)?;
+        if config.authority() != accounts.authority.key() {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        let mints = config.mints();
+        let count = mints.len();
+        if accounts.remaining.len() != count * 2 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (vaults, recipients) = accounts.remaining.split_at(count);
+
+        // 2. MultiConfig PDA 签名
+        let seed_binding = config.seed().to_le_bytes();
+        let bump = config.config_bump();
+        let config_seeds =
+            [Seed::from(MULTI_CONFIG_SEED), Seed::from(&seed_binding), Seed::from(&bump)];
+        let signer = Signer::from(&config_seeds);
+
+        // 3. 逐个转出超出记账储备的部分
+        for i in 0..count {
+            let excess = vault_amount(&vaults[i], accounts.config, &mints[i])?
+                .saturating_sub(config.reserve(i));
+            if excess == 0 {
+                continue;
+            }
+
+            Transfer {
+                from: &vaults[i],
+                to: &recipients[i],
+                authority: accounts.config,
+                amount: excess,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+        }
+
+        Ok(())
+    }
+}
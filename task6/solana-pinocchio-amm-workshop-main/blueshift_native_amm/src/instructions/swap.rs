@@ -1,19 +1,29 @@
-use constant_product_curve::{ConstantProduct, LiquidityPair};
+use blueshift_events::{Event, Swapped};
+use blueshift_log::bslog;
 use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
-    instruction::{Seed, Signer},
+    instruction::Signer,
     program_error::ProgramError,
     sysvars::{Sysvar, clock::Clock},
 };
-use pinocchio_token::{instructions::Transfer, state::TokenAccount};
+use pinocchio_token::{
+    instructions::Transfer,
+    state::{Mint, TokenAccount},
+};
 
-use crate::{CONFIG_SEED, Config};
+use super::multi_helpers::check_vault;
+use crate::{
+    Config,
+    curve::{Curve, PoolCurve},
+};
 
 /*
     计算通过将一定数量的 mint_y 发送到 AMM（或反之）后，能够接收到的 mint_x 的数量，包括手续费。
 
     将 from 代币转移到金库，并将 to 代币转移到用户的代币账户。
+
+    可选追加 user_lp_ata 与 mint_lp：用户持有的 LP 达到 Config 中的返还档位时，按返还后的费率计算。
 */
 pub struct SwapAccounts<'a> {
     pub user: &'a AccountInfo,
@@ -23,6 +33,8 @@ pub struct SwapAccounts<'a> {
     pub vault_y: &'a AccountInfo,
     pub config: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
+    // 可选：(user_lp_ata, mint_lp)，用于手续费返还
+    pub lp: Option<(&'a AccountInfo, &'a AccountInfo)>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for SwapAccounts<'a> {
@@ -38,6 +50,7 @@ impl<'a> TryFrom<&'a [AccountInfo]> for SwapAccounts<'a> {
             vault_y: iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
             config: iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
             token_program: iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            lp: iter.next().zip(iter.next()),
         })
     }
 }
@@ -55,7 +68,8 @@ impl<'a> TryFrom<&'a [u8]> for SwapInstructionData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() < size_of::<Self>() {
+        // 长度必须精确匹配；is_x 只能是 0 或 1，其它取值直接读成 bool 是未定义行为
+        if data.len() != size_of::<Self>() || data[0] > 1 {
             return Err(ProgramError::InvalidInstructionData);
         }
         Ok(unsafe { *(data.as_ptr() as *const Self) })
@@ -101,80 +115,139 @@ impl<'a> Swap<'a> {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        // 3. 获取金库当前余额并计算交换
-        let vault_x = unsafe { TokenAccount::from_account_info_unchecked(accounts.vault_x)? };
-        let vault_y = unsafe { TokenAccount::from_account_info_unchecked(accounts.vault_y)? };
-
-        let mut curve = ConstantProduct::init(
-            vault_x.amount(),
-            vault_y.amount(),
-            vault_x.amount(), // 这里 supply 通常用于初始价格，交换中主要看储备
-            config.fee(),
-            None,
-        )
-        .map_err(|_| ProgramError::ArithmeticOverflow)?;
-
-        let pair = if data.is_x {
-            LiquidityPair::X
-        } else {
-            LiquidityPair::Y
+        // 3. 校验金库，按记账储备计算交换（直接转入金库的代币不会改变价格）
+        check_vault(accounts.vault_x, accounts.config, config.mint_x())?;
+        check_vault(accounts.vault_y, accounts.config, config.mint_y())?;
+        let (reserve_x, reserve_y) = (config.reserve_x(), config.reserve_y());
+
+        // LP 持有者按返还档位享受更低的费率
+        let fee = match accounts.lp {
+            Some((user_lp_ata, mint_lp)) => {
+                let lp_balance = lp_balance(user_lp_ata, mint_lp, accounts.user, accounts.config)?;
+                config.effective_fee(lp_balance)
+            }
+            None => config.fee(),
         };
-        let swap_result = curve
-            .swap(pair, data.amount, data.min)
-            .map_err(|_| ProgramError::InvalidArgument)?;
+
+        // 加权池使用恒定均值公式，50/50 池沿用恒定乘积曲线
+        let (deposit, withdraw) = PoolCurve::with_fee(&config, reserve_x, reserve_y, fee).swap(
+            data.is_x,
+            data.amount,
+            data.min,
+        )?;
 
         // 4. 准备签名种子 (用于从金库转出)
-        let seed_binding = config.seed().to_le_bytes();
-        let mint_x_key = config.mint_x();
-        let mint_y_key = config.mint_y();
-        let bump = config.config_bump();
-
-        let config_seeds = [
-            Seed::from(CONFIG_SEED),
-            Seed::from(&seed_binding),
-            Seed::from(mint_x_key.as_ref()),
-            Seed::from(mint_y_key.as_ref()),
-            Seed::from(&bump),
-        ];
-        let signer = Signer::from(&config_seeds);
+        let config_seeds = config.signer_seeds();
+        let signer = config_seeds.signer();
 
         // 5. 执行原子转账
-        if data.is_x {
-            // X -> Y: 用户发送 X 到 vault_x，金库发送 Y 到 user_y_ata
-            Transfer {
-                from: accounts.user_x_ata,
-                to: accounts.vault_x,
-                authority: accounts.user,
-                amount: swap_result.deposit,
-            }
-            .invoke()?;
-
-            Transfer {
-                from: accounts.vault_y,
-                to: accounts.user_y_ata,
-                authority: accounts.config,
-                amount: swap_result.withdraw,
-            }
-            .invoke_signed(&[signer])?;
-        } else {
-            // Y -> X: 用户发送 Y 到 vault_y，金库发送 X 到 user_x_ata
-            Transfer {
-                from: accounts.user_y_ata,
-                to: accounts.vault_y,
-                authority: accounts.user,
-                amount: swap_result.deposit,
-            }
-            .invoke()?;
-
-            Transfer {
-                from: accounts.vault_x,
-                to: accounts.user_x_ata,
-                authority: accounts.config,
-                amount: swap_result.withdraw,
-            }
-            .invoke_signed(&[signer])?;
+        transfer_swap(
+            SwapLeg {
+                user: accounts.user,
+                user_x_ata: accounts.user_x_ata,
+                user_y_ata: accounts.user_y_ata,
+            },
+            SwapPool {
+                vault_x: accounts.vault_x,
+                vault_y: accounts.vault_y,
+                config: accounts.config,
+            },
+            data.is_x,
+            deposit,
+            withdraw,
+            signer,
+        )?;
+
+        // 6. 更新记账储备
+        drop(config);
+        Config::load_mut(accounts.config)?.apply_swap(data.is_x, deposit, withdraw)?;
+
+        // 7. 发出事件，fee 为本次实际使用的费率（含 LP 返还档位）
+        Swapped {
+            config: *accounts.config.key(),
+            user: *accounts.user.key(),
+            is_x: data.is_x,
+            amount_in: deposit,
+            amount_out: withdraw,
+            fee,
         }
+        .emit();
+        bslog!(
+            "swap",
+            "is_x={} in={} out={} fee={}",
+            data.is_x,
+            deposit,
+            withdraw,
+            fee
+        );
 
         Ok(())
     }
 }
+
+/// 用户持有的本池 LP 数量。
+/// mint_lp 的 mint authority 必须是 config PDA，只有本程序能为它铸币，因此无法伪造。
+fn lp_balance(
+    user_lp_ata: &AccountInfo,
+    mint_lp: &AccountInfo,
+    user: &AccountInfo,
+    config: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    let mint = Mint::from_account_info(mint_lp)?;
+    if mint.mint_authority() != Some(config.key()) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let ata = TokenAccount::from_account_info(user_lp_ata)?;
+    if ata.owner().ne(user.key()) || ata.mint().ne(mint_lp.key()) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(ata.amount())
+}
+
+/// 一次兑换涉及的用户侧账户
+pub(crate) struct SwapLeg<'a> {
+    pub user: &'a AccountInfo,
+    pub user_x_ata: &'a AccountInfo,
+    pub user_y_ata: &'a AccountInfo,
+}
+
+/// 兑换涉及的池子账户
+pub(crate) struct SwapPool<'a> {
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+/// 用户存入输入代币，金库（Config PDA 签名）支付输出代币
+pub(crate) fn transfer_swap(
+    leg: SwapLeg,
+    pool: SwapPool,
+    is_x: bool,
+    deposit: u64,
+    withdraw: u64,
+    signer: Signer,
+) -> ProgramResult {
+    let (user_in, vault_in, vault_out, user_out) = if is_x {
+        // X -> Y: 用户发送 X 到 vault_x，金库发送 Y 到 user_y_ata
+        (leg.user_x_ata, pool.vault_x, pool.vault_y, leg.user_y_ata)
+    } else {
+        // Y -> X: 用户发送 Y 到 vault_y，金库发送 X 到 user_x_ata
+        (leg.user_y_ata, pool.vault_y, pool.vault_x, leg.user_x_ata)
+    };
+
+    Transfer {
+        from: user_in,
+        to: vault_in,
+        authority: leg.user,
+        amount: deposit,
+    }
+    .invoke()?;
+
+    Transfer {
+        from: vault_out,
+        to: user_out,
+        authority: pool.config,
+        amount: withdraw,
+    }
+    .invoke_signed(&[signer])
+}
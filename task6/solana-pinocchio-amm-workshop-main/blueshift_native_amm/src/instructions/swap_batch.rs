@@ -0,0 +1,196 @@
+use blueshift_common::read_i64_le;
+use blueshift_events::{Event, Swapped};
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use super::{
+    multi_helpers::check_vault,
+    swap::{SwapLeg, SwapPool, transfer_swap},
+};
+use crate::{
+    Config,
+    curve::{Curve, PoolCurve},
+};
+
+/*
+    在同一个池子里依次执行多笔相互独立的兑换，供做市机器人压缩交易数量。
+
+    每一笔兑换 (leg) 的用户账户通过 remaining accounts 传入：[user, user_x_ata, user_y_ata] * n，
+    可以是多个用户，也可以是同一用户的多笔不同规模的兑换。
+
+    每笔兑换都基于上一笔完成后的记账储备重新报价，任意一笔失败则整笔交易回滚。
+    每笔兑换各发出一条与 swap 相同的 Swapped 事件，索引器不需要区分两种指令。
+*/
+
+pub struct SwapBatchAccounts<'a> {
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub legs: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SwapBatchAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [vault_x, vault_y, config, token_program, legs @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Ok(Self {
+            vault_x,
+            vault_y,
+            config,
+            token_program,
+            legs,
+        })
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct SwapBatchLeg {
+    pub is_x: bool,
+    pub amount: u64,
+    pub min: u64,
+}
+
+/// 数据布局：expiration (i64) | count (u8) | SwapBatchLeg * count
+pub struct SwapBatchInstructionData<'a> {
+    pub expiration: i64,
+    pub legs: &'a [u8],
+}
+
+impl<'a> SwapBatchInstructionData<'a> {
+    const HEADER_LEN: usize = size_of::<i64>() + size_of::<u8>();
+
+    pub fn count(&self) -> usize {
+        self.legs.len() / size_of::<SwapBatchLeg>()
+    }
+
+    pub fn leg(&self, index: usize) -> SwapBatchLeg {
+        let offset = index * size_of::<SwapBatchLeg>();
+        unsafe { (self.legs[offset..].as_ptr() as *const SwapBatchLeg).read_unaligned() }
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SwapBatchInstructionData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() < Self::HEADER_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let expiration = read_i64_le(data, 0).ok_or(ProgramError::InvalidInstructionData)?;
+        let count = data[8] as usize;
+        let legs = &data[Self::HEADER_LEN..];
+        if count == 0 || legs.len() != count * size_of::<SwapBatchLeg>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        // is_x 只能是 0 或 1，其它取值直接读成 bool 是未定义行为
+        if legs.chunks_exact(size_of::<SwapBatchLeg>()).any(|leg| leg[0] > 1) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self { expiration, legs })
+    }
+}
+
+pub struct SwapBatch<'a> {
+    pub accounts: SwapBatchAccounts<'a>,
+    pub instruction_data: SwapBatchInstructionData<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SwapBatch<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SwapBatchAccounts::try_from(accounts)?;
+        let instruction_data = SwapBatchInstructionData::try_from(data)?;
+
+        // 每笔兑换需要 3 个用户账户
+        if accounts.legs.len() != instruction_data.count() * 3 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SwapBatch<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &14;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let data = &self.instruction_data;
+
+        // 1. 验证过期时间 (对整批生效)
+        let clock = Clock::get()?;
+        if clock.unix_timestamp > data.expiration {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 2. 加载配置和状态
+        let config = Config::load(accounts.config)?;
+        if config.state() != 1 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        check_vault(accounts.vault_x, accounts.config, config.mint_x())?;
+        check_vault(accounts.vault_y, accounts.config, config.mint_y())?;
+        let config_seeds = config.signer_seeds();
+
+        // 3. 依次执行每笔兑换，每笔都基于前一笔之后的储备报价
+        let (mut reserve_x, mut reserve_y) = (config.reserve_x(), config.reserve_y());
+        for (index, users) in accounts.legs.chunks_exact(3).enumerate() {
+            let leg = data.leg(index);
+            let (deposit, withdraw) = PoolCurve::from_config(&config, reserve_x, reserve_y)
+                .swap(leg.is_x, leg.amount, leg.min)?;
+
+            transfer_swap(
+                SwapLeg {
+                    user: &users[0],
+                    user_x_ata: &users[1],
+                    user_y_ata: &users[2],
+                },
+                SwapPool {
+                    vault_x: accounts.vault_x,
+                    vault_y: accounts.vault_y,
+                    config: accounts.config,
+                },
+                leg.is_x,
+                deposit,
+                withdraw,
+                config_seeds.signer(),
+            )?;
+
+            // 批量兑换不查询 LP 返还档位，费率就是池子的基础费率
+            Swapped {
+                config: *accounts.config.key(),
+                user: *users[0].key(),
+                is_x: leg.is_x,
+                amount_in: deposit,
+                amount_out: withdraw,
+                fee: config.fee(),
+            }
+            .emit();
+
+            (reserve_x, reserve_y) = if leg.is_x {
+                (reserve_x + deposit, reserve_y - withdraw)
+            } else {
+                (reserve_x - withdraw, reserve_y + deposit)
+            };
+        }
+
+        // 4. 更新记账储备
+        drop(config);
+        Config::load_mut(accounts.config)?.set_reserves(reserve_x, reserve_y);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+
+use super::multi_helpers::check_vault;
+use crate::{AmmState, MULTI_CONFIG_SEED, MultiConfig, curve::constant_product_out};
+
+pub struct SwapMultiAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub vault_in: &'a AccountInfo,
+    pub vault_out: &'a AccountInfo,
+    pub user_in_ata: &'a AccountInfo,
+    pub user_out_ata: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SwapMultiAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let mut account_iter = accounts.iter();
+        Ok(Self {
+            user: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            config: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            vault_in: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            vault_out: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            user_in_ata: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            user_out_ata: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            token_program: account_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+        })
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct SwapMultiInstructionData {
+    pub index_in: u8,
+    pub index_out: u8,
+    pub amount: u64,
+    pub min: u64,
+    pub expiration: i64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SwapMultiInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+pub struct SwapMulti<'a> {
+    pub accounts: SwapMultiAccounts<'a>,
+    pub instruction_data: SwapMultiInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SwapMulti<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SwapMultiAccounts::try_from(accounts)?;
+        let instruction_data = SwapMultiInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SwapMulti<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &7;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let data = &self.instruction_data;
+
+        // 1. 验证过期时间
+        let clock = Clock::get()?;
+        if clock.unix_timestamp > data.expiration {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 2. 加载配置和状态
+        let config = MultiConfig::load(accounts.config)?;
+        if config.state() != AmmState::Initialized as u8 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let count = config.token_count();
+        let (index_in, index_out) = (data.index_in as usize, data.index_out as usize);
+        if index_in == index_out || index_in >= count || index_out >= count {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if data.amount == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 3. 校验金库并按记账储备的两币恒定乘积计算输出
+        let mints = config.mints();
+        check_vault(accounts.vault_in, accounts.config, &mints[index_in])?;
+        check_vault(accounts.vault_out, accounts.config, &mints[index_out])?;
+        let amount_out = constant_product_out(
+            config.reserve(index_in),
+            config.reserve(index_out),
+            data.amount,
+            config.fee(),
+        )?;
+        if amount_out == 0 || amount_out < data.min {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 4. 准备签名种子 (用于从金库转出)
+        let seed_binding = config.seed().to_le_bytes();
+        let bump = config.config_bump();
+        let config_seeds =
+            [Seed::from(MULTI_CONFIG_SEED), Seed::from(&seed_binding), Seed::from(&bump)];
+
+        // 5. 执行原子转账
+        Transfer {
+            from: accounts.user_in_ata,
+            to: accounts.vault_in,
+            authority: accounts.user,
+            amount: data.amount,
+        }
+        .invoke()?;
+
+        Transfer {
+            from: accounts.vault_out,
+            to: accounts.user_out_ata,
+            authority: accounts.config,
+            amount: amount_out,
+        }
+        .invoke_signed(&[Signer::from(&config_seeds)])?;
+
+        // 6. 更新记账储备
+        drop(config);
+        MultiConfig::load_mut(accounts.config)?.apply_swap(
+            index_in,
+            index_out,
+            data.amount,
+            amount_out,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use blueshift_common::{AccountCheck, SignerAccount};
+use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+
+use super::multi_helpers::vault_amount;
+use crate::{Config, MultiConfig};
+
+/*
+    将 Config 中的记账储备对齐到金库的实际余额，把直接转入金库的代币并入池子。
+
+    只有 authority 可以执行，否则任何人都能通过"捐赠 + sync"瞬间移动价格。
+    多资产池使用 SyncReservesMulti，金库按 MultiConfig::mints 的顺序作为剩余账户传入。
+*/
+
+pub struct SyncReservesAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SyncReservesAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, vault_x, vault_y, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        SignerAccount::check(authority)?;
+        Ok(Self {
+            authority,
+            config,
+            vault_x,
+            vault_y,
+        })
+    }
+}
+
+pub struct SyncReserves<'a> {
+    pub accounts: SyncReservesAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SyncReserves<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = SyncReservesAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> SyncReserves<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &17;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+
+        // 1. 校验 authority
+        let mut config = Config::load_mut(accounts.config)?;
+        if config.has_authority() != Some(*accounts.authority.key()) {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+
+        // 2. 读取金库实际余额作为新的记账储备
+        let balance_x = vault_amount(accounts.vault_x, accounts.config, config.mint_x())?;
+        let balance_y = vault_amount(accounts.vault_y, accounts.config, config.mint_y())?;
+        config.set_reserves(balance_x, balance_y);
+
+        Ok(())
+    }
+}
+
+pub struct SyncReservesMultiAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub vaults: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SyncReservesMultiAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, vaults @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        SignerAccount::check(authority)?;
+        Ok(Self {
+            authority,
+            config,
+            vaults,
+        })
+    }
+}
+
+pub struct SyncReservesMulti<'a> {
+    pub accounts: SyncReservesMultiAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SyncReservesMulti<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = SyncReservesMultiAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> SyncReservesMulti<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &19;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+
+        // 1. 校验 authority
+        let mut config = MultiConfig::load_mut(accounts.config)?;
+        if config.authority() != accounts.authority.key() {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+        let count = config.token_count();
+        if accounts.vaults.len() != count {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        // 2. 逐个读取金库实际余额作为新的记账储备
+        for (i, vault) in accounts.vaults.iter().enumerate() {
+            let balance = vault_amount(vault, accounts.config, &config.mints()[i])?;
+            config.set_reserve(i, balance);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use blueshift_common::feature_gate;
+use blueshift_version::{FeatureGate, Version, features};
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    sysvars::{Sysvar, rent::Rent},
+};
+use pinocchio_system::instructions::CreateAccount;
+
+/// 本程序的版本与已实现的功能（多资产池、CLMM、批量兑换、timelock）。
+/// 新功能分阶段上线时在 `features::amm` 中加一位，处理函数里用
+/// `feature_gate::require_feature` 检查，部署后由升级权限打开。
+pub const VERSION: Version = blueshift_version::version!(features::amm::SUPPORTED);
+
+/// get_version：账户可选传入功能开关 PDA `["feature_gate"]`，结果写入 return data
+pub fn get_version(accounts: &[AccountInfo]) -> ProgramResult {
+    feature_gate::get_version(VERSION, &crate::ID, accounts)
+}
+
+/// set_features：升级权限覆盖写入功能位，开关账户不存在时由 authority 付租金创建。
+///
+/// 账户：authority、program_data、feature_gate、system_program；数据：u64 (LE) 功能位
+pub fn set_features(data: &[u8], accounts: &[AccountInfo]) -> ProgramResult {
+    feature_gate::set_features(&crate::ID, accounts, data, |payer, gate, bump| {
+        let bump_binding = [bump];
+        let seeds = [
+            Seed::from(pdas::FEATURE_GATE_SEED),
+            Seed::from(&bump_binding),
+        ];
+        CreateAccount {
+            from: payer,
+            to: gate,
+            lamports: Rent::get()?.minimum_balance(FeatureGate::LEN),
+            space: FeatureGate::LEN as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Signer::from(&seeds)])
+    })
+}
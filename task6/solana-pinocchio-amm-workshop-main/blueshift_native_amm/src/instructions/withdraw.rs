@@ -1,17 +1,22 @@
-use constant_product_curve::ConstantProduct;
+use blueshift_common::{AccountCheck, SignerAccount};
+use blueshift_events::{Event, LiquidityWithdrawn};
+use blueshift_log::bslog;
 use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
-    instruction::{Seed, Signer},
     program_error::ProgramError,
     sysvars::{Sysvar, clock::Clock},
 };
 use pinocchio_token::{
     instructions::{Burn, Transfer},
-    state::{Mint, TokenAccount},
+    state::Mint,
 };
 
-use crate::{CONFIG_SEED, Config};
+use super::{lp_lock::LockAccounts, multi_helpers::check_vault};
+use crate::{
+    AmmError, AmmState, Config,
+    curve::{Curve, PoolCurve},
+};
 
 /*
     根据用户希望 burn 的 LP 数量，提取 mint_x 和 mint_y 代币。
@@ -19,6 +24,9 @@ use crate::{CONFIG_SEED, Config};
     计算提取金额，并检查金额是否不低于用户指定的 mint_x 和 mint_y。
 
     从用户的 ata 中销毁相应数量的 mint_lp。
+
+    若追加 lp_lock 与 lock_vault 账户，则从到期的锁仓中赎回：LP 从 lock vault 销毁。
+    锁仓加成在存款时已从奖励池以 LP 形式发放，赎回与普通提取一样严格按 LP 份额计算。
 */
 
 pub struct WithdrawAccounts<'a> {
@@ -31,6 +39,7 @@ pub struct WithdrawAccounts<'a> {
     pub user_lp_ata: &'a AccountInfo,
     pub config: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
+    pub lock: Option<LockAccounts<'a>>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
@@ -48,6 +57,13 @@ impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
             user_lp_ata: iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
             config: iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
             token_program: iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?,
+            lock: match (iter.next(), iter.next()) {
+                (Some(lp_lock), Some(lock_vault)) => Some(LockAccounts {
+                    lp_lock,
+                    lock_vault,
+                }),
+                _ => None,
+            },
         })
     }
 }
@@ -65,7 +81,8 @@ impl<'a> TryFrom<&'a [u8]> for WithdrawInstructionData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() < size_of::<Self>() {
+        // 长度必须精确匹配，避免尾部多余字节被静默忽略
+        if data.len() != size_of::<Self>() {
             return Err(ProgramError::InvalidInstructionData);
         }
         Ok(unsafe { *(data.as_ptr() as *const Self) })
@@ -107,62 +124,61 @@ impl<'a> Withdraw<'a> {
 
         // 2. 加载状态并检查 (Withdraw 要求非 Disabled)
         let config = Config::load(accounts.config)?;
-        // 假设 0: Uninitialized, 1: Initialized, 2: Disabled
-        if config.state() == 2 {
+        // 禁用超过宽限期后自动视为 WithdrawOnly，LP 仍可提取
+        if config.effective_state(clock.slot) == AmmState::Disabled as u8 {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        // 3. 反序列化代币信息
+        // 3. 金库必须由 config PDA 持有且 mint 匹配；按记账储备计算
         let mint_lp = unsafe { Mint::from_account_info_unchecked(accounts.mint_lp)? };
-        let vault_x = unsafe { TokenAccount::from_account_info_unchecked(accounts.vault_x)? };
-        let vault_y = unsafe { TokenAccount::from_account_info_unchecked(accounts.vault_y)? };
-
-        // 4. 计算应退还的 X, Y 数量
-        let (x, y) = if mint_lp.supply() == data.amount {
-            // 全额提取：直接取走所有余额，防止舍入误差留下“尘埃”
-            (vault_x.amount(), vault_y.amount())
-        } else {
-            let amounts = ConstantProduct::xy_withdraw_amounts_from_l(
-                vault_x.amount(),
-                vault_y.amount(),
-                mint_lp.supply(),
-                data.amount,
-                6, // LP decimals
-            )
-            .map_err(|_| ProgramError::ArithmeticOverflow)?;
-            (amounts.x, amounts.y)
-        };
-
-        // 5. 滑点检查
-        if x < data.min_x || y < data.min_y {
+        let supply = mint_lp.supply();
+        if data.amount == 0 || data.amount > supply {
             return Err(ProgramError::InvalidArgument);
         }
+        check_vault(accounts.vault_x, accounts.config, config.mint_x())?;
+        check_vault(accounts.vault_y, accounts.config, config.mint_y())?;
+        let (reserve_x, reserve_y) = (config.reserve_x(), config.reserve_y());
+
+        // 4. 锁仓赎回：必须已到期，且不超过锁仓记录中的 LP（含存款时发放的奖励）
+        if let Some(lock) = &accounts.lock {
+            SignerAccount::check(accounts.user)?;
+            lock.check_vault(accounts.config, accounts.mint_lp)?;
+            lock.load(accounts.user, accounts.config)?
+                .unlock(data.amount, clock.unix_timestamp)?;
+        }
+
+        // 5. 按 LP 份额计算应退还的 X, Y 数量（向下取整，全额提取时取走全部记账储备）
+        let (x, y) = PoolCurve::from_config(&config, reserve_x, reserve_y)
+            .withdraw_amounts(data.amount, supply)?;
 
-        // 6. 销毁用户的 LP 代币 (用户签名)
-        Burn {
-            mint: accounts.mint_lp,
-            account: accounts.user_lp_ata,
-            authority: accounts.user,
-            amount: data.amount,
+        // 6. 滑点检查：min_x / min_y 只是下限，实际支付的是上面计算出的数量
+        if x < data.min_x || y < data.min_y {
+            return Err(AmmError::SlippageExceeded.into());
         }
-        .invoke()?;
 
         // 7. 构造 Config PDA 签名以从金库转账
-        let seed_binding = config.seed().to_le_bytes();
-        let mint_x_key = config.mint_x();
-        let mint_y_key = config.mint_y();
-        let bump = config.config_bump();
-
-        let config_seeds = [
-            Seed::from(CONFIG_SEED),
-            Seed::from(&seed_binding),
-            Seed::from(mint_x_key.as_ref()),
-            Seed::from(mint_y_key.as_ref()),
-            Seed::from(&bump),
-        ];
-        let signer = Signer::from(&config_seeds);
-
-        // 8. 转移 Token X 和 Y (Config PDA 签名)
+        let config_seeds = config.signer_seeds();
+        let signer = config_seeds.signer();
+
+        // 8. 销毁 LP 代币：普通提取由用户签名，锁仓赎回由 Config PDA 从 lock vault 销毁
+        match &accounts.lock {
+            Some(lock) => Burn {
+                mint: accounts.mint_lp,
+                account: lock.lock_vault,
+                authority: accounts.config,
+                amount: data.amount,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?,
+            None => Burn {
+                mint: accounts.mint_lp,
+                account: accounts.user_lp_ata,
+                authority: accounts.user,
+                amount: data.amount,
+            }
+            .invoke()?,
+        }
+
+        // 9. 转移 Token X 和 Y (Config PDA 签名)
         Transfer {
             from: accounts.vault_x,
             to: accounts.user_x_ata,
@@ -170,7 +186,7 @@ impl<'a> Withdraw<'a> {
             amount: x,
         }
         // .invoke_signed(&[signer.clone()])?;
-        .invoke_signed(std::slice::from_ref(&signer))?;
+        .invoke_signed(core::slice::from_ref(&signer))?;
 
         Transfer {
             from: accounts.vault_y,
@@ -180,6 +196,21 @@ impl<'a> Withdraw<'a> {
         }
         .invoke_signed(&[signer])?;
 
+        // 10. 更新记账储备
+        drop(config);
+        Config::load_mut(accounts.config)?.set_reserves(reserve_x - x, reserve_y - y);
+
+        // 11. 发出事件
+        LiquidityWithdrawn {
+            config: *accounts.config.key(),
+            user: *accounts.user.key(),
+            lp_amount: data.amount,
+            amount_x: x,
+            amount_y: y,
+        }
+        .emit();
+        bslog!("withdraw", "lp={} x={} y={}", data.amount, x, y);
+
         Ok(())
     }
 }
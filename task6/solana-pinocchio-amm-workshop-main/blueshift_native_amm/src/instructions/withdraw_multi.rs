@@ -0,0 +1,171 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::{
+    instructions::{Burn, Transfer},
+    state::Mint,
+};
+
+use super::multi_helpers::{check_vault, read_u64};
+use crate::{
+    AmmError, AmmState, MAX_POOL_TOKENS, MULTI_CONFIG_SEED, MultiConfig, curve::proportional_amount,
+};
+
+/// 账户顺序与 `DepositMulti` 相同。
+pub struct WithdrawMultiAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub mint_lp: &'a AccountInfo,
+    pub user_lp_ata: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub remaining: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawMultiAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, mint_lp, user_lp_ata, config, token_program, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Ok(Self {
+            user,
+            mint_lp,
+            user_lp_ata,
+            config,
+            token_program,
+            remaining,
+        })
+    }
+}
+
+/// 定长头部，后面紧跟 `token_count` 个小端 u64 的 min 数组。
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct WithdrawMultiInstructionData {
+    pub amount: u64,
+    pub expiration: i64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for WithdrawMultiInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() < size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+pub struct WithdrawMulti<'a> {
+    pub accounts: WithdrawMultiAccounts<'a>,
+    pub instruction_data: WithdrawMultiInstructionData,
+    pub min_amounts: &'a [u8],
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for WithdrawMulti<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = WithdrawMultiAccounts::try_from(accounts)?;
+        let instruction_data = WithdrawMultiInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            min_amounts: &data[size_of::<WithdrawMultiInstructionData>()..],
+        })
+    }
+}
+
+impl<'a> WithdrawMulti<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &6;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let data = &self.instruction_data;
+
+        // 1. 过期检查
+        let clock = Clock::get()?;
+        if clock.unix_timestamp > data.expiration {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 2. 加载状态并检查 (Withdraw 要求非 Disabled)
+        let config = MultiConfig::load(accounts.config)?;
+        if config.state() == AmmState::Disabled as u8 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mints = config.mints();
+        let count = mints.len();
+        if accounts.remaining.len() != count * 2
+            || self.min_amounts.len() != count * size_of::<u64>()
+        {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (vaults, user_atas) = accounts.remaining.split_at(count);
+
+        // 3. 先读取 supply，再销毁用户的 LP 代币 (用户签名)
+        let supply = unsafe { Mint::from_account_info_unchecked(accounts.mint_lp)? }.supply();
+        if data.amount == 0 || data.amount > supply {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Burn {
+            mint: accounts.mint_lp,
+            account: accounts.user_lp_ata,
+            authority: accounts.user,
+            amount: data.amount,
+        }
+        .invoke()?;
+
+        // 4. 构造 MultiConfig PDA 签名以从金库转账
+        let seed_binding = config.seed().to_le_bytes();
+        let bump = config.config_bump();
+        let config_seeds =
+            [Seed::from(MULTI_CONFIG_SEED), Seed::from(&seed_binding), Seed::from(&bump)];
+        let signer = Signer::from(&config_seeds);
+
+        // 5. 按记账储备的份额向下取整退还每个代币
+        let mut reserves = [0u64; MAX_POOL_TOKENS];
+        for i in 0..count {
+            check_vault(&vaults[i], accounts.config, &mints[i])?;
+            let reserve = config.reserve(i);
+            let amount = if supply == data.amount {
+                // 全额提取：取走全部记账储备，防止舍入误差留下“尘埃”
+                reserve
+            } else {
+                proportional_amount(reserve, data.amount, supply, false)?
+            };
+
+            // 滑点检查
+            if amount < read_u64(self.min_amounts, i)? {
+                return Err(AmmError::SlippageExceeded.into());
+            }
+
+            Transfer {
+                from: &vaults[i],
+                to: &user_atas[i],
+                authority: accounts.config,
+                amount,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            reserves[i] = reserve - amount;
+        }
+
+        // 6. 更新记账储备
+        drop(config);
+        let mut config = MultiConfig::load_mut(accounts.config)?;
+        for (i, reserve) in reserves[..count].iter().enumerate() {
+            config.set_reserve(i, *reserve);
+        }
+
+        Ok(())
+    }
+}
@@ -1,8 +1,31 @@
+// 链上构建不链接 std；程序代码不允许 unwrap / expect / panic!，出错一律返回 ProgramError
+#![no_std]
+#![deny(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+use blueshift_errors::AnchorCompat;
+use blueshift_version::{GET_VERSION_DISCRIMINATOR, SET_FEATURES_DISCRIMINATOR};
 use pinocchio::{
-    ProgramResult, account_info::AccountInfo, entrypoint, program_error::ProgramError,
-    pubkey::Pubkey,
+    ProgramResult, account_info::AccountInfo, entrypoint, nostd_panic_handler,
+    program_error::ProgramError, pubkey::Pubkey,
 };
 entrypoint!(process_instruction);
+nostd_panic_handler!();
+
+// bslog! 的日志前缀 `[amm v<版本>]`；`--features quiet` 构建时去掉全部文本日志
+blueshift_log::program!("amm");
+
+// security.txt：让浏览器展示漏洞披露方式；只在 `--features security-txt` 构建链上程序时嵌入
+#[cfg(feature = "security-txt")]
+solana_security_txt::security_txt! {
+    name: "Blueshift Native AMM",
+    project_url: "https://github.com/o7reconversion/solana_bootcamp_2026",
+    contacts: "link:https://github.com/o7reconversion/solana_bootcamp_2026/security/advisories/new",
+    policy: "https://github.com/o7reconversion/solana_bootcamp_2026/blob/main/SECURITY.md",
+    source_code: "https://github.com/o7reconversion/solana_bootcamp_2026/tree/main/task6/solana-pinocchio-amm-workshop-main/blueshift_native_amm"
+}
+
+pub mod errors;
+pub use errors::*;
 
 pub mod instructions;
 pub use instructions::*;
@@ -10,17 +33,41 @@ pub use instructions::*;
 pub mod state;
 pub use state::*;
 
+pub mod clmm;
+
+pub mod curve;
+
+pub mod weighted_math;
+pub use weighted_math::{MIN_WEIGHT, WEIGHT_ONE};
+
 // 22222222222222222222222222222222222222222222
 pub const ID: Pubkey = [
     0x0f, 0x1e, 0x6b, 0x14, 0x21, 0xc0, 0x4a, 0x07, 0x04, 0x31, 0x26, 0x5c, 0x19, 0xc5, 0xbb, 0xee,
     0x19, 0x92, 0xba, 0xe8, 0xaf, 0xd1, 0xcd, 0x07, 0x8e, 0xf8, 0xaf, 0x70, 0x47, 0xdc, 0x11, 0xf7,
 ];
 
+// 账户校验失败时返回与 Anchor 程序相同的框架错误码（3005 / 3010 / 2006 ……），
+// 客户端只需一张 BootcampError 表；SlippageExceeded 等 AmmError 原样返回
 fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    dispatch(program_id, accounts, instruction_data).map_err(AnchorCompat::anchor_compat)
+}
+
+fn dispatch(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
+    // get_version / set_features 使用各训练营程序统一的 8 字节判别器
+    if instruction_data.starts_with(&GET_VERSION_DISCRIMINATOR) {
+        return get_version(accounts);
+    }
+    if let Some(data) = instruction_data.strip_prefix(&SET_FEATURES_DISCRIMINATOR) {
+        return set_features(data, accounts);
+    }
     match instruction_data.split_first() {
         Some((Initialize::DISCRIMINATOR, data)) => {
             Initialize::try_from((data, accounts))?.process()
@@ -28,6 +75,41 @@ fn process_instruction(
         Some((Deposit::DISCRIMINATOR, data)) => Deposit::try_from((data, accounts))?.process(),
         Some((Withdraw::DISCRIMINATOR, data)) => Withdraw::try_from((data, accounts))?.process(),
         Some((Swap::DISCRIMINATOR, data)) => Swap::try_from((data, accounts))?.process(),
+        Some((InitializeMulti::DISCRIMINATOR, data)) => {
+            InitializeMulti::try_from((data, accounts))?.process()
+        }
+        Some((DepositMulti::DISCRIMINATOR, data)) => {
+            DepositMulti::try_from((data, accounts))?.process()
+        }
+        Some((WithdrawMulti::DISCRIMINATOR, data)) => {
+            WithdrawMulti::try_from((data, accounts))?.process()
+        }
+        Some((SwapMulti::DISCRIMINATOR, data)) => SwapMulti::try_from((data, accounts))?.process(),
+        Some((ClmmInitializePool::DISCRIMINATOR, data)) => {
+            ClmmInitializePool::try_from((data, accounts))?.process()
+        }
+        Some((ClmmOpenPosition::DISCRIMINATOR, data)) => {
+            ClmmOpenPosition::try_from((data, accounts))?.process()
+        }
+        Some((ClmmIncreaseLiquidity::DISCRIMINATOR, data)) => {
+            ClmmIncreaseLiquidity::try_from((data, accounts))?.process()
+        }
+        Some((ClmmDecreaseLiquidity::DISCRIMINATOR, data)) => {
+            ClmmDecreaseLiquidity::try_from((data, accounts))?.process()
+        }
+        Some((ClmmSwap::DISCRIMINATOR, data)) => ClmmSwap::try_from((data, accounts))?.process(),
+        Some((ClmmCollect::DISCRIMINATOR, _)) => ClmmCollect::try_from(accounts)?.process(),
+        Some((SwapBatch::DISCRIMINATOR, data)) => SwapBatch::try_from((data, accounts))?.process(),
+        Some((ProposeUpdate::DISCRIMINATOR, data)) => {
+            ProposeUpdate::try_from((data, accounts))?.process()
+        }
+        Some((ExecuteUpdate::DISCRIMINATOR, _)) => ExecuteUpdate::try_from(accounts)?.process(),
+        Some((SyncReserves::DISCRIMINATOR, _)) => SyncReserves::try_from(accounts)?.process(),
+        Some((Skim::DISCRIMINATOR, _)) => Skim::try_from(accounts)?.process(),
+        Some((SyncReservesMulti::DISCRIMINATOR, _)) => {
+            SyncReservesMulti::try_from(accounts)?.process()
+        }
+        Some((SkimMulti::DISCRIMINATOR, _)) => SkimMulti::try_from(accounts)?.process(),
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
@@ -1,14 +1,38 @@
+use blueshift_zerocopy::{layout, Pod, Zeroable};
 use core::mem::size_of;
 use pinocchio::{
     account_info::{AccountInfo, Ref, RefMut},
+    instruction::{Seed, Signer},
     program_error::ProgramError,
     pubkey::Pubkey,
 };
 
-pub const CONFIG_SEED: &[u8] = b"config";
-pub const MINT_LP_SEED: &[u8] = b"mint_lp";
+use crate::{MIN_WEIGHT, WEIGHT_ONE};
 
+// 种子布局与派生函数在 pdas crate，客户端与测试共用同一份
+pub use pdas::{CONFIG_SEED, LP_LOCK_SEED, LP_REWARDS_SEED, MINT_LP_SEED, MULTI_CONFIG_SEED};
+/// 未指定时 LP mint 的小数位数
+pub const DEFAULT_LP_DECIMALS: u8 = 6;
+
+const DAY: i64 = 24 * 60 * 60;
+/// 参数变更提案的默认生效延迟，给 LP 留出退出时间
+pub const DEFAULT_TIMELOCK_DELAY: i64 = 2 * DAY;
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * DAY;
+
+/// 池子被禁用后，超过宽限期（slot 数，约 400ms/slot）自动恢复提取，
+/// 即使 authority 丢失私钥也不会永久冻结 LP 资金
+pub const DEFAULT_WITHDRAW_GRACE_SLOTS: u64 = 7 * 216_000;
+pub const MAX_WITHDRAW_GRACE_SLOTS: u64 = 30 * 216_000;
+
+/// 手续费返还档位数；每档为 (LP 持有量门槛 u64, 返还比例 u16 基点)，共 10 字节
+pub const MAX_REBATE_TIERS: usize = 3;
+const REBATE_TIER_LEN: usize = 10;
+
+/// 原生 AMM 池子的配置账户。全部字段都是字节数组，对齐为 1，
+/// 通过 blueshift_zerocopy 直接借用账户数据，布局由文件末尾的 `layout!` 核对
 #[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[bytemuck(crate = "blueshift_zerocopy::bytemuck")]
 pub struct Config {
     state: u8,
     seed: [u8; 8],
@@ -16,7 +40,69 @@ pub struct Config {
     mint_x: Pubkey,
     mint_y: Pubkey,
     fee: [u8; 2],
+    weight_x: [u8; 2],
     config_bump: [u8; 1],
+    canonical: u8,
+    timelock_delay: [u8; 8],
+    pending_kind: u8,
+    pending_eta: [u8; 8],
+    pending_value: [u8; 32],
+    rebate_tiers: [[u8; REBATE_TIER_LEN]; MAX_REBATE_TIERS],
+    reserve_x: [u8; 8],
+    reserve_y: [u8; 8],
+    lp_decimals: u8,
+    disabled_slot: [u8; 8],
+    withdraw_grace_slots: [u8; 8],
+}
+
+/// Config PDA 的签名种子。
+/// 规范池：[CONFIG_SEED, mint_x, mint_y, bump]；非规范（seeded）池：[CONFIG_SEED, seed, mint_x, mint_y, bump]。
+pub struct ConfigSeeds<'a> {
+    seeds: [Seed<'a>; 5],
+    len: usize,
+}
+
+impl ConfigSeeds<'_> {
+    #[inline(always)]
+    pub fn signer(&self) -> Signer<'_, '_> {
+        Signer::from(&self.seeds[..self.len])
+    }
+}
+
+/// 档位编码为 threshold u64 + rebate u16；不足 10 字节时视为未启用
+#[inline(always)]
+fn decode_rebate_tier(bytes: &[u8]) -> (u64, u16) {
+    match bytes.split_first_chunk::<8>() {
+        Some((threshold, [lo, hi, ..])) => (
+            u64::from_le_bytes(*threshold),
+            u16::from_le_bytes([*lo, *hi]),
+        ),
+        _ => (0, 0),
+    }
+}
+
+/// 参数变更值的前 8 字节（定长数组解构，不会越界）
+#[inline(always)]
+fn value_word(value: &[u8; 32]) -> [u8; 8] {
+    let [a, b, c, d, e, f, g, h, ..] = *value;
+    [a, b, c, d, e, f, g, h]
+}
+
+/// 启用的档位门槛必须严格递增，返还比例不超过 100%；门槛为 0 的档位视为未启用
+fn valid_rebate_tiers(tiers: &[u8]) -> bool {
+    if tiers.len() != MAX_REBATE_TIERS * REBATE_TIER_LEN {
+        return false;
+    }
+    let mut last_threshold = 0;
+    tiers.chunks_exact(REBATE_TIER_LEN).all(|bytes| {
+        let (threshold, rebate) = decode_rebate_tier(bytes);
+        if threshold == 0 {
+            return rebate == 0;
+        }
+        let increasing = threshold > last_threshold;
+        last_threshold = threshold;
+        increasing && rebate <= 10_000
+    })
 }
 
 #[repr(u8)]
@@ -27,6 +113,41 @@ pub enum AmmState {
     WithdrawOnly = 3u8,
 }
 
+/// 需要经过 timelock 的参数变更类型，提案值统一编码在 32 字节中
+#[repr(u8)]
+pub enum ConfigUpdate {
+    None = 0u8,
+    /// u16 小端
+    Fee = 1u8,
+    /// u8
+    State = 2u8,
+    /// Pubkey
+    Authority = 3u8,
+    /// i64 小端（秒）
+    TimelockDelay = 4u8,
+    /// MAX_REBATE_TIERS 个 (u64 门槛, u16 返还基点)，小端
+    RebateTiers = 5u8,
+    /// u64 小端（slot）
+    WithdrawGraceSlots = 6u8,
+}
+
+impl TryFrom<u8> for ConfigUpdate {
+    type Error = ProgramError;
+
+    fn try_from(kind: u8) -> Result<Self, Self::Error> {
+        match kind {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Fee),
+            2 => Ok(Self::State),
+            3 => Ok(Self::Authority),
+            4 => Ok(Self::TimelockDelay),
+            5 => Ok(Self::RebateTiers),
+            6 => Ok(Self::WithdrawGraceSlots),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
 impl Config {
     pub const LEN: usize = size_of::<Config>();
 
@@ -38,14 +159,16 @@ impl Config {
         if account_info.owner().ne(&crate::ID) {
             return Err(ProgramError::InvalidAccountOwner);
         }
-        Ok(Ref::map(account_info.try_borrow_data()?, |data| unsafe {
-            Self::from_bytes_unchecked(data)
-        }))
+        // 长度已检查、对齐为 1，bytemuck::from_bytes 不会失败
+        Ok(Ref::map(
+            account_info.try_borrow_data()?,
+            blueshift_zerocopy::bytemuck::from_bytes,
+        ))
     }
 
     #[inline(always)]
     /// # Safety
-    /// This function is unsafe because it dereferences a raw pointer without checking nullability.
+    /// 不经借用检查直接读取账户数据，调用方必须保证此时没有对同一账户的可变借用。
     pub unsafe fn load_unchecked(account_info: &AccountInfo) -> Result<&Self, ProgramError> {
         if account_info.data_len() != Self::LEN {
             return Err(ProgramError::InvalidAccountData);
@@ -53,30 +176,19 @@ impl Config {
         if account_info.owner() != &crate::ID {
             return Err(ProgramError::InvalidAccountOwner);
         }
-        Ok(unsafe { Self::from_bytes_unchecked(account_info.borrow_data_unchecked()) })
+        Self::from_bytes(unsafe { account_info.borrow_data_unchecked() })
     }
 
-    /// Return a `Config` from the given bytes.
-    ///
-    /// # Safety
-    ///
-    /// The caller must ensure that `bytes` contains a valid representation of `Config`, and
-    /// it is properly aligned to be interpreted as an instance of `Config`.
-    /// At the moment `Config` has an alignment of 1 byte.
-    /// This method does not perform a length validation.
+    /// 把账户数据借用为 `Config`，长度不是 `Config::LEN` 时返回 `InvalidAccountData`
     #[inline(always)]
-    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
-        unsafe { &*(bytes.as_ptr() as *const Config) }
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        blueshift_zerocopy::load(bytes).map_err(|_| ProgramError::InvalidAccountData)
     }
 
-    /// Return a mutable `Config` reference from the given bytes.
-    ///
-    /// # Safety
-    ///
-    /// The caller must ensure that `bytes` contains a valid representation of `Config`.
+    /// [`Config::from_bytes`] 的可变版本
     #[inline(always)]
-    pub unsafe fn from_bytes_unchecked_mut(bytes: &mut [u8]) -> &mut Self {
-        unsafe { &mut *(bytes.as_mut_ptr() as *mut Config) }
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        blueshift_zerocopy::load_mut(bytes).map_err(|_| ProgramError::InvalidAccountData)
     }
     // Getter methods for safe field access
     #[inline(always)]
@@ -103,10 +215,139 @@ impl Config {
     pub fn fee(&self) -> u16 {
         u16::from_le_bytes(self.fee)
     }
+    /// mint_x 的权重（基点），mint_y 的权重为 `WEIGHT_ONE - weight_x`
+    #[inline(always)]
+    pub fn weight_x(&self) -> u16 {
+        u16::from_le_bytes(self.weight_x)
+    }
+    #[inline(always)]
+    pub fn weight_y(&self) -> u16 {
+        WEIGHT_ONE - self.weight_x()
+    }
+    /// 50/50 池退化为普通恒定乘积池
+    #[inline(always)]
+    pub fn is_weighted(&self) -> bool {
+        self.weight_x() != self.weight_y()
+    }
     #[inline(always)]
     pub fn config_bump(&self) -> [u8; 1] {
         self.config_bump
     }
+    /// 规范池：每个（排序后的）mint 对只有一个，PDA 不含 seed
+    #[inline(always)]
+    pub fn is_canonical(&self) -> bool {
+        self.canonical == 1
+    }
+
+    /// 参数变更从提案到可执行之间的最短间隔（秒）
+    #[inline(always)]
+    pub fn timelock_delay(&self) -> i64 {
+        i64::from_le_bytes(self.timelock_delay)
+    }
+    /// 待执行的提案：(类型, 最早执行时间, 编码后的值)
+    #[inline(always)]
+    pub fn pending_update(&self) -> Option<(u8, i64, &[u8; 32])> {
+        if self.pending_kind == ConfigUpdate::None as u8 {
+            return None;
+        }
+        Some((
+            self.pending_kind,
+            i64::from_le_bytes(self.pending_eta),
+            &self.pending_value,
+        ))
+    }
+
+    /// 记账储备：只随本程序的指令变化，直接转入金库的代币不影响价格
+    #[inline(always)]
+    pub fn reserve_x(&self) -> u64 {
+        u64::from_le_bytes(self.reserve_x)
+    }
+    #[inline(always)]
+    pub fn reserve_y(&self) -> u64 {
+        u64::from_le_bytes(self.reserve_y)
+    }
+    #[inline(always)]
+    pub fn lp_decimals(&self) -> u8 {
+        self.lp_decimals
+    }
+    /// 池子被禁用时的 slot，未禁用时为 0
+    #[inline(always)]
+    pub fn disabled_slot(&self) -> u64 {
+        u64::from_le_bytes(self.disabled_slot)
+    }
+    #[inline(always)]
+    pub fn withdraw_grace_slots(&self) -> u64 {
+        u64::from_le_bytes(self.withdraw_grace_slots)
+    }
+    /// 当前 slot 下生效的状态：禁用超过宽限期后视为 WithdrawOnly
+    #[inline(always)]
+    pub fn effective_state(&self, slot: u64) -> u8 {
+        if self.state == AmmState::Disabled as u8
+            && slot >= self.disabled_slot().saturating_add(self.withdraw_grace_slots())
+        {
+            return AmmState::WithdrawOnly as u8;
+        }
+        self.state
+    }
+    /// 第 `index` 档返还：(LP 门槛, 返还比例基点)，门槛为 0 表示未启用
+    #[inline(always)]
+    pub fn rebate_tier(&self, index: usize) -> (u64, u16) {
+        decode_rebate_tier(&self.rebate_tiers[index])
+    }
+    /// 持有 `lp_balance` 个 LP 的用户实际支付的手续费：取满足门槛的最高档返还
+    pub fn effective_fee(&self, lp_balance: u64) -> u16 {
+        let rebate = (0..MAX_REBATE_TIERS)
+            .map(|index| self.rebate_tier(index))
+            .filter(|(threshold, _)| *threshold != 0 && lp_balance >= *threshold)
+            .map(|(_, rebate)| rebate)
+            .max()
+            .unwrap_or(0);
+        (self.fee() as u32 * (10_000 - rebate as u32) / 10_000) as u16
+    }
+
+    #[inline(always)]
+    pub fn signer_seeds(&self) -> ConfigSeeds<'_> {
+        if self.is_canonical() {
+            ConfigSeeds {
+                seeds: [
+                    Seed::from(CONFIG_SEED),
+                    Seed::from(&self.mint_x),
+                    Seed::from(&self.mint_y),
+                    Seed::from(&self.config_bump),
+                    Seed::from(CONFIG_SEED), // 占位，不参与签名
+                ],
+                len: 4,
+            }
+        } else {
+            ConfigSeeds {
+                seeds: [
+                    Seed::from(CONFIG_SEED),
+                    Seed::from(&self.seed),
+                    Seed::from(&self.mint_x),
+                    Seed::from(&self.mint_y),
+                    Seed::from(&self.config_bump),
+                ],
+                len: 5,
+            }
+        }
+    }
+
+    /// 按字节序排序一对 mint，规范池总是以 (较小, 较大) 存储
+    #[inline(always)]
+    pub fn sorted_mints<'a>(mint_a: &'a Pubkey, mint_b: &'a Pubkey) -> (&'a Pubkey, &'a Pubkey) {
+        if mint_a <= mint_b {
+            (mint_a, mint_b)
+        } else {
+            (mint_b, mint_a)
+        }
+    }
+
+    /// 任意顺序的 mint 对对应的规范池地址与 bump
+    #[inline(always)]
+    pub fn find_canonical_address(mint_a: &Pubkey, mint_b: &Pubkey) -> (Pubkey, u8) {
+        let (mint_x, mint_y) = Self::sorted_mints(mint_a, mint_b);
+        pdas::amm_canonical_config_pda(&crate::ID, mint_x, mint_y)
+    }
 
     #[inline(always)]
     pub fn load_mut<'a>(account_info: &'a AccountInfo) -> Result<RefMut<'a, Self>, ProgramError> {
@@ -116,15 +357,16 @@ impl Config {
         if account_info.owner().ne(&crate::ID) {
             return Err(ProgramError::InvalidAccountOwner);
         }
+        // 长度已检查、对齐为 1，bytemuck::from_bytes_mut 不会失败
         Ok(RefMut::map(
             account_info.try_borrow_mut_data()?,
-            |data| unsafe { Self::from_bytes_unchecked_mut(data) },
+            blueshift_zerocopy::bytemuck::from_bytes_mut,
         ))
     }
 
     #[inline(always)]
     pub fn set_state(&mut self, state: u8) -> Result<(), ProgramError> {
-        if state.ge(&(AmmState::WithdrawOnly as u8)) {
+        if state.gt(&(AmmState::WithdrawOnly as u8)) {
             return Err(ProgramError::InvalidAccountData);
         }
         self.state = state;
@@ -160,11 +402,166 @@ impl Config {
         Ok(())
     }
 
+    #[inline(always)]
+    pub fn set_weight_x(&mut self, weight_x: u16) -> Result<(), ProgramError> {
+        if weight_x.lt(&MIN_WEIGHT) || weight_x.gt(&(WEIGHT_ONE - MIN_WEIGHT)) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        self.weight_x = weight_x.to_le_bytes();
+        Ok(())
+    }
+
     #[inline(always)]
     pub fn set_config_bump(&mut self, config_bump: [u8; 1]) {
         self.config_bump = config_bump;
     }
 
+    #[inline(always)]
+    pub fn set_canonical(&mut self, canonical: bool) {
+        self.canonical = canonical as u8;
+    }
+
+    #[inline(always)]
+    pub fn set_timelock_delay(&mut self, delay: i64) -> Result<(), ProgramError> {
+        if !(0..=MAX_TIMELOCK_DELAY).contains(&delay) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        self.timelock_delay = delay.to_le_bytes();
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_withdraw_grace_slots(&mut self, grace_slots: u64) -> Result<(), ProgramError> {
+        if grace_slots > MAX_WITHDRAW_GRACE_SLOTS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        self.withdraw_grace_slots = grace_slots.to_le_bytes();
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_lp_decimals(&mut self, lp_decimals: u8) {
+        self.lp_decimals = lp_decimals;
+    }
+
+    #[inline(always)]
+    pub fn set_reserves(&mut self, reserve_x: u64, reserve_y: u64) {
+        self.reserve_x = reserve_x.to_le_bytes();
+        self.reserve_y = reserve_y.to_le_bytes();
+    }
+
+    /// swap 后的记账储备：输入侧增加 `deposit`，输出侧减少 `withdraw`
+    #[inline(always)]
+    pub fn apply_swap(
+        &mut self,
+        is_x: bool,
+        deposit: u64,
+        withdraw: u64,
+    ) -> Result<(), ProgramError> {
+        let (reserve_in, reserve_out) = if is_x {
+            (self.reserve_x(), self.reserve_y())
+        } else {
+            (self.reserve_y(), self.reserve_x())
+        };
+        let reserve_in = reserve_in.checked_add(deposit).ok_or(ProgramError::ArithmeticOverflow)?;
+        let reserve_out =
+            reserve_out.checked_sub(withdraw).ok_or(ProgramError::InsufficientFunds)?;
+        if is_x {
+            self.set_reserves(reserve_in, reserve_out);
+        } else {
+            self.set_reserves(reserve_out, reserve_in);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_rebate_tiers(&mut self, tiers: &[u8]) -> Result<(), ProgramError> {
+        if !valid_rebate_tiers(tiers) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        for (tier, bytes) in self.rebate_tiers.iter_mut().zip(tiers.chunks_exact(REBATE_TIER_LEN)) {
+            tier.copy_from_slice(bytes);
+        }
+        Ok(())
+    }
+
+    /// 记录参数变更提案（覆盖旧提案），`ConfigUpdate::None` 表示撤销
+    pub fn propose_update(
+        &mut self,
+        kind: u8,
+        value: [u8; 32],
+        now: i64,
+    ) -> Result<(), ProgramError> {
+        // 提案时即校验取值，避免等待延迟后才发现无法执行
+        let valid = match ConfigUpdate::try_from(kind)? {
+            ConfigUpdate::None | ConfigUpdate::Authority => true,
+            ConfigUpdate::Fee => u16::from_le_bytes([value[0], value[1]]) < 10_000,
+            ConfigUpdate::State => {
+                (AmmState::Initialized as u8..=AmmState::WithdrawOnly as u8).contains(&value[0])
+            }
+            ConfigUpdate::TimelockDelay => {
+                (0..=MAX_TIMELOCK_DELAY).contains(&i64::from_le_bytes(value_word(&value)))
+            }
+            ConfigUpdate::RebateTiers => {
+                valid_rebate_tiers(&value[..MAX_REBATE_TIERS * REBATE_TIER_LEN])
+            }
+            ConfigUpdate::WithdrawGraceSlots => {
+                u64::from_le_bytes(value_word(&value)) <= MAX_WITHDRAW_GRACE_SLOTS
+            }
+        };
+        if !valid {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let eta = if kind == ConfigUpdate::None as u8 {
+            0
+        } else {
+            now.checked_add(self.timelock_delay()).ok_or(ProgramError::ArithmeticOverflow)?
+        };
+        self.pending_kind = kind;
+        self.pending_eta = eta.to_le_bytes();
+        self.pending_value = value;
+        Ok(())
+    }
+
+    /// 延迟到期后应用提案并清空；禁用池子时记录当前 slot 作为宽限期起点
+    pub fn execute_update(&mut self, now: i64, slot: u64) -> Result<(), ProgramError> {
+        let (kind, eta, value) = match self.pending_update() {
+            Some((kind, eta, value)) => (kind, eta, *value),
+            None => return Err(ProgramError::InvalidAccountData),
+        };
+        if now < eta {
+            return Err(ProgramError::InvalidArgument);
+        }
+        match ConfigUpdate::try_from(kind)? {
+            ConfigUpdate::None => return Err(ProgramError::InvalidAccountData),
+            ConfigUpdate::Fee => self.set_fee(u16::from_le_bytes([value[0], value[1]]))?,
+            ConfigUpdate::State => {
+                self.set_state(value[0])?;
+                let disabled_slot = if value[0] == AmmState::Disabled as u8 {
+                    slot
+                } else {
+                    0
+                };
+                self.disabled_slot = disabled_slot.to_le_bytes();
+            }
+            ConfigUpdate::Authority => self.set_authority(value),
+            ConfigUpdate::TimelockDelay => {
+                self.set_timelock_delay(i64::from_le_bytes(value_word(&value)))?
+            }
+            ConfigUpdate::RebateTiers => {
+                self.set_rebate_tiers(&value[..MAX_REBATE_TIERS * REBATE_TIER_LEN])?
+            }
+            ConfigUpdate::WithdrawGraceSlots => {
+                self.set_withdraw_grace_slots(u64::from_le_bytes(value_word(&value)))?
+            }
+        }
+        self.pending_kind = ConfigUpdate::None as u8;
+        self.pending_eta = [0; 8];
+        self.pending_value = [0; 32];
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     #[inline(always)]
     pub fn set_inner(
         &mut self,
@@ -173,7 +570,9 @@ impl Config {
         mint_x: Pubkey,
         mint_y: Pubkey,
         fee: u16,
+        weight_x: u16,
         config_bump: [u8; 1],
+        canonical: bool,
     ) -> Result<(), ProgramError> {
         self.set_state(AmmState::Initialized as u8)?;
         self.set_seed(seed);
@@ -181,21 +580,109 @@ impl Config {
         self.set_mint_x(mint_x);
         self.set_mint_y(mint_y);
         self.set_fee(fee)?;
+        self.set_weight_x(weight_x)?;
         self.set_config_bump(config_bump);
+        self.set_canonical(canonical);
+        self.set_timelock_delay(DEFAULT_TIMELOCK_DELAY)?;
+        self.set_withdraw_grace_slots(DEFAULT_WITHDRAW_GRACE_SLOTS)?;
         Ok(())
     }
 
     #[inline(always)]
     pub fn has_authority(&self) -> Option<Pubkey> {
-        let bytes = self.authority();
-        let chunks: &[u64; 4] = unsafe { &*(bytes.as_ptr() as *const [u64; 4]) };
-        if chunks.iter().any(|&x| x != 0) {
+        if self.authority.iter().any(|&byte| byte != 0) {
             Some(self.authority)
         } else {
             None
         }
     }
 
+    /// 强制以可变引用加载账户数据，不检查所有者 (用于初始化)
+    /// # Safety
+    /// 调用者必须确保账户空间足够且已由程序控制
+    #[allow(clippy::mut_from_ref)]
+    #[inline(always)]
+    pub unsafe fn load_mut_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&mut Self, ProgramError> {
+        Self::from_bytes_mut(unsafe { account_info.borrow_mut_data_unchecked() })
+    }
+}
+
+layout!(Config = 223 {
+    STATE_OFFSET: state = 0,
+    SEED_OFFSET: seed = 1,
+    AUTHORITY_OFFSET: authority = 9,
+    MINT_X_OFFSET: mint_x = 41,
+    MINT_Y_OFFSET: mint_y = 73,
+    FEE_OFFSET: fee = 105,
+    WEIGHT_X_OFFSET: weight_x = 107,
+    CONFIG_BUMP_OFFSET: config_bump = 109,
+    CANONICAL_OFFSET: canonical = 110,
+    TIMELOCK_DELAY_OFFSET: timelock_delay = 111,
+    PENDING_KIND_OFFSET: pending_kind = 119,
+    PENDING_ETA_OFFSET: pending_eta = 120,
+    PENDING_VALUE_OFFSET: pending_value = 128,
+    REBATE_TIERS_OFFSET: rebate_tiers = 160,
+    RESERVE_X_OFFSET: reserve_x = 190,
+    RESERVE_Y_OFFSET: reserve_y = 198,
+    LP_DECIMALS_OFFSET: lp_decimals = 206,
+    DISABLED_SLOT_OFFSET: disabled_slot = 207,
+    WITHDRAW_GRACE_SLOTS_OFFSET: withdraw_grace_slots = 215,
+});
+
+/// 多资产池最多支持的代币数量
+pub const MAX_POOL_TOKENS: usize = 8;
+
+/// 多资产（指数型）池的配置账户。
+///
+/// 与双币池的 `Config` 并列存在：`mints` 前 `token_count` 项有效，
+/// 第 i 个金库是 config PDA 持有的 `mints[i]` 代币账户。
+/// 不变量为等权重的几何平均 `∏ reserve_i = k`，任意两币之间的兑换
+/// 在其余储备不变时退化为恒定乘积公式。
+/// 与双币池一样按 `reserves` 中的记账储备定价，直接转入金库的代币不影响价格。
+#[repr(C)]
+pub struct MultiConfig {
+    state: u8,
+    seed: [u8; 8],
+    authority: Pubkey,
+    token_count: u8,
+    fee: [u8; 2],
+    config_bump: [u8; 1],
+    mints: [Pubkey; MAX_POOL_TOKENS],
+    reserves: [[u8; 8]; MAX_POOL_TOKENS],
+}
+
+impl MultiConfig {
+    pub const LEN: usize = size_of::<MultiConfig>();
+
+    #[inline(always)]
+    pub fn load<'a>(account_info: &'a AccountInfo) -> Result<Ref<'a, Self>, ProgramError> {
+        if account_info.data_len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if account_info.owner().ne(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Ref::map(account_info.try_borrow_data()?, |data| unsafe {
+            Self::from_bytes_unchecked(data)
+        }))
+    }
+
+    #[inline(always)]
+    pub fn load_mut<'a>(account_info: &'a AccountInfo) -> Result<RefMut<'a, Self>, ProgramError> {
+        if account_info.data_len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if account_info.owner().ne(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(RefMut::map(
+            account_info.try_borrow_mut_data()?,
+            |data| unsafe { Self::from_bytes_unchecked_mut(data) },
+        ))
+    }
+
     /// 强制以可变引用加载账户数据，不检查所有者 (用于初始化)
     /// # Safety
     /// 调用者必须确保账户空间足够且已由程序控制
@@ -207,7 +694,306 @@ impl Config {
         if account_info.data_len() != Self::LEN {
             return Err(ProgramError::InvalidAccountData);
         }
-        // 直接获取账户数据的原始指针并转换为可变结构体引用
         Ok(unsafe { Self::from_bytes_unchecked_mut(account_info.borrow_mut_data_unchecked()) })
     }
+
+    /// Return a `MultiConfig` from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` contains a valid representation of `MultiConfig`.
+    /// `MultiConfig` has an alignment of 1 byte.
+    #[inline(always)]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        unsafe { &*(bytes.as_ptr() as *const MultiConfig) }
+    }
+
+    /// Return a mutable `MultiConfig` reference from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` contains a valid representation of `MultiConfig`.
+    #[inline(always)]
+    pub unsafe fn from_bytes_unchecked_mut(bytes: &mut [u8]) -> &mut Self {
+        unsafe { &mut *(bytes.as_mut_ptr() as *mut MultiConfig) }
+    }
+
+    #[inline(always)]
+    pub fn state(&self) -> u8 {
+        self.state
+    }
+    #[inline(always)]
+    pub fn seed(&self) -> u64 {
+        u64::from_le_bytes(self.seed)
+    }
+    #[inline(always)]
+    pub fn authority(&self) -> &Pubkey {
+        &self.authority
+    }
+    #[inline(always)]
+    pub fn token_count(&self) -> usize {
+        self.token_count as usize
+    }
+    #[inline(always)]
+    pub fn fee(&self) -> u16 {
+        u16::from_le_bytes(self.fee)
+    }
+    #[inline(always)]
+    pub fn config_bump(&self) -> [u8; 1] {
+        self.config_bump
+    }
+    /// 只返回有效的 mint 列表
+    #[inline(always)]
+    pub fn mints(&self) -> &[Pubkey] {
+        &self.mints[..self.token_count()]
+    }
+    /// 第 `index` 个代币的记账储备，只随本程序的指令变化
+    #[inline(always)]
+    pub fn reserve(&self, index: usize) -> u64 {
+        u64::from_le_bytes(self.reserves[index])
+    }
+
+    #[inline(always)]
+    pub fn set_reserve(&mut self, index: usize, reserve: u64) {
+        self.reserves[index] = reserve.to_le_bytes();
+    }
+
+    /// swap 后的记账储备：`index_in` 增加 `deposit`，`index_out` 减少 `withdraw`
+    #[inline(always)]
+    pub fn apply_swap(
+        &mut self,
+        index_in: usize,
+        index_out: usize,
+        deposit: u64,
+        withdraw: u64,
+    ) -> Result<(), ProgramError> {
+        let reserve_in = self
+            .reserve(index_in)
+            .checked_add(deposit)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let reserve_out = self
+            .reserve(index_out)
+            .checked_sub(withdraw)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        self.set_reserve(index_in, reserve_in);
+        self.set_reserve(index_out, reserve_out);
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        seed: u64,
+        authority: Pubkey,
+        mints: &[Pubkey],
+        fee: u16,
+        config_bump: [u8; 1],
+    ) -> Result<(), ProgramError> {
+        if mints.len() < 2 || mints.len() > MAX_POOL_TOKENS {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if fee.ge(&10_000) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        self.state = AmmState::Initialized as u8;
+        self.seed = seed.to_le_bytes();
+        self.authority = authority;
+        self.token_count = mints.len() as u8;
+        self.fee = fee.to_le_bytes();
+        self.config_bump = config_bump;
+        self.mints = [[0u8; 32]; MAX_POOL_TOKENS];
+        self.mints[..mints.len()].copy_from_slice(mints);
+        self.reserves = [[0u8; 8]; MAX_POOL_TOKENS];
+        Ok(())
+    }
+}
+
+/// 锁仓时长（秒）与对应的奖励加成（基点），按时长从短到长排列
+pub const LOCK_BOOST_TIERS: [(i64, u16); 4] =
+    [(7 * DAY, 200), (30 * DAY, 1_000), (90 * DAY, 2_500), (365 * DAY, 5_000)];
+pub const MAX_LOCK_DURATION: i64 = 365 * DAY;
+
+/// 用户在某个池子里的锁仓 LP。PDA: [LP_LOCK_SEED, config, owner]。
+///
+/// 锁仓期间 LP 存放在 config PDA 持有的 lock vault 中，到期后通过 withdraw 按普通 LP 份额赎回。
+/// 锁仓存款时按 `boost_bps` 从奖励池（[LP_REWARDS_SEED, config] 持有的 LP）划出奖励 LP
+/// 一并锁入并计入 `amount`，奖励池不足时只发放剩余部分；加成来自奖励池而不是其他 LP 的本金。
+#[repr(C)]
+pub struct LpLock {
+    owner: Pubkey,
+    config: Pubkey,
+    amount: [u8; 8],
+    unlock_ts: [u8; 8],
+    boost_bps: [u8; 2],
+    bump: [u8; 1],
+}
+
+impl LpLock {
+    pub const LEN: usize = size_of::<LpLock>();
+
+    #[inline(always)]
+    pub fn load_mut<'a>(account_info: &'a AccountInfo) -> Result<RefMut<'a, Self>, ProgramError> {
+        if account_info.data_len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if account_info.owner().ne(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(RefMut::map(
+            account_info.try_borrow_mut_data()?,
+            |data| unsafe { &mut *(data.as_mut_ptr() as *mut LpLock) },
+        ))
+    }
+
+    #[inline(always)]
+    pub fn owner(&self) -> &Pubkey {
+        &self.owner
+    }
+    #[inline(always)]
+    pub fn config(&self) -> &Pubkey {
+        &self.config
+    }
+    #[inline(always)]
+    pub fn amount(&self) -> u64 {
+        u64::from_le_bytes(self.amount)
+    }
+    #[inline(always)]
+    pub fn unlock_ts(&self) -> i64 {
+        i64::from_le_bytes(self.unlock_ts)
+    }
+    #[inline(always)]
+    pub fn boost_bps(&self) -> u16 {
+        u16::from_le_bytes(self.boost_bps)
+    }
+    #[inline(always)]
+    pub fn bump(&self) -> [u8; 1] {
+        self.bump
+    }
+
+    /// 锁仓时长对应的加成，短于最短档位不允许锁仓
+    pub fn boost_for(duration: i64) -> Result<u16, ProgramError> {
+        if duration > MAX_LOCK_DURATION {
+            return Err(ProgramError::InvalidArgument);
+        }
+        LOCK_BOOST_TIERS
+            .iter()
+            .rev()
+            .find(|(min_duration, _)| duration >= *min_duration)
+            .map(|(_, boost)| *boost)
+            .ok_or(ProgramError::InvalidArgument)
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, owner: Pubkey, config: Pubkey, bump: [u8; 1]) {
+        self.owner = owner;
+        self.config = config;
+        self.bump = bump;
+    }
+
+    /// 追加锁仓：解锁时间取较晚者，加成按剩余锁仓时长重新计算
+    pub fn lock(&mut self, amount: u64, now: i64, duration: i64) -> Result<(), ProgramError> {
+        let unlock_ts = self.unlock_ts().max(now + duration);
+        self.boost_bps = Self::boost_for(unlock_ts - now)?.to_le_bytes();
+        self.unlock_ts = unlock_ts.to_le_bytes();
+        self.amount = self
+            .amount()
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .to_le_bytes();
+        Ok(())
+    }
+
+    /// 记入从奖励池划入的奖励 LP，与本金一同锁定到 `unlock_ts`
+    pub fn credit(&mut self, bonus: u64) -> Result<(), ProgramError> {
+        self.amount = self
+            .amount()
+            .checked_add(bonus)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .to_le_bytes();
+        Ok(())
+    }
+
+    /// 到期后赎回部分或全部锁仓 LP
+    pub fn unlock(&mut self, amount: u64, now: i64) -> Result<(), ProgramError> {
+        if now < self.unlock_ts() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.amount = self
+            .amount()
+            .checked_sub(amount)
+            .ok_or(ProgramError::InsufficientFunds)?
+            .to_le_bytes();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 快照中的取值见 snapshots/config.layout 的注释
+    #[test]
+    fn config_layout_matches_snapshot() -> Result<(), ProgramError> {
+        let mut data = [0u8; Config::LEN];
+        let config = Config::from_bytes_mut(&mut data)?;
+        config.set_inner(
+            0x0807_0605_0403_0201,
+            [0xaa; 32],
+            [0x11; 32],
+            [0x22; 32],
+            30,
+            6_000,
+            [0xfd],
+            true,
+        )?;
+
+        let mut fee = [0u8; 32];
+        fee[..2].copy_from_slice(&25u16.to_le_bytes());
+        config.propose_update(ConfigUpdate::Fee as u8, fee, 1_000)?;
+
+        let mut tiers = [0u8; MAX_REBATE_TIERS * REBATE_TIER_LEN];
+        for (tier, (threshold, rebate)) in tiers
+            .chunks_exact_mut(REBATE_TIER_LEN)
+            .zip([(1_000u64, 500u16), (1_000_000, 1_000)])
+        {
+            tier[..8].copy_from_slice(&threshold.to_le_bytes());
+            tier[8..].copy_from_slice(&rebate.to_le_bytes());
+        }
+        config.set_rebate_tiers(&tiers)?;
+
+        config.set_reserves(1_000_000_000, 4_000_000_000);
+        config.set_lp_decimals(DEFAULT_LP_DECIMALS);
+        config.disabled_slot = 123_456u64.to_le_bytes();
+
+        layout_snapshot::assert_layout(&data, include_str!("../snapshots/config.layout"));
+        Ok(())
+    }
+
+    #[test]
+    fn multi_config_swap_moves_tracked_reserves() -> Result<(), ProgramError> {
+        let mut data = [0u8; MultiConfig::LEN];
+        let config = unsafe { MultiConfig::from_bytes_unchecked_mut(&mut data) };
+        config.set_inner(
+            1,
+            [0xaa; 32],
+            &[[0x11; 32], [0x22; 32], [0x33; 32]],
+            30,
+            [0xfd],
+        )?;
+        config.set_reserve(0, 1_000);
+        config.set_reserve(2, 500);
+
+        config.apply_swap(0, 2, 100, 45)?;
+        assert_eq!(
+            (config.reserve(0), config.reserve(1), config.reserve(2)),
+            (1_100, 0, 455)
+        );
+        // 输出侧不能低于 0，其余储备保持不变
+        assert_eq!(
+            config.apply_swap(1, 2, 1, 456),
+            Err(ProgramError::InsufficientFunds)
+        );
+        assert_eq!(config.reserve(1), 0);
+        Ok(())
+    }
 }
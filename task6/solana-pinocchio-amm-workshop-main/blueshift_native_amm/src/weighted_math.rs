@@ -0,0 +1,110 @@
+//! 加权恒定均值（Balancer 风格）曲线的定点数学。
+//!
+//! 不变量为 `B_x^w_x * B_y^w_y = k`。按比例存入/取出时两边余额同比例变化，
+//! 不变量随 LP 供应量线性缩放，因此 deposit/withdraw 仍使用等比例公式；
+//! 只有 swap 需要下面的幂运算：
+//!
+//! `out = B_out * (1 - (B_in / (B_in + A_in)) ^ (w_in / w_out))`
+//!
+//! 所有数值以 `WAD = 1e18` 定点表示，舍入方向始终对池子有利。
+
+use pinocchio::program_error::ProgramError;
+
+/// 定点精度 1e18
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+/// ln(2) * WAD
+const LN_2: u128 = 693_147_180_559_945_309;
+/// 幂运算的误差上界（以 WAD 计），结果向上补偿以保证输出向下取整
+const POW_ERROR_MARGIN: u128 = 10_000;
+
+/// 权重以基点表示，x 与 y 的权重之和为 10_000
+pub const WEIGHT_ONE: u16 = 10_000;
+/// 单边最小权重 1%，避免指数过大导致精度损失
+pub const MIN_WEIGHT: u16 = 100;
+
+/// `ln(x / WAD) * WAD`，要求 `x >= WAD`。
+fn ln_wad(x: u128) -> u128 {
+    // 1. 提取 2 的整数次幂：x = m * 2^k，m ∈ [WAD, 2 * WAD)
+    let mut k = 0u128;
+    let mut m = x;
+    while m >= 2 * WAD {
+        m /= 2;
+        k += 1;
+    }
+
+    // 2. ln(m) = 2 * atanh(z)，z = (m - 1) / (m + 1) ∈ [0, 1/3)
+    let z = (m - WAD) * WAD / (m + WAD);
+    let z2 = z * z / WAD;
+    let mut term = z;
+    let mut sum = 0u128;
+    let mut n = 1u128;
+    while term > 0 {
+        sum += term / n;
+        term = term * z2 / WAD;
+        n += 2;
+    }
+
+    k * LN_2 + 2 * sum
+}
+
+/// `exp(-t / WAD) * WAD`，t 为非负定点数。
+fn exp_neg_wad(t: u128) -> u128 {
+    // 1. t = k * ln2 + r，r ∈ [0, ln2)
+    let k = t / LN_2;
+    if k >= 64 {
+        return 0;
+    }
+    let r = t % LN_2;
+
+    // 2. exp(r) 泰勒展开，再取倒数
+    let mut term = WAD;
+    let mut sum = WAD;
+    let mut n = 1u128;
+    while term > 0 {
+        term = term * r / WAD / n;
+        sum += term;
+        n += 1;
+    }
+
+    (WAD * WAD / sum) >> k
+}
+
+/// `(base / WAD) ^ (num / den) * WAD`，要求 `0 < base <= WAD`。
+/// 结果向上补偿 `POW_ERROR_MARGIN`，调用方据此计算的输出只会偏小。
+pub fn pow_wad_up(base: u128, num: u128, den: u128) -> Result<u128, ProgramError> {
+    if base == 0 || base > WAD || den == 0 {
+        return Err(ProgramError::ArithmeticOverflow);
+    }
+    if base == WAD {
+        return Ok(WAD);
+    }
+    // ln(1 / base) 为正，避免有符号运算
+    let ln_inv = ln_wad(WAD * WAD / base);
+    let t = ln_inv.checked_mul(num).ok_or(ProgramError::ArithmeticOverflow)? / den;
+    Ok((exp_neg_wad(t) + POW_ERROR_MARGIN).min(WAD))
+}
+
+/// 加权池的 swap 输出（手续费以基点从输入中扣除）。
+pub fn weighted_swap_out(
+    balance_in: u64,
+    weight_in: u16,
+    balance_out: u64,
+    weight_out: u16,
+    amount_in: u64,
+    fee: u16,
+) -> Result<u64, ProgramError> {
+    if balance_in == 0 || balance_out == 0 || weight_in == 0 || weight_out == 0 {
+        return Err(ProgramError::ArithmeticOverflow);
+    }
+    let amount_in_after_fee =
+        amount_in as u128 * (WEIGHT_ONE as u128 - fee as u128) / WEIGHT_ONE as u128;
+
+    // ratio = B_in / (B_in + A_in)，向上取整使 ratio 偏大、输出偏小
+    let denominator = balance_in as u128 + amount_in_after_fee;
+    let ratio = (balance_in as u128 * WAD).div_ceil(denominator);
+
+    let power = pow_wad_up(ratio, weight_in as u128, weight_out as u128)?;
+    let out = balance_out as u128 * (WAD - power) / WAD;
+
+    u64::try_from(out).map_err(|_| ProgramError::ArithmeticOverflow)
+}
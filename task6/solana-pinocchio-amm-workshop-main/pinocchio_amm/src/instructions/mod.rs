@@ -1,9 +1,11 @@
 pub mod deposit;
 pub mod initialize;
 pub mod swap;
+pub mod version;
 pub mod withdraw;
 
 pub use deposit::*;
 pub use initialize::*;
 pub use swap::*;
+pub use version::*;
 pub use withdraw::*;
@@ -169,7 +169,7 @@ impl<'a> Withdraw<'a> {
             amount: x,
         }
         // .invoke_signed(&[signer.clone()])?;
-        .invoke_signed(std::slice::from_ref(&signer))?;
+        .invoke_signed(core::slice::from_ref(&signer))?;
 
         Transfer {
             from: accounts.vault_y,
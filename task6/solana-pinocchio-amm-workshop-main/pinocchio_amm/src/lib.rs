@@ -1,7 +1,22 @@
+#![no_std]
+
+use blueshift_version::{GET_VERSION_DISCRIMINATOR, SET_FEATURES_DISCRIMINATOR};
 use pinocchio::{
     AccountView, Address, ProgramResult, address::declare_id, entrypoint, error::ProgramError,
+    nostd_panic_handler,
 };
 entrypoint!(process_instruction);
+nostd_panic_handler!();
+
+// security.txt（security-txt feature）
+#[cfg(feature = "security-txt")]
+solana_security_txt::security_txt! {
+    name: "Pinocchio AMM",
+    project_url: "https://github.com/o7reconversion/solana_bootcamp_2026",
+    contacts: "link:https://github.com/o7reconversion/solana_bootcamp_2026/security/advisories/new",
+    policy: "https://github.com/o7reconversion/solana_bootcamp_2026/blob/main/SECURITY.md",
+    source_code: "https://github.com/o7reconversion/solana_bootcamp_2026/tree/main/task6/solana-pinocchio-amm-workshop-main/pinocchio_amm"
+}
 
 pub mod instructions;
 pub use instructions::*;
@@ -17,6 +32,13 @@ fn process_instruction(
     accounts: &[AccountView],
     instruction_data: &[u8],
 ) -> ProgramResult {
+    // 训练营程序共用的 8 字节判别器，先于单字节判别器匹配
+    if instruction_data.starts_with(&GET_VERSION_DISCRIMINATOR) {
+        return get_version(accounts);
+    }
+    if let Some(data) = instruction_data.strip_prefix(&SET_FEATURES_DISCRIMINATOR) {
+        return set_features(data, accounts);
+    }
     match instruction_data.split_first() {
         Some((Initialize::DISCRIMINATOR, data)) => {
             Initialize::try_from((data, accounts))?.process()
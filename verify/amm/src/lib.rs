@@ -0,0 +1,117 @@
+//! task6 原生 AMM 曲线数学的证明：按份额存取的舍入方向对池子有利、取款不超过储备、
+//! 先存后取拿不回更多代币，以及恒定乘积 swap 不溢出、不减小 k。运行方式见 verify/README.md。
+
+#![cfg(kani)]
+
+use blueshift_native_amm::curve::{
+    constant_product_out, proportional_amount, ConstantProduct, Curve,
+};
+
+/// u128 乘除的性质只在这个范围内证明，以控制求解时间
+const BOUND: u64 = 1 << 32;
+
+fn bounded() -> u64 {
+    let value: u64 = kani::any();
+    kani::assume(value <= BOUND);
+    value
+}
+
+/// 合法费率：Initialize / ExecuteUpdate 都要求 fee < 10_000
+fn fee() -> u16 {
+    let fee: u16 = kani::any();
+    kani::assume(fee < 10_000);
+    fee
+}
+
+/// 存款向上取整、取款向下取整，两者最多相差 1
+#[kani::proof]
+fn proportional_amount_rounds_toward_pool() {
+    let (balance, lp, supply) = (bounded(), bounded(), bounded());
+    kani::assume(supply > 0 && lp <= supply);
+    let exact = balance as u128 * lp as u128;
+
+    let floor = proportional_amount(balance, lp, supply, false).unwrap();
+    let ceil = proportional_amount(balance, lp, supply, true).unwrap();
+    assert!(floor as u128 * supply as u128 <= exact);
+    assert!(ceil as u128 * supply as u128 >= exact);
+    assert!(ceil - floor <= 1);
+    assert!(ceil <= balance);
+}
+
+/// 任意 lp ≤ supply 的取款都不超过储备，全额销毁时恰好取走全部储备
+#[kani::proof]
+fn withdraw_never_exceeds_reserves() {
+    let pool = ConstantProduct {
+        reserve_x: kani::any(),
+        reserve_y: kani::any(),
+        fee: fee(),
+    };
+    let supply: u64 = kani::any();
+    let lp: u64 = kani::any();
+    kani::assume(supply > 0 && lp <= supply);
+
+    let (x, y) = pool.withdraw_amounts(lp, supply).unwrap();
+    assert!(x <= pool.reserve_x && y <= pool.reserve_y);
+    if lp == supply {
+        assert_eq!((x, y), (pool.reserve_x, pool.reserve_y));
+    }
+}
+
+/// 存入 lp 份额后立刻取出同样的份额，两侧都拿不回比存入更多的代币
+#[kani::proof]
+fn withdraw_after_deposit_returns_at_most_deposit() {
+    let pool = ConstantProduct {
+        reserve_x: bounded(),
+        reserve_y: bounded(),
+        fee: fee(),
+    };
+    let (lp, supply) = (bounded(), bounded());
+    kani::assume(supply > 0 && lp > 0 && lp <= supply);
+
+    let (dx, dy) = pool.deposit_amounts(lp, supply).unwrap();
+    let after = ConstantProduct {
+        reserve_x: pool.reserve_x + dx,
+        reserve_y: pool.reserve_y + dy,
+        fee: pool.fee,
+    };
+    let (wx, wy) = after.withdraw_amounts(lp, supply + lp).unwrap();
+    assert!(wx <= dx && wy <= dy);
+}
+
+/// 任意储备与输入都不会 panic；成功时输出不超过输出侧储备，储备非空时取不空池子
+#[kani::proof]
+fn constant_product_out_never_panics() {
+    let balance_in: u64 = kani::any();
+    let balance_out: u64 = kani::any();
+    let amount_in: u64 = kani::any();
+
+    if let Ok(out) = constant_product_out(balance_in, balance_out, amount_in, fee()) {
+        assert!(out <= balance_out);
+        if balance_in > 0 && balance_out > 0 {
+            assert!(out < balance_out);
+        }
+    }
+}
+
+/// swap 后池子收到全部输入（含手续费）、付出 out，乘积 k 不减小
+#[kani::proof]
+fn constant_product_swap_keeps_k() {
+    let pool = ConstantProduct {
+        reserve_x: bounded(),
+        reserve_y: bounded(),
+        fee: fee(),
+    };
+    let amount_in = bounded();
+    let is_x: bool = kani::any();
+
+    let (balance_in, balance_out) = if is_x {
+        (pool.reserve_x, pool.reserve_y)
+    } else {
+        (pool.reserve_y, pool.reserve_x)
+    };
+    if let Ok((paid, out)) = pool.swap(is_x, amount_in, 0) {
+        let before = balance_in as u128 * balance_out as u128;
+        let after = (balance_in + paid) as u128 * (balance_out - out) as u128;
+        assert!(after >= before);
+    }
+}
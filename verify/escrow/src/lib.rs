@@ -0,0 +1,89 @@
+//! task5 托管的数量记账证明：Make 拒绝空存款，Take / Refund 转出的数量就是金库余额，
+//! 关闭托管账户时 lamports 全部转给 maker；TakePartial 按比例向上取整，既不少付也不超过 receive。
+//! 运行方式见 verify/README.md。
+
+#![cfg(kani)]
+
+use blueshift_common::read_u64_le;
+use blueshift_escrow::{merged_lamports, partial_receive, MakeInstructionData};
+
+/// SPL Token 账户长度，amount 位于偏移 64..72
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+fn word(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+/// MakeInstructionData：只接受 64 字节，amount 为 0 时拒绝，字段与输入字节一致
+#[kani::proof]
+fn make_data_requires_deposit() {
+    let data: [u8; 65] = kani::any();
+    let len: usize = kani::any();
+    kani::assume(len <= data.len());
+
+    match MakeInstructionData::try_from(&data[..len]) {
+        Ok(parsed) => {
+            assert_eq!(len, 64);
+            assert!(parsed.amount > 0);
+            assert_eq!(parsed.seed, word(&data, 0));
+            assert_eq!(parsed.receive, word(&data, 8));
+            assert_eq!(parsed.amount, word(&data, 16));
+            assert_eq!(parsed.expiry, word(&data, 24) as i64);
+            assert_eq!(parsed.taker.to_bytes()[..], data[32..64]);
+        }
+        Err(_) => assert!(len != 64 || word(&data, 16) == 0),
+    }
+}
+
+/// Take / Refund 转出的数量就是金库 token 账户的 amount 字段；数据过短时返回 None，不会越界
+#[kani::proof]
+fn vault_amount_is_token_balance() {
+    let data: [u8; TOKEN_ACCOUNT_LEN] = kani::any();
+    let len: usize = kani::any();
+    kani::assume(len <= TOKEN_ACCOUNT_LEN);
+
+    match read_u64_le(&data[..len], 64) {
+        Some(amount) => {
+            assert!(len >= 72);
+            assert_eq!(amount, word(&data, 64));
+        }
+        None => assert!(len < 72),
+    }
+}
+
+/// 关闭托管账户（Take / Refund 的最后一步）：目标账户收到全部 lamports，只有总和超过 u64 时才失败
+#[kani::proof]
+fn close_moves_every_lamport() {
+    let destination: u64 = kani::any();
+    let closed: u64 = kani::any();
+    let total = destination as u128 + closed as u128;
+
+    match merged_lamports(destination, closed) {
+        Ok(balance) => assert_eq!(balance as u128, total),
+        Err(_) => assert!(total > u64::MAX as u128),
+    }
+}
+
+/// TakePartial 应付的代币 B：不少于按比例应得的数量，多出的不到 1 个，且不超过剩余的 receive；
+/// 金库还有剩余时一定小于 receive，剩下的代币 A 不会变成 0 价格。
+/// 除此之外只有 amount 为 0 或超过金库余额时拒绝
+#[kani::proof]
+fn partial_receive_rounds_up_within_receive() {
+    let receive: u64 = kani::any();
+    let amount: u64 = kani::any();
+    let vault: u64 = kani::any();
+    let exact = receive as u128 * amount as u128;
+
+    match partial_receive(receive, amount, vault) {
+        Ok(owed) => {
+            assert!(amount > 0 && amount <= vault);
+            assert!(owed <= receive);
+            assert!(owed as u128 * vault as u128 >= exact);
+            assert!((owed as u128 * vault as u128) < exact + vault as u128);
+            assert!(amount == vault || owed < receive);
+        }
+        Err(_) => assert!(
+            amount == 0 || amount > vault || exact.div_ceil(vault as u128) >= receive as u128
+        ),
+    }
+}